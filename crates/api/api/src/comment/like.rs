@@ -5,6 +5,7 @@ use lemmy_api_utils::{
   context::LemmyContext,
   plugins::{plugin_hook_after, plugin_hook_before},
   send_activity::{ActivityChannel, SendActivityData},
+  stream::StreamChannel,
   utils::{
     check_bot_account,
     check_community_user_action,
@@ -29,6 +30,7 @@ use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
 use lemmy_utils::error::LemmyResult;
 use std::ops::Deref;
+use tracing::warn;
 
 pub async fn like_comment(
   Json(data): Json<CreateCommentLike>,
@@ -80,6 +82,10 @@ pub async fn like_comment(
   .await?;
 
   plugin_hook_after("comment_after_vote", &like);
+  StreamChannel::comment_vote(comment_id, &context)
+    .await
+    .inspect_err(|e| warn!("Failed to stream comment vote: {e}"))
+    .ok();
 
   // Mark any notification as read
   Notification::mark_read_by_comment_and_recipient(
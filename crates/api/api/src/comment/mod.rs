@@ -1,6 +1,7 @@
 pub mod distinguish;
 pub mod like;
 pub mod list_comment_likes;
+pub mod list_updates;
 pub mod lock;
 pub mod save;
 pub mod warning;
@@ -2,6 +2,7 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use diesel_async::scoped_futures::ScopedFutureExt;
 use lemmy_api_utils::{
+  cache::invalidate_community,
   context::LemmyContext,
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
@@ -100,5 +101,7 @@ pub async fn add_mod_to_community(
     &context,
   )?;
 
+  invalidate_community(data.community_id).await;
+
   Ok(Json(AddModToCommunityResponse { moderators }))
 }
@@ -1,6 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use diesel_async::scoped_futures::ScopedFutureExt;
+use itertools::Itertools;
 use lemmy_api_utils::{
   context::LemmyContext,
   notify::notify_mod_action,
@@ -16,16 +17,19 @@ use lemmy_db_schema::{
     community::{Community, CommunityActions, CommunityPersonBanForm},
     local_user::LocalUser,
     modlog::{Modlog, ModlogInsertForm},
+    person::Person,
   },
   traits::{Bannable, Followable},
 };
-use lemmy_db_views_community::api::BanFromCommunity;
+use lemmy_db_schema_file::PersonId;
+use lemmy_db_views_community::api::{BanFromCommunity, BanManyFromCommunity};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::{PersonView, api::PersonResponse};
+use lemmy_db_views_site::api::SuccessResponse;
 use lemmy_diesel_utils::{connection::get_conn, traits::Crud};
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
-  utils::validation::is_valid_body_field,
+  utils::validation::{check_api_elements_count, is_valid_body_field},
 };
 
 pub async fn ban_from_community(
@@ -129,3 +133,108 @@ pub async fn ban_from_community(
 
   Ok(Json(PersonResponse { person_view }))
 }
+
+pub async fn ban_many_from_community(
+  Json(data): Json<BanManyFromCommunity>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_api_elements_count(data.person_ids.len())?;
+  let my_person_id = local_user_view.person.id;
+  let expires_at = check_expire_time(data.expires_at)?;
+  let person_ids: Vec<PersonId> = data.person_ids.iter().copied().unique().collect();
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // Verify that only mods or admins can ban
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  LocalUser::is_higher_mod_or_admin_check(
+    &mut context.pool(),
+    data.community_id,
+    my_person_id,
+    person_ids.clone(),
+  )
+  .await?;
+
+  is_valid_body_field(&data.reason, false)?;
+  let targets = Person::read_many(&mut context.pool(), &person_ids).await?;
+
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let tx_data = data.clone();
+  let actions = conn
+    .run_transaction(|conn| {
+      async move {
+        let mut actions = Vec::with_capacity(person_ids.len());
+        for banned_person_id in person_ids {
+          let community_user_ban_form = CommunityPersonBanForm {
+            ban_expires_at: Some(expires_at),
+            ..CommunityPersonBanForm::new(tx_data.community_id, banned_person_id)
+          };
+          if tx_data.ban {
+            CommunityActions::ban(&mut conn.into(), &community_user_ban_form).await?;
+
+            // Also unsubscribe them from the community, if they are subscribed
+            CommunityActions::unfollow(&mut conn.into(), banned_person_id, tx_data.community_id)
+              .await
+              .ok();
+          } else {
+            CommunityActions::unban(&mut conn.into(), &community_user_ban_form).await?;
+          }
+
+          // Every ban gets its own modlog entry, so that bulk data removals can reference it
+          let form = ModlogInsertForm::mod_ban_from_community(
+            my_person_id,
+            tx_data.community_id,
+            banned_person_id,
+            tx_data.ban,
+            expires_at,
+            &tx_data.reason,
+          );
+          let action = Modlog::create(&mut conn.into(), &[form]).await?;
+
+          let ban_id = action.first().ok_or(LemmyErrorType::NotFound)?.id;
+          if tx_data.remove_or_restore_data.unwrap_or(false) {
+            remove_or_restore_user_data_in_community(
+              tx_data.community_id,
+              my_person_id,
+              banned_person_id,
+              tx_data.ban,
+              &tx_data.reason,
+              ban_id,
+              &mut conn.into(),
+            )
+            .await?;
+          };
+          actions.extend(action);
+        }
+
+        Ok(actions)
+      }
+      .scope_boxed()
+    })
+    .await?;
+  notify_mod_action(actions, &context);
+
+  for target in targets {
+    let person_id = target.id;
+    ActivityChannel::submit_activity(
+      SendActivityData::BanFromCommunity {
+        moderator: local_user_view.person.clone(),
+        community_id: data.community_id,
+        target,
+        data: BanFromCommunity {
+          community_id: data.community_id,
+          person_id,
+          ban: data.ban,
+          remove_or_restore_data: data.remove_or_restore_data,
+          reason: data.reason.clone(),
+          expires_at: data.expires_at,
+        },
+      },
+      &context,
+    )?;
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
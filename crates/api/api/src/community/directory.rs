@@ -0,0 +1,29 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_schema::source::remote_community_directory::RemoteCommunityDirectory;
+use lemmy_db_views_community::api::ListCommunityDirectory;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_community_directory(
+  Query(data): Query<ListCommunityDirectory>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<PagedResponse<RemoteCommunityDirectory>>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let communities = RemoteCommunityDirectory::list(
+    &mut context.pool(),
+    data.search_term.as_deref(),
+    data.show_nsfw.unwrap_or_default(),
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(communities))
+}
@@ -18,12 +18,17 @@ use lemmy_utils::error::LemmyResult;
 pub mod add_mod;
 pub mod ban;
 pub mod block;
+pub mod directory;
 pub mod follow;
 pub mod multi_community_follow;
 pub mod pending_follows;
+pub mod post_template;
+pub mod quarantine;
 pub mod random;
+pub mod recurring_post;
 pub mod tag;
 pub mod transfer;
+pub mod trending;
 pub mod update_notifications;
 
 pub(super) async fn do_follow_community(
@@ -0,0 +1,162 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, check_private_instance, slur_regex},
+};
+use lemmy_db_schema::source::{
+  community::Community,
+  community_post_template::{
+    CommunityPostTemplate,
+    CommunityPostTemplateInsertForm,
+    CommunityPostTemplateUpdateForm,
+  },
+};
+use lemmy_db_views_community::{
+  CommunityView,
+  api::{
+    CreateCommunityPostTemplate,
+    DeleteCommunityPostTemplate,
+    EditCommunityPostTemplate,
+    ListCommunityPostTemplates,
+    ListCommunityPostTemplatesResponse,
+  },
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_post_title},
+  },
+};
+use regex::Regex;
+
+/// Maximum number of post templates per community.
+const MAX_POST_TEMPLATES: usize = 50;
+
+pub async fn create_community_post_template(
+  Json(data): Json<CreateCommunityPostTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityPostTemplate>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // Verify that only mods can create templates
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let templates =
+    CommunityPostTemplate::list_for_community(&mut context.pool(), community.id).await?;
+  if templates.len() >= MAX_POST_TEMPLATES {
+    return Err(LemmyErrorType::TooManyItems.into());
+  }
+  let slur_regex = slur_regex(&context).await?;
+  check_template_fields(
+    Some(&data.name),
+    data.title.as_deref(),
+    data.body.as_deref(),
+    &slur_regex,
+  )?;
+
+  let form = CommunityPostTemplateInsertForm {
+    title: data.title,
+    body: data.body,
+    enforce_headings: data.enforce_headings,
+    ..CommunityPostTemplateInsertForm::new(community.id, data.name.trim().to_string())
+  };
+  let template = CommunityPostTemplate::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(template))
+}
+
+pub async fn edit_community_post_template(
+  Json(data): Json<EditCommunityPostTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityPostTemplate>> {
+  let template = CommunityPostTemplate::read(&mut context.pool(), data.template_id).await?;
+  let community = Community::read(&mut context.pool(), template.community_id).await?;
+
+  // Verify that only mods can update templates
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let slur_regex = slur_regex(&context).await?;
+  check_template_fields(
+    data.name.as_deref(),
+    data.title.as_deref(),
+    data.body.as_deref(),
+    &slur_regex,
+  )?;
+
+  let form = CommunityPostTemplateUpdateForm {
+    name: data.name.map(|n| n.trim().to_string()),
+    title: diesel_string_update(data.title.as_deref()),
+    body: diesel_string_update(data.body.as_deref()),
+    enforce_headings: data.enforce_headings,
+    updated_at: Some(Some(Utc::now())),
+  };
+  let template = CommunityPostTemplate::update(&mut context.pool(), template.id, &form).await?;
+
+  Ok(Json(template))
+}
+
+pub async fn delete_community_post_template(
+  Json(data): Json<DeleteCommunityPostTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityPostTemplate>> {
+  let template = CommunityPostTemplate::read(&mut context.pool(), data.template_id).await?;
+  let community = Community::read(&mut context.pool(), template.community_id).await?;
+
+  // Verify that only mods can delete templates
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  CommunityPostTemplate::delete(&mut context.pool(), template.id).await?;
+
+  Ok(Json(template))
+}
+
+pub async fn list_community_post_templates(
+  Query(data): Query<ListCommunityPostTemplates>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListCommunityPostTemplatesResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  // Fails if the community isn't visible to the user
+  let local_user = local_user_view.as_ref().map(|l| &l.local_user);
+  CommunityView::read(&mut context.pool(), data.community_id, local_user, false).await?;
+
+  let templates =
+    CommunityPostTemplate::list_for_community(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListCommunityPostTemplatesResponse { templates }))
+}
+
+fn check_template_fields(
+  name: Option<&str>,
+  title: Option<&str>,
+  body: Option<&str>,
+  slur_regex: &Regex,
+) -> LemmyResult<()> {
+  if let Some(name) = name {
+    is_valid_post_title(name)?;
+    check_slurs(name, slur_regex)?;
+  }
+  // The title is only a prefix, so it may be shorter than a valid post title
+  if let Some(title) = title {
+    if title.contains('\n') {
+      return Err(LemmyErrorType::InvalidPostTitle.into());
+    }
+    check_slurs(title, slur_regex)?;
+  }
+  if let Some(body) = body {
+    is_valid_body_field(body, true)?;
+    check_slurs(body, slur_regex)?;
+  }
+  Ok(())
+}
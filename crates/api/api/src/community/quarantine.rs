@@ -0,0 +1,50 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  build_response::build_community_response,
+  context::LemmyContext,
+  utils::is_admin,
+};
+use lemmy_db_schema::source::{
+  community::{Community, CommunityUpdateForm},
+  modlog::{Modlog, ModlogInsertForm},
+};
+use lemmy_db_views_community::api::{CommunityResponse, QuarantineCommunity};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn quarantine_community(
+  Json(data): Json<QuarantineCommunity>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityResponse>> {
+  // Only an admin can quarantine a community
+  is_admin(&local_user_view)?;
+
+  // Make sure the community exists
+  let community_id = Community::read(&mut context.pool(), data.community_id)
+    .await?
+    .id;
+
+  // Quarantine is a local moderation decision, so it isn't federated
+  Community::update(
+    &mut context.pool(),
+    community_id,
+    &CommunityUpdateForm {
+      quarantined: Some(data.quarantined),
+      ..Default::default()
+    },
+  )
+  .await?;
+
+  let form = ModlogInsertForm::admin_quarantine_community(
+    &local_user_view.person,
+    community_id,
+    data.quarantined,
+    &data.reason,
+  );
+  Modlog::create(&mut context.pool(), &[form]).await?;
+
+  build_community_response(&context, local_user_view, community_id).await
+}
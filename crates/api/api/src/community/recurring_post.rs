@@ -0,0 +1,198 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::{DateTime, TimeZone, Utc};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, slur_regex},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    community::Community,
+    community_recurring_post::{
+      CommunityRecurringPost,
+      CommunityRecurringPostInsertForm,
+      CommunityRecurringPostUpdateForm,
+    },
+    person::Person,
+  },
+};
+use lemmy_db_schema_file::PersonId;
+use lemmy_db_views_community::api::{
+  CreateCommunityRecurringPost,
+  DeleteCommunityRecurringPost,
+  EditCommunityRecurringPost,
+  ListCommunityRecurringPosts,
+  ListCommunityRecurringPostsResponse,
+};
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::{connection::DbPool, traits::Crud, utils::diesel_string_update};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_post_title},
+  },
+};
+use regex::Regex;
+
+/// Maximum number of recurring posts per community.
+const MAX_RECURRING_POSTS: usize = 20;
+
+pub async fn create_community_recurring_post(
+  Json(data): Json<CreateCommunityRecurringPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityRecurringPost>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // Verify that only mods can create recurring posts
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let recurring_posts =
+    CommunityRecurringPost::list_for_community(&mut context.pool(), community.id).await?;
+  if recurring_posts.len() >= MAX_RECURRING_POSTS {
+    return Err(LemmyErrorType::TooManyItems.into());
+  }
+  let slur_regex = slur_regex(&context).await?;
+  check_recurring_post_fields(
+    Some(&data.title),
+    data.body.as_deref(),
+    Some(data.interval_days),
+    &slur_regex,
+  )?;
+  let creator_id = data.creator_id.unwrap_or(local_user_view.person.id);
+  check_recurring_post_creator(community.id, creator_id, &mut context.pool()).await?;
+
+  let form = CommunityRecurringPostInsertForm {
+    body: data.body,
+    feature: data.feature,
+    ..CommunityRecurringPostInsertForm::new(
+      community.id,
+      creator_id,
+      data.title.trim().to_string(),
+      data.interval_days,
+      convert_time(data.first_post_at)?,
+    )
+  };
+  let recurring_post = CommunityRecurringPost::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(recurring_post))
+}
+
+pub async fn edit_community_recurring_post(
+  Json(data): Json<EditCommunityRecurringPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityRecurringPost>> {
+  let recurring_post =
+    CommunityRecurringPost::read(&mut context.pool(), data.recurring_post_id).await?;
+  let community = Community::read(&mut context.pool(), recurring_post.community_id).await?;
+
+  // Verify that only mods can update recurring posts
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let slur_regex = slur_regex(&context).await?;
+  check_recurring_post_fields(
+    data.title.as_deref(),
+    data.body.as_deref(),
+    data.interval_days,
+    &slur_regex,
+  )?;
+  if let Some(creator_id) = data.creator_id {
+    check_recurring_post_creator(community.id, creator_id, &mut context.pool()).await?;
+  }
+
+  let form = CommunityRecurringPostUpdateForm {
+    creator_id: data.creator_id,
+    title: data.title.map(|t| t.trim().to_string()),
+    body: diesel_string_update(data.body.as_deref()),
+    interval_days: data.interval_days,
+    next_post_at: data.next_post_at.map(convert_time).transpose()?,
+    feature: data.feature,
+    updated_at: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+  let recurring_post =
+    CommunityRecurringPost::update(&mut context.pool(), recurring_post.id, &form).await?;
+
+  Ok(Json(recurring_post))
+}
+
+pub async fn delete_community_recurring_post(
+  Json(data): Json<DeleteCommunityRecurringPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityRecurringPost>> {
+  let recurring_post =
+    CommunityRecurringPost::read(&mut context.pool(), data.recurring_post_id).await?;
+  let community = Community::read(&mut context.pool(), recurring_post.community_id).await?;
+
+  // Verify that only mods can delete recurring posts
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  CommunityRecurringPost::delete(&mut context.pool(), recurring_post.id).await?;
+
+  Ok(Json(recurring_post))
+}
+
+pub async fn list_community_recurring_posts(
+  Query(data): Query<ListCommunityRecurringPosts>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListCommunityRecurringPostsResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let recurring_posts =
+    CommunityRecurringPost::list_for_community(&mut context.pool(), community.id).await?;
+
+  Ok(Json(ListCommunityRecurringPostsResponse {
+    recurring_posts,
+  }))
+}
+
+fn check_recurring_post_fields(
+  title: Option<&str>,
+  body: Option<&str>,
+  interval_days: Option<i32>,
+  slur_regex: &Regex,
+) -> LemmyResult<()> {
+  if let Some(title) = title {
+    is_valid_post_title(title)?;
+    check_slurs(title, slur_regex)?;
+  }
+  if let Some(body) = body {
+    is_valid_body_field(body, true)?;
+    check_slurs(body, slur_regex)?;
+  }
+  if let Some(interval_days) = interval_days
+    && !(1..=365).contains(&interval_days)
+  {
+    return Err(LemmyErrorType::InvalidRecurringPostInterval.into());
+  }
+  Ok(())
+}
+
+/// The posts are created by a local moderator of the community, so that they can be federated.
+async fn check_recurring_post_creator(
+  community_id: CommunityId,
+  creator_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let creator = Person::read(pool, creator_id).await?;
+  if !creator.local {
+    return Err(LemmyErrorType::InvalidRecurringPostCreator.into());
+  }
+  CommunityModeratorView::check_is_community_moderator(pool, community_id, creator_id).await
+}
+
+fn convert_time(unix: i64) -> LemmyResult<DateTime<Utc>> {
+  Ok(
+    Utc
+      .timestamp_opt(unix, 0)
+      .single()
+      .ok_or(LemmyErrorType::InvalidUnixTime)?,
+  )
+}
@@ -2,6 +2,7 @@ use actix_web::web::{Data, Json};
 use anyhow::Context;
 use diesel_async::scoped_futures::ScopedFutureExt;
 use lemmy_api_utils::{
+  cache::invalidate_community,
   context::LemmyContext,
   notify::notify_mod_action,
   utils::{check_community_user_action, is_admin, is_top_mod},
@@ -96,6 +97,8 @@ pub async fn transfer_community(
   let community_id = data.community_id;
   let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
 
+  invalidate_community(community_id).await;
+
   // Return the jwt
   Ok(Json(GetCommunityResponse {
     community_view,
@@ -0,0 +1,26 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_views_community::{
+  TrendingCommunityView,
+  api::{ListTrendingCommunities, ListTrendingCommunitiesResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_trending_communities(
+  Query(data): Query<ListTrendingCommunities>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListTrendingCommunitiesResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+  let communities =
+    TrendingCommunityView::list(&mut context.pool(), local_user, &site, data.limit).await?;
+
+  Ok(Json(ListTrendingCommunitiesResponse { communities }))
+}
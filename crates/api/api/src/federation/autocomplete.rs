@@ -0,0 +1,74 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_schema::{
+  source::{community::Community, person::Person},
+  utils::limit_fetch,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{
+  SiteView,
+  api::{Autocomplete, AutocompleteCommunity, AutocompletePerson, AutocompleteResponse},
+};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn autocomplete(
+  Query(data): Query<Autocomplete>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<AutocompleteResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  // Clients may include the prefix which is used for mentions and community links
+  let q = data.q.trim().trim_start_matches(['@', '!']);
+  if q.is_empty() {
+    return Ok(Json(AutocompleteResponse {
+      communities: vec![],
+      persons: vec![],
+    }));
+  }
+  let (name, domain) = match q.split_once('@') {
+    Some((name, domain)) => (name, Some(domain)),
+    None => (q, None),
+  };
+  let limit = limit_fetch(data.limit, None)?;
+
+  let communities = Community::list_by_name_prefix(
+    &mut context.pool(),
+    name,
+    domain,
+    local_user_view.is_some(),
+    limit,
+  )
+  .await?
+  .into_iter()
+  .map(|c| AutocompleteCommunity {
+    id: c.id,
+    name: c.name,
+    title: c.title,
+    ap_id: c.ap_id,
+    icon: c.icon,
+    local: c.local,
+    nsfw: c.nsfw,
+  })
+  .collect();
+
+  let persons = Person::list_by_name_prefix(&mut context.pool(), name, domain, limit)
+    .await?
+    .into_iter()
+    .map(|p| AutocompletePerson {
+      id: p.id,
+      name: p.name,
+      display_name: p.display_name,
+      ap_id: p.ap_id,
+      avatar: p.avatar,
+      local: p.local,
+      bot_account: p.bot_account,
+    })
+    .collect();
+
+  Ok(Json(AutocompleteResponse {
+    communities,
+    persons,
+  }))
+}
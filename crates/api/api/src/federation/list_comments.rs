@@ -7,14 +7,34 @@ use crate::federation::{
 };
 use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
-use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
-use lemmy_db_schema::source::comment::Comment;
+use lemmy_api_utils::{
+  conditional::ConditionalJson,
+  context::LemmyContext,
+  utils::check_private_instance,
+};
+use lemmy_db_schema::source::{comment::Comment, community::Community, post::Post};
 use lemmy_db_views_comment::{CommentSlimView, CommentView, api::GetComments, impls::CommentQuery};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{pagination::PagedResponse, traits::Crud};
 use lemmy_utils::error::LemmyResult;
 
+/// Fields which are left out with `minimal`.
+const MINIMAL_OMITTED_FIELDS: &[&str] = &[
+  "creator",
+  "post",
+  "community",
+  "tags",
+  "comment.score",
+  "comment.upvotes",
+  "comment.downvotes",
+  "comment.child_count",
+  "comment.hot_rank",
+  "comment.controversy_rank",
+  "comment.report_count",
+  "comment.unresolved_report_count",
+];
+
 /// A common fetcher for both the CommentView, and CommentSlimView.
 async fn list_comments_common(
   data: GetComments,
@@ -29,6 +49,7 @@ async fn list_comments_common(
     parent_id,
     post_id,
     search_term,
+    show_removed,
     page_cursor,
     ..
   } = data;
@@ -43,8 +64,24 @@ async fn list_comments_common(
   )
   .await?;
   let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+  // The community is only needed for its default sort
+  let sort_community_id = if data.sort.is_some() {
+    None
+  } else if community_id.is_none()
+    && let Some(post_id) = post_id
+  {
+    Some(Post::read(&mut context.pool(), post_id).await?.community_id)
+  } else {
+    community_id
+  };
+  let community = if let Some(community_id) = sort_community_id {
+    Some(Community::read(&mut context.pool(), community_id).await?)
+  } else {
+    None
+  };
   let sort = Some(comment_sort_type_with_default(
     data.sort,
+    community.as_ref(),
     local_user,
     &local_site,
   ));
@@ -84,6 +121,8 @@ async fn list_comments_common(
     post_id,
     local_user,
     search_term,
+    sort_by_search_rank: None,
+    show_removed,
     page_cursor,
     limit,
   }
@@ -95,10 +134,15 @@ pub async fn list_comments(
   Query(data): Query<GetComments>,
   context: Data<LemmyContext>,
   local_user_view: Option<LocalUserView>,
-) -> LemmyResult<Json<PagedResponse<CommentView>>> {
+) -> LemmyResult<ConditionalJson<PagedResponse<CommentView>>> {
+  let minimal = data.minimal.unwrap_or_default();
   let common = list_comments_common(data, context, local_user_view).await?;
 
-  Ok(Json(common))
+  let mut res = ConditionalJson::new(common);
+  if minimal {
+    res = res.omit_fields(MINIMAL_OMITTED_FIELDS);
+  }
+  Ok(res)
 }
 
 pub async fn list_comments_slim(
@@ -6,24 +6,90 @@ use crate::federation::{
   post_time_range_seconds_with_default,
 };
 use activitypub_federation::config::Data;
-use actix_web::web::{Json, Query};
-use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use actix_web::web::Query;
+use lemmy_api_utils::{
+  cache::{ANONYMOUS_POSTS, get_or_load},
+  conditional::ConditionalJson,
+  context::LemmyContext,
+  utils::check_private_instance,
+};
 use lemmy_db_schema::{
   newtypes::PostId,
-  source::{keyword_block::LocalUserKeywordBlock, post::PostActions},
+  source::{community::Community, keyword_block::LocalUserKeywordBlock, post::PostActions},
 };
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::{PostView, api::GetPosts, impls::PostQuery};
 use lemmy_db_views_site::SiteView;
-use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_diesel_utils::{pagination::PagedResponse, traits::Crud};
 use lemmy_utils::error::LemmyResult;
 use std::cmp::min;
 
+/// Fields which are left out with `minimal`.
+const MINIMAL_OMITTED_FIELDS: &[&str] = &[
+  "creator",
+  "community",
+  "image_details",
+  "tags",
+  "post.comments",
+  "post.score",
+  "post.upvotes",
+  "post.downvotes",
+  "post.hot_rank",
+  "post.hot_rank_active",
+  "post.controversy_rank",
+  "post.scaled_rank",
+  "post.report_count",
+  "post.unresolved_report_count",
+];
+
 pub async fn list_posts(
-  Query(data): Query<GetPosts>,
+  Query(mut data): Query<GetPosts>,
   context: Data<LemmyContext>,
   local_user_view: Option<LocalUserView>,
-) -> LemmyResult<Json<PagedResponse<PostView>>> {
+) -> LemmyResult<ConditionalJson<PagedResponse<PostView>>> {
+  // Only changes the serialization, so minimal listings share the cache with full ones
+  let minimal = data.minimal.take().unwrap_or_default();
+  let omitted_fields = if minimal { MINIMAL_OMITTED_FIELDS } else { &[] };
+
+  if local_user_view.is_some() {
+    let posts = read_posts(data, &context, local_user_view).await?;
+    return Ok(ConditionalJson::new(posts).omit_fields(omitted_fields));
+  }
+
+  // Anonymous users all see the same listings, so that the front page rarely hits the database
+  let posts = get_or_load(
+    &ANONYMOUS_POSTS,
+    data.clone(),
+    read_posts(data, &context, None),
+  )
+  .await?;
+  // Votes and comments don't update the post, so there is no Last-Modified
+  let state: Vec<_> = posts
+    .iter()
+    .map(|p| {
+      (
+        p.post.id,
+        p.post.updated_at,
+        p.post.newest_comment_time_at,
+        p.post.score,
+        p.post.comments,
+        p.creator.updated_at,
+        p.community.updated_at,
+      )
+    })
+    .collect();
+  Ok(
+    ConditionalJson::new(posts)
+      .etag((minimal, state))
+      .omit_fields(omitted_fields),
+  )
+}
+
+async fn read_posts(
+  data: GetPosts,
+  context: &Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<PagedResponse<PostView>> {
   let SiteView {
     site, local_site, ..
   } = &SiteView::read_local(&mut context.pool()).await?;
@@ -33,7 +99,7 @@ pub async fn list_posts(
   let community_id = resolve_community_identifier(
     &data.community_name,
     data.community_id,
-    &context,
+    context,
     &local_user_view,
   )
   .await?;
@@ -41,7 +107,7 @@ pub async fn list_posts(
   let multi_community_id = resolve_multi_community_identifier(
     &data.multi_community_name,
     data.multi_community_id,
-    &context,
+    context,
     &local_user_view,
   )
   .await?;
@@ -51,11 +117,13 @@ pub async fn list_posts(
     show_read,
     // Show nsfw content if param is true, or if content_warning exists
     show_nsfw,
+    show_removed,
     hide_media,
     no_comments_only,
     search_term,
     search_title_only,
     search_url_only,
+    hashtag,
     page_cursor,
     ..
   } = data;
@@ -68,8 +136,19 @@ pub async fn list_posts(
     community_id,
   ));
 
+  // The community is only needed for its default sort
+  let community = if let Some(community_id) = community_id
+    && data.sort.is_none()
+  {
+    Some(Community::read(&mut context.pool(), community_id).await?)
+  } else {
+    None
+  };
   let sort = Some(post_sort_type_with_default(
-    data.sort, local_user, local_site,
+    data.sort,
+    community.as_ref(),
+    local_user,
+    local_site,
   ));
   let time_range_seconds =
     post_time_range_seconds_with_default(data.time_range_seconds, local_user, local_site);
@@ -95,12 +174,15 @@ pub async fn list_posts(
     show_hidden,
     show_read,
     show_nsfw,
+    show_removed,
     hide_media,
     no_comments_only,
     keyword_blocks,
     search_term,
     search_title_only,
     search_url_only,
+    sort_by_search_rank: None,
+    hashtag,
     page_cursor,
   }
   .list(&mut context.pool(), site, local_site)
@@ -116,5 +198,5 @@ pub async fn list_posts(
     PostActions::mark_as_read(&mut context.pool(), local_user.person_id, &post_ids).await?;
   }
 
-  Ok(Json(posts))
+  Ok(posts)
 }
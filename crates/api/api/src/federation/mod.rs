@@ -1,10 +1,11 @@
 use lemmy_apub_objects::objects::person::ApubPerson;
 use lemmy_db_schema::{
   newtypes::CommunityId,
-  source::{local_site::LocalSite, local_user::LocalUser},
+  source::{community::Community, local_site::LocalSite, local_user::LocalUser},
 };
 use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType};
 
+pub mod autocomplete;
 mod fetcher;
 pub mod list_comments;
 pub mod list_person_content;
@@ -13,7 +14,10 @@ pub mod list_posts;
 pub mod read_community;
 pub mod read_multi_community;
 pub mod read_person;
+pub mod remote_mod_log;
+pub mod resolve_many;
 pub mod resolve_object;
+pub mod resolve_permalink;
 pub mod search;
 pub mod user_settings_backup;
 
@@ -37,18 +41,23 @@ fn listing_type_with_default(
   }
 }
 
-/// Returns a default instance-level post sort type, if none is given by the user.
-/// Order is type, local user default, then site default.
+/// Returns a default post sort type, if none is given by the user.
+/// Order is type, local user default, community default, then site default. The user default only
+/// applies if the user changed it, as it is initialized with the site default.
 fn post_sort_type_with_default(
   type_: Option<PostSortType>,
+  community: Option<&Community>,
   local_user: Option<&LocalUser>,
   local_site: &LocalSite,
 ) -> PostSortType {
-  type_.unwrap_or(
-    local_user
-      .map(|u| u.default_post_sort_type)
-      .unwrap_or(local_site.default_post_sort_type),
-  )
+  type_
+    .or(
+      local_user
+        .map(|u| u.default_post_sort_type)
+        .filter(|s| *s != local_site.default_post_sort_type),
+    )
+    .or(community.and_then(|c| c.default_post_sort_type))
+    .unwrap_or(local_site.default_post_sort_type)
 }
 
 /// Returns a default post_time_range.
@@ -71,18 +80,23 @@ fn post_time_range_seconds_with_default(
   }
 }
 
-/// Returns a default instance-level comment sort type, if none is given by the user.
-/// Order is type, local user default, then site default.
+/// Returns a default comment sort type, if none is given by the user.
+/// Order is type, local user default, community default, then site default. The user default only
+/// applies if the user changed it, as it is initialized with the site default.
 fn comment_sort_type_with_default(
   type_: Option<CommentSortType>,
+  community: Option<&Community>,
   local_user: Option<&LocalUser>,
   local_site: &LocalSite,
 ) -> CommentSortType {
-  type_.unwrap_or(
-    local_user
-      .map(|u| u.default_comment_sort_type)
-      .unwrap_or(local_site.default_comment_sort_type),
-  )
+  type_
+    .or(
+      local_user
+        .map(|u| u.default_comment_sort_type)
+        .filter(|s| *s != local_site.default_comment_sort_type),
+    )
+    .or(community.and_then(|c| c.default_comment_sort_type))
+    .unwrap_or(local_site.default_comment_sort_type)
 }
 
 /// Returns a default page fetch limit.
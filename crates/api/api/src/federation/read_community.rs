@@ -1,11 +1,14 @@
 use crate::federation::fetcher::resolve_community_identifier;
 use activitypub_federation::config::Data;
-use actix_web::web::{Json, Query};
+use actix_web::web::Query;
+use chrono::{DateTime, Utc};
 use lemmy_api_utils::{
+  cache::{COMMUNITIES, get_or_load},
+  conditional::ConditionalJson,
   context::LemmyContext,
   utils::{check_private_instance, is_mod_or_admin_opt, read_site_for_actor},
 };
-use lemmy_db_schema::source::actor_language::CommunityLanguage;
+use lemmy_db_schema::{newtypes::CommunityId, source::actor_language::CommunityLanguage};
 use lemmy_db_views_community::{
   CommunityView,
   api::{GetCommunity, GetCommunityResponse},
@@ -19,7 +22,7 @@ pub async fn get_community(
   Query(data): Query<GetCommunity>,
   context: Data<LemmyContext>,
   local_user_view: Option<LocalUserView>,
-) -> LemmyResult<Json<GetCommunityResponse>> {
+) -> LemmyResult<ConditionalJson<GetCommunityResponse>> {
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
 
   if data.name.is_none() && data.id.is_none() {
@@ -28,19 +31,70 @@ pub async fn get_community(
 
   check_private_instance(&local_user_view, &local_site)?;
 
-  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
-
   let community_id = resolve_community_identifier(&data.name, data.id, &context, &local_user_view)
     .await?
     .ok_or(LemmyErrorType::NoIdGiven)?;
 
-  let is_mod_or_admin = is_mod_or_admin_opt(
-    &mut context.pool(),
-    local_user_view.as_ref(),
-    Some(community_id),
+  if local_user_view.is_some() {
+    let res = read_community(community_id, local_user_view.as_ref(), &context).await?;
+    return Ok(ConditionalJson::new(res));
+  }
+
+  // The sidebar is read much more often than it changes, so it is cached for anonymous users
+  let res = get_or_load(
+    &COMMUNITIES,
+    community_id,
+    read_community(community_id, None, &context),
+  )
+  .await?;
+  let state = community_state(&res);
+  Ok(
+    ConditionalJson::new(res)
+      .etag(&state)
+      .last_modified(state.0),
   )
-  .await
-  .is_ok();
+}
+
+/// Update timestamps of everything in the response, and the counters which change without
+/// updating a timestamp.
+fn community_state(res: &GetCommunityResponse) -> (Vec<DateTime<Utc>>, [i32; 7]) {
+  let community = &res.community_view.community;
+  let mut timestamps = vec![community.updated_at.unwrap_or(community.published_at)];
+  timestamps.extend(
+    res
+      .moderators
+      .iter()
+      .map(|m| m.moderator.updated_at.unwrap_or(m.moderator.published_at)),
+  );
+  timestamps.extend(
+    res
+      .site
+      .as_ref()
+      .map(|s| s.updated_at.unwrap_or(s.published_at)),
+  );
+  let counts = [
+    community.subscribers,
+    community.posts,
+    community.comments,
+    community.users_active_day,
+    community.users_active_week,
+    community.users_active_month,
+    community.users_active_half_year,
+  ];
+  (timestamps, counts)
+}
+
+async fn read_community(
+  community_id: CommunityId,
+  local_user_view: Option<&LocalUserView>,
+  context: &LemmyContext,
+) -> LemmyResult<GetCommunityResponse> {
+  let local_user = local_user_view.map(|u| &u.local_user);
+
+  let is_mod_or_admin =
+    is_mod_or_admin_opt(&mut context.pool(), local_user_view, Some(community_id))
+      .await
+      .is_ok();
 
   let community_view = CommunityView::read(
     &mut context.pool(),
@@ -52,15 +106,15 @@ pub async fn get_community(
 
   let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
 
-  let site = read_site_for_actor(community_view.community.ap_id.clone(), &context).await?;
+  let site = read_site_for_actor(community_view.community.ap_id.clone(), context).await?;
 
   let community_id = community_view.community.id;
   let discussion_languages = CommunityLanguage::read(&mut context.pool(), community_id).await?;
 
-  Ok(Json(GetCommunityResponse {
+  Ok(GetCommunityResponse {
     community_view,
     site,
     moderators,
     discussion_languages,
-  }))
+  })
 }
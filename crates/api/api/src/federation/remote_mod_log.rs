@@ -0,0 +1,273 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::{DateTime, Utc};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_apub_objects::utils::quirks::{InstanceQuirks, Software};
+use lemmy_db_schema::{source::community::Community, utils::limit_fetch};
+use lemmy_db_schema_file::enums::ModlogKind;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_modlog::api::{GetRemoteModlog, GetRemoteModlogResponse, RemoteModlogEntry};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult, UntranslatedError};
+use serde::Deserialize;
+use std::cmp::Reverse;
+use url::Url;
+
+/// Fetches the modlog of a remote community from the instance where it is hosted.
+///
+/// Moderation actions are only federated to instances which know the affected object, and some
+/// (like bans) are not federated at all. So the remote instance is the only complete source.
+/// Uses the v3 API which is supported by all Lemmy versions in use.
+pub async fn get_remote_mod_log(
+  Query(data): Query<GetRemoteModlog>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetRemoteModlogResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  let quirks = InstanceQuirks::read(&mut context.pool(), community.instance_id).await?;
+  if community.local || quirks.software != Software::Lemmy {
+    return Err(LemmyErrorType::RemoteModlogUnavailable.into());
+  }
+  let limit = limit_fetch(data.limit, None)?;
+  let domain = community
+    .ap_id
+    .inner()
+    .domain()
+    .ok_or(UntranslatedError::UrlWithoutDomain)?;
+  let client = context.client();
+
+  // The remote instance uses its own ids, so look up the community by name first
+  let url = format!("https://{domain}/api/v3/community?name={}", community.name);
+  let remote_community = client
+    .get(&url)
+    .send()
+    .await?
+    .error_for_status()?
+    .json::<RemoteGetCommunity>()
+    .await?
+    .community_view
+    .community;
+
+  let url = format!(
+    "https://{domain}/api/v3/modlog?community_id={}&limit={limit}",
+    remote_community.id
+  );
+  let modlog = client
+    .get(&url)
+    .send()
+    .await?
+    .error_for_status()?
+    .json::<RemoteModlog>()
+    .await?;
+
+  let mut entries = modlog.into_entries();
+  entries.sort_by_key(|e| Reverse(e.published_at));
+  entries.truncate(limit.try_into()?);
+  Ok(Json(GetRemoteModlogResponse { entries }))
+}
+
+#[derive(Deserialize)]
+struct RemoteGetCommunity {
+  community_view: RemoteCommunityView,
+}
+
+#[derive(Deserialize)]
+struct RemoteCommunityView {
+  community: RemoteCommunity,
+}
+
+#[derive(Deserialize)]
+struct RemoteCommunity {
+  id: i32,
+}
+
+/// Only the modlog types which apply to community content are read.
+#[derive(Deserialize)]
+struct RemoteModlog {
+  removed_posts: Vec<RemoteRemovePost>,
+  locked_posts: Vec<RemoteLockPost>,
+  removed_comments: Vec<RemoteRemoveComment>,
+  banned_from_community: Vec<RemoteBanFromCommunity>,
+}
+
+#[derive(Deserialize)]
+struct RemotePerson {
+  actor_id: Url,
+  name: String,
+}
+
+#[derive(Deserialize)]
+struct RemotePost {
+  ap_id: Url,
+  name: String,
+}
+
+#[derive(Deserialize)]
+struct RemoteComment {
+  ap_id: Url,
+  content: String,
+}
+
+#[derive(Deserialize)]
+struct RemoteModAction {
+  reason: Option<String>,
+  removed: Option<bool>,
+  locked: Option<bool>,
+  banned: Option<bool>,
+  when_: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct RemoteRemovePost {
+  #[serde(rename = "mod_remove_post")]
+  action: RemoteModAction,
+  moderator: Option<RemotePerson>,
+  post: RemotePost,
+}
+
+#[derive(Deserialize)]
+struct RemoteLockPost {
+  #[serde(rename = "mod_lock_post")]
+  action: RemoteModAction,
+  moderator: Option<RemotePerson>,
+  post: RemotePost,
+}
+
+#[derive(Deserialize)]
+struct RemoteRemoveComment {
+  #[serde(rename = "mod_remove_comment")]
+  action: RemoteModAction,
+  moderator: Option<RemotePerson>,
+  comment: RemoteComment,
+}
+
+#[derive(Deserialize)]
+struct RemoteBanFromCommunity {
+  #[serde(rename = "mod_ban_from_community")]
+  action: RemoteModAction,
+  moderator: Option<RemotePerson>,
+  banned_person: RemotePerson,
+}
+
+impl RemoteModAction {
+  fn into_entry(
+    self,
+    kind: ModlogKind,
+    applied: Option<bool>,
+    moderator: Option<RemotePerson>,
+    target: Url,
+    target_name: String,
+  ) -> RemoteModlogEntry {
+    RemoteModlogEntry {
+      kind,
+      is_revert: !applied.unwrap_or(true),
+      published_at: self.when_,
+      reason: self.reason,
+      moderator: moderator.map(|m| m.actor_id.into()),
+      target: target.into(),
+      target_name,
+    }
+  }
+}
+
+impl RemoteModlog {
+  fn into_entries(self) -> Vec<RemoteModlogEntry> {
+    let removed_posts = self.removed_posts.into_iter().map(|r| {
+      let applied = r.action.removed;
+      r.action.into_entry(
+        ModlogKind::ModRemovePost,
+        applied,
+        r.moderator,
+        r.post.ap_id,
+        r.post.name,
+      )
+    });
+    let locked_posts = self.locked_posts.into_iter().map(|l| {
+      let applied = l.action.locked;
+      l.action.into_entry(
+        ModlogKind::ModLockPost,
+        applied,
+        l.moderator,
+        l.post.ap_id,
+        l.post.name,
+      )
+    });
+    let removed_comments = self.removed_comments.into_iter().map(|r| {
+      let applied = r.action.removed;
+      r.action.into_entry(
+        ModlogKind::ModRemoveComment,
+        applied,
+        r.moderator,
+        r.comment.ap_id,
+        r.comment.content,
+      )
+    });
+    let banned = self.banned_from_community.into_iter().map(|b| {
+      let applied = b.action.banned;
+      b.action.into_entry(
+        ModlogKind::ModBanFromCommunity,
+        applied,
+        b.moderator,
+        b.banned_person.actor_id,
+        b.banned_person.name,
+      )
+    });
+    removed_posts
+      .chain(locked_posts)
+      .chain(removed_comments)
+      .chain(banned)
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  #[test]
+  fn test_parse_remote_modlog() -> LemmyResult<()> {
+    let modlog: RemoteModlog = serde_json::from_value(json!({
+      "removed_posts": [{
+        "mod_remove_post": {
+          "id": 1, "mod_person_id": 2, "post_id": 3, "reason": "spam", "removed": true,
+          "when_": "2024-01-02T00:00:00Z"
+        },
+        "moderator": { "actor_id": "https://example.com/u/mod", "name": "mod" },
+        "post": { "ap_id": "https://example.com/post/3", "name": "Buy now" },
+        "community": {}
+      }],
+      "locked_posts": [],
+      "removed_comments": [],
+      "banned_from_community": [{
+        "mod_ban_from_community": {
+          "id": 1, "banned": false, "when_": "2024-01-03T00:00:00Z"
+        },
+        "banned_person": { "actor_id": "https://example.com/u/alice", "name": "alice" },
+        "community": {}
+      }],
+      "featured_posts": [],
+      "banned": []
+    }))?;
+    let entries = modlog.into_entries();
+    let [removed_post, unban] = entries.as_slice() else {
+      return Err(LemmyErrorType::NotFound.into());
+    };
+
+    assert_eq!(ModlogKind::ModRemovePost, removed_post.kind);
+    assert!(!removed_post.is_revert);
+    assert_eq!(Some("spam"), removed_post.reason.as_deref());
+    assert!(removed_post.moderator.is_some());
+    assert_eq!("Buy now", removed_post.target_name);
+
+    assert_eq!(ModlogKind::ModBanFromCommunity, unban.kind);
+    assert!(unban.is_revert);
+    assert_eq!(None, unban.moderator);
+    Ok(())
+  }
+}
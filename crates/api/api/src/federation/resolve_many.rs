@@ -0,0 +1,98 @@
+use super::resolve_object::resolve_object_internal;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use futures::future::join_all;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt},
+};
+use lemmy_db_schema::source::post::Post;
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_community::CommunityView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person::PersonView;
+use lemmy_db_views_post::PostView;
+use lemmy_db_views_site::{
+  ResolveObjectView,
+  SiteView,
+  api::{ResolveMany, ResolveManyResponse},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Maximum number of objects in a single request.
+const RESOLVE_MANY_LIMIT: usize = 50;
+
+pub async fn resolve_many(
+  Json(data): Json<ResolveMany>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ResolveManyResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let post_ids = data.post_ids.unwrap_or_default();
+  let comment_ids = data.comment_ids.unwrap_or_default();
+  let community_ids = data.community_ids.unwrap_or_default();
+  let person_ids = data.person_ids.unwrap_or_default();
+  let urls = data.urls.unwrap_or_default();
+  let count =
+    post_ids.len() + comment_ids.len() + community_ids.len() + person_ids.len() + urls.len();
+  if count > RESOLVE_MANY_LIMIT {
+    return Err(LemmyErrorType::TooManyItems.into());
+  }
+
+  let local_user = local_user_view.as_ref().map(|l| &l.local_user);
+  let my_person_id = local_user_view.as_ref().map(|l| l.person.id);
+  let is_admin = local_user.is_some_and(|l| l.admin);
+  let local_instance_id = site.instance_id;
+  let pool = &mut context.pool();
+  let mut objects = vec![];
+
+  // Objects which aren't found or not visible are skipped, so that a single deleted item doesn't
+  // break the whole list
+  for post_id in post_ids {
+    let Ok(post) = Post::read(pool, post_id).await else {
+      continue;
+    };
+    let is_mod = is_mod_or_admin_opt(pool, local_user_view.as_ref(), Some(post.community_id))
+      .await
+      .is_ok();
+    if let Ok(p) = PostView::read(pool, post_id, local_user, local_instance_id, is_mod).await {
+      objects.push(ResolveObjectView::Post(p));
+    }
+  }
+  for comment_id in comment_ids {
+    if let Ok(c) = CommentView::read(pool, comment_id, local_user, local_instance_id).await {
+      objects.push(ResolveObjectView::Comment(c));
+    }
+  }
+  for community_id in community_ids {
+    let is_mod = is_mod_or_admin_opt(pool, local_user_view.as_ref(), Some(community_id))
+      .await
+      .is_ok();
+    if let Ok(c) = CommunityView::read(pool, community_id, local_user, is_mod).await {
+      objects.push(ResolveObjectView::Community(c));
+    }
+  }
+  for person_id in person_ids {
+    if let Ok(p) =
+      PersonView::read(pool, person_id, my_person_id, local_instance_id, is_admin).await
+    {
+      objects.push(ResolveObjectView::Person(p));
+    }
+  }
+
+  // Urls of unknown objects may need to be fetched from other instances, so do this in parallel
+  let resolved = join_all(
+    urls
+      .iter()
+      .map(|url| resolve_object_internal(url, false, &local_user_view, &context)),
+  )
+  .await;
+  objects.extend(resolved.into_iter().flatten());
+
+  Ok(Json(ResolveManyResponse { objects }))
+}
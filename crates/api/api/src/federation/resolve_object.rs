@@ -1,6 +1,7 @@
 use activitypub_federation::{
   config::Data,
   fetch::{object_id::ObjectId, webfinger::webfinger_resolve_actor},
+  traits::Object,
 };
 use actix_web::web::{Json, Query};
 use either::Either::*;
@@ -27,21 +28,27 @@ pub async fn resolve_object(
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
   check_private_instance(&local_user_view, &local_site)?;
 
-  let resolve = resolve_object_internal(&data.q, &local_user_view, &context).await?;
+  let refresh = data.refresh.unwrap_or_default();
+  let resolve = resolve_object_internal(&data.q, refresh, &local_user_view, &context).await?;
   Ok(Json(resolve))
 }
 
 pub(super) async fn resolve_object_internal(
   query: &str,
+  refresh: bool,
   local_user_view: &Option<LocalUserView>,
   context: &Data<LemmyContext>,
 ) -> LemmyResult<ResolveObjectView> {
   use ResolveObjectView::*;
   let is_authenticated = local_user_view.as_ref().is_some_and(|l| !l.banned);
 
+  if refresh && !is_authenticated {
+    return Err(LemmyErrorType::NotLoggedIn.into());
+  }
+
   let object = if is_authenticated || cfg!(debug_assertions) {
     // user is fully authenticated; allow remote lookups as well.
-    search_query_to_object_id(query.to_string(), context).await
+    search_query_to_object_id(query.to_string(), refresh, context).await
   } else {
     // user isn't authenticated only allow a local search.
     search_query_to_object_id_local(query, context).await
@@ -80,25 +87,33 @@ pub(super) async fn resolve_object_internal(
 /// Converts search query to object id. The query can either be an URL, which will be treated as
 /// ObjectId directly, or a webfinger identifier (@user@example.com or !community@example.com)
 /// which gets resolved to an URL.
+///
+/// With `refresh`, objects which are known already are fetched again from their instance.
 async fn search_query_to_object_id(
   mut query: String,
+  refresh: bool,
   context: &Data<LemmyContext>,
 ) -> LemmyResult<SearchableObjects> {
-  Ok(match Url::parse(&query) {
-    Ok(url) => {
-      // its already an url, just go with it
-      ObjectId::from(url).dereference(context).await?
-    }
+  let object_id: ObjectId<SearchableObjects> = match Url::parse(&query) {
+    // its already an url, just go with it
+    Ok(url) => url.into(),
     Err(_) => {
       // not an url, try to resolve via webfinger
       if query.starts_with('!') || query.starts_with('@') {
         query.remove(0);
       }
-      Left(Right(
-        webfinger_resolve_actor::<LemmyContext, UserOrCommunity>(&query, context).await?,
-      ))
+      let actor = webfinger_resolve_actor::<LemmyContext, UserOrCommunity>(&query, context).await?;
+      if !refresh {
+        return Ok(Left(Right(actor)));
+      }
+      actor.id().clone().into()
     }
-  })
+  };
+  if refresh && !object_id.is_local(context) {
+    object_id.dereference_forced(context).await
+  } else {
+    object_id.dereference(context).await
+  }
 }
 
 /// Converts a search query to an object id.  The query MUST bbe a URL which will bbe treated
@@ -160,13 +175,13 @@ mod tests {
     let query = post.ap_id.to_string();
 
     // Objects should be resolvable without authentication
-    let res = resolve_object_internal(&query, &None, &context).await?;
+    let res = resolve_object_internal(&query, false, &None, &context).await?;
     assert_response(res, &post);
     // Objects should be resolvable by regular users
-    let res = resolve_object_internal(&query, &Some(regular_user.clone()), &context).await?;
+    let res = resolve_object_internal(&query, false, &Some(regular_user.clone()), &context).await?;
     assert_response(res, &post);
     // Objects should be resolvable by admins
-    let res = resolve_object_internal(&query, &Some(admin_user.clone()), &context).await?;
+    let res = resolve_object_internal(&query, false, &Some(admin_user.clone()), &context).await?;
     assert_response(res, &post);
 
     Post::update(
@@ -180,13 +195,13 @@ mod tests {
     .await?;
 
     // Deleted objects should not be resolvable without authentication
-    let res = resolve_object_internal(&query, &None, &context).await;
+    let res = resolve_object_internal(&query, false, &None, &context).await;
     assert!(res.is_err_and(|e| e.error_type == LemmyErrorType::NotFound));
     // Deleted objects should not be resolvable by regular users
-    let res = resolve_object_internal(&query, &Some(regular_user.clone()), &context).await;
+    let res = resolve_object_internal(&query, false, &Some(regular_user.clone()), &context).await;
     assert!(res.is_err_and(|e| e.error_type == LemmyErrorType::NotFound));
     // Deleted objects should be resolvable by admins
-    let res = resolve_object_internal(&query, &Some(admin_user.clone()), &context).await?;
+    let res = resolve_object_internal(&query, false, &Some(admin_user.clone()), &context).await?;
     assert_response(res, &post);
 
     LocalSite::delete(pool).await?;
@@ -0,0 +1,147 @@
+use super::resolve_object::resolve_object_internal;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{ResolveObjectView, SiteView, api::ResolvePermalink};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use url::Url;
+
+/// Resolves a link to the local copy of the object, or fetches it if it is unknown. This allows
+/// clients to open any link in the instance of the user.
+pub async fn resolve_permalink(
+  Query(data): Query<ResolvePermalink>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ResolveObjectView>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let url = Url::parse(&data.url).with_lemmy_type(LemmyErrorType::InvalidUrl)?;
+  let mut res = Err(LemmyErrorType::NotFound.into());
+  for query in permalink_candidates(&url) {
+    res = resolve_object_internal(&query, false, &local_user_view, &context).await;
+    if res.is_ok() {
+      break;
+    }
+  }
+  Ok(Json(res?))
+}
+
+/// Returns the ids under which the linked object may be known, most likely first. Links from the
+/// web interface of many platforms differ from the ActivityPub id, so these are mapped based on
+/// the path. The original link is always included, as other platforms use it as id directly.
+fn permalink_candidates(url: &Url) -> Vec<String> {
+  let segments: Vec<&str> = url
+    .path_segments()
+    .map(Iterator::collect)
+    .unwrap_or_default();
+  let comment_fragment = url.fragment().and_then(|f| f.strip_prefix("comment_"));
+  let is_id = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+  let path = match (segments.as_slice(), comment_fragment) {
+    // Piefed links comments as `/post/1#comment_2`
+    (["post", ..], Some(comment_id)) if is_id(comment_id) => Some(format!("/comment/{comment_id}")),
+    // Lemmy links comments as `/post/1/2`
+    (["post", _, comment_id], _) if is_id(comment_id) => Some(format!("/comment/{comment_id}")),
+    // Kbin and Mbin use `/m/magazine/t/1/slug/comment/2`, but the id has no slug
+    (["m", magazine, "t", entry_id, _, "comment", comment_id, ..], _) => {
+      Some(format!("/m/{magazine}/t/{entry_id}/-/comment/{comment_id}"))
+    }
+    (["m", magazine, "t", entry_id, ..], _) => Some(format!("/m/{magazine}/t/{entry_id}")),
+    // Mastodon uses `/@alice/1` for statuses of its own users
+    ([user, status_id], _) if is_mastodon_user(user) && is_id(status_id) => Some(format!(
+      "/users/{}/statuses/{status_id}",
+      user.trim_start_matches('@')
+    )),
+    ([user], _) if is_mastodon_user(user) => {
+      Some(format!("/users/{}", user.trim_start_matches('@')))
+    }
+    _ => None,
+  };
+
+  let mut candidates = vec![];
+  if let Some(path) = path {
+    let mut mapped = url.clone();
+    mapped.set_path(&path);
+    mapped.set_query(None);
+    mapped.set_fragment(None);
+    candidates.push(mapped.to_string());
+  }
+  // Mastodon shows remote users as `/@alice@example.com`, these can be resolved via webfinger
+  if let [user] = segments.as_slice()
+    && user.starts_with('@')
+    && user.matches('@').count() == 2
+  {
+    candidates.push(user.to_string());
+  }
+  candidates.push(url.to_string());
+  candidates.dedup();
+  candidates
+}
+
+fn is_mastodon_user(segment: &str) -> bool {
+  segment.len() > 1 && segment.starts_with('@') && segment.matches('@').count() == 1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  fn candidates(url: &str) -> LemmyResult<Vec<String>> {
+    Ok(permalink_candidates(&Url::parse(url)?))
+  }
+
+  #[test]
+  fn test_permalink_candidates() -> LemmyResult<()> {
+    assert_eq!(
+      vec!["https://lemmy.ml/comment/2", "https://lemmy.ml/post/1/2"],
+      candidates("https://lemmy.ml/post/1/2")?
+    );
+    assert_eq!(
+      vec!["https://lemmy.ml/post/1"],
+      candidates("https://lemmy.ml/post/1")?
+    );
+    assert_eq!(
+      vec![
+        "https://piefed.social/comment/2",
+        "https://piefed.social/post/1#comment_2"
+      ],
+      candidates("https://piefed.social/post/1#comment_2")?
+    );
+    assert_eq!(
+      vec![
+        "https://kbin.social/m/tech/t/1/-/comment/2",
+        "https://kbin.social/m/tech/t/1/some-title/comment/2"
+      ],
+      candidates("https://kbin.social/m/tech/t/1/some-title/comment/2")?
+    );
+    assert_eq!(
+      vec![
+        "https://kbin.social/m/tech/t/1",
+        "https://kbin.social/m/tech/t/1/some-title"
+      ],
+      candidates("https://kbin.social/m/tech/t/1/some-title")?
+    );
+    assert_eq!(
+      vec![
+        "https://mastodon.social/users/alice/statuses/123",
+        "https://mastodon.social/@alice/123"
+      ],
+      candidates("https://mastodon.social/@alice/123")?
+    );
+    assert_eq!(
+      vec!["https://kbin.social/m/tech/t/1"],
+      candidates("https://kbin.social/m/tech/t/1")?
+    );
+    assert_eq!(
+      vec![
+        "@alice@example.com",
+        "https://mastodon.social/@alice@example.com"
+      ],
+      candidates("https://mastodon.social/@alice@example.com")?
+    );
+    Ok(())
+  }
+}
@@ -1,24 +1,40 @@
+use crate::federation::fetcher::{resolve_community_identifier, resolve_person_identifier};
 use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
-use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  search::{SearchKind, search_ids},
+  utils::check_private_instance,
+};
 use lemmy_db_schema::{
   CommunitySortType,
   MultiCommunityListingType,
   MultiCommunitySortType,
   PersonListingType,
   PersonSortType,
+  SearchType,
+  newtypes::{CommentId, CommunityId, PostId},
+  source::{comment::Comment, post::Post},
 };
 use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType};
-use lemmy_db_views_comment::impls::CommentQuery;
-use lemmy_db_views_community::impls::{CommunityQuery, MultiCommunityQuery};
+use lemmy_db_views_comment::{CommentView, impls::CommentQuery};
+use lemmy_db_views_community::{
+  CommunityView,
+  impls::{CommunityQuery, MultiCommunityQuery},
+};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::impls::PersonQuery;
-use lemmy_db_views_post::impls::PostQuery;
+use lemmy_db_views_post::{PostView, impls::PostQuery};
 use lemmy_db_views_site::{
   SiteView,
-  api::{Search, SearchResponse},
+  api::{Search, SearchCommentView, SearchPostView, SearchResponse},
 };
 use lemmy_utils::error::LemmyResult;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Maximum number of results for each type which are requested from an external search backend
+const SEARCH_BACKEND_LIMIT: i64 = 20;
 
 pub async fn search(
   Query(data): Query<Search>,
@@ -29,72 +45,213 @@ pub async fn search(
     local_site, site, ..
   } = SiteView::read_local(&mut context.pool()).await?;
 
-  let search_term = Some(data.search_term);
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let (search_term, operators) = SearchOperators::parse(&data.search_term);
   let listing_type = Some(ListingType::All);
   let search_title_only = data.search_title_only;
+  let types = data.types.unwrap_or_default();
+  // Searching within a single post only returns its comments
+  let include = |type_| {
+    (types.is_empty() || types.contains(&type_))
+      && (data.post_id.is_none() || type_ == SearchType::Comments)
+  };
 
-  check_private_instance(&local_user_view, &local_site)?;
+  // Explicit parameters take precedence over operators in the search term
+  let creator_name = data.creator_name.or(operators.author);
+  let creator_id = if creator_name.is_some() || data.creator_id.is_some() {
+    Some(
+      resolve_person_identifier(data.creator_id, &creator_name, &context, &local_user_view).await?,
+    )
+  } else {
+    None
+  };
+  let community_name = data.community_name.or(operators.community);
+  let community_id = resolve_community_identifier(
+    &community_name,
+    data.community_id,
+    &context,
+    &local_user_view,
+  )
+  .await?;
+  let domain = data.domain.or(operators.site);
+
+  // The external search backend indexes titles and bodies together, and doesn't know about the
+  // filters, so they always use the database.
+  let use_search_backend = !search_title_only.unwrap_or_default()
+    && creator_id.is_none()
+    && community_id.is_none()
+    && domain.is_none()
+    && data.show_nsfw.is_none()
+    && data.published_after.is_none()
+    && data.published_before.is_none()
+    && data.min_score.is_none()
+    && data.post_id.is_none();
+
+  // A search term which only consists of operators lists all posts and comments matching the
+  // filters. Other types can only be found by name.
+  let search_term = Some(search_term).filter(|s| !s.is_empty());
+  let by_name = |type_| include(type_) && search_term.is_some();
 
   let local_user = local_user_view.as_ref().map(|u| &u.local_user);
 
-  let posts = PostQuery {
-    search_term: search_term.clone(),
-    search_title_only,
-    local_user,
-    listing_type,
-    sort: Some(PostSortType::New),
-    ..Default::default()
-  }
-  .list(&mut context.pool(), &site, &local_site)
-  .await?
-  .items;
-
-  let comments = CommentQuery {
-    search_term: search_term.clone(),
-    local_user,
-    listing_type,
-    sort: Some(CommentSortType::New),
-    ..Default::default()
-  }
-  .list(&site, &mut context.pool())
-  .await?
-  .items;
-
-  let persons = PersonQuery {
-    search_term: search_term.clone(),
-    search_title_only,
-    local_user,
-    listing_type: Some(PersonListingType::All),
-    sort: Some(PersonSortType::New),
-    ..Default::default()
-  }
-  .list(&site, &mut context.pool())
-  .await?
-  .items;
-
-  let communities = CommunityQuery {
-    search_term: search_term.clone(),
-    search_title_only,
-    local_user,
-    listing_type,
-    sort: Some(CommunitySortType::New),
-    ..Default::default()
-  }
-  .list(&site, &mut context.pool())
-  .await?
-  .items;
-
-  let multi_communities = MultiCommunityQuery {
-    search_term,
-    search_title_only,
-    local_user,
-    listing_type: Some(MultiCommunityListingType::All),
-    sort: Some(MultiCommunitySortType::New),
-    ..Default::default()
-  }
-  .list(&mut context.pool())
-  .await?
-  .items;
+  let post_ids = match &search_term {
+    Some(search_term) if use_search_backend && include(SearchType::Posts) => {
+      backend_search_ids(SearchKind::Post, search_term, &context).await
+    }
+    _ => None,
+  };
+  let posts = if !include(SearchType::Posts) {
+    vec![]
+  } else if let Some(post_ids) = post_ids {
+    let mut posts = vec![];
+    for id in post_ids {
+      // Ignore results which don't exist anymore or aren't visible for the user
+      let post = PostView::read(
+        &mut context.pool(),
+        PostId(id),
+        local_user,
+        site.instance_id,
+        false,
+      )
+      .await;
+      posts.extend(post.ok());
+    }
+    posts
+  } else {
+    PostQuery {
+      search_term: search_term.clone(),
+      search_title_only,
+      sort_by_search_rank: Some(true),
+      local_user,
+      listing_type,
+      sort: Some(PostSortType::New),
+      creator_id,
+      community_id,
+      domain: domain.clone(),
+      show_nsfw: data.show_nsfw,
+      published_after: data.published_after,
+      published_before: data.published_before,
+      min_score: data.min_score,
+      ..Default::default()
+    }
+    .list(&mut context.pool(), &site, &local_site)
+    .await?
+    .items
+  };
+
+  let comment_ids = match &search_term {
+    Some(search_term) if use_search_backend && include(SearchType::Comments) => {
+      backend_search_ids(SearchKind::Comment, search_term, &context).await
+    }
+    _ => None,
+  };
+  // Comments don't have a domain, so they are left out when filtering by it
+  let comments = if !include(SearchType::Comments) || domain.is_some() {
+    vec![]
+  } else if let Some(comment_ids) = comment_ids {
+    let mut comments = vec![];
+    for id in comment_ids {
+      let comment = CommentView::read(
+        &mut context.pool(),
+        CommentId(id),
+        local_user,
+        site.instance_id,
+      )
+      .await;
+      comments.extend(comment.ok());
+    }
+    comments
+  } else {
+    CommentQuery {
+      search_term: search_term.clone(),
+      sort_by_search_rank: Some(true),
+      local_user,
+      listing_type,
+      sort: Some(CommentSortType::New),
+      post_id: data.post_id,
+      creator_id,
+      community_id,
+      show_nsfw: data.show_nsfw,
+      published_after: data.published_after,
+      published_before: data.published_before,
+      min_score: data.min_score,
+      ..Default::default()
+    }
+    .list(&site, &mut context.pool())
+    .await?
+    .items
+  };
+
+  // Only return the matching parts of post bodies and comments, to keep the response small
+  let snippet_term = search_term
+    .as_deref()
+    .filter(|_| !data.full_content.unwrap_or_default());
+  let posts = with_post_snippets(posts, snippet_term, &context).await?;
+  let comments = with_comment_snippets(comments, snippet_term, &context).await?;
+
+  let persons = if by_name(SearchType::Persons) {
+    PersonQuery {
+      search_term: search_term.clone(),
+      search_title_only,
+      local_user,
+      listing_type: Some(PersonListingType::All),
+      sort: Some(PersonSortType::New),
+      ..Default::default()
+    }
+    .list(&site, &mut context.pool())
+    .await?
+    .items
+  } else {
+    vec![]
+  };
+
+  let community_ids = match &search_term {
+    Some(search_term) if use_search_backend && include(SearchType::Communities) => {
+      backend_search_ids(SearchKind::Community, search_term, &context).await
+    }
+    _ => None,
+  };
+  let communities = if !by_name(SearchType::Communities) {
+    vec![]
+  } else if let Some(community_ids) = community_ids {
+    let mut communities = vec![];
+    for id in community_ids {
+      let community =
+        CommunityView::read(&mut context.pool(), CommunityId(id), local_user, false).await;
+      communities.extend(community.ok());
+    }
+    communities
+  } else {
+    CommunityQuery {
+      search_term: search_term.clone(),
+      search_title_only,
+      local_user,
+      listing_type,
+      sort: Some(CommunitySortType::New),
+      show_nsfw: data.show_nsfw,
+      ..Default::default()
+    }
+    .list(&site, &mut context.pool())
+    .await?
+    .items
+  };
+
+  let multi_communities = if by_name(SearchType::MultiCommunities) {
+    MultiCommunityQuery {
+      search_term,
+      search_title_only,
+      local_user,
+      listing_type: Some(MultiCommunityListingType::All),
+      sort: Some(MultiCommunitySortType::New),
+      ..Default::default()
+    }
+    .list(&mut context.pool())
+    .await?
+    .items
+  } else {
+    vec![]
+  };
 
   let res = SearchResponse {
     comments,
@@ -106,3 +263,151 @@ pub async fn search(
 
   Ok(Json(res))
 }
+
+/// Filters which are written directly in the search term, like `author:name`.
+#[derive(Debug, Default, PartialEq)]
+struct SearchOperators {
+  author: Option<String>,
+  community: Option<String>,
+  site: Option<String>,
+}
+
+impl SearchOperators {
+  /// Removes the operators from the search term, and returns the remaining term. Unknown
+  /// operators and operators without value are kept as part of the term.
+  fn parse(search_term: &str) -> (String, Self) {
+    let mut operators = SearchOperators::default();
+    let mut remaining = vec![];
+    for word in search_term.split_whitespace() {
+      let (target, value) = match word.split_once(':') {
+        Some(("author", value)) => (&mut operators.author, value),
+        Some(("community", value)) => (&mut operators.community, value),
+        Some(("site", value)) => (&mut operators.site, value),
+        _ => {
+          remaining.push(word);
+          continue;
+        }
+      };
+      // Allow the usual prefixes, like `author:@name` and `community:!name`
+      let value = value.trim_start_matches(['@', '!']);
+      if value.is_empty() {
+        remaining.push(word);
+      } else {
+        *target = Some(value.to_string());
+      }
+    }
+    (remaining.join(" "), operators)
+  }
+}
+
+/// Replaces the post bodies with highlighted snippets if a search term is given. The bodies of
+/// deleted and removed posts are never included.
+async fn with_post_snippets(
+  posts: Vec<PostView>,
+  search_term: Option<&str>,
+  context: &LemmyContext,
+) -> LemmyResult<Vec<SearchPostView>> {
+  let mut snippets: HashMap<_, _> = match search_term {
+    Some(search_term) => {
+      let post_ids: Vec<_> = posts
+        .iter()
+        .filter(|p| !p.post.deleted && !p.post.removed)
+        .map(|p| p.post.id)
+        .collect();
+      Post::read_search_snippets(&mut context.pool(), &post_ids, search_term)
+        .await?
+        .into_iter()
+        .collect()
+    }
+    None => HashMap::new(),
+  };
+  Ok(
+    posts
+      .into_iter()
+      .map(|mut post_view| {
+        let snippet = snippets.remove(&post_view.post.id);
+        if snippet.is_some() {
+          post_view.post.body = None;
+        }
+        SearchPostView { post_view, snippet }
+      })
+      .collect(),
+  )
+}
+
+/// Replaces the comment contents with highlighted snippets if a search term is given.
+async fn with_comment_snippets(
+  comments: Vec<CommentView>,
+  search_term: Option<&str>,
+  context: &LemmyContext,
+) -> LemmyResult<Vec<SearchCommentView>> {
+  let mut snippets: HashMap<_, _> = match search_term {
+    Some(search_term) => {
+      let comment_ids: Vec<_> = comments
+        .iter()
+        .filter(|c| !c.comment.deleted && !c.comment.removed)
+        .map(|c| c.comment.id)
+        .collect();
+      Comment::read_search_snippets(&mut context.pool(), &comment_ids, search_term)
+        .await?
+        .into_iter()
+        .collect()
+    }
+    None => HashMap::new(),
+  };
+  Ok(
+    comments
+      .into_iter()
+      .map(|mut comment_view| {
+        let snippet = snippets.remove(&comment_view.comment.id);
+        if snippet.is_some() {
+          comment_view.comment.content = String::new();
+        }
+        SearchCommentView {
+          comment_view,
+          snippet,
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Ids of matching items from the external search backend, or `None` if the database should be
+/// searched instead. Falls back to the database if the search backend is unavailable.
+async fn backend_search_ids(
+  kind: SearchKind,
+  search_term: &str,
+  context: &LemmyContext,
+) -> Option<Vec<i32>> {
+  search_ids(kind, search_term, SEARCH_BACKEND_LIMIT, context)
+    .await
+    .inspect_err(|e| warn!("Search backend failed: {e}"))
+    .ok()
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_search_operators() {
+    let (term, operators) =
+      SearchOperators::parse("rust author:alice@lemmy.ml \"borrow checker\" site:github.com");
+    assert_eq!("rust \"borrow checker\"", term);
+    assert_eq!(
+      SearchOperators {
+        author: Some("alice@lemmy.ml".to_string()),
+        community: None,
+        site: Some("github.com".to_string()),
+      },
+      operators
+    );
+
+    let (term, operators) = SearchOperators::parse("community:!rust@lemmy.ml author: http://x");
+    assert_eq!("author: http://x", term);
+    assert_eq!(Some("rust@lemmy.ml".to_string()), operators.community);
+    assert_eq!(None, operators.author);
+  }
+}
@@ -1,12 +1,18 @@
 use lemmy_api_utils::{context::LemmyContext, utils::is_mod_or_admin_opt};
-use lemmy_db_schema::newtypes::CommunityId;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, LocalUserId},
+  source::totp_recovery_code::TotpRecoveryCode,
+};
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::sensitive::SensitiveString;
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::slurs::check_slurs,
 };
 use regex::Regex;
+use sha2::{Digest, Sha256};
 use totp_rs::{Secret, TOTP};
+use uuid::Uuid;
 
 pub mod comment;
 pub mod community;
@@ -16,6 +22,7 @@ pub mod post;
 pub mod reports;
 pub mod site;
 pub mod sitemap;
+pub mod stream;
 
 /// Check size of report
 pub(crate) fn check_report_reason(reason: &str, slur_regex: &Regex) -> LemmyResult<()> {
@@ -54,6 +61,63 @@ pub(crate) fn check_totp_2fa_valid(
   Ok(())
 }
 
+/// Same as [check_totp_2fa_valid], but also accepts one of the user's recovery codes instead of
+/// the totp token. The recovery code can't be used again afterwards.
+pub(crate) async fn check_totp_2fa_or_recovery_code(
+  local_user_view: &LocalUserView,
+  totp_token: &Option<String>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let res = check_totp_2fa_valid(local_user_view, totp_token, &context.settings().hostname);
+  match (res, totp_token) {
+    (Err(e), Some(token)) if e.error_type == LemmyErrorType::IncorrectTotpToken => {
+      TotpRecoveryCode::use_code(
+        &mut context.pool(),
+        local_user_view.local_user.id,
+        &hash_totp_recovery_code(token),
+      )
+      .await
+    }
+    (res, _) => res,
+  }
+}
+
+const TOTP_RECOVERY_CODE_COUNT: usize = 10;
+
+/// Replace the recovery codes of the user with new ones, which are returned in plain text.
+pub(crate) async fn generate_totp_recovery_codes(
+  local_user_id: LocalUserId,
+  context: &LemmyContext,
+) -> LemmyResult<Vec<SensitiveString>> {
+  let codes = (0..TOTP_RECOVERY_CODE_COUNT)
+    .map(|_| new_totp_recovery_code())
+    .collect::<Vec<_>>();
+  let code_hashes = codes.iter().map(|c| hash_totp_recovery_code(c)).collect();
+  TotpRecoveryCode::replace_for_user(&mut context.pool(), local_user_id, code_hashes).await?;
+  Ok(codes.into_iter().map(Into::into).collect())
+}
+
+fn new_totp_recovery_code() -> String {
+  let mut code = Uuid::new_v4()
+    .simple()
+    .to_string()
+    .chars()
+    .take(10)
+    .collect::<String>();
+  code.insert(5, '-');
+  code
+}
+
+/// Case and separators are ignored, so that codes are easier to type.
+fn hash_totp_recovery_code(code: &str) -> String {
+  let normalized = code
+    .chars()
+    .filter(char::is_ascii_alphanumeric)
+    .map(|c| c.to_ascii_lowercase())
+    .collect::<String>();
+  format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
 pub(crate) fn generate_totp_2fa_secret() -> String {
   Secret::generate_secret().to_string()
 }
@@ -107,4 +171,26 @@ mod tests {
     let totp = build_totp_2fa("lemmy.ml", "my_name", &generated_secret);
     assert!(totp.is_ok());
   }
+
+  #[test]
+  fn test_totp_recovery_code() {
+    let code = new_totp_recovery_code();
+    assert_eq!(11, code.len());
+    assert_eq!(Some(5), code.find('-'));
+    assert_ne!(code, new_totp_recovery_code());
+
+    assert_eq!(
+      hash_totp_recovery_code("ab12c-3de45"),
+      hash_totp_recovery_code(" AB12C3DE45 ")
+    );
+    assert_ne!(
+      hash_totp_recovery_code("ab12c-3de45"),
+      hash_totp_recovery_code("ab12c-3de46")
+    );
+    // Stored as hex encoded sha256 of the normalized code
+    assert_eq!(
+      "9f66f3864a0be76368f5056c60220f33fe6ee348062539a3aeaa7504b61f94f8",
+      hash_totp_recovery_code("ab12c-3de45")
+    );
+  }
 }
@@ -1,5 +1,10 @@
 use actix_web::web::{Data, Json};
-use lemmy_api_utils::{context::LemmyContext, notify::notify_mod_action, utils::is_admin};
+use lemmy_api_utils::{
+  cache::invalidate_site,
+  context::LemmyContext,
+  notify::notify_mod_action,
+  utils::is_admin,
+};
 use lemmy_db_schema::source::{
   local_user::{LocalUser, LocalUserUpdateForm},
   modlog::{Modlog, ModlogInsertForm},
@@ -60,6 +65,8 @@ pub async fn add_admin(
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
   notify_mod_action(action.clone(), &context);
 
+  invalidate_site();
+
   let admins = PersonView::list_admins(
     Some(my_person_id),
     local_user_view.person.instance_id,
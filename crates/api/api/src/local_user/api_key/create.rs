@@ -0,0 +1,43 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_local_user_valid, hash_token, slur_regex},
+};
+use lemmy_db_schema::source::api_key::{ApiKey, ApiKeyInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateApiKey, CreateApiKeyResponse};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_display_name},
+};
+use uuid::Uuid;
+
+pub async fn create_api_key(
+  Json(data): Json<CreateApiKey>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateApiKeyResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  is_valid_display_name(&data.name)?;
+  check_slurs(&data.name, &slur_regex(&context).await?)?;
+  if data.rate_limit.is_some_and(|r| r <= 0) {
+    return Err(LemmyErrorType::InvalidBodyField.into());
+  }
+
+  let key = Uuid::new_v4().simple().to_string();
+  let form = ApiKeyInsertForm {
+    rate_limit: data.rate_limit,
+    ..ApiKeyInsertForm::new(
+      local_user_view.local_user.id,
+      data.name,
+      hash_token(&key),
+      data.scope,
+    )
+  };
+  let api_key = ApiKey::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CreateApiKeyResponse {
+    api_key,
+    key: key.into(),
+  }))
+}
@@ -0,0 +1,19 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::api_key::ApiKey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeleteApiKey, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_api_key(
+  Json(data): Json<DeleteApiKey>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let deleted = ApiKey::delete(&mut context.pool(), local_user_view.local_user.id, data.id).await?;
+  if deleted == 0 {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
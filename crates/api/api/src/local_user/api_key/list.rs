@@ -0,0 +1,15 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::api_key::ApiKey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListApiKeysResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_api_keys(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListApiKeysResponse>> {
+  let api_keys = ApiKey::list_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListApiKeysResponse { api_keys }))
+}
@@ -8,9 +8,10 @@ use lemmy_api_utils::{
   context::LemmyContext,
   utils::{check_local_user_valid, password_length_check},
 };
-use lemmy_db_schema::source::{local_user::LocalUser, login_token::LoginToken};
+use lemmy_db_schema::source::{api_key::ApiKey, local_user::LocalUser, login_token::LoginToken};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::{ChangePassword, LoginResponse};
+use lemmy_email::account::send_password_changed_email;
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 pub async fn change_password(
@@ -45,11 +46,17 @@ pub async fn change_password(
     LocalUser::update_password(&mut context.pool(), local_user_id, &new_password).await?;
 
   LoginToken::invalidate_all(&mut context.pool(), local_user_view.local_user.id).await?;
+  ApiKey::delete_all_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  if local_user_view.local_user.email.is_some() {
+    send_password_changed_email(&local_user_view, context.settings())?;
+  }
 
   // Return the jwt
   Ok(Json(LoginResponse {
     jwt: Some(Claims::generate(updated_local_user.id, data.stay_logged_in, req, &context).await?),
     verify_email_sent: false,
     registration_created: false,
+    refresh_token: None,
   }))
 }
@@ -1,11 +1,14 @@
 use actix_web::web::{Data, Json};
 use lemmy_api_utils::{context::LemmyContext, utils::password_length_check};
 use lemmy_db_schema::source::{
+  api_key::ApiKey,
   local_user::LocalUser,
   login_token::LoginToken,
   password_reset_request::PasswordResetRequest,
 };
+use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::{PasswordChangeAfterReset, SuccessResponse};
+use lemmy_email::account::send_password_changed_email;
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 pub async fn change_password_after_reset(
@@ -30,6 +33,11 @@ pub async fn change_password_after_reset(
   LocalUser::update_password(&mut context.pool(), local_user_id, &password).await?;
 
   LoginToken::invalidate_all(&mut context.pool(), local_user_id).await?;
+  ApiKey::delete_all_for_user(&mut context.pool(), local_user_id).await?;
+
+  // The reset email was sent to this address, so it must exist
+  let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
+  send_password_changed_email(&local_user_view, context.settings())?;
 
   Ok(Json(SuccessResponse::default()))
 }
@@ -5,22 +5,19 @@ use actix_web::{
     StatusCode,
     header::{CacheControl, CacheDirective},
   },
-  web::Json,
+  web::{Data, Json},
 };
-use lemmy_api_utils::plugins::{is_captcha_plugin_loaded, plugin_get_captcha};
-use lemmy_db_views_site::api::GetCaptchaResponse;
+use lemmy_api_utils::{captcha::get_captcha as generate_captcha, context::LemmyContext};
+use lemmy_db_views_site::{SiteView, api::GetCaptchaResponse};
 use lemmy_utils::error::LemmyResult;
 
-pub async fn get_captcha() -> LemmyResult<HttpResponse> {
+pub async fn get_captcha(context: Data<LemmyContext>) -> LemmyResult<HttpResponse> {
   let mut res = HttpResponseBuilder::new(StatusCode::OK);
   res.insert_header(CacheControl(vec![CacheDirective::NoStore]));
 
-  if !is_captcha_plugin_loaded() {
-    return Ok(res.json(Json(GetCaptchaResponse { ok: None })));
-  }
-
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
   let captcha = GetCaptchaResponse {
-    ok: Some(plugin_get_captcha().await?),
+    ok: generate_captcha(&local_site).await?,
   };
   Ok(res.json(Json(captcha)))
 }
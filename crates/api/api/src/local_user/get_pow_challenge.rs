@@ -0,0 +1,38 @@
+use actix_web::{
+  HttpResponse,
+  HttpResponseBuilder,
+  http::{
+    StatusCode,
+    header::{CacheControl, CacheDirective},
+  },
+  web::{Data, Json},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::pow_challenge::{PowChallenge, PowChallengeInsertForm};
+use lemmy_db_views_site::{
+  SiteView,
+  api::{GetPowChallengeResponse, PowChallengeResponse},
+};
+use lemmy_utils::error::LemmyResult;
+use uuid::Uuid;
+
+pub async fn get_pow_challenge(context: Data<LemmyContext>) -> LemmyResult<HttpResponse> {
+  let mut res = HttpResponseBuilder::new(StatusCode::OK);
+  res.insert_header(CacheControl(vec![CacheDirective::NoStore]));
+
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  let Some(difficulty) = local_site.pow_challenge_difficulty else {
+    return Ok(res.json(Json(GetPowChallengeResponse { ok: None })));
+  };
+
+  let form = PowChallengeInsertForm {
+    challenge: Uuid::new_v4().simple().to_string(),
+    difficulty,
+  };
+  let pow_challenge = PowChallenge::create(&mut context.pool(), &form).await?;
+  let ok = Some(PowChallengeResponse {
+    challenge: pow_challenge.challenge,
+    difficulty: pow_challenge.difficulty,
+  });
+  Ok(res.json(Json(GetPowChallengeResponse { ok })))
+}
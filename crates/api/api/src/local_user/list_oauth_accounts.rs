@@ -0,0 +1,16 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::oauth_account::OAuthAccount;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListOAuthAccountsResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_oauth_accounts(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListOAuthAccountsResponse>> {
+  let oauth_accounts =
+    OAuthAccount::list_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListOAuthAccountsResponse { oauth_accounts }))
+}
@@ -3,7 +3,7 @@ use actix_web::web::{Json, Query};
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person_content_combined::api::ListPersonRead;
-use lemmy_db_views_post::PostView;
+use lemmy_db_views_post::{PostReadState, PostView, api::SyncReadPosts};
 use lemmy_diesel_utils::pagination::PagedResponse;
 use lemmy_utils::error::LemmyResult;
 
@@ -23,3 +23,21 @@ pub async fn list_person_read(
 
   Ok(Json(read))
 }
+
+/// Read markers since the last sync, so that clients on other devices can hide the same posts.
+pub async fn sync_read_posts(
+  Query(data): Query<SyncReadPosts>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<PostReadState>>> {
+  let read = PostReadState::list(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.since,
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(read))
+}
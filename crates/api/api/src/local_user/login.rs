@@ -1,4 +1,4 @@
-use crate::check_totp_2fa_valid;
+use crate::{check_totp_2fa_or_recovery_code, local_user::webauthn::verify_webauthn_assertion};
 use actix_web::{
   HttpRequest,
   web::{Data, Json},
@@ -7,7 +7,20 @@ use bcrypt::verify;
 use lemmy_api_utils::{
   claims::Claims,
   context::LemmyContext,
-  utils::{check_email_verified, check_local_user_deleted, check_registration_application},
+  utils::{
+    cancel_account_deletion,
+    check_email_verified,
+    check_local_user_deleted,
+    check_login_not_locked,
+    check_registration_application,
+    client_ip,
+    record_login_failure,
+    send_new_login_email_if_new_device,
+  },
+};
+use lemmy_db_schema::source::{
+  login_failure::LoginFailure,
+  webauthn_credential::WebauthnCredential,
 };
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::{
@@ -22,11 +35,25 @@ pub async fn login(
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<LoginResponse>> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let ip = client_ip(&req).map(|ip| ip.to_string());
+  check_login_not_locked(None, ip.as_deref(), &mut context.pool()).await?;
 
   // Fetch that username / email
   let username_or_email = data.username_or_email.clone();
   let local_user_view =
-    LocalUserView::find_by_email_or_name(&mut context.pool(), &username_or_email).await?;
+    match LocalUserView::find_by_email_or_name(&mut context.pool(), &username_or_email).await {
+      Ok(local_user_view) => local_user_view,
+      Err(e) => {
+        record_login_failure(None, ip.as_deref(), &context).await?;
+        return Err(e);
+      }
+    };
+  check_login_not_locked(
+    Some(local_user_view.local_user.id),
+    None,
+    &mut context.pool(),
+  )
+  .await?;
 
   // Verify the password
   let valid: bool = local_user_view
@@ -36,6 +63,7 @@ pub async fn login(
     .and_then(|password_encrypted| verify(&data.password, password_encrypted).ok())
     .unwrap_or(false);
   if !valid {
+    record_login_failure(Some(&local_user_view), ip.as_deref(), &context).await?;
     return Err(LemmyErrorType::IncorrectLogin.into());
   }
   check_local_user_deleted(&local_user_view)?;
@@ -44,26 +72,65 @@ pub async fn login(
   check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
     .await?;
 
-  // Check the totp if enabled
-  if local_user_view.local_user.totp_2fa_enabled {
-    check_totp_2fa_valid(
-      &local_user_view,
-      &data.totp_2fa_token,
-      &context.settings().hostname,
-    )?;
+  if let Err(e) = check_second_factor(&data, &local_user_view, &context).await {
+    // Only count a wrong second factor, not a missing one. Clients first send the password alone
+    // to find out whether 2fa is needed.
+    if matches!(
+      e.error_type,
+      LemmyErrorType::IncorrectTotpToken | LemmyErrorType::InvalidWebauthnCredential
+    ) {
+      record_login_failure(Some(&local_user_view), ip.as_deref(), &context).await?;
+    }
+    return Err(e);
   }
 
-  let jwt = Claims::generate(
-    local_user_view.local_user.id,
-    data.stay_logged_in,
-    req,
-    &context,
-  )
-  .await?;
+  LoginFailure::clear(&mut context.pool(), local_user_view.local_user.id).await?;
+  cancel_account_deletion(&local_user_view, &mut context.pool()).await?;
+  send_new_login_email_if_new_device(&local_user_view, &req, &context).await?;
+  let (jwt, refresh_token) = if data.use_refresh_token.unwrap_or_default() {
+    let (jwt, refresh_token) =
+      Claims::generate_with_refresh_token(local_user_view.local_user.id, req, &context).await?;
+    (jwt, Some(refresh_token))
+  } else {
+    let jwt = Claims::generate(
+      local_user_view.local_user.id,
+      data.stay_logged_in,
+      req,
+      &context,
+    )
+    .await?;
+    (jwt, None)
+  };
 
   Ok(Json(LoginResponse {
-    jwt: Some(jwt.clone()),
+    jwt: Some(jwt),
     verify_email_sent: false,
     registration_created: false,
+    refresh_token,
   }))
 }
+
+/// Checks the second factor if enabled, either totp or a passkey.
+async fn check_second_factor(
+  data: &Login,
+  local_user_view: &LocalUserView,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  if let Some(assertion) = &data.webauthn_assertion {
+    verify_webauthn_assertion(
+      assertion,
+      Some(local_user_view.local_user.id),
+      false,
+      context,
+    )
+    .await?;
+  } else if local_user_view.local_user.totp_2fa_enabled {
+    check_totp_2fa_or_recovery_code(local_user_view, &data.totp_2fa_token, context).await?;
+  } else if !WebauthnCredential::list_for_user(&mut context.pool(), local_user_view.local_user.id)
+    .await?
+    .is_empty()
+  {
+    return Err(LemmyErrorType::MissingWebauthnAssertion.into());
+  }
+  Ok(())
+}
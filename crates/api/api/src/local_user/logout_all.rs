@@ -0,0 +1,20 @@
+use activitypub_federation::config::Data;
+use actix_web::{HttpResponse, cookie::Cookie};
+use lemmy_api_utils::{context::LemmyContext, utils::AUTH_COOKIE_NAME};
+use lemmy_db_schema::source::login_token::LoginToken;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_utils::error::LemmyResult;
+
+/// Log out of all sessions, including the current one.
+pub async fn logout_all(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<HttpResponse> {
+  LoginToken::invalidate_all(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  let mut res = HttpResponse::Ok().json(SuccessResponse::default());
+  let cookie = Cookie::new(AUTH_COOKIE_NAME, "");
+  res.add_removal_cookie(&cookie)?;
+  Ok(res)
+}
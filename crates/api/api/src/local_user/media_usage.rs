@@ -0,0 +1,33 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{image_upload_quota_bytes, is_admin},
+};
+use lemmy_db_schema::source::images::LocalImage;
+use lemmy_db_views_local_image::api::{GetMediaUsage, GetMediaUsageResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_media_usage(
+  Query(data): Query<GetMediaUsage>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetMediaUsageResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+
+  // Only admins can see the usage of other users
+  let user_view = match data.person_id {
+    Some(person_id) if person_id != local_user_view.person.id => {
+      is_admin(&local_user_view)?;
+      LocalUserView::read_person(&mut context.pool(), person_id).await?
+    }
+    _ => local_user_view,
+  };
+
+  let used_bytes = LocalImage::used_bytes(&mut context.pool(), user_view.person.id).await?;
+  Ok(Json(GetMediaUsageResponse {
+    used_bytes,
+    quota_bytes: image_upload_quota_bytes(&user_view, &local_site),
+  }))
+}
@@ -1,4 +1,5 @@
 pub mod add_admin;
+pub mod api_key;
 pub mod ban_person;
 pub mod block;
 pub mod change_password;
@@ -7,21 +8,34 @@ pub mod donation_dialog_shown;
 pub mod export_data;
 pub mod generate_totp_secret;
 pub mod get_captcha;
+pub mod get_pow_challenge;
 pub mod list_hidden;
 pub mod list_liked;
 pub mod list_logins;
 pub mod list_media;
+pub mod list_oauth_accounts;
 pub mod list_read;
 pub mod list_saved;
 pub mod login;
 pub mod logout;
+pub mod logout_all;
+pub mod media_usage;
+pub mod mute_person;
 pub mod note_person;
 pub mod notifications;
+pub mod push_subscription;
+pub mod refresh_token;
+pub mod regenerate_totp_recovery_codes;
 pub mod resend_verification_email;
 pub mod reset_password;
+pub mod revoke_login;
 pub mod save_settings;
+pub mod saved_search;
+pub mod unlink_oauth_account;
 pub mod unread_counts;
 pub mod update_totp;
 pub mod user_block_instance;
 pub mod validate_auth;
 pub mod verify_email;
+pub mod verify_pow_challenge;
+pub mod webauthn;
@@ -0,0 +1,42 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
+use lemmy_db_schema::source::person::{PersonActions, PersonMuteForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person::{
+  PersonView,
+  api::{MutePerson, PersonResponse},
+};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn user_mute_person(
+  Json(data): Json<MutePerson>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PersonResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let target_id = data.person_id;
+  let my_person_id = local_user_view.person.id;
+  let local_instance_id = local_user_view.person.instance_id;
+
+  // Don't let a person mute themselves
+  if target_id == my_person_id {
+    return Err(LemmyErrorType::CantMuteYourself.into());
+  }
+
+  if data.mute {
+    let form = PersonMuteForm::new(my_person_id, target_id);
+    PersonActions::mute(&mut context.pool(), &form).await?;
+  } else {
+    PersonActions::unmute(&mut context.pool(), my_person_id, target_id).await?;
+  }
+
+  let person_view = PersonView::read(
+    &mut context.pool(),
+    target_id,
+    Some(my_person_id),
+    local_instance_id,
+    false,
+  )
+  .await?;
+  Ok(Json(PersonResponse { person_view }))
+}
@@ -0,0 +1,58 @@
+use super::current_login;
+use actix_web::{
+  HttpRequest,
+  web::{Data, Json},
+};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  push::is_push_provider_enabled,
+  request::check_url_is_public,
+};
+use lemmy_db_schema::source::push_subscription::{PushSubscription, PushSubscriptionForm};
+use lemmy_db_schema_file::enums::PushProvider;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{RegisterPushSubscription, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub async fn register_push_subscription(
+  req: HttpRequest,
+  Json(data): Json<RegisterPushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let provider = data.provider.unwrap_or_default();
+  if !is_push_provider_enabled(provider, context.settings()) {
+    return Err(LemmyErrorType::PushNotificationsDisabled.into());
+  }
+  let endpoint = match provider {
+    // The device token is sent to the proxy of the instance
+    PushProvider::Fcm => data.endpoint,
+    // Notifications are sent to the endpoint by the server, so only allow push services
+    PushProvider::WebPush | PushProvider::UnifiedPush => {
+      let endpoint = Url::parse(&data.endpoint).with_lemmy_type(LemmyErrorType::InvalidUrl)?;
+      if endpoint.scheme() != "https" {
+        return Err(LemmyErrorType::InvalidUrlScheme.into());
+      }
+      check_url_is_public(&endpoint).await?;
+      endpoint.into()
+    }
+  };
+  if provider == PushProvider::WebPush && (data.p256dh.is_none() || data.auth.is_none()) {
+    return Err(LemmyErrorType::InvalidBodyField.into());
+  }
+
+  let login = current_login(&req, &local_user_view, &context).await?;
+  let form = PushSubscriptionForm {
+    local_user_id: local_user_view.local_user.id,
+    login_token_id: login.id,
+    endpoint,
+    p256dh_key: data.p256dh,
+    auth_key: data.auth,
+    provider,
+    device_name: data.device_name,
+  };
+  PushSubscription::upsert(&mut context.pool(), &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,21 @@
+use super::current_login;
+use actix_web::{
+  HttpRequest,
+  web::{Data, Json},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn unregister_push_subscription(
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let login = current_login(&req, &local_user_view, &context).await?;
+  PushSubscription::delete_for_login(&mut context.pool(), login.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
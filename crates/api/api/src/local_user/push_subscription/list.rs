@@ -0,0 +1,16 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListPushSubscriptionsResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_push_subscriptions(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListPushSubscriptionsResponse>> {
+  let push_subscriptions =
+    PushSubscription::list_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListPushSubscriptionsResponse { push_subscriptions }))
+}
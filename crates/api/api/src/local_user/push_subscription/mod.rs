@@ -0,0 +1,19 @@
+use actix_web::HttpRequest;
+use lemmy_api_utils::{context::LemmyContext, utils::read_auth_token};
+use lemmy_db_schema::source::login_token::LoginToken;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+/// Push subscriptions belong to the login session which registered them.
+async fn current_login(
+  req: &HttpRequest,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<LoginToken> {
+  let jwt = read_auth_token(req)?.ok_or(LemmyErrorType::NotLoggedIn)?;
+  LoginToken::validate(&mut context.pool(), local_user_view.local_user.id, &jwt).await
+}
@@ -0,0 +1,18 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{claims::Claims, context::LemmyContext};
+use lemmy_db_views_site::api::{LoginResponse, RefreshToken};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn refresh_token(
+  Json(data): Json<RefreshToken>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<LoginResponse>> {
+  let (jwt, refresh_token) = Claims::refresh(&data.refresh_token, &context).await?;
+
+  Ok(Json(LoginResponse {
+    jwt: Some(jwt),
+    verify_email_sent: false,
+    registration_created: false,
+    refresh_token: Some(refresh_token),
+  }))
+}
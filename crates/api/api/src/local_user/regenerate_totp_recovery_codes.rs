@@ -0,0 +1,29 @@
+use crate::{check_totp_2fa_valid, generate_totp_recovery_codes};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{RegenerateTotpRecoveryCodes, TotpRecoveryCodesResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Replace the recovery codes for two-factor-authentication with new ones, which invalidates all
+/// of the previous codes. Requires a valid totp token.
+pub async fn regenerate_totp_recovery_codes(
+  Json(data): Json<RegenerateTotpRecoveryCodes>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<TotpRecoveryCodesResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  if !local_user_view.local_user.totp_2fa_enabled {
+    return Err(LemmyErrorType::TotpNotEnabled.into());
+  }
+  check_totp_2fa_valid(
+    &local_user_view,
+    &Some(data.totp_token),
+    &context.settings().hostname,
+  )?;
+
+  let recovery_codes =
+    generate_totp_recovery_codes(local_user_view.local_user.id, &context).await?;
+
+  Ok(Json(TotpRecoveryCodesResponse { recovery_codes }))
+}
@@ -22,7 +22,7 @@ use lemmy_diesel_utils::{
   traits::Crud,
   utils::{diesel_opt_number_update, diesel_string_update},
 };
-use lemmy_email::account::send_verification_email;
+use lemmy_email::account::{send_email_change_emails, send_verification_email};
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   utils::validation::{
@@ -32,7 +32,6 @@ use lemmy_utils::{
     is_valid_matrix_id,
   },
 };
-use std::ops::Deref;
 
 pub async fn save_user_settings(
   Json(data): Json<SaveUserSettings>,
@@ -59,21 +58,35 @@ pub async fn save_user_settings(
   let display_name = diesel_string_update(data.display_name.as_deref().map(str::trim));
   let matrix_user_id = diesel_string_update(data.matrix_user_id.as_deref());
   let email_deref = data.email.as_deref().map(str::to_lowercase);
-  let email = diesel_string_update(email_deref.as_deref());
+  let mut email = diesel_string_update(email_deref.as_deref());
 
-  if let Some(Some(email)) = email.clone() {
-    let previous_email = local_user_view.local_user.email.clone().unwrap_or_default();
+  if let Some(Some(new_email)) = email.clone() {
+    let previous_email = local_user_view.local_user.email.clone();
     // if email was changed, check that it is not taken and send verification mail
-    if previous_email.deref() != email {
-      LocalUser::check_is_email_taken(&mut context.pool(), &email).await?;
-      send_verification_email(
-        &local_site,
-        &local_user_view,
-        email.into(),
-        &mut context.pool(),
-        context.settings(),
-      )
-      .await?;
+    if previous_email.as_deref() != Some(new_email.as_str()) {
+      LocalUser::check_is_email_taken(&mut context.pool(), &new_email).await?;
+      if previous_email.is_some() {
+        // The existing email stays valid until the new one is confirmed
+        send_email_change_emails(
+          &local_user_view,
+          new_email.into(),
+          &mut context.pool(),
+          context.settings(),
+        )
+        .await?;
+      } else {
+        send_verification_email(
+          &local_site,
+          &local_user_view,
+          new_email.into(),
+          &mut context.pool(),
+          context.settings(),
+        )
+        .await?;
+      }
+    }
+    if previous_email.is_some() {
+      email = None;
     }
   }
 
@@ -98,6 +111,14 @@ pub async fn save_user_settings(
     is_valid_matrix_id(matrix_user_id)?;
   }
 
+  // Quiet hours are given in minutes after midnight
+  let valid_minute = |m: &i32| (0..24 * 60).contains(m);
+  if !data.quiet_hours_start.iter().all(valid_minute)
+    || !data.quiet_hours_end.iter().all(valid_minute)
+  {
+    return Err(LemmyErrorType::InvalidQuietHours.into());
+  }
+
   if let Some(send_notifications_to_email) = data.send_notifications_to_email
     && local_site.email_notifications_disabled
     && send_notifications_to_email
@@ -156,6 +177,14 @@ pub async fn save_user_settings(
     show_avatars: data.show_avatars,
     show_read_posts: data.show_read_posts,
     send_notifications_to_email: data.send_notifications_to_email,
+    push_replies: data.push_replies,
+    push_mentions: data.push_mentions,
+    push_private_messages: data.push_private_messages,
+    push_reports: data.push_reports,
+    push_registration_applications: data.push_registration_applications,
+    quiet_hours_enabled: data.quiet_hours_enabled,
+    quiet_hours_start: data.quiet_hours_start,
+    quiet_hours_end: data.quiet_hours_end,
     show_nsfw: data.show_nsfw,
     blur_nsfw: data.blur_nsfw,
     show_bot_accounts: data.show_bot_accounts,
@@ -174,6 +203,7 @@ pub async fn save_user_settings(
     collapse_bot_comments: data.collapse_bot_comments,
     auto_mark_fetched_posts_as_read: data.auto_mark_fetched_posts_as_read,
     hide_media: data.hide_media,
+    hide_blocked_person_threads: data.hide_blocked_person_threads,
     // Update the vote display modes
     show_score: data.show_score,
     show_upvotes: data.show_upvotes,
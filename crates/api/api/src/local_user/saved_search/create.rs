@@ -0,0 +1,36 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
+use lemmy_db_schema::source::saved_search::{SavedSearch, SavedSearchInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateSavedSearch, SavedSearchResponse};
+use lemmy_utils::{
+  MAX_SAVED_SEARCHES,
+  error::{LemmyErrorType, LemmyResult},
+};
+
+pub async fn create_saved_search(
+  Json(data): Json<CreateSavedSearch>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SavedSearchResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let search_term = data.search_term.trim().to_string();
+  if search_term.is_empty() {
+    return Err(LemmyErrorType::InvalidBodyField.into());
+  }
+
+  let local_user_id = local_user_view.local_user.id;
+  let saved_searches = SavedSearch::list_for_user(&mut context.pool(), local_user_id).await?;
+  if saved_searches.len() >= MAX_SAVED_SEARCHES {
+    return Err(LemmyErrorType::TooManyItems.into());
+  }
+
+  let form = SavedSearchInsertForm {
+    community_id: data.community_id,
+    notify: data.notify,
+    ..SavedSearchInsertForm::new(local_user_id, search_term)
+  };
+  let saved_search = SavedSearch::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(SavedSearchResponse { saved_search }))
+}
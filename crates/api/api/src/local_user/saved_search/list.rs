@@ -0,0 +1,16 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::saved_search::SavedSearch;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListSavedSearchesResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_saved_searches(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListSavedSearchesResponse>> {
+  let saved_searches =
+    SavedSearch::list_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListSavedSearchesResponse { saved_searches }))
+}
@@ -0,0 +1,34 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::{
+  oauth_account::OAuthAccount,
+  webauthn_credential::WebauthnCredential,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{SuccessResponse, UnlinkOAuthAccount};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn unlink_oauth_account(
+  Json(data): Json<UnlinkOAuthAccount>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let local_user_id = local_user_view.local_user.id;
+  let oauth_accounts = OAuthAccount::list_for_user(&mut context.pool(), local_user_id).await?;
+  let passkeys = WebauthnCredential::list_for_user(&mut context.pool(), local_user_id).await?;
+
+  // Users who registered with OAuth have no password, so they need to keep at least one linked
+  // account or a passkey to be able to login.
+  let has_other_login = local_user_view.local_user.password_encrypted.is_some()
+    || !passkeys.is_empty()
+    || oauth_accounts
+      .iter()
+      .any(|a| a.oauth_provider_id != data.oauth_provider_id);
+  if !has_other_login {
+    return Err(LemmyErrorType::CantUnlinkLastLoginMethod.into());
+  }
+
+  OAuthAccount::delete(&mut context.pool(), local_user_id, data.oauth_provider_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
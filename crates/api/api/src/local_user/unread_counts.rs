@@ -21,6 +21,9 @@ pub async fn get_unread_counts(
 
   let notification_count =
     NotificationView::get_unread_count(&mut context.pool(), person, show_bot_accounts).await?;
+  let subscribed_count =
+    NotificationView::get_unread_subscribed_count(&mut context.pool(), person, show_bot_accounts)
+      .await?;
 
   // Community mods get additional counts for reports and pending follows for private communities.
   let (report_count, pending_follow_count) =
@@ -53,6 +56,7 @@ pub async fn get_unread_counts(
 
   Ok(Json(UnreadCountsResponse {
     notification_count,
+    subscribed_count,
     report_count,
     pending_follow_count,
     registration_application_count,
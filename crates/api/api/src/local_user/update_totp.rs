@@ -1,7 +1,10 @@
-use crate::check_totp_2fa_valid;
+use crate::{check_totp_2fa_or_recovery_code, check_totp_2fa_valid, generate_totp_recovery_codes};
 use actix_web::web::{Data, Json};
 use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
-use lemmy_db_schema::source::local_user::{LocalUser, LocalUserUpdateForm};
+use lemmy_db_schema::source::{
+  local_user::{LocalUser, LocalUserUpdateForm},
+  totp_recovery_code::TotpRecoveryCode,
+};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::{EditTotp, EditTotpResponse};
 use lemmy_utils::error::LemmyResult;
@@ -10,21 +13,22 @@ use lemmy_utils::error::LemmyResult;
 /// [LocalUser.totp_2fa_enabled].
 ///
 /// To enable, you need to first call [generate_totp_secret] and then pass a valid token to this
-/// function.
+/// function. This returns new recovery codes.
 ///
 /// Disabling is only possible if 2FA was previously enabled. Again it is necessary to pass a valid
-/// token.
+/// token, or a recovery code.
 pub async fn edit_totp(
   Json(data): Json<EditTotp>,
   local_user_view: LocalUserView,
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<EditTotpResponse>> {
   check_local_user_valid(&local_user_view)?;
-  check_totp_2fa_valid(
-    &local_user_view,
-    &Some(data.totp_token.clone()),
-    &context.settings().hostname,
-  )?;
+  let totp_token = Some(data.totp_token.clone());
+  if data.enabled {
+    check_totp_2fa_valid(&local_user_view, &totp_token, &context.settings().hostname)?;
+  } else {
+    check_totp_2fa_or_recovery_code(&local_user_view, &totp_token, &context).await?;
+  }
 
   // toggle the 2fa setting
   let local_user_form = LocalUserUpdateForm {
@@ -41,7 +45,16 @@ pub async fn edit_totp(
   )
   .await?;
 
+  let local_user_id = local_user_view.local_user.id;
+  let recovery_codes = if data.enabled {
+    Some(generate_totp_recovery_codes(local_user_id, &context).await?)
+  } else {
+    TotpRecoveryCode::delete_for_user(&mut context.pool(), local_user_id).await?;
+    None
+  };
+
   Ok(Json(EditTotpResponse {
     enabled: data.enabled,
+    recovery_codes,
   }))
 }
@@ -1,9 +1,14 @@
 use actix_web::web::{Data, Json};
-use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  push::send_new_applicant_push_to_admins,
+  utils::check_local_user_valid,
+};
 use lemmy_db_schema::source::{
   email_verification::EmailVerification,
   local_user::{LocalUser, LocalUserUpdateForm},
 };
+use lemmy_db_schema_file::enums::RegistrationMode;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::{
   SiteView,
@@ -48,6 +53,11 @@ pub async fn verify_email(
     )
     .await?;
   }
+  if site_view.local_site.registration_mode == RegistrationMode::RequireApplication
+    && !email_already_verified
+  {
+    send_new_applicant_push_to_admins(&local_user_view.person.name, &context).await?;
+  }
 
   send_email_verified_email(&local_user_view, context.settings())?;
 
@@ -0,0 +1,18 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::is_valid_pow_solution};
+use lemmy_db_schema::source::pow_challenge::PowChallenge;
+use lemmy_db_views_site::api::{SuccessResponse, VerifyPowChallenge};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Lets clients check their solution before registering. The challenge stays valid, and is only
+/// used up by the registration.
+pub async fn verify_pow_challenge(
+  Json(data): Json<VerifyPowChallenge>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let pow_challenge = PowChallenge::read(&mut context.pool(), &data.challenge).await?;
+  if !is_valid_pow_solution(&data.challenge, &data.nonce, pow_challenge.difficulty) {
+    return Err(LemmyErrorType::InvalidPowChallenge.into());
+  }
+  Ok(Json(SuccessResponse::default()))
+}
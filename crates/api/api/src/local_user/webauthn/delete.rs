@@ -0,0 +1,31 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::{
+  oauth_account::OAuthAccount,
+  webauthn_credential::WebauthnCredential,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeleteWebauthnCredential, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_webauthn_credential(
+  Json(data): Json<DeleteWebauthnCredential>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let local_user_id = local_user_view.local_user.id;
+  let oauth_accounts = OAuthAccount::list_for_user(&mut context.pool(), local_user_id).await?;
+  let passkeys = WebauthnCredential::list_for_user(&mut context.pool(), local_user_id).await?;
+
+  // Users without password may have unlinked their OAuth accounts after adding a passkey
+  let has_other_login = local_user_view.local_user.password_encrypted.is_some()
+    || !oauth_accounts.is_empty()
+    || passkeys.iter().any(|p| p.id != data.id);
+  if !has_other_login {
+    return Err(LemmyErrorType::CantUnlinkLastLoginMethod.into());
+  }
+
+  WebauthnCredential::delete(&mut context.pool(), local_user_id, data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,18 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webauthn_credential::WebauthnCredential;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListWebauthnCredentialsResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_webauthn_credentials(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListWebauthnCredentialsResponse>> {
+  let webauthn_credentials =
+    WebauthnCredential::list_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListWebauthnCredentialsResponse {
+    webauthn_credentials,
+  }))
+}
@@ -0,0 +1,56 @@
+use super::verify_webauthn_assertion;
+use actix_web::{
+  HttpRequest,
+  web::{Data, Json},
+};
+use lemmy_api_utils::{
+  claims::Claims,
+  context::LemmyContext,
+  utils::{
+    cancel_account_deletion,
+    check_email_verified,
+    check_local_user_deleted,
+    check_registration_application,
+    send_new_login_email_if_new_device,
+  },
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{
+  SiteView,
+  api::{LoginResponse, WebauthnLogin},
+};
+use lemmy_utils::error::LemmyResult;
+
+/// Passwordless login with a passkey. User verification by the authenticator is required, so that
+/// it counts as two factors.
+pub async fn webauthn_login(
+  Json(data): Json<WebauthnLogin>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<LoginResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let credential = verify_webauthn_assertion(&data.assertion, None, true, &context).await?;
+
+  let local_user_view = LocalUserView::read(&mut context.pool(), credential.local_user_id).await?;
+  check_local_user_deleted(&local_user_view)?;
+  check_email_verified(&local_user_view, &site_view)?;
+  check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
+    .await?;
+
+  cancel_account_deletion(&local_user_view, &mut context.pool()).await?;
+  send_new_login_email_if_new_device(&local_user_view, &req, &context).await?;
+  let jwt = Claims::generate(
+    local_user_view.local_user.id,
+    data.stay_logged_in,
+    req,
+    &context,
+  )
+  .await?;
+
+  Ok(Json(LoginResponse {
+    jwt: Some(jwt),
+    verify_email_sent: false,
+    registration_created: false,
+    refresh_token: None,
+  }))
+}
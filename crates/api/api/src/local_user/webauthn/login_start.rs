@@ -0,0 +1,38 @@
+use super::{create_challenge, rp_id};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webauthn_credential::WebauthnCredential;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{StartWebauthnLogin, WebauthnLoginOptions};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn start_webauthn_login(
+  Json(data): Json<StartWebauthnLogin>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<WebauthnLoginOptions>> {
+  let mut allow_credentials = vec![];
+  let mut local_user_id = None;
+  if let Some(username_or_email) = &data.username_or_email {
+    // Dont reveal if the user exists
+    let local_user_view =
+      LocalUserView::find_by_email_or_name(&mut context.pool(), username_or_email)
+        .await
+        .ok();
+    if let Some(local_user_view) = local_user_view {
+      local_user_id = Some(local_user_view.local_user.id);
+      allow_credentials =
+        WebauthnCredential::list_for_user(&mut context.pool(), local_user_view.local_user.id)
+          .await?
+          .into_iter()
+          .map(|c| c.credential_id)
+          .collect();
+    }
+  }
+
+  Ok(Json(WebauthnLoginOptions {
+    // Bind the challenge to the user, so that it can only be used for their passkeys
+    challenge: create_challenge(local_user_id, &context).await?,
+    rp_id: rp_id(&context)?,
+    allow_credentials,
+  }))
+}
@@ -0,0 +1,388 @@
+//! Passkey support, which is verified directly without attestation. The public key is read from
+//! the authenticator data of the attestation object, which is signed with it when logging in.
+//! Only ES256 is supported, which all common authenticators use.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ciborium::Value;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::{
+    webauthn_challenge::{WebauthnChallenge, WebauthnChallengeInsertForm},
+    webauthn_credential::WebauthnCredential,
+  },
+};
+use lemmy_db_views_site::api::WebauthnAssertion;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub mod delete;
+pub mod list;
+pub mod login;
+pub mod login_start;
+pub mod register;
+pub mod register_start;
+
+/// COSE algorithm identifier
+const ES256: i32 = -7;
+
+/// COSE key parameters, and the values which are used for ES256 keys
+const COSE_KEY_TYPE: i32 = 1;
+const COSE_KEY_TYPE_EC2: i32 = 2;
+const COSE_ALGORITHM: i32 = 3;
+const COSE_CURVE: i32 = -1;
+const COSE_CURVE_P256: i32 = 1;
+const COSE_X: i32 = -2;
+const COSE_Y: i32 = -3;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+/// Length of rp id hash, flags and signature counter at the start of authenticator data
+const AUTHENTICATOR_DATA_MIN_LENGTH: usize = 37;
+const AAGUID_LENGTH: usize = 16;
+
+#[derive(Deserialize)]
+struct ClientData {
+  #[serde(rename = "type")]
+  type_: String,
+  challenge: String,
+  origin: String,
+}
+
+fn decode(data: &str) -> LemmyResult<Vec<u8>> {
+  URL_SAFE_NO_PAD
+    .decode(data.trim_end_matches('='))
+    .map_err(|_e| LemmyErrorType::InvalidWebauthnCredential.into())
+}
+
+pub(crate) fn encode(data: &[u8]) -> String {
+  URL_SAFE_NO_PAD.encode(data)
+}
+
+fn rp_id(context: &LemmyContext) -> LemmyResult<String> {
+  Ok(context.settings().get_hostname_without_port()?)
+}
+
+/// Generate a random challenge which needs to be signed by the authenticator.
+async fn create_challenge(
+  local_user_id: Option<LocalUserId>,
+  context: &LemmyContext,
+) -> LemmyResult<String> {
+  let random = [Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat();
+  let form = WebauthnChallengeInsertForm {
+    challenge: encode(&random),
+    local_user_id,
+  };
+  Ok(
+    WebauthnChallenge::create(&mut context.pool(), &form)
+      .await?
+      .challenge,
+  )
+}
+
+/// Check that the client data belongs to this site and a challenge that we issued.
+async fn verify_client_data(
+  client_data_json: &[u8],
+  expected_type: &str,
+  context: &LemmyContext,
+) -> LemmyResult<WebauthnChallenge> {
+  let client_data: ClientData = serde_json::from_slice(client_data_json)
+    .map_err(|_e| LemmyErrorType::InvalidWebauthnCredential)?;
+  if client_data.type_ != expected_type
+    || client_data.origin != context.settings().get_protocol_and_hostname()
+  {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+  WebauthnChallenge::read_and_delete(&mut context.pool(), &client_data.challenge).await
+}
+
+/// Validates the authenticator data, and returns its flags and signature counter.
+fn parse_authenticator_data(
+  authenticator_data: &[u8],
+  rp_id: &str,
+  require_user_verified: bool,
+) -> LemmyResult<(u8, u32)> {
+  let (rp_id_hash, rest) = authenticator_data
+    .split_first_chunk::<32>()
+    .ok_or(LemmyErrorType::InvalidWebauthnCredential)?;
+  let (flags, rest) = rest
+    .split_first()
+    .ok_or(LemmyErrorType::InvalidWebauthnCredential)?;
+  let (sign_count, _) = rest
+    .split_first_chunk::<4>()
+    .ok_or(LemmyErrorType::InvalidWebauthnCredential)?;
+
+  let user_verified = flags & FLAG_USER_VERIFIED != 0;
+  if *rp_id_hash != *Sha256::digest(rp_id.as_bytes())
+    || flags & FLAG_USER_PRESENT == 0
+    || (require_user_verified && !user_verified)
+  {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+  Ok((*flags, u32::from_be_bytes(*sign_count)))
+}
+
+/// Read the authenticator data from a CBOR encoded attestation object.
+fn attestation_authenticator_data(attestation_object: &[u8]) -> LemmyResult<Vec<u8>> {
+  let attestation: Value = ciborium::from_reader(attestation_object)
+    .map_err(|_e| LemmyErrorType::InvalidWebauthnCredential)?;
+  attestation
+    .as_map()
+    .into_iter()
+    .flatten()
+    .find(|(key, _)| key.as_text() == Some("authData"))
+    .and_then(|(_, value)| value.as_bytes())
+    .cloned()
+    .ok_or(LemmyErrorType::InvalidWebauthnCredential.into())
+}
+
+/// Extract the credential id and the SEC1 encoded public key from the authenticator data of a new
+/// registration.
+fn attested_credential(authenticator_data: &[u8]) -> LemmyResult<(&[u8], Vec<u8>)> {
+  let start = AUTHENTICATOR_DATA_MIN_LENGTH + AAGUID_LENGTH;
+  let flags = authenticator_data.get(32).copied().unwrap_or_default();
+  let length = authenticator_data
+    .get(start..)
+    .and_then(<[u8]>::first_chunk::<2>)
+    .filter(|_| flags & FLAG_ATTESTED_CREDENTIAL_DATA != 0)
+    .map(|l| usize::from(u16::from_be_bytes(*l)))
+    .ok_or(LemmyErrorType::InvalidWebauthnCredential)?;
+  let (credential_id, cose_key) = authenticator_data
+    .get(start + 2..)
+    .and_then(|rest| rest.split_at_checked(length))
+    .ok_or(LemmyErrorType::InvalidWebauthnCredential)?;
+  Ok((credential_id, cose_key_to_sec1(cose_key)?))
+}
+
+/// Convert a COSE encoded ES256 public key to SEC1 format. Extensions which may follow the key are
+/// ignored.
+fn cose_key_to_sec1(cose_key: &[u8]) -> LemmyResult<Vec<u8>> {
+  let key: Value =
+    ciborium::from_reader(cose_key).map_err(|_e| LemmyErrorType::InvalidWebauthnCredential)?;
+  let param = |label: i32| {
+    key
+      .as_map()
+      .into_iter()
+      .flatten()
+      .find(|(k, _)| k.as_integer() == Some(label.into()))
+      .map(|(_, v)| v)
+  };
+  let integer = |label| param(label).and_then(Value::as_integer);
+  let coordinate = |label| {
+    param(label)
+      .and_then(Value::as_bytes)
+      .filter(|c| c.len() == 32)
+  };
+
+  if integer(COSE_KEY_TYPE) != Some(COSE_KEY_TYPE_EC2.into())
+    || integer(COSE_ALGORITHM) != Some(ES256.into())
+    || integer(COSE_CURVE) != Some(COSE_CURVE_P256.into())
+  {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+  let (Some(x), Some(y)) = (coordinate(COSE_X), coordinate(COSE_Y)) else {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  };
+  let public_key = [[0x04].as_slice(), x, y].concat();
+
+  // Make sure that the key can be used later
+  p256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key)
+    .map_err(|_e| LemmyErrorType::InvalidWebauthnCredential)?;
+  Ok(public_key)
+}
+
+fn verify_signature(
+  algorithm: i32,
+  public_key: &[u8],
+  message: &[u8],
+  signature: &[u8],
+) -> LemmyResult<()> {
+  use p256::ecdsa::{Signature, VerifyingKey, signature::Verifier};
+
+  let key = VerifyingKey::from_sec1_bytes(public_key);
+  let signature = Signature::from_der(signature);
+  let valid = algorithm == ES256
+    && key
+      .ok()
+      .zip(signature.ok())
+      .is_some_and(|(key, signature)| key.verify(message, &signature).is_ok());
+  if valid {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::InvalidWebauthnCredential.into())
+  }
+}
+
+/// Verify a signed login challenge, and return the passkey which was used. If `local_user_id` is
+/// given, the passkey must belong to that user.
+pub(crate) async fn verify_webauthn_assertion(
+  assertion: &WebauthnAssertion,
+  local_user_id: Option<LocalUserId>,
+  require_user_verified: bool,
+  context: &LemmyContext,
+) -> LemmyResult<WebauthnCredential> {
+  let credential =
+    WebauthnCredential::read_from_credential_id(&mut context.pool(), &assertion.credential_id)
+      .await?;
+  if local_user_id.is_some_and(|id| id != credential.local_user_id) {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+
+  let client_data_json = decode(&assertion.client_data_json)?;
+  let challenge = verify_client_data(&client_data_json, "webauthn.get", context).await?;
+  let challenge_valid = match local_user_id {
+    // As second factor, the login must have been started for this user
+    Some(_) => challenge.local_user_id == Some(credential.local_user_id),
+    None => challenge
+      .local_user_id
+      .is_none_or(|id| id == credential.local_user_id),
+  };
+  if !challenge_valid {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+  let authenticator_data = decode(&assertion.authenticator_data)?;
+  let (_, sign_count) =
+    parse_authenticator_data(&authenticator_data, &rp_id(context)?, require_user_verified)?;
+
+  let message = [
+    authenticator_data.as_slice(),
+    &Sha256::digest(&client_data_json),
+  ]
+  .concat();
+  verify_signature(
+    credential.algorithm,
+    &credential.public_key,
+    &message,
+    &decode(&assertion.signature)?,
+  )?;
+
+  // Authenticators which support it increase the counter on each use, so a lower value means it
+  // was cloned.
+  let sign_count = i64::from(sign_count);
+  if (sign_count > 0 || credential.sign_count > 0) && sign_count <= credential.sign_count {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+  WebauthnCredential::mark_used(&mut context.pool(), credential.id, sign_count).await?;
+
+  Ok(credential)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use p256::ecdsa::{Signature, SigningKey, signature::Signer};
+  use pretty_assertions::assert_eq;
+
+  fn authenticator_data(rp_id: &str, flags: u8, sign_count: u32) -> Vec<u8> {
+    [
+      Sha256::digest(rp_id.as_bytes()).as_slice(),
+      &[flags],
+      &sign_count.to_be_bytes(),
+    ]
+    .concat()
+  }
+
+  fn signing_key() -> LemmyResult<SigningKey> {
+    Ok(SigningKey::from_slice(&[7; 32])?)
+  }
+
+  fn cose_key(signing_key: &SigningKey, algorithm: i32) -> LemmyResult<Vec<u8>> {
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    let (Some(x), Some(y)) = (point.x(), point.y()) else {
+      return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+    };
+    let key = Value::Map(vec![
+      (COSE_KEY_TYPE.into(), COSE_KEY_TYPE_EC2.into()),
+      (COSE_ALGORITHM.into(), algorithm.into()),
+      (COSE_CURVE.into(), COSE_CURVE_P256.into()),
+      (COSE_X.into(), Value::Bytes(x.to_vec())),
+      (COSE_Y.into(), Value::Bytes(y.to_vec())),
+    ]);
+    let mut bytes = vec![];
+    ciborium::into_writer(&key, &mut bytes)?;
+    Ok(bytes)
+  }
+
+  #[test]
+  fn test_parse_authenticator_data() -> LemmyResult<()> {
+    let data = authenticator_data("example.com", FLAG_USER_PRESENT, 5);
+    assert_eq!(
+      (FLAG_USER_PRESENT, 5),
+      parse_authenticator_data(&data, "example.com", false)?
+    );
+    // Wrong site
+    assert!(parse_authenticator_data(&data, "example.org", false).is_err());
+    // User not verified
+    assert!(parse_authenticator_data(&data, "example.com", true).is_err());
+    // User not present
+    let data = authenticator_data("example.com", FLAG_USER_VERIFIED, 5);
+    assert!(parse_authenticator_data(&data, "example.com", false).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_attested_credential() -> LemmyResult<()> {
+    let signing_key = signing_key()?;
+    let data = |flags, algorithm| -> LemmyResult<Vec<u8>> {
+      let mut data = authenticator_data("example.com", flags, 0);
+      data.extend([0; AAGUID_LENGTH]);
+      data.extend(3u16.to_be_bytes());
+      data.extend([1, 2, 3]);
+      data.extend(cose_key(&signing_key, algorithm)?);
+      Ok(data)
+    };
+    let attested = data(FLAG_USER_PRESENT | FLAG_ATTESTED_CREDENTIAL_DATA, ES256)?;
+    let (credential_id, public_key) = attested_credential(&attested)?;
+    assert_eq!(&[1, 2, 3], credential_id);
+    assert_eq!(
+      signing_key
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes(),
+      public_key
+    );
+
+    let not_attested = data(FLAG_USER_PRESENT, ES256)?;
+    assert!(attested_credential(&not_attested).is_err());
+
+    // RS256 isn't supported
+    let rsa = data(FLAG_USER_PRESENT | FLAG_ATTESTED_CREDENTIAL_DATA, -257)?;
+    assert!(attested_credential(&rsa).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_attestation_authenticator_data() -> LemmyResult<()> {
+    let attestation = Value::Map(vec![
+      ("fmt".into(), "none".into()),
+      ("attStmt".into(), Value::Map(vec![])),
+      ("authData".into(), Value::Bytes(vec![1, 2, 3])),
+    ]);
+    let mut bytes = vec![];
+    ciborium::into_writer(&attestation, &mut bytes)?;
+    assert_eq!(vec![1, 2, 3], attestation_authenticator_data(&bytes)?);
+
+    assert!(attestation_authenticator_data(&[1, 2, 3]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_verify_es256_signature() -> LemmyResult<()> {
+    let signing_key = signing_key()?;
+    let public_key = signing_key.verifying_key().to_encoded_point(false);
+    let public_key = public_key.as_bytes();
+
+    let message = b"authenticator data and client data hash";
+    let signature: Signature = signing_key.sign(message);
+    let signature = signature.to_der();
+    verify_signature(ES256, public_key, message, signature.as_bytes())?;
+
+    assert!(verify_signature(ES256, public_key, b"other", signature.as_bytes()).is_err());
+    assert!(verify_signature(-257, public_key, message, signature.as_bytes()).is_err());
+    Ok(())
+  }
+}
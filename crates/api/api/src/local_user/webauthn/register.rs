@@ -0,0 +1,61 @@
+use super::{
+  ES256,
+  attestation_authenticator_data,
+  attested_credential,
+  decode,
+  parse_authenticator_data,
+  rp_id,
+  verify_client_data,
+};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
+use lemmy_db_schema::source::webauthn_credential::{
+  WebauthnCredential,
+  WebauthnCredentialInsertForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{RegisterWebauthnCredential, WebauthnCredentialResponse};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_display_name,
+};
+
+pub async fn register_webauthn_credential(
+  Json(data): Json<RegisterWebauthnCredential>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebauthnCredentialResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  is_valid_display_name(&data.name)?;
+
+  let challenge = verify_client_data(
+    &decode(&data.client_data_json)?,
+    "webauthn.create",
+    &context,
+  )
+  .await?;
+  if challenge.local_user_id != Some(local_user_view.local_user.id) {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+
+  let authenticator_data = attestation_authenticator_data(&decode(&data.attestation_object)?)?;
+  let (_, sign_count) = parse_authenticator_data(&authenticator_data, &rp_id(&context)?, false)?;
+  let (credential_id, public_key) = attested_credential(&authenticator_data)?;
+  if credential_id != decode(&data.credential_id)? {
+    return Err(LemmyErrorType::InvalidWebauthnCredential.into());
+  }
+
+  let form = WebauthnCredentialInsertForm::new(
+    local_user_view.local_user.id,
+    data.name,
+    data.credential_id,
+    public_key,
+    ES256,
+    sign_count.into(),
+  );
+  let webauthn_credential = WebauthnCredential::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(WebauthnCredentialResponse {
+    webauthn_credential,
+  }))
+}
@@ -0,0 +1,30 @@
+use super::{create_challenge, encode, rp_id};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webauthn_credential::WebauthnCredential;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{SiteView, api::WebauthnRegistrationOptions};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn start_webauthn_registration(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebauthnRegistrationOptions>> {
+  let local_user_id = local_user_view.local_user.id;
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let challenge = create_challenge(Some(local_user_id), &context).await?;
+  let exclude_credentials = WebauthnCredential::list_for_user(&mut context.pool(), local_user_id)
+    .await?
+    .into_iter()
+    .map(|c| c.credential_id)
+    .collect();
+
+  Ok(Json(WebauthnRegistrationOptions {
+    challenge,
+    rp_id: rp_id(&context)?,
+    rp_name: site_view.site.name,
+    user_id: encode(&local_user_id.0.to_be_bytes()),
+    user_name: local_user_view.person.name,
+    exclude_credentials,
+  }))
+}
@@ -5,6 +5,7 @@ use lemmy_api_utils::{
   context::LemmyContext,
   plugins::{plugin_hook_after, plugin_hook_before},
   send_activity::{ActivityChannel, SendActivityData},
+  stream::StreamChannel,
   utils::{
     check_bot_account,
     check_community_user_action,
@@ -29,6 +30,7 @@ use lemmy_db_views_post::{
 use lemmy_db_views_site::SiteView;
 use lemmy_utils::error::LemmyResult;
 use std::ops::Deref;
+use tracing::warn;
 
 pub async fn like_post(
   Json(data): Json<CreatePostLike>,
@@ -77,6 +79,10 @@ pub async fn like_post(
   .await?;
 
   plugin_hook_after("post_after_vote", &like);
+  StreamChannel::post_vote(post_id, &context)
+    .await
+    .inspect_err(|e| warn!("Failed to stream post vote: {e}"))
+    .ok();
 
   // Mark Post Read
   PostActions::mark_as_read(&mut context.pool(), my_person_id, &[post_id]).await?;
@@ -0,0 +1,21 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_schema::source::hashtag::Hashtag;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::{ListHashtags, ListHashtagsResponse};
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_hashtags(
+  Query(data): Query<ListHashtags>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<ListHashtagsResponse>> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let hashtags =
+    Hashtag::list(&mut context.pool(), data.search_term.as_deref(), data.limit).await?;
+
+  Ok(Json(ListHashtagsResponse { hashtags }))
+}
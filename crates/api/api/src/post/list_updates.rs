@@ -0,0 +1,31 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{PostUpdateView, PostView, api::GetPostUpdates};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_post_updates(
+  Query(data): Query<GetPostUpdates>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<PagedResponse<PostUpdateView>>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let updates = PostView::list_updates(
+    &mut context.pool(),
+    data.since,
+    data.community_id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    site.instance_id,
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+  Ok(Json(updates))
+}
@@ -2,11 +2,15 @@ pub mod feature;
 pub mod get_link_metadata;
 pub mod hide;
 pub mod like;
+pub mod list_hashtags;
 pub mod list_post_likes;
+pub mod list_updates;
 pub mod lock;
 pub mod mark_many_read;
 pub mod mark_read;
 pub mod mod_update;
 pub mod save;
+pub mod schedule_feature;
+pub mod similar;
 pub mod update_notifications;
 pub mod warning;
@@ -0,0 +1,77 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::{DateTime, TimeZone, Utc};
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_db_schema::source::{
+  community::Community,
+  post::Post,
+  post_feature_schedule::{PostFeatureSchedule, PostFeatureScheduleForm},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::{
+  ListPostFeatureSchedules,
+  ListPostFeatureSchedulesResponse,
+  PostFeatureScheduleResponse,
+  SchedulePostFeature,
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn schedule_post_feature(
+  Json(data): Json<SchedulePostFeature>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostFeatureScheduleResponse>> {
+  let post = Post::read(&mut context.pool(), data.post_id).await?;
+  let community = Community::read(&mut context.pool(), post.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let feature_at = convert_time(data.feature_at)?;
+  let unfeature_at = convert_time(data.unfeature_at)?;
+  if feature_at.is_none() && unfeature_at.is_none() {
+    PostFeatureSchedule::delete(&mut context.pool(), post.id).await?;
+    return Ok(Json(PostFeatureScheduleResponse { schedule: None }));
+  }
+  if let Some(unfeature_at) = unfeature_at
+    && (unfeature_at < Utc::now() || feature_at.is_some_and(|f| f >= unfeature_at))
+  {
+    return Err(LemmyErrorType::PostScheduleTimeMustBeInFuture.into());
+  }
+
+  let form = PostFeatureScheduleForm::new(
+    post.id,
+    local_user_view.person.id,
+    feature_at,
+    unfeature_at,
+    data.rotation_order,
+  );
+  let schedule = PostFeatureSchedule::upsert(&mut context.pool(), &form).await?;
+
+  Ok(Json(PostFeatureScheduleResponse {
+    schedule: Some(schedule),
+  }))
+}
+
+pub async fn list_post_feature_schedules(
+  Query(data): Query<ListPostFeatureSchedules>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListPostFeatureSchedulesResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let schedules =
+    PostFeatureSchedule::list_for_community(&mut context.pool(), community.id).await?;
+
+  Ok(Json(ListPostFeatureSchedulesResponse { schedules }))
+}
+
+fn convert_time(unix: Option<i64>) -> LemmyResult<Option<DateTime<Utc>>> {
+  let time = unix.map(|t| {
+    Utc
+      .timestamp_opt(t, 0)
+      .single()
+      .ok_or(LemmyErrorType::InvalidUnixTime)
+  });
+  Ok(time.transpose()?)
+}
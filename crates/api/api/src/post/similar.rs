@@ -0,0 +1,73 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
+use lemmy_db_schema::source::community::Community;
+use lemmy_db_schema_file::enums::{DuplicateUrlPolicy, ListingType, PostSortType};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{
+  api::{GetSimilarPosts, GetSimilarPostsResponse},
+  impls::PostQuery,
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+/// Only a few suggestions are shown while typing
+const SIMILAR_POSTS_LIMIT: i64 = 10;
+
+pub async fn get_similar_posts(
+  Query(data): Query<GetSimilarPosts>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetSimilarPostsResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+
+  let title = data.q.trim();
+  let posts = if title.is_empty() {
+    vec![]
+  } else {
+    PostQuery {
+      similar_title: Some(title.to_string()),
+      community_id: data.community_id,
+      local_user,
+      listing_type: Some(ListingType::All),
+      sort: Some(PostSortType::New),
+      limit: Some(SIMILAR_POSTS_LIMIT),
+      ..Default::default()
+    }
+    .list(&mut context.pool(), &site, &local_site)
+    .await?
+    .items
+  };
+
+  let duplicate_url_posts = match (data.community_id, data.url) {
+    (Some(community_id), Some(url)) => {
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      if community.duplicate_url_policy == DuplicateUrlPolicy::Allow {
+        vec![]
+      } else {
+        PostQuery {
+          search_term: Some(url),
+          search_url_only: Some(true),
+          community_id: Some(community_id),
+          published_after: Some(community.duplicate_url_since()),
+          local_user,
+          sort: Some(PostSortType::New),
+          ..Default::default()
+        }
+        .list(&mut context.pool(), &site, &local_site)
+        .await?
+        .items
+      }
+    }
+    _ => vec![],
+  };
+
+  Ok(Json(GetSimilarPostsResponse {
+    posts,
+    duplicate_url_posts,
+  }))
+}
@@ -5,6 +5,7 @@ use either::Either;
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::plugin_hook_after,
+  push::send_new_report_push,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_comment_deleted_or_removed,
@@ -12,11 +13,13 @@ use lemmy_api_utils::{
     check_local_user_valid,
     slur_regex,
   },
+  webhook::send_webhook_event,
 };
 use lemmy_db_schema::{
   source::comment_report::{CommentReport, CommentReportForm},
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEvent;
 use lemmy_db_views_comment::CommentView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
@@ -72,8 +75,9 @@ pub async fn create_comment_report(
   let comment_report_view =
     ReportCombinedViewInternal::read_comment_report(&mut context.pool(), report.id, person).await?;
   plugin_hook_after("comment_report_after_create", &comment_report_view);
+  send_webhook_event(WebhookEvent::ReportCreated, &comment_report_view, &context);
 
-  // Email the admins
+  // Notify the admins and the mods of the community
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
   if local_site.reports_email_admins {
     send_new_report_email_to_admins(
@@ -84,6 +88,13 @@ pub async fn create_comment_report(
     )
     .await?;
   }
+  send_new_report_push(
+    &comment_report_view.creator.name,
+    &comment_report_view.comment_creator.name,
+    (!report.violates_instance_rules).then_some(comment_view.community.id),
+    &context,
+  )
+  .await?;
 
   if !report.violates_instance_rules {
     ActivityChannel::submit_activity(
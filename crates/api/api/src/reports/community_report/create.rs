@@ -5,8 +5,10 @@ use either::Either;
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::plugin_hook_after,
+  push::send_new_report_push,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_local_user_valid, slur_regex},
+  webhook::send_webhook_event,
 };
 use lemmy_db_schema::{
   source::{
@@ -16,6 +18,7 @@ use lemmy_db_schema::{
   },
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEvent;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
   ReportCombinedViewInternal,
@@ -59,8 +62,13 @@ pub async fn create_community_report(
     ReportCombinedViewInternal::read_community_report(&mut context.pool(), report.id, person)
       .await?;
   plugin_hook_after("community_report_after_create", &community_report_view);
+  send_webhook_event(
+    WebhookEvent::ReportCreated,
+    &community_report_view,
+    &context,
+  );
 
-  // Email the admins
+  // Notify the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
   if local_site.reports_email_admins {
     send_new_report_email_to_admins(
@@ -74,6 +82,13 @@ pub async fn create_community_report(
     )
     .await?;
   }
+  send_new_report_push(
+    &community_report_view.creator.name,
+    &community_report_view.community.name,
+    None,
+    &context,
+  )
+  .await?;
 
   ActivityChannel::submit_activity(
     SendActivityData::CreateReport {
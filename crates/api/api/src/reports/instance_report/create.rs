@@ -0,0 +1,81 @@
+use crate::check_report_reason;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  plugins::plugin_hook_after,
+  push::send_new_report_push,
+  utils::{check_local_user_valid, slur_regex},
+  webhook::send_webhook_event,
+};
+use lemmy_db_schema::{
+  source::{
+    instance::Instance,
+    instance_report::{InstanceReport, InstanceReportForm},
+  },
+  traits::Reportable,
+};
+use lemmy_db_schema_file::enums::WebhookEvent;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_report_combined::{
+  ReportCombinedViewInternal,
+  api::{CreateInstanceReport, InstanceReportResponse},
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_email::admin::send_new_report_email_to_admins;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn create_instance_report(
+  Json(data): Json<CreateInstanceReport>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<InstanceReportResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let reason = data.reason.trim().to_string();
+  let slur_regex = slur_regex(&context).await?;
+  check_report_reason(&reason, &slur_regex)?;
+
+  let person = &local_user_view.person;
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+
+  let report_form = InstanceReportForm {
+    creator_id: person.id,
+    instance_id: instance.id,
+    original_instance_domain: instance.domain,
+    reason,
+  };
+
+  let report = InstanceReport::report(&mut context.pool(), &report_form).await?;
+
+  let instance_report_view =
+    ReportCombinedViewInternal::read_instance_report(&mut context.pool(), report.id, person)
+      .await?;
+  plugin_hook_after("instance_report_after_create", &instance_report_view);
+  send_webhook_event(WebhookEvent::ReportCreated, &instance_report_view, &context);
+
+  // Notify the admins
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  if local_site.reports_email_admins {
+    send_new_report_email_to_admins(
+      &instance_report_view.creator.name,
+      // Like for community reports there is no single person responsible, so use the domain
+      &instance_report_view.instance.domain,
+      &mut context.pool(),
+      context.settings(),
+    )
+    .await?;
+  }
+  send_new_report_push(
+    &instance_report_view.creator.name,
+    &instance_report_view.instance.domain,
+    None,
+    &context,
+  )
+  .await?;
+
+  // Instance reports are only meant for local admins, so they are not federated
+
+  Ok(Json(InstanceReportResponse {
+    instance_report_view,
+  }))
+}
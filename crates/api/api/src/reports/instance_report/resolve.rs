@@ -0,0 +1,30 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::{source::instance_report::InstanceReport, traits::Reportable};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_report_combined::{
+  ReportCombinedViewInternal,
+  api::{InstanceReportResponse, ResolveInstanceReport},
+};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn resolve_instance_report(
+  Json(data): Json<ResolveInstanceReport>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<InstanceReportResponse>> {
+  is_admin(&local_user_view)?;
+
+  let report_id = data.report_id;
+  let person = &local_user_view.person;
+  InstanceReport::update_resolved(&mut context.pool(), report_id, person.id, data.resolved).await?;
+
+  let instance_report_view =
+    ReportCombinedViewInternal::read_instance_report(&mut context.pool(), report_id, person)
+      .await?;
+
+  Ok(Json(InstanceReportResponse {
+    instance_report_view,
+  }))
+}
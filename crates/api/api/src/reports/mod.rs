@@ -1,5 +1,6 @@
 pub mod comment_report;
 pub mod community_report;
+pub mod instance_report;
 pub mod post_report;
 pub mod private_message_report;
 pub mod report_combined;
@@ -5,6 +5,7 @@ use either::Either;
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::plugin_hook_after,
+  push::send_new_report_push,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_community_user_action,
@@ -12,11 +13,13 @@ use lemmy_api_utils::{
     check_post_deleted_or_removed,
     slur_regex,
   },
+  webhook::send_webhook_event,
 };
 use lemmy_db_schema::{
   source::post_report::{PostReport, PostReportForm},
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEvent;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_report_combined::{
@@ -69,8 +72,9 @@ pub async fn create_post_report(
   let post_report_view =
     ReportCombinedViewInternal::read_post_report(&mut context.pool(), report.id, person).await?;
   plugin_hook_after("post_report_after_create", &post_report_view);
+  send_webhook_event(WebhookEvent::ReportCreated, &post_report_view, &context);
 
-  // Email the admins
+  // Notify the admins and the mods of the community
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
   if local_site.reports_email_admins {
     send_new_report_email_to_admins(
@@ -81,6 +85,13 @@ pub async fn create_post_report(
     )
     .await?;
   }
+  send_new_report_push(
+    &post_report_view.creator.name,
+    &post_report_view.post_creator.name,
+    (!report.violates_instance_rules).then_some(orig_post.community.id),
+    &context,
+  )
+  .await?;
 
   if !report.violates_instance_rules {
     ActivityChannel::submit_activity(
@@ -3,7 +3,9 @@ use actix_web::web::{Data, Json};
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::plugin_hook_after,
+  push::send_new_report_push,
   utils::{check_local_user_valid, slur_regex},
+  webhook::send_webhook_event,
 };
 use lemmy_db_schema::{
   source::{
@@ -12,6 +14,7 @@ use lemmy_db_schema::{
   },
   traits::Reportable,
 };
+use lemmy_db_schema_file::enums::WebhookEvent;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
   ReportCombinedViewInternal,
@@ -57,8 +60,13 @@ pub async fn create_pm_report(
     "private_message_report_after_create",
     &private_message_report_view,
   );
+  send_webhook_event(
+    WebhookEvent::ReportCreated,
+    &private_message_report_view,
+    &context,
+  );
 
-  // Email the admins
+  // Notify the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
   if local_site.reports_email_admins {
     send_new_report_email_to_admins(
@@ -69,6 +77,13 @@ pub async fn create_pm_report(
     )
     .await?;
   }
+  send_new_report_push(
+    &private_message_report_view.creator.name,
+    &private_message_report_view.private_message_creator.name,
+    None,
+    &context,
+  )
+  .await?;
 
   // TODO: consider federating this
 
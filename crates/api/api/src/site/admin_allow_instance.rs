@@ -1,6 +1,6 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
-use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_api_utils::{cache::invalidate_instances, context::LemmyContext, utils::is_admin};
 use lemmy_db_schema::source::{
   federation_allowlist::{FederationAllowList, FederationAllowListForm},
   instance::Instance,
@@ -40,6 +40,8 @@ pub async fn admin_allow_instance(
   );
   Modlog::create(&mut context.pool(), &[form]).await?;
 
+  invalidate_instances();
+
   Ok(Json(
     FederatedInstanceView::read(&mut context.pool(), instance_id).await?,
   ))
@@ -1,6 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
+  cache::invalidate_instances,
   context::LemmyContext,
   utils::{check_expire_time, is_admin},
 };
@@ -31,7 +32,11 @@ pub async fn admin_block_instance(
     .await?
     .id;
 
-  let form = FederationBlockListForm::new(instance_id, expires_at);
+  let form = FederationBlockListForm {
+    severity: data.severity.unwrap_or_default(),
+    reason: Some(data.reason.clone()),
+    ..FederationBlockListForm::new(instance_id, expires_at)
+  };
 
   if data.block {
     FederationBlockList::block(&mut context.pool(), &form).await?;
@@ -47,6 +52,8 @@ pub async fn admin_block_instance(
   );
   Modlog::create(&mut context.pool(), &[form]).await?;
 
+  invalidate_instances();
+
   Ok(Json(
     FederatedInstanceView::read(&mut context.pool(), instance_id).await?,
   ))
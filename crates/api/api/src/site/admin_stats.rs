@@ -0,0 +1,26 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::site_stats::SiteStats;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{GetAdminStats, GetAdminStatsResponse};
+use lemmy_utils::error::LemmyResult;
+
+const DEFAULT_STATS_DAYS: i64 = 30;
+const MAX_STATS_DAYS: i64 = 365;
+
+pub async fn get_admin_stats(
+  Query(data): Query<GetAdminStats>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetAdminStatsResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let days = data
+    .days
+    .unwrap_or(DEFAULT_STATS_DAYS)
+    .clamp(1, MAX_STATS_DAYS);
+  let stats = SiteStats::list(&mut context.pool(), days).await?;
+
+  Ok(Json(GetAdminStatsResponse { stats }))
+}
@@ -0,0 +1,33 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ClearClientPenalties, ClearClientPenaltiesResponse};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use std::net::IpAddr;
+
+pub async fn clear_client_penalties(
+  Json(data): Json<ClearClientPenalties>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ClearClientPenaltiesResponse>> {
+  is_admin(&local_user_view)?;
+
+  // Also accepts the IPv6 subnets which are listed, like `2001:db8:0:1::/64`
+  let ip = data
+    .ip
+    .as_deref()
+    .map(|ip| {
+      let addr = ip.split('/').next().unwrap_or(ip).trim();
+      addr
+        .parse::<IpAddr>()
+        .with_lemmy_type(LemmyErrorType::InvalidIpRange)
+        .map(|_| addr)
+    })
+    .transpose()?;
+  let cleared = context.rate_limit_cell().clear_penalties(ip);
+
+  Ok(Json(ClearClientPenaltiesResponse {
+    cleared: i64::try_from(cleared).unwrap_or(i64::MAX),
+  }))
+}
@@ -0,0 +1,59 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{cache::invalidate_instances, context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::{instance::Instance, instance_move::InstanceMove};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ListInstanceMovesResponse, ResolveInstanceMove};
+use lemmy_utils::{error::LemmyResult, spawn_try_task};
+use tracing::info;
+
+pub async fn list_instance_moves(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListInstanceMovesResponse>> {
+  is_admin(&local_user_view)?;
+
+  let instance_moves = InstanceMove::list_pending(&mut context.pool()).await?;
+
+  Ok(Json(ListInstanceMovesResponse { instance_moves }))
+}
+
+pub async fn resolve_instance_move(
+  Json(data): Json<ResolveInstanceMove>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<InstanceMove>> {
+  is_admin(&local_user_view)?;
+
+  let instance_move = InstanceMove::resolve(
+    &mut context.pool(),
+    data.instance_move_id,
+    data.approve,
+    local_user_view.person.id,
+  )
+  .await?;
+
+  if data.approve {
+    info!(
+      "Moving instance {} to {}",
+      instance_move.old_domain, instance_move.new_domain
+    );
+    // Replacing the urls takes a while for large instances, so it runs in the background
+    let context = context.clone();
+    let instance_move = instance_move.clone();
+    spawn_try_task(async move {
+      Instance::change_remote_domain(
+        &mut context.pool(),
+        instance_move.instance_id,
+        &instance_move.old_origin,
+        &instance_move.new_origin,
+        &instance_move.new_domain,
+      )
+      .await?;
+      invalidate_instances();
+      Ok(())
+    });
+  }
+
+  Ok(Json(instance_move))
+}
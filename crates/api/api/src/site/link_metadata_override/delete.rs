@@ -0,0 +1,22 @@
+use super::parse_override_domain;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::link_metadata_override::LinkMetadataOverride;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::DeleteLinkMetadataOverride;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_link_metadata_override(
+  Json(data): Json<DeleteLinkMetadataOverride>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  is_admin(&local_user_view)?;
+
+  let domain = parse_override_domain(&data.domain)?;
+  LinkMetadataOverride::delete(&mut context.pool(), &domain).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
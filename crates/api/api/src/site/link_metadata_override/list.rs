@@ -0,0 +1,20 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::link_metadata_override::LinkMetadataOverride;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::ListLinkMetadataOverridesResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_link_metadata_overrides(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListLinkMetadataOverridesResponse>> {
+  is_admin(&local_user_view)?;
+
+  let link_metadata_overrides = LinkMetadataOverride::list(&mut context.pool()).await?;
+
+  Ok(Json(ListLinkMetadataOverridesResponse {
+    link_metadata_overrides,
+  }))
+}
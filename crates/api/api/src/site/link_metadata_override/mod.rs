@@ -0,0 +1,32 @@
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub mod delete;
+pub mod list;
+pub mod set;
+
+/// Normalizes the domain of an override, which must be a plain hostname without scheme or path.
+fn parse_override_domain(domain: &str) -> LemmyResult<String> {
+  let domain = domain.trim().to_lowercase();
+  let url = Url::parse(&format!("https://{domain}"))?;
+  if url.domain() != Some(domain.as_str()) || url.path() != "/" {
+    return Err(LemmyErrorType::InvalidUrl.into());
+  }
+  Ok(domain)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::parse_override_domain;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_override_domain() -> LemmyResult<()> {
+    assert_eq!("youtube.com", parse_override_domain(" YouTube.com ")?);
+    assert!(parse_override_domain("https://youtube.com").is_err());
+    assert!(parse_override_domain("youtube.com/watch").is_err());
+    assert!(parse_override_domain("127.0.0.1").is_err());
+    Ok(())
+  }
+}
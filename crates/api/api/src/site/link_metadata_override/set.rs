@@ -0,0 +1,40 @@
+use super::parse_override_domain;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::link_metadata_override::{
+  LinkMetadataOverride,
+  LinkMetadataOverrideForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::{LinkMetadataOverrideResponse, SetLinkMetadataOverride};
+use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_url};
+use url::Url;
+
+pub async fn set_link_metadata_override(
+  Json(data): Json<SetLinkMetadataOverride>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<LinkMetadataOverrideResponse>> {
+  is_admin(&local_user_view)?;
+
+  let oembed_endpoint = match &data.oembed_endpoint {
+    Some(endpoint) => {
+      let endpoint = Url::parse(endpoint)?;
+      is_valid_url(&endpoint)?;
+      Some(endpoint.into())
+    }
+    None => None,
+  };
+  let form = LinkMetadataOverrideForm {
+    domain: parse_override_domain(&data.domain)?,
+    oembed_endpoint,
+    disable_metadata: data.disable_metadata.unwrap_or_default(),
+    disable_embed: data.disable_embed.unwrap_or_default(),
+  };
+  let link_metadata_override = LinkMetadataOverride::upsert(&mut context.pool(), &form).await?;
+
+  Ok(Json(LinkMetadataOverrideResponse {
+    link_metadata_override,
+  }))
+}
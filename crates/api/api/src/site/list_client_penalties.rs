@@ -0,0 +1,36 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ClientPenalty, ListClientPenaltiesResponse};
+use lemmy_utils::error::LemmyResult;
+use std::cmp::Reverse;
+
+/// Maximum number of clients which are returned.
+const CLIENT_PENALTIES_LIMIT: usize = 100;
+
+pub async fn list_client_penalties(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListClientPenaltiesResponse>> {
+  is_admin(&local_user_view)?;
+
+  let count = |it: u64| i64::try_from(it).unwrap_or(i64::MAX);
+  let mut clients: Vec<_> = context
+    .rate_limit_cell()
+    .penalty_stats()
+    .into_iter()
+    .map(|s| ClientPenalty {
+      ip: s.ip,
+      strikes: s.strikes.into(),
+      blocked_until: s.blocked_until,
+      rate_limited: count(s.rate_limited),
+      auth_failures: count(s.auth_failures),
+    })
+    .collect();
+
+  clients.sort_by_key(|c| (Reverse(c.blocked_until), Reverse(c.strikes)));
+  clients.truncate(CLIENT_PENALTIES_LIMIT);
+
+  Ok(Json(ListClientPenaltiesResponse { clients }))
+}
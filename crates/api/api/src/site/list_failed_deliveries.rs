@@ -0,0 +1,40 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::sent_activity_delivery::SentActivityDelivery;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{
+  FailedDelivery,
+  ListFailedDeliveries,
+  ListFailedDeliveriesResponse,
+};
+use lemmy_utils::error::LemmyResult;
+
+/// Maximum number of failed deliveries which are returned.
+const FAILED_DELIVERIES_LIMIT: i64 = 100;
+
+pub async fn list_failed_deliveries(
+  Query(data): Query<ListFailedDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFailedDeliveriesResponse>> {
+  is_admin(&local_user_view)?;
+
+  let deliveries = SentActivityDelivery::list_failed(
+    &mut context.pool(),
+    data.instance_id,
+    FAILED_DELIVERIES_LIMIT,
+  )
+  .await?
+  .into_iter()
+  .map(
+    |(delivery, activity_ap_id, activity_published_at)| FailedDelivery {
+      delivery,
+      activity_ap_id,
+      activity_published_at,
+    },
+  )
+  .collect();
+
+  Ok(Json(ListFailedDeliveriesResponse { deliveries }))
+}
@@ -0,0 +1,35 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{FederationInboxInstance, ListFederationInboxResponse};
+use lemmy_utils::error::LemmyResult;
+use std::cmp::Reverse;
+
+/// Maximum number of instances which are returned.
+const FEDERATION_INBOX_LIMIT: usize = 100;
+
+pub async fn list_federation_inbox(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFederationInboxResponse>> {
+  is_admin(&local_user_view)?;
+
+  let count = |it: u64| i64::try_from(it).unwrap_or(i64::MAX);
+  let mut instances: Vec<_> = context
+    .rate_limit_cell()
+    .inbox_stats()
+    .into_iter()
+    .map(|s| FederationInboxInstance {
+      domain: s.domain,
+      received: count(s.received),
+      rejected: count(s.rejected),
+      throttled_until: s.throttled_until,
+    })
+    .collect();
+
+  instances.sort_by_key(|i| (Reverse(i.rejected), Reverse(i.received)));
+  instances.truncate(FEDERATION_INBOX_LIMIT);
+
+  Ok(Json(ListFederationInboxResponse { instances }))
+}
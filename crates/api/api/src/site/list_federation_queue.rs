@@ -0,0 +1,96 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::{
+  newtypes::ActivityId,
+  source::{
+    activity::SentActivity,
+    federation_queue_state::FederationQueueState,
+    instance::Instance,
+  },
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{FederationQueueInstance, ListFederationQueueResponse};
+use lemmy_utils::error::LemmyResult;
+use std::{cmp::Reverse, collections::HashMap};
+
+/// Maximum number of instances which are returned.
+const FEDERATION_QUEUE_LIMIT: usize = 100;
+
+pub async fn list_federation_queue(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFederationQueueResponse>> {
+  is_admin(&local_user_view)?;
+
+  let latest_id = SentActivity::read_latest_id(&mut context.pool())
+    .await?
+    .unwrap_or(ActivityId(0));
+  let mut queue_states: HashMap<_, _> = FederationQueueState::list(&mut context.pool())
+    .await?
+    .into_iter()
+    .map(|s| (s.instance_id, s))
+    .collect();
+  let local_domain = context.settings().get_hostname_without_port()?;
+
+  let mut paused = 0;
+  let mut instances = vec![];
+  for (instance, allowed, is_dead) in
+    Instance::read_federated_with_blocked_and_dead(&mut context.pool()).await?
+  {
+    if instance.domain == local_domain {
+      continue;
+    }
+    // Same as the federation workers, which are only started for these instances
+    if !allowed || is_dead {
+      paused += 1;
+      continue;
+    }
+    let queue_state = queue_states
+      .remove(&instance.id)
+      .unwrap_or_else(|| FederationQueueState {
+        instance_id: instance.id,
+        last_successful_id: Some(latest_id),
+        last_successful_published_time_at: None,
+        fail_count: 0,
+        last_retry_at: None,
+      });
+    let activities_behind = latest_id.0 - queue_state.last_successful_id.map_or(0, |i| i.0);
+    instances.push(FederationQueueInstance {
+      instance,
+      queue_state,
+      activities_behind,
+    });
+  }
+
+  let count = |it: usize| i64::try_from(it).unwrap_or(i64::MAX);
+  let failing = count(
+    instances
+      .iter()
+      .filter(|i| i.queue_state.fail_count > 0)
+      .count(),
+  );
+  let behind = count(
+    instances
+      .iter()
+      .filter(|i| i.queue_state.fail_count == 0 && i.activities_behind > 0)
+      .count(),
+  );
+  let up_to_date = count(instances.len()) - failing - behind;
+
+  instances.sort_by_key(|i| {
+    (
+      Reverse(i.activities_behind),
+      Reverse(i.queue_state.fail_count),
+    )
+  });
+  instances.truncate(FEDERATION_QUEUE_LIMIT);
+
+  Ok(Json(ListFederationQueueResponse {
+    up_to_date,
+    behind,
+    failing,
+    paused,
+    instances,
+  }))
+}
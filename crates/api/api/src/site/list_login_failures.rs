@@ -0,0 +1,51 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::login_failure::LoginFailure;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListLoginFailuresResponse;
+use lemmy_utils::error::LemmyResult;
+
+/// Maximum number of accounts and ips which are returned.
+const LOGIN_FAILURES_LIMIT: usize = 100;
+
+pub async fn list_login_failures(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListLoginFailuresResponse>> {
+  is_admin(&local_user_view)?;
+
+  let mut login_failures = LoginFailure::list_recent(&mut context.pool()).await?;
+  let now = Utc::now();
+  let is_locked = |f: &&LoginFailure| f.locked_until.is_some_and(|l| l > now);
+  let count = |it: usize| i64::try_from(it).unwrap_or(i64::MAX);
+  let locked_accounts = count(
+    login_failures
+      .iter()
+      .filter(is_locked)
+      .filter(|f| f.local_user_id.is_some())
+      .count(),
+  );
+  let locked_ips = count(
+    login_failures
+      .iter()
+      .filter(is_locked)
+      .filter(|f| f.ip.is_some())
+      .count(),
+  );
+  // Failed logins are counted both for the account and the ip
+  let failures_last_day = login_failures
+    .iter()
+    .filter(|f| f.ip.is_some())
+    .map(|f| i64::from(f.failure_count))
+    .sum();
+  login_failures.truncate(LOGIN_FAILURES_LIMIT);
+
+  Ok(Json(ListLoginFailuresResponse {
+    locked_accounts,
+    locked_ips,
+    failures_last_day,
+    login_failures,
+  }))
+}
@@ -0,0 +1,28 @@
+use actix_web::web::{Data, Json, Query};
+use chrono::{TimeDelta, Utc};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::{source::images::LocalImage, utils::limit_fetch};
+use lemmy_db_views_local_image::api::{ListOrphanedMedia, ListOrphanedMediaResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Dry run of the orphaned image cleanup, so that admins can check which uploads would be
+/// deleted before enabling it.
+pub async fn list_orphaned_media(
+  Query(data): Query<ListOrphanedMedia>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListOrphanedMediaResponse>> {
+  is_admin(&local_user_view)?;
+
+  let pictrs_config = context.settings().pictrs()?;
+  let published_before =
+    Utc::now() - TimeDelta::days(pictrs_config.orphaned_image_grace_days.into());
+  let limit = limit_fetch(data.limit, None)?;
+  let images = LocalImage::list_orphaned(&mut context.pool(), published_before, limit).await?;
+
+  Ok(Json(ListOrphanedMediaResponse {
+    images,
+    cleanup_enabled: pictrs_config.delete_orphaned_images,
+  }))
+}
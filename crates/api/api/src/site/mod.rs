@@ -1,8 +1,21 @@
 pub mod admin_allow_instance;
 pub mod admin_block_instance;
 pub mod admin_list_users;
+pub mod admin_stats;
+pub mod clear_client_penalties;
 pub mod federated_instances;
+pub mod instance_move;
+pub mod link_metadata_override;
 pub mod list_all_media;
+pub mod list_client_penalties;
+pub mod list_failed_deliveries;
+pub mod list_federation_inbox;
+pub mod list_federation_queue;
+pub mod list_login_failures;
+pub mod list_orphaned_media;
 pub mod mod_log;
 pub mod purge;
+pub mod rate_limit_override;
+pub mod read_pseudonym;
 pub mod registration_applications;
+pub mod replay_failed_deliveries;
@@ -1,6 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
   utils::is_admin,
@@ -60,5 +61,8 @@ pub async fn purge_community(
     &context,
   )?;
 
+  invalidate_community(data.community_id).await;
+  invalidate_posts();
+
   Ok(Json(SuccessResponse::default()))
 }
@@ -1,6 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
+  cache::invalidate_posts,
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{is_admin, purge_post_images},
@@ -55,5 +56,7 @@ pub async fn purge_post(
     &context,
   )?;
 
+  invalidate_posts();
+
   Ok(Json(SuccessResponse::default()))
 }
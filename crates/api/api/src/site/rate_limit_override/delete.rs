@@ -0,0 +1,23 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{is_admin, reload_rate_limit_overrides},
+};
+use lemmy_db_schema::source::local_site_rate_limit_override::LocalSiteRateLimitOverride;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeleteRateLimitOverride, SuccessResponse};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_rate_limit_override(
+  Json(data): Json<DeleteRateLimitOverride>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  is_admin(&local_user_view)?;
+
+  LocalSiteRateLimitOverride::delete(&mut context.pool(), data.user_class, data.action).await?;
+  reload_rate_limit_overrides(&context).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
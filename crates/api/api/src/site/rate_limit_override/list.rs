@@ -0,0 +1,20 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::local_site_rate_limit_override::LocalSiteRateLimitOverride;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListRateLimitOverridesResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_rate_limit_overrides(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListRateLimitOverridesResponse>> {
+  is_admin(&local_user_view)?;
+
+  let rate_limit_overrides = LocalSiteRateLimitOverride::list(&mut context.pool()).await?;
+
+  Ok(Json(ListRateLimitOverridesResponse {
+    rate_limit_overrides,
+  }))
+}
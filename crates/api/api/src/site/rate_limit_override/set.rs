@@ -0,0 +1,38 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{is_admin, reload_rate_limit_overrides},
+};
+use lemmy_db_schema::source::local_site_rate_limit_override::{
+  LocalSiteRateLimitOverride,
+  LocalSiteRateLimitOverrideForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{RateLimitOverrideResponse, SetRateLimitOverride};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn set_rate_limit_override(
+  Json(data): Json<SetRateLimitOverride>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<RateLimitOverrideResponse>> {
+  is_admin(&local_user_view)?;
+
+  if data.max_requests <= 0 || data.interval_seconds <= 0 {
+    return Err(LemmyErrorType::InvalidRateLimit.into());
+  }
+  let form = LocalSiteRateLimitOverrideForm {
+    user_class: data.user_class,
+    action: data.action,
+    max_requests: data.max_requests,
+    interval_seconds: data.interval_seconds,
+  };
+  let rate_limit_override = LocalSiteRateLimitOverride::upsert(&mut context.pool(), &form).await?;
+  // Other processes pick up the change when they reload the site settings
+  reload_rate_limit_overrides(&context).await?;
+
+  Ok(Json(RateLimitOverrideResponse {
+    rate_limit_override,
+  }))
+}
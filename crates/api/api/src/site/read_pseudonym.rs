@@ -0,0 +1,23 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::{community_pseudonym::CommunityPseudonym, person::Person};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{GetPseudonym, GetPseudonymResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn read_pseudonym(
+  Query(data): Query<GetPseudonym>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetPseudonymResponse>> {
+  is_admin(&local_user_view)?;
+
+  let pseudonym = CommunityPseudonym::read_for_pseudonym(&mut context.pool(), data.person_id)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+  let person = Person::read(&mut context.pool(), pseudonym.person_id).await?;
+
+  Ok(Json(GetPseudonymResponse { pseudonym, person }))
+}
@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::sent_activity_delivery::SentActivityDelivery;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ReplayFailedDeliveries, ReplayFailedDeliveriesResponse};
+use lemmy_utils::error::LemmyResult;
+
+/// Marks failed deliveries for replay. The federation worker for the instance sends them again
+/// once it has caught up with new activities.
+pub async fn replay_failed_deliveries(
+  Json(data): Json<ReplayFailedDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ReplayFailedDeliveriesResponse>> {
+  is_admin(&local_user_view)?;
+
+  let replayed =
+    SentActivityDelivery::request_replay(&mut context.pool(), data.instance_id, data.since).await?;
+
+  Ok(Json(ReplayFailedDeliveriesResponse {
+    replayed: i64::try_from(replayed).unwrap_or(i64::MAX),
+  }))
+}
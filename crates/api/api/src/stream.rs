@@ -0,0 +1,74 @@
+use actix_web::{
+  HttpRequest,
+  HttpResponse,
+  http::header::CACHE_CONTROL,
+  web::{Bytes, Data, Query},
+};
+use futures::stream;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  stream::StreamConnection,
+  utils::{check_private_instance, client_ip},
+};
+use lemmy_db_schema::{
+  StreamType,
+  newtypes::{CommunityId, PostId},
+};
+use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
+use lemmy_db_views_community::CommunityView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::PostView;
+use lemmy_db_views_site::{SiteView, api::GetStream};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::convert::Infallible;
+
+pub async fn get_stream(
+  Query(data): Query<GetStream>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<HttpResponse> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+
+  // Only allow following content which the user can see
+  match data.type_ {
+    StreamType::Community => {
+      let community_view =
+        CommunityView::read(&mut context.pool(), CommunityId(data.id), local_user, false).await?;
+      let is_follower = community_view
+        .community_actions
+        .and_then(|a| a.follow_state)
+        == Some(CommunityFollowerState::Accepted);
+      if community_view.community.visibility == CommunityVisibility::Private
+        && !is_follower
+        && !community_view.can_mod
+      {
+        return Err(LemmyErrorType::NotFound.into());
+      }
+    }
+    StreamType::Post => {
+      PostView::read(
+        &mut context.pool(),
+        PostId(data.id),
+        local_user,
+        site_view.site.instance_id,
+        false,
+      )
+      .await?;
+    }
+  }
+
+  let connection = StreamConnection::open(client_ip(&req), data.type_, data.id)?;
+  let body = stream::unfold(connection, |mut connection| async move {
+    let chunk = connection.next().await?;
+    Some((Ok::<_, Infallible>(Bytes::from(chunk)), connection))
+  });
+  Ok(
+    HttpResponse::Ok()
+      .content_type("text/event-stream")
+      .insert_header((CACHE_CONTROL, "no-cache"))
+      .streaming(body),
+  )
+}
@@ -4,23 +4,46 @@ pub use lemmy_db_views_person_saved_combined::ListPersonSaved;
 pub use lemmy_db_views_post_comment_combined::PostCommentCombinedView;
 pub use lemmy_db_views_site::api::{DeleteAccount, MyUserInfo, SaveUserSettings};
 pub mod auth {
-  pub use lemmy_db_schema::source::login_token::LoginToken;
+  pub use lemmy_db_schema::{
+    newtypes::{ApiKeyId, LoginTokenId, WebauthnCredentialId},
+    source::{api_key::ApiKey, login_token::LoginToken, webauthn_credential::WebauthnCredential},
+  };
   pub use lemmy_db_views_registration_applications::api::{CaptchaAnswer, Register};
   pub use lemmy_db_views_site::api::{
     CaptchaResponse,
     ChangePassword,
+    CreateApiKey,
+    CreateApiKeyResponse,
+    DeleteApiKey,
+    DeleteWebauthnCredential,
     EditTotp,
     EditTotpResponse,
     ExportDataResponse,
     GenerateTotpSecretResponse,
     GetCaptchaResponse,
+    GetPowChallengeResponse,
+    ListApiKeysResponse,
     ListLoginsResponse,
+    ListWebauthnCredentialsResponse,
     Login,
     LoginResponse,
     PasswordChangeAfterReset,
     PasswordReset,
+    PowChallengeResponse,
+    RefreshToken,
+    RegenerateTotpRecoveryCodes,
+    RegisterWebauthnCredential,
     ResendVerificationEmail,
+    RevokeLogin,
+    StartWebauthnLogin,
+    TotpRecoveryCodesResponse,
     UserSettingsBackup,
     VerifyEmail,
+    VerifyPowChallenge,
+    WebauthnAssertion,
+    WebauthnCredentialResponse,
+    WebauthnLogin,
+    WebauthnLoginOptions,
+    WebauthnRegistrationOptions,
   };
 }
@@ -5,7 +5,7 @@ pub use lemmy_db_schema::{
 pub use lemmy_db_views_comment::{
   CommentSlimView,
   CommentView,
-  api::{CommentResponse, GetComment, GetComments},
+  api::{CommentResponse, GetComment, GetCommentUpdates, GetComments},
 };
 
 pub mod actions {
@@ -23,6 +23,7 @@ pub mod actions {
       ListCommentLikes,
       PurgeComment,
       RemoveComment,
+      RemoveManyComments,
     };
   }
 }
@@ -1,9 +1,18 @@
 pub use lemmy_db_schema::{
-  newtypes::{CommunityId, CommunityTagId, MultiCommunityId},
+  newtypes::{
+    CommunityId,
+    CommunityPostTemplateId,
+    CommunityRecurringPostId,
+    CommunityTagId,
+    MultiCommunityId,
+  },
   source::{
     community::{Community, CommunityActions},
+    community_post_template::CommunityPostTemplate,
+    community_recurring_post::CommunityRecurringPost,
     community_tag::{CommunityTag, CommunityTagsView},
     multi_community::{MultiCommunity, MultiCommunityFollow},
+    remote_community_directory::RemoteCommunityDirectory,
   },
 };
 pub use lemmy_db_schema_file::enums::CommunityVisibility;
@@ -23,6 +32,9 @@ pub use lemmy_db_views_community::{
     GetMultiCommunityResponse,
     GetRandomCommunity,
     ListCommunities,
+    ListCommunityDirectory,
+    ListCommunityPostTemplates,
+    ListCommunityPostTemplatesResponse,
     ListMultiCommunities,
   },
 };
@@ -44,13 +56,23 @@ pub mod actions {
       AddModToCommunityResponse,
       ApproveCommunityPendingFollower,
       BanFromCommunity,
+      BanManyFromCommunity,
       CommunityIdQuery,
+      CreateCommunityPostTemplate,
+      CreateCommunityRecurringPost,
       CreateCommunityTag,
       DeleteCommunity,
+      DeleteCommunityPostTemplate,
+      DeleteCommunityRecurringPost,
       DeleteCommunityTag,
       EditCommunity,
+      EditCommunityPostTemplate,
+      EditCommunityRecurringPost,
       EditCommunityTag,
+      ListCommunityRecurringPosts,
+      ListCommunityRecurringPostsResponse,
       PurgeCommunity,
+      QuarantineCommunity,
       RemoveCommunity,
       TransferCommunity,
     };
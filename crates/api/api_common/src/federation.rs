@@ -7,15 +7,39 @@ pub use lemmy_db_schema::{
     instance::{Instance, InstanceActions},
   },
 };
-pub use lemmy_db_schema_file::{InstanceId, enums::FederationMode};
+pub use lemmy_db_schema_file::{
+  InstanceId,
+  enums::{FederationBlockSeverity, FederationMode},
+};
 pub use lemmy_db_views_site::api::{
   GetFederatedInstances,
   GetFederatedInstancesKind,
+  ResolveMany,
+  ResolveManyResponse,
   ResolveObject,
+  ResolvePermalink,
   UserBlockInstanceCommunitiesParams,
   UserBlockInstancePersonsParams,
 };
 
 pub mod administration {
-  pub use lemmy_db_views_site::api::{AdminAllowInstanceParams, AdminBlockInstanceParams};
+  pub use lemmy_db_schema::{
+    newtypes::InstanceMoveId,
+    source::{instance_move::InstanceMove, sent_activity_delivery::SentActivityDelivery},
+  };
+  pub use lemmy_db_views_site::api::{
+    AdminAllowInstanceParams,
+    AdminBlockInstanceParams,
+    FailedDelivery,
+    FederationInboxInstance,
+    FederationQueueInstance,
+    ListFailedDeliveries,
+    ListFailedDeliveriesResponse,
+    ListFederationInboxResponse,
+    ListFederationQueueResponse,
+    ListInstanceMovesResponse,
+    ReplayFailedDeliveries,
+    ReplayFailedDeliveriesResponse,
+    ResolveInstanceMove,
+  };
 }
@@ -1,5 +1,15 @@
 pub use lemmy_db_schema::source::images::{ImageDetails, LocalImage, RemoteImage};
 pub use lemmy_db_views_local_image::{
   LocalImageView,
-  api::{DeleteImageParams, ImageGetParams, ImageProxyParams, ListMedia, UploadImageResponse},
+  api::{
+    DeleteImageParams,
+    GetMediaUsage,
+    GetMediaUsageResponse,
+    ImageGetParams,
+    ImageProxyParams,
+    ListMedia,
+    ListOrphanedMedia,
+    ListOrphanedMediaResponse,
+    UploadImageResponse,
+  },
 };
@@ -1,13 +1,26 @@
 pub use lemmy_db_schema::{
-  newtypes::OAuthProviderId,
+  newtypes::{OAuthApplicationId, OAuthProviderId},
   source::{
     oauth_account::OAuthAccount,
+    oauth_application::OAuthApplication,
     oauth_provider::{AdminOAuthProvider, PublicOAuthProvider},
   },
 };
+pub use lemmy_db_schema_file::enums::TokenScope;
 pub use lemmy_db_views_site::api::{
   AuthenticateWithOauth,
+  AuthorizeOAuthApplication,
+  AuthorizeOAuthApplicationResponse,
+  CreateOAuthApplication,
+  CreateOAuthApplicationResponse,
   CreateOAuthProvider,
+  DeleteOAuthApplication,
   DeleteOAuthProvider,
   EditOAuthProvider,
+  ListOAuthAccountsResponse,
+  ListOAuthApplicationsResponse,
+  OAuthTokenRequest,
+  OAuthTokenResponse,
+  RevokeOAuthApplication,
+  UnlinkOAuthAccount,
 };
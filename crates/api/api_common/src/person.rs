@@ -15,7 +15,7 @@ pub use lemmy_db_views_person::{
 
 pub mod actions {
   pub use lemmy_db_schema::newtypes::PersonContentCombinedId;
-  pub use lemmy_db_views_person::api::{BlockPerson, NotePerson};
+  pub use lemmy_db_views_person::api::{BlockPerson, MutePerson, NotePerson};
   pub use lemmy_db_views_person_content_combined::ListPersonContent;
 
   pub mod moderation {
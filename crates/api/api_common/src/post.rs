@@ -1,18 +1,32 @@
 pub use lemmy_db_schema::{
   PostFeatureType,
-  newtypes::PostId,
-  source::post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  newtypes::{HashtagId, PostId},
+  source::{
+    hashtag::Hashtag,
+    link_metadata_override::LinkMetadataOverride,
+    post::{Post, PostActions, PostInsertForm, PostLikeForm},
+    post_feature_schedule::PostFeatureSchedule,
+  },
 };
 pub use lemmy_db_schema_file::enums::{PostListingMode, PostNotificationsMode};
 pub use lemmy_db_views_post::{
+  PostReadState,
+  PostTombstone,
+  PostUpdateView,
   PostView,
   api::{
+    GetPostUpdates,
     GetPosts,
     GetSiteMetadata,
     GetSiteMetadataResponse,
     LinkMetadata,
+    LinkMetadataOverrideResponse,
+    ListHashtags,
+    ListHashtagsResponse,
+    ListLinkMetadataOverridesResponse,
     OpenGraphData,
     PostResponse,
+    SyncReadPosts,
   },
 };
 pub mod actions {
@@ -30,12 +44,19 @@ pub mod actions {
 
   pub mod moderation {
     pub use lemmy_db_views_post::api::{
+      DeleteLinkMetadataOverride,
       FeaturePost,
+      ListPostFeatureSchedules,
+      ListPostFeatureSchedulesResponse,
       ListPostLikes,
       LockPost,
       ModEditPost,
+      PostFeatureScheduleResponse,
       PurgePost,
+      RemoveManyPosts,
       RemovePost,
+      SchedulePostFeature,
+      SetLinkMetadataOverride,
     };
   }
 }
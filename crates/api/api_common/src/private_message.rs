@@ -1,10 +1,19 @@
-pub use lemmy_db_schema::{newtypes::PrivateMessageId, source::private_message::PrivateMessage};
-pub use lemmy_db_views_private_message::{PrivateMessageView, api::PrivateMessageResponse};
+pub use lemmy_db_schema::{
+  newtypes::{PersonEncryptionKeyId, PrivateMessageId},
+  source::{person_encryption_key::PersonEncryptionKey, private_message::PrivateMessage},
+};
+pub use lemmy_db_views_private_message::{
+  PrivateMessageView,
+  api::{EncryptionKeyResponse, ListEncryptionKeysResponse, PrivateMessageResponse},
+};
 
 pub mod actions {
   pub use lemmy_db_views_private_message::api::{
+    CreateEncryptionKey,
     CreatePrivateMessage,
+    DeleteEncryptionKey,
     DeletePrivateMessage,
     EditPrivateMessage,
+    ListEncryptionKeys,
   };
 }
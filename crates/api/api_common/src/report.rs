@@ -1,9 +1,16 @@
 pub use lemmy_db_schema::{
   ReportType,
-  newtypes::{CommentReportId, CommunityReportId, PostReportId, PrivateMessageReportId},
+  newtypes::{
+    CommentReportId,
+    CommunityReportId,
+    InstanceReportId,
+    PostReportId,
+    PrivateMessageReportId,
+  },
   source::{
     comment_report::CommentReport,
     community_report::CommunityReport,
+    instance_report::InstanceReport,
     post_report::PostReport,
     private_message_report::PrivateMessageReport,
   },
@@ -11,6 +18,7 @@ pub use lemmy_db_schema::{
 pub use lemmy_db_views_report_combined::{
   CommentReportView,
   CommunityReportView,
+  InstanceReportView,
   PostReportView,
   PrivateMessageReportView,
   ReportCombinedView,
@@ -19,13 +27,16 @@ pub use lemmy_db_views_report_combined::{
     CommunityReportResponse,
     CreateCommentReport,
     CreateCommunityReport,
+    CreateInstanceReport,
     CreatePostReport,
     CreatePrivateMessageReport,
+    InstanceReportResponse,
     ListReports,
     PostReportResponse,
     PrivateMessageReportResponse,
     ResolveCommentReport,
     ResolveCommunityReport,
+    ResolveInstanceReport,
     ResolvePostReport,
     ResolvePrivateMessageReport,
   },
@@ -14,11 +14,21 @@ pub use lemmy_db_views_site::{
 };
 
 pub mod administration {
+  pub use lemmy_db_schema::source::{
+    community_pseudonym::CommunityPseudonym,
+    login_failure::LoginFailure,
+  };
   pub use lemmy_db_views_local_user::api::AdminListUsers;
   pub use lemmy_db_views_person::api::{AddAdmin, AddAdminResponse};
   pub use lemmy_db_views_registration_applications::api::{
     ApproveRegistrationApplication,
     ListRegistrationApplications,
   };
-  pub use lemmy_db_views_site::api::{CreateSite, EditSite};
+  pub use lemmy_db_views_site::api::{
+    CreateSite,
+    EditSite,
+    GetPseudonym,
+    GetPseudonymResponse,
+    ListLoginFailuresResponse,
+  };
 }
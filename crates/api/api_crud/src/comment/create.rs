@@ -3,14 +3,17 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_comment_response,
+  classifier::classify_comment,
   context::LemmyContext,
   notify::NotifyData,
   plugins::{plugin_hook_after, plugin_hook_before},
   send_activity::{ActivityChannel, SendActivityData},
+  stream::StreamChannel,
   utils::{
     check_comment_depth,
     check_community_user_action,
     check_post_deleted_or_removed,
+    get_or_create_pseudonym,
     get_url_blocklist,
     is_mod_or_admin,
     process_markdown,
@@ -104,10 +107,16 @@ pub async fn create_comment(
     check_comment_depth(parent)?;
   }
 
+  let creator = if data.pseudonymous.unwrap_or_default() {
+    get_or_create_pseudonym(&local_user_view, &post_view.community, &context).await?
+  } else {
+    local_user_view.person.clone()
+  };
+
   let mut comment_form = CommentInsertForm {
     language_id: data.language_id,
     federation_pending: Some(community_use_pending(&post_view.community, &context).await),
-    ..CommentInsertForm::new(my_person_id, data.post_id, content.clone())
+    ..CommentInsertForm::new(creator.id, data.post_id, content.clone())
   };
   comment_form = plugin_hook_before("local_comment_before_create", comment_form).await?;
   validate_post_language(&mut context.pool(), comment_form.language_id, community_id).await?;
@@ -117,20 +126,18 @@ pub async fn create_comment(
   let inserted_comment =
     Comment::create(&mut context.pool(), &comment_form, parent_path.as_ref()).await?;
   plugin_hook_after("local_comment_after_create", &inserted_comment);
+  classify_comment(inserted_comment.clone(), context.clone());
+  StreamChannel::comment_created(&inserted_comment, community_id);
 
   NotifyData {
     comment: Some(inserted_comment.clone()),
     do_send_email: !local_site.email_notifications_disabled,
-    ..NotifyData::new(
-      post.clone(),
-      local_user_view.person.clone(),
-      post_view.community,
-    )
+    ..NotifyData::new(post.clone(), creator.clone(), post_view.community)
   }
   .send(&context);
 
   // You like your own comment by default
-  let like_form = CommentLikeForm::new(inserted_comment.id, my_person_id, Some(true));
+  let like_form = CommentLikeForm::new(inserted_comment.id, creator.id, Some(true));
 
   CommentActions::like(&mut context.pool(), &like_form).await?;
 
@@ -4,7 +4,7 @@ use lemmy_api_utils::{
   build_response::build_comment_response,
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::check_community_user_action,
+  utils::{check_community_user_action, own_content_creator},
 };
 use lemmy_db_schema::source::comment::{Comment, CommentUpdateForm};
 use lemmy_db_views_comment::{
@@ -43,9 +43,13 @@ pub async fn delete_comment(
   .await?;
 
   // Verify that only the creator can delete
-  if local_user_view.person.id != orig_comment.creator.id {
-    return Err(LemmyErrorType::NoCommentEditAllowed.into());
-  }
+  let creator = own_content_creator(
+    &local_user_view,
+    orig_comment.creator.id,
+    &mut context.pool(),
+  )
+  .await?
+  .ok_or(LemmyErrorType::NoCommentEditAllowed)?;
 
   // Do the delete
   let deleted = data.deleted;
@@ -62,11 +66,7 @@ pub async fn delete_comment(
   let updated_comment_id = updated_comment.id;
 
   ActivityChannel::submit_activity(
-    SendActivityData::DeleteComment(
-      updated_comment,
-      local_user_view.person.clone(),
-      orig_comment.community,
-    ),
+    SendActivityData::DeleteComment(updated_comment, creator, orig_comment.community),
     &context,
   )?;
 
@@ -1,5 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use itertools::Itertools;
 use lemmy_api_utils::{
   build_response::build_comment_response,
   context::LemmyContext,
@@ -8,21 +10,29 @@ use lemmy_api_utils::{
   utils::check_community_mod_action,
 };
 use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId},
   source::{
     comment::{Comment, CommentUpdateForm},
     comment_report::CommentReport,
+    community::Community,
     local_user::LocalUser,
     modlog::{Modlog, ModlogInsertForm},
   },
   traits::Reportable,
 };
+use lemmy_db_schema_file::PersonId;
 use lemmy_db_views_comment::{
   CommentView,
-  api::{CommentResponse, RemoveComment},
+  api::{CommentResponse, RemoveComment, RemoveManyComments},
 };
 use lemmy_db_views_local_user::LocalUserView;
-use lemmy_diesel_utils::traits::Crud;
-use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::{connection::get_conn, traits::Crud};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::check_api_elements_count,
+};
+use std::collections::HashMap;
 
 pub async fn remove_comment(
   Json(data): Json<RemoveComment>,
@@ -158,3 +168,109 @@ pub async fn remove_comment(
     .await?,
   ))
 }
+
+pub async fn remove_many_comments(
+  Json(data): Json<RemoveManyComments>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_api_elements_count(data.comment_ids.len())?;
+  let my_person_id = local_user_view.person.id;
+  let local_instance_id = local_user_view.person.instance_id;
+  let comment_ids: Vec<CommentId> = data.comment_ids.iter().copied().unique().collect();
+
+  let mut orig_comments: Vec<(Comment, Community)> = Vec::with_capacity(comment_ids.len());
+  let mut creators: HashMap<CommunityId, (Community, Vec<PersonId>)> = HashMap::new();
+  for comment_id in &comment_ids {
+    let orig_comment = CommentView::read(
+      &mut context.pool(),
+      *comment_id,
+      Some(&local_user_view.local_user),
+      local_instance_id,
+    )
+    .await?;
+
+    // Don't allow removing or restoring comment which was deleted by user, as it would reveal
+    // the comment text in mod log.
+    if orig_comment.comment.deleted {
+      return Err(LemmyErrorType::CouldntUpdate.into());
+    }
+
+    creators
+      .entry(orig_comment.community.id)
+      .or_insert_with(|| (orig_comment.community.clone(), vec![]))
+      .1
+      .push(orig_comment.creator.id);
+    orig_comments.push((orig_comment.comment, orig_comment.community));
+  }
+
+  // Check permissions for all comments before changing any of them, once per community
+  for (community, creator_ids) in creators.into_values() {
+    check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+    LocalUser::is_higher_mod_or_admin_check(
+      &mut context.pool(),
+      community.id,
+      my_person_id,
+      creator_ids,
+    )
+    .await?;
+  }
+
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let tx_data = data.clone();
+  let (comments, actions) = conn
+    .run_transaction(|conn| {
+      async move {
+        let mut comments = Vec::with_capacity(orig_comments.len());
+        for (orig_comment, community) in orig_comments {
+          let comment = Comment::update(
+            &mut conn.into(),
+            orig_comment.id,
+            &CommentUpdateForm {
+              removed: Some(tx_data.removed),
+              ..Default::default()
+            },
+          )
+          .await?;
+          CommentReport::resolve_all_for_object(&mut conn.into(), comment.id, my_person_id).await?;
+          comments.push((comment, community));
+        }
+
+        let forms: Vec<_> = comments
+          .iter()
+          .map(|(comment, community)| {
+            ModlogInsertForm::mod_remove_comment(
+              my_person_id,
+              comment,
+              community.id,
+              tx_data.removed,
+              &tx_data.reason,
+              None,
+            )
+          })
+          .collect();
+        let actions = Modlog::create(&mut conn.into(), &forms).await?;
+
+        Ok((comments, actions))
+      }
+      .scope_boxed()
+    })
+    .await?;
+  notify_mod_action(actions, &context);
+
+  for (comment, community) in comments {
+    ActivityChannel::submit_activity(
+      SendActivityData::RemoveComment {
+        comment,
+        moderator: local_user_view.person.clone(),
+        community,
+        reason: data.reason.clone(),
+        with_replies: false,
+      },
+      &context,
+    )?;
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -7,7 +7,13 @@ use lemmy_api_utils::{
   notify::NotifyData,
   plugins::{plugin_hook_after, plugin_hook_before},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_community_user_action, get_url_blocklist, process_markdown_opt, slur_regex},
+  utils::{
+    check_community_user_action,
+    get_url_blocklist,
+    own_content_creator,
+    process_markdown_opt,
+    slur_regex,
+  },
 };
 use lemmy_db_schema::{
   impls::actor_language::validate_post_language,
@@ -50,9 +56,13 @@ pub async fn edit_comment(
   .await?;
 
   // Verify that only the creator can edit
-  if local_user_view.person.id != orig_comment.creator.id {
-    return Err(LemmyErrorType::NoCommentEditAllowed.into());
-  }
+  let creator = own_content_creator(
+    &local_user_view,
+    orig_comment.creator.id,
+    &mut context.pool(),
+  )
+  .await?
+  .ok_or(LemmyErrorType::NoCommentEditAllowed)?;
 
   let slur_regex = slur_regex(&context).await?;
   let url_blocklist = get_url_blocklist(&context).await?;
@@ -90,11 +100,7 @@ pub async fn edit_comment(
   // Do the mentions / recipients
   NotifyData {
     comment: Some(updated_comment.clone()),
-    ..NotifyData::new(
-      orig_comment.post,
-      local_user_view.person.clone(),
-      orig_comment.community,
-    )
+    ..NotifyData::new(orig_comment.post, creator, orig_comment.community)
   }
   .send(&context);
 
@@ -3,8 +3,10 @@ use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_community_response,
   context::LemmyContext,
+  search::{SearchIndexQueue, SearchIndexTask},
   utils::{
     check_local_user_valid,
+    check_new_account_community_creation,
     check_nsfw_allowed,
     generate_featured_url,
     generate_followers_url,
@@ -15,6 +17,7 @@ use lemmy_api_utils::{
     process_markdown_opt,
     slur_regex,
   },
+  webhook::send_webhook_event,
 };
 use lemmy_db_schema::{
   source::{
@@ -29,7 +32,7 @@ use lemmy_db_schema::{
   },
   traits::{ApubActor, Followable},
 };
-use lemmy_db_schema_file::enums::CommunityFollowerState;
+use lemmy_db_schema_file::enums::{CommunityFollowerState, WebhookEvent};
 use lemmy_db_views_community::api::{CommunityResponse, CreateCommunity};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
@@ -60,6 +63,7 @@ pub async fn create_community(
   if local_site.community_creation_admin_only && is_admin(&local_user_view).is_err() {
     return Err(LemmyErrorType::OnlyAdminsCanCreateCommunities.into());
   }
+  check_new_account_community_creation(&local_user_view, &local_site)?;
 
   check_nsfw_allowed(data.nsfw, Some(&local_site))?;
   let slur_regex = slur_regex(&context).await?;
@@ -89,12 +93,18 @@ pub async fn create_community(
     check_slurs(summary, &slur_regex)?;
   }
 
+  if data.duplicate_url_days.is_some_and(|d| d <= 0) {
+    return Err(LemmyErrorType::InvalidBodyField.into());
+  }
+
   is_valid_actor_name(&data.name)?;
 
   // Double check for duplicate community actor_ids
   let community_ap_id = Community::generate_local_actor_url(&data.name, context.settings())?;
   let community_dupe = Community::read_from_apub_id(&mut context.pool(), &community_ap_id).await?;
-  if community_dupe.is_some() {
+  // Also check the names of existing communities, which may differ from the ap_id after a rename
+  let name_dupe = Community::read_from_name(&mut context.pool(), &data.name, None, true).await?;
+  if community_dupe.is_some() || name_dupe.is_some() {
     return Err(LemmyErrorType::AlreadyExists.into());
   }
 
@@ -111,6 +121,11 @@ pub async fn create_community(
     featured_url: Some(generate_featured_url(&community_ap_id)?),
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    duplicate_url_policy: data.duplicate_url_policy,
+    duplicate_url_days: data.duplicate_url_days,
+    pseudonymous_posting: data.pseudonymous_posting,
+    default_post_sort_type: data.default_post_sort_type,
+    default_comment_sort_type: data.default_comment_sort_type,
     ..CommunityInsertForm::new(
       site.instance_id,
       data.name.clone(),
@@ -121,6 +136,12 @@ pub async fn create_community(
 
   let inserted_community = Community::create(&mut context.pool(), &community_form).await?;
   let community_id = inserted_community.id;
+  SearchIndexQueue::submit(SearchIndexTask::community(&inserted_community), &context)?;
+  send_webhook_event(
+    WebhookEvent::CommunityCreated,
+    &inserted_community,
+    &context,
+  );
 
   // The community creator becomes a moderator
   let community_moderator_form =
@@ -2,6 +2,7 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_community_response,
+  cache::{invalidate_community, invalidate_posts},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_community_mod_action, check_local_user_valid, is_top_mod},
@@ -47,5 +48,8 @@ pub async fn delete_community(
     &context,
   )?;
 
+  invalidate_community(community_id).await;
+  invalidate_posts();
+
   build_community_response(&context, local_user_view, community_id).await
 }
@@ -2,6 +2,7 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_community_response,
+  cache::{invalidate_community, invalidate_posts},
   context::LemmyContext,
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
@@ -75,5 +76,8 @@ pub async fn remove_community(
     &context,
   )?;
 
+  invalidate_community(community_id).await;
+  invalidate_posts();
+
   build_community_response(&context, local_user_view, community_id).await
 }
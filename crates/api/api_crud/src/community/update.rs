@@ -3,6 +3,7 @@ use actix_web::web::Json;
 use chrono::Utc;
 use lemmy_api_utils::{
   build_response::build_community_response,
+  cache::{invalidate_community, invalidate_posts},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
@@ -10,14 +11,18 @@ use lemmy_api_utils::{
     check_local_user_valid,
     check_nsfw_allowed,
     get_url_blocklist,
+    is_admin,
     process_markdown_opt,
     slur_regex,
   },
 };
-use lemmy_db_schema::source::{
-  actor_language::{CommunityLanguage, SiteLanguage},
-  community::{Community, CommunityUpdateForm},
-  modlog::{Modlog, ModlogInsertForm},
+use lemmy_db_schema::{
+  source::{
+    actor_language::{CommunityLanguage, SiteLanguage},
+    community::{Community, CommunityUpdateForm},
+    modlog::{Modlog, ModlogInsertForm},
+  },
+  traits::ApubActor,
 };
 use lemmy_db_views_community::api::{CommunityResponse, EditCommunity};
 use lemmy_db_views_local_user::LocalUserView;
@@ -27,7 +32,7 @@ use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   utils::{
     slurs::{check_slurs, check_slurs_opt},
-    validation::{is_valid_body_field, is_valid_display_name},
+    validation::{is_valid_actor_name, is_valid_body_field, is_valid_display_name},
   },
 };
 
@@ -68,6 +73,9 @@ pub async fn edit_community(
   }
 
   let summary = diesel_string_update(data.summary.as_deref());
+  if data.duplicate_url_days.is_some_and(|d| d <= 0) {
+    return Err(LemmyErrorType::InvalidBodyField.into());
+  }
 
   let old_community = Community::read(&mut context.pool(), data.community_id).await?;
 
@@ -75,7 +83,22 @@ pub async fn edit_community(
   check_community_mod_action(&local_user_view, &old_community, false, &mut context.pool()).await?;
 
   let community_id = data.community_id;
-  if let Some(languages) = data.discussion_languages.clone() {
+  if let Some(name) = &data.name
+    && name != &old_community.name
+  {
+    if !old_community.local {
+      return Err(LemmyErrorType::OnlyLocalAdminCanRenameCommunity.into());
+    }
+    is_admin(&local_user_view)?;
+    is_valid_actor_name(name)?;
+    check_slurs(name, &slur_regex)?;
+    // Renaming to a previous name of the same community is allowed
+    let existing = Community::read_from_name(&mut context.pool(), name, None, true).await?;
+    if existing.is_some_and(|c| c.id != community_id) {
+      return Err(LemmyErrorType::AlreadyExists.into());
+    }
+  }
+  if let Some(languages) = &data.discussion_languages {
     let site_languages = SiteLanguage::read_local_raw(&mut context.pool()).await?;
     // check that community languages are a subset of site languages
     // https://stackoverflow.com/a/64227550
@@ -83,9 +106,18 @@ pub async fn edit_community(
     if !is_subset {
       return Err(LemmyErrorType::LanguageNotAllowed.into());
     }
-    CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
   }
 
+  let (default_post_sort_type, default_comment_sort_type) =
+    if data.clear_default_sort_types.unwrap_or_default() {
+      (Some(None), Some(None))
+    } else {
+      (
+        data.default_post_sort_type.map(Some),
+        data.default_comment_sort_type.map(Some),
+      )
+    };
+
   let community_form = CommunityUpdateForm {
     title,
     sidebar,
@@ -93,12 +125,29 @@ pub async fn edit_community(
     nsfw: data.nsfw,
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    duplicate_url_policy: data.duplicate_url_policy,
+    duplicate_url_days: data.duplicate_url_days,
+    pseudonymous_posting: data.pseudonymous_posting,
+    default_post_sort_type,
+    default_comment_sort_type,
     updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
 
-  let community_id = data.community_id;
-  let community = Community::update(&mut context.pool(), community_id, &community_form).await?;
+  let community = match &data.name {
+    Some(name) if name != &old_community.name => {
+      Community::rename(&mut context.pool(), &old_community, name, &community_form).await?
+    }
+    _ => Community::update(&mut context.pool(), community_id, &community_form).await?,
+  };
+  if let Some(languages) = data.discussion_languages.clone() {
+    CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
+  }
+
+  // Anonymous listings of the community use its default sort
+  if old_community.default_post_sort_type != community.default_post_sort_type {
+    invalidate_posts();
+  }
 
   let visibility_changed = old_community.visibility != community.visibility;
   if visibility_changed {
@@ -128,5 +177,7 @@ pub async fn edit_community(
     &context,
   )?;
 
+  invalidate_community(community_id).await;
+
   build_community_response(&context, local_user_view, community_id).await
 }
@@ -5,12 +5,14 @@ pub mod comment;
 pub mod community;
 pub mod custom_emoji;
 pub mod multi_community;
+pub mod oauth_application;
 pub mod oauth_provider;
 pub mod post;
 pub mod private_message;
 pub mod site;
 pub mod tagline;
 pub mod user;
+pub mod webhook;
 
 /// Only mark new posts/comments to remote community as pending if it has any local followers.
 /// Otherwise it could never get updated to be marked as published.
@@ -0,0 +1,49 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_local_user_valid, hash_token},
+};
+use lemmy_db_schema::source::{
+  oauth_application::OAuthApplication,
+  oauth_authorization_code::{OAuthAuthorizationCode, OAuthAuthorizationCodeInsertForm},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{AuthorizeOAuthApplication, AuthorizeOAuthApplicationResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use uuid::Uuid;
+
+pub async fn authorize_oauth_application(
+  Json(data): Json<AuthorizeOAuthApplication>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AuthorizeOAuthApplicationResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let oauth_application =
+    OAuthApplication::read_from_client_id(&mut context.pool(), &data.client_id).await?;
+
+  // The redirect uri must match exactly, otherwise the code could be sent to an attacker
+  if oauth_application.redirect_uri.as_str() != data.redirect_uri {
+    return Err(LemmyErrorType::OauthAuthorizationInvalid.into());
+  }
+  if data.code_challenge.is_some()
+    && data.code_challenge_method.as_deref().unwrap_or("S256") != "S256"
+  {
+    return Err(LemmyErrorType::OauthAuthorizationInvalid.into());
+  }
+
+  let code = Uuid::new_v4().to_string();
+  let form = OAuthAuthorizationCodeInsertForm {
+    code_hash: hash_token(&code),
+    oauth_application_id: oauth_application.id,
+    local_user_id: local_user_view.local_user.id,
+    scope: data.scope,
+    redirect_uri: oauth_application.redirect_uri,
+    code_challenge: data.code_challenge,
+  };
+  OAuthAuthorizationCode::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(AuthorizeOAuthApplicationResponse {
+    code: code.into(),
+  }))
+}
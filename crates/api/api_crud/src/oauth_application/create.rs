@@ -0,0 +1,44 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use bcrypt::{DEFAULT_COST, hash};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_local_user_valid, slur_regex},
+};
+use lemmy_db_schema::source::oauth_application::{OAuthApplication, OAuthApplicationInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateOAuthApplication, CreateOAuthApplicationResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::{slurs::check_slurs, validation::is_valid_display_name},
+};
+use url::Url;
+use uuid::Uuid;
+
+pub async fn create_oauth_application(
+  Json(data): Json<CreateOAuthApplication>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateOAuthApplicationResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  is_valid_display_name(&data.name)?;
+  check_slurs(&data.name, &slur_regex(&context).await?)?;
+  let redirect_uri = Url::parse(&data.redirect_uri)?;
+
+  // The secret is only stored as hash, so it can't be shown again later
+  let client_secret = Uuid::new_v4().to_string();
+  let form = OAuthApplicationInsertForm::new(
+    local_user_view.local_user.id,
+    data.name,
+    Uuid::new_v4().to_string(),
+    hash(&client_secret, DEFAULT_COST)?,
+    redirect_uri.into(),
+  );
+  let oauth_application = OAuthApplication::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CreateOAuthApplicationResponse {
+    oauth_application,
+    client_secret: client_secret.into(),
+  }))
+}
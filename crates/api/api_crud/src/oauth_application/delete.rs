@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::oauth_application::OAuthApplication;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeleteOAuthApplication, SuccessResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_oauth_application(
+  Json(data): Json<DeleteOAuthApplication>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let oauth_application = OAuthApplication::read(&mut context.pool(), data.id).await?;
+  if oauth_application.creator_id != local_user_view.local_user.id {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  // Tokens which were issued to the app are deleted via cascade
+  OAuthApplication::delete(&mut context.pool(), data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,17 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::oauth_application::OAuthApplication;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListOAuthApplicationsResponse;
+use lemmy_utils::error::LemmyResult;
+
+/// List the apps which were registered by the current user.
+pub async fn list_oauth_applications(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListOAuthApplicationsResponse>> {
+  let oauth_applications =
+    OAuthApplication::list_for_creator(&mut context.pool(), local_user_view.local_user.id).await?;
+  Ok(Json(ListOAuthApplicationsResponse { oauth_applications }))
+}
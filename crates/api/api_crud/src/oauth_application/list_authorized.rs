@@ -0,0 +1,17 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::login_token::LoginToken;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListOAuthApplicationsResponse;
+use lemmy_utils::error::LemmyResult;
+
+/// List the apps which currently have access to the account of the current user.
+pub async fn list_authorized_oauth_applications(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListOAuthApplicationsResponse>> {
+  let oauth_applications =
+    LoginToken::list_applications(&mut context.pool(), local_user_view.local_user.id).await?;
+  Ok(Json(ListOAuthApplicationsResponse { oauth_applications }))
+}
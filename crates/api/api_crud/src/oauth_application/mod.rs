@@ -0,0 +1,7 @@
+pub mod authorize;
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod list_authorized;
+pub mod revoke;
+pub mod token;
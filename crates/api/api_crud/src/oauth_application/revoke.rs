@@ -0,0 +1,22 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::login_token::LoginToken;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{RevokeOAuthApplication, SuccessResponse};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn revoke_oauth_application(
+  Json(data): Json<RevokeOAuthApplication>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  LoginToken::invalidate_application(
+    &mut context.pool(),
+    local_user_view.local_user.id,
+    data.oauth_application_id,
+  )
+  .await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,95 @@
+use activitypub_federation::config::Data;
+use actix_web::{
+  HttpRequest,
+  web::{Form, Json},
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use bcrypt::verify;
+use lemmy_api_utils::{
+  claims::{APPLICATION_TOKEN_VALIDITY, Claims},
+  context::LemmyContext,
+  utils::hash_token,
+};
+use lemmy_db_schema::source::{
+  oauth_application::OAuthApplication,
+  oauth_authorization_code::OAuthAuthorizationCode,
+};
+use lemmy_db_views_site::api::{OAuthTokenRequest, OAuthTokenResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use sha2::{Digest, Sha256};
+
+/// Called by third-party apps to exchange an authorization code for an access token.
+pub async fn oauth_application_token(
+  Form(data): Form<OAuthTokenRequest>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<OAuthTokenResponse>> {
+  if data.grant_type != "authorization_code" {
+    return Err(LemmyErrorType::OauthAuthorizationInvalid.into());
+  }
+  let oauth_application =
+    OAuthApplication::read_from_client_id(&mut context.pool(), &data.client_id).await?;
+  let code_hash = hash_token(&data.code);
+  let code = OAuthAuthorizationCode::read(&mut context.pool(), &code_hash).await?;
+  if code.oauth_application_id != oauth_application.id
+    || code.redirect_uri.as_str() != data.redirect_uri
+  {
+    return Err(LemmyErrorType::OauthAuthorizationInvalid.into());
+  }
+
+  let secret_valid = data
+    .client_secret
+    .as_ref()
+    .is_some_and(|s| verify(s, &oauth_application.client_secret).unwrap_or(false));
+  let pkce_valid = code
+    .code_challenge
+    .as_ref()
+    .zip(data.code_verifier.as_ref())
+    .is_some_and(|(challenge, verifier)| pkce_challenge(verifier) == *challenge);
+  // Public clients which can't keep a secret need to use PKCE instead
+  let valid = if code.code_challenge.is_some() {
+    pkce_valid && (data.client_secret.is_none() || secret_valid)
+  } else {
+    secret_valid
+  };
+  if !valid {
+    return Err(LemmyErrorType::OauthAuthorizationInvalid.into());
+  }
+  // Only delete the code after it was validated, so that a wrong request doesn't invalidate it
+  OAuthAuthorizationCode::delete_used(&mut context.pool(), &code_hash).await?;
+
+  let access_token = Claims::generate_for_application(
+    code.local_user_id,
+    oauth_application.id,
+    code.scope,
+    req,
+    &context,
+  )
+  .await?;
+
+  Ok(Json(OAuthTokenResponse {
+    access_token,
+    token_type: "bearer".to_string(),
+    expires_in: APPLICATION_TOKEN_VALIDITY.num_seconds(),
+    scope: code.scope,
+  }))
+}
+
+/// S256 code challenge method as defined in RFC 7636.
+fn pkce_challenge(verifier: &str) -> String {
+  URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::pkce_challenge;
+
+  #[test]
+  fn test_pkce_challenge() {
+    // Example from RFC 7636, Appendix B
+    assert_eq!(
+      "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM",
+      pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")
+    );
+  }
+}
@@ -1,6 +1,6 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
-use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_api_utils::{cache::invalidate_site, context::LemmyContext, utils::is_admin};
 use lemmy_db_schema::source::oauth_provider::{AdminOAuthProvider, OAuthProviderInsertForm};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::CreateOAuthProvider;
@@ -34,5 +34,7 @@ pub async fn create_oauth_provider(
   };
   let oauth_provider =
     AdminOAuthProvider::create(&mut context.pool(), &oauth_provider_form).await?;
+  invalidate_site();
+
   Ok(Json(oauth_provider))
 }
@@ -1,6 +1,6 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
-use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_api_utils::{cache::invalidate_site, context::LemmyContext, utils::is_admin};
 use lemmy_db_schema::source::oauth_provider::AdminOAuthProvider;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::{DeleteOAuthProvider, SuccessResponse};
@@ -17,5 +17,7 @@ pub async fn delete_oauth_provider(
 
   AdminOAuthProvider::delete(&mut context.pool(), data.id).await?;
 
+  invalidate_site();
+
   Ok(Json(SuccessResponse::default()))
 }
@@ -1,7 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use chrono::Utc;
-use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_api_utils::{cache::invalidate_site, context::LemmyContext, utils::is_admin};
 use lemmy_db_schema::source::oauth_provider::{AdminOAuthProvider, OAuthProviderUpdateForm};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::api::EditOAuthProvider;
@@ -40,5 +40,7 @@ pub async fn edit_oauth_provider(
   let update_result =
     AdminOAuthProvider::update(&mut context.pool(), data.id, &oauth_provider_form).await?;
   let oauth_provider = AdminOAuthProvider::read(&mut context.pool(), update_result.id).await?;
+  invalidate_site();
+
   Ok(Json(oauth_provider))
 }
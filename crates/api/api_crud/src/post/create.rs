@@ -4,27 +4,38 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_post_response,
+  classifier::classify_post,
   context::LemmyContext,
   notify::NotifyData,
   plugins::{plugin_hook_after, plugin_hook_before},
   request::generate_post_link_metadata,
   send_activity::SendActivityData,
+  stream::StreamChannel,
   utils::{
     check_community_user_action,
+    check_new_account_link_post,
+    check_new_account_post_limit,
     check_nsfw_allowed,
+    get_or_create_pseudonym,
     get_url_blocklist,
     honeypot_check,
     process_markdown_opt,
     send_webmention,
     slur_regex,
+    update_post_hashtags,
     update_post_tags,
   },
+  webhook::send_webhook_event,
 };
 use lemmy_db_schema::{
   impls::actor_language::validate_post_language,
-  source::post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  source::{
+    community_post_template::CommunityPostTemplate,
+    post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  },
   traits::Likeable,
 };
+use lemmy_db_schema_file::enums::{DuplicateUrlPolicy, WebhookEvent};
 use lemmy_db_views_community::CommunityView;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
@@ -32,7 +43,8 @@ use lemmy_db_views_post::api::{CreatePost, PostResponse};
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{traits::Crud, utils::diesel_url_create};
 use lemmy_utils::{
-  error::LemmyResult,
+  error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
   utils::{
     slurs::check_slurs,
     validation::{
@@ -68,6 +80,8 @@ pub async fn create_post(
   let url = diesel_url_create(data.url.as_deref())?;
   let custom_thumbnail = diesel_url_create(data.custom_thumbnail.as_deref())?;
   check_nsfw_allowed(data.nsfw, Some(&local_site))?;
+  check_new_account_link_post(url.is_some(), &local_user_view, &local_site)?;
+  check_new_account_post_limit(&local_user_view, &local_site, &mut context.pool()).await?;
 
   is_valid_post_title(&data.name)?;
 
@@ -105,6 +119,29 @@ pub async fn create_post(
     data.nsfw
   };
 
+  if let Some(url) = &url
+    && community.duplicate_url_policy == DuplicateUrlPolicy::Block
+  {
+    let duplicate = Post::read_duplicate_url(
+      &mut context.pool(),
+      community.id,
+      url,
+      community.duplicate_url_since(),
+    )
+    .await?;
+    if duplicate.is_some() {
+      return Err(LemmyErrorType::DuplicatePostUrl.into());
+    }
+  }
+
+  if let Some(template_id) = data.template_id {
+    let template = CommunityPostTemplate::read(&mut context.pool(), template_id).await?;
+    if template.community_id != community.id {
+      return Err(LemmyErrorType::NotFound.into());
+    }
+    template.check_post_body(body.as_deref())?;
+  }
+
   if community.posting_restricted_to_mods {
     let community_id = data.community_id;
     CommunityModeratorView::check_is_community_moderator(
@@ -115,6 +152,12 @@ pub async fn create_post(
     .await?;
   }
 
+  let creator = if data.pseudonymous.unwrap_or_default() {
+    get_or_create_pseudonym(&local_user_view, community, &context).await?
+  } else {
+    local_user_view.person.clone()
+  };
+
   let scheduled_publish_time_at =
     convert_published_time(data.scheduled_publish_time_at, &local_user_view, &context).await?;
   let mut post_form = PostInsertForm {
@@ -125,11 +168,7 @@ pub async fn create_post(
     language_id: data.language_id,
     federation_pending: Some(community_use_pending(community, &context).await),
     scheduled_publish_time_at,
-    ..PostInsertForm::new(
-      data.name.trim().to_string(),
-      local_user_view.person.id,
-      data.community_id,
-    )
+    ..PostInsertForm::new(data.name.trim().to_string(), creator.id, data.community_id)
   };
 
   post_form = plugin_hook_before("local_post_before_create", post_form).await?;
@@ -143,43 +182,44 @@ pub async fn create_post(
   let inserted_post = Post::create(&mut context.pool(), &post_form).await?;
 
   plugin_hook_after("local_post_after_create", &inserted_post);
+  classify_post(inserted_post.clone(), context.clone());
 
+  update_post_hashtags(&inserted_post, vec![], &context).await?;
   if let Some(tags) = &data.tags {
     update_post_tags(&inserted_post, tags, &context).await?;
   }
 
   let community_id = community.id;
   let federate_post = if scheduled_publish_time_at.is_none() {
+    send_webhook_event(WebhookEvent::PostCreated, &inserted_post, &context);
+    StreamChannel::post_created(&inserted_post);
     send_webmention(inserted_post.clone(), community);
     |post| Some(SendActivityData::CreatePost(post))
   } else {
     |_| None
   };
-  generate_post_link_metadata(
+  // Fetch metadata and thumbnail in background, because some sites are very slow to respond. The
+  // post is federated once this is finished.
+  spawn_try_task(generate_post_link_metadata(
     inserted_post.clone(),
     custom_thumbnail.map(Into::into),
     federate_post,
     context.clone(),
-  )
-  .await?;
+  ));
 
   // They like their own post by default
-  let person_id = local_user_view.person.id;
   let post_id = inserted_post.id;
-  let like_form = PostLikeForm::new(post_id, person_id, Some(true));
+  let like_form = PostLikeForm::new(post_id, creator.id, Some(true));
 
   PostActions::like(&mut context.pool(), &like_form).await?;
 
   NotifyData {
     do_send_email: !local_site.email_notifications_disabled,
-    ..NotifyData::new(
-      inserted_post.clone(),
-      local_user_view.person.clone(),
-      community.clone(),
-    )
+    ..NotifyData::new(inserted_post.clone(), creator, community.clone())
   }
   .send(&context);
 
+  let person_id = local_user_view.person.id;
   PostActions::mark_as_read(&mut context.pool(), person_id, &[post_id]).await?;
 
   build_post_response(&context, community_id, local_user_view, post_id).await
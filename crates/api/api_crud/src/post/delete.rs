@@ -2,9 +2,10 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
   build_response::build_post_response,
+  cache::invalidate_posts,
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::check_community_user_action,
+  utils::{check_community_user_action, own_content_creator},
 };
 use lemmy_db_schema::source::{
   community::Community,
@@ -32,9 +33,9 @@ pub async fn delete_post(
   check_community_user_action(&local_user_view, &community, &mut context.pool()).await?;
 
   // Verify that only the creator can delete
-  if !Post::is_post_creator(local_user_view.person.id, orig_post.creator_id) {
-    return Err(LemmyErrorType::NoPostEditAllowed.into());
-  }
+  let creator = own_content_creator(&local_user_view, orig_post.creator_id, &mut context.pool())
+    .await?
+    .ok_or(LemmyErrorType::NoPostEditAllowed)?;
 
   // Update the post
   let post = Post::update(
@@ -48,9 +49,11 @@ pub async fn delete_post(
   .await?;
 
   ActivityChannel::submit_activity(
-    SendActivityData::DeletePost(post, local_user_view.person.clone(), community),
+    SendActivityData::DeletePost(post, creator, community),
     &context,
   )?;
 
+  invalidate_posts();
+
   build_post_response(&context, orig_post.community_id, local_user_view, post_id).await
 }
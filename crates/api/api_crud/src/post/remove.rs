@@ -1,13 +1,17 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use itertools::Itertools;
 use lemmy_api_utils::{
   build_response::build_post_response,
+  cache::invalidate_posts,
   context::LemmyContext,
   notify::notify_mod_action,
   send_activity::{ActivityChannel, SendActivityData},
   utils::check_community_mod_action,
 };
 use lemmy_db_schema::{
+  newtypes::{CommunityId, PostId},
   source::{
     comment::Comment,
     comment_report::CommentReport,
@@ -19,10 +23,13 @@ use lemmy_db_schema::{
   },
   traits::Reportable,
 };
+use lemmy_db_schema_file::PersonId;
 use lemmy_db_views_local_user::LocalUserView;
-use lemmy_db_views_post::api::{PostResponse, RemovePost};
-use lemmy_diesel_utils::traits::Crud;
-use lemmy_utils::error::LemmyResult;
+use lemmy_db_views_post::api::{PostResponse, RemoveManyPosts, RemovePost};
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::{connection::get_conn, traits::Crud};
+use lemmy_utils::{error::LemmyResult, utils::validation::check_api_elements_count};
+use std::collections::HashMap;
 
 pub async fn remove_post(
   Json(data): Json<RemovePost>,
@@ -111,5 +118,180 @@ pub async fn remove_post(
     &context,
   )?;
 
+  invalidate_posts();
+
   build_post_response(&context, community.id, local_user_view, post_id).await
 }
+
+pub async fn remove_many_posts(
+  Json(data): Json<RemoveManyPosts>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_api_elements_count(data.post_ids.len())?;
+  let my_person_id = local_user_view.person.id;
+  let post_ids: Vec<PostId> = data.post_ids.iter().copied().unique().collect();
+
+  // Check permissions for all posts before changing any of them, once per community
+  let mut creators: HashMap<CommunityId, Vec<PersonId>> = HashMap::new();
+  for post_id in &post_ids {
+    let orig_post = Post::read(&mut context.pool(), *post_id).await?;
+    creators
+      .entry(orig_post.community_id)
+      .or_default()
+      .push(orig_post.creator_id);
+  }
+  for (community_id, creator_ids) in creators {
+    let community = Community::read(&mut context.pool(), community_id).await?;
+    check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+    LocalUser::is_higher_mod_or_admin_check(
+      &mut context.pool(),
+      community_id,
+      my_person_id,
+      creator_ids,
+    )
+    .await?;
+  }
+
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let tx_data = data.clone();
+  let (posts, actions) = conn
+    .run_transaction(|conn| {
+      async move {
+        let mut posts = Vec::with_capacity(post_ids.len());
+        for post_id in post_ids {
+          let post = Post::update(
+            &mut conn.into(),
+            post_id,
+            &PostUpdateForm {
+              removed: Some(tx_data.removed),
+              ..Default::default()
+            },
+          )
+          .await?;
+          PostReport::resolve_all_for_object(&mut conn.into(), post_id, my_person_id).await?;
+          posts.push(post);
+        }
+
+        let forms: Vec<_> = posts
+          .iter()
+          .map(|post| {
+            ModlogInsertForm::mod_remove_post(
+              my_person_id,
+              post,
+              tx_data.removed,
+              &tx_data.reason,
+              None,
+            )
+          })
+          .collect();
+        let actions = Modlog::create(&mut conn.into(), &forms).await?;
+
+        Ok((posts, actions))
+      }
+      .scope_boxed()
+    })
+    .await?;
+  notify_mod_action(actions, &context);
+
+  for post in posts {
+    ActivityChannel::submit_activity(
+      SendActivityData::RemovePost {
+        post,
+        moderator: local_user_view.person.clone(),
+        reason: data.reason.clone(),
+        removed: data.removed,
+        with_replies: false,
+      },
+      &context,
+    )?;
+  }
+
+  invalidate_posts();
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::remove_many_posts;
+  use actix_web::web::Json;
+  use lemmy_api_utils::context::LemmyContext;
+  use lemmy_db_schema::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_db_views_local_user::LocalUserView;
+  use lemmy_db_views_post::api::RemoveManyPosts;
+  use lemmy_diesel_utils::traits::Crud;
+  use lemmy_utils::error::LemmyResult;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_remove_many_posts() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let admin = Person::create(
+      pool,
+      &PersonInsertForm::test_form(data.instance.id, "remove_many_admin"),
+    )
+    .await?;
+    LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form_admin(admin.id),
+      vec![],
+    )
+    .await?;
+    let admin_view = LocalUserView::read_person(pool, admin.id).await?;
+
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "remove_many".into(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+    let post_1 = Post::create(
+      pool,
+      &PostInsertForm::new("post 1".into(), data.person.id, community.id),
+    )
+    .await?;
+    let post_2 = Post::create(
+      pool,
+      &PostInsertForm::new("post 2".into(), data.person.id, community.id),
+    )
+    .await?;
+
+    // Duplicate ids are only removed once
+    remove_many_posts(
+      Json(RemoveManyPosts {
+        post_ids: vec![post_1.id, post_2.id, post_1.id],
+        removed: true,
+        reason: "spam".to_string(),
+      }),
+      context.clone(),
+      admin_view,
+    )
+    .await?;
+
+    assert!(Post::read(pool, post_1.id).await?.removed);
+    assert!(Post::read(pool, post_2.id).await?.removed);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, admin.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -4,6 +4,7 @@ use actix_web::web::Json;
 use chrono::Utc;
 use lemmy_api_utils::{
   build_response::build_post_response,
+  cache::invalidate_posts,
   context::LemmyContext,
   notify::NotifyData,
   plugins::{plugin_hook_after, plugin_hook_before},
@@ -11,11 +12,14 @@ use lemmy_api_utils::{
   send_activity::SendActivityData,
   utils::{
     check_community_user_action,
+    check_new_account_link_post,
     check_nsfw_allowed,
     get_url_blocklist,
+    own_content_creator,
     process_markdown_opt,
     send_webmention,
     slur_regex,
+    update_post_hashtags,
     update_post_tags,
   },
 };
@@ -38,6 +42,7 @@ use lemmy_diesel_utils::{
 };
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
   utils::{
     slurs::check_slurs,
     validation::{
@@ -79,6 +84,7 @@ pub async fn edit_post(
   );
 
   check_nsfw_allowed(data.nsfw, Some(&local_site))?;
+  check_new_account_link_post(matches!(url, Some(Some(_))), &local_user_view, &local_site)?;
 
   let alt_text = diesel_string_update(data.alt_text.as_deref());
 
@@ -123,9 +129,13 @@ pub async fn edit_post(
   check_community_user_action(&local_user_view, &orig_post.community, &mut context.pool()).await?;
 
   // Verify that only the creator can edit
-  if !Post::is_post_creator(local_user_view.person.id, orig_post.post.creator_id) {
-    return Err(LemmyErrorType::NoPostEditAllowed.into());
-  }
+  let creator = own_content_creator(
+    &local_user_view,
+    orig_post.post.creator_id,
+    &mut context.pool(),
+  )
+  .await?
+  .ok_or(LemmyErrorType::NoPostEditAllowed)?;
 
   // handle changes to scheduled_publish_time
   let scheduled_publish_time_at = match (
@@ -165,16 +175,12 @@ pub async fn edit_post(
   let updated_post = Post::update(&mut context.pool(), post_id, &post_form).await?;
   plugin_hook_after("local_post_after_update", &post_form);
 
+  update_post_hashtags(&updated_post, vec![], &context).await?;
   if let Some(tags) = &data.tags {
     update_post_tags(&orig_post.post, tags, &context).await?;
   }
 
-  NotifyData::new(
-    updated_post.clone(),
-    local_user_view.person.clone(),
-    orig_post.community.clone(),
-  )
-  .send(&context);
+  NotifyData::new(updated_post.clone(), creator, orig_post.community.clone()).send(&context);
 
   // send out federation/webmention if necessary
   match (
@@ -185,28 +191,26 @@ pub async fn edit_post(
     (Some(_), None) => {
       let community = Community::read(&mut context.pool(), orig_post.community.id).await?;
       send_webmention(updated_post.clone(), &community);
-      generate_post_link_metadata(
+      spawn_try_task(generate_post_link_metadata(
         updated_post.clone(),
         custom_thumbnail.flatten().map(Into::into),
         |post| Some(SendActivityData::CreatePost(post)),
         context.clone(),
-      )
-      .await?;
-    }
-    // post was already public, send update
-    (None, _) => {
-      generate_post_link_metadata(
-        updated_post.clone(),
-        custom_thumbnail.flatten().map(Into::into),
-        |post| Some(SendActivityData::UpdatePost(post)),
-        context.clone(),
-      )
-      .await?
+      ));
     }
+    // post was already public, send update once the metadata is refreshed in background
+    (None, _) => spawn_try_task(generate_post_link_metadata(
+      updated_post.clone(),
+      custom_thumbnail.flatten().map(Into::into),
+      |post| Some(SendActivityData::UpdatePost(post)),
+      context.clone(),
+    )),
     // schedule was changed, do nothing
     (Some(_), Some(_)) => {}
   };
 
+  invalidate_posts();
+
   build_post_response(
     context.deref(),
     orig_post.community.id,
@@ -27,7 +27,10 @@ use lemmy_db_views_private_message::{
 };
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::traits::Crud;
-use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_body_field};
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::validation::{is_valid_body_field, is_valid_encrypted_private_message},
+};
 
 pub async fn create_private_message(
   Json(data): Json<CreatePrivateMessage>,
@@ -36,6 +39,11 @@ pub async fn create_private_message(
 ) -> LemmyResult<Json<PrivateMessageResponse>> {
   check_local_user_valid(&local_user_view)?;
 
+  is_valid_encrypted_private_message(
+    &data.content,
+    data.ciphertext.as_deref(),
+    data.encryption_metadata.as_deref(),
+  )?;
   let slur_regex = slur_regex(&context).await?;
   let url_blocklist = get_url_blocklist(&context).await?;
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -66,11 +74,15 @@ pub async fn create_private_message(
     check_private_messages_enabled(&recipient_local_user)?;
   }
 
-  let mut form = PrivateMessageInsertForm::new(
-    local_user_view.person.id,
-    data.recipient_id,
-    content.clone(),
-  );
+  let mut form = PrivateMessageInsertForm {
+    ciphertext: data.ciphertext,
+    encryption_metadata: data.encryption_metadata,
+    ..PrivateMessageInsertForm::new(
+      local_user_view.person.id,
+      data.recipient_id,
+      content.clone(),
+    )
+  };
 
   form = plugin_hook_before("local_private_message_before_create", form).await?;
   let inserted_private_message = PrivateMessage::create(&mut context.pool(), &form).await?;
@@ -0,0 +1,42 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_local_user_valid, slur_regex},
+};
+use lemmy_db_schema::source::person_encryption_key::{
+  PersonEncryptionKey,
+  PersonEncryptionKeyInsertForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_private_message::api::{CreateEncryptionKey, EncryptionKeyResponse};
+use lemmy_utils::{
+  MAX_ENCRYPTION_KEYS,
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_display_name, is_valid_encryption_key},
+  },
+};
+
+pub async fn create_encryption_key(
+  Json(data): Json<CreateEncryptionKey>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<EncryptionKeyResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  is_valid_display_name(&data.device_name)?;
+  check_slurs(&data.device_name, &slur_regex(&context).await?)?;
+  is_valid_encryption_key(&data.public_key)?;
+
+  let person_id = local_user_view.person.id;
+  let keys = PersonEncryptionKey::list_for_person(&mut context.pool(), person_id).await?;
+  if keys.len() >= MAX_ENCRYPTION_KEYS {
+    return Err(LemmyErrorType::TooManyItems.into());
+  }
+
+  let form = PersonEncryptionKeyInsertForm::new(person_id, data.device_name, data.public_key);
+  let encryption_key = PersonEncryptionKey::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(EncryptionKeyResponse { encryption_key }))
+}
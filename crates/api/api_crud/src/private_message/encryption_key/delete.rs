@@ -0,0 +1,22 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::person_encryption_key::PersonEncryptionKey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_private_message::api::DeleteEncryptionKey;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_encryption_key(
+  Json(data): Json<DeleteEncryptionKey>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let deleted =
+    PersonEncryptionKey::delete(&mut context.pool(), local_user_view.person.id, data.id).await?;
+  if deleted == 0 {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
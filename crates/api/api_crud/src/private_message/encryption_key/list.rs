@@ -0,0 +1,16 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::person_encryption_key::PersonEncryptionKey;
+use lemmy_db_views_private_message::api::{ListEncryptionKeys, ListEncryptionKeysResponse};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_encryption_keys(
+  Query(data): Query<ListEncryptionKeys>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<ListEncryptionKeysResponse>> {
+  let encryption_keys =
+    PersonEncryptionKey::list_for_person(&mut context.pool(), data.person_id).await?;
+
+  Ok(Json(ListEncryptionKeysResponse { encryption_keys }))
+}
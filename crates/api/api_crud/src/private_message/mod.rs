@@ -1,3 +1,4 @@
 pub mod create;
 pub mod delete;
+pub mod encryption_key;
 pub mod update;
@@ -18,7 +18,7 @@ use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
-  utils::validation::is_valid_body_field,
+  utils::validation::{is_valid_body_field, is_valid_encrypted_private_message},
 };
 
 pub async fn edit_private_message(
@@ -35,6 +35,11 @@ pub async fn edit_private_message(
   }
 
   // Doing the update
+  is_valid_encrypted_private_message(
+    &data.content,
+    data.ciphertext.as_deref(),
+    data.encryption_metadata.as_deref(),
+  )?;
   let slur_regex = slur_regex(&context).await?;
   let url_blocklist = get_url_blocklist(&context).await?;
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -51,6 +56,8 @@ pub async fn edit_private_message(
   let private_message_id = data.private_message_id;
   let mut form = PrivateMessageUpdateForm {
     content: Some(content),
+    ciphertext: Some(data.ciphertext),
+    encryption_metadata: Some(data.encryption_metadata),
     updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
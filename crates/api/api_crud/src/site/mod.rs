@@ -1,4 +1,5 @@
-use lemmy_db_schema_file::enums::{ListingType, RegistrationMode};
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_db_schema_file::enums::{CaptchaProvider, ListingType, RegistrationMode};
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 pub mod create;
@@ -40,6 +41,128 @@ pub fn application_question_check(
   }
 }
 
+/// Checks that the restrictions for new accounts aren't negative.
+pub fn new_account_restrictions_check(
+  min_age_days: Option<i32>,
+  min_karma: Option<i32>,
+  max_posts_per_day: Option<i32>,
+) -> LemmyResult<()> {
+  if [min_age_days, min_karma, max_posts_per_day]
+    .iter()
+    .flatten()
+    .any(|v| *v < 0)
+  {
+    Err(LemmyErrorType::InvalidNewAccountRestriction.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the account deletion grace period is between zero days and a year.
+pub fn account_deletion_grace_period_check(grace_period_days: Option<i32>) -> LemmyResult<()> {
+  if grace_period_days.is_some_and(|d| !(0..=365).contains(&d)) {
+    Err(LemmyErrorType::InvalidAccountDeletionGracePeriod.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the registration ip limit and retention period aren't negative.
+pub fn registration_ip_settings_check(
+  registrations_per_ip_per_day: Option<i32>,
+  registration_ip_retention_days: Option<i32>,
+) -> LemmyResult<()> {
+  if registrations_per_ip_per_day.is_some_and(|r| r < 0)
+    || registration_ip_retention_days.is_some_and(|d| d < 0)
+  {
+    Err(LemmyErrorType::InvalidRegistrationIpSetting.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the proof of work difficulty is at most 32 leading zero bits, which already takes
+/// billions of hashes. 0 disables it.
+pub fn pow_challenge_difficulty_check(difficulty: Option<i32>) -> LemmyResult<()> {
+  if difficulty.is_some_and(|d| !(0..=32).contains(&d)) {
+    Err(LemmyErrorType::InvalidPowChallengeDifficulty.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the upload quota isn't negative. 0 removes the limit.
+pub fn image_upload_quota_check(quota_mb: Option<i32>) -> LemmyResult<()> {
+  if quota_mb.is_some_and(|q| q < 0) {
+    Err(LemmyErrorType::InvalidImageUploadQuota.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the maximum image dimensions aren't negative. 0 removes the limit.
+pub fn image_max_dimensions_check(width: Option<i32>, height: Option<i32>) -> LemmyResult<()> {
+  if width.is_some_and(|w| w < 0) || height.is_some_and(|h| h < 0) {
+    Err(LemmyErrorType::InvalidImageMaxDimensions.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the maximum size of proxied images isn't negative. 0 removes the limit.
+pub fn image_proxy_max_size_check(max_size_mb: Option<i32>) -> LemmyResult<()> {
+  if max_size_mb.is_some_and(|s| s < 0) {
+    Err(LemmyErrorType::InvalidImageProxyMaxSize.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the number of concurrent sends per instance is between 1 and 16.
+pub fn federation_concurrent_sends_check(concurrent_sends: Option<i32>) -> LemmyResult<()> {
+  if concurrent_sends.is_some_and(|c| !(1..=16).contains(&c)) {
+    Err(LemmyErrorType::InvalidFederationConcurrentSends.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the limits for uploaded videos are positive.
+pub fn video_limits_check(max_size_mb: Option<i32>, max_duration: Option<i32>) -> LemmyResult<()> {
+  if max_size_mb.is_some_and(|s| s <= 0) || max_duration.is_some_and(|d| d <= 0) {
+    Err(LemmyErrorType::InvalidVideoLimits.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Checks that the keys which are needed by an external captcha provider are set. Values which
+/// aren't changed are taken from the current site settings, and empty strings erase them.
+pub fn captcha_settings_check(
+  local_site: &LocalSite,
+  provider: Option<CaptchaProvider>,
+  site_key: Option<&str>,
+  secret_key: Option<&str>,
+  instance_url: Option<&str>,
+) -> LemmyResult<()> {
+  let is_set = |new: Option<&str>, current: &Option<String>| match new {
+    Some(new) => !new.is_empty(),
+    None => current.is_some(),
+  };
+  let has_keys = is_set(site_key, &local_site.captcha_site_key)
+    && is_set(secret_key, &local_site.captcha_secret_key);
+  let valid = match provider.unwrap_or(local_site.captcha_provider) {
+    CaptchaProvider::Internal => true,
+    CaptchaProvider::HCaptcha | CaptchaProvider::Turnstile => has_keys,
+    CaptchaProvider::MCaptcha => has_keys && is_set(instance_url, &local_site.captcha_instance_url),
+  };
+  if valid {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::CaptchaNotConfigured.into())
+  }
+}
+
 fn not_zero(val: Option<i32>) -> Option<i32> {
   match val {
     Some(0) => None,
@@ -50,8 +173,21 @@ fn not_zero(val: Option<i32>) -> Option<i32> {
 #[cfg(test)]
 mod tests {
 
-  use crate::site::{application_question_check, not_zero, site_default_post_listing_type_check};
-  use lemmy_db_schema_file::enums::{ListingType, RegistrationMode};
+  use crate::site::{
+    account_deletion_grace_period_check,
+    application_question_check,
+    captcha_settings_check,
+    image_max_dimensions_check,
+    image_proxy_max_size_check,
+    image_upload_quota_check,
+    new_account_restrictions_check,
+    not_zero,
+    pow_challenge_difficulty_check,
+    registration_ip_settings_check,
+    site_default_post_listing_type_check,
+  };
+  use lemmy_db_schema::source::local_site::LocalSite;
+  use lemmy_db_schema_file::enums::{CaptchaProvider, ListingType, RegistrationMode};
 
   #[test]
   fn test_site_default_post_listing_type_check() {
@@ -114,6 +250,123 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_new_account_restrictions_check() {
+    assert!(new_account_restrictions_check(None, None, None).is_ok());
+    assert!(new_account_restrictions_check(Some(0), Some(0), Some(0)).is_ok());
+    assert!(new_account_restrictions_check(Some(7), Some(10), Some(3)).is_ok());
+    assert!(new_account_restrictions_check(Some(-1), None, None).is_err());
+    assert!(new_account_restrictions_check(None, Some(-10), None).is_err());
+    assert!(new_account_restrictions_check(None, None, Some(-3)).is_err());
+  }
+
+  #[test]
+  fn test_account_deletion_grace_period_check() {
+    assert!(account_deletion_grace_period_check(None).is_ok());
+    assert!(account_deletion_grace_period_check(Some(0)).is_ok());
+    assert!(account_deletion_grace_period_check(Some(30)).is_ok());
+    assert!(account_deletion_grace_period_check(Some(-1)).is_err());
+    assert!(account_deletion_grace_period_check(Some(366)).is_err());
+  }
+
+  #[test]
+  fn test_registration_ip_settings_check() {
+    assert!(registration_ip_settings_check(None, None).is_ok());
+    assert!(registration_ip_settings_check(Some(0), Some(0)).is_ok());
+    assert!(registration_ip_settings_check(Some(3), Some(30)).is_ok());
+    assert!(registration_ip_settings_check(Some(-1), None).is_err());
+    assert!(registration_ip_settings_check(None, Some(-30)).is_err());
+  }
+
+  #[test]
+  fn test_pow_challenge_difficulty_check() {
+    assert!(pow_challenge_difficulty_check(None).is_ok());
+    assert!(pow_challenge_difficulty_check(Some(0)).is_ok());
+    assert!(pow_challenge_difficulty_check(Some(20)).is_ok());
+    assert!(pow_challenge_difficulty_check(Some(-1)).is_err());
+    assert!(pow_challenge_difficulty_check(Some(33)).is_err());
+  }
+
+  #[test]
+  fn test_image_upload_quota_check() {
+    assert!(image_upload_quota_check(None).is_ok());
+    assert!(image_upload_quota_check(Some(0)).is_ok());
+    assert!(image_upload_quota_check(Some(500)).is_ok());
+    assert!(image_upload_quota_check(Some(-1)).is_err());
+  }
+
+  #[test]
+  fn test_image_max_dimensions_check() {
+    assert!(image_max_dimensions_check(None, None).is_ok());
+    assert!(image_max_dimensions_check(Some(0), Some(4096)).is_ok());
+    assert!(image_max_dimensions_check(Some(-1), None).is_err());
+    assert!(image_max_dimensions_check(None, Some(-1)).is_err());
+  }
+
+  #[test]
+  fn test_image_proxy_max_size_check() {
+    assert!(image_proxy_max_size_check(None).is_ok());
+    assert!(image_proxy_max_size_check(Some(0)).is_ok());
+    assert!(image_proxy_max_size_check(Some(10)).is_ok());
+    assert!(image_proxy_max_size_check(Some(-1)).is_err());
+  }
+
+  #[test]
+  fn test_captcha_settings_check() {
+    let local_site = LocalSite::default();
+    assert!(captcha_settings_check(&local_site, None, None, None, None).is_ok());
+    assert!(
+      captcha_settings_check(
+        &local_site,
+        Some(CaptchaProvider::HCaptcha),
+        None,
+        None,
+        None
+      )
+      .is_err()
+    );
+    assert!(
+      captcha_settings_check(
+        &local_site,
+        Some(CaptchaProvider::Turnstile),
+        Some("site"),
+        Some("secret"),
+        None
+      )
+      .is_ok()
+    );
+    assert!(
+      captcha_settings_check(
+        &local_site,
+        Some(CaptchaProvider::MCaptcha),
+        Some("site"),
+        Some("secret"),
+        None
+      )
+      .is_err()
+    );
+
+    // Keys which are already stored don't need to be sent again, but can't be erased
+    let local_site = LocalSite {
+      captcha_provider: CaptchaProvider::HCaptcha,
+      captcha_site_key: Some("site".to_string()),
+      captcha_secret_key: Some("secret".to_string()),
+      ..Default::default()
+    };
+    assert!(captcha_settings_check(&local_site, None, None, None, None).is_ok());
+    assert!(captcha_settings_check(&local_site, None, None, Some(""), None).is_err());
+    assert!(
+      captcha_settings_check(
+        &local_site,
+        Some(CaptchaProvider::Internal),
+        Some(""),
+        None,
+        None
+      )
+      .is_ok()
+    );
+  }
+
   #[test]
   fn test_not_zero() {
     assert_eq!(None, not_zero(None));
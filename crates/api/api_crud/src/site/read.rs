@@ -1,41 +1,105 @@
-use actix_web::web::{Data, Json};
+use actix_web::web::Data;
+use chrono::{DateTime, Utc};
 use lemmy_api_utils::{
+  cache::{SITE, get_or_load},
+  captcha::is_captcha_enabled,
+  conditional::ConditionalJson,
   context::LemmyContext,
-  plugins::{is_captcha_plugin_loaded, plugin_metadata},
+  plugins::plugin_metadata,
+  push::is_push_provider_enabled,
 };
 use lemmy_db_schema::source::{
   actor_language::SiteLanguage,
   language::Language,
+  local_site_registration_ip_range::LocalSiteRegistrationIpRange,
   local_site_url_blocklist::LocalSiteUrlBlocklist,
   oauth_provider::AdminOAuthProvider,
   registration_application::RegistrationApplication,
   tagline::Tagline,
 };
+use lemmy_db_schema_file::enums::PushProvider;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::PersonView;
 use lemmy_db_views_site::{SiteView, api::GetSiteResponse};
-use lemmy_utils::{CacheLock, VERSION, build_cache, error::LemmyResult};
-use std::sync::LazyLock;
+use lemmy_utils::{VERSION, error::LemmyResult};
 
 pub async fn get_site(
   local_user_view: Option<LocalUserView>,
   context: Data<LemmyContext>,
-) -> LemmyResult<Json<GetSiteResponse>> {
+) -> LemmyResult<ConditionalJson<GetSiteResponse>> {
   // This data is independent from the user account so we can cache it across requests
-  static CACHE: CacheLock<GetSiteResponse> = LazyLock::new(build_cache);
-  let mut site_response = Box::pin(CACHE.try_get_with((), read_site(&context)))
-    .await
-    .map_err(|e| anyhow::anyhow!("Failed to construct site response: {e}"))?;
+  let mut site_response = get_or_load(&SITE, (), read_site(&context)).await?;
+  let state = site_state(&site_response);
 
-  // filter oauth_providers for public access
+  // filter oauth_providers and the registration firewall for public access
   if !local_user_view
+    .as_ref()
     .map(|l| l.local_user.admin)
     .unwrap_or_default()
   {
     site_response.admin_oauth_providers = vec![];
+    site_response.admin_registration_ip_ranges = vec![];
   }
 
-  Ok(Json(site_response))
+  if local_user_view.is_some() {
+    return Ok(ConditionalJson::new(site_response));
+  }
+  Ok(
+    ConditionalJson::new(site_response)
+      .etag(&state)
+      .last_modified(state.0),
+  )
+}
+
+/// Update timestamps of everything in the response, and the counters which change without
+/// updating a timestamp.
+fn site_state(res: &GetSiteResponse) -> (Vec<DateTime<Utc>>, [i32; 8], Option<i64>) {
+  let SiteView {
+    site,
+    local_site,
+    local_site_rate_limit,
+    ..
+  } = &res.site_view;
+  let mut timestamps = vec![
+    site.updated_at.unwrap_or(site.published_at),
+    local_site.updated_at.unwrap_or(local_site.published_at),
+  ];
+  timestamps.extend(local_site_rate_limit.updated_at);
+  timestamps.extend(
+    res
+      .admins
+      .iter()
+      .map(|a| a.person.updated_at.unwrap_or(a.person.published_at)),
+  );
+  timestamps.extend(
+    res
+      .admin_oauth_providers
+      .iter()
+      .map(|p| p.updated_at.unwrap_or(p.published_at)),
+  );
+  timestamps.extend(
+    res
+      .blocked_urls
+      .iter()
+      .map(|u| u.updated_at.unwrap_or(u.published_at)),
+  );
+  timestamps.extend(
+    res
+      .tagline
+      .as_ref()
+      .map(|t| t.updated_at.unwrap_or(t.published_at)),
+  );
+  let counts = [
+    local_site.users,
+    local_site.posts,
+    local_site.comments,
+    local_site.communities,
+    local_site.users_active_day,
+    local_site.users_active_week,
+    local_site.users_active_month,
+    local_site.users_active_half_year,
+  ];
+  (timestamps, counts, res.last_application_duration_seconds)
 }
 
 async fn read_site(context: &LemmyContext) -> LemmyResult<GetSiteResponse> {
@@ -44,6 +108,8 @@ async fn read_site(context: &LemmyContext) -> LemmyResult<GetSiteResponse> {
   let all_languages = Language::read_all(&mut context.pool()).await?;
   let discussion_languages = SiteLanguage::read_local_raw(&mut context.pool()).await?;
   let blocked_urls = LocalSiteUrlBlocklist::get_all(&mut context.pool()).await?;
+  let admin_registration_ip_ranges =
+    LocalSiteRegistrationIpRange::get_all(&mut context.pool()).await?;
   let tagline = Tagline::get_random(&mut context.pool()).await.ok();
   let admin_oauth_providers = AdminOAuthProvider::get_all(&mut context.pool()).await?;
   let oauth_providers =
@@ -55,17 +121,31 @@ async fn read_site(context: &LemmyContext) -> LemmyResult<GetSiteResponse> {
       .and_then(|u| u.updated_published_duration());
 
   Ok(GetSiteResponse {
-    site_view,
     admins,
     version: VERSION.to_string(),
     all_languages,
     discussion_languages,
     blocked_urls,
+    admin_registration_ip_ranges,
     tagline,
     oauth_providers,
     admin_oauth_providers,
     active_plugins: plugin_metadata(),
     last_application_duration_seconds,
-    captcha_enabled: is_captcha_plugin_loaded(),
+    captcha_enabled: is_captcha_enabled(&site_view.local_site),
+    vapid_public_key: context
+      .settings()
+      .web_push
+      .as_ref()
+      .map(|w| w.vapid_public_key.clone()),
+    push_providers: [
+      PushProvider::WebPush,
+      PushProvider::UnifiedPush,
+      PushProvider::Fcm,
+    ]
+    .into_iter()
+    .filter(|p| is_push_provider_enabled(*p, context.settings()))
+    .collect(),
+    site_view,
   })
 }
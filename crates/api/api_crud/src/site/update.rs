@@ -1,14 +1,29 @@
 use super::not_zero;
-use crate::site::{application_question_check, site_default_post_listing_type_check};
+use crate::site::{
+  account_deletion_grace_period_check,
+  application_question_check,
+  captcha_settings_check,
+  federation_concurrent_sends_check,
+  image_max_dimensions_check,
+  image_proxy_max_size_check,
+  image_upload_quota_check,
+  new_account_restrictions_check,
+  pow_challenge_difficulty_check,
+  registration_ip_settings_check,
+  site_default_post_listing_type_check,
+  video_limits_check,
+};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use chrono::Utc;
 use lemmy_api_utils::{
+  cache::invalidate_site,
   context::LemmyContext,
   utils::{
     get_url_blocklist,
     is_admin,
     local_site_rate_limit_to_rate_limit_config,
+    parse_ip_ranges,
     process_markdown_opt,
     slur_regex,
   },
@@ -19,6 +34,7 @@ use lemmy_db_schema::{
     actor_language::SiteLanguage,
     local_site::{LocalSite, LocalSiteUpdateForm},
     local_site_rate_limit::{LocalSiteRateLimit, LocalSiteRateLimitUpdateForm},
+    local_site_registration_ip_range::LocalSiteRegistrationIpRange,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
     local_user::LocalUser,
     site::{Site, SiteUpdateForm},
@@ -37,6 +53,7 @@ use lemmy_diesel_utils::{
 use lemmy_utils::{
   error::LemmyResult,
   utils::{
+    domain_pattern::build_domain_patterns,
     slurs::check_slurs_opt,
     validation::{
       build_and_check_regex,
@@ -140,6 +157,37 @@ pub async fn edit_site(
     image_max_upload_size: data.image_max_upload_size,
     image_allow_video_uploads: data.image_allow_video_uploads,
     image_upload_disabled: data.image_upload_disabled,
+    new_account_min_age_days: data.new_account_min_age_days,
+    new_account_min_karma: data.new_account_min_karma,
+    new_account_max_posts_per_day: diesel_opt_number_update(data.new_account_max_posts_per_day),
+    new_account_link_posts_disabled: data.new_account_link_posts_disabled,
+    new_account_image_upload_disabled: data.new_account_image_upload_disabled,
+    new_account_community_creation_disabled: data.new_account_community_creation_disabled,
+    moderators_require_totp: data.moderators_require_totp,
+    account_deletion_grace_period_days: data.account_deletion_grace_period_days,
+    registrations_per_ip_per_day: diesel_opt_number_update(data.registrations_per_ip_per_day),
+    registration_dnsbl: diesel_string_update(data.registration_dnsbl.as_deref()),
+    registration_ip_retention_days: data.registration_ip_retention_days,
+    pow_challenge_difficulty: diesel_opt_number_update(data.pow_challenge_difficulty),
+    authorized_fetch: data.authorized_fetch,
+    blocked_instances_patterns: diesel_string_update(data.blocked_instances_patterns.as_deref()),
+    allowed_instances_patterns: diesel_string_update(data.allowed_instances_patterns.as_deref()),
+    image_upload_quota_mb: diesel_opt_number_update(data.image_upload_quota_mb),
+    image_strip_metadata: data.image_strip_metadata,
+    image_convert_to_webp: data.image_convert_to_webp,
+    image_allow_animated: data.image_allow_animated,
+    image_max_width: diesel_opt_number_update(data.image_max_width),
+    image_max_height: diesel_opt_number_update(data.image_max_height),
+    image_proxy_markdown: data.image_proxy_markdown,
+    image_proxy_safelist: diesel_string_update(data.image_proxy_safelist.as_deref()),
+    image_proxy_max_size_mb: diesel_opt_number_update(data.image_proxy_max_size_mb),
+    captcha_provider: data.captcha_provider,
+    captcha_site_key: diesel_string_update(data.captcha_site_key.as_deref()),
+    captcha_secret_key: diesel_string_update(data.captcha_secret_key.as_deref()),
+    captcha_instance_url: diesel_string_update(data.captcha_instance_url.as_deref()),
+    federation_concurrent_sends_per_instance: data.federation_concurrent_sends_per_instance,
+    video_max_upload_size_mb: data.video_max_upload_size_mb,
+    video_max_duration_seconds: data.video_max_duration_seconds,
   };
 
   let update_local_site = LocalSite::update(&mut context.pool(), &local_site_form)
@@ -177,6 +225,16 @@ pub async fn edit_site(
     LocalSiteUrlBlocklist::replace(&mut context.pool(), parsed_urls).await?;
   }
 
+  if let Some(ip_ranges) = &data.registration_blocked_ip_ranges {
+    let ip_ranges = parse_ip_ranges(ip_ranges)?;
+    LocalSiteRegistrationIpRange::replace(&mut context.pool(), false, ip_ranges).await?;
+  }
+
+  if let Some(ip_ranges) = &data.registration_allowed_ip_ranges {
+    let ip_ranges = parse_ip_ranges(ip_ranges)?;
+    LocalSiteRegistrationIpRange::replace(&mut context.pool(), true, ip_ranges).await?;
+  }
+
   // TODO can't think of a better way to do this.
   // If the server suddenly requires email verification, or required applications, no old users
   // will be able to log in. It really only wants this to be a requirement for NEW signups.
@@ -207,6 +265,8 @@ pub async fn edit_site(
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
   context.rate_limit_cell().set_config(rate_limit_config);
 
+  invalidate_site();
+
   Ok(Json(SiteResponse { site_view }))
 }
 
@@ -233,6 +293,35 @@ fn validate_update_payload(local_site: &LocalSite, edit_site: &EditSite) -> Lemm
 
   site_default_post_listing_type_check(&edit_site.default_post_listing_type)?;
 
+  new_account_restrictions_check(
+    edit_site.new_account_min_age_days,
+    edit_site.new_account_min_karma,
+    edit_site.new_account_max_posts_per_day,
+  )?;
+  account_deletion_grace_period_check(edit_site.account_deletion_grace_period_days)?;
+  registration_ip_settings_check(
+    edit_site.registrations_per_ip_per_day,
+    edit_site.registration_ip_retention_days,
+  )?;
+  build_domain_patterns(edit_site.blocked_instances_patterns.as_deref())?;
+  build_domain_patterns(edit_site.allowed_instances_patterns.as_deref())?;
+  pow_challenge_difficulty_check(edit_site.pow_challenge_difficulty)?;
+  image_upload_quota_check(edit_site.image_upload_quota_mb)?;
+  image_max_dimensions_check(edit_site.image_max_width, edit_site.image_max_height)?;
+  image_proxy_max_size_check(edit_site.image_proxy_max_size_mb)?;
+  federation_concurrent_sends_check(edit_site.federation_concurrent_sends_per_instance)?;
+  video_limits_check(
+    edit_site.video_max_upload_size_mb,
+    edit_site.video_max_duration_seconds,
+  )?;
+  captcha_settings_check(
+    local_site,
+    edit_site.captcha_provider,
+    edit_site.captcha_site_key.as_deref(),
+    edit_site.captcha_secret_key.as_deref(),
+    edit_site.captcha_instance_url.as_deref(),
+  )?;
+
   // Ensure that the sidebar has fewer than the max num characters...
   if let Some(sidebar) = &edit_site.sidebar {
     is_valid_body_field(sidebar, false)?;
@@ -305,6 +394,36 @@ mod tests {
           ..Default::default()
         },
       ),
+      (
+        "EditSite has a negative minimum account age",
+        &LemmyErrorType::InvalidNewAccountRestriction,
+        &LocalSite {
+          private_instance: true,
+          federation_enabled: false,
+          registration_mode: RegistrationMode::Open,
+          ..Default::default()
+        },
+        &EditSite {
+          name: Some(String::from("site_name")),
+          new_account_min_age_days: Some(-1),
+          ..Default::default()
+        },
+      ),
+      (
+        "EditSite has a negative minimum karma",
+        &LemmyErrorType::InvalidNewAccountRestriction,
+        &LocalSite {
+          private_instance: true,
+          federation_enabled: false,
+          registration_mode: RegistrationMode::Open,
+          ..Default::default()
+        },
+        &EditSite {
+          name: Some(String::from("site_name")),
+          new_account_min_karma: Some(-5),
+          ..Default::default()
+        },
+      ),
       (
         "EditSite requires application, but neither it nor LocalSite has an application question",
         &LemmyErrorType::ApplicationQuestionRequired,
@@ -1,6 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
+  cache::invalidate_site,
   context::LemmyContext,
   utils::{get_url_blocklist, is_admin, process_markdown, slur_regex},
 };
@@ -37,5 +38,7 @@ pub async fn create_tagline(
 
   let tagline = Tagline::create(&mut context.pool(), &tagline_form).await?;
 
+  invalidate_site();
+
   Ok(Json(TaglineResponse { tagline }))
 }
@@ -2,6 +2,7 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use chrono::Utc;
 use lemmy_api_utils::{
+  cache::invalidate_site,
   context::LemmyContext,
   utils::{get_url_blocklist, is_admin, process_markdown, slur_regex},
 };
@@ -41,5 +42,7 @@ pub async fn edit_tagline(
 
   let tagline = Tagline::update(&mut context.pool(), data.id, &tagline_form).await?;
 
+  invalidate_site();
+
   Ok(Json(TaglineResponse { tagline }))
 }
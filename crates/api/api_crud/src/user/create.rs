@@ -6,21 +6,28 @@ use activitypub_federation::{
 use actix_web::{HttpRequest, rt::time::sleep, web::Json};
 use diesel_async::{AsyncPgConnection, scoped_futures::ScopedFutureExt};
 use lemmy_api_utils::{
+  captcha::validate_captcha,
   claims::Claims,
   context::LemmyContext,
-  plugins::{is_captcha_plugin_loaded, plugin_validate_captcha},
+  push::send_new_applicant_push_to_admins,
   utils::{
+    cancel_account_deletion,
     check_email_verified,
     check_local_user_valid,
+    check_pow_challenge,
     check_registration_application,
+    check_registration_ip,
+    client_ip,
     generate_featured_url,
     generate_followers_url,
     generate_inbox_url,
     generate_moderators_url,
     honeypot_check,
     password_length_check,
+    send_new_login_email_if_new_device,
     slur_regex,
   },
+  webhook::send_webhook_event,
 };
 use lemmy_apub_objects::objects::community::ApubCommunity;
 use lemmy_db_schema::{
@@ -39,7 +46,7 @@ use lemmy_db_schema::{
   },
   traits::{ApubActor, Likeable},
 };
-use lemmy_db_schema_file::enums::RegistrationMode;
+use lemmy_db_schema_file::enums::{RegistrationMode, WebhookEvent};
 use lemmy_db_views_community::CommunityView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::PersonView;
@@ -65,7 +72,7 @@ use lemmy_utils::{
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use std::{collections::HashSet, sync::LazyLock, time::Duration};
+use std::{collections::HashSet, net::IpAddr, sync::LazyLock, time::Duration};
 use tracing::info;
 
 #[skip_serializing_none]
@@ -111,12 +118,25 @@ pub async fn register(
     return Err(LemmyErrorType::PasswordsDoNotMatch.into());
   }
 
-  if local_site.site_setup && is_captcha_plugin_loaded() {
+  if local_site.site_setup {
     let answer = data.captcha_answer.clone().unwrap_or_default();
     let uuid = data.captcha_uuid.clone().unwrap_or_default();
-    plugin_validate_captcha(answer, uuid).await?;
+    validate_captcha(&local_site, answer, uuid, client_ip(&req), &context).await?;
+  }
+
+  if local_site.site_setup && local_site.pow_challenge_difficulty.is_some() {
+    check_pow_challenge(
+      data.pow_challenge.as_deref(),
+      data.pow_nonce.as_deref(),
+      pool,
+    )
+    .await?;
   }
 
+  let ip = client_ip(&req);
+  check_registration_ip(ip, &local_site, pool).await?;
+  let registration_ip = stored_registration_ip(ip, &local_site);
+
   let slur_regex = slur_regex(&context).await?;
   check_slurs(&data.username, &slur_regex)?;
   check_slurs_opt(&data.answer, &slur_regex)?;
@@ -154,6 +174,7 @@ pub async fn register(
           email: tx_data.email.as_deref().map(str::to_lowercase),
           show_nsfw: Some(show_nsfw),
           accepted_application,
+          registration_ip,
           ..LocalUserInsertForm::new(person.id, Some(tx_data.password.to_string()))
         };
 
@@ -189,16 +210,21 @@ pub async fn register(
       .scope_boxed()
     })
     .await?;
+  send_webhook_event(WebhookEvent::PersonCreated, &user.person, &context);
 
   // Email the admins, only if email verification is not required
   if local_site.application_email_admins && !local_site.email_verification_required {
     send_new_applicant_email_to_admins(&data.username, pool, context.settings()).await?;
   }
+  if require_registration_application && !local_site.email_verification_required {
+    send_new_applicant_push_to_admins(&data.username, &context).await?;
+  }
 
   let mut login_response = LoginResponse {
     jwt: None,
     registration_created: false,
     verify_email_sent: false,
+    refresh_token: None,
   };
 
   // Log the user in directly if the site is not setup, or email verification and application aren't
@@ -296,6 +322,7 @@ pub async fn authenticate_with_oauth(
     jwt: None,
     registration_created: false,
     verify_email_sent: false,
+    refresh_token: None,
   };
 
   // Lookup user by oauth_user_id
@@ -315,6 +342,8 @@ pub async fn authenticate_with_oauth(
     check_local_user_valid(&user_view)?;
     check_email_verified(&user_view, &site_view)?;
     check_registration_application(&user_view, &site_view.local_site, pool).await?;
+    cancel_account_deletion(&user_view, pool).await?;
+    send_new_login_email_if_new_device(&user_view, &req, &context).await?;
     local_user
   } else {
     // user has never previously registered using oauth
@@ -350,6 +379,7 @@ pub async fn authenticate_with_oauth(
         check_local_user_valid(&user_view)?;
         check_email_verified(&user_view, &site_view)?;
         check_registration_application(&user_view, &site_view.local_site, pool).await?;
+        cancel_account_deletion(&user_view, pool).await?;
 
         // Link with OAUTH => Login user
         let oauth_account_form =
@@ -367,6 +397,10 @@ pub async fn authenticate_with_oauth(
       // make sure the registration answer is provided when the registration application is required
       validate_registration_answer(require_registration_application, &data.answer)?;
 
+      let ip = client_ip(&req);
+      check_registration_ip(ip, &local_site, pool).await?;
+      let registration_ip = stored_registration_ip(ip, &local_site);
+
       let slur_regex = slur_regex(&context).await?;
 
       // Wrap the insert person, insert local user, and create registration,
@@ -397,6 +431,7 @@ pub async fn authenticate_with_oauth(
               show_nsfw: Some(show_nsfw),
               accepted_application: Some(!require_registration_application),
               email_verified: Some(oauth_provider.auto_verify_email),
+              registration_ip,
               ..LocalUserInsertForm::new(person.id, None)
             };
 
@@ -441,6 +476,7 @@ pub async fn authenticate_with_oauth(
           .scope_boxed()
         })
         .await?;
+      send_webhook_event(WebhookEvent::PersonCreated, &user.person, &context);
 
       // Check email is verified when required
       login_response.verify_email_sent = send_verification_email_if_required(
@@ -548,6 +584,12 @@ async fn create_local_user(
   Ok(inserted_local_user)
 }
 
+/// The registration ip is only stored if the site keeps it for some time.
+fn stored_registration_ip(ip: Option<IpAddr>, local_site: &LocalSite) -> Option<String> {
+  ip.filter(|_| local_site.registration_ip_retention_days > 0)
+    .map(|ip| ip.to_string())
+}
+
 fn validate_registration_answer(
   require_registration_application: bool,
   answer: &Option<String>,
@@ -1,19 +1,18 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use bcrypt::verify;
-use lemmy_api_utils::{
-  context::LemmyContext,
-  send_activity::{ActivityChannel, SendActivityData},
-  utils::purge_user_account,
-};
+use chrono::{Days, Utc};
+use lemmy_api_utils::{context::LemmyContext, utils::delete_user_account};
 use lemmy_db_schema::source::{
-  community::CommunityActions,
+  api_key::ApiKey,
+  local_user::{LocalUser, LocalUserUpdateForm},
   login_token::LoginToken,
-  oauth_account::OAuthAccount,
-  person::Person,
 };
 use lemmy_db_views_local_user::LocalUserView;
-use lemmy_db_views_site::api::{DeleteAccount, SuccessResponse};
+use lemmy_db_views_site::{
+  SiteView,
+  api::{DeleteAccount, SuccessResponse},
+};
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 
 pub async fn delete_account(
@@ -21,8 +20,6 @@ pub async fn delete_account(
   context: Data<LemmyContext>,
   local_user_view: LocalUserView,
 ) -> LemmyResult<Json<SuccessResponse>> {
-  let local_instance_id = local_user_view.person.instance_id;
-
   // Verify the password
   let valid: bool = local_user_view
     .local_user
@@ -34,31 +31,26 @@ pub async fn delete_account(
     return Err(LemmyErrorType::IncorrectLogin.into());
   }
 
-  if data.delete_content {
-    purge_user_account(local_user_view.person.id, local_instance_id, &context).await?;
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  let grace_period_days = u64::try_from(local_site.account_deletion_grace_period_days)?;
+  if grace_period_days == 0 {
+    delete_user_account(local_user_view, data.delete_content, &context).await?;
   } else {
-    // These are already run in purge_user_account,
-    // but should be done anyway even if delete_content is false
-    OAuthAccount::delete_user_accounts(&mut context.pool(), local_user_view.local_user.id).await?;
-    CommunityActions::leave_mod_team_for_all_communities(
-      &mut context.pool(),
-      local_user_view.person.id,
-    )
-    .await?;
-    Person::delete_account(
-      &mut context.pool(),
-      local_user_view.person.id,
-      local_instance_id,
-    )
-    .await?;
-  }
-
-  LoginToken::invalidate_all(&mut context.pool(), local_user_view.local_user.id).await?;
+    // Only mark the account, it is erased by a scheduled task after the grace period
+    let deletion_scheduled_at = Utc::now()
+      .checked_add_days(Days::new(grace_period_days))
+      .ok_or(LemmyErrorType::InvalidAccountDeletionGracePeriod)?;
+    let form = LocalUserUpdateForm {
+      deletion_scheduled_at: Some(Some(deletion_scheduled_at)),
+      deletion_delete_content: Some(data.delete_content),
+      ..Default::default()
+    };
+    LocalUser::update(&mut context.pool(), local_user_view.local_user.id, &form).await?;
 
-  ActivityChannel::submit_activity(
-    SendActivityData::DeleteUser(local_user_view.person, data.delete_content),
-    &context,
-  )?;
+    // Log out everywhere, logging in again cancels the deletion
+    LoginToken::invalidate_all(&mut context.pool(), local_user_view.local_user.id).await?;
+    ApiKey::delete_all_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+  }
 
   Ok(Json(SuccessResponse::default()))
 }
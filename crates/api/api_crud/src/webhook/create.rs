@@ -0,0 +1,49 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::webhook::{Webhook, WebhookInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateWebhook, WebhookResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub async fn create_webhook(
+  Json(data): Json<CreateWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  check_webhook_url(&data.url)?;
+  check_webhook_secret(&data.secret)?;
+
+  let webhook_form = WebhookInsertForm {
+    url: data.url.into(),
+    secret: data.secret,
+    post_created: data.post_created.unwrap_or_default(),
+    person_created: data.person_created.unwrap_or_default(),
+    report_created: data.report_created.unwrap_or_default(),
+    community_created: data.community_created.unwrap_or_default(),
+  };
+
+  let webhook = Webhook::create(&mut context.pool(), &webhook_form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
+
+pub(super) fn check_webhook_url(url: &Url) -> LemmyResult<()> {
+  if !["http", "https"].contains(&url.scheme()) {
+    return Err(LemmyErrorType::InvalidUrlScheme.into());
+  }
+  Ok(())
+}
+
+/// Receivers can only verify the payloads if there is a secret.
+pub(super) fn check_webhook_secret(secret: &str) -> LemmyResult<()> {
+  if secret.trim().is_empty() {
+    return Err(LemmyErrorType::InvalidBodyField.into());
+  }
+  Ok(())
+}
@@ -0,0 +1,21 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::webhook::Webhook;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeleteWebhook, SuccessResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_webhook(
+  Json(data): Json<DeleteWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  Webhook::delete(&mut context.pool(), data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
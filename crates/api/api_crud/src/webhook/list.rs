@@ -0,0 +1,18 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::webhook::Webhook;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListWebhooksResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_webhooks(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListWebhooksResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let webhooks = Webhook::list(&mut context.pool()).await?;
+
+  Ok(Json(ListWebhooksResponse { webhooks }))
+}
@@ -0,0 +1,26 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::webhook::WebhookDelivery;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListWebhookDeliveries;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_webhook_deliveries(
+  Query(data): Query<ListWebhookDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<WebhookDelivery>>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let deliveries = WebhookDelivery::list(
+    &mut context.pool(),
+    data.webhook_id,
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(deliveries))
+}
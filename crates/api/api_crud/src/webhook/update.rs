@@ -0,0 +1,41 @@
+use super::create::{check_webhook_secret, check_webhook_url};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::webhook::{Webhook, WebhookUpdateForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{EditWebhook, WebhookResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn edit_webhook(
+  Json(data): Json<EditWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  if let Some(url) = &data.url {
+    check_webhook_url(url)?;
+  }
+  if let Some(secret) = &data.secret {
+    check_webhook_secret(secret)?;
+  }
+
+  let webhook_form = WebhookUpdateForm {
+    url: data.url.map(Into::into),
+    secret: data.secret,
+    post_created: data.post_created,
+    person_created: data.person_created,
+    report_created: data.report_created,
+    community_created: data.community_created,
+    enabled: data.enabled,
+    updated_at: Some(Some(Utc::now())),
+  };
+
+  let webhook = Webhook::update(&mut context.pool(), data.id, &webhook_form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
@@ -0,0 +1,150 @@
+//! Caches for the heaviest read endpoints, which are shared by all requests of this process.
+//! Writes which change the cached data invalidate the affected entries, but only in the process
+//! which handles the write. Other processes pick up the change once the entries expire, so the
+//! durations are kept short.
+
+use lemmy_db_schema::newtypes::CommunityId;
+use lemmy_db_views_community::api::GetCommunityResponse;
+use lemmy_db_views_post::{PostView, api::GetPosts};
+use lemmy_db_views_site::{
+  FederatedInstanceView,
+  api::{GetFederatedInstances, GetSiteResponse},
+};
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::{LemmyError, LemmyResult};
+use moka::future::Cache;
+use std::{
+  hash::Hash,
+  sync::{Arc, LazyLock},
+  time::Duration,
+};
+
+// Api tests expect changes to be visible right away. Unit tests use the real durations to check
+// the invalidation.
+#[cfg(all(debug_assertions, not(test)))]
+const SITE_DURATION: Duration = Duration::from_secs(0);
+#[cfg(any(not(debug_assertions), test))]
+const SITE_DURATION: Duration = Duration::from_secs(60);
+
+#[cfg(all(debug_assertions, not(test)))]
+const COMMUNITY_DURATION: Duration = Duration::from_secs(0);
+#[cfg(any(not(debug_assertions), test))]
+const COMMUNITY_DURATION: Duration = Duration::from_secs(60);
+
+/// New posts and votes show up on the front page with this delay for anonymous users.
+#[cfg(all(debug_assertions, not(test)))]
+const POSTS_DURATION: Duration = Duration::from_secs(0);
+#[cfg(any(not(debug_assertions), test))]
+const POSTS_DURATION: Duration = Duration::from_secs(30);
+
+#[cfg(all(debug_assertions, not(test)))]
+const INSTANCES_DURATION: Duration = Duration::from_secs(0);
+#[cfg(any(not(debug_assertions), test))]
+const INSTANCES_DURATION: Duration = Duration::from_secs(300);
+
+/// Response of GetSite, including the fields which are only visible to admins.
+pub static SITE: LazyLock<Cache<(), GetSiteResponse>> =
+  LazyLock::new(|| build_cache(1, SITE_DURATION));
+
+/// Response of GetCommunity for anonymous users.
+pub static COMMUNITIES: LazyLock<Cache<CommunityId, GetCommunityResponse>> =
+  LazyLock::new(|| build_cache(1000, COMMUNITY_DURATION));
+
+/// Post listings for anonymous users, like the front page.
+pub static ANONYMOUS_POSTS: LazyLock<Cache<GetPosts, PagedResponse<PostView>>> =
+  LazyLock::new(|| build_cache(1000, POSTS_DURATION));
+
+pub static FEDERATED_INSTANCES: LazyLock<
+  Cache<GetFederatedInstances, PagedResponse<FederatedInstanceView>>,
+> = LazyLock::new(|| build_cache(100, INSTANCES_DURATION));
+
+fn build_cache<K, V>(max_capacity: u64, time_to_live: Duration) -> Cache<K, V>
+where
+  K: Eq + Hash + Send + Sync + 'static,
+  V: Clone + Send + Sync + 'static,
+{
+  Cache::builder()
+    .max_capacity(max_capacity)
+    .time_to_live(time_to_live)
+    .build()
+}
+
+/// Returns the cached value, or loads and caches it. Errors are returned with their type, but
+/// aren't cached.
+pub async fn get_or_load<K, V>(
+  cache: &Cache<K, V>,
+  key: K,
+  load: impl Future<Output = LemmyResult<V>>,
+) -> LemmyResult<V>
+where
+  K: Eq + Hash + Send + Sync + 'static,
+  V: Clone + Send + Sync + 'static,
+{
+  Box::pin(cache.try_get_with(key, load))
+    .await
+    .map_err(|e: Arc<LemmyError>| e.error_type.clone().into())
+}
+
+/// After changes to the site settings, admins or taglines. Post listings are also invalidated, as
+/// they depend on the site settings.
+pub fn invalidate_site() {
+  SITE.invalidate_all();
+  ANONYMOUS_POSTS.invalidate_all();
+}
+
+/// After changes to the community or its moderators.
+pub async fn invalidate_community(community_id: CommunityId) {
+  COMMUNITIES.invalidate(&community_id).await;
+}
+
+/// After posts were removed or deleted, so that they disappear right away. New posts show up once
+/// the listings expire.
+pub fn invalidate_posts() {
+  ANONYMOUS_POSTS.invalidate_all();
+}
+
+/// After instances were blocked or allowed.
+pub fn invalidate_instances() {
+  FEDERATED_INSTANCES.invalidate_all();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  async fn load_posts(loads: &AtomicUsize) -> LemmyResult<PagedResponse<PostView>> {
+    get_or_load(&ANONYMOUS_POSTS, GetPosts::default(), async {
+      loads.fetch_add(1, Ordering::Relaxed);
+      Ok(PagedResponse {
+        items: vec![],
+        next_page: None,
+        prev_page: None,
+      })
+    })
+    .await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_invalidate_posts() -> LemmyResult<()> {
+    let loads = AtomicUsize::new(0);
+    invalidate_posts();
+
+    load_posts(&loads).await?;
+    load_posts(&loads).await?;
+    assert_eq!(1, loads.load(Ordering::Relaxed));
+
+    invalidate_posts();
+    load_posts(&loads).await?;
+    assert_eq!(2, loads.load(Ordering::Relaxed));
+
+    // Site changes also affect post listings
+    invalidate_site();
+    load_posts(&loads).await?;
+    assert_eq!(3, loads.load(Ordering::Relaxed));
+    Ok(())
+  }
+}
@@ -0,0 +1,311 @@
+use crate::{
+  context::LemmyContext,
+  plugins::{is_captcha_plugin_loaded, plugin_get_captcha, plugin_validate_captcha},
+};
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_db_schema_file::enums::CaptchaProvider;
+use lemmy_db_views_site::api::CaptchaResponse;
+use lemmy_utils::{
+  REQWEST_TIMEOUT,
+  error::{LemmyErrorType, LemmyResult},
+};
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use url::form_urlencoded;
+
+const HCAPTCHA_VERIFY_URL: &str = "https://api.hcaptcha.com/siteverify";
+const TURNSTILE_VERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+
+/// A captcha which needs to be solved during registration.
+trait CaptchaBackend: Sized {
+  fn new(local_site: &LocalSite) -> LemmyResult<Self>;
+
+  /// Generates a new captcha, or returns `None` if the client renders it with the widget of the
+  /// provider.
+  async fn get_captcha(&self) -> LemmyResult<Option<CaptchaResponse>> {
+    Ok(None)
+  }
+
+  /// Checks the answer of the user. For external providers this is the token which is generated
+  /// by their widget, and `uuid` is ignored.
+  async fn verify(
+    &self,
+    answer: String,
+    uuid: String,
+    ip: Option<IpAddr>,
+    context: &LemmyContext,
+  ) -> LemmyResult<()>;
+}
+
+/// Image and audio captcha which is generated by the captcha plugin.
+struct InternalCaptcha;
+
+impl CaptchaBackend for InternalCaptcha {
+  fn new(_local_site: &LocalSite) -> LemmyResult<Self> {
+    Ok(InternalCaptcha)
+  }
+
+  async fn get_captcha(&self) -> LemmyResult<Option<CaptchaResponse>> {
+    plugin_get_captcha().await.map(Some)
+  }
+
+  async fn verify(
+    &self,
+    answer: String,
+    uuid: String,
+    _ip: Option<IpAddr>,
+    _context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    plugin_validate_captcha(answer, uuid).await
+  }
+}
+
+/// Keys for an external captcha provider, which are required by all of them.
+struct CaptchaKeys {
+  site_key: String,
+  secret_key: String,
+}
+
+impl CaptchaKeys {
+  fn new(local_site: &LocalSite) -> LemmyResult<Self> {
+    match (&local_site.captcha_site_key, &local_site.captcha_secret_key) {
+      (Some(site_key), Some(secret_key)) => Ok(CaptchaKeys {
+        site_key: site_key.clone(),
+        secret_key: secret_key.clone(),
+      }),
+      _ => Err(LemmyErrorType::CaptchaNotConfigured.into()),
+    }
+  }
+}
+
+struct HCaptcha(CaptchaKeys);
+
+impl CaptchaBackend for HCaptcha {
+  fn new(local_site: &LocalSite) -> LemmyResult<Self> {
+    CaptchaKeys::new(local_site).map(HCaptcha)
+  }
+
+  async fn verify(
+    &self,
+    answer: String,
+    _uuid: String,
+    ip: Option<IpAddr>,
+    context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    let mut form = siteverify_form(&self.0.secret_key, &answer, ip);
+    form.append_pair("sitekey", &self.0.site_key);
+    siteverify(HCAPTCHA_VERIFY_URL, form.finish(), context).await
+  }
+}
+
+struct Turnstile(CaptchaKeys);
+
+impl CaptchaBackend for Turnstile {
+  fn new(local_site: &LocalSite) -> LemmyResult<Self> {
+    CaptchaKeys::new(local_site).map(Turnstile)
+  }
+
+  async fn verify(
+    &self,
+    answer: String,
+    _uuid: String,
+    ip: Option<IpAddr>,
+    context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    let form = siteverify_form(&self.0.secret_key, &answer, ip).finish();
+    siteverify(TURNSTILE_VERIFY_URL, form, context).await
+  }
+}
+
+/// Form parameters which hCaptcha and Turnstile have in common.
+fn siteverify_form(
+  secret_key: &str,
+  answer: &str,
+  ip: Option<IpAddr>,
+) -> form_urlencoded::Serializer<'static, String> {
+  let mut form = form_urlencoded::Serializer::new(String::new());
+  form
+    .append_pair("secret", secret_key)
+    .append_pair("response", answer);
+  if let Some(ip) = ip {
+    form.append_pair("remoteip", &ip.to_string());
+  }
+  form
+}
+
+#[derive(Deserialize)]
+struct SiteverifyResponse {
+  success: bool,
+}
+
+async fn siteverify(url: &str, form: String, context: &LemmyContext) -> LemmyResult<()> {
+  let res: SiteverifyResponse = context
+    .client()
+    .post(url)
+    .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+    .timeout(REQWEST_TIMEOUT)
+    .body(form)
+    .send()
+    .await?
+    .error_for_status()?
+    .json()
+    .await?;
+  if res.success {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::CaptchaIncorrect.into())
+  }
+}
+
+/// Self-hosted proof of work captcha.
+struct MCaptcha {
+  keys: CaptchaKeys,
+  instance_url: String,
+}
+
+#[derive(Serialize)]
+struct MCaptchaVerifyRequest<'a> {
+  token: &'a str,
+  key: &'a str,
+  secret: &'a str,
+}
+
+#[derive(Deserialize)]
+struct MCaptchaVerifyResponse {
+  valid: bool,
+}
+
+impl CaptchaBackend for MCaptcha {
+  fn new(local_site: &LocalSite) -> LemmyResult<Self> {
+    let instance_url = local_site
+      .captcha_instance_url
+      .clone()
+      .ok_or(LemmyErrorType::CaptchaNotConfigured)?;
+    Ok(MCaptcha {
+      keys: CaptchaKeys::new(local_site)?,
+      instance_url,
+    })
+  }
+
+  async fn verify(
+    &self,
+    answer: String,
+    _uuid: String,
+    _ip: Option<IpAddr>,
+    context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    let url = format!(
+      "{}/api/v1/pow/siteverify",
+      self.instance_url.trim_end_matches('/')
+    );
+    let request = MCaptchaVerifyRequest {
+      token: &answer,
+      key: &self.keys.site_key,
+      secret: &self.keys.secret_key,
+    };
+    let res: MCaptchaVerifyResponse = context
+      .client()
+      .post(url)
+      .header(CONTENT_TYPE, "application/json")
+      .timeout(REQWEST_TIMEOUT)
+      .body(serde_json::to_string(&request)?)
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    if res.valid {
+      Ok(())
+    } else {
+      Err(LemmyErrorType::CaptchaIncorrect.into())
+    }
+  }
+}
+
+/// Returns true if users need to solve a captcha for registration.
+pub fn is_captcha_enabled(local_site: &LocalSite) -> bool {
+  match local_site.captcha_provider {
+    CaptchaProvider::Internal => is_captcha_plugin_loaded(),
+    CaptchaProvider::HCaptcha | CaptchaProvider::MCaptcha | CaptchaProvider::Turnstile => true,
+  }
+}
+
+/// Generates a new captcha with the configured provider. Returns `None` if captchas are disabled,
+/// or if the client renders them with the widget of an external provider.
+pub async fn get_captcha(local_site: &LocalSite) -> LemmyResult<Option<CaptchaResponse>> {
+  if !is_captcha_enabled(local_site) {
+    return Ok(None);
+  }
+  match local_site.captcha_provider {
+    CaptchaProvider::Internal => InternalCaptcha::new(local_site)?.get_captcha().await,
+    CaptchaProvider::HCaptcha => HCaptcha::new(local_site)?.get_captcha().await,
+    CaptchaProvider::MCaptcha => MCaptcha::new(local_site)?.get_captcha().await,
+    CaptchaProvider::Turnstile => Turnstile::new(local_site)?.get_captcha().await,
+  }
+}
+
+/// Checks the captcha answer of a new registration with the configured provider.
+pub async fn validate_captcha(
+  local_site: &LocalSite,
+  answer: String,
+  uuid: String,
+  ip: Option<IpAddr>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if !is_captcha_enabled(local_site) {
+    return Ok(());
+  }
+  if answer.is_empty() {
+    return Err(LemmyErrorType::CaptchaIncorrect.into());
+  }
+  match local_site.captcha_provider {
+    CaptchaProvider::Internal => verify::<InternalCaptcha>(local_site, answer, uuid, ip, context),
+    CaptchaProvider::HCaptcha => verify::<HCaptcha>(local_site, answer, uuid, ip, context),
+    CaptchaProvider::MCaptcha => verify::<MCaptcha>(local_site, answer, uuid, ip, context),
+    CaptchaProvider::Turnstile => verify::<Turnstile>(local_site, answer, uuid, ip, context),
+  }
+  .await
+}
+
+async fn verify<B: CaptchaBackend>(
+  local_site: &LocalSite,
+  answer: String,
+  uuid: String,
+  ip: Option<IpAddr>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  B::new(local_site)?.verify(answer, uuid, ip, context).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_captcha_backend_config() {
+    let mut local_site = LocalSite {
+      captcha_provider: CaptchaProvider::MCaptcha,
+      captcha_site_key: Some("site".to_string()),
+      ..Default::default()
+    };
+    assert!(is_captcha_enabled(&local_site));
+    assert!(HCaptcha::new(&local_site).is_err());
+
+    local_site.captcha_secret_key = Some("secret".to_string());
+    assert!(Turnstile::new(&local_site).is_ok());
+    assert!(MCaptcha::new(&local_site).is_err());
+
+    local_site.captcha_instance_url = Some("https://mcaptcha.example.com/".to_string());
+    assert!(MCaptcha::new(&local_site).is_ok());
+  }
+
+  #[test]
+  fn test_siteverify_form() {
+    let form = siteverify_form("secret", "token", "127.0.0.1".parse().ok()).finish();
+    assert_eq!("secret=secret&response=token&remoteip=127.0.0.1", form);
+    let form = siteverify_form("se&cret", "token", None).finish();
+    assert_eq!("secret=se%26cret&response=token", form);
+  }
+}
@@ -1,14 +1,27 @@
-use crate::context::LemmyContext;
+use crate::{context::LemmyContext, utils::hash_token};
 use actix_web::{HttpRequest, http::header::USER_AGENT};
 use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use lemmy_db_schema::{
-  newtypes::LocalUserId,
+  newtypes::{LocalUserId, OAuthApplicationId},
   source::login_token::{LoginToken, LoginTokenCreateForm},
 };
+use lemmy_db_schema_file::enums::TokenScope;
 use lemmy_diesel_utils::sensitive::SensitiveString;
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+/// How long tokens of third-party apps are valid.
+pub const APPLICATION_TOKEN_VALIDITY: Duration = Duration::days(30);
+
+/// How long access tokens are valid, if the session uses a refresh token.
+pub const ACCESS_TOKEN_VALIDITY: Duration = Duration::minutes(15);
+
+/// How long refresh tokens are valid. Every refresh issues a new one, so the session only ends
+/// if it isn't used for this long.
+pub const REFRESH_TOKEN_VALIDITY: Duration = Duration::days(30);
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Claims {
@@ -23,15 +36,21 @@ pub struct Claims {
 }
 
 impl Claims {
-  pub async fn validate(jwt: &str, context: &LemmyContext) -> LemmyResult<LocalUserId> {
+  pub async fn validate(jwt: &str, context: &LemmyContext) -> LemmyResult<LoginToken> {
     let validation = Validation::default();
     let jwt_secret = &context.secret().jwt_secret;
     let key = DecodingKey::from_secret(jwt_secret.as_ref());
     let claims =
       decode::<Claims>(jwt, &key, &validation).with_lemmy_type(LemmyErrorType::NotLoggedIn)?;
     let user_id = LocalUserId(claims.claims.sub.parse()?);
-    LoginToken::validate(&mut context.pool(), user_id, jwt).await?;
-    Ok(user_id)
+    let login_token = LoginToken::validate(&mut context.pool(), user_id, jwt).await?;
+    // Only informational, so a failed update shouldn't prevent the login
+    if login_token.last_used_at < Utc::now() - Duration::hours(1)
+      && let Err(e) = LoginToken::update_last_used(&mut context.pool(), jwt).await
+    {
+      warn!("Failed to update last use of login token: {e}");
+    }
+    Ok(login_token)
   }
 
   pub async fn generate(
@@ -40,15 +59,130 @@ impl Claims {
     req: HttpRequest,
     context: &LemmyContext,
   ) -> LemmyResult<SensitiveString> {
-    let hostname = context.settings().hostname.clone();
-    let now = Utc::now();
     let exp = if stay_logged_in.unwrap_or_default() {
       // Login doesnt expire
       DateTime::<Utc>::MAX_UTC
     } else {
       // Login expires after one week
-      now + Duration::weeks(1)
+      Utc::now() + Duration::weeks(1)
+    };
+    Self::generate_inner(user_id, exp, None, None, None, req, context).await
+  }
+
+  /// Generate a short-lived access token, and a refresh token which can be used to get new ones.
+  pub async fn generate_with_refresh_token(
+    user_id: LocalUserId,
+    req: HttpRequest,
+    context: &LemmyContext,
+  ) -> LemmyResult<(SensitiveString, SensitiveString)> {
+    let refresh_token = new_refresh_token();
+    let jwt = Self::generate_inner(
+      user_id,
+      Utc::now() + ACCESS_TOKEN_VALIDITY,
+      None,
+      None,
+      Some(hash_token(&refresh_token)),
+      req,
+      context,
+    )
+    .await?;
+    Ok((jwt, refresh_token.into()))
+  }
+
+  /// Exchange a refresh token for a new access token and refresh token. The previous access
+  /// token stops working. If a refresh token is used twice, it was probably leaked, so the whole
+  /// session is revoked.
+  pub async fn refresh(
+    refresh_token: &str,
+    context: &LemmyContext,
+  ) -> LemmyResult<(SensitiveString, SensitiveString)> {
+    let old_refresh_token_hash = hash_token(refresh_token);
+    let new_refresh_token = new_refresh_token();
+    let login_token =
+      LoginToken::read_by_refresh_token(&mut context.pool(), &old_refresh_token_hash).await;
+    let Ok(login_token) = login_token else {
+      let revoked = LoginToken::invalidate_by_revoked_refresh_token(
+        &mut context.pool(),
+        &old_refresh_token_hash,
+      )
+      .await?;
+      if revoked > 0 {
+        warn!("Refresh token was used twice, revoked the session");
+      }
+      return Err(LemmyErrorType::NotLoggedIn.into());
+    };
+
+    let jwt = Self::encode(
+      login_token.user_id,
+      Utc::now() + ACCESS_TOKEN_VALIDITY,
+      context,
+    )?;
+    LoginToken::rotate_refresh_token(
+      &mut context.pool(),
+      &old_refresh_token_hash,
+      jwt.clone(),
+      hash_token(&new_refresh_token),
+      Utc::now() + REFRESH_TOKEN_VALIDITY,
+    )
+    .await?;
+    Ok((jwt, new_refresh_token.into()))
+  }
+
+  /// Generate a token for a third-party app, which is valid until it expires or the user revokes
+  /// it.
+  pub async fn generate_for_application(
+    user_id: LocalUserId,
+    oauth_application_id: OAuthApplicationId,
+    scope: TokenScope,
+    req: HttpRequest,
+    context: &LemmyContext,
+  ) -> LemmyResult<SensitiveString> {
+    Self::generate_inner(
+      user_id,
+      Utc::now() + APPLICATION_TOKEN_VALIDITY,
+      Some(oauth_application_id),
+      Some(scope),
+      None,
+      req,
+      context,
+    )
+    .await
+  }
+
+  async fn generate_inner(
+    user_id: LocalUserId,
+    exp: DateTime<Utc>,
+    oauth_application_id: Option<OAuthApplicationId>,
+    scope: Option<TokenScope>,
+    refresh_token_hash: Option<String>,
+    req: HttpRequest,
+    context: &LemmyContext,
+  ) -> LemmyResult<SensitiveString> {
+    let token = Self::encode(user_id, exp, context)?;
+    let refresh_token_expires_at = refresh_token_hash
+      .is_some()
+      .then(|| Utc::now() + REFRESH_TOKEN_VALIDITY);
+    let form = LoginTokenCreateForm {
+      token: token.clone(),
+      user_id,
+      ip: login_ip(&req),
+      user_agent: login_user_agent(&req),
+      oauth_application_id,
+      scope,
+      refresh_token_hash,
+      refresh_token_expires_at,
     };
+    LoginToken::create(&mut context.pool(), form).await?;
+    Ok(token)
+  }
+
+  fn encode(
+    user_id: LocalUserId,
+    exp: DateTime<Utc>,
+    context: &LemmyContext,
+  ) -> LemmyResult<SensitiveString> {
+    let hostname = context.settings().hostname.clone();
+    let now = Utc::now();
     let my_claims = Claims {
       sub: user_id.0.to_string(),
       iss: hostname,
@@ -58,27 +192,29 @@ impl Claims {
 
     let secret = &context.secret().jwt_secret;
     let key = EncodingKey::from_secret(secret.as_ref());
-    let token: SensitiveString = encode(&Header::default(), &my_claims, &key)?.into();
-    let ip = req
-      .connection_info()
-      .realip_remote_addr()
-      .map(ToString::to_string);
-    let user_agent = req
-      .headers()
-      .get(USER_AGENT)
-      .and_then(|ua| ua.to_str().ok())
-      .map(ToString::to_string);
-    let form = LoginTokenCreateForm {
-      token: token.clone(),
-      user_id,
-      ip,
-      user_agent,
-    };
-    LoginToken::create(&mut context.pool(), form).await?;
-    Ok(token)
+    Ok(encode(&Header::default(), &my_claims, &key)?.into())
   }
 }
 
+fn new_refresh_token() -> String {
+  format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+pub(crate) fn login_ip(req: &HttpRequest) -> Option<String> {
+  req
+    .connection_info()
+    .realip_remote_addr()
+    .map(ToString::to_string)
+}
+
+pub(crate) fn login_user_agent(req: &HttpRequest) -> Option<String> {
+  req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|ua| ua.to_str().ok())
+    .map(ToString::to_string)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,341 @@
+use crate::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+};
+use activitypub_federation::config::Data;
+use lemmy_db_schema::{
+  source::{
+    comment::Comment,
+    comment_report::{CommentReport, CommentReportForm},
+    local_site::LocalSite,
+    post::{Post, PostUpdateForm},
+    post_report::{PostReport, PostReportForm},
+  },
+  traits::Reportable,
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  REQWEST_TIMEOUT,
+  error::{LemmyErrorType, LemmyResult},
+  settings::structs::ClassifierConfig,
+  spawn_try_task,
+};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use url::Url;
+
+/// The kind of content which is sent for classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassifiedContentType {
+  Post,
+  Comment,
+  Upload,
+}
+
+/// The data which is sent to the classifier.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClassificationRequest {
+  #[serde(rename = "type")]
+  pub type_: ClassifiedContentType,
+  pub ap_id: Option<Url>,
+  pub text: Option<String>,
+  /// Links and images which belong to the content.
+  pub urls: Vec<Url>,
+}
+
+/// Labels returned by the classifier. All of them are optional, so that a classifier only needs
+/// to implement the ones it supports.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ClassificationLabels {
+  pub nsfw: Option<bool>,
+  /// Likelihood that the content is spam, between 0 and 1.
+  pub spam_score: Option<f32>,
+}
+
+impl ClassificationLabels {
+  fn is_nsfw(&self) -> bool {
+    self.nsfw.unwrap_or_default()
+  }
+
+  /// Content which isn't marked as nsfw by its creator gets marked automatically.
+  fn should_mark_nsfw(&self, already_nsfw: bool) -> bool {
+    self.is_nsfw() && !already_nsfw
+  }
+
+  /// Uploads can't be marked, so they are rejected if nsfw isn't allowed.
+  fn should_reject_upload(&self, nsfw_content_disallowed: bool) -> bool {
+    self.is_nsfw() && nsfw_content_disallowed
+  }
+
+  /// Returns the report reason if the content should be reported as spam.
+  fn spam_report_reason(&self, threshold: f32) -> Option<String> {
+    self
+      .spam_score
+      .filter(|s| *s >= threshold)
+      .map(|s| format!("Automatically reported as likely spam (score {s:.2})"))
+  }
+}
+
+/// Integration point for automatic content classification, so that admins can plug in their
+/// own models.
+pub trait ContentClassifier {
+  fn classify(
+    &self,
+    request: &ClassificationRequest,
+    context: &LemmyContext,
+  ) -> impl Future<Output = LemmyResult<ClassificationLabels>> + Send;
+
+  /// Content with a spam score of at least this value is reported.
+  fn spam_report_threshold(&self) -> f32;
+}
+
+/// Sends content as JSON to the webhook configured in [ClassifierConfig].
+pub struct WebhookClassifier {
+  config: ClassifierConfig,
+}
+
+impl WebhookClassifier {
+  /// Returns `None` if no classifier is configured.
+  pub fn from_context(context: &LemmyContext) -> Option<Self> {
+    context
+      .settings()
+      .classifier
+      .clone()
+      .map(|config| WebhookClassifier { config })
+  }
+}
+
+impl ContentClassifier for WebhookClassifier {
+  async fn classify(
+    &self,
+    request: &ClassificationRequest,
+    context: &LemmyContext,
+  ) -> LemmyResult<ClassificationLabels> {
+    let mut req = context
+      .client()
+      .post(self.config.url.as_str())
+      .header(CONTENT_TYPE, "application/json")
+      .timeout(REQWEST_TIMEOUT)
+      .body(serde_json::to_string(request)?);
+    if let Some(api_key) = &self.config.api_key {
+      req = req.header(AUTHORIZATION, format!("Bearer {api_key}"));
+    }
+    let res = req.send().await?.error_for_status()?;
+    Ok(res.json().await?)
+  }
+
+  fn spam_report_threshold(&self) -> f32 {
+    self.config.spam_report_threshold
+  }
+}
+
+/// Classify a new local post in the background. If the classifier labels it as nsfw, the post
+/// gets marked as such and an update is federated. Likely spam is reported to the community
+/// mods.
+pub fn classify_post(post: Post, context: Data<LemmyContext>) {
+  let Some(classifier) = WebhookClassifier::from_context(&context) else {
+    return;
+  };
+  spawn_try_task(classify_post_with(classifier, post, context));
+}
+
+async fn classify_post_with(
+  classifier: impl ContentClassifier,
+  post: Post,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let text = match &post.body {
+    Some(body) => format!("{}\n\n{body}", post.name),
+    None => post.name.clone(),
+  };
+  let request = ClassificationRequest {
+    type_: ClassifiedContentType::Post,
+    ap_id: Some(post.ap_id.inner().clone()),
+    text: Some(text),
+    urls: [&post.url, &post.thumbnail_url]
+      .into_iter()
+      .flatten()
+      .map(|u| u.inner().clone())
+      .collect(),
+  };
+  let labels = classifier.classify(&request, &context).await?;
+
+  if labels.should_mark_nsfw(post.nsfw) {
+    let form = PostUpdateForm {
+      nsfw: Some(true),
+      ..Default::default()
+    };
+    let updated_post = Post::update(&mut context.pool(), post.id, &form).await?;
+    // Scheduled posts are federated with the updated value once they get published
+    if updated_post.scheduled_publish_time_at.is_none() {
+      ActivityChannel::submit_activity(SendActivityData::UpdatePost(updated_post), &context)?;
+    }
+  }
+
+  if let Some(reason) = labels.spam_report_reason(classifier.spam_report_threshold()) {
+    let system_account = SiteView::read_system_account(&mut context.pool()).await?;
+    let form = PostReportForm {
+      creator_id: system_account.id,
+      post_id: post.id,
+      original_post_name: post.name,
+      original_post_url: post.url,
+      original_post_body: post.body,
+      reason,
+      violates_instance_rules: false,
+    };
+    PostReport::report(&mut context.pool(), &form).await?;
+  }
+  Ok(())
+}
+
+/// Classify a new local comment in the background, and report it to the community mods if it
+/// is likely spam.
+pub fn classify_comment(comment: Comment, context: Data<LemmyContext>) {
+  let Some(classifier) = WebhookClassifier::from_context(&context) else {
+    return;
+  };
+  spawn_try_task(classify_comment_with(classifier, comment, context));
+}
+
+async fn classify_comment_with(
+  classifier: impl ContentClassifier,
+  comment: Comment,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let request = ClassificationRequest {
+    type_: ClassifiedContentType::Comment,
+    ap_id: Some(comment.ap_id.inner().clone()),
+    text: Some(comment.content.clone()),
+    urls: vec![],
+  };
+  let labels = classifier.classify(&request, &context).await?;
+
+  if let Some(reason) = labels.spam_report_reason(classifier.spam_report_threshold()) {
+    let system_account = SiteView::read_system_account(&mut context.pool()).await?;
+    let form = CommentReportForm {
+      creator_id: system_account.id,
+      comment_id: comment.id,
+      original_comment_text: comment.content,
+      reason,
+      violates_instance_rules: false,
+    };
+    CommentReport::report(&mut context.pool(), &form).await?;
+  }
+  Ok(())
+}
+
+/// Classify a new upload before returning it to the user. Returns an error if the upload is
+/// labeled as nsfw, but nsfw content is disallowed on this instance. If the classifier is
+/// unavailable the upload is allowed, so that it doesn't break uploads for everyone.
+pub async fn classify_upload(
+  image_url: &Url,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let Some(classifier) = WebhookClassifier::from_context(context) else {
+    return Ok(());
+  };
+  classify_upload_with(&classifier, image_url, local_site, context).await
+}
+
+async fn classify_upload_with(
+  classifier: &impl ContentClassifier,
+  image_url: &Url,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let request = ClassificationRequest {
+    type_: ClassifiedContentType::Upload,
+    ap_id: None,
+    text: None,
+    urls: vec![image_url.clone()],
+  };
+  let labels = match classifier.classify(&request, context).await {
+    Ok(labels) => labels,
+    Err(e) => {
+      warn!("Failed to classify upload {image_url}: {e}");
+      return Ok(());
+    }
+  };
+  if labels.should_reject_upload(local_site.nsfw_content_disallowed) {
+    info!("Rejected upload {image_url} which was classified as nsfw");
+    return Err(LemmyErrorType::NsfwNotAllowed.into());
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{ClassificationLabels, ClassificationRequest, ClassifiedContentType};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use url::Url;
+
+  #[test]
+  fn test_serialize_request() -> LemmyResult<()> {
+    let request = ClassificationRequest {
+      type_: ClassifiedContentType::Upload,
+      ap_id: None,
+      text: None,
+      urls: vec![Url::parse("https://example.com/pictrs/image/abc.png")?],
+    };
+    assert_eq!(
+      r#"{"type":"upload","ap_id":null,"text":null,"urls":["https://example.com/pictrs/image/abc.png"]}"#,
+      serde_json::to_string(&request)?
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_labels() -> LemmyResult<()> {
+    let labels: ClassificationLabels = serde_json::from_str(r#"{"nsfw":true,"spam_score":0.9}"#)?;
+    assert!(labels.is_nsfw());
+    assert!(labels.spam_report_reason(0.8).is_some());
+    assert!(labels.spam_report_reason(0.95).is_none());
+
+    // Classifiers may only return some of the labels, or additional ones
+    let labels: ClassificationLabels = serde_json::from_str(r#"{"toxicity":0.1}"#)?;
+    assert_eq!(ClassificationLabels::default(), labels);
+    assert!(!labels.is_nsfw());
+    assert!(labels.spam_report_reason(0.0).is_none());
+    Ok(())
+  }
+
+  #[test]
+  fn test_nsfw_decision() {
+    let nsfw = ClassificationLabels {
+      nsfw: Some(true),
+      ..Default::default()
+    };
+    assert!(nsfw.should_mark_nsfw(false));
+    // Already marked by the creator, nothing to update
+    assert!(!nsfw.should_mark_nsfw(true));
+    assert!(nsfw.should_reject_upload(true));
+    assert!(!nsfw.should_reject_upload(false));
+
+    let safe = ClassificationLabels {
+      nsfw: Some(false),
+      spam_score: Some(0.1),
+    };
+    assert!(!safe.should_mark_nsfw(false));
+    assert!(!safe.should_reject_upload(true));
+  }
+
+  #[test]
+  fn test_spam_report_reason() {
+    let labels = ClassificationLabels {
+      nsfw: None,
+      spam_score: Some(0.8),
+    };
+    // The threshold is inclusive
+    assert_eq!(
+      Some("Automatically reported as likely spam (score 0.80)".to_string()),
+      labels.spam_report_reason(0.8)
+    );
+    assert_eq!(None, labels.spam_report_reason(0.81));
+  }
+}
@@ -0,0 +1,240 @@
+//! Conditional requests for read endpoints which return the same data to all anonymous users.
+//! Clients and CDNs can revalidate a response with `If-None-Match` or `If-Modified-Since`, and get
+//! an empty `304 Not Modified` if nothing changed. Listings can also leave out heavy fields, for
+//! clients which only need a few of them.
+
+use actix_web::{
+  HttpRequest,
+  HttpResponse,
+  Responder,
+  body::BoxBody,
+  http::header::{
+    self,
+    CacheControl,
+    CacheDirective,
+    ETag,
+    EntityTag,
+    Header,
+    IfModifiedSince,
+    IfNoneMatch,
+    LastModified,
+  },
+};
+use chrono::{DateTime, Utc};
+use lemmy_utils::VERSION;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+  hash::{DefaultHasher, Hash, Hasher},
+  time::SystemTime,
+};
+
+/// Json response with optional validators for conditional requests.
+pub struct ConditionalJson<T> {
+  pub data: T,
+  etag: Option<EntityTag>,
+  last_modified: Option<DateTime<Utc>>,
+  omitted_fields: &'static [&'static str],
+}
+
+impl<T> ConditionalJson<T> {
+  /// Response without validators, for data which depends on the user.
+  pub fn new(data: T) -> Self {
+    Self {
+      data,
+      etag: None,
+      last_modified: None,
+      omitted_fields: &[],
+    }
+  }
+
+  /// Leaves out the given fields of each item in a paged response, or of the response itself.
+  /// Nested fields are separated by dots, like `post.score`.
+  pub fn omit_fields(mut self, fields: &'static [&'static str]) -> Self {
+    self.omitted_fields = fields;
+    self
+  }
+
+  /// Adds a weak ETag, which changes whenever the given state changes. The state consists of
+  /// update timestamps, and of counters which change without updating a timestamp.
+  pub fn etag(mut self, state: impl Hash) -> Self {
+    let mut hasher = DefaultHasher::new();
+    // The format of responses may change with a new version
+    VERSION.hash(&mut hasher);
+    state.hash(&mut hasher);
+    self.etag = Some(EntityTag::new_weak(format!("{:x}", hasher.finish())));
+    self
+  }
+
+  /// Adds a `Last-Modified` header with the newest of the timestamps. Only for clients which don't
+  /// support ETags, so changes of counters are ignored.
+  pub fn last_modified(mut self, timestamps: impl IntoIterator<Item = DateTime<Utc>>) -> Self {
+    self.last_modified = timestamps.into_iter().max();
+    self
+  }
+
+  fn is_not_modified(&self, req: &HttpRequest) -> bool {
+    // If-None-Match takes precedence, as it is more precise
+    if req.headers().contains_key(header::IF_NONE_MATCH) {
+      return match (IfNoneMatch::parse(req), &self.etag) {
+        (Ok(IfNoneMatch::Any), Some(_)) => true,
+        (Ok(IfNoneMatch::Items(tags)), Some(etag)) => tags.iter().any(|t| t.weak_eq(etag)),
+        _ => false,
+      };
+    }
+    match (IfModifiedSince::parse(req), self.last_modified) {
+      // Http dates only have second precision
+      (Ok(IfModifiedSince(since)), Some(last_modified)) => {
+        last_modified.timestamp() <= DateTime::<Utc>::from(SystemTime::from(since)).timestamp()
+      }
+      _ => false,
+    }
+  }
+}
+
+impl<T: Serialize> Responder for ConditionalJson<T> {
+  type Body = BoxBody;
+
+  fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+    let not_modified = self.is_not_modified(req);
+    let mut res = if not_modified {
+      HttpResponse::NotModified()
+    } else {
+      HttpResponse::Ok()
+    };
+    if let Some(etag) = &self.etag {
+      res.insert_header(ETag(etag.clone()));
+    }
+    if let Some(last_modified) = self.last_modified {
+      res.insert_header(LastModified(SystemTime::from(last_modified).into()));
+    }
+    if self.etag.is_some() || self.last_modified.is_some() {
+      // Caches need to revalidate before each use, and logged in users get different data
+      res.insert_header(CacheControl(vec![CacheDirective::NoCache]));
+      res.insert_header((header::VARY, "Authorization, Cookie"));
+    }
+    if not_modified {
+      res.finish()
+    } else if self.omitted_fields.is_empty() {
+      res.json(self.data)
+    } else {
+      match serde_json::to_value(&self.data) {
+        Ok(mut value) => {
+          omit_fields(&mut value, self.omitted_fields);
+          res.json(value)
+        }
+        // Fails again and returns the error
+        Err(_) => res.json(self.data),
+      }
+    }
+  }
+}
+
+fn omit_fields(value: &mut Value, fields: &[&str]) {
+  if let Some(Value::Array(items)) = value.get_mut("items") {
+    for item in items {
+      fields.iter().for_each(|f| omit_field(item, f));
+    }
+  } else {
+    fields.iter().for_each(|f| omit_field(value, f));
+  }
+}
+
+fn omit_field(value: &mut Value, field: &str) {
+  if let Value::Object(map) = value {
+    match field.split_once('.') {
+      Some((first, rest)) => {
+        if let Some(nested) = map.get_mut(first) {
+          omit_field(nested, rest);
+        }
+      }
+      None => {
+        map.remove(field);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use actix_web::{http::StatusCode, test::TestRequest};
+  use chrono::TimeDelta;
+  use pretty_assertions::{assert_eq, assert_ne};
+  use serde_json::json;
+
+  #[test]
+  fn test_etag() {
+    let etag = |state| ConditionalJson::new(()).etag(state).etag;
+    assert_eq!(etag(1), etag(1));
+    assert_ne!(etag(1), etag(2));
+  }
+
+  #[test]
+  fn test_conditional_request() {
+    let updated_at = Utc::now() - TimeDelta::hours(1);
+    let response = || {
+      ConditionalJson::new("data")
+        .etag(updated_at)
+        .last_modified([updated_at])
+    };
+    let etag = response().etag;
+    assert!(etag.is_some());
+
+    let req = TestRequest::default().to_http_request();
+    assert_eq!(StatusCode::OK, response().respond_to(&req).status());
+
+    let req = TestRequest::default()
+      .insert_header(IfNoneMatch::Items(etag.into_iter().collect()))
+      .to_http_request();
+    assert_eq!(
+      StatusCode::NOT_MODIFIED,
+      response().respond_to(&req).status()
+    );
+
+    let other = EntityTag::new_weak("other".to_string());
+    let req = TestRequest::default()
+      .insert_header(IfNoneMatch::Items(vec![other]))
+      .to_http_request();
+    assert_eq!(StatusCode::OK, response().respond_to(&req).status());
+
+    let since = SystemTime::from(Utc::now()).into();
+    let req = TestRequest::default()
+      .insert_header(IfModifiedSince(since))
+      .to_http_request();
+    assert_eq!(
+      StatusCode::NOT_MODIFIED,
+      response().respond_to(&req).status()
+    );
+
+    let since = SystemTime::from(updated_at - TimeDelta::hours(1)).into();
+    let req = TestRequest::default()
+      .insert_header(IfModifiedSince(since))
+      .to_http_request();
+    assert_eq!(StatusCode::OK, response().respond_to(&req).status());
+  }
+
+  #[test]
+  fn test_omit_fields() {
+    let mut value = json!({
+      "items": [
+        { "post": { "id": 1, "score": 5 }, "creator": { "id": 2 } },
+        { "post": { "id": 3 } },
+      ],
+      "next_page": "abc",
+    });
+    omit_fields(&mut value, &["post.score", "creator", "community"]);
+    let expected = json!({
+      "items": [
+        { "post": { "id": 1 } },
+        { "post": { "id": 3 } },
+      ],
+      "next_page": "abc",
+    });
+    assert_eq!(expected, value);
+
+    let mut value = json!({ "post": { "id": 1 }, "creator": { "id": 2 } });
+    omit_fields(&mut value, &["creator"]);
+    assert_eq!(json!({ "post": { "id": 1 } }), value);
+  }
+}
@@ -1,8 +1,18 @@
 pub mod build_response;
+pub mod cache;
+pub mod captcha;
 pub mod claims;
+pub mod classifier;
+pub mod conditional;
 pub mod context;
+pub mod link_metadata;
 pub mod notify;
 pub mod plugins;
+pub mod push;
 pub mod request;
+pub mod search;
 pub mod send_activity;
+pub mod stream;
 pub mod utils;
+pub mod video;
+pub mod webhook;
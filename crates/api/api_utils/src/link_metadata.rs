@@ -0,0 +1,425 @@
+use crate::{
+  context::LemmyContext,
+  request::{check_url_is_public, collect_bytes_until_limit},
+};
+use encoding_rs::{Encoding, UTF_8};
+use lemmy_db_schema::source::link_metadata_override::LinkMetadataOverride;
+use lemmy_db_views_post::api::OpenGraphData;
+use lemmy_utils::{REQWEST_TIMEOUT, error::LemmyResult};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{collections::HashMap, sync::LazyLock};
+use url::Url;
+use webpage::{HTML, OpengraphObject};
+
+/// oEmbed endpoints of popular sites which don't link them from all of their pages, or which
+/// can't be scraped at all. https://oembed.com/providers.json
+const OEMBED_PROVIDERS: [(&str, &str); 4] = [
+  ("youtube.com", "https://www.youtube.com/oembed"),
+  ("youtu.be", "https://www.youtube.com/oembed"),
+  ("vimeo.com", "https://vimeo.com/api/oembed.json"),
+  ("soundcloud.com", "https://soundcloud.com/oembed"),
+];
+
+/// oEmbed responses are small, don't read more than this.
+const OEMBED_MAX_BYTES: usize = 64 * 1024;
+
+#[expect(clippy::expect_used)]
+static OEMBED_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(r#"(?i)<link\s[^>]*type\s*=\s*["']application/json\+oembed["'][^>]*>"#)
+    .expect("compile regex")
+});
+#[expect(clippy::expect_used)]
+static HREF_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r#"(?i)\shref\s*=\s*["']([^"']+)["']"#).expect("compile regex"));
+#[expect(clippy::expect_used)]
+static IFRAME_SRC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(r#"(?i)<iframe\s[^>]*src\s*=\s*["']([^"']+)["']"#).expect("compile regex")
+});
+
+/// Metadata which was extracted from a HTML page.
+#[derive(Debug, Default)]
+pub(crate) struct PageMetadata {
+  pub(crate) opengraph_data: OpenGraphData,
+  /// oEmbed endpoint which the page links to
+  pub(crate) oembed_url: Option<Url>,
+}
+
+/// Extract site metadata from HTML. Opengraph tags are preferred, then Twitter cards and finally
+/// the plain HTML title and description. Also works for pages without doctype.
+pub(crate) fn extract_page_metadata(html_bytes: &[u8], url: &Url) -> LemmyResult<PageMetadata> {
+  let html = String::from_utf8_lossy(html_bytes);
+
+  let mut page = HTML::from_string(html.to_string(), None)?;
+
+  // If the web page specifies that it isn't actually UTF-8, re-decode the received bytes with the
+  // proper encoding. If the specified encoding cannot be found, fall back to the original UTF-8
+  // version.
+  if let Some(charset) = page.meta.get("charset")
+    && charset != UTF_8.name()
+    && let Some(encoding) = Encoding::for_label(charset.as_bytes())
+  {
+    page = HTML::from_string(encoding.decode(html_bytes).0.into(), None)?;
+  }
+
+  let og = &page.opengraph;
+  let twitter = |field: &str| twitter_card_field(&page.meta, field);
+  // join also works if the target URL is absolute
+  let join = |link: &str| {
+    Some(link)
+      .filter(|l| !l.is_empty())
+      .and_then(|l| url.join(l).ok())
+  };
+
+  let title = og
+    .properties
+    .get("title")
+    .cloned()
+    .or_else(|| twitter("title"))
+    .or(page.title.clone());
+  let description = og
+    .properties
+    .get("description")
+    .cloned()
+    .or_else(|| twitter("description"))
+    .or(page.description.clone());
+
+  let og_image = og.images.first().filter(|v| !v.url.is_empty());
+  let (image, (image_width, image_height)) = match og_image {
+    Some(ogo) => (join(&ogo.url), opengraph_width_and_height(ogo)),
+    None => (
+      twitter("image").and_then(|i| join(&i)),
+      (
+        twitter_int("image:width", &page.meta),
+        twitter_int("image:height", &page.meta),
+      ),
+    ),
+  };
+
+  // Sometime sites provide `og:video` tags with empty content
+  let og_video = og.videos.first().filter(|v| !v.url.is_empty());
+  let (embed_video_url, (video_width, video_height)) = match og_video {
+    Some(ogo) => (join(&ogo.url), opengraph_width_and_height(ogo)),
+    None => (
+      twitter("player").and_then(|p| join(&p)),
+      (
+        twitter_int("player:width", &page.meta),
+        twitter_int("player:height", &page.meta),
+      ),
+    ),
+  };
+
+  Ok(PageMetadata {
+    opengraph_data: OpenGraphData {
+      title,
+      description,
+      image: image.map(Into::into),
+      image_width,
+      image_height,
+      embed_video_url: embed_video_url.map(Into::into),
+      video_width,
+      video_height,
+    },
+    oembed_url: discover_oembed_url(&html, url),
+  })
+}
+
+/// Twitter card fields are stored with the other meta tags of the page.
+fn twitter_card_field(meta: &HashMap<String, String>, field: &str) -> Option<String> {
+  meta
+    .get(&format!("twitter:{field}"))
+    .filter(|v| !v.is_empty())
+    .cloned()
+}
+
+fn twitter_int(field: &str, meta: &HashMap<String, String>) -> Option<u16> {
+  twitter_card_field(meta, field)?.parse().ok()
+}
+
+fn opengraph_width_and_height(ogo: &OpengraphObject) -> (Option<u16>, Option<u16>) {
+  let int_field = |field: &str| ogo.properties.get(field)?.parse::<u16>().ok();
+  (int_field("width"), int_field("height"))
+}
+
+/// Finds the oEmbed endpoint in a `<link rel="alternate" type="application/json+oembed">` tag.
+fn discover_oembed_url(html: &str, url: &Url) -> Option<Url> {
+  let link = OEMBED_LINK_REGEX.find(html)?;
+  let href = HREF_REGEX.captures(link.as_str())?.get(1)?.as_str();
+  url.join(&href.replace("&amp;", "&")).ok()
+}
+
+/// Checks if content without a HTML content type is still a HTML page, eg if the server sends it
+/// as `text/plain` or without any content type.
+pub(crate) fn looks_like_html(bytes: &[u8]) -> bool {
+  let start = bytes.get(..1024).unwrap_or(bytes);
+  let start = String::from_utf8_lossy(start).to_lowercase();
+  let start = start.trim_start();
+  start.starts_with("<!doctype html") || start.contains("<html") || start.contains("<head")
+}
+
+/// Returns the oEmbed endpoint for the link, including the link as parameter. An endpoint which
+/// is configured by admins takes precedence over the one linked from the page, followed by known
+/// providers.
+pub(crate) fn oembed_endpoint(
+  url: &Url,
+  discovered: Option<Url>,
+  link_override: Option<&LinkMetadataOverride>,
+) -> Option<Url> {
+  let endpoint_with_url = |endpoint: &Url| {
+    let mut endpoint = endpoint.clone();
+    endpoint
+      .query_pairs_mut()
+      .append_pair("format", "json")
+      .append_pair("url", url.as_str());
+    endpoint
+  };
+  if let Some(endpoint) = link_override.and_then(|o| o.oembed_endpoint.as_ref()) {
+    return Some(endpoint_with_url(endpoint.inner()));
+  }
+  if discovered.is_some() {
+    return discovered;
+  }
+  let domain = url.domain()?;
+  let (_, endpoint) = OEMBED_PROVIDERS
+    .iter()
+    .find(|(d, _)| domain == *d || domain.ends_with(&format!(".{d}")))?;
+  Url::parse(endpoint).ok().as_ref().map(endpoint_with_url)
+}
+
+/// The parts of an oEmbed response which are used for link previews.
+/// https://oembed.com/#section2.3
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct OEmbed {
+  title: Option<String>,
+  thumbnail_url: Option<String>,
+  thumbnail_width: Option<Value>,
+  thumbnail_height: Option<Value>,
+  html: Option<String>,
+  width: Option<Value>,
+  height: Option<Value>,
+}
+
+impl OEmbed {
+  /// Fills in the fields which are missing in the page metadata.
+  pub(crate) fn merge_into(self, data: &mut OpenGraphData, url: &Url) {
+    if data.title.is_none() {
+      data.title = self.title;
+    }
+    if data.image.is_none()
+      && let Some(thumbnail) = self.thumbnail_url.and_then(|t| url.join(&t).ok())
+    {
+      data.image = Some(thumbnail.into());
+      data.image_width = self.thumbnail_width.as_ref().and_then(dimension);
+      data.image_height = self.thumbnail_height.as_ref().and_then(dimension);
+    }
+    // Video and rich embeds contain an iframe with the player
+    if data.embed_video_url.is_none()
+      && let Some(src) = self.html.as_deref().and_then(iframe_src)
+      && let Ok(src) = url.join(&src)
+    {
+      data.embed_video_url = Some(src.into());
+      data.video_width = self.width.as_ref().and_then(dimension);
+      data.video_height = self.height.as_ref().and_then(dimension);
+    }
+  }
+}
+
+/// Dimensions are numbers according to the spec, but some providers send strings.
+fn dimension(value: &Value) -> Option<u16> {
+  match value {
+    Value::Number(n) => n.as_u64()?.try_into().ok(),
+    Value::String(s) => s.parse().ok(),
+    _ => None,
+  }
+}
+
+fn iframe_src(html: &str) -> Option<String> {
+  let src = IFRAME_SRC_REGEX.captures(html)?.get(1)?.as_str();
+  Some(src.replace("&amp;", "&"))
+}
+
+pub(crate) async fn fetch_oembed(endpoint: &Url, context: &LemmyContext) -> LemmyResult<OEmbed> {
+  check_url_is_public(endpoint).await?;
+  let response = context
+    .client()
+    .get(endpoint.as_str())
+    .timeout(REQWEST_TIMEOUT)
+    .send()
+    .await?
+    .error_for_status()?;
+  let bytes = collect_bytes_until_limit(response, OEMBED_MAX_BYTES).await?;
+  Ok(serde_json::from_slice(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::Utc;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_resolve_image_url() -> LemmyResult<()> {
+    // url that lists the opengraph fields
+    let url = Url::parse("https://example.com/one/two.html")?;
+
+    // root relative url
+    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='/image.jpg'></head><body></body></html>";
+    let metadata = extract_page_metadata(html_bytes, &url)?.opengraph_data;
+    assert_eq!(
+      metadata.image,
+      Some(Url::parse("https://example.com/image.jpg")?.into())
+    );
+
+    // base relative url
+    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='image.jpg'></head><body></body></html>";
+    let metadata = extract_page_metadata(html_bytes, &url)?.opengraph_data;
+    assert_eq!(
+      metadata.image,
+      Some(Url::parse("https://example.com/one/image.jpg")?.into())
+    );
+
+    // absolute url
+    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='https://cdn.host.com/image.jpg'></head><body></body></html>";
+    let metadata = extract_page_metadata(html_bytes, &url)?.opengraph_data;
+    assert_eq!(
+      metadata.image,
+      Some(Url::parse("https://cdn.host.com/image.jpg")?.into())
+    );
+
+    // protocol relative url
+    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='//example.com/image.jpg'></head><body></body></html>";
+    let metadata = extract_page_metadata(html_bytes, &url)?.opengraph_data;
+    assert_eq!(
+      metadata.image,
+      Some(Url::parse("https://example.com/image.jpg")?.into())
+    );
+
+    // image width and height
+    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='/image.jpg'><meta property='og:image:width' content='400' /><meta property='og:image:height' content='200' /></head><body></body></html>";
+    let metadata = extract_page_metadata(html_bytes, &url)?.opengraph_data;
+    assert_eq!(
+      (metadata.image_width, metadata.image_height),
+      (Some(400), Some(200))
+    );
+
+    // Empty urls shouldn't return anything
+    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content=''></head><body></body></html>";
+    let metadata = extract_page_metadata(html_bytes, &url)?.opengraph_data;
+    assert_eq!(metadata.image, None);
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_twitter_card_without_doctype() -> LemmyResult<()> {
+    let url = Url::parse("https://example.com/article")?;
+    let html_bytes = br#"<head>
+      <title>Page title</title>
+      <meta name="twitter:title" content="Card title">
+      <meta name="twitter:image" content="/card.png">
+      <meta name="twitter:player" content="https://example.com/player/1">
+      <meta name="twitter:player:width" content="640">
+      <link rel="alternate" type="application/json+oembed" href="/oembed?url=https%3A%2F%2Fexample.com%2Farticle&amp;format=json">
+    </head>"#;
+    assert!(looks_like_html(html_bytes));
+    let metadata = extract_page_metadata(html_bytes, &url)?;
+    let data = metadata.opengraph_data;
+    assert_eq!(Some("Card title".to_string()), data.title);
+    assert_eq!(
+      Some(Url::parse("https://example.com/card.png")?.into()),
+      data.image
+    );
+    assert_eq!(
+      Some(Url::parse("https://example.com/player/1")?.into()),
+      data.embed_video_url
+    );
+    assert_eq!((Some(640), None), (data.video_width, data.video_height));
+    assert_eq!(
+      Some(Url::parse(
+        "https://example.com/oembed?url=https%3A%2F%2Fexample.com%2Farticle&format=json"
+      )?),
+      metadata.oembed_url
+    );
+
+    assert!(!looks_like_html(b"just some text"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_oembed_endpoint() -> LemmyResult<()> {
+    let url = Url::parse("https://www.youtube.com/watch?v=abc")?;
+    assert_eq!(
+      Some(Url::parse(
+        "https://www.youtube.com/oembed?format=json&url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3Dabc"
+      )?),
+      oembed_endpoint(&url, None, None)
+    );
+
+    // Discovered endpoints are preferred over known providers, but overrides take precedence
+    let discovered = Url::parse("https://www.youtube.com/oembed?url=discovered")?;
+    assert_eq!(
+      Some(discovered.clone()),
+      oembed_endpoint(&url, Some(discovered.clone()), None)
+    );
+    let link_override = LinkMetadataOverride {
+      domain: "youtube.com".to_string(),
+      oembed_endpoint: Some(Url::parse("https://oembed.example.com/")?.into()),
+      disable_metadata: false,
+      disable_embed: false,
+      published_at: Utc::now(),
+      updated_at: None,
+    };
+    assert_eq!(
+      Some(Url::parse(
+        "https://oembed.example.com/?format=json&url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3Dabc"
+      )?),
+      oembed_endpoint(&url, Some(discovered), Some(&link_override))
+    );
+
+    let url = Url::parse("https://example.com/")?;
+    assert_eq!(None, oembed_endpoint(&url, None, None));
+    Ok(())
+  }
+
+  #[test]
+  fn test_merge_oembed() -> LemmyResult<()> {
+    let url = Url::parse("https://vimeo.com/123")?;
+    let oembed: OEmbed = serde_json::from_str(
+      r#"{
+        "type": "video",
+        "title": "My video",
+        "thumbnail_url": "https://i.vimeocdn.com/video/123.jpg",
+        "thumbnail_width": 640,
+        "thumbnail_height": "360",
+        "html": "<iframe src=\"https://player.vimeo.com/video/123?h=abc&amp;dnt=1\" width=\"640\" height=\"360\"></iframe>",
+        "width": 640,
+        "height": 360
+      }"#,
+    )?;
+    let mut data = OpenGraphData {
+      title: Some("Page title".to_string()),
+      ..Default::default()
+    };
+    oembed.merge_into(&mut data, &url);
+    assert_eq!(Some("Page title".to_string()), data.title);
+    assert_eq!(
+      Some(Url::parse("https://i.vimeocdn.com/video/123.jpg")?.into()),
+      data.image
+    );
+    assert_eq!(
+      (Some(640), Some(360)),
+      (data.image_width, data.image_height)
+    );
+    assert_eq!(
+      Some(Url::parse("https://player.vimeo.com/video/123?h=abc&dnt=1")?.into()),
+      data.embed_video_url
+    );
+    assert_eq!(
+      (Some(640), Some(360)),
+      (data.video_width, data.video_height)
+    );
+    Ok(())
+  }
+}
@@ -1,21 +1,38 @@
-use crate::{context::LemmyContext, plugins::plugin_hook_notification};
+use crate::{
+  context::LemmyContext,
+  plugins::plugin_hook_notification,
+  push::send_push_notification,
+};
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
   source::{
     comment::Comment,
     community::{Community, CommunityActions},
+    community_pseudonym::CommunityPseudonym,
     instance::InstanceActions,
+    local_site::LocalSite,
     modlog::Modlog,
     notification::{Notification, NotificationInsertForm},
     person::{Person, PersonActions},
     post::{Post, PostActions},
+    saved_search::SavedSearch,
+    site::Site,
   },
   traits::{ApubActor, Blockable},
+  utils::FETCH_LIMIT_MAX,
 };
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommunityNotificationsMode, NotificationType, PostNotificationsMode},
+  enums::{
+    CommunityNotificationsMode,
+    ListingType,
+    NotificationType,
+    PostNotificationsMode,
+    PostSortType,
+  },
 };
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::impls::PostQuery;
 use lemmy_db_views_private_message::PrivateMessageView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
@@ -29,8 +46,17 @@ use std::{
   collections::HashSet,
   hash::{Hash, Hasher},
 };
+use tracing::warn;
 use url::Url;
 
+/// Maximum number of users who are notified about a single new post or comment because they
+/// follow the community or post. Larger audiences should rely on the feed instead.
+const MAX_SUBSCRIBER_NOTIFICATIONS: i64 = 1000;
+
+/// Notifications are inserted in batches, so that a large fan-out doesn't end up in a single huge
+/// query.
+const NOTIFICATION_BATCH_SIZE: usize = 200;
+
 #[derive(derive_new::new, Debug, Clone)]
 pub struct NotifyData {
   pub post: Post,
@@ -114,12 +140,13 @@ impl NotifyData {
         continue;
       };
 
+      send_push_notification(&user_view, &c.local_url, &c.data, &context);
       if self.do_send_email {
         send_notification_email(user_view, c.local_url, c.data, context.settings());
       }
     }
-    if !forms.is_empty() {
-      let notifications = Notification::create(&mut context.pool(), &forms).await?;
+    for batch in forms.chunks(NOTIFICATION_BATCH_SIZE) {
+      let notifications = Notification::create(&mut context.pool(), batch).await?;
       plugin_hook_notification(notifications, &context).await?;
     }
 
@@ -134,6 +161,8 @@ impl NotifyData {
     let pool = &mut context.pool();
     // TODO: this needs too many queries for each user
     PersonActions::read_block(pool, potential_blocker_id, self.post.creator_id).await?;
+    PersonActions::read_block(pool, potential_blocker_id, self.creator.id).await?;
+    PersonActions::read_mute(pool, potential_blocker_id, self.creator.id).await?;
     InstanceActions::read_communities_block(pool, potential_blocker_id, self.community.instance_id)
       .await?;
     InstanceActions::read_persons_block(pool, potential_blocker_id, self.creator.instance_id)
@@ -192,6 +221,11 @@ impl NotifyData {
       } else {
         (self.post.creator_id, None)
       };
+    // Replies to a pseudonym go to the person behind it
+    let parent_creator_id =
+      CommunityPseudonym::read_for_pseudonym(&mut context.pool(), parent_creator_id)
+        .await?
+        .map_or(parent_creator_id, |p| p.person_id);
 
     Ok(vec![CollectedNotifyData {
       recipient_id: parent_creator_id,
@@ -249,9 +283,19 @@ impl NotifyData {
   ) -> LemmyResult<Vec<CollectedNotifyData<'a>>> {
     let is_post = self.comment.is_none();
     let subscribers = vec![
-      PostActions::list_subscribers(self.post.id, &mut context.pool()).await?,
-      CommunityActions::list_subscribers(self.post.community_id, is_post, &mut context.pool())
-        .await?,
+      PostActions::list_subscribers(
+        self.post.id,
+        MAX_SUBSCRIBER_NOTIFICATIONS,
+        &mut context.pool(),
+      )
+      .await?,
+      CommunityActions::list_subscribers(
+        self.post.community_id,
+        is_post,
+        MAX_SUBSCRIBER_NOTIFICATIONS,
+        &mut context.pool(),
+      )
+      .await?,
     ]
     .into_iter()
     .flatten()
@@ -303,18 +347,23 @@ async fn notify_private_message_internal(
 
   if is_create {
     plugin_hook_notification(notifications, context).await?;
+    // Messages from muted persons are still shown in the inbox, but without push notification or
+    // email
+    if PersonActions::read_mute(&mut context.pool(), view.recipient.id, view.creator.id)
+      .await
+      .is_err()
+    {
+      return Ok(());
+    }
+    let d = NotificationEmailData::PrivateMessage {
+      sender: &view.creator,
+      content: &view.private_message.content,
+    };
+    let link = view.private_message.local_url(context.settings())?;
+    send_push_notification(&local_recipient, &link, &d, context);
     let site_view = SiteView::read_local(&mut context.pool()).await?;
     if !site_view.local_site.email_notifications_disabled {
-      let d = NotificationEmailData::PrivateMessage {
-        sender: &view.creator,
-        content: &view.private_message.content,
-      };
-      send_notification_email(
-        local_recipient,
-        view.private_message.local_url(context.settings())?,
-        d,
-        context.settings(),
-      );
+      send_notification_email(local_recipient, link, d, context.settings());
     }
   }
   Ok(())
@@ -370,6 +419,62 @@ pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
   })
 }
 
+/// Notifies users about new posts which match one of their saved searches. Checks all posts
+/// which were published since the previous run.
+pub async fn notify_saved_searches(context: &LemmyContext) -> LemmyResult<()> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  let checked_at = Utc::now();
+  for saved_search in SavedSearch::list_with_notifications(&mut context.pool()).await? {
+    notify_saved_search(&saved_search, checked_at, &site, &local_site, context)
+      .await
+      .inspect_err(|e| warn!("Failed to check saved search {}: {e}", saved_search.id.0))
+      .ok();
+  }
+  Ok(())
+}
+
+async fn notify_saved_search(
+  saved_search: &SavedSearch,
+  checked_at: DateTime<Utc>,
+  site: &Site,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let user_view = LocalUserView::read(&mut context.pool(), saved_search.local_user_id).await?;
+  let posts = PostQuery {
+    search_term: Some(saved_search.search_term.clone()),
+    community_id: saved_search.community_id,
+    published_after: Some(saved_search.last_checked_at),
+    published_before: Some(checked_at),
+    local_user: Some(&user_view.local_user),
+    listing_type: Some(ListingType::All),
+    sort: Some(PostSortType::New),
+    limit: Some(FETCH_LIMIT_MAX.try_into()?),
+    ..Default::default()
+  }
+  .list(&mut context.pool(), site, local_site)
+  .await?
+  .items;
+
+  let forms: Vec<_> = posts
+    .iter()
+    // Dont get notified about own posts
+    .filter(|p| p.post.creator_id != user_view.person.id)
+    .map(|p| {
+      NotificationInsertForm::new_post(&p.post, user_view.person.id, NotificationType::SavedSearch)
+    })
+    .collect();
+  if !forms.is_empty() {
+    let notifications = Notification::create(&mut context.pool(), &forms).await?;
+    plugin_hook_notification(notifications, context).await?;
+  }
+
+  SavedSearch::update_last_checked(&mut context.pool(), saved_search.id, checked_at).await?;
+  Ok(())
+}
+
 #[cfg(test)]
 #[expect(clippy::indexing_slicing)]
 mod tests {
@@ -382,16 +487,20 @@ mod tests {
     assert_length,
     source::{
       comment::{Comment, CommentInsertForm},
-      community::{Community, CommunityInsertForm},
+      community::{Community, CommunityActions, CommunityInsertForm},
       instance::{Instance, InstanceActions, InstancePersonsBlockForm},
       notification::{Notification, NotificationInsertForm},
       person::{Person, PersonActions, PersonBlockForm, PersonInsertForm, PersonUpdateForm},
-      post::{Post, PostInsertForm},
+      post::{Post, PostActions, PostInsertForm},
       private_message::{PrivateMessage, PrivateMessageInsertForm, PrivateMessageUpdateForm},
     },
     traits::Blockable,
   };
-  use lemmy_db_schema_file::enums::NotificationType;
+  use lemmy_db_schema_file::enums::{
+    CommunityNotificationsMode,
+    NotificationType,
+    PostNotificationsMode,
+  };
   use lemmy_db_views_local_user::LocalUserView;
   use lemmy_db_views_notification::{NotificationData, NotificationView, impls::NotificationQuery};
   use lemmy_db_views_private_message::PrivateMessageView;
@@ -734,6 +843,63 @@ mod tests {
   }
 
   /// Useful in combination with filter_map
+  #[tokio::test]
+  #[serial]
+  async fn subscriptions() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = init_data(pool).await?;
+
+    // Sara wants to know about new posts in the community, and new comments in timmys post
+    CommunityActions::update_notification_state(
+      data.community.id,
+      data.sara.person.id,
+      CommunityNotificationsMode::AllPosts,
+      pool,
+    )
+    .await?;
+    PostActions::update_notification_state(
+      data.timmy_post.id,
+      data.sara.person.id,
+      PostNotificationsMode::AllComments,
+      pool,
+    )
+    .await?;
+
+    NotifyData::new(
+      data.jessica_post.clone(),
+      data.jessica.clone(),
+      data.community.clone(),
+    )
+    .send_internal(context.app_data().clone())
+    .await?;
+    NotifyData {
+      comment: Some(data.timmy_comment.clone()),
+      ..NotifyData::new(
+        data.timmy_post.clone(),
+        data.timmy.person.clone(),
+        data.community.clone(),
+      )
+    }
+    .send_internal(context.app_data().clone())
+    .await?;
+
+    let sara_unread = NotificationView::get_unread_count(pool, &data.sara.person, true).await?;
+    let sara_unread_subscribed =
+      NotificationView::get_unread_subscribed_count(pool, &data.sara.person, true).await?;
+    assert_eq!(2, sara_unread);
+    assert_eq!(2, sara_unread_subscribed);
+
+    // Timmy doesn't follow anything, and isn't notified about his own comment
+    let timmy_unread_subscribed =
+      NotificationView::get_unread_subscribed_count(pool, &data.timmy.person, true).await?;
+    assert_eq!(0, timmy_unread_subscribed);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   fn to_pm(x: NotificationView) -> Option<PrivateMessageView> {
     if let NotificationData::PrivateMessage(v) = x.data {
       Some(v)
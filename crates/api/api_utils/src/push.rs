@@ -0,0 +1,374 @@
+use crate::{context::LemmyContext, request::check_url_is_public};
+use chrono::Utc;
+use lemmy_db_schema::{newtypes::CommunityId, source::push_subscription::PushSubscription};
+use lemmy_db_schema_file::enums::PushProvider;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::dburl::DbUrl;
+use lemmy_email::{notifications::NotificationEmailData, user_language};
+use lemmy_utils::{
+  REQWEST_TIMEOUT,
+  error::{LemmyErrorType, LemmyResult},
+  settings::structs::{FcmProxyConfig, Settings, WebPushConfig},
+  spawn_try_task,
+};
+use reqwest::{
+  StatusCode,
+  header::{CONTENT_ENCODING, CONTENT_TYPE},
+};
+use reqwest_middleware::RequestBuilder;
+use serde::Serialize;
+use std::collections::HashSet;
+use tracing::warn;
+use url::Url;
+use web_push::{ContentEncoding, SubscriptionInfo, VapidSignatureBuilder, WebPushMessageBuilder};
+
+/// How long the push service keeps a notification while the browser is offline.
+const PUSH_TTL_SECONDS: u32 = 60 * 60 * 24;
+
+/// Push services only accept small payloads, so long texts are shortened.
+const PUSH_BODY_MAX_LENGTH: usize = 300;
+
+/// Content of a push notification, which the service worker of the frontend shows to the user.
+#[derive(Debug, Serialize, PartialEq)]
+struct PushMessage {
+  title: String,
+  body: String,
+  link: String,
+}
+
+impl PushMessage {
+  fn new(title: String, body: &str, link: String) -> Self {
+    PushMessage {
+      title,
+      body: body.chars().take(PUSH_BODY_MAX_LENGTH).collect(),
+      link,
+    }
+  }
+}
+
+/// Sends a push notification about a reply, mention or private message to all devices where the
+/// user subscribed, if the user enabled push notifications for it.
+pub fn send_push_notification(
+  local_user_view: &LocalUserView,
+  link: &DbUrl,
+  data: &NotificationEmailData,
+  context: &LemmyContext,
+) {
+  let local_user = &local_user_view.local_user;
+  let lang = user_language(local_user);
+  let message = match data {
+    NotificationEmailData::Mention { content, person } if local_user.push_mentions => {
+      PushMessage::new(
+        lang.notification_mentioned_by_subject(&person.name),
+        content,
+        link.to_string(),
+      )
+    }
+    NotificationEmailData::Reply {
+      comment,
+      person,
+      parent_comment: Some(_),
+      ..
+    } if local_user.push_replies => PushMessage::new(
+      lang.notification_comment_reply_subject(&person.name),
+      &comment.content,
+      link.to_string(),
+    ),
+    NotificationEmailData::Reply {
+      comment,
+      person,
+      parent_comment: None,
+      ..
+    } if local_user.push_replies => PushMessage::new(
+      lang.notification_post_reply_subject(&person.name),
+      &comment.content,
+      link.to_string(),
+    ),
+    NotificationEmailData::PrivateMessage { sender, content }
+      if local_user.push_private_messages =>
+    {
+      PushMessage::new(
+        lang.notification_private_message_subject(&sender.name),
+        content,
+        link.to_string(),
+      )
+    }
+    // Other notifications are only shown in the inbox
+    _ => return,
+  };
+  send_push_message(local_user_view, message, context);
+}
+
+/// Notifies all admins about a new report, like the report emails. If a community is given, its
+/// local mods are notified as well. This should be left out for reports which only admins can see.
+pub async fn send_new_report_push(
+  reporter_username: &str,
+  reported_username: &str,
+  community_id: Option<CommunityId>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let mut recipients = LocalUserView::list_admins(&mut context.pool()).await?;
+  if let Some(community_id) = community_id {
+    recipients
+      .extend(LocalUserView::list_community_moderators(&mut context.pool(), community_id).await?);
+  }
+  // Admins who also moderate the community only need a single notification
+  let mut notified = HashSet::new();
+  recipients.retain(|r| notified.insert(r.local_user.id));
+
+  let reports_link = format!("{}/reports", context.settings().get_protocol_and_hostname());
+  for recipient in recipients {
+    if !recipient.local_user.push_reports {
+      continue;
+    }
+    let lang = user_language(&recipient.local_user);
+    let title = lang.new_report_subject(
+      &context.settings().hostname,
+      reported_username,
+      reporter_username,
+    );
+    let message = PushMessage::new(title, "", reports_link.clone());
+    send_push_message(&recipient, message, context);
+  }
+  Ok(())
+}
+
+/// Notifies all admins about a new registration application, like the application emails.
+pub async fn send_new_applicant_push_to_admins(
+  applicant_username: &str,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let applications_link = format!(
+    "{}/registration_applications",
+    context.settings().get_protocol_and_hostname()
+  );
+  for admin in LocalUserView::list_admins(&mut context.pool()).await? {
+    if !admin.local_user.push_registration_applications {
+      continue;
+    }
+    let lang = user_language(&admin.local_user);
+    let title = lang.new_application_subject(&context.settings().hostname, applicant_username);
+    let message = PushMessage::new(title, "", applications_link.clone());
+    send_push_message(&admin, message, context);
+  }
+  Ok(())
+}
+
+fn send_push_message(
+  local_user_view: &LocalUserView,
+  message: PushMessage,
+  context: &LemmyContext,
+) {
+  if local_user_view.banned || local_user_view.local_user.is_quiet_hours(Utc::now()) {
+    return;
+  }
+  let local_user_id = local_user_view.local_user.id;
+  let context = context.clone();
+  spawn_try_task(async move {
+    for subscription in PushSubscription::list_for_user(&mut context.pool(), local_user_id).await? {
+      deliver(&subscription, &message, &context)
+        .await
+        .inspect_err(|e| warn!("Failed to send push notification: {e}"))
+        .ok();
+    }
+    Ok(())
+  })
+}
+
+/// Returns true if devices can subscribe to notifications with the given provider.
+pub fn is_push_provider_enabled(provider: PushProvider, settings: &Settings) -> bool {
+  match provider {
+    PushProvider::WebPush => settings.web_push.is_some(),
+    PushProvider::UnifiedPush => settings.unified_push,
+    PushProvider::Fcm => settings.fcm_proxy.is_some(),
+  }
+}
+
+/// A service which delivers push notifications to devices.
+trait PushBackend: Sized {
+  fn new(settings: &Settings) -> LemmyResult<Self>;
+
+  /// Builds the request which sends the message to the device of the subscription.
+  fn request(
+    &self,
+    subscription: &PushSubscription,
+    message: &PushMessage,
+    context: &LemmyContext,
+  ) -> LemmyResult<RequestBuilder>;
+}
+
+/// Browser push services, with the message encrypted for the browser.
+/// https://datatracker.ietf.org/doc/html/rfc8030
+struct WebPush(WebPushConfig);
+
+impl PushBackend for WebPush {
+  fn new(settings: &Settings) -> LemmyResult<Self> {
+    settings
+      .web_push
+      .clone()
+      .map(WebPush)
+      .ok_or(LemmyErrorType::PushNotificationsDisabled.into())
+  }
+
+  fn request(
+    &self,
+    subscription: &PushSubscription,
+    message: &PushMessage,
+    context: &LemmyContext,
+  ) -> LemmyResult<RequestBuilder> {
+    let (Some(p256dh_key), Some(auth_key)) = (&subscription.p256dh_key, &subscription.auth_key)
+    else {
+      return Err(LemmyErrorType::InvalidBodyField.into());
+    };
+    let info = SubscriptionInfo::new(&subscription.endpoint, p256dh_key, auth_key);
+    let mut signature = VapidSignatureBuilder::from_base64(&self.0.vapid_private_key, &info)?;
+    let subject = self
+      .0
+      .subject
+      .clone()
+      .unwrap_or_else(|| context.settings().get_protocol_and_hostname());
+    signature.add_claim("sub", subject);
+
+    let payload = serde_json::to_vec(message)?;
+    let mut builder = WebPushMessageBuilder::new(&info);
+    builder.set_ttl(PUSH_TTL_SECONDS);
+    builder.set_payload(ContentEncoding::Aes128Gcm, &payload);
+    builder.set_vapid_signature(signature.build()?);
+    let message = builder.build()?;
+
+    let mut request = context
+      .client()
+      .post(&subscription.endpoint)
+      .header("TTL", message.ttl);
+    if let Some(payload) = message.payload {
+      request = request
+        .header(CONTENT_ENCODING, payload.content_encoding.to_str())
+        .header(CONTENT_TYPE, "application/octet-stream");
+      for (name, value) in payload.crypto_headers {
+        request = request.header(name, value);
+      }
+      request = request.body(payload.content);
+    }
+    Ok(request)
+  }
+}
+
+/// Distributor apps which forward the message to the app as is.
+/// https://unifiedpush.org/developers/spec/server/
+struct UnifiedPush;
+
+impl PushBackend for UnifiedPush {
+  fn new(settings: &Settings) -> LemmyResult<Self> {
+    if !settings.unified_push {
+      return Err(LemmyErrorType::PushNotificationsDisabled.into());
+    }
+    Ok(UnifiedPush)
+  }
+
+  fn request(
+    &self,
+    subscription: &PushSubscription,
+    message: &PushMessage,
+    context: &LemmyContext,
+  ) -> LemmyResult<RequestBuilder> {
+    Ok(
+      context
+        .client()
+        .post(&subscription.endpoint)
+        .header("TTL", PUSH_TTL_SECONDS)
+        .header(CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(message)?),
+    )
+  }
+}
+
+/// Proxy for Firebase Cloud Messaging, which only the instance can use.
+struct Fcm(FcmProxyConfig);
+
+#[derive(Serialize)]
+struct FcmProxyRequest<'a> {
+  token: &'a str,
+  #[serde(flatten)]
+  message: &'a PushMessage,
+}
+
+impl PushBackend for Fcm {
+  fn new(settings: &Settings) -> LemmyResult<Self> {
+    settings
+      .fcm_proxy
+      .clone()
+      .map(Fcm)
+      .ok_or(LemmyErrorType::PushNotificationsDisabled.into())
+  }
+
+  fn request(
+    &self,
+    subscription: &PushSubscription,
+    message: &PushMessage,
+    context: &LemmyContext,
+  ) -> LemmyResult<RequestBuilder> {
+    let body = FcmProxyRequest {
+      token: &subscription.endpoint,
+      message,
+    };
+    let mut request = context
+      .client()
+      .post(self.0.url.as_str())
+      .header(CONTENT_TYPE, "application/json")
+      .body(serde_json::to_vec(&body)?);
+    if let Some(api_key) = &self.0.api_key {
+      request = request.bearer_auth(api_key);
+    }
+    Ok(request)
+  }
+}
+
+/// Sends the message to the device with the provider of the subscription.
+async fn deliver(
+  subscription: &PushSubscription,
+  message: &PushMessage,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let request = match subscription.provider {
+    PushProvider::WebPush => request::<WebPush>(subscription, message, context),
+    PushProvider::UnifiedPush => request::<UnifiedPush>(subscription, message, context),
+    PushProvider::Fcm => request::<Fcm>(subscription, message, context),
+  }?;
+  // The endpoint is chosen by the user, so it must not point to the local network
+  if subscription.provider != PushProvider::Fcm {
+    check_url_is_public(&Url::parse(&subscription.endpoint)?).await?;
+  }
+  let res = request.timeout(REQWEST_TIMEOUT).send().await?;
+
+  // The device unsubscribed, or the subscription expired
+  if matches!(res.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+    PushSubscription::delete(&mut context.pool(), subscription.id).await?;
+    return Ok(());
+  }
+  res.error_for_status()?;
+  Ok(())
+}
+
+fn request<B: PushBackend>(
+  subscription: &PushSubscription,
+  message: &PushMessage,
+  context: &LemmyContext,
+) -> LemmyResult<RequestBuilder> {
+  B::new(context.settings())?.request(subscription, message, context)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_push_message_length() {
+    let message = PushMessage::new(
+      "title".to_string(),
+      &"ä".repeat(1000),
+      "https://example.com".to_string(),
+    );
+    assert_eq!(PUSH_BODY_MAX_LENGTH, message.body.chars().count());
+  }
+}
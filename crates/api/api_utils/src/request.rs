@@ -1,33 +1,41 @@
 use crate::{
   context::LemmyContext,
+  link_metadata::{
+    PageMetadata,
+    extract_page_metadata,
+    fetch_oembed,
+    looks_like_html,
+    oembed_endpoint,
+  },
   send_activity::{ActivityChannel, SendActivityData},
   utils::proxy_image_link,
 };
 use activitypub_federation::config::Data;
 use chrono::{DateTime, Utc};
-use encoding_rs::{Encoding, UTF_8};
 use futures::StreamExt;
 use lemmy_db_schema::source::{
-  images::{ImageDetailsInsertForm, LocalImage, LocalImageForm},
+  images::{ImageDetailsInsertForm, LocalImage, LocalImageForm, RemoteImage},
+  link_metadata_override::LinkMetadataOverride,
   local_site::LocalSite,
   post::{Post, PostUpdateForm},
 };
 use lemmy_db_schema_file::enums::ImageMode;
 use lemmy_db_views_post::api::{LinkMetadata, OpenGraphData};
 use lemmy_db_views_site::SiteView;
-use lemmy_diesel_utils::traits::Crud;
+use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
 use lemmy_utils::{
   REQWEST_TIMEOUT,
   VERSION,
   error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
   settings::structs::Settings,
+  spawn_try_task,
 };
-use mime::{Mime, TEXT_HTML};
+use mime::{Mime, TEXT_HTML, TEXT_PLAIN};
 use reqwest::{
   Client,
   ClientBuilder,
   Response,
-  header::{CONTENT_TYPE, LOCATION, RANGE},
+  header::{CONTENT_LENGTH, CONTENT_TYPE, LOCATION, RANGE},
   redirect::Policy,
 };
 use reqwest_middleware::ClientWithMiddleware;
@@ -37,7 +45,6 @@ use tokio::net::lookup_host;
 use tracing::{info, warn};
 use url::Url;
 use urlencoding::encode;
-use webpage::{HTML, OpengraphObject};
 
 pub fn client_builder(settings: &Settings) -> ClientBuilder {
   // https://github.com/seanmonstar/reqwest/issues/2924
@@ -65,22 +72,11 @@ pub async fn fetch_link_metadata(
   if url.scheme() != "http" && url.scheme() != "https" {
     return Err(LemmyErrorType::InvalidUrl.into());
   }
+  check_url_is_public(url).await?;
 
-  // Resolve the domain and throw an error if it points to any internal IP,
-  // using logic from nightly IpAddr::is_global.
-  if !cfg!(debug_assertions) {
-    // TODO: Replace with IpAddr::is_global() once stabilized
-    //       https://doc.rust-lang.org/std/net/enum.IpAddr.html#method.is_global
-    let domain = url.domain().ok_or(UntranslatedError::UrlWithoutDomain)?;
-    let invalid_ip = lookup_host((domain.to_owned(), 80))
-      .await?
-      .any(|addr| match addr.ip() {
-        IpAddr::V4(addr) => v4_is_invalid(addr),
-        IpAddr::V6(addr) => v6_is_invalid(addr),
-      });
-    if invalid_ip {
-      return Err(LemmyErrorType::InvalidUrl.into());
-    }
+  let link_override = LinkMetadataOverride::read_for_url(&mut context.pool(), url).await?;
+  if link_override.as_ref().is_some_and(|o| o.disable_metadata) {
+    return Ok(LinkMetadata::default());
   }
 
   info!("Fetching site metadata for url: {}", url);
@@ -118,51 +114,93 @@ pub async fn fetch_link_metadata(
     // then try to infer the content_type from the file extension.
     .or(mime_guess::from_path(url.path()).first());
 
-  let opengraph_data = {
-    let is_html = content_type
-      .as_ref()
-      .map(|c| {
-        // application/xhtml+xml is a subset of HTML
-        let application_xhtml: Mime = "application/xhtml+xml".parse::<Mime>().unwrap_or(TEXT_HTML);
-        let allowed_mime_types = [TEXT_HTML.essence_str(), application_xhtml.essence_str()];
-        allowed_mime_types.contains(&c.essence_str())
-      })
-      .unwrap_or_default();
-
-    if is_html {
-      // Can't use .text() here, because it only checks the content header, not the actual bytes
-      // https://github.com/LemmyNet/lemmy/issues/1964
-      // So we want to do deep inspection of the actually returned bytes but need to be careful
-      // not spend too much time parsing binary data as HTML
-      // only take first bytes regardless of how many bytes the server returns
-      let html_bytes = collect_bytes_until_limit(response, bytes_to_fetch).await?;
-      extract_opengraph_data(&html_bytes, url)
+  let is_html = content_type
+    .as_ref()
+    .map(|c| {
+      // application/xhtml+xml is a subset of HTML
+      let application_xhtml: Mime = "application/xhtml+xml".parse::<Mime>().unwrap_or(TEXT_HTML);
+      let allowed_mime_types = [TEXT_HTML.essence_str(), application_xhtml.essence_str()];
+      allowed_mime_types.contains(&c.essence_str())
+    })
+    .unwrap_or_default();
+  // Some servers send HTML pages as plain text or without content type
+  let maybe_html = content_type
+    .as_ref()
+    .is_none_or(|c| c.essence_str() == TEXT_PLAIN.essence_str());
+
+  let page = if is_html || maybe_html {
+    // Can't use .text() here, because it only checks the content header, not the actual bytes
+    // https://github.com/LemmyNet/lemmy/issues/1964
+    // So we want to do deep inspection of the actually returned bytes but need to be careful
+    // not spend too much time parsing binary data as HTML
+    // only take first bytes regardless of how many bytes the server returns
+    let html_bytes = collect_bytes_until_limit(response, bytes_to_fetch).await?;
+    if is_html || looks_like_html(&html_bytes) {
+      if !is_html {
+        content_type = Some(TEXT_HTML);
+      }
+      extract_page_metadata(&html_bytes, url)
         .map_err(|e| info!("{e}"))
         .unwrap_or_default()
     } else {
-      let is_octet_type = content_type
-        .as_ref()
-        .map(|c| c.subtype() == "octet-stream")
-        .unwrap_or_default();
-
-      // Overwrite the content type if its an octet type
-      if is_octet_type {
-        // Don't need to fetch as much data for this as we do with opengraph
-        let octet_bytes = collect_bytes_until_limit(response, 512).await?;
-        content_type =
-          infer::get(&octet_bytes).map_or(content_type, |t| t.mime_type().parse().ok());
-      }
+      PageMetadata::default()
+    }
+  } else {
+    let is_octet_type = content_type
+      .as_ref()
+      .map(|c| c.subtype() == "octet-stream")
+      .unwrap_or_default();
 
-      Default::default()
+    // Overwrite the content type if its an octet type
+    if is_octet_type {
+      // Don't need to fetch as much data for this as we do with opengraph
+      let octet_bytes = collect_bytes_until_limit(response, 512).await?;
+      content_type = infer::get(&octet_bytes).map_or(content_type, |t| t.mime_type().parse().ok());
     }
+
+    PageMetadata::default()
   };
 
+  // Fill in missing data from oEmbed, which is also the only source of embeds for some sites
+  let mut opengraph_data = page.opengraph_data;
+  if let Some(endpoint) = oembed_endpoint(url, page.oembed_url, link_override.as_ref()) {
+    match fetch_oembed(&endpoint, context).await {
+      Ok(oembed) => oembed.merge_into(&mut opengraph_data, url),
+      Err(e) => info!("Failed to fetch oEmbed for {url}: {e}"),
+    }
+  }
+  if link_override.is_some_and(|o| o.disable_embed) {
+    opengraph_data.embed_video_url = None;
+    opengraph_data.video_width = None;
+    opengraph_data.video_height = None;
+  }
+
   Ok(LinkMetadata {
     opengraph_data,
     content_type: content_type.map(|c| c.to_string()),
   })
 }
 
+/// Resolve the domain and throw an error if it points to any internal IP,
+/// using logic from nightly IpAddr::is_global.
+pub async fn check_url_is_public(url: &Url) -> LemmyResult<()> {
+  if !cfg!(debug_assertions) {
+    // TODO: Replace with IpAddr::is_global() once stabilized
+    //       https://doc.rust-lang.org/std/net/enum.IpAddr.html#method.is_global
+    let domain = url.domain().ok_or(UntranslatedError::UrlWithoutDomain)?;
+    let invalid_ip = lookup_host((domain.to_owned(), 80))
+      .await?
+      .any(|addr| match addr.ip() {
+        IpAddr::V4(addr) => v4_is_invalid(addr),
+        IpAddr::V6(addr) => v6_is_invalid(addr),
+      });
+    if invalid_ip {
+      return Err(LemmyErrorType::InvalidUrl.into());
+    }
+  }
+  Ok(())
+}
+
 fn v4_is_invalid(v4: Ipv4Addr) -> bool {
   v4.is_private()
     || v4.is_loopback()
@@ -187,7 +225,7 @@ fn v6_is_invalid(v6: Ipv6Addr) -> bool {
     || v6.to_ipv4_mapped().is_some_and(v4_is_invalid)
 }
 
-async fn collect_bytes_until_limit(
+pub(crate) async fn collect_bytes_until_limit(
   response: Response,
   requested_bytes: usize,
 ) -> Result<Vec<u8>, LemmyError> {
@@ -290,82 +328,6 @@ pub async fn generate_post_link_metadata(
   Ok(())
 }
 
-/// Extract site metadata from HTML Opengraph attributes.
-fn extract_opengraph_data(html_bytes: &[u8], url: &Url) -> LemmyResult<OpenGraphData> {
-  let html = String::from_utf8_lossy(html_bytes);
-
-  let mut page = HTML::from_string(html.to_string(), None)?;
-
-  // If the web page specifies that it isn't actually UTF-8, re-decode the received bytes with the
-  // proper encoding. If the specified encoding cannot be found, fall back to the original UTF-8
-  // version.
-  if let Some(charset) = page.meta.get("charset")
-    && charset != UTF_8.name()
-    && let Some(encoding) = Encoding::for_label(charset.as_bytes())
-  {
-    page = HTML::from_string(encoding.decode(html_bytes).0.into(), None)?;
-  }
-
-  let page_title = page.title;
-  let page_description = page.description;
-
-  let og_description = page
-    .opengraph
-    .properties
-    .get("description")
-    .map(std::string::ToString::to_string);
-  let og_title = page
-    .opengraph
-    .properties
-    .get("title")
-    .map(std::string::ToString::to_string);
-  let og_image = page
-    .opengraph
-    .images
-    .first()
-    .filter(|v| !v.url.is_empty())
-    // join also works if the target URL is absolute
-    .and_then(|ogo| url.join(&ogo.url).ok());
-
-  let (og_image_width, og_image_height) =
-    extract_opengraph_width_and_height(page.opengraph.images.first());
-
-  let og_embed_url = page
-    .opengraph
-    .videos
-    .first()
-    // Sometime sites provide `og:video` tags with empty content
-    .filter(|v| !v.url.is_empty())
-    // join also works if the target URL is absolute
-    .and_then(|v| url.join(&v.url).ok());
-
-  let (og_video_width, og_video_height) =
-    extract_opengraph_width_and_height(page.opengraph.videos.first());
-
-  Ok(OpenGraphData {
-    title: og_title.or(page_title),
-    description: og_description.or(page_description),
-    image: og_image.map(Into::into),
-    image_width: og_image_width,
-    image_height: og_image_height,
-    embed_video_url: og_embed_url.map(Into::into),
-    video_width: og_video_width,
-    video_height: og_video_height,
-  })
-}
-
-fn extract_opengraph_width_and_height(ogo: Option<&OpengraphObject>) -> (Option<u16>, Option<u16>) {
-  (
-    ogo.and_then(|ogo| extract_opengraph_int_field(ogo, "width")),
-    ogo.and_then(|ogo| extract_opengraph_int_field(ogo, "height")),
-  )
-}
-
-fn extract_opengraph_int_field(ogo: &OpengraphObject, field: &str) -> Option<u16> {
-  let w = ogo.properties.get(field)?;
-  w.parse::<u16>().ok()
-}
-
 #[derive(Deserialize, Serialize, Debug)]
 pub struct PictrsResponse {
   #[serde(default)]
@@ -410,6 +372,7 @@ impl PictrsFileDetails {
       height: self.height.into(),
       content_type: self.content_type.clone(),
       blurhash: self.blurhash.clone(),
+      duration_seconds: None,
     }
   }
 }
@@ -547,6 +510,7 @@ async fn generate_pictrs_thumbnail(
     // For thumbnails, the person_id is the post creator
     person_id: post.creator_id,
     thumbnail_for_post_id: Some(Some(post.id)),
+    size_bytes: fetch_pictrs_file_size(&image.file, context).await.ok(),
   };
   let protocol_and_hostname = context.settings().get_protocol_and_hostname();
   let thumbnail_url = image.image_url(&protocol_and_hostname)?;
@@ -558,6 +522,27 @@ async fn generate_pictrs_thumbnail(
   Ok(thumbnail_url)
 }
 
+/// Returns the size of a file which is stored in pictrs, in bytes.
+pub async fn fetch_pictrs_file_size(alias: &str, context: &LemmyContext) -> LemmyResult<i64> {
+  let url = format!("{}image/original/{alias}", context.settings().pictrs()?.url);
+  let res = context
+    .pictrs_client()
+    .head(&url)
+    .timeout(REQWEST_TIMEOUT)
+    .send()
+    .await?
+    .error_for_status()?;
+  let size = res
+    .headers()
+    .get(CONTENT_LENGTH)
+    .and_then(|v| v.to_str().ok())
+    .and_then(|v| v.parse().ok())
+    .ok_or(LemmyErrorType::PictrsResponseError(
+      "missing content length".to_string(),
+    ))?;
+  Ok(size)
+}
+
 /// Fetches the image details for pictrs proxied images
 ///
 /// We don't need to check for image mode, as that's already been done
@@ -595,6 +580,97 @@ pub async fn fetch_pictrs_proxied_image_details(
   Ok(res)
 }
 
+/// Stores a permanent copy of a remote image in pictrs, which is then served by the image proxy
+/// instead of fetching it from the remote instance. Images are only downloaded once, so this
+/// does nothing if the image is already cached.
+///
+/// The image must be in the `remote_image` table already.
+pub async fn cache_remote_image(image_url: &Url, context: &LemmyContext) -> LemmyResult<()> {
+  let pictrs_config = context.settings().pictrs()?;
+  let link: DbUrl = image_url.clone().into();
+  if !pictrs_config.cache_remote_images
+    || RemoteImage::read(&mut context.pool(), &link)
+      .await?
+      .cached_alias
+      .is_some()
+  {
+    return Ok(());
+  }
+
+  let fetch_url = format!(
+    "{}image/download?url={}",
+    pictrs_config.url,
+    encode(image_url.as_str()),
+  );
+  let res = context
+    .pictrs_client()
+    .get(&fetch_url)
+    .timeout(REQWEST_TIMEOUT)
+    .send()
+    .await?
+    .error_for_status()?
+    .json::<PictrsResponse>()
+    .await?;
+  let image = res
+    .files
+    .first()
+    .ok_or(LemmyErrorType::PictrsResponseError(res.msg))?;
+
+  RemoteImage::set_cached_alias(&mut context.pool(), &link, Some(&image.file)).await
+}
+
+/// Removes the cached copies of actor images after the actor changed them, in the background.
+/// Takes pairs of old and new proxied urls as stored in the database, eg for avatar and banner.
+pub fn uncache_replaced_images(
+  images: Vec<(Option<DbUrl>, Option<DbUrl>)>,
+  context: &LemmyContext,
+) {
+  let context = context.clone();
+  spawn_try_task(async move {
+    for (old, new) in images {
+      uncache_replaced_image(old, &new, &context).await?;
+    }
+    Ok(())
+  });
+}
+
+async fn uncache_replaced_image(
+  old: Option<DbUrl>,
+  new: &Option<DbUrl>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let Some(old) = old.filter(|o| Some(o) != new.as_ref()) else {
+    return Ok(());
+  };
+  // Other actors may use the same image, eg a default avatar
+  if RemoteImage::is_used_by_actor(&mut context.pool(), &old).await? {
+    return Ok(());
+  }
+  let Some(link) = proxied_image_original(&old) else {
+    return Ok(());
+  };
+  let link: DbUrl = link.into();
+  let Ok(remote_image) = RemoteImage::read(&mut context.pool(), &link).await else {
+    return Ok(());
+  };
+  if let Some(alias) = remote_image.cached_alias {
+    RemoteImage::set_cached_alias(&mut context.pool(), &link, None).await?;
+    delete_image_alias(&alias, context).await?;
+  }
+  Ok(())
+}
+
+/// Returns the original remote url of an image which is served through the image proxy.
+fn proxied_image_original(url: &Url) -> Option<Url> {
+  if !url.path().ends_with("/image/proxy") {
+    return None;
+  }
+  url
+    .query_pairs()
+    .find(|(k, _)| k == "url")
+    .and_then(|(_, v)| Url::parse(&v).ok())
+}
+
 // TODO: get rid of this by reading content type from db
 
 async fn is_image_content_type(client: &ClientWithMiddleware, url: &Url) -> LemmyResult<()> {
@@ -617,7 +693,7 @@ mod tests {
 
   use crate::{
     context::LemmyContext,
-    request::{extract_opengraph_data, fetch_link_metadata},
+    request::{fetch_link_metadata, proxied_image_original},
   };
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -656,55 +732,16 @@ mod tests {
   }
 
   #[test]
-  fn test_resolve_image_url() -> LemmyResult<()> {
-    // url that lists the opengraph fields
-    let url = Url::parse("https://example.com/one/two.html")?;
-
-    // root relative url
-    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='/image.jpg'></head><body></body></html>";
-    let metadata = extract_opengraph_data(html_bytes, &url)?;
-    assert_eq!(
-      metadata.image,
-      Some(Url::parse("https://example.com/image.jpg")?.into())
-    );
-
-    // base relative url
-    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='image.jpg'></head><body></body></html>";
-    let metadata = extract_opengraph_data(html_bytes, &url)?;
-    assert_eq!(
-      metadata.image,
-      Some(Url::parse("https://example.com/one/image.jpg")?.into())
-    );
-
-    // absolute url
-    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='https://cdn.host.com/image.jpg'></head><body></body></html>";
-    let metadata = extract_opengraph_data(html_bytes, &url)?;
+  fn test_proxied_image_original() -> LemmyResult<()> {
+    let proxied = Url::parse(
+      "https://lemmy-alpha/api/v4/image/proxy?url=http%3A%2F%2Flemmy-beta%2Fimage.png&max_size=256",
+    )?;
     assert_eq!(
-      metadata.image,
-      Some(Url::parse("https://cdn.host.com/image.jpg")?.into())
+      Some(Url::parse("http://lemmy-beta/image.png")?),
+      proxied_image_original(&proxied)
     );
-
-    // protocol relative url
-    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='//example.com/image.jpg'></head><body></body></html>";
-    let metadata = extract_opengraph_data(html_bytes, &url)?;
-    assert_eq!(
-      metadata.image,
-      Some(Url::parse("https://example.com/image.jpg")?.into())
-    );
-
-    // image width and height
-    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content='/image.jpg'><meta property='og:image:width' content='400' /><meta property='og:image:height' content='200' /></head><body></body></html>";
-    let metadata = extract_opengraph_data(html_bytes, &url)?;
-    assert_eq!(
-      (metadata.image_width, metadata.image_height),
-      (Some(400), Some(200))
-    );
-
-    // Empty urls shouldn't return anything
-    let html_bytes = b"<!DOCTYPE html><html><head><meta property='og:image' content=''></head><body></body></html>";
-    let metadata = extract_opengraph_data(html_bytes, &url)?;
-    assert_eq!(metadata.image, None);
-
+    let local = Url::parse("https://lemmy-alpha/api/v4/image/abc.png")?;
+    assert_eq!(None, proxied_image_original(&local));
     Ok(())
   }
 }
@@ -0,0 +1,492 @@
+use crate::{context::LemmyContext, send_activity::SendActivityData};
+use activitypub_federation::config::Data;
+use lemmy_db_schema::source::{comment::Comment, community::Community, post::Post};
+use lemmy_utils::{
+  REQWEST_TIMEOUT,
+  error::LemmyResult,
+  settings::structs::{SearchBackendType, SearchConfig},
+};
+use reqwest::{
+  StatusCode,
+  header::{AUTHORIZATION, CONTENT_TYPE},
+};
+use reqwest_middleware::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::LazyLock;
+use tokio::{
+  sync::{
+    Mutex,
+    mpsc,
+    mpsc::{UnboundedReceiver, UnboundedSender, WeakUnboundedSender},
+  },
+  task::JoinHandle,
+};
+use tracing::warn;
+
+/// The kinds of content which are stored in the search index, each in a separate index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchKind {
+  Post,
+  Comment,
+  Community,
+}
+
+impl SearchKind {
+  fn index_name(self, config: &SearchConfig) -> String {
+    let name = match self {
+      SearchKind::Post => "posts",
+      SearchKind::Comment => "comments",
+      SearchKind::Community => "communities",
+    };
+    format!("{}_{name}", config.index_prefix)
+  }
+}
+
+/// A post, comment or community as it is stored in the search index.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchDocument {
+  pub id: i32,
+  pub title: Option<String>,
+  pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchIndexTask {
+  Upsert(SearchKind, SearchDocument),
+  Delete(SearchKind, i32),
+}
+
+impl SearchIndexTask {
+  /// Deleted and removed content is taken out of the index.
+  pub fn post(post: &Post) -> Self {
+    if post.deleted || post.removed {
+      Self::Delete(SearchKind::Post, post.id.0)
+    } else {
+      Self::Upsert(
+        SearchKind::Post,
+        SearchDocument {
+          id: post.id.0,
+          title: Some(post.name.clone()),
+          body: post.body.clone(),
+        },
+      )
+    }
+  }
+
+  pub fn comment(comment: &Comment) -> Self {
+    if comment.deleted || comment.removed {
+      Self::Delete(SearchKind::Comment, comment.id.0)
+    } else {
+      Self::Upsert(
+        SearchKind::Comment,
+        SearchDocument {
+          id: comment.id.0,
+          title: None,
+          body: Some(comment.content.clone()),
+        },
+      )
+    }
+  }
+
+  pub fn community(community: &Community) -> Self {
+    if community.deleted || community.removed {
+      Self::Delete(SearchKind::Community, community.id.0)
+    } else {
+      let body = [&community.summary, &community.sidebar]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+      Self::Upsert(
+        SearchKind::Community,
+        SearchDocument {
+          id: community.id.0,
+          title: Some(format!("{} {}", community.name, community.title)),
+          body: Some(body),
+        },
+      )
+    }
+  }
+
+  /// Changes to local content which need to be reflected in the search index.
+  fn from_activity(data: &SendActivityData) -> Option<Self> {
+    use SendActivityData::*;
+    match data {
+      CreatePost(post) | UpdatePost(post) | DeletePost(post, ..) | RemovePost { post, .. } => {
+        Some(Self::post(post))
+      }
+      CreateComment(comment)
+      | UpdateComment(comment)
+      | DeleteComment(comment, ..)
+      | RemoveComment { comment, .. } => Some(Self::comment(comment)),
+      UpdateCommunity(_, community)
+      | DeleteCommunity(_, community, _)
+      | RemoveCommunity { community, .. } => Some(Self::community(community)),
+      _ => None,
+    }
+  }
+}
+
+/// A search engine which indexes content, and returns the ids of matching items.
+trait SearchBackend {
+  async fn index(
+    &self,
+    kind: SearchKind,
+    document: &SearchDocument,
+    context: &LemmyContext,
+  ) -> LemmyResult<()>;
+
+  async fn delete(&self, kind: SearchKind, id: i32, context: &LemmyContext) -> LemmyResult<()>;
+
+  /// Ids of matching items ordered by relevance, or `None` if the database should be searched
+  /// instead.
+  async fn search(
+    &self,
+    kind: SearchKind,
+    search_term: &str,
+    limit: i64,
+    context: &LemmyContext,
+  ) -> LemmyResult<Option<Vec<i32>>>;
+}
+
+/// The default backend, which uses the full-text search of Postgres. It doesn't need a separate
+/// index, as the database is queried directly.
+struct PostgresSearch;
+
+impl SearchBackend for PostgresSearch {
+  async fn index(&self, _: SearchKind, _: &SearchDocument, _: &LemmyContext) -> LemmyResult<()> {
+    Ok(())
+  }
+
+  async fn delete(&self, _: SearchKind, _: i32, _: &LemmyContext) -> LemmyResult<()> {
+    Ok(())
+  }
+
+  async fn search(
+    &self,
+    _: SearchKind,
+    _: &str,
+    _: i64,
+    _: &LemmyContext,
+  ) -> LemmyResult<Option<Vec<i32>>> {
+    Ok(None)
+  }
+}
+
+fn search_request(
+  builder: RequestBuilder,
+  auth_scheme: &str,
+  config: &SearchConfig,
+) -> RequestBuilder {
+  let builder = builder
+    .header(CONTENT_TYPE, "application/json")
+    .timeout(REQWEST_TIMEOUT);
+  match &config.api_key {
+    Some(api_key) => builder.header(AUTHORIZATION, format!("{auth_scheme} {api_key}")),
+    None => builder,
+  }
+}
+
+fn base_url(config: &SearchConfig) -> &str {
+  config.url.as_str().trim_end_matches('/')
+}
+
+/// https://www.meilisearch.com/docs/reference/api/documents
+struct Meilisearch<'a>(&'a SearchConfig);
+
+#[derive(Deserialize)]
+struct MeilisearchResponse {
+  hits: Vec<MeilisearchHit>,
+}
+
+#[derive(Deserialize)]
+struct MeilisearchHit {
+  id: i32,
+}
+
+impl Meilisearch<'_> {
+  fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+    search_request(builder, "Bearer", self.0)
+  }
+
+  fn index_url(&self, kind: SearchKind) -> String {
+    format!("{}/indexes/{}", base_url(self.0), kind.index_name(self.0))
+  }
+}
+
+impl SearchBackend for Meilisearch<'_> {
+  async fn index(
+    &self,
+    kind: SearchKind,
+    document: &SearchDocument,
+    context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    let url = format!("{}/documents?primaryKey=id", self.index_url(kind));
+    self
+      .request(context.client().post(url))
+      .body(serde_json::to_string(&[document])?)
+      .send()
+      .await?
+      .error_for_status()?;
+    Ok(())
+  }
+
+  async fn delete(&self, kind: SearchKind, id: i32, context: &LemmyContext) -> LemmyResult<()> {
+    let url = format!("{}/documents/{id}", self.index_url(kind));
+    self
+      .request(context.client().delete(url))
+      .send()
+      .await?
+      .error_for_status()?;
+    Ok(())
+  }
+
+  async fn search(
+    &self,
+    kind: SearchKind,
+    search_term: &str,
+    limit: i64,
+    context: &LemmyContext,
+  ) -> LemmyResult<Option<Vec<i32>>> {
+    let url = format!("{}/search", self.index_url(kind));
+    let body = json!({ "q": search_term, "limit": limit, "attributesToRetrieve": ["id"] });
+    let res: MeilisearchResponse = self
+      .request(context.client().post(url))
+      .body(body.to_string())
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    Ok(Some(res.hits.into_iter().map(|h| h.id).collect()))
+  }
+}
+
+/// https://www.elastic.co/docs/api/doc/elasticsearch/group/endpoint-document
+///
+/// OpenSearch uses the same api, but doesn't support api keys. Instead, credentials can be
+/// included in the url for basic authentication.
+struct Elasticsearch<'a>(&'a SearchConfig);
+
+#[derive(Deserialize)]
+struct ElasticsearchResponse {
+  hits: ElasticsearchHits,
+}
+
+#[derive(Deserialize)]
+struct ElasticsearchHits {
+  hits: Vec<ElasticsearchHit>,
+}
+
+#[derive(Deserialize)]
+struct ElasticsearchHit {
+  #[serde(rename = "_id")]
+  id: String,
+}
+
+impl Elasticsearch<'_> {
+  fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+    search_request(builder, "ApiKey", self.0)
+  }
+
+  fn document_url(&self, kind: SearchKind, id: i32) -> String {
+    format!("{}/{}/_doc/{id}", base_url(self.0), kind.index_name(self.0))
+  }
+}
+
+impl SearchBackend for Elasticsearch<'_> {
+  async fn index(
+    &self,
+    kind: SearchKind,
+    document: &SearchDocument,
+    context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    self
+      .request(context.client().put(self.document_url(kind, document.id)))
+      .body(serde_json::to_string(document)?)
+      .send()
+      .await?
+      .error_for_status()?;
+    Ok(())
+  }
+
+  async fn delete(&self, kind: SearchKind, id: i32, context: &LemmyContext) -> LemmyResult<()> {
+    let res = self
+      .request(context.client().delete(self.document_url(kind, id)))
+      .send()
+      .await?;
+    // Deleting content which was never indexed is fine
+    if res.status() != StatusCode::NOT_FOUND {
+      res.error_for_status()?;
+    }
+    Ok(())
+  }
+
+  async fn search(
+    &self,
+    kind: SearchKind,
+    search_term: &str,
+    limit: i64,
+    context: &LemmyContext,
+  ) -> LemmyResult<Option<Vec<i32>>> {
+    let url = format!("{}/{}/_search", base_url(self.0), kind.index_name(self.0));
+    let body = json!({
+      "query": {
+        "multi_match": { "query": search_term, "fields": ["title^2", "body"] }
+      },
+      "size": limit,
+      "_source": false,
+    });
+    let res: ElasticsearchResponse = self
+      .request(context.client().post(url))
+      .body(body.to_string())
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    let ids = res.hits.hits.into_iter().filter_map(|h| h.id.parse().ok());
+    Ok(Some(ids.collect()))
+  }
+}
+
+async fn run_task<B: SearchBackend>(
+  backend: B,
+  task: SearchIndexTask,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  match task {
+    SearchIndexTask::Upsert(kind, document) => backend.index(kind, &document, context).await,
+    SearchIndexTask::Delete(kind, id) => backend.delete(kind, id, context).await,
+  }
+}
+
+/// Searches with the configured backend. Returns the ids of matching items ordered by relevance,
+/// or `None` if the database should be searched instead.
+pub async fn search_ids(
+  kind: SearchKind,
+  search_term: &str,
+  limit: i64,
+  context: &LemmyContext,
+) -> LemmyResult<Option<Vec<i32>>> {
+  let Some(config) = &context.settings().search else {
+    return PostgresSearch
+      .search(kind, search_term, limit, context)
+      .await;
+  };
+  match config.backend {
+    SearchBackendType::Meilisearch => {
+      Meilisearch(config)
+        .search(kind, search_term, limit, context)
+        .await
+    }
+    SearchBackendType::Elasticsearch => {
+      Elasticsearch(config)
+        .search(kind, search_term, limit, context)
+        .await
+    }
+  }
+}
+
+static SEARCH_INDEX_QUEUE: LazyLock<SearchIndexQueue> = LazyLock::new(|| {
+  let (sender, receiver) = mpsc::unbounded_channel();
+  let weak_sender = sender.downgrade();
+  SearchIndexQueue {
+    weak_sender,
+    receiver: Mutex::new(receiver),
+    keepalive_sender: Mutex::new(Some(sender)),
+  }
+});
+
+/// Changes to posts, comments and communities are sent to the external search backend in the
+/// background, so that a slow or unavailable search engine doesn't affect the api.
+pub struct SearchIndexQueue {
+  weak_sender: WeakUnboundedSender<SearchIndexTask>,
+  receiver: Mutex<UnboundedReceiver<SearchIndexTask>>,
+  keepalive_sender: Mutex<Option<UnboundedSender<SearchIndexTask>>>,
+}
+
+impl SearchIndexQueue {
+  /// Does nothing if no external search backend is configured.
+  pub fn submit(task: SearchIndexTask, context: &LemmyContext) -> LemmyResult<()> {
+    if context.settings().search.is_none() {
+      return Ok(());
+    }
+    if let Some(sender) = SEARCH_INDEX_QUEUE.weak_sender.upgrade() {
+      sender.send(task)?;
+    }
+    Ok(())
+  }
+
+  pub(crate) fn submit_activity(
+    data: &SendActivityData,
+    context: &LemmyContext,
+  ) -> LemmyResult<()> {
+    match SearchIndexTask::from_activity(data) {
+      Some(task) => Self::submit(task, context),
+      None => Ok(()),
+    }
+  }
+
+  async fn retrieve() -> Option<SearchIndexTask> {
+    let mut lock = SEARCH_INDEX_QUEUE.receiver.lock().await;
+    lock.recv().await
+  }
+
+  pub async fn close(search_index_task: JoinHandle<()>) -> LemmyResult<()> {
+    SEARCH_INDEX_QUEUE.keepalive_sender.lock().await.take();
+    search_index_task.await?;
+    Ok(())
+  }
+}
+
+/// Sends queued changes to the search backend until the queue is closed.
+pub async fn handle_search_index_queue(context: Data<LemmyContext>) {
+  while let Some(task) = SearchIndexQueue::retrieve().await {
+    let Some(config) = &context.settings().search else {
+      continue;
+    };
+    let res = match config.backend {
+      SearchBackendType::Meilisearch => run_task(Meilisearch(config), task, &context).await,
+      SearchBackendType::Elasticsearch => run_task(Elasticsearch(config), task, &context).await,
+    };
+    if let Err(e) = res {
+      warn!("Failed to update search index: {e}");
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_index_name() {
+    let config = SearchConfig {
+      index_prefix: "lemmy_ml".to_string(),
+      ..Default::default()
+    };
+    assert_eq!("lemmy_ml_posts", SearchKind::Post.index_name(&config));
+    assert_eq!(
+      "lemmy_ml_communities",
+      SearchKind::Community.index_name(&config)
+    );
+  }
+
+  #[test]
+  fn test_search_document() -> LemmyResult<()> {
+    let document = SearchDocument {
+      id: 5,
+      title: None,
+      body: Some("body".to_string()),
+    };
+    assert_eq!(
+      r#"{"id":5,"title":null,"body":"body"}"#,
+      serde_json::to_string(&document)?
+    );
+    Ok(())
+  }
+}
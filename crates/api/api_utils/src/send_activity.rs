@@ -1,4 +1,4 @@
-use crate::context::LemmyContext;
+use crate::{context::LemmyContext, search::SearchIndexQueue};
 use activitypub_federation::config::Data;
 use either::Either;
 use lemmy_db_schema::{
@@ -27,6 +27,7 @@ use tokio::{
   },
   task::JoinHandle,
 };
+use tracing::{Span, info_span};
 use url::Url;
 
 #[derive(Debug)]
@@ -125,22 +126,25 @@ static ACTIVITY_CHANNEL: LazyLock<ActivityChannel> = LazyLock::new(|| {
 });
 
 pub struct ActivityChannel {
-  weak_sender: WeakUnboundedSender<SendActivityData>,
-  receiver: Mutex<UnboundedReceiver<SendActivityData>>,
-  keepalive_sender: Mutex<Option<UnboundedSender<SendActivityData>>>,
+  weak_sender: WeakUnboundedSender<(SendActivityData, Span)>,
+  receiver: Mutex<UnboundedReceiver<(SendActivityData, Span)>>,
+  keepalive_sender: Mutex<Option<UnboundedSender<(SendActivityData, Span)>>>,
 }
 
 impl ActivityChannel {
-  pub async fn retrieve_activity() -> Option<SendActivityData> {
+  /// Returns the activity together with a span below the request which submitted it, so that
+  /// it can be traced.
+  pub async fn retrieve_activity() -> Option<(SendActivityData, Span)> {
     let mut lock = ACTIVITY_CHANNEL.receiver.lock().await;
     lock.recv().await
   }
 
-  pub fn submit_activity(data: SendActivityData, _context: &Data<LemmyContext>) -> LemmyResult<()> {
+  pub fn submit_activity(data: SendActivityData, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    SearchIndexQueue::submit_activity(&data, context)?;
     // could do `ACTIVITY_CHANNEL.keepalive_sender.lock()` instead and get rid of weak_sender,
     // not sure which way is more efficient
     if let Some(sender) = ACTIVITY_CHANNEL.weak_sender.upgrade() {
-      sender.send(data)?;
+      sender.send((data, info_span!("outgoing_activity")))?;
     }
     Ok(())
   }
@@ -0,0 +1,289 @@
+use crate::context::LemmyContext;
+use lemmy_db_schema::{
+  StreamType,
+  newtypes::{CommentId, CommunityId, PostId},
+  source::{comment::Comment, post::Post},
+};
+use lemmy_db_views_site::api::StreamEvent;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::{
+  collections::HashMap,
+  net::IpAddr,
+  sync::{LazyLock, Mutex, PoisonError},
+  time::Duration,
+};
+use tokio::{
+  sync::broadcast::{self, Receiver, Sender, error::RecvError},
+  time::{Instant, Interval, interval, sleep_until},
+};
+
+/// Events which are buffered for each connection. Connections which fall further behind are told
+/// to reload the content.
+const STREAM_BUFFER_SIZE: usize = 1000;
+
+/// Maximum number of streams which can be open at the same time from a single ip.
+const MAX_STREAMS_PER_IP: usize = 5;
+
+/// Maximum number of events which are sent to a connection per second. Additional events are
+/// left out, and the client is told to reload instead.
+const MAX_STREAM_EVENTS_PER_SECOND: u32 = 20;
+
+/// Comment which is sent regularly, so that proxies don't close idle connections.
+const STREAM_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Streams are closed after this time, and browsers reconnect automatically. This way permission
+/// changes and expired logins are checked again.
+const STREAM_MAX_DURATION: Duration = Duration::from_secs(60 * 60);
+
+static STREAM_CHANNEL: LazyLock<Sender<StreamEvent>> =
+  LazyLock::new(|| broadcast::channel(STREAM_BUFFER_SIZE).0);
+
+static OPEN_STREAMS: LazyLock<Mutex<HashMap<IpAddr, usize>>> = LazyLock::new(Default::default);
+
+/// Sends new posts, comments and votes to the clients which follow a community or post live.
+pub struct StreamChannel;
+
+impl StreamChannel {
+  fn send(event: StreamEvent) {
+    // Fails if nobody is listening, which is fine
+    STREAM_CHANNEL.send(event).ok();
+  }
+
+  /// Avoids reading the new scores from the database if nobody is listening.
+  fn has_listeners() -> bool {
+    STREAM_CHANNEL.receiver_count() > 0
+  }
+
+  /// Only call this once the post is published.
+  pub fn post_created(post: &Post) {
+    Self::send(StreamEvent::PostCreated {
+      post_id: post.id,
+      community_id: post.community_id,
+    });
+  }
+
+  pub fn comment_created(comment: &Comment, community_id: CommunityId) {
+    Self::send(StreamEvent::CommentCreated {
+      comment_id: comment.id,
+      post_id: comment.post_id,
+      community_id,
+    });
+  }
+
+  /// Sends the new score after a vote on the post.
+  pub async fn post_vote(post_id: PostId, context: &LemmyContext) -> LemmyResult<()> {
+    if !Self::has_listeners() {
+      return Ok(());
+    }
+    let post = Post::read(&mut context.pool(), post_id).await?;
+    Self::send(StreamEvent::PostVote {
+      post_id,
+      community_id: post.community_id,
+      score: post.score,
+      upvotes: post.upvotes,
+      downvotes: post.downvotes,
+    });
+    Ok(())
+  }
+
+  /// Sends the new score after a vote on the comment.
+  pub async fn comment_vote(comment_id: CommentId, context: &LemmyContext) -> LemmyResult<()> {
+    if !Self::has_listeners() {
+      return Ok(());
+    }
+    let comment = Comment::read(&mut context.pool(), comment_id).await?;
+    let post = Post::read(&mut context.pool(), comment.post_id).await?;
+    Self::send(StreamEvent::CommentVote {
+      comment_id,
+      post_id: comment.post_id,
+      community_id: post.community_id,
+      score: comment.score,
+      upvotes: comment.upvotes,
+      downvotes: comment.downvotes,
+    });
+    Ok(())
+  }
+}
+
+/// A single client which follows a community or post. The slot of the ip is freed when the
+/// connection is dropped.
+pub struct StreamConnection {
+  ip: Option<IpAddr>,
+  type_: StreamType,
+  id: i32,
+  receiver: Receiver<StreamEvent>,
+  keepalive: Interval,
+  closes_at: Instant,
+  window_start: Instant,
+  sent_in_window: u32,
+  skipped: bool,
+}
+
+impl StreamConnection {
+  pub fn open(ip: Option<IpAddr>, type_: StreamType, id: i32) -> LemmyResult<Self> {
+    if let Some(ip) = ip {
+      let mut open_streams = OPEN_STREAMS.lock().unwrap_or_else(PoisonError::into_inner);
+      let count = open_streams.entry(ip).or_default();
+      if *count >= MAX_STREAMS_PER_IP {
+        return Err(LemmyErrorType::TooManyRequests.into());
+      }
+      *count += 1;
+    }
+    let now = Instant::now();
+    Ok(StreamConnection {
+      ip,
+      type_,
+      id,
+      receiver: STREAM_CHANNEL.subscribe(),
+      keepalive: interval(STREAM_KEEPALIVE_INTERVAL),
+      closes_at: now + STREAM_MAX_DURATION,
+      window_start: now,
+      sent_in_window: 0,
+      skipped: false,
+    })
+  }
+
+  /// Waits for the next chunk of the response body, in server-sent events format. Returns `None`
+  /// once the stream should be closed.
+  pub async fn next(&mut self) -> Option<String> {
+    loop {
+      let event = tokio::select! {
+        _ = sleep_until(self.closes_at) => return None,
+        _ = self.keepalive.tick() => return Some(": keepalive\n\n".to_string()),
+        res = self.receiver.recv() => match res {
+          Ok(event) if is_in_scope(&event, self.type_, self.id) => event,
+          Ok(_) => continue,
+          Err(RecvError::Lagged(_)) => StreamEvent::Lagged,
+          Err(RecvError::Closed) => return None,
+        },
+      };
+      if let Some(event) = self.throttle(event, Instant::now())
+        && let Ok(data) = serde_json::to_string(&event)
+      {
+        return Some(format!("data: {data}\n\n"));
+      }
+    }
+  }
+
+  /// Limits the number of events per second. After events were left out, the next one is
+  /// replaced by [StreamEvent::Lagged].
+  fn throttle(&mut self, event: StreamEvent, now: Instant) -> Option<StreamEvent> {
+    if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+      self.window_start = now;
+      self.sent_in_window = 0;
+    }
+    if self.sent_in_window >= MAX_STREAM_EVENTS_PER_SECOND {
+      self.skipped = true;
+      return None;
+    }
+    self.sent_in_window += 1;
+    if std::mem::take(&mut self.skipped) {
+      Some(StreamEvent::Lagged)
+    } else {
+      Some(event)
+    }
+  }
+}
+
+impl Drop for StreamConnection {
+  fn drop(&mut self) {
+    let Some(ip) = self.ip else {
+      return;
+    };
+    let mut open_streams = OPEN_STREAMS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(count) = open_streams.get_mut(&ip) {
+      *count = count.saturating_sub(1);
+      if *count == 0 {
+        open_streams.remove(&ip);
+      }
+    }
+  }
+}
+
+/// Returns true if the event belongs to the community or post which is followed.
+fn is_in_scope(event: &StreamEvent, type_: StreamType, id: i32) -> bool {
+  use StreamEvent::*;
+  let (post_id, community_id) = match event {
+    PostCreated {
+      post_id,
+      community_id,
+    }
+    | CommentCreated {
+      post_id,
+      community_id,
+      ..
+    }
+    | PostVote {
+      post_id,
+      community_id,
+      ..
+    }
+    | CommentVote {
+      post_id,
+      community_id,
+      ..
+    } => (post_id, community_id),
+    Lagged => return true,
+  };
+  match type_ {
+    StreamType::Community => community_id.0 == id,
+    // New posts can't be part of an existing post
+    StreamType::Post => post_id.0 == id && !matches!(event, PostCreated { .. }),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use std::net::Ipv4Addr;
+
+  #[test]
+  fn test_is_in_scope() {
+    let post_created = StreamEvent::PostCreated {
+      post_id: PostId(1),
+      community_id: CommunityId(2),
+    };
+    let comment_created = StreamEvent::CommentCreated {
+      comment_id: CommentId(3),
+      post_id: PostId(1),
+      community_id: CommunityId(2),
+    };
+    assert!(is_in_scope(&post_created, StreamType::Community, 2));
+    assert!(!is_in_scope(&post_created, StreamType::Community, 1));
+    assert!(!is_in_scope(&post_created, StreamType::Post, 1));
+    assert!(is_in_scope(&comment_created, StreamType::Community, 2));
+    assert!(is_in_scope(&comment_created, StreamType::Post, 1));
+    assert!(!is_in_scope(&comment_created, StreamType::Post, 2));
+  }
+
+  #[tokio::test]
+  async fn test_stream_limits() -> LemmyResult<()> {
+    let ip = Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)));
+    let mut connections = (0..MAX_STREAMS_PER_IP)
+      .map(|_| StreamConnection::open(ip, StreamType::Post, 1))
+      .collect::<LemmyResult<Vec<_>>>()?;
+    assert!(StreamConnection::open(ip, StreamType::Post, 1).is_err());
+    // Closing a stream frees the slot
+    connections.pop();
+    let mut connection = StreamConnection::open(ip, StreamType::Post, 1)?;
+
+    let event = StreamEvent::PostCreated {
+      post_id: PostId(1),
+      community_id: CommunityId(2),
+    };
+    let now = Instant::now();
+    for _ in 0..MAX_STREAM_EVENTS_PER_SECOND {
+      assert_eq!(Some(event.clone()), connection.throttle(event.clone(), now));
+    }
+    assert_eq!(None, connection.throttle(event.clone(), now));
+    let later = now + Duration::from_secs(1);
+    assert_eq!(
+      Some(StreamEvent::Lagged),
+      connection.throttle(event.clone(), later)
+    );
+    assert_eq!(Some(event.clone()), connection.throttle(event, later));
+    Ok(())
+  }
+}
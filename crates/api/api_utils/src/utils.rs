@@ -1,48 +1,81 @@
 use crate::{
-  claims::Claims,
+  claims::{Claims, login_ip, login_user_agent},
   context::LemmyContext,
-  request::{delete_image_alias, fetch_pictrs_proxied_image_details, purge_image_from_pictrs_url},
+  request::{
+    cache_remote_image,
+    check_url_is_public,
+    delete_image_alias,
+    fetch_pictrs_proxied_image_details,
+    purge_image_from_pictrs_url,
+  },
+  send_activity::{ActivityChannel, SendActivityData},
 };
+use activitypub_federation::{config::Data, http_signatures::generate_actor_keypair};
 use actix_web::{HttpRequest, http::header::Header};
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
-use chrono::{DateTime, Days, Local, TimeZone, Utc};
+use chrono::{DateTime, Days, Local, TimeDelta, TimeZone, Utc};
 use enum_map::{EnumMap, enum_map};
+use futures::future::BoxFuture;
+use ipnet::IpNet;
 use lemmy_db_schema::{
-  newtypes::{CommunityId, CommunityTagId, ModlogId, PostId, PostOrCommentId},
+  newtypes::{CommunityId, CommunityTagId, LocalUserId, ModlogId, PostId, PostOrCommentId},
   source::{
+    api_key::ApiKey,
     comment::{Comment, CommentActions, CommentLikeForm},
     community::{Community, CommunityActions, CommunityUpdateForm},
+    community_pseudonym::CommunityPseudonym,
     community_tag::{CommunityTag, PostCommunityTag},
+    hashtag::PostHashtag,
     images::{ImageDetails, RemoteImage},
     instance::InstanceActions,
     local_site::LocalSite,
     local_site_rate_limit::LocalSiteRateLimit,
+    local_site_rate_limit_override::LocalSiteRateLimitOverride,
+    local_site_registration_ip_range::LocalSiteRegistrationIpRange,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
+    local_user::{LocalUser, LocalUserUpdateForm},
+    login_failure::LoginFailure,
+    login_token::LoginToken,
     modlog::{Modlog, ModlogInsertForm},
     oauth_account::OAuthAccount,
-    person::{Person, PersonUpdateForm},
+    person::{Person, PersonInsertForm, PersonUpdateForm},
     post::{Post, PostActions, PostLikeForm, PostReadCommentsForm},
+    pow_challenge::PowChallenge,
     private_message::PrivateMessage,
+    rate_limit_bucket::RateLimitBucket,
     registration_application::RegistrationApplication,
     site::Site,
   },
-  traits::Likeable,
+  traits::{ApubActor, Likeable},
 };
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  enums::{FederationMode, ImageMode, RegistrationMode},
+  enums::{
+    FederationMode,
+    ImageMode,
+    RateLimitAction,
+    RateLimitUserClass,
+    RegistrationMode,
+    TokenScope,
+  },
 };
 use lemmy_db_views_community_follower_approval::PendingFollowerView;
 use lemmy_db_views_community_moderator::{CommunityModeratorView, CommunityPersonBanView};
 use lemmy_db_views_local_image::LocalImageView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
-use lemmy_diesel_utils::{connection::DbPool, dburl::DbUrl, traits::Crud};
+use lemmy_diesel_utils::{
+  connection::{ActualDbPool, DbPool},
+  dburl::DbUrl,
+  traits::Crud,
+};
+use lemmy_email::account::{send_login_lockout_email, send_new_login_email};
 use lemmy_utils::{
   CACHE_DURATION_FEDERATION,
   CacheLock,
   MAX_COMMENT_DEPTH_LIMIT,
+  REQWEST_TIMEOUT,
   error::{
     LemmyError,
     LemmyErrorExt,
@@ -51,24 +84,40 @@ use lemmy_utils::{
     LemmyResult,
     UntranslatedError,
   },
-  rate_limit::{ActionType, BucketConfig},
+  rate_limit::{ActionType, BucketConfig, RateLimitOverrides, RateLimitStore, UserClass},
   settings::SETTINGS,
   spawn_try_task,
   utils::{
-    markdown::{image_links::markdown_rewrite_image_links, markdown_check_for_blocked_urls},
+    hashtag::scrape_text_for_hashtags,
+    markdown::{
+      image_links::{markdown_remote_image_links, markdown_rewrite_image_links},
+      markdown_check_for_blocked_urls,
+    },
     slurs::remove_slurs,
     validation::{build_and_check_regex, clean_urls_in_text},
   },
 };
 use moka::future::Cache;
 use regex::{Regex, RegexSet, escape};
-use std::{collections::HashSet, sync::LazyLock};
-use tracing::Instrument;
+use sha2::{Digest, Sha256};
+use std::{
+  collections::HashSet,
+  net::{IpAddr, SocketAddr},
+  sync::LazyLock,
+  time::Duration,
+};
+use tracing::{Instrument, warn};
 use url::{ParseError, Url};
 use urlencoding::encode;
+use uuid::Uuid;
 use webmention::{Webmention, WebmentionError};
 
 pub const AUTH_COOKIE_NAME: &str = "jwt";
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+/// How often the site settings are reloaded from the database, so that changes made on another
+/// server apply without a restart.
+const RELOAD_SITE_SETTINGS_INTERVAL: Duration = Duration::from_secs(30);
 
 pub async fn check_is_mod_or_admin(
   pool: &mut DbPool<'_>,
@@ -260,6 +309,69 @@ pub async fn check_community_user_action(
   Ok(())
 }
 
+/// Returns the pseudonym of the user in the community, and creates it when it is used for the
+/// first time. Bans of the pseudonym apply in addition to those of the user.
+pub async fn get_or_create_pseudonym(
+  local_user_view: &LocalUserView,
+  community: &Community,
+  context: &LemmyContext,
+) -> LemmyResult<Person> {
+  if !community.pseudonymous_posting {
+    return Err(LemmyErrorType::PseudonymousPostingDisabled.into());
+  }
+  let pool = &mut context.pool();
+  let person_id = local_user_view.person.id;
+  let pseudonym = match CommunityPseudonym::read(pool, community.id, person_id).await? {
+    Some(p) => Person::read(pool, p.pseudonym_id).await?,
+    None => {
+      // The name must not contain anything from the real account
+      let random: String = Uuid::new_v4()
+        .simple()
+        .to_string()
+        .chars()
+        .take(12)
+        .collect();
+      let name = format!("anon_{random}");
+      let keypair = generate_actor_keypair()?;
+      let form = PersonInsertForm {
+        ap_id: Some(Person::generate_local_actor_url(&name, context.settings())?),
+        inbox_url: Some(generate_inbox_url()?),
+        private_key: Some(keypair.private_key),
+        ..PersonInsertForm::new(name, keypair.public_key, local_user_view.person.instance_id)
+      };
+      match CommunityPseudonym::create_with_person(pool, community.id, person_id, &form).await {
+        Ok(pseudonym) => pseudonym,
+        // Another request of the same user created the pseudonym in the meantime
+        Err(e) => match CommunityPseudonym::read(pool, community.id, person_id).await? {
+          Some(p) => Person::read(pool, p.pseudonym_id).await?,
+          None => return Err(e),
+        },
+      }
+    }
+  };
+  CommunityPersonBanView::check(pool, pseudonym.id, community.id).await?;
+  InstanceActions::check_ban(pool, pseudonym.id, community.instance_id).await?;
+  Ok(pseudonym)
+}
+
+/// Returns the creator of a post or comment if it belongs to the user, either because they
+/// created it directly or under their pseudonym. This is the actor for federated edits.
+pub async fn own_content_creator(
+  local_user_view: &LocalUserView,
+  creator_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Option<Person>> {
+  if local_user_view.person.id == creator_id {
+    return Ok(Some(local_user_view.person.clone()));
+  }
+  match CommunityPseudonym::read_for_pseudonym(pool, creator_id).await? {
+    Some(p) if p.person_id == local_user_view.person.id => {
+      Ok(Some(Person::read(pool, creator_id).await?))
+    }
+    _ => Ok(None),
+  }
+}
+
 pub fn check_community_deleted_removed(community: &Community) -> LemmyResult<()> {
   if community.deleted || community.removed {
     return Err(LemmyErrorType::Deleted.into());
@@ -382,6 +494,129 @@ pub fn honeypot_check(honeypot: &Option<String>) -> LemmyResult<()> {
   }
 }
 
+/// The ip of the client which sent the request, without the port.
+pub fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+  let ip = login_ip(req)?;
+  ip.parse::<IpAddr>()
+    .ok()
+    .or_else(|| ip.parse::<SocketAddr>().ok().map(|a| a.ip()))
+    .map(|ip| ip.to_canonical())
+}
+
+/// Parses ip ranges in CIDR notation, or single ips, and returns them normalized.
+pub fn parse_ip_ranges(ip_ranges: &[String]) -> LemmyResult<Vec<String>> {
+  ip_ranges
+    .iter()
+    .map(|r| {
+      let r = r.trim();
+      r.parse::<IpNet>()
+        .or_else(|_| r.parse::<IpAddr>().map(IpNet::from))
+        .map(|n| n.trunc().to_string())
+        .with_lemmy_type(LemmyErrorType::InvalidIpRange)
+    })
+    .collect()
+}
+
+/// The registration firewall. Registrations from allowed ranges skip all the other checks.
+pub async fn check_registration_ip(
+  ip: Option<IpAddr>,
+  local_site: &LocalSite,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let Some(ip) = ip else {
+    return Ok(());
+  };
+
+  let ip_ranges = LocalSiteRegistrationIpRange::get_all(pool).await?;
+  let in_ranges = |allowed: bool| {
+    ip_ranges
+      .iter()
+      .filter(|r| r.allowed == allowed)
+      .filter_map(|r| r.ip_range.parse::<IpNet>().ok())
+      .any(|r| r.contains(&ip))
+  };
+  if in_ranges(true) {
+    return Ok(());
+  }
+  if in_ranges(false) {
+    return Err(LemmyErrorType::RegistrationIpBlocked.into());
+  }
+
+  if let Some(max_registrations) = local_site.registrations_per_ip_per_day {
+    let count = LocalUser::count_registrations_from_ip(pool, &ip.to_string()).await?;
+    if count >= i64::from(max_registrations) {
+      return Err(LemmyErrorType::TooManyRegistrationsFromIp.into());
+    }
+  }
+
+  if let Some(zone) = &local_site.registration_dnsbl
+    && is_listed_in_dnsbl(ip, zone).await
+  {
+    return Err(LemmyErrorType::RegistrationIpBlocked.into());
+  }
+  Ok(())
+}
+
+/// Listed ips resolve to an address in the blocklist zone. Failed lookups count as not listed,
+/// so that registrations still work if the blocklist is down.
+async fn is_listed_in_dnsbl(ip: IpAddr, zone: &str) -> bool {
+  let query = dnsbl_query(ip, zone);
+  let lookup = tokio::net::lookup_host((query.as_str(), 0));
+  match tokio::time::timeout(Duration::from_secs(5), lookup).await {
+    Ok(Ok(mut addrs)) => addrs.next().is_some(),
+    _ => false,
+  }
+}
+
+/// The reversed ip, in nibbles for ipv6, prepended to the zone.
+fn dnsbl_query(ip: IpAddr, zone: &str) -> String {
+  let reversed: Vec<String> = match ip {
+    IpAddr::V4(ip) => ip.octets().iter().rev().map(u8::to_string).collect(),
+    IpAddr::V6(ip) => ip
+      .octets()
+      .iter()
+      .rev()
+      .flat_map(|b| [b & 0xf, b >> 4])
+      .map(|n| format!("{n:x}"))
+      .collect(),
+  };
+  format!("{}.{zone}", reversed.join("."))
+}
+
+/// Uses up the proof of work challenge, and checks the solution with the difficulty at the time
+/// it was issued.
+pub async fn check_pow_challenge(
+  challenge: Option<&str>,
+  nonce: Option<&str>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let (Some(challenge), Some(nonce)) = (challenge, nonce) else {
+    return Err(LemmyErrorType::InvalidPowChallenge.into());
+  };
+  let pow_challenge = PowChallenge::read_and_delete(pool, challenge).await?;
+  if is_valid_pow_solution(challenge, nonce, pow_challenge.difficulty) {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::InvalidPowChallenge.into())
+  }
+}
+
+/// Checks that the sha256 hash of challenge and nonce starts with `difficulty` zero bits.
+pub fn is_valid_pow_solution(challenge: &str, nonce: &str, difficulty: i32) -> bool {
+  let hash = Sha256::new()
+    .chain_update(challenge)
+    .chain_update(nonce)
+    .finalize();
+  let mut zero_bits = 0;
+  for b in hash {
+    zero_bits += b.leading_zeros();
+    if b != 0 {
+      break;
+    }
+  }
+  i64::from(zero_bits) >= i64::from(difficulty)
+}
+
 pub fn local_site_rate_limit_to_rate_limit_config(
   l: &LocalSiteRateLimit,
 ) -> EnumMap<ActionType, BucketConfig> {
@@ -400,6 +635,104 @@ pub fn local_site_rate_limit_to_rate_limit_config(
   })
 }
 
+/// Converts the overrides from the database to the config of the rate limiter.
+pub fn rate_limit_overrides(overrides: &[LocalSiteRateLimitOverride]) -> RateLimitOverrides {
+  let mut res = RateLimitOverrides::default();
+  for o in overrides {
+    let user_class = match o.user_class {
+      RateLimitUserClass::Anonymous => UserClass::Anonymous,
+      RateLimitUserClass::NewAccount => UserClass::NewAccount,
+      RateLimitUserClass::Established => UserClass::Established,
+      RateLimitUserClass::Bot => UserClass::Bot,
+      RateLimitUserClass::Admin => UserClass::Admin,
+    };
+    let action = match o.action {
+      RateLimitAction::Message => ActionType::Message,
+      RateLimitAction::Register => ActionType::Register,
+      RateLimitAction::Post => ActionType::Post,
+      RateLimitAction::Image => ActionType::Image,
+      RateLimitAction::Comment => ActionType::Comment,
+      RateLimitAction::Search => ActionType::Search,
+      RateLimitAction::ImportUserSettings => ActionType::ImportUserSettings,
+    };
+    res[user_class][action] = Some(BucketConfig {
+      max_requests: u32::try_from(o.max_requests).unwrap_or(0),
+      interval: u32::try_from(o.interval_seconds).unwrap_or(0),
+    });
+  }
+  res
+}
+
+/// Reads the rate limit overrides from the database, and applies them to this process.
+pub async fn reload_rate_limit_overrides(context: &LemmyContext) -> LemmyResult<()> {
+  let overrides = LocalSiteRateLimitOverride::list(&mut context.pool()).await?;
+  context
+    .rate_limit_cell()
+    .set_overrides(rate_limit_overrides(&overrides));
+  Ok(())
+}
+
+/// Which rate limits apply to the user. Without the local site, the user is treated like an
+/// established account.
+pub fn rate_limit_user_class(
+  local_user_view: &LocalUserView,
+  local_site: Option<&LocalSite>,
+) -> UserClass {
+  if local_user_view.local_user.admin {
+    UserClass::Admin
+  } else if local_user_view.person.bot_account {
+    UserClass::Bot
+  } else if local_site.is_some_and(|s| is_restricted_new_account(local_user_view, s)) {
+    UserClass::NewAccount
+  } else {
+    UserClass::Established
+  }
+}
+
+/// Applies the site settings which are kept in memory, like the rate limits. Editing the site only
+/// updates the process which handles the request, so other processes pick up changes from here
+/// without a restart. Other settings are read from the database cache when they are used.
+pub async fn reload_site_settings(context: Data<LemmyContext>) {
+  let mut interval = tokio::time::interval(RELOAD_SITE_SETTINGS_INTERVAL);
+  loop {
+    interval.tick().await;
+    match SiteView::read_local(&mut context.pool()).await {
+      Ok(site_view) => {
+        context
+          .rate_limit_cell()
+          .set_config(local_site_rate_limit_to_rate_limit_config(
+            &site_view.local_site_rate_limit,
+          ))
+      }
+      Err(e) => warn!("Failed to reload site settings: {e}"),
+    }
+    reload_rate_limit_overrides(&context)
+      .await
+      .inspect_err(|e| warn!("Failed to reload rate limit overrides: {e}"))
+      .ok();
+  }
+}
+
+/// Keeps the rate limit buckets in the database, so that all Lemmy servers share the limits.
+pub struct PostgresRateLimitStore(pub ActualDbPool);
+
+impl RateLimitStore for PostgresRateLimitStore {
+  fn count_request(
+    &self,
+    key: String,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, DateTime<Utc>)>> {
+    Box::pin(async move {
+      let bucket = RateLimitBucket::count_request(&mut (&self.0).into(), &key, interval).await?;
+      Ok((u64::try_from(bucket.count).unwrap_or(0), bucket.expires_at))
+    })
+  }
+
+  fn rollback(&self, key: String) -> BoxFuture<'_, LemmyResult<()>> {
+    Box::pin(async move { RateLimitBucket::rollback(&mut (&self.0).into(), &key).await })
+  }
+}
+
 pub async fn slur_regex(context: &LemmyContext) -> LemmyResult<Regex> {
   static CACHE: CacheLock<Regex> = LazyLock::new(|| {
     Cache::builder()
@@ -458,6 +791,107 @@ pub fn check_nsfw_allowed(nsfw: Option<bool>, local_site: Option<&LocalSite>) ->
   Ok(())
 }
 
+/// Check if the restrictions for new accounts apply to this user, because the account is younger
+/// than the configured number of days, or has too little karma. Admins are never restricted.
+pub fn is_restricted_new_account(local_user_view: &LocalUserView, local_site: &LocalSite) -> bool {
+  if local_user_view.local_user.admin {
+    return false;
+  }
+  let person = &local_user_view.person;
+
+  let min_age = Days::new(
+    local_site
+      .new_account_min_age_days
+      .try_into()
+      .unwrap_or_default(),
+  );
+  let too_young = person
+    .published_at
+    .checked_add_days(min_age)
+    .is_some_and(|t| t > Utc::now());
+
+  let karma = person.post_score.saturating_add(person.comment_score);
+  let too_little_karma =
+    local_site.new_account_min_karma > 0 && karma < local_site.new_account_min_karma;
+
+  too_young || too_little_karma
+}
+
+/// Enforce the daily post limit for new accounts.
+pub async fn check_new_account_post_limit(
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let Some(max_posts) = local_site.new_account_max_posts_per_day else {
+    return Ok(());
+  };
+  if !is_restricted_new_account(local_user_view, local_site) {
+    return Ok(());
+  }
+  let since = Utc::now() - Days::new(1);
+  let count = Post::user_post_count_since(local_user_view.person.id, since, pool).await?;
+  if count >= i64::from(max_posts) {
+    return Err(LemmyErrorType::NewAccountPostLimitReached.into());
+  }
+  Ok(())
+}
+
+/// Dont allow new accounts to create link posts, if this is disabled.
+pub fn check_new_account_link_post(
+  is_link_post: bool,
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+) -> LemmyResult<()> {
+  if is_link_post
+    && local_site.new_account_link_posts_disabled
+    && is_restricted_new_account(local_user_view, local_site)
+  {
+    return Err(LemmyErrorType::NewAccountLinkPostsNotAllowed.into());
+  }
+  Ok(())
+}
+
+/// Dont allow new accounts to upload images, if this is disabled.
+pub fn check_new_account_image_upload(
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+) -> LemmyResult<()> {
+  if local_site.new_account_image_upload_disabled
+    && is_restricted_new_account(local_user_view, local_site)
+  {
+    return Err(LemmyErrorType::NewAccountImageUploadNotAllowed.into());
+  }
+  Ok(())
+}
+
+/// Returns the upload quota of the user in bytes, or `None` if uploads are unlimited. Admins don't
+/// have a quota.
+pub fn image_upload_quota_bytes(
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+) -> Option<i64> {
+  if local_user_view.local_user.admin {
+    return None;
+  }
+  local_site
+    .image_upload_quota_mb
+    .map(|q| i64::from(q) * 1024 * 1024)
+}
+
+/// Dont allow new accounts to create communities, if this is disabled.
+pub fn check_new_account_community_creation(
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+) -> LemmyResult<()> {
+  if local_site.new_account_community_creation_disabled
+    && is_restricted_new_account(local_user_view, local_site)
+  {
+    return Err(LemmyErrorType::NewAccountCommunityCreationNotAllowed.into());
+  }
+  Ok(())
+}
+
 /// Read the site for an ap_id.
 ///
 /// Used for GetCommunityResponse and GetPersonDetails
@@ -773,6 +1207,53 @@ pub async fn purge_user_account(
   Ok(())
 }
 
+/// Erases the account, logs out all sessions and federates the deletion. Called after the grace
+/// period, or right away if there is none.
+pub async fn delete_user_account(
+  local_user_view: LocalUserView,
+  delete_content: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let person_id = local_user_view.person.id;
+  let local_instance_id = local_user_view.person.instance_id;
+
+  if delete_content {
+    purge_user_account(person_id, local_instance_id, context).await?;
+  } else {
+    // These are already run in purge_user_account,
+    // but should be done anyway even if delete_content is false
+    OAuthAccount::delete_user_accounts(pool, local_user_view.local_user.id).await?;
+    CommunityActions::leave_mod_team_for_all_communities(pool, person_id).await?;
+    Person::delete_account(pool, person_id, local_instance_id).await?;
+  }
+
+  LoginToken::invalidate_all(pool, local_user_view.local_user.id).await?;
+  ApiKey::delete_all_for_user(pool, local_user_view.local_user.id).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::DeleteUser(local_user_view.person, delete_content),
+    context,
+  )?;
+  Ok(())
+}
+
+/// Logging in during the grace period of an account deletion cancels it.
+pub async fn cancel_account_deletion(
+  local_user_view: &LocalUserView,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  if local_user_view.local_user.deletion_scheduled_at.is_some() {
+    let form = LocalUserUpdateForm {
+      deletion_scheduled_at: Some(None),
+      deletion_delete_content: Some(false),
+      ..Default::default()
+    };
+    LocalUser::update(pool, local_user_view.local_user.id, &form).await?;
+  }
+  Ok(())
+}
+
 pub fn generate_followers_url(ap_id: &DbUrl) -> Result<DbUrl, ParseError> {
   Ok(Url::parse(&format!("{ap_id}/followers"))?.into())
 }
@@ -844,8 +1325,20 @@ pub async fn process_markdown(
 
   markdown_check_for_blocked_urls(&text, url_blocklist)?;
 
-  if local_site.image_mode == ImageMode::ProxyAllImages {
-    let (text, links) = markdown_rewrite_image_links(text);
+  if local_site.image_mode == ImageMode::ProxyAllImages || local_site.image_proxy_markdown {
+    let safelist = image_proxy_safelist(local_site);
+    let mut too_large = HashSet::new();
+    if let Some(max_size_mb) = local_site.image_proxy_max_size_mb {
+      let max_bytes = u64::try_from(max_size_mb)? * 1024 * 1024;
+      for link in markdown_remote_image_links(&text) {
+        if remote_image_too_large(&link, max_bytes, context).await {
+          too_large.insert(link);
+        }
+      }
+    }
+    let (text, links) = markdown_rewrite_image_links(text, |link| {
+      !too_large.contains(link) && is_image_safelisted(link, safelist.as_deref())
+    });
     RemoteImage::create(&mut context.pool(), links.clone()).await?;
 
     // Create images and image detail rows
@@ -864,6 +1357,80 @@ pub async fn process_markdown(
   }
 }
 
+/// Keeps permanent copies of the custom emojis from a received post or comment in the background,
+/// as the same emojis are usually shown in many posts. Does nothing unless markdown images are
+/// proxied.
+pub fn cache_remote_emojis(links: Vec<Url>, local_site: &LocalSite, context: &LemmyContext) {
+  if local_site.image_mode != ImageMode::ProxyAllImages && !local_site.image_proxy_markdown {
+    return;
+  }
+  let safelist = image_proxy_safelist(local_site);
+  let links: Vec<_> = links
+    .into_iter()
+    .filter(|link| link.domain() != Some(&context.settings().hostname))
+    .filter(|link| is_image_safelisted(link, safelist.as_deref()))
+    .collect();
+  if links.is_empty() {
+    return;
+  }
+  let context = context.clone();
+  spawn_try_task(async move {
+    RemoteImage::create(&mut context.pool(), links.clone()).await?;
+    for link in links {
+      if check_url_is_public(&link).await.is_err() {
+        continue;
+      }
+      cache_remote_image(&link, &context)
+        .await
+        .inspect_err(|e| warn!("Failed to cache emoji {link}: {e}"))
+        .ok();
+    }
+    Ok(())
+  });
+}
+
+/// Domains from which external images in markdown may be embedded, or `None` if all are allowed.
+fn image_proxy_safelist(local_site: &LocalSite) -> Option<Vec<String>> {
+  let safelist = local_site.image_proxy_safelist.as_deref()?;
+  Some(
+    safelist
+      .split(',')
+      .map(|d| d.trim().to_lowercase())
+      .filter(|d| !d.is_empty())
+      .collect(),
+  )
+}
+
+/// Checks if the image is hosted on one of the safelisted domains or their subdomains.
+fn is_image_safelisted(link: &Url, safelist: Option<&[String]>) -> bool {
+  let Some(safelist) = safelist else {
+    return true;
+  };
+  let Some(domain) = link.domain().map(str::to_lowercase) else {
+    return false;
+  };
+  safelist
+    .iter()
+    .any(|s| domain == *s || domain.ends_with(&format!(".{s}")))
+}
+
+/// Checks the size of a remote image with a HEAD request. Images on non-public hosts are treated
+/// as too large so that they are never proxied, while unknown sizes are allowed.
+async fn remote_image_too_large(link: &Url, max_bytes: u64, context: &LemmyContext) -> bool {
+  if check_url_is_public(link).await.is_err() {
+    return true;
+  }
+  context
+    .client()
+    .head(link.as_str())
+    .timeout(REQWEST_TIMEOUT)
+    .send()
+    .await
+    .ok()
+    .and_then(|res| res.content_length())
+    .is_some_and(|len| len > max_bytes)
+}
+
 pub async fn process_markdown_opt(
   text: &Option<String>,
   slur_regex: &Regex,
@@ -887,6 +1454,7 @@ async fn proxy_image_link_internal(
   link: Url,
   local_site: &LocalSite,
   is_thumbnail: bool,
+  cache_image: bool,
   context: &LemmyContext,
 ) -> LemmyResult<DbUrl> {
   // Dont rewrite links pointing to local domain.
@@ -904,6 +1472,13 @@ async fn proxy_image_link_internal(
       ImageDetails::create(&mut context.pool(), &details_form).await?;
     };
 
+    // Keep a permanent copy in the background, so that it can be served even if the remote
+    // instance goes down
+    if cache_image {
+      let context = context.clone();
+      spawn_try_task(async move { cache_remote_image(&link, &context).await });
+    }
+
     Ok(proxied.into())
   } else {
     Ok(link.into())
@@ -911,23 +1486,24 @@ async fn proxy_image_link_internal(
 }
 
 /// Rewrite a link to go through `/api/v4/image_proxy` endpoint. This is only for remote urls and
-/// if image_proxy setting is enabled.
+/// if image_proxy setting is enabled. Thumbnails are also cached permanently in pictrs.
 pub async fn proxy_image_link(
   link: Url,
   local_site: &LocalSite,
   is_thumbnail: bool,
   context: &LemmyContext,
 ) -> LemmyResult<DbUrl> {
-  proxy_image_link_internal(link, local_site, is_thumbnail, context).await
+  proxy_image_link_internal(link, local_site, is_thumbnail, is_thumbnail, context).await
 }
 
+/// Proxies the avatar, icon or banner of a remote actor, and caches it permanently in pictrs.
 pub async fn proxy_image_link_opt_apub(
   link: Option<Url>,
   local_site: &LocalSite,
   context: &LemmyContext,
 ) -> LemmyResult<Option<DbUrl>> {
   if let Some(l) = link {
-    proxy_image_link(l, local_site, false, context)
+    proxy_image_link_internal(l, local_site, false, true, context)
       .await
       .map(Some)
   } else {
@@ -956,13 +1532,198 @@ pub async fn local_user_view_from_jwt(
   jwt: &str,
   context: &LemmyContext,
 ) -> LemmyResult<LocalUserView> {
-  let local_user_id = Claims::validate(jwt, context)
+  Ok(local_user_view_and_scope_from_jwt(jwt, context).await?.0)
+}
+
+/// Same as [local_user_view_from_jwt], but also returns the scope of the token. This is `None`
+/// for regular logins which have full access.
+pub async fn local_user_view_and_scope_from_jwt(
+  jwt: &str,
+  context: &LemmyContext,
+) -> LemmyResult<(LocalUserView, Option<TokenScope>)> {
+  let login_token = Claims::validate(jwt, context)
     .await
     .with_lemmy_type(LemmyErrorType::NotLoggedIn)?;
-  let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), login_token.user_id).await?;
+  check_local_user_deleted(&local_user_view)?;
+
+  Ok((local_user_view, login_token.scope))
+}
+
+pub fn read_api_key(req: &HttpRequest) -> Option<String> {
+  req
+    .headers()
+    .get(API_KEY_HEADER)
+    .and_then(|h| h.to_str().ok())
+    .map(ToString::to_string)
+}
+
+/// Send an email to the user if they log in with a device which isn't used by any of their current
+/// sessions. Needs to be called before the new login token is created.
+pub async fn send_new_login_email_if_new_device(
+  local_user_view: &LocalUserView,
+  req: &HttpRequest,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if local_user_view.local_user.email.is_none() {
+    return Ok(());
+  }
+  let user_agent = login_user_agent(req);
+  let sessions = LoginToken::list(&mut context.pool(), local_user_view.local_user.id).await?;
+  if sessions.iter().any(|s| s.user_agent == user_agent) {
+    return Ok(());
+  }
+  send_new_login_email(
+    local_user_view,
+    login_ip(req).as_deref(),
+    user_agent.as_deref(),
+    context.settings(),
+  )
+}
+
+/// Failed logins before an account is locked. Ips have a higher limit, as they may be shared by
+/// many users.
+const LOGIN_FAILURES_BEFORE_ACCOUNT_LOCKOUT: i32 = 5;
+const LOGIN_FAILURES_BEFORE_IP_LOCKOUT: i32 = 20;
+const MAX_LOGIN_LOCKOUT_MINUTES: i64 = 24 * 60;
+
+/// Rejects logins for locked accounts and from locked ips.
+pub async fn check_login_not_locked(
+  local_user_id: Option<LocalUserId>,
+  ip: Option<&str>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  if LoginFailure::is_locked(pool, local_user_id, ip).await? {
+    Err(LemmyErrorType::TooManyLoginAttempts.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Counts a failed login for the account and the ip, and locks them after repeated failures. The
+/// user gets an email when their account is locked for the first time.
+pub async fn record_login_failure(
+  local_user_view: Option<&LocalUserView>,
+  ip: Option<&str>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if let Some(ip) = ip {
+    let failure = LoginFailure::record_for_ip(&mut context.pool(), ip).await?;
+    if let Some(minutes) =
+      login_lockout_minutes(failure.failure_count, LOGIN_FAILURES_BEFORE_IP_LOCKOUT)
+    {
+      let locked_until = Utc::now() + TimeDelta::minutes(minutes);
+      LoginFailure::lock(&mut context.pool(), failure.id, locked_until).await?;
+    }
+  }
+
+  if let Some(local_user_view) = local_user_view {
+    let failure =
+      LoginFailure::record_for_user(&mut context.pool(), local_user_view.local_user.id).await?;
+    if let Some(minutes) =
+      login_lockout_minutes(failure.failure_count, LOGIN_FAILURES_BEFORE_ACCOUNT_LOCKOUT)
+    {
+      let locked_until = Utc::now() + TimeDelta::minutes(minutes);
+      LoginFailure::lock(&mut context.pool(), failure.id, locked_until).await?;
+
+      if failure.failure_count == LOGIN_FAILURES_BEFORE_ACCOUNT_LOCKOUT
+        && local_user_view.local_user.email.is_some()
+      {
+        send_login_lockout_email(
+          local_user_view,
+          ip,
+          failure.failure_count,
+          minutes,
+          context.settings(),
+        )?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// The lockout doubles with every failure after the limit, up to a day.
+fn login_lockout_minutes(failure_count: i32, limit: i32) -> Option<i64> {
+  let exponent = u32::try_from(failure_count - limit).ok()?.min(20);
+  Some((1i64 << exponent).min(MAX_LOGIN_LOCKOUT_MINUTES))
+}
+
+/// Only a hash of api keys and OAuth authorization codes is stored, so that they are useless if
+/// the database leaks.
+pub fn hash_token(token: &str) -> String {
+  format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub async fn local_user_view_from_api_key(
+  key: &str,
+  context: &LemmyContext,
+) -> LemmyResult<(LocalUserView, ApiKey)> {
+  let api_key = ApiKey::read_from_hash(&mut context.pool(), &hash_token(key)).await?;
+  check_api_key_rate_limit(&api_key, context)?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), api_key.local_user_id).await?;
   check_local_user_deleted(&local_user_view)?;
 
-  Ok(local_user_view)
+  Ok((local_user_view, api_key))
+}
+
+/// Apply the rate limit of an api key, in addition to the regular rate limits.
+fn check_api_key_rate_limit(api_key: &ApiKey, context: &LemmyContext) -> LemmyResult<()> {
+  let Some(rate_limit) = api_key.rate_limit else {
+    return Ok(());
+  };
+  let max_requests = u32::try_from(rate_limit).unwrap_or_default();
+  if context
+    .rate_limit_cell()
+    .check_api_key(api_key.id.0, max_requests)
+  {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::TooManyRequests.into())
+  }
+}
+
+/// Check that a token with the given scope may be used for an endpoint which requires the
+/// `required` scope, or `None` if it can only be used with a regular login. Tokens without scope
+/// are regular logins, and can be used everywhere.
+pub fn check_token_scope(
+  scope: Option<TokenScope>,
+  required: Option<TokenScope>,
+) -> LemmyResult<()> {
+  let Some(scope) = scope else {
+    return Ok(());
+  };
+  match required {
+    Some(required) if scope >= required => Ok(()),
+    _ => Err(LemmyErrorType::InsufficientTokenScope.into()),
+  }
+}
+
+/// If the site requires it, admins and mods need to enable two-factor authentication before they
+/// can use moderation endpoints. Requests by other users are rejected later by the endpoint
+/// itself.
+pub async fn check_moderator_totp_enabled(
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if local_user_view.local_user.totp_2fa_enabled {
+    return Ok(());
+  }
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  if !local_site.moderators_require_totp {
+    return Ok(());
+  }
+  let is_moderator = local_user_view.local_user.admin
+    || CommunityModeratorView::is_community_moderator_of_any(
+      &mut context.pool(),
+      local_user_view.person.id,
+    )
+    .await
+    .is_ok();
+  if is_moderator {
+    Err(LemmyErrorType::TotpRequiredForModeration.into())
+  } else {
+    Ok(())
+  }
 }
 
 pub fn read_auth_token(req: &HttpRequest) -> LemmyResult<Option<String>> {
@@ -1033,12 +1794,36 @@ pub async fn update_post_tags(
   Ok(())
 }
 
+/// Stores the hashtags which are used in the post title and body, together with any hashtags
+/// that were received over federation.
+pub async fn update_post_hashtags(
+  post: &Post,
+  federated_hashtags: Vec<String>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let text = format!("{} {}", post.name, post.body.as_deref().unwrap_or_default());
+  let mut hashtags = scrape_text_for_hashtags(&text);
+  for hashtag in federated_hashtags {
+    if !hashtags.contains(&hashtag) {
+      hashtags.push(hashtag);
+    }
+  }
+  PostHashtag::update(&mut context.pool(), post.id, &hashtags).await?;
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
   use diesel_ltree::Ltree;
   use lemmy_db_schema::{
     newtypes::{CommentId, LanguageId},
+    source::{
+      api_key::ApiKeyInsertForm,
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::PersonInsertForm,
+    },
     test_data::TestData,
   };
   use pretty_assertions::assert_eq;
@@ -1053,6 +1838,228 @@ mod tests {
     assert!(password_length_check("looooooooooooooooooooooooooooooooooooooooooooooooooooooooooong").is_err());
   }
 
+  #[test]
+  fn test_token_scope() {
+    use TokenScope::*;
+
+    // Regular logins have full access
+    assert!(check_token_scope(None, None).is_ok());
+    assert!(check_token_scope(None, Some(Moderate)).is_ok());
+
+    assert!(check_token_scope(Some(Read), Some(Read)).is_ok());
+    assert!(check_token_scope(Some(Read), Some(Write)).is_err());
+    assert!(check_token_scope(Some(Write), Some(Read)).is_ok());
+    assert!(check_token_scope(Some(Write), Some(Moderate)).is_err());
+    assert!(check_token_scope(Some(Moderate), Some(Moderate)).is_ok());
+
+    // Account security can't be changed by third-party apps at all
+    assert!(check_token_scope(Some(Moderate), None).is_err());
+  }
+
+  #[test]
+  fn test_parse_ip_ranges() -> LemmyResult<()> {
+    let ip_ranges = parse_ip_ranges(&[
+      "192.0.2.17/24".to_string(),
+      " 198.51.100.7 ".to_string(),
+      "2001:db8::1/32".to_string(),
+    ])?;
+    assert_eq!(
+      vec!["192.0.2.0/24", "198.51.100.7/32", "2001:db8::/32"],
+      ip_ranges
+    );
+    assert!(parse_ip_ranges(&["192.0.2.0/33".to_string()]).is_err());
+    assert!(parse_ip_ranges(&["example.com".to_string()]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_is_valid_pow_solution() -> LemmyResult<()> {
+    assert!(is_valid_pow_solution("challenge", "any nonce", 0));
+    assert!(!is_valid_pow_solution("challenge", "any nonce", 257));
+
+    let nonce = (0..u32::MAX)
+      .map(|n| n.to_string())
+      .find(|n| is_valid_pow_solution("challenge", n, 8))
+      .ok_or(LemmyErrorType::InvalidPowChallenge)?;
+    let hash = Sha256::new()
+      .chain_update("challenge")
+      .chain_update(&nonce)
+      .finalize();
+    assert_eq!(Some(&0), hash.first());
+    Ok(())
+  }
+
+  #[test]
+  fn test_login_lockout_minutes() {
+    assert_eq!(None, login_lockout_minutes(4, 5));
+    assert_eq!(Some(1), login_lockout_minutes(5, 5));
+    assert_eq!(Some(2), login_lockout_minutes(6, 5));
+    assert_eq!(Some(16), login_lockout_minutes(9, 5));
+    assert_eq!(Some(24 * 60), login_lockout_minutes(16, 5));
+    assert_eq!(Some(24 * 60), login_lockout_minutes(i32::MAX, 5));
+  }
+
+  #[test]
+  fn test_dnsbl_query() -> LemmyResult<()> {
+    assert_eq!(
+      "2.0.0.127.zen.spamhaus.org",
+      dnsbl_query("127.0.0.2".parse()?, "zen.spamhaus.org")
+    );
+    assert_eq!(
+      "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.dnsbl.test",
+      dnsbl_query("2001:db8::1".parse()?, "dnsbl.test")
+    );
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_key_login() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "rob bot")).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+
+    let form = ApiKeyInsertForm {
+      rate_limit: Some(2),
+      ..ApiKeyInsertForm::new(
+        local_user.id,
+        "bot".to_string(),
+        hash_token("secret"),
+        TokenScope::Read,
+      )
+    };
+    ApiKey::create(pool, &form).await?;
+
+    // Only the hash is stored, but the key itself is needed to log in
+    assert!(
+      local_user_view_from_api_key(&hash_token("secret"), &context)
+        .await
+        .is_err()
+    );
+    let (local_user_view, api_key) = local_user_view_from_api_key("secret", &context).await?;
+    assert_eq!(local_user.id, local_user_view.local_user.id);
+
+    // The key can only be used within its scope
+    assert!(check_token_scope(Some(api_key.scope), Some(TokenScope::Read)).is_ok());
+    assert!(check_token_scope(Some(api_key.scope), Some(TokenScope::Write)).is_err());
+
+    // Rate limit is exceeded by the third request
+    assert!(
+      local_user_view_from_api_key("secret", &context)
+        .await
+        .is_ok()
+    );
+    assert!(
+      local_user_view_from_api_key("secret", &context)
+        .await
+        .is_err()
+    );
+
+    Person::delete(&mut context.pool(), person.id).await?;
+    Instance::delete(&mut context.pool(), instance.id).await?;
+    Ok(())
+  }
+
+  fn new_account_view(admin: bool, age_days: u64, karma: i32) -> LemmyResult<LocalUserView> {
+    let published_at = Utc::now() - Days::new(age_days);
+    let ap_id: DbUrl = Url::parse("http://example.com/u/newbie")?.into();
+    Ok(LocalUserView {
+      local_user: LocalUser {
+        admin,
+        ..Default::default()
+      },
+      person: Person {
+        id: PersonId(0),
+        name: "newbie".to_string(),
+        display_name: None,
+        avatar: None,
+        published_at,
+        updated_at: None,
+        ap_id: ap_id.clone(),
+        bio: None,
+        local: true,
+        private_key: None,
+        public_key: "pubkey".to_string(),
+        last_refreshed_at: published_at,
+        banner: None,
+        deleted: false,
+        inbox_url: ap_id,
+        matrix_user_id: None,
+        bot_account: false,
+        instance_id: InstanceId(0),
+        post_count: 0,
+        post_score: karma,
+        comment_count: 0,
+        comment_score: 0,
+      },
+      banned: false,
+      ban_expires_at: None,
+    })
+  }
+
+  #[test]
+  fn test_is_restricted_new_account() -> LemmyResult<()> {
+    let young = new_account_view(false, 1, 0)?;
+    let old = new_account_view(false, 10, 0)?;
+    let young_admin = new_account_view(true, 1, 0)?;
+
+    // Restrictions are disabled by default
+    assert!(!is_restricted_new_account(&young, &LocalSite::default()));
+
+    let min_age = LocalSite {
+      new_account_min_age_days: 7,
+      ..Default::default()
+    };
+    assert!(is_restricted_new_account(&young, &min_age));
+    assert!(!is_restricted_new_account(&old, &min_age));
+    assert!(!is_restricted_new_account(&young_admin, &min_age));
+
+    let min_karma = LocalSite {
+      new_account_min_karma: 10,
+      ..Default::default()
+    };
+    assert!(is_restricted_new_account(&old, &min_karma));
+    assert!(!is_restricted_new_account(
+      &new_account_view(false, 1, 15)?,
+      &min_karma
+    ));
+    Ok(())
+  }
+
+  #[test]
+  fn test_new_account_checks() -> LemmyResult<()> {
+    let young = new_account_view(false, 1, 0)?;
+    let old = new_account_view(false, 10, 0)?;
+    let allowed = LocalSite {
+      new_account_min_age_days: 7,
+      ..Default::default()
+    };
+    let disabled = LocalSite {
+      new_account_link_posts_disabled: true,
+      new_account_image_upload_disabled: true,
+      new_account_community_creation_disabled: true,
+      ..allowed.clone()
+    };
+
+    assert!(check_new_account_link_post(true, &young, &allowed).is_ok());
+    assert!(check_new_account_link_post(true, &young, &disabled).is_err());
+    assert!(check_new_account_link_post(false, &young, &disabled).is_ok());
+    assert!(check_new_account_link_post(true, &old, &disabled).is_ok());
+
+    assert!(check_new_account_image_upload(&young, &allowed).is_ok());
+    assert!(check_new_account_image_upload(&young, &disabled).is_err());
+    assert!(check_new_account_image_upload(&old, &disabled).is_ok());
+
+    assert!(check_new_account_community_creation(&young, &allowed).is_ok());
+    assert!(check_new_account_community_creation(&young, &disabled).is_err());
+    assert!(check_new_account_community_creation(&old, &disabled).is_ok());
+    Ok(())
+  }
+
   #[test]
   fn honeypot() {
     assert!(honeypot_check(&None).is_ok());
@@ -1089,13 +2096,14 @@ mod tests {
 
     // image from local domain is unchanged
     let local_url = Url::parse("http://lemmy-alpha/image.png")?;
-    let proxied = proxy_image_link_internal(local_url.clone(), local_site, false, &context).await?;
+    let proxied =
+      proxy_image_link_internal(local_url.clone(), local_site, false, false, &context).await?;
     assert_eq!(&local_url, proxied.inner());
 
     // image from remote domain is proxied
     let remote_image = Url::parse("http://lemmy-beta/image.png")?;
     let proxied =
-      proxy_image_link_internal(remote_image.clone(), local_site, false, &context).await?;
+      proxy_image_link_internal(remote_image.clone(), local_site, false, false, &context).await?;
     assert_eq!(
       "https://lemmy-alpha/api/v4/image/proxy?url=http%3A%2F%2Flemmy-beta%2Fimage.png",
       proxied.as_str()
@@ -1113,6 +2121,36 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn test_image_proxy_safelist() -> LemmyResult<()> {
+    let local_site = LocalSite::default();
+    let safelist = image_proxy_safelist(&local_site);
+    assert_eq!(None, safelist);
+    assert!(is_image_safelisted(
+      &Url::parse("https://example.com/image.png")?,
+      safelist.as_deref()
+    ));
+
+    let local_site = LocalSite {
+      image_proxy_safelist: Some("i.imgur.com, Wikimedia.org,".to_string()),
+      ..Default::default()
+    };
+    let safelist = image_proxy_safelist(&local_site);
+    assert_eq!(
+      Some(vec!["i.imgur.com".to_string(), "wikimedia.org".to_string()]),
+      safelist
+    );
+    let allowed = |url: &str| -> LemmyResult<bool> {
+      Ok(is_image_safelisted(&Url::parse(url)?, safelist.as_deref()))
+    };
+    assert!(allowed("https://i.imgur.com/image.png")?);
+    assert!(allowed("https://upload.wikimedia.org/image.png")?);
+    assert!(!allowed("https://imgur.com/image.png")?);
+    assert!(!allowed("https://notwikimedia.org/image.png")?);
+    assert!(!allowed("https://127.0.0.1/image.png")?);
+    Ok(())
+  }
+
   #[test]
   fn test_comment_depth() -> LemmyResult<()> {
     let mut comment = Comment {
@@ -1139,6 +2177,7 @@ mod tests {
       unresolved_report_count: 0,
       federation_pending: false,
       locked: false,
+      changed_at: Utc::now(),
     };
     assert!(check_comment_depth(&comment).is_ok());
     comment.path = Ltree("0.123.456".to_string());
@@ -0,0 +1,181 @@
+use crate::{
+  context::LemmyContext,
+  request::{PictrsFile, PictrsFileDetails, delete_image_alias, fetch_pictrs_file_size},
+};
+use lemmy_db_schema::source::{
+  images::{ImageDetails, LocalImage, LocalImageForm},
+  local_site::LocalSite,
+};
+use lemmy_db_schema_file::PersonId;
+use lemmy_utils::{
+  REQWEST_TIMEOUT,
+  error::{LemmyErrorType, LemmyResult},
+};
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::warn;
+use url::Url;
+
+/// Video formats which can be uploaded.
+const ALLOWED_VIDEO_TYPES: [&str; 2] = ["video/mp4", "video/webm"];
+
+/// The data which is sent to the transcoding webhook.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct VideoTranscodeRequest {
+  /// Pictrs alias of the uploaded video
+  file: String,
+  url: Url,
+  content_type: String,
+  width: u16,
+  height: u16,
+}
+
+/// Response of the transcoding webhook. All fields are optional, so that a webhook which only
+/// inspects videos doesn't need to transcode them.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+struct VideoTranscodeResponse {
+  /// Pictrs alias of a transcoded copy, which replaces the original upload
+  file: Option<String>,
+  duration_seconds: Option<i32>,
+}
+
+impl VideoTranscodeResponse {
+  fn is_too_long(&self, max_duration_seconds: i32) -> bool {
+    self
+      .duration_seconds
+      .is_some_and(|d| d > max_duration_seconds)
+  }
+}
+
+/// Checks an uploaded video against the limits in the site settings and passes it to the
+/// transcoding webhook, if there is one. Returns the file which should be used, which is the
+/// transcoded copy if the webhook provided one. Uploads which aren't videos are returned unchanged.
+///
+/// The caller is responsible for deleting the upload if this fails.
+pub async fn process_video_upload(
+  file: PictrsFile,
+  upload_size: Option<u64>,
+  person_id: PersonId,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<PictrsFile> {
+  let content_type = file.details.content_type.as_str();
+  if !content_type.starts_with("video/") {
+    return Ok(file);
+  }
+  if !ALLOWED_VIDEO_TYPES.contains(&content_type) {
+    return Err(LemmyErrorType::UnsupportedVideoFormat.into());
+  }
+  let max_size = u64::try_from(local_site.video_max_upload_size_mb)? * 1024 * 1024;
+  if upload_size.is_some_and(|s| s > max_size) {
+    return Err(LemmyErrorType::VideoTooLarge.into());
+  }
+  let Some(webhook) = context.settings().pictrs()?.video_transcode_webhook else {
+    return Ok(file);
+  };
+
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  let url = file.image_url(&protocol_and_hostname)?;
+  let request = VideoTranscodeRequest {
+    file: file.file.clone(),
+    url: url.clone(),
+    content_type: file.details.content_type.clone(),
+    width: file.details.width,
+    height: file.details.height,
+  };
+  // Transcoding takes a while, so allow as much time as for the upload itself
+  let res: VideoTranscodeResponse = context
+    .client()
+    .post(webhook.as_str())
+    .header(CONTENT_TYPE, "application/json")
+    .timeout(Duration::from_secs(
+      local_site.image_upload_timeout_seconds.try_into()?,
+    ))
+    .body(serde_json::to_string(&request)?)
+    .send()
+    .await?
+    .error_for_status()?
+    .json()
+    .await?;
+
+  if res.is_too_long(local_site.video_max_duration_seconds) {
+    if let Some(transcoded) = &res.file {
+      delete_image_alias(transcoded, context).await?;
+    }
+    return Err(LemmyErrorType::VideoTooLong.into());
+  }
+
+  match res.file {
+    Some(transcoded) if transcoded != file.file => {
+      let transcoded = PictrsFile {
+        details: fetch_pictrs_file_details(&transcoded, context).await?,
+        file: transcoded,
+      };
+      let form = LocalImageForm {
+        pictrs_alias: transcoded.file.clone(),
+        person_id,
+        thumbnail_for_post_id: None,
+        size_bytes: fetch_pictrs_file_size(&transcoded.file, context).await.ok(),
+      };
+      let mut details_form = transcoded
+        .details
+        .build_image_details_form(&transcoded.image_url(&protocol_and_hostname)?);
+      details_form.duration_seconds = res.duration_seconds;
+      LocalImage::create(&mut context.pool(), &form, &details_form).await?;
+
+      if let Err(e) = delete_image_alias(&file.file, context).await {
+        warn!("Failed to delete original of transcoded video {url}: {e}");
+      }
+      Ok(transcoded)
+    }
+    _ => {
+      ImageDetails::set_duration(&mut context.pool(), &url.into(), res.duration_seconds).await?;
+      Ok(file)
+    }
+  }
+}
+
+async fn fetch_pictrs_file_details(
+  alias: &str,
+  context: &LemmyContext,
+) -> LemmyResult<PictrsFileDetails> {
+  let details_url = format!(
+    "{}image/details/original/{alias}",
+    context.settings().pictrs()?.url
+  );
+  Ok(
+    context
+      .pictrs_client()
+      .get(&details_url)
+      .timeout(REQWEST_TIMEOUT)
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::VideoTranscodeResponse;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_transcode_response() -> LemmyResult<()> {
+    let res: VideoTranscodeResponse =
+      serde_json::from_str(r#"{"file":"abc.webm","duration_seconds":90}"#)?;
+    assert_eq!(Some("abc.webm".to_string()), res.file);
+    assert!(res.is_too_long(60));
+    assert!(!res.is_too_long(90));
+
+    // The webhook may only inspect the video without transcoding it
+    let res: VideoTranscodeResponse = serde_json::from_str("{}")?;
+    assert_eq!(VideoTranscodeResponse::default(), res);
+    assert!(!res.is_too_long(0));
+    Ok(())
+  }
+}
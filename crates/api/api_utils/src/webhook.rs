@@ -0,0 +1,169 @@
+use crate::context::LemmyContext;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use lemmy_db_schema::source::webhook::{Webhook, WebhookDelivery, WebhookDeliveryInsertForm};
+use lemmy_db_schema_file::enums::WebhookEvent;
+use lemmy_utils::{REQWEST_TIMEOUT, error::LemmyResult, spawn_try_task};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::warn;
+
+/// Failed deliveries are retried with increasing delays, and given up after this many attempts.
+const MAX_WEBHOOK_ATTEMPTS: i32 = 6;
+
+/// The JSON body which is sent to webhooks.
+#[derive(Serialize)]
+struct WebhookPayload<'a, T: Serialize> {
+  event: WebhookEvent,
+  published_at: DateTime<Utc>,
+  data: &'a T,
+}
+
+/// Sends the event to all webhooks which subscribed to it. Every delivery is logged, and failed
+/// deliveries are retried by a scheduled task.
+pub fn send_webhook_event<T: Serialize>(event: WebhookEvent, data: &T, context: &LemmyContext) {
+  let payload = WebhookPayload {
+    event,
+    published_at: Utc::now(),
+    data,
+  };
+  let payload = match serde_json::to_string(&payload) {
+    Ok(p) => p,
+    Err(e) => {
+      warn!("Failed to serialize webhook payload: {e}");
+      return;
+    }
+  };
+  let context = context.clone();
+  spawn_try_task(async move {
+    let webhooks = Webhook::list_for_event(&mut context.pool(), event).await?;
+    if webhooks.is_empty() {
+      return Ok(());
+    }
+    // In case the instance is stopped before sending, the scheduled task takes over
+    let next_attempt_at = Some(Utc::now() + retry_delay(1));
+    let forms: Vec<_> = webhooks
+      .iter()
+      .map(|w| WebhookDeliveryInsertForm {
+        webhook_id: w.id,
+        event,
+        payload: payload.clone(),
+        next_attempt_at,
+      })
+      .collect();
+    let deliveries = WebhookDelivery::create(&mut context.pool(), &forms).await?;
+    for delivery in deliveries {
+      if let Some(webhook) = webhooks.iter().find(|w| w.id == delivery.webhook_id) {
+        deliver(&delivery, webhook, &context)
+          .await
+          .inspect_err(|e| warn!("Failed to deliver webhook {}: {e}", delivery.id))
+          .ok();
+      }
+    }
+    Ok(())
+  })
+}
+
+/// Retries the deliveries which failed before. An error in one delivery doesn't stop the others.
+pub async fn deliver_pending_webhooks(context: &LemmyContext) -> LemmyResult<()> {
+  for (delivery, webhook) in WebhookDelivery::list_pending(&mut context.pool()).await? {
+    deliver(&delivery, &webhook, context)
+      .await
+      .inspect_err(|e| warn!("Failed to deliver webhook {}: {e}", delivery.id))
+      .ok();
+  }
+  Ok(())
+}
+
+/// Posts the payload to the webhook, and stores the result of the attempt.
+async fn deliver(
+  delivery: &WebhookDelivery,
+  webhook: &Webhook,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let timestamp = Utc::now().timestamp();
+  let signature = sign(&webhook.secret, timestamp, &delivery.payload)?;
+  let res = context
+    .client()
+    .post(webhook.url.as_str())
+    .header(CONTENT_TYPE, "application/json")
+    .header("X-Lemmy-Event", delivery.event.to_string())
+    .header("X-Lemmy-Timestamp", timestamp.to_string())
+    .header("X-Lemmy-Signature", format!("sha256={signature}"))
+    .timeout(REQWEST_TIMEOUT)
+    .body(delivery.payload.clone())
+    .send()
+    .await;
+
+  let (status_code, error) = match res {
+    Ok(res) if res.status().is_success() => (Some(res.status().as_u16().into()), None),
+    Ok(res) => (
+      Some(res.status().as_u16().into()),
+      Some(format!("Unexpected status {}", res.status())),
+    ),
+    Err(e) => (None, Some(e.to_string())),
+  };
+  let attempts = delivery.attempts + 1;
+  let next_attempt_at = match error {
+    Some(ref e) if attempts < MAX_WEBHOOK_ATTEMPTS => {
+      warn!("Failed to deliver webhook to {}: {e}", webhook.url);
+      Some(Utc::now() + retry_delay(attempts))
+    }
+    _ => None,
+  };
+  WebhookDelivery::update_attempt(
+    &mut context.pool(),
+    delivery.id,
+    status_code,
+    error,
+    next_attempt_at,
+  )
+  .await?;
+  Ok(())
+}
+
+/// Hex encoded HMAC-SHA256 of `timestamp.payload`, so that receivers can verify that it was sent
+/// by this instance. Including the timestamp allows receivers to reject replayed requests.
+fn sign(secret: &str, timestamp: i64, payload: &str) -> LemmyResult<String> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+    .map_err(|e| anyhow::anyhow!("Failed to sign webhook payload: {e}"))?;
+  mac.update(format!("{timestamp}.{payload}").as_bytes());
+  Ok(format!("{:x}", mac.finalize().into_bytes()))
+}
+
+/// Waits 5 minutes after the first failed attempt, then 20 minutes, and so on. The last retry
+/// waits 1280 minutes (over 21 hours), so all retries together span more than a day.
+fn retry_delay(attempts: i32) -> Duration {
+  Duration::minutes(5 * 4_i64.pow(attempts.saturating_sub(1).unsigned_abs()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_sign() -> LemmyResult<()> {
+    let signature = sign(
+      "key",
+      1_700_000_000,
+      "The quick brown fox jumps over the lazy dog",
+    )?;
+    assert_eq!(
+      "2f658d6aef4f246e91cd741bbcded7479e9605f9d41c9e248122a117e0e1765b",
+      signature
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_retry_delay() {
+    assert_eq!(Duration::minutes(5), retry_delay(1));
+    assert_eq!(Duration::minutes(20), retry_delay(2));
+    assert_eq!(
+      Duration::minutes(1280),
+      retry_delay(MAX_WEBHOOK_ATTEMPTS - 1)
+    );
+  }
+}
@@ -0,0 +1,84 @@
+//! Optional GraphQL endpoint at `/api/v4/graphql`, for clients which want to fetch posts,
+//! comments, communities and persons with their nested data in a single request. The resolvers
+//! call the same handlers as the REST api, so permissions and blocks apply in the same way. The
+//! endpoint is registered in the `/api/v4` scope, which provides the login and rate limits.
+
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query, ServiceConfig, get, post, resource};
+use async_graphql::{EmptyMutation, EmptySubscription, Request, Response, Schema, Variables};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::{error::LemmyResult, rate_limit::RateLimit};
+use query::QueryRoot;
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+mod objects;
+mod query;
+
+/// Nested fields load more data from the database, so deeply nested queries are rejected.
+const MAX_DEPTH: usize = 8;
+const MAX_COMPLEXITY: usize = 500;
+
+type LemmySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+static SCHEMA: LazyLock<LemmySchema> = LazyLock::new(|| {
+  Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+    .limit_depth(MAX_DEPTH)
+    .limit_complexity(MAX_COMPLEXITY)
+    .finish()
+});
+
+/// A single query can load several lists, so it is rate limited like searches. Queries sent with
+/// GET only need a token with read scope.
+pub(crate) fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
+  cfg.service(
+    resource("/graphql")
+      .wrap(rate_limit.search())
+      .route(get().to(graphql_get))
+      .route(post().to(graphql_post)),
+  );
+}
+
+/// Query parameters of a GraphQL request sent with GET.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlQuery {
+  query: String,
+  operation_name: Option<String>,
+  /// Variables as JSON object
+  variables: Option<String>,
+}
+
+async fn graphql_get(
+  Query(data): Query<GraphqlQuery>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<Response>> {
+  let mut request = Request::new(data.query);
+  if let Some(operation_name) = data.operation_name {
+    request = request.operation_name(operation_name);
+  }
+  if let Some(variables) = data.variables {
+    request = request.variables(Variables::from_json(serde_json::from_str(&variables)?));
+  }
+  Ok(Json(execute(request, context, local_user_view).await))
+}
+
+async fn graphql_post(
+  Json(request): Json<Request>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> Json<Response> {
+  Json(execute(request, context, local_user_view).await)
+}
+
+async fn execute(
+  request: Request,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> Response {
+  SCHEMA
+    .execute(request.data(context).data(local_user_view))
+    .await
+}
@@ -0,0 +1,325 @@
+//! GraphQL types, which are converted from the views of the REST api. Nested lists like the
+//! comments of a post are loaded with another call to the corresponding handler, only when they
+//! are requested.
+
+use super::query::{load_comments, load_post, load_posts, parse};
+use async_graphql::{ComplexObject, Context, OutputType, Result, SimpleObject};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, PostId},
+  source::{community, person},
+};
+use lemmy_db_views_comment::{CommentView, api::GetComments};
+use lemmy_db_views_post::{PostView, api::GetPosts};
+use lemmy_diesel_utils::{
+  dburl::DbUrl,
+  pagination::{PagedResponse, PaginationCursor},
+};
+use serde_json::Value;
+
+/// A page of items. Pass `nextPage` or `prevPage` as `pageCursor` to get the adjacent pages.
+#[derive(SimpleObject)]
+#[graphql(concrete(name = "PostPage", params(Post)))]
+#[graphql(concrete(name = "CommentPage", params(Comment)))]
+#[graphql(concrete(name = "CommunityPage", params(Community)))]
+pub(super) struct Page<T: OutputType> {
+  items: Vec<T>,
+  next_page: Option<String>,
+  prev_page: Option<String>,
+}
+
+impl<T: OutputType> Page<T> {
+  pub(super) fn new<V>(res: PagedResponse<V>, convert: impl Fn(V) -> T) -> Self {
+    Page {
+      items: res.items.into_iter().map(convert).collect(),
+      next_page: cursor_to_string(res.next_page),
+      prev_page: cursor_to_string(res.prev_page),
+    }
+  }
+}
+
+fn cursor_to_string(cursor: Option<PaginationCursor>) -> Option<String> {
+  match serde_json::to_value(cursor?) {
+    Ok(Value::String(s)) => Some(s),
+    _ => None,
+  }
+}
+
+fn url_to_string(url: Option<DbUrl>) -> Option<String> {
+  url.as_ref().map(ToString::to_string)
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(super) struct Post {
+  #[graphql(skip)]
+  post_id: PostId,
+  id: i32,
+  name: String,
+  url: Option<String>,
+  body: Option<String>,
+  alt_text: Option<String>,
+  thumbnail_url: Option<String>,
+  ap_id: String,
+  local: bool,
+  nsfw: bool,
+  locked: bool,
+  featured_community: bool,
+  featured_local: bool,
+  published_at: DateTime<Utc>,
+  updated_at: Option<DateTime<Utc>>,
+  comment_count: i32,
+  score: i32,
+  upvotes: i32,
+  downvotes: i32,
+  /// Vote of the logged in user, true for upvote and false for downvote.
+  my_vote: Option<bool>,
+  saved: bool,
+  read: bool,
+  creator: Person,
+  community: Community,
+}
+
+impl From<PostView> for Post {
+  fn from(view: PostView) -> Self {
+    let post = view.post;
+    let actions = view.post_actions;
+    Post {
+      post_id: post.id,
+      id: post.id.0,
+      name: post.name,
+      url: url_to_string(post.url),
+      body: post.body,
+      alt_text: post.alt_text,
+      thumbnail_url: url_to_string(post.thumbnail_url),
+      ap_id: post.ap_id.to_string(),
+      local: post.local,
+      nsfw: post.nsfw,
+      locked: post.locked,
+      featured_community: post.featured_community,
+      featured_local: post.featured_local,
+      published_at: post.published_at,
+      updated_at: post.updated_at,
+      comment_count: post.comments,
+      score: post.score,
+      upvotes: post.upvotes,
+      downvotes: post.downvotes,
+      my_vote: actions.as_ref().and_then(|a| a.vote_is_upvote),
+      saved: actions.as_ref().is_some_and(|a| a.saved_at.is_some()),
+      read: actions.as_ref().is_some_and(|a| a.read_at.is_some()),
+      creator: view.creator.into(),
+      community: view.community.into(),
+    }
+  }
+}
+
+#[ComplexObject]
+impl Post {
+  /// Comments of the post, like `GET /api/v4/comment/list?post_id=...`.
+  async fn comments(
+    &self,
+    ctx: &Context<'_>,
+    sort: Option<String>,
+    max_depth: Option<i32>,
+    page_cursor: Option<String>,
+    limit: Option<i64>,
+  ) -> Result<Page<Comment>> {
+    let form = GetComments {
+      post_id: Some(self.post_id),
+      sort: parse(sort)?,
+      max_depth,
+      page_cursor: parse(page_cursor)?,
+      limit,
+      ..Default::default()
+    };
+    load_comments(ctx, form).await
+  }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(super) struct Comment {
+  #[graphql(skip)]
+  comment_id: CommentId,
+  #[graphql(skip)]
+  post_id: PostId,
+  id: i32,
+  content: String,
+  /// Ids of the parent comments, separated by dots and starting with `0`.
+  path: String,
+  ap_id: String,
+  local: bool,
+  distinguished: bool,
+  locked: bool,
+  published_at: DateTime<Utc>,
+  updated_at: Option<DateTime<Utc>>,
+  score: i32,
+  upvotes: i32,
+  downvotes: i32,
+  child_count: i32,
+  /// Vote of the logged in user, true for upvote and false for downvote.
+  my_vote: Option<bool>,
+  saved: bool,
+  creator: Person,
+  community: Community,
+}
+
+impl From<CommentView> for Comment {
+  fn from(view: CommentView) -> Self {
+    let comment = view.comment;
+    let actions = view.comment_actions;
+    Comment {
+      comment_id: comment.id,
+      post_id: comment.post_id,
+      id: comment.id.0,
+      content: comment.content,
+      path: comment.path.0,
+      ap_id: comment.ap_id.to_string(),
+      local: comment.local,
+      distinguished: comment.distinguished,
+      locked: comment.locked,
+      published_at: comment.published_at,
+      updated_at: comment.updated_at,
+      score: comment.score,
+      upvotes: comment.upvotes,
+      downvotes: comment.downvotes,
+      child_count: comment.child_count,
+      my_vote: actions.as_ref().and_then(|a| a.vote_is_upvote),
+      saved: actions.as_ref().is_some_and(|a| a.saved_at.is_some()),
+      creator: view.creator.into(),
+      community: view.community.into(),
+    }
+  }
+}
+
+#[ComplexObject]
+impl Comment {
+  async fn post(&self, ctx: &Context<'_>) -> Result<Post> {
+    load_post(ctx, self.post_id).await
+  }
+
+  /// Direct and indirect replies to the comment.
+  async fn replies(
+    &self,
+    ctx: &Context<'_>,
+    sort: Option<String>,
+    max_depth: Option<i32>,
+    page_cursor: Option<String>,
+    limit: Option<i64>,
+  ) -> Result<Page<Comment>> {
+    let form = GetComments {
+      parent_id: Some(self.comment_id),
+      sort: parse(sort)?,
+      max_depth,
+      page_cursor: parse(page_cursor)?,
+      limit,
+      ..Default::default()
+    };
+    load_comments(ctx, form).await
+  }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(super) struct Community {
+  #[graphql(skip)]
+  community_id: CommunityId,
+  id: i32,
+  name: String,
+  title: String,
+  summary: Option<String>,
+  sidebar: Option<String>,
+  icon: Option<String>,
+  banner: Option<String>,
+  ap_id: String,
+  local: bool,
+  nsfw: bool,
+  posting_restricted_to_mods: bool,
+  published_at: DateTime<Utc>,
+  updated_at: Option<DateTime<Utc>>,
+  subscribers: i32,
+  post_count: i32,
+  comment_count: i32,
+  users_active_month: i32,
+}
+
+impl From<community::Community> for Community {
+  fn from(c: community::Community) -> Self {
+    Community {
+      community_id: c.id,
+      id: c.id.0,
+      name: c.name,
+      title: c.title,
+      summary: c.summary,
+      sidebar: c.sidebar,
+      icon: url_to_string(c.icon),
+      banner: url_to_string(c.banner),
+      ap_id: c.ap_id.to_string(),
+      local: c.local,
+      nsfw: c.nsfw,
+      posting_restricted_to_mods: c.posting_restricted_to_mods,
+      published_at: c.published_at,
+      updated_at: c.updated_at,
+      subscribers: c.subscribers,
+      post_count: c.posts,
+      comment_count: c.comments,
+      users_active_month: c.users_active_month,
+    }
+  }
+}
+
+#[ComplexObject]
+impl Community {
+  /// Posts in the community, like `GET /api/v4/post/list?community_id=...`.
+  async fn posts(
+    &self,
+    ctx: &Context<'_>,
+    sort: Option<String>,
+    page_cursor: Option<String>,
+    limit: Option<i64>,
+  ) -> Result<Page<Post>> {
+    let form = GetPosts {
+      community_id: Some(self.community_id),
+      sort: parse(sort)?,
+      page_cursor: parse(page_cursor)?,
+      limit,
+      ..Default::default()
+    };
+    load_posts(ctx, form).await
+  }
+}
+
+#[derive(SimpleObject)]
+pub(super) struct Person {
+  id: i32,
+  name: String,
+  display_name: Option<String>,
+  avatar: Option<String>,
+  banner: Option<String>,
+  bio: Option<String>,
+  ap_id: String,
+  local: bool,
+  bot_account: bool,
+  published_at: DateTime<Utc>,
+  post_count: i32,
+  comment_count: i32,
+}
+
+impl From<person::Person> for Person {
+  fn from(p: person::Person) -> Self {
+    Person {
+      id: p.id.0,
+      name: p.name,
+      display_name: p.display_name,
+      avatar: url_to_string(p.avatar),
+      banner: url_to_string(p.banner),
+      bio: p.bio,
+      ap_id: p.ap_id.to_string(),
+      local: p.local,
+      bot_account: p.bot_account,
+      published_at: p.published_at,
+      post_count: p.post_count,
+      comment_count: p.comment_count,
+    }
+  }
+}
@@ -0,0 +1,224 @@
+use super::objects::{Comment, Community, Page, Person, Post};
+use activitypub_federation::config::Data;
+use actix_web::web::{self, Query};
+use async_graphql::{Context, Error, InputObject, Object, Result};
+use lemmy_api::federation::{
+  list_comments::list_comments,
+  list_posts::list_posts,
+  read_community::get_community,
+  read_person::read_person,
+};
+use lemmy_api_crud::{community::list::list_communities, post::read::get_post};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::newtypes::{CommentId, CommunityId, PostId};
+use lemmy_db_schema_file::PersonId;
+use lemmy_db_views_comment::api::GetComments;
+use lemmy_db_views_community::api::{GetCommunity, ListCommunities};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person::api::GetPersonDetails;
+use lemmy_db_views_post::api::{GetPost, GetPosts};
+use lemmy_utils::error::LemmyError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+pub struct QueryRoot;
+
+/// Filters for posts. Enum values are written like in the REST api, eg `sort: "Active"`.
+#[derive(InputObject, Default)]
+pub struct PostFilter {
+  #[graphql(name = "type")]
+  type_: Option<String>,
+  sort: Option<String>,
+  community_id: Option<i32>,
+  community_name: Option<String>,
+  search_term: Option<String>,
+}
+
+#[derive(InputObject, Default)]
+pub struct CommentFilter {
+  #[graphql(name = "type")]
+  type_: Option<String>,
+  sort: Option<String>,
+  post_id: Option<i32>,
+  parent_id: Option<i32>,
+  community_id: Option<i32>,
+  max_depth: Option<i32>,
+}
+
+#[derive(InputObject, Default)]
+pub struct CommunityFilter {
+  #[graphql(name = "type")]
+  type_: Option<String>,
+  sort: Option<String>,
+  search_term: Option<String>,
+}
+
+#[Object]
+impl QueryRoot {
+  /// Lists posts, like `GET /api/v4/post/list`.
+  async fn posts(
+    &self,
+    ctx: &Context<'_>,
+    #[graphql(default)] filter: PostFilter,
+    page_cursor: Option<String>,
+    limit: Option<i64>,
+  ) -> Result<Page<Post>> {
+    let form = GetPosts {
+      type_: parse(filter.type_)?,
+      sort: parse(filter.sort)?,
+      community_id: filter.community_id.map(CommunityId),
+      community_name: filter.community_name,
+      search_term: filter.search_term,
+      page_cursor: parse(page_cursor)?,
+      limit,
+      ..Default::default()
+    };
+    load_posts(ctx, form).await
+  }
+
+  async fn post(&self, ctx: &Context<'_>, id: i32) -> Result<Post> {
+    load_post(ctx, PostId(id)).await
+  }
+
+  /// Lists comments, like `GET /api/v4/comment/list`.
+  async fn comments(
+    &self,
+    ctx: &Context<'_>,
+    #[graphql(default)] filter: CommentFilter,
+    page_cursor: Option<String>,
+    limit: Option<i64>,
+  ) -> Result<Page<Comment>> {
+    let form = GetComments {
+      type_: parse(filter.type_)?,
+      sort: parse(filter.sort)?,
+      post_id: filter.post_id.map(PostId),
+      parent_id: filter.parent_id.map(CommentId),
+      community_id: filter.community_id.map(CommunityId),
+      max_depth: filter.max_depth,
+      page_cursor: parse(page_cursor)?,
+      limit,
+      ..Default::default()
+    };
+    load_comments(ctx, form).await
+  }
+
+  /// Reads a community by id, or by name like `name` or `name@instance.tld`.
+  async fn community(
+    &self,
+    ctx: &Context<'_>,
+    id: Option<i32>,
+    name: Option<String>,
+  ) -> Result<Community> {
+    let (context, local_user_view) = request_data(ctx)?;
+    let form = GetCommunity {
+      id: id.map(CommunityId),
+      name,
+    };
+    let res = get_community(Query(form), context, local_user_view)
+      .await
+      .map_err(to_graphql_error)?;
+    Ok(res.data.community_view.community.into())
+  }
+
+  /// Lists communities, like `GET /api/v4/community/list`.
+  async fn communities(
+    &self,
+    ctx: &Context<'_>,
+    #[graphql(default)] filter: CommunityFilter,
+    page_cursor: Option<String>,
+    limit: Option<i64>,
+  ) -> Result<Page<Community>> {
+    let (context, local_user_view) = request_data(ctx)?;
+    let form = ListCommunities {
+      type_: parse(filter.type_)?,
+      sort: parse(filter.sort)?,
+      search_term: filter.search_term,
+      page_cursor: parse(page_cursor)?,
+      limit,
+      ..Default::default()
+    };
+    let res = list_communities(Query(form), actix_data(&context), local_user_view)
+      .await
+      .map_err(to_graphql_error)?;
+    Ok(Page::new(res.0, |c| c.community.into()))
+  }
+
+  /// Reads a person by id, or by name like `name` or `name@instance.tld`.
+  async fn person(
+    &self,
+    ctx: &Context<'_>,
+    id: Option<i32>,
+    name: Option<String>,
+  ) -> Result<Person> {
+    let (context, local_user_view) = request_data(ctx)?;
+    let form = GetPersonDetails {
+      person_id: id.map(PersonId),
+      username: name,
+    };
+    let res = read_person(Query(form), context, local_user_view)
+      .await
+      .map_err(to_graphql_error)?;
+    Ok(res.0.person_view.person.into())
+  }
+}
+
+pub(super) async fn load_posts(ctx: &Context<'_>, form: GetPosts) -> Result<Page<Post>> {
+  let (context, local_user_view) = request_data(ctx)?;
+  let res = list_posts(Query(form), context, local_user_view)
+    .await
+    .map_err(to_graphql_error)?;
+  Ok(Page::new(res.data, Post::from))
+}
+
+pub(super) async fn load_post(ctx: &Context<'_>, id: PostId) -> Result<Post> {
+  let (context, local_user_view) = request_data(ctx)?;
+  let form = GetPost {
+    id: Some(id),
+    comment_id: None,
+  };
+  let res = get_post(Query(form), actix_data(&context), local_user_view)
+    .await
+    .map_err(to_graphql_error)?;
+  Ok(res.0.post_view.into())
+}
+
+pub(super) async fn load_comments(ctx: &Context<'_>, form: GetComments) -> Result<Page<Comment>> {
+  let (context, local_user_view) = request_data(ctx)?;
+  let res = list_comments(Query(form), context, local_user_view)
+    .await
+    .map_err(to_graphql_error)?;
+  Ok(Page::new(res.data, Comment::from))
+}
+
+/// The context and login of the GraphQL request, which are passed to the api handlers.
+fn request_data(ctx: &Context<'_>) -> Result<(Data<LemmyContext>, Option<LocalUserView>)> {
+  let context = ctx.data::<Data<LemmyContext>>()?.reset_request_count();
+  let local_user_view = ctx.data::<Option<LocalUserView>>()?.clone();
+  Ok((context, local_user_view))
+}
+
+/// Some handlers take the context as actix data instead of federation data.
+fn actix_data(context: &Data<LemmyContext>) -> web::Data<LemmyContext> {
+  web::Data::new(LemmyContext::clone(context))
+}
+
+/// Parses enum values and page cursors, which are passed as strings like in the REST api.
+pub(super) fn parse<T: DeserializeOwned>(value: Option<String>) -> Result<Option<T>> {
+  value
+    .map(|v| serde_json::from_value(Value::String(v)))
+    .transpose()
+    .map_err(|e| Error::new(e.to_string()))
+}
+
+/// Uses the same error code as the REST api as message, and in the `error` extension.
+fn to_graphql_error(e: LemmyError) -> Error {
+  let code = match serde_json::to_value(&e.error_type) {
+    Ok(Value::Object(o)) => o
+      .get("error")
+      .and_then(Value::as_str)
+      .map(ToString::to_string),
+    _ => None,
+  }
+  .unwrap_or_else(|| e.error_type.to_string());
+  Error::new(code.clone()).extend_with(|_, ext| ext.set("error", code))
+}
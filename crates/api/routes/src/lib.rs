@@ -4,23 +4,40 @@ use lemmy_api::{
     distinguish::distinguish_comment,
     like::like_comment,
     list_comment_likes::list_comment_likes,
+    list_updates::list_comment_updates,
     lock::lock_comment,
     save::save_comment,
     warning::create_comment_warning,
   },
   community::{
     add_mod::add_mod_to_community,
-    ban::ban_from_community,
+    ban::{ban_from_community, ban_many_from_community},
     block::user_block_community,
+    directory::list_community_directory,
     follow::follow_community,
     multi_community_follow::follow_multi_community,
     pending_follows::{approve::post_pending_follows_approve, list::get_pending_follows_list},
+    post_template::{
+      create_community_post_template,
+      delete_community_post_template,
+      edit_community_post_template,
+      list_community_post_templates,
+    },
+    quarantine::quarantine_community,
     random::get_random_community,
+    recurring_post::{
+      create_community_recurring_post,
+      delete_community_recurring_post,
+      edit_community_recurring_post,
+      list_community_recurring_posts,
+    },
     tag::{create_community_tag, delete_community_tag, edit_community_tag},
     transfer::transfer_community,
+    trending::list_trending_communities,
     update_notifications::edit_community_notifications,
   },
   federation::{
+    autocomplete::autocomplete,
     list_comments::{list_comments, list_comments_slim},
     list_person_content::list_person_content,
     list_persons::list_persons,
@@ -28,12 +45,16 @@ use lemmy_api::{
     read_community::get_community,
     read_multi_community::read_multi_community,
     read_person::read_person,
+    remote_mod_log::get_remote_mod_log,
+    resolve_many::resolve_many,
     resolve_object::resolve_object,
+    resolve_permalink::resolve_permalink,
     search::search,
     user_settings_backup::{export_settings, import_settings},
   },
   local_user::{
     add_admin::add_admin,
+    api_key::{create::create_api_key, delete::delete_api_key, list::list_api_keys},
     ban_person::ban_from_site,
     block::user_block_person,
     change_password::change_password,
@@ -42,46 +63,79 @@ use lemmy_api::{
     export_data::export_data,
     generate_totp_secret::generate_totp_secret,
     get_captcha::get_captcha,
+    get_pow_challenge::get_pow_challenge,
     list_hidden::list_person_hidden,
     list_liked::list_person_liked,
     list_logins::list_logins,
     list_media::list_media,
-    list_read::list_person_read,
+    list_oauth_accounts::list_oauth_accounts,
+    list_read::{list_person_read, sync_read_posts},
     list_saved::list_person_saved,
     login::login,
     logout::logout,
+    logout_all::logout_all,
+    media_usage::get_media_usage,
+    mute_person::user_mute_person,
     note_person::user_note_person,
     notifications::{
       list::list_notifications,
       mark_all_read::mark_all_notifications_read,
       mark_notification_read::mark_notification_as_read,
     },
+    push_subscription::{
+      create::register_push_subscription,
+      delete::unregister_push_subscription,
+      list::list_push_subscriptions,
+    },
+    refresh_token::refresh_token,
+    regenerate_totp_recovery_codes::regenerate_totp_recovery_codes,
     resend_verification_email::resend_verification_email,
     reset_password::reset_password,
+    revoke_login::revoke_login,
     save_settings::save_user_settings,
+    saved_search::{
+      create::create_saved_search,
+      delete::delete_saved_search,
+      list::list_saved_searches,
+    },
+    unlink_oauth_account::unlink_oauth_account,
     unread_counts::get_unread_counts,
     update_totp::edit_totp,
     user_block_instance::{user_block_instance_communities, user_block_instance_persons},
     validate_auth::validate_auth,
     verify_email::verify_email,
+    verify_pow_challenge::verify_pow_challenge,
+    webauthn::{
+      delete::delete_webauthn_credential,
+      list::list_webauthn_credentials,
+      login::webauthn_login,
+      login_start::start_webauthn_login,
+      register::register_webauthn_credential,
+      register_start::start_webauthn_registration,
+    },
   },
   post::{
     feature::feature_post,
     get_link_metadata::get_link_metadata,
     hide::hide_post,
     like::like_post,
+    list_hashtags::list_hashtags,
     list_post_likes::list_post_likes,
+    list_updates::list_post_updates,
     lock::lock_post,
     mark_many_read::mark_posts_as_read,
     mark_read::mark_post_as_read,
     mod_update::mod_edit_post,
     save::save_post,
+    schedule_feature::{list_post_feature_schedules, schedule_post_feature},
+    similar::get_similar_posts,
     update_notifications::edit_post_notifications,
     warning::create_post_warning,
   },
   reports::{
     comment_report::{create::create_comment_report, resolve::resolve_comment_report},
     community_report::{create::create_community_report, resolve::resolve_community_report},
+    instance_report::{create::create_instance_report, resolve::resolve_instance_report},
     post_report::{create::create_post_report, resolve::resolve_post_report},
     private_message_report::{create::create_pm_report, resolve::resolve_pm_report},
     report_combined::list::list_reports,
@@ -90,8 +144,22 @@ use lemmy_api::{
     admin_allow_instance::admin_allow_instance,
     admin_block_instance::admin_block_instance,
     admin_list_users::admin_list_users,
+    admin_stats::get_admin_stats,
+    clear_client_penalties::clear_client_penalties,
     federated_instances::get_federated_instances,
+    instance_move::{list_instance_moves, resolve_instance_move},
+    link_metadata_override::{
+      delete::delete_link_metadata_override,
+      list::list_link_metadata_overrides,
+      set::set_link_metadata_override,
+    },
     list_all_media::list_all_media,
+    list_client_penalties::list_client_penalties,
+    list_failed_deliveries::list_failed_deliveries,
+    list_federation_inbox::list_federation_inbox,
+    list_federation_queue::list_federation_queue,
+    list_login_failures::list_login_failures,
+    list_orphaned_media::list_orphaned_media,
     mod_log::get_mod_log,
     purge::{
       comment::purge_comment,
@@ -99,19 +167,27 @@ use lemmy_api::{
       person::purge_person,
       post::purge_post,
     },
+    rate_limit_override::{
+      delete::delete_rate_limit_override,
+      list::list_rate_limit_overrides,
+      set::set_rate_limit_override,
+    },
+    read_pseudonym::read_pseudonym,
     registration_applications::{
       approve::approve_registration_application,
       get::get_registration_application,
       list::list_registration_applications,
     },
+    replay_failed_deliveries::replay_failed_deliveries,
   },
+  stream::get_stream,
 };
 use lemmy_api_crud::{
   comment::{
     create::create_comment,
     delete::delete_comment,
     read::get_comment,
-    remove::remove_comment,
+    remove::{remove_comment, remove_many_comments},
     update::edit_comment,
   },
   community::{
@@ -134,6 +210,15 @@ use lemmy_api_crud::{
     list::list_multi_communities,
     update::edit_multi_community,
   },
+  oauth_application::{
+    authorize::authorize_oauth_application,
+    create::create_oauth_application,
+    delete::delete_oauth_application,
+    list::list_oauth_applications,
+    list_authorized::list_authorized_oauth_applications,
+    revoke::revoke_oauth_application,
+    token::oauth_application_token,
+  },
   oauth_provider::{
     create::create_oauth_provider,
     delete::delete_oauth_provider,
@@ -143,12 +228,17 @@ use lemmy_api_crud::{
     create::create_post,
     delete::delete_post,
     read::get_post,
-    remove::remove_post,
+    remove::{remove_many_posts, remove_post},
     update::edit_post,
   },
   private_message::{
     create::create_private_message,
     delete::delete_private_message,
+    encryption_key::{
+      create::create_encryption_key,
+      delete::delete_encryption_key,
+      list::list_encryption_keys,
+    },
     update::edit_private_message,
   },
   site::{create::create_site, read::get_site, update::edit_site},
@@ -163,39 +253,77 @@ use lemmy_api_crud::{
     delete::delete_account,
     my_user::get_my_user,
   },
-};
-use lemmy_routes::images::{
-  delete::{
-    delete_community_banner,
-    delete_community_icon,
-    delete_image,
-    delete_image_admin,
-    delete_site_banner,
-    delete_site_icon,
-    delete_user_avatar,
-    delete_user_banner,
+  webhook::{
+    create::create_webhook,
+    delete::delete_webhook,
+    list::list_webhooks,
+    list_deliveries::list_webhook_deliveries,
+    update::edit_webhook,
   },
-  download::{get_image, image_proxy},
-  pictrs_health,
-  upload::{
-    upload_community_banner,
-    upload_community_icon,
-    upload_image,
-    upload_site_banner,
-    upload_site_icon,
-    upload_user_avatar,
-    upload_user_banner,
+};
+use lemmy_routes::{
+  images::{
+    delete::{
+      delete_community_banner,
+      delete_community_icon,
+      delete_image,
+      delete_image_admin,
+      delete_site_banner,
+      delete_site_icon,
+      delete_user_avatar,
+      delete_user_banner,
+    },
+    download::{get_image, image_proxy},
+    pictrs_health,
+    upload::{
+      upload_community_banner,
+      upload_community_icon,
+      upload_image,
+      upload_site_banner,
+      upload_site_icon,
+      upload_user_avatar,
+      upload_user_banner,
+    },
   },
+  middleware::{tarpit::TarpitMiddleware, token_scope::TokenScopeMiddleware},
 };
 use lemmy_utils::rate_limit::RateLimit;
 
+#[cfg(feature = "graphql")]
+mod graphql;
+
+#[cfg(not(feature = "graphql"))]
+mod graphql {
+  use actix_web::web::ServiceConfig;
+  use lemmy_utils::rate_limit::RateLimit;
+
+  pub(crate) fn config(_cfg: &mut ServiceConfig, _rate_limit: &RateLimit) {}
+}
+
+#[cfg(feature = "openapi")]
+mod openapi;
+
+#[cfg(not(feature = "openapi"))]
+mod openapi {
+  use actix_web::web::ServiceConfig;
+  use lemmy_utils::rate_limit::RateLimit;
+
+  pub(crate) fn config(_cfg: &mut ServiceConfig, _rate_limit: &RateLimit) {}
+}
+
 pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
+  openapi::config(cfg, rate_limit);
   cfg.service(
     scope("/api/v4")
       .wrap(rate_limit.message())
+      // Runs before the rate limit, so that blocked clients don't use up its buckets
+      .wrap(TarpitMiddleware::new(rate_limit.clone()))
+      // Scoped tokens can read everything and make changes, unless a stricter scope is declared
+      .wrap(TokenScopeMiddleware::by_method())
       // Site
       .service(
         scope("/site")
+          .wrap(TokenScopeMiddleware::moderate_changes())
           .route("", get().to(get_site))
           .route("", post().to(create_site))
           .route("", put().to(edit_site))
@@ -205,16 +333,37 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/banner", delete().to(delete_site_banner)),
       )
       .route("/modlog", get().to(get_mod_log))
+      .route("/modlog/remote", get().to(get_remote_mod_log))
       .service(
         resource("/search")
           .wrap(rate_limit.search())
           .route(get().to(search)),
       )
+      .route("/search/autocomplete", get().to(autocomplete))
       .service(
         resource("/resolve_object")
           .wrap(rate_limit.search())
-          .route(get().to(resolve_object)),
+          .route(get().to(resolve_object))
+          .route(post().to(resolve_object)),
+      )
+      .service(
+        resource("/resolve_many")
+          .wrap(rate_limit.search())
+          .route(post().to(resolve_many)),
+      )
+      .service(
+        resource("/resolve_permalink")
+          .wrap(rate_limit.search())
+          .route(get().to(resolve_permalink)),
+      )
+      // Live updates as server-sent events. Opening streams is limited like searches, because
+      // each one keeps a connection open.
+      .service(
+        resource("/stream")
+          .wrap(rate_limit.search())
+          .route(get().to(get_stream)),
       )
+      .configure(|cfg| graphql::config(cfg, rate_limit))
       // Community
       .service(
         resource("/community")
@@ -228,25 +377,81 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", put().to(edit_community))
           .route("", delete().to(delete_community))
           .route("/random", get().to(get_random_community))
+          .route("/trending", get().to(list_trending_communities))
+          .route("/directory", get().to(list_community_directory))
           .route("/list", get().to(list_communities))
           .route("/follow", post().to(follow_community))
           .route("/report", post().to(create_community_report))
-          .route("/report/resolve", put().to(resolve_community_report))
-          // Mod Actions
-          .route("/remove", post().to(remove_community))
-          .route("/transfer", post().to(transfer_community))
-          .route("/ban_user", post().to(ban_from_community))
-          .route("/mod", post().to(add_mod_to_community))
           .route("/icon", post().to(upload_community_icon))
           .route("/icon", delete().to(delete_community_icon))
           .route("/banner", post().to(upload_community_banner))
           .route("/banner", delete().to(delete_community_banner))
-          .route("/tag", post().to(create_community_tag))
-          .route("/tag", put().to(edit_community_tag))
-          .route("/tag", delete().to(delete_community_tag))
           .route("/notifications", post().to(edit_community_notifications))
+          .route(
+            "/post_template/list",
+            get().to(list_community_post_templates),
+          )
+          // Mod Actions
+          .service(
+            resource("/report/resolve")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(put().to(resolve_community_report)),
+          )
+          .service(
+            resource("/remove")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(remove_community)),
+          )
+          .service(
+            resource("/quarantine")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(quarantine_community)),
+          )
+          .service(
+            resource("/transfer")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(transfer_community)),
+          )
+          .service(
+            resource("/ban_user")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(ban_from_community)),
+          )
+          .service(
+            resource("/ban_user_many")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(ban_many_from_community)),
+          )
+          .service(
+            resource("/mod")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(add_mod_to_community)),
+          )
+          .service(
+            resource("/tag")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(create_community_tag))
+              .route(put().to(edit_community_tag))
+              .route(delete().to(delete_community_tag)),
+          )
+          .service(
+            resource("/post_template")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(create_community_post_template))
+              .route(put().to(edit_community_post_template))
+              .route(delete().to(delete_community_post_template)),
+          )
+          .service(
+            scope("/recurring_post")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route("", post().to(create_community_recurring_post))
+              .route("", put().to(edit_community_recurring_post))
+              .route("", delete().to(delete_community_recurring_post))
+              .route("/list", get().to(list_community_recurring_posts)),
+          )
           .service(
             scope("/pending_follows")
+              .wrap(TokenScopeMiddleware::moderate())
               .route("/list", get().to(get_pending_follows_list))
               .route("/approve", post().to(post_pending_follows_approve)),
           ),
@@ -275,26 +480,73 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.search())
           .route(get().to(get_link_metadata)),
       )
+      .service(
+        resource("/post/similar")
+          .wrap(rate_limit.search())
+          .route(get().to(get_similar_posts)),
+      )
       .service(
         scope("/post")
           .route("", get().to(get_post))
           .route("", put().to(edit_post))
           .route("", delete().to(delete_post))
-          .route("/remove", post().to(remove_post))
           .route("/mark_as_read", post().to(mark_post_as_read))
           .route("/mark_as_read/many", post().to(mark_posts_as_read))
           .route("/hide", post().to(hide_post))
-          .route("/lock", post().to(lock_post))
-          .route("/feature", post().to(feature_post))
           .route("/list", get().to(list_posts))
+          .route("/list/updates", get().to(list_post_updates))
+          .route("/hashtag/list", get().to(list_hashtags))
           .route("/like", post().to(like_post))
-          .route("/like/list", get().to(list_post_likes))
           .route("/save", put().to(save_post))
           .route("/report", post().to(create_post_report))
-          .route("/report/resolve", put().to(resolve_post_report))
           .route("/notifications", post().to(edit_post_notifications))
-          .route("/mod_edit", put().to(mod_edit_post))
-          .route("/warn", post().to(create_post_warning)),
+          // Mod Actions
+          .service(
+            resource("/remove")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(remove_post)),
+          )
+          .service(
+            resource("/remove_many")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(remove_many_posts)),
+          )
+          .service(
+            resource("/lock")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(lock_post)),
+          )
+          .service(
+            resource("/feature")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(feature_post)),
+          )
+          .service(
+            scope("/feature/schedule")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route("", post().to(schedule_post_feature))
+              .route("/list", get().to(list_post_feature_schedules)),
+          )
+          .service(
+            resource("/like/list")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(get().to(list_post_likes)),
+          )
+          .service(
+            resource("/report/resolve")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(put().to(resolve_post_report)),
+          )
+          .service(
+            resource("/mod_edit")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(put().to(mod_edit_post)),
+          )
+          .service(
+            resource("/warn")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(create_post_warning)),
+          ),
       )
       // Comment
       .service(
@@ -309,17 +561,48 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", get().to(get_comment))
           .route("", put().to(edit_comment))
           .route("", delete().to(delete_comment))
-          .route("/remove", post().to(remove_comment))
-          .route("/distinguish", post().to(distinguish_comment))
           .route("/like", post().to(like_comment))
-          .route("/like/list", get().to(list_comment_likes))
           .route("/save", put().to(save_comment))
-          .route("/lock", post().to(lock_comment))
           .route("/list", get().to(list_comments))
           .route("/list/slim", get().to(list_comments_slim))
-          .route("/warn", post().to(create_comment_warning))
+          .route("/list/updates", get().to(list_comment_updates))
           .route("/report", post().to(create_comment_report))
-          .route("/report/resolve", put().to(resolve_comment_report)),
+          // Mod Actions
+          .service(
+            resource("/remove")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(remove_comment)),
+          )
+          .service(
+            resource("/remove_many")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(remove_many_comments)),
+          )
+          .service(
+            resource("/distinguish")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(distinguish_comment)),
+          )
+          .service(
+            resource("/like/list")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(get().to(list_comment_likes)),
+          )
+          .service(
+            resource("/lock")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(lock_comment)),
+          )
+          .service(
+            resource("/warn")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(post().to(create_comment_warning)),
+          )
+          .service(
+            resource("/report/resolve")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(put().to(resolve_comment_report)),
+          ),
       )
       // Private Message
       .service(
@@ -328,28 +611,66 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", put().to(edit_private_message))
           .route("", delete().to(delete_private_message))
           .route("/report", post().to(create_pm_report))
-          .route("/report/resolve", put().to(resolve_pm_report)),
+          .route("/encryption_key/list", get().to(list_encryption_keys))
+          .service(
+            resource("/encryption_key")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route(post().to(create_encryption_key))
+              .route(delete().to(delete_encryption_key)),
+          )
+          .service(
+            resource("/report/resolve")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(put().to(resolve_pm_report)),
+          ),
       )
       // Reports
       .service(
         scope("/report")
           .wrap(rate_limit.message())
-          .route("/list", get().to(list_reports)),
+          .route("/instance", post().to(create_instance_report))
+          .service(
+            resource("/list")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(get().to(list_reports)),
+          )
+          .service(
+            resource("/instance/resolve")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(put().to(resolve_instance_report)),
+          ),
       )
       // User
+      .service(
+        // Registered separately, as clients need to refresh more often than the register rate
+        // limit of the other auth endpoints allows
+        resource("/account/auth/refresh_token")
+          .guard(guard::Post())
+          .wrap(TokenScopeMiddleware::login_only())
+          .route(post().to(refresh_token)),
+      )
       .service(
         scope("/account/auth")
           .guard(guard::Post())
           .wrap(rate_limit.register())
+          .wrap(TokenScopeMiddleware::login_only())
           .route("/register", post().to(register))
           .route("/login", post().to(login))
           .route("/logout", post().to(logout))
+          .route("/logout_all", post().to(logout_all))
           .route("/password_reset", post().to(reset_password))
           .route("/password_change", post().to(change_password_after_reset))
           .route("/change_password", put().to(change_password))
           .route("/totp/generate", post().to(generate_totp_secret))
           .route("/totp/edit", post().to(edit_totp))
+          .route(
+            "/totp/recovery_codes",
+            post().to(regenerate_totp_recovery_codes),
+          )
+          .route("/webauthn/login/start", post().to(start_webauthn_login))
+          .route("/webauthn/login", post().to(webauthn_login))
           .route("/verify_email", post().to(verify_email))
+          .route("/pow_challenge/verify", post().to(verify_pow_challenge))
           .route(
             "/resend_verification_email",
             post().to(resend_verification_email),
@@ -358,12 +679,20 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
       .service(
         scope("/account")
           .route("/auth/get_captcha", get().to(get_captcha))
+          .route("/auth/pow_challenge", get().to(get_pow_challenge))
           .route("", get().to(get_my_user))
+          .service(
+            resource("")
+              .guard(guard::Delete())
+              .wrap(TokenScopeMiddleware::login_only())
+              .route(delete().to(delete_account)),
+          )
           .route("/unread_counts", get().to(get_unread_counts))
           .service(
             scope("/media")
               .route("", delete().to(delete_image))
-              .route("/list", get().to(list_media)),
+              .route("/list", get().to(list_media))
+              .route("/usage", get().to(get_media_usage)),
           )
           .service(
             scope("/notification")
@@ -371,8 +700,57 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
               .route("/mark_as_read/all", post().to(mark_all_notifications_read))
               .route("/mark_as_read", post().to(mark_notification_as_read)),
           )
-          .route("", delete().to(delete_account))
-          .route("/login/list", get().to(list_logins))
+          // Unified inbox with all notification types, same as /notification/list
+          .route("/inbox", get().to(list_notifications))
+          .service(
+            scope("/login")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route("/list", get().to(list_logins))
+              .route("/revoke", post().to(revoke_login)),
+          )
+          .service(
+            scope("/api_key")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route("", post().to(create_api_key))
+              .route("", delete().to(delete_api_key))
+              .route("/list", get().to(list_api_keys)),
+          )
+          .service(
+            scope("/push_subscription")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route("", post().to(register_push_subscription))
+              .route("", delete().to(unregister_push_subscription))
+              .route("/list", get().to(list_push_subscriptions)),
+          )
+          .service(
+            scope("/saved_search")
+              .route("", post().to(create_saved_search))
+              .route("", delete().to(delete_saved_search))
+              .route("/list", get().to(list_saved_searches)),
+          )
+          .service(
+            scope("/webauthn")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route("", delete().to(delete_webauthn_credential))
+              .route("/list", get().to(list_webauthn_credentials))
+              .route("/register/start", post().to(start_webauthn_registration))
+              .route("/register", post().to(register_webauthn_credential)),
+          )
+          .service(
+            scope("/oauth_account")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route("", delete().to(unlink_oauth_account))
+              .route("/list", get().to(list_oauth_accounts)),
+          )
+          .service(
+            scope("/oauth_application")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route(
+                "/authorized/list",
+                get().to(list_authorized_oauth_applications),
+              )
+              .route("/revoke", post().to(revoke_oauth_application)),
+          )
           .route("/validate_auth", get().to(validate_auth))
           .route("/donation_dialog_shown", post().to(donation_dialog_shown))
           .route("/avatar", post().to(upload_user_avatar))
@@ -391,19 +769,26 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           )
           .route("/saved", get().to(list_person_saved))
           .route("/read", get().to(list_person_read))
+          .route("/read/sync", get().to(sync_read_posts))
           .route("/hidden", get().to(list_person_hidden))
           .route("/liked", get().to(list_person_liked))
-          .route("/settings/save", put().to(save_user_settings))
+          .service(
+            resource("/settings/save")
+              .wrap(TokenScopeMiddleware::login_only())
+              .route(put().to(save_user_settings)),
+          )
           // Account settings import / export have a strict rate limit
           .service(
             scope("/settings")
               .wrap(rate_limit.import_user_settings())
+              .wrap(TokenScopeMiddleware::login_only())
               .route("/export", get().to(export_settings))
               .route("/import", post().to(import_settings)),
           )
           .service(
             resource("/data/export")
               .wrap(rate_limit.import_user_settings())
+              .wrap(TokenScopeMiddleware::login_only())
               .route(get().to(export_data)),
           ),
       )
@@ -413,11 +798,13 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", get().to(read_person))
           .route("/list", get().to(list_persons))
           .route("/content", get().to(list_person_content))
-          .route("/note", post().to(user_note_person)),
+          .route("/note", post().to(user_note_person))
+          .route("/mute", post().to(user_mute_person)),
       )
       // Admin Actions
       .service(
         scope("/admin")
+          .wrap(TokenScopeMiddleware::moderate())
           .route("/add", post().to(add_admin))
           .service(
             scope("/registration_application")
@@ -439,16 +826,54 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
               .route("", delete().to(delete_tagline))
               .route("/list", get().to(list_taglines)),
           )
+          .service(
+            scope("/webhook")
+              .route("", post().to(create_webhook))
+              .route("", put().to(edit_webhook))
+              .route("", delete().to(delete_webhook))
+              .route("/list", get().to(list_webhooks))
+              .route("/delivery/list", get().to(list_webhook_deliveries)),
+          )
+          .service(
+            scope("/rate_limit_override")
+              .route("", post().to(set_rate_limit_override))
+              .route("", delete().to(delete_rate_limit_override))
+              .route("/list", get().to(list_rate_limit_overrides)),
+          )
+          .service(
+            scope("/link_metadata_override")
+              .route("", post().to(set_link_metadata_override))
+              .route("", delete().to(delete_link_metadata_override))
+              .route("/list", get().to(list_link_metadata_overrides)),
+          )
           .route("/ban", post().to(ban_from_site))
           .route("/users", get().to(admin_list_users))
+          .route("/stats", get().to(get_admin_stats))
+          .route("/login_failures", get().to(list_login_failures))
+          .route("/pseudonym", get().to(read_pseudonym))
+          .route("/federation_queue", get().to(list_federation_queue))
+          .route("/federation_inbox", get().to(list_federation_inbox))
+          .service(
+            scope("/client_penalties")
+              .route("", get().to(list_client_penalties))
+              .route("/clear", post().to(clear_client_penalties)),
+          )
+          .service(
+            scope("/federation_deliveries")
+              .route("", get().to(list_failed_deliveries))
+              .route("/replay", post().to(replay_failed_deliveries)),
+          )
           .service(
             scope("/instance")
               .route("/block", post().to(admin_block_instance))
-              .route("/allow", post().to(admin_allow_instance)),
+              .route("/allow", post().to(admin_allow_instance))
+              .route("/move/list", get().to(list_instance_moves))
+              .route("/move/resolve", post().to(resolve_instance_move)),
           ),
       )
       .service(
         scope("/custom_emoji")
+          .wrap(TokenScopeMiddleware::moderate_changes())
           .route("", post().to(create_custom_emoji))
           .route("", put().to(edit_custom_emoji))
           .route("", delete().to(delete_custom_emoji))
@@ -456,26 +881,57 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
       )
       .service(
         scope("/oauth_provider")
+          .wrap(TokenScopeMiddleware::login_only())
           .route("", post().to(create_oauth_provider))
           .route("", put().to(edit_oauth_provider))
           .route("", delete().to(delete_oauth_provider)),
       )
+      .service(
+        resource("/oauth_application/token")
+          .wrap(rate_limit.register())
+          .wrap(TokenScopeMiddleware::login_only())
+          .route(post().to(oauth_application_token)),
+      )
+      .service(
+        scope("/oauth_application")
+          .wrap(TokenScopeMiddleware::login_only())
+          .route("", post().to(create_oauth_application))
+          .route("", delete().to(delete_oauth_application))
+          .route("/list", get().to(list_oauth_applications))
+          .route("/authorize", post().to(authorize_oauth_application)),
+      )
       .service(
         scope("/oauth")
           .wrap(rate_limit.register())
+          .wrap(TokenScopeMiddleware::login_only())
           .route("/authenticate", post().to(authenticate_with_oauth)),
       )
       .service(
         scope("/image")
           .service(
             resource("")
+              .guard(guard::Delete())
               .wrap(rate_limit.image())
-              .route(post().to(upload_image))
+              .wrap(TokenScopeMiddleware::moderate())
               .route(delete().to(delete_image_admin)),
           )
+          .service(
+            resource("")
+              .wrap(rate_limit.image())
+              .route(post().to(upload_image)),
+          )
           .route("/proxy", get().to(image_proxy))
           .route("/health", get().to(pictrs_health))
-          .route("/list", get().to(list_all_media))
+          .service(
+            resource("/list")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(get().to(list_all_media)),
+          )
+          .service(
+            resource("/orphaned")
+              .wrap(TokenScopeMiddleware::moderate())
+              .route(get().to(list_orphaned_media)),
+          )
           .route("/{filename}", get().to(get_image)),
       ),
   );
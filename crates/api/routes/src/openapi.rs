@@ -0,0 +1,567 @@
+//! Optional OpenAPI 3 document of the api at `/api/docs/openapi.json`. The parameters, request
+//! bodies and responses of each operation are derived from the argument and return types of its
+//! handler, and the schemas from the api types. Only the list of operations below needs to be kept
+//! in sync with the routes in [crate::config].
+
+use activitypub_federation::config::Data;
+use actix_web::{
+  Either,
+  Handler,
+  HttpRequest,
+  HttpResponse,
+  web::{self, Form, Json, Path, Payload, Query, ServiceConfig, get, resource},
+};
+use lemmy_api_utils::{conditional::ConditionalJson, context::LemmyContext};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::{VERSION, error::LemmyError, rate_limit::RateLimit};
+use std::{collections::HashSet, sync::LazyLock};
+use utoipa::{
+  ToSchema,
+  openapi::{
+    ComponentsBuilder,
+    Content,
+    Info,
+    KnownFormat,
+    ObjectBuilder,
+    OpenApi,
+    OpenApiBuilder,
+    Ref,
+    RefOr,
+    Required,
+    ResponseBuilder,
+    Schema,
+    SchemaFormat,
+    Server,
+    Type,
+    path::{HttpMethod, Operation, OperationBuilder, ParameterBuilder, ParameterIn, Paths},
+    request_body::{RequestBody, RequestBodyBuilder},
+    schema::SchemaType,
+    security::{Http, HttpAuthScheme, SecurityRequirement, SecurityScheme},
+  },
+};
+
+/// Name of the security scheme for the login token, which is sent as bearer token.
+const BEARER_AUTH: &str = "bearer_auth";
+/// Name of the schema for error responses.
+const ERROR_SCHEMA: &str = "LemmyError";
+
+static OPENAPI: LazyLock<OpenApi> = LazyLock::new(document);
+
+pub(crate) fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
+  cfg.service(
+    resource("/api/docs/openapi.json")
+      .wrap(rate_limit.message())
+      .route(get().to(|| async { Json(&*OPENAPI) })),
+  );
+}
+
+type Schemas = Vec<(String, RefOr<Schema>)>;
+
+/// Adds an operation for each route of the api, with the same method and path as in
+/// [crate::config].
+macro_rules! operations {
+  ($doc:ident; $($method:ident $path:literal $handler:ident,)*) => {
+    $($doc.add(HttpMethod::$method, $path, stringify!($handler), crate::$handler);)*
+  };
+}
+
+fn document() -> OpenApi {
+  let mut doc = Document::default();
+  operations! { doc;
+    Get "/site" get_site,
+    Post "/site" create_site,
+    Put "/site" edit_site,
+    Post "/site/icon" upload_site_icon,
+    Delete "/site/icon" delete_site_icon,
+    Post "/site/banner" upload_site_banner,
+    Delete "/site/banner" delete_site_banner,
+    Get "/modlog" get_mod_log,
+    Get "/modlog/remote" get_remote_mod_log,
+    Get "/search" search,
+    Get "/search/autocomplete" autocomplete,
+    Get "/resolve_object" resolve_object,
+    Post "/resolve_object" resolve_object,
+    Post "/resolve_many" resolve_many,
+    Get "/resolve_permalink" resolve_permalink,
+    Get "/stream" get_stream,
+    Post "/community" create_community,
+    Get "/community" get_community,
+    Put "/community" edit_community,
+    Delete "/community" delete_community,
+    Get "/community/random" get_random_community,
+    Get "/community/trending" list_trending_communities,
+    Get "/community/directory" list_community_directory,
+    Get "/community/list" list_communities,
+    Post "/community/follow" follow_community,
+    Post "/community/report" create_community_report,
+    Post "/community/icon" upload_community_icon,
+    Delete "/community/icon" delete_community_icon,
+    Post "/community/banner" upload_community_banner,
+    Delete "/community/banner" delete_community_banner,
+    Post "/community/notifications" edit_community_notifications,
+    Get "/community/post_template/list" list_community_post_templates,
+    Put "/community/report/resolve" resolve_community_report,
+    Post "/community/remove" remove_community,
+    Post "/community/quarantine" quarantine_community,
+    Post "/community/transfer" transfer_community,
+    Post "/community/ban_user" ban_from_community,
+    Post "/community/ban_user_many" ban_many_from_community,
+    Post "/community/mod" add_mod_to_community,
+    Post "/community/tag" create_community_tag,
+    Put "/community/tag" edit_community_tag,
+    Delete "/community/tag" delete_community_tag,
+    Post "/community/post_template" create_community_post_template,
+    Put "/community/post_template" edit_community_post_template,
+    Delete "/community/post_template" delete_community_post_template,
+    Post "/community/recurring_post" create_community_recurring_post,
+    Put "/community/recurring_post" edit_community_recurring_post,
+    Delete "/community/recurring_post" delete_community_recurring_post,
+    Get "/community/recurring_post/list" list_community_recurring_posts,
+    Get "/community/pending_follows/list" get_pending_follows_list,
+    Post "/community/pending_follows/approve" post_pending_follows_approve,
+    Post "/multi_community" create_multi_community,
+    Put "/multi_community" edit_multi_community,
+    Get "/multi_community" read_multi_community,
+    Post "/multi_community/entry" create_multi_community_entry,
+    Delete "/multi_community/entry" delete_multi_community_entry,
+    Get "/multi_community/list" list_multi_communities,
+    Post "/multi_community/follow" follow_multi_community,
+    Get "/federated_instances" get_federated_instances,
+    Post "/post" create_post,
+    Get "/post/site_metadata" get_link_metadata,
+    Get "/post/similar" get_similar_posts,
+    Get "/post" get_post,
+    Put "/post" edit_post,
+    Delete "/post" delete_post,
+    Post "/post/mark_as_read" mark_post_as_read,
+    Post "/post/mark_as_read/many" mark_posts_as_read,
+    Post "/post/hide" hide_post,
+    Get "/post/list" list_posts,
+    Get "/post/list/updates" list_post_updates,
+    Get "/post/hashtag/list" list_hashtags,
+    Post "/post/like" like_post,
+    Put "/post/save" save_post,
+    Post "/post/report" create_post_report,
+    Post "/post/notifications" edit_post_notifications,
+    Post "/post/remove" remove_post,
+    Post "/post/remove_many" remove_many_posts,
+    Post "/post/lock" lock_post,
+    Post "/post/feature" feature_post,
+    Post "/post/feature/schedule" schedule_post_feature,
+    Get "/post/feature/schedule/list" list_post_feature_schedules,
+    Get "/post/like/list" list_post_likes,
+    Put "/post/report/resolve" resolve_post_report,
+    Put "/post/mod_edit" mod_edit_post,
+    Post "/post/warn" create_post_warning,
+    Post "/comment" create_comment,
+    Get "/comment" get_comment,
+    Put "/comment" edit_comment,
+    Delete "/comment" delete_comment,
+    Post "/comment/like" like_comment,
+    Put "/comment/save" save_comment,
+    Get "/comment/list" list_comments,
+    Get "/comment/list/slim" list_comments_slim,
+    Get "/comment/list/updates" list_comment_updates,
+    Post "/comment/report" create_comment_report,
+    Post "/comment/remove" remove_comment,
+    Post "/comment/remove_many" remove_many_comments,
+    Post "/comment/distinguish" distinguish_comment,
+    Get "/comment/like/list" list_comment_likes,
+    Post "/comment/lock" lock_comment,
+    Post "/comment/warn" create_comment_warning,
+    Put "/comment/report/resolve" resolve_comment_report,
+    Post "/private_message" create_private_message,
+    Put "/private_message" edit_private_message,
+    Delete "/private_message" delete_private_message,
+    Post "/private_message/report" create_pm_report,
+    Get "/private_message/encryption_key/list" list_encryption_keys,
+    Post "/private_message/encryption_key" create_encryption_key,
+    Delete "/private_message/encryption_key" delete_encryption_key,
+    Put "/private_message/report/resolve" resolve_pm_report,
+    Post "/report/instance" create_instance_report,
+    Get "/report/list" list_reports,
+    Put "/report/instance/resolve" resolve_instance_report,
+    Post "/account/auth/refresh_token" refresh_token,
+    Post "/account/auth/register" register,
+    Post "/account/auth/login" login,
+    Post "/account/auth/logout" logout,
+    Post "/account/auth/logout_all" logout_all,
+    Post "/account/auth/password_reset" reset_password,
+    Post "/account/auth/password_change" change_password_after_reset,
+    Put "/account/auth/change_password" change_password,
+    Post "/account/auth/totp/generate" generate_totp_secret,
+    Post "/account/auth/totp/edit" edit_totp,
+    Post "/account/auth/totp/recovery_codes" regenerate_totp_recovery_codes,
+    Post "/account/auth/webauthn/login/start" start_webauthn_login,
+    Post "/account/auth/webauthn/login" webauthn_login,
+    Post "/account/auth/verify_email" verify_email,
+    Post "/account/auth/pow_challenge/verify" verify_pow_challenge,
+    Post "/account/auth/resend_verification_email" resend_verification_email,
+    Get "/account/auth/get_captcha" get_captcha,
+    Get "/account/auth/pow_challenge" get_pow_challenge,
+    Get "/account" get_my_user,
+    Delete "/account" delete_account,
+    Get "/account/unread_counts" get_unread_counts,
+    Delete "/account/media" delete_image,
+    Get "/account/media/list" list_media,
+    Get "/account/media/usage" get_media_usage,
+    Get "/account/notification/list" list_notifications,
+    Post "/account/notification/mark_as_read/all" mark_all_notifications_read,
+    Post "/account/notification/mark_as_read" mark_notification_as_read,
+    Get "/account/inbox" list_notifications,
+    Get "/account/login/list" list_logins,
+    Post "/account/login/revoke" revoke_login,
+    Post "/account/api_key" create_api_key,
+    Delete "/account/api_key" delete_api_key,
+    Get "/account/api_key/list" list_api_keys,
+    Post "/account/push_subscription" register_push_subscription,
+    Delete "/account/push_subscription" unregister_push_subscription,
+    Get "/account/push_subscription/list" list_push_subscriptions,
+    Post "/account/saved_search" create_saved_search,
+    Delete "/account/saved_search" delete_saved_search,
+    Get "/account/saved_search/list" list_saved_searches,
+    Delete "/account/webauthn" delete_webauthn_credential,
+    Get "/account/webauthn/list" list_webauthn_credentials,
+    Post "/account/webauthn/register/start" start_webauthn_registration,
+    Post "/account/webauthn/register" register_webauthn_credential,
+    Delete "/account/oauth_account" unlink_oauth_account,
+    Get "/account/oauth_account/list" list_oauth_accounts,
+    Get "/account/oauth_application/authorized/list" list_authorized_oauth_applications,
+    Post "/account/oauth_application/revoke" revoke_oauth_application,
+    Get "/account/validate_auth" validate_auth,
+    Post "/account/donation_dialog_shown" donation_dialog_shown,
+    Post "/account/avatar" upload_user_avatar,
+    Delete "/account/avatar" delete_user_avatar,
+    Post "/account/banner" upload_user_banner,
+    Delete "/account/banner" delete_user_banner,
+    Post "/account/block/person" user_block_person,
+    Post "/account/block/community" user_block_community,
+    Post "/account/block/instance/communities" user_block_instance_communities,
+    Post "/account/block/instance/persons" user_block_instance_persons,
+    Get "/account/saved" list_person_saved,
+    Get "/account/read" list_person_read,
+    Get "/account/read/sync" sync_read_posts,
+    Get "/account/hidden" list_person_hidden,
+    Get "/account/liked" list_person_liked,
+    Put "/account/settings/save" save_user_settings,
+    Get "/account/settings/export" export_settings,
+    Post "/account/settings/import" import_settings,
+    Get "/account/data/export" export_data,
+    Get "/person" read_person,
+    Get "/person/list" list_persons,
+    Get "/person/content" list_person_content,
+    Post "/person/note" user_note_person,
+    Post "/person/mute" user_mute_person,
+    Post "/admin/add" add_admin,
+    Get "/admin/registration_application" get_registration_application,
+    Get "/admin/registration_application/list" list_registration_applications,
+    Put "/admin/registration_application/approve" approve_registration_application,
+    Post "/admin/purge/person" purge_person,
+    Post "/admin/purge/community" purge_community,
+    Post "/admin/purge/post" purge_post,
+    Post "/admin/purge/comment" purge_comment,
+    Post "/admin/tagline" create_tagline,
+    Put "/admin/tagline" edit_tagline,
+    Delete "/admin/tagline" delete_tagline,
+    Get "/admin/tagline/list" list_taglines,
+    Post "/admin/webhook" create_webhook,
+    Put "/admin/webhook" edit_webhook,
+    Delete "/admin/webhook" delete_webhook,
+    Get "/admin/webhook/list" list_webhooks,
+    Get "/admin/webhook/delivery/list" list_webhook_deliveries,
+    Post "/admin/rate_limit_override" set_rate_limit_override,
+    Delete "/admin/rate_limit_override" delete_rate_limit_override,
+    Get "/admin/rate_limit_override/list" list_rate_limit_overrides,
+    Post "/admin/link_metadata_override" set_link_metadata_override,
+    Delete "/admin/link_metadata_override" delete_link_metadata_override,
+    Get "/admin/link_metadata_override/list" list_link_metadata_overrides,
+    Post "/admin/ban" ban_from_site,
+    Get "/admin/users" admin_list_users,
+    Get "/admin/stats" get_admin_stats,
+    Get "/admin/login_failures" list_login_failures,
+    Get "/admin/pseudonym" read_pseudonym,
+    Get "/admin/federation_queue" list_federation_queue,
+    Get "/admin/federation_inbox" list_federation_inbox,
+    Get "/admin/client_penalties" list_client_penalties,
+    Post "/admin/client_penalties/clear" clear_client_penalties,
+    Get "/admin/federation_deliveries" list_failed_deliveries,
+    Post "/admin/federation_deliveries/replay" replay_failed_deliveries,
+    Post "/admin/instance/block" admin_block_instance,
+    Post "/admin/instance/allow" admin_allow_instance,
+    Get "/admin/instance/move/list" list_instance_moves,
+    Post "/admin/instance/move/resolve" resolve_instance_move,
+    Post "/custom_emoji" create_custom_emoji,
+    Put "/custom_emoji" edit_custom_emoji,
+    Delete "/custom_emoji" delete_custom_emoji,
+    Get "/custom_emoji/list" list_custom_emojis,
+    Post "/oauth_provider" create_oauth_provider,
+    Put "/oauth_provider" edit_oauth_provider,
+    Delete "/oauth_provider" delete_oauth_provider,
+    Post "/oauth_application/token" oauth_application_token,
+    Post "/oauth_application" create_oauth_application,
+    Delete "/oauth_application" delete_oauth_application,
+    Get "/oauth_application/list" list_oauth_applications,
+    Post "/oauth_application/authorize" authorize_oauth_application,
+    Post "/oauth/authenticate" authenticate_with_oauth,
+    Delete "/image" delete_image_admin,
+    Post "/image" upload_image,
+    Get "/image/proxy" image_proxy,
+    Get "/image/health" pictrs_health,
+    Get "/image/list" list_all_media,
+    Get "/image/orphaned" list_orphaned_media,
+    Get "/image/{filename}" get_image,
+  }
+  doc.build()
+}
+
+#[derive(Default)]
+struct Document {
+  paths: Paths,
+  schemas: Schemas,
+  operation_ids: HashSet<String>,
+}
+
+impl Document {
+  fn add<F, Args>(&mut self, method: HttpMethod, path: &str, operation_id: &str, _handler: F)
+  where
+    F: Handler<Args>,
+    Args: ApiArgs,
+    F::Output: ApiOutput,
+  {
+    // Operation ids need to be unique, but some handlers are registered for several methods
+    let mut operation_id = operation_id.to_string();
+    if !self.operation_ids.insert(operation_id.clone()) {
+      operation_id = format!("{operation_id}_{method:?}").to_lowercase();
+      self.operation_ids.insert(operation_id.clone());
+    }
+    let tag = path.split('/').find(|s| !s.is_empty()).unwrap_or_default();
+    let mut operation = OperationBuilder::new()
+      .operation_id(Some(operation_id))
+      .tag(tag)
+      .build();
+    for name in path
+      .split('/')
+      .filter_map(|s| s.strip_prefix('{')?.strip_suffix('}'))
+    {
+      let parameter = ParameterBuilder::new()
+        .name(name)
+        .parameter_in(ParameterIn::Path)
+        .required(Required::True)
+        .schema(Some(ObjectBuilder::new().schema_type(Type::String)))
+        .build();
+      operation
+        .parameters
+        .get_or_insert_with(Vec::new)
+        .push(parameter);
+    }
+    Args::add_all_to(&mut operation, &mut self.schemas);
+    <F::Output as ApiOutput>::add_responses_to(&mut operation, &mut self.schemas);
+    self.paths.add_path_operation(path, vec![method], operation);
+  }
+
+  fn build(self) -> OpenApi {
+    let error = ObjectBuilder::new()
+      .description(Some(
+        "Error of a failed request, the possible values of `error` are listed in LemmyErrorType",
+      ))
+      .property("error", ObjectBuilder::new().schema_type(Type::String))
+      .required("error")
+      .property(
+        "message",
+        ObjectBuilder::new().schema_type(SchemaType::AnyValue),
+      );
+    let components = ComponentsBuilder::new()
+      .schemas_from_iter(self.schemas)
+      .schema(ERROR_SCHEMA, error)
+      .security_scheme(
+        BEARER_AUTH,
+        SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+      )
+      .build();
+    OpenApiBuilder::new()
+      .info(Info::new("Lemmy", VERSION.as_str()))
+      .servers(Some([Server::new("/api/v4")]))
+      .paths(self.paths)
+      .components(Some(components))
+      .build()
+  }
+}
+
+/// Inlines the schema of a request or response type, and collects the schemas it refers to.
+fn schema<T: ToSchema>(schemas: &mut Schemas) -> RefOr<Schema> {
+  T::schemas(schemas);
+  T::schema()
+}
+
+fn request_body(content_type: &str, schema: impl Into<RefOr<Schema>>) -> RequestBody {
+  RequestBodyBuilder::new()
+    .content(content_type, Content::new(Some(schema)))
+    .required(Some(Required::True))
+    .build()
+}
+
+/// Describes how an argument of a handler is passed in the request.
+trait ApiArg {
+  fn add_to(_operation: &mut Operation, _schemas: &mut Schemas) {}
+}
+
+/// Arguments which are not part of the api, like the context.
+impl ApiArg for Data<LemmyContext> {}
+impl ApiArg for web::Data<LemmyContext> {}
+impl ApiArg for HttpRequest {}
+/// Path parameters are read from the path of the operation.
+impl ApiArg for Path<String> {}
+
+impl ApiArg for LocalUserView {
+  fn add_to(operation: &mut Operation, _schemas: &mut Schemas) {
+    operation.security = Some(vec![SecurityRequirement::new(
+      BEARER_AUTH,
+      Vec::<String>::new(),
+    )]);
+  }
+}
+
+impl ApiArg for Option<LocalUserView> {
+  fn add_to(operation: &mut Operation, _schemas: &mut Schemas) {
+    operation.security = Some(vec![
+      SecurityRequirement::default(),
+      SecurityRequirement::new(BEARER_AUTH, Vec::<String>::new()),
+    ]);
+  }
+}
+
+impl<T: ToSchema> ApiArg for Json<T> {
+  fn add_to(operation: &mut Operation, schemas: &mut Schemas) {
+    operation.request_body = Some(request_body("application/json", schema::<T>(schemas)));
+  }
+}
+
+impl<T: ToSchema> ApiArg for Form<T> {
+  fn add_to(operation: &mut Operation, schemas: &mut Schemas) {
+    operation.request_body = Some(request_body(
+      "application/x-www-form-urlencoded",
+      schema::<T>(schemas),
+    ));
+  }
+}
+
+/// Each field of the query struct is a separate parameter.
+impl<T: ToSchema> ApiArg for Query<T> {
+  fn add_to(operation: &mut Operation, schemas: &mut Schemas) {
+    let RefOr::T(Schema::Object(object)) = schema::<T>(schemas) else {
+      return;
+    };
+    let parameters = operation.parameters.get_or_insert_with(Vec::new);
+    for (name, property) in object.properties {
+      let required = if object.required.contains(&name) {
+        Required::True
+      } else {
+        Required::False
+      };
+      let parameter = ParameterBuilder::new()
+        .name(name)
+        .parameter_in(ParameterIn::Query)
+        .required(required)
+        .schema(Some(property))
+        .build();
+      parameters.push(parameter);
+    }
+  }
+}
+
+/// Images are uploaded as multipart form, which is passed on to pict-rs.
+impl ApiArg for Payload {
+  fn add_to(operation: &mut Operation, _schemas: &mut Schemas) {
+    let file = ObjectBuilder::new()
+      .schema_type(Type::String)
+      .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)));
+    let form = ObjectBuilder::new()
+      .property("images[]", file)
+      .required("images[]");
+    operation.request_body = Some(request_body("multipart/form-data", form));
+  }
+}
+
+/// The arguments of a handler, which are passed to it as tuple.
+trait ApiArgs {
+  fn add_all_to(_operation: &mut Operation, _schemas: &mut Schemas) {}
+}
+
+impl ApiArgs for () {}
+
+macro_rules! impl_api_args {
+  ($($arg:ident),+) => {
+    impl<$($arg: ApiArg),+> ApiArgs for ($($arg,)+) {
+      fn add_all_to(operation: &mut Operation, schemas: &mut Schemas) {
+        $($arg::add_to(operation, schemas);)+
+      }
+    }
+  };
+}
+
+impl_api_args!(A);
+impl_api_args!(A, B);
+impl_api_args!(A, B, C);
+impl_api_args!(A, B, C, D);
+impl_api_args!(A, B, C, D, E);
+impl_api_args!(A, B, C, D, E, F);
+
+/// Describes the responses of a handler by its return type.
+trait ApiOutput {
+  fn add_responses_to(operation: &mut Operation, schemas: &mut Schemas);
+}
+
+impl<T: ToSchema> ApiOutput for Json<T> {
+  fn add_responses_to(operation: &mut Operation, schemas: &mut Schemas) {
+    add_json_response::<T>(operation, schemas);
+  }
+}
+
+impl<T: ToSchema> ApiOutput for ConditionalJson<T> {
+  fn add_responses_to(operation: &mut Operation, schemas: &mut Schemas) {
+    add_json_response::<T>(operation, schemas);
+  }
+}
+
+/// Responses which are built manually, like captchas, images or event streams.
+impl<B> ApiOutput for HttpResponse<B> {
+  fn add_responses_to(operation: &mut Operation, _schemas: &mut Schemas) {
+    add_response(
+      operation,
+      "200",
+      ResponseBuilder::new().description("Success"),
+    );
+  }
+}
+
+impl<L: ApiOutput, R: ApiOutput> ApiOutput for Either<L, R> {
+  fn add_responses_to(operation: &mut Operation, schemas: &mut Schemas) {
+    L::add_responses_to(operation, schemas);
+    R::add_responses_to(operation, schemas);
+  }
+}
+
+impl<T: ApiOutput> ApiOutput for Result<T, LemmyError> {
+  fn add_responses_to(operation: &mut Operation, schemas: &mut Schemas) {
+    T::add_responses_to(operation, schemas);
+    let response = ResponseBuilder::new().description("Error").content(
+      "application/json",
+      Content::new(Some(Ref::from_schema_name(ERROR_SCHEMA))),
+    );
+    add_response(operation, "400", response);
+  }
+}
+
+fn add_json_response<T: ToSchema>(operation: &mut Operation, schemas: &mut Schemas) {
+  let response = ResponseBuilder::new()
+    .description("Success")
+    .content("application/json", Content::new(Some(schema::<T>(schemas))));
+  add_response(operation, "200", response);
+}
+
+fn add_response(operation: &mut Operation, status: &str, response: ResponseBuilder) {
+  operation
+    .responses
+    .responses
+    .insert(status.to_string(), RefOr::T(response.build()));
+}
@@ -55,9 +55,10 @@ use lemmy_api_019::{
     SearchResponse as SearchResponseV3,
   },
 };
-use lemmy_api_utils::plugins::is_captcha_plugin_loaded;
+use lemmy_api_utils::captcha::is_captcha_enabled;
 use lemmy_db_schema::{
   CommunitySortType,
+  SearchType,
   newtypes::LanguageId,
   source::{
     comment::Comment,
@@ -614,6 +615,7 @@ pub(crate) fn convert_db_url(db_url: DbUrl) -> DbUrlV3 {
 }
 
 pub(crate) fn convert_local_site(local_site: LocalSite) -> LocalSiteV3 {
+  let captcha_enabled = is_captcha_enabled(&local_site);
   let LocalSite {
     site_id,
     site_setup,
@@ -656,7 +658,7 @@ pub(crate) fn convert_local_site(local_site: LocalSite) -> LocalSiteV3 {
     slur_filter_regex,
     actor_name_max_length: 20,
     federation_enabled,
-    captcha_enabled: is_captcha_plugin_loaded(),
+    captcha_enabled,
     captcha_difficulty: String::new(),
     published: published_at,
     updated: updated_at,
@@ -714,14 +716,33 @@ pub(crate) fn convert_score(score: i16) -> Option<bool> {
   }
 }
 
+pub(crate) fn convert_search_type(type_: Option<SearchTypeV3>) -> Option<Vec<SearchType>> {
+  let type_ = match type_? {
+    SearchTypeV3::All => return None,
+    SearchTypeV3::Comments => SearchType::Comments,
+    SearchTypeV3::Posts | SearchTypeV3::Url => SearchType::Posts,
+    SearchTypeV3::Communities => SearchType::Communities,
+    SearchTypeV3::Users => SearchType::Persons,
+  };
+  Some(vec![type_])
+}
+
 pub(crate) fn convert_search_response(
   res: SearchResponse,
   type_: Option<SearchTypeV3>,
 ) -> SearchResponseV3 {
   SearchResponseV3 {
     type_: type_.unwrap_or(SearchTypeV3::All),
-    comments: res.comments.into_iter().map(convert_comment_view).collect(),
-    posts: res.posts.into_iter().map(convert_post_view).collect(),
+    comments: res
+      .comments
+      .into_iter()
+      .map(|c| convert_comment_view(c.comment_view))
+      .collect(),
+    posts: res
+      .posts
+      .into_iter()
+      .map(|p| convert_post_view(p.post_view))
+      .collect(),
     communities: res
       .communities
       .into_iter()
@@ -871,6 +892,7 @@ pub(crate) fn convert_login_response(res: LoginResponse) -> LemmyResult<Json<Log
     jwt,
     registration_created,
     verify_email_sent,
+    ..
   } = res;
   Ok(Json(LoginResponseV3 {
     jwt: jwt.map(convert_sensitive),
@@ -19,6 +19,7 @@ use crate::convert::{
   convert_resolve_object_response,
   convert_score,
   convert_search_response,
+  convert_search_type,
   convert_site,
   convert_site_view,
 };
@@ -108,6 +109,7 @@ use lemmy_api_crud::{
 };
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_schema::newtypes::{CommentId, CommunityId, LanguageId, PostId};
+use lemmy_db_schema_file::PersonId;
 use lemmy_db_views_comment::api::{
   CreateComment,
   CreateCommentLike,
@@ -187,7 +189,9 @@ pub(crate) async fn list_posts_v3(
     limit,
     ..Default::default()
   };
-  let res = list_posts(Query(data), context, local_user_view).await?.0;
+  let res = list_posts(Query(data), context, local_user_view)
+    .await?
+    .data;
   Ok(Json(GetPostsResponseV3 {
     posts: res.into_iter().map(convert_post_view).collect(),
     next_page: None,
@@ -223,10 +227,12 @@ pub(crate) async fn list_comments_v3(
     parent_id: parent_id.map(|p| CommentId(p.0)),
     time_range_seconds: None,
     search_term: None,
+    show_removed: None,
+    minimal: None,
   };
   let comments = list_comments(Query(data), context, local_user_view)
     .await?
-    .0;
+    .data;
   Ok(Json(GetCommentsResponseV3 {
     comments: comments.into_iter().map(convert_comment_view).collect(),
   }))
@@ -253,7 +259,9 @@ pub(crate) async fn get_site_v3(
     tagline,
     blocked_urls,
     ..
-  } = get_site(local_user_view.clone(), context.clone()).await?.0;
+  } = get_site(local_user_view.clone(), context.clone())
+    .await?
+    .data;
   let my_user = if let Some(local_user_view) = local_user_view {
     Some(Box::pin(get_my_user(local_user_view, context)).await?.0)
   } else {
@@ -370,6 +378,8 @@ pub(crate) async fn create_post_v3(
     custom_thumbnail,
     tags: None,
     scheduled_publish_time_at: None,
+    pseudonymous: None,
+    template_id: None,
   };
   let res = Box::pin(create_post(Json(data), context, local_user_view)).await?;
   convert_post_response(res)
@@ -384,11 +394,21 @@ pub(crate) async fn search_v3(
     type_,
     q: search_term,
     post_title_only: search_title_only,
+    community_id,
+    community_name,
+    creator_id,
     ..
   } = data;
   let form = Search {
     search_term,
     search_title_only,
+    types: convert_search_type(type_),
+    community_id: community_id.map(|c| CommunityId(c.0)),
+    community_name,
+    creator_id: creator_id.map(|p| PersonId(p.0)),
+    // Snippets didn't exist in the old api
+    full_content: Some(true),
+    ..Default::default()
   };
   let data = search(Query(form), context, local_user_view).await?;
   Ok(Json(convert_search_response(data.0, type_)))
@@ -535,7 +555,7 @@ pub(crate) async fn get_community_v3(
   context: ApubData<LemmyContext>,
   local_user_view: Option<LocalUserView>,
 ) -> LemmyResult<Json<GetCommunityResponseV3>> {
-  let res = get_community(data, context, local_user_view).await?.0;
+  let res = get_community(data, context, local_user_view).await?.data;
   Ok(Json(GetCommunityResponseV3 {
     community_view: convert_community_view(res.community_view),
     site: res.site.map(convert_site),
@@ -30,6 +30,7 @@ use crate::handlers::{
 };
 use actix_web::{guard, web::*};
 use lemmy_api::local_user::donation_dialog_shown::donation_dialog_shown;
+use lemmy_routes::middleware::{tarpit::TarpitMiddleware, token_scope::TokenScopeMiddleware};
 use lemmy_utils::rate_limit::RateLimit;
 
 mod convert;
@@ -39,6 +40,8 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
   cfg.service(
     scope("/api/v3")
       .wrap(rate_limit.message())
+      .wrap(TarpitMiddleware::new(rate_limit.clone()))
+      .wrap(TokenScopeMiddleware::by_method())
       // Site
       .service(scope("/site").route("", get().to(get_site_v3)))
       .service(
@@ -96,12 +99,14 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
         resource("/user/login")
           .guard(guard::Post())
           .wrap(rate_limit.register())
+          .wrap(TokenScopeMiddleware::login_only())
           .route(post().to(login_v3)),
       )
       .service(
         resource("/user/register")
           .guard(guard::Post())
           .wrap(rate_limit.register())
+          .wrap(TokenScopeMiddleware::login_only())
           .route(post().to(register_v3)),
       )
       .service(
@@ -17,6 +17,7 @@ use crate::protocol::{
     reject::RejectFollow,
     undo_follow::UndoFollow,
   },
+  site::move_site::MoveSite,
   voting::{undo_vote::UndoVote, vote::Vote},
 };
 use activitypub_federation::{config::Data, traits::Activity};
@@ -46,6 +47,7 @@ pub enum SharedInboxActivities {
   Report(Report),
   ResolveReport(ResolveReport),
   AnnounceActivity(AnnounceActivity),
+  MoveSite(MoveSite),
   /// This is a catch-all and needs to be last
   RawAnnouncableActivities(RawAnnouncableActivities),
 }
@@ -16,6 +16,7 @@ use activitypub_federation::{
 };
 use chrono::{DateTime, Utc};
 use lemmy_api_utils::{
+  cache::invalidate_posts,
   context::LemmyContext,
   notify::notify_mod_action,
   utils::{remove_or_restore_user_data, remove_or_restore_user_data_in_community},
@@ -160,6 +161,7 @@ impl Activity for BlockUser {
           } else {
             update_removed_for_instance(&blocked_person, &site, true, pool).await?;
           }
+          invalidate_posts();
         }
       }
       SiteOrCommunity::Right(community) => {
@@ -197,6 +199,7 @@ impl Activity for BlockUser {
             &mut context.pool(),
           )
           .await?;
+          invalidate_posts();
         }
       }
     }
@@ -15,6 +15,7 @@ use activitypub_federation::{
   traits::{Activity, Actor, Object},
 };
 use lemmy_api_utils::{
+  cache::invalidate_posts,
   context::LemmyContext,
   notify::notify_mod_action,
   utils::{remove_or_restore_user_data, remove_or_restore_user_data_in_community},
@@ -129,6 +130,7 @@ impl Activity for UndoBlockUser {
           } else {
             update_removed_for_instance(&blocked_person, &site, false, pool).await?;
           }
+          invalidate_posts();
         }
       }
       SiteOrCommunity::Right(community) => {
@@ -160,6 +162,7 @@ impl Activity for UndoBlockUser {
             &mut context.pool(),
           )
           .await?;
+          invalidate_posts();
         }
       }
     }
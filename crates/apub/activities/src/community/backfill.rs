@@ -0,0 +1,168 @@
+use crate::{
+  activity_lists::AnnouncableActivities,
+  protocol::community::announce::AnnounceActivity,
+};
+use activitypub_federation::{
+  config::Data,
+  fetch::fetch_object_http,
+  traits::{Activity, Object},
+};
+use futures::future::join_all;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::{
+  objects::{comment::ApubComment, community::ApubCommunity},
+  protocol::{group::Group, note::Note},
+};
+use lemmy_utils::{error::LemmyResult, spawn_try_task};
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::{sync::Semaphore, time::sleep};
+use tracing::info;
+use url::Url;
+
+/// Only a single community is backfilled at a time, to limit the load on remote instances and on
+/// the local database.
+static BACKFILL_PERMITS: Semaphore = Semaphore::const_new(1);
+
+/// Number of outbox items or comments which are processed in parallel.
+const BACKFILL_BATCH_SIZE: usize = 10;
+
+/// Delay between batches, so that the remote instance isn't flooded with requests.
+const BACKFILL_BATCH_DELAY: Duration = Duration::from_secs(1);
+
+/// A page of an outbox or other collection. Lemmy puts all items directly into the collection,
+/// while most other software uses pagination.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionPage<T> {
+  #[serde(default = "Vec::new")]
+  ordered_items: Vec<T>,
+  first: Option<Value>,
+  next: Option<Url>,
+}
+
+impl<T> CollectionPage<T> {
+  fn next_page(&self) -> Option<Url> {
+    match &self.first {
+      Some(Value::String(first)) => Url::parse(first).ok(),
+      _ => self.next.clone(),
+    }
+  }
+}
+
+/// Fetches the latest posts and their top-level comments of a remote community in the
+/// background, so that it doesn't look empty for the first local subscriber.
+pub(crate) fn spawn_backfill_community(community: ApubCommunity, context: Data<LemmyContext>) {
+  let pages = context.settings().federation.backfill_pages;
+  if community.local || pages == 0 {
+    return;
+  }
+  spawn_try_task(async move {
+    let _permit = BACKFILL_PERMITS.acquire().await?;
+    info!("Backfilling community {}", community.ap_id);
+    backfill_community(&community, pages, &context).await
+  });
+}
+
+async fn backfill_community(
+  community: &ApubCommunity,
+  pages: u8,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let group: Group = fetch_object_http(&community.ap_id.clone().into(), context)
+    .await?
+    .object;
+  let mut next_url = Some(group.outbox);
+
+  // One more request for paginated outboxes, where the collection only links to the first page
+  for _ in 0..=pages {
+    let Some(url) = next_url.take() else {
+      break;
+    };
+    let page: CollectionPage<Value> = fetch_object_http(&url, context).await?.object;
+    next_url = page.next_page();
+    for batch in page.ordered_items.chunks(BACKFILL_BATCH_SIZE) {
+      let posts = join_all(
+        batch
+          .iter()
+          .cloned()
+          .map(|item| backfill_post(item, context)),
+      )
+      .await;
+      // Comments are fetched after the whole batch of posts, to avoid too many parallel requests
+      for post_context in posts.into_iter().flatten() {
+        backfill_comments(post_context, context).await.ok();
+      }
+      sleep(BACKFILL_BATCH_DELAY).await;
+    }
+  }
+  Ok(())
+}
+
+/// Receives a post from the outbox and returns its id together with the url of its comment
+/// collection.
+async fn backfill_post(item: Value, context: &Data<LemmyContext>) -> Option<(Url, Url)> {
+  // Same as in the outbox handler: the announce can't be received directly because it requires a
+  // local community follower, so extract the inner activity.
+  let announce: AnnounceActivity = serde_json::from_value(item).ok()?;
+  let inner: AnnouncableActivities = announce
+    .object
+    .object(context)
+    .await
+    .ok()?
+    .try_into()
+    .ok()?;
+  let AnnouncableActivities::CreateOrUpdatePost(create) = inner else {
+    return None;
+  };
+  let post_id = create.object.id.inner().clone();
+  let comments_url = create.object.context_url();
+  create.verify(context).await.ok()?;
+  create.receive(context).await.ok()?;
+  comments_url.map(|c| (post_id, c))
+}
+
+async fn backfill_comments(
+  (post_id, comments_url): (Url, Url),
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let limit: usize = context
+    .settings()
+    .federation
+    .backfill_comments_per_post
+    .into();
+  let collection: CollectionPage<Url> = fetch_object_http(&comments_url, context).await?.object;
+  let comment_ids: Vec<_> = collection
+    .ordered_items
+    .into_iter()
+    .filter(|id| id != &post_id)
+    .collect();
+
+  let mut fetched = 0;
+  for batch in comment_ids.chunks(BACKFILL_BATCH_SIZE) {
+    let notes = join_all(
+      batch
+        .iter()
+        .map(|id| fetch_object_http::<LemmyContext, Note>(id, context)),
+    )
+    .await;
+    for note in notes.into_iter().flatten().map(|n| n.object) {
+      // Only top-level comments, replies can be fetched later on demand
+      if note.in_reply_to() != &post_id || fetched >= limit {
+        continue;
+      }
+      let id = note.id.inner().clone();
+      if ApubComment::verify(&note, &id, context).await.is_ok()
+        && ApubComment::from_json(note, context).await.is_ok()
+      {
+        fetched += 1;
+      }
+    }
+    if fetched >= limit {
+      break;
+    }
+    sleep(BACKFILL_BATCH_DELAY).await;
+  }
+  Ok(())
+}
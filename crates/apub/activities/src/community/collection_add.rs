@@ -12,6 +12,7 @@ use activitypub_federation::{
   traits::{Activity, Actor, Object},
 };
 use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
   context::LemmyContext,
   notify::notify_mod_action,
   utils::{generate_featured_url, generate_moderators_url},
@@ -140,6 +141,7 @@ impl Activity for CollectionAdd {
             ModlogInsertForm::mod_add_to_community(actor.id, community.id, new_mod.id, false);
           let action = Modlog::create(&mut context.pool(), &[form]).await?;
           notify_mod_action(action, context);
+          invalidate_community(community.id).await;
         }
       }
       CollectionType::Featured => {
@@ -151,6 +153,7 @@ impl Activity for CollectionAdd {
           ..Default::default()
         };
         Post::update(&mut context.pool(), post.id, &form).await?;
+        invalidate_posts();
       }
     }
     Ok(())
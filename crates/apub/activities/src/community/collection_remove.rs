@@ -12,6 +12,7 @@ use activitypub_federation::{
   traits::{Activity, Actor, Object},
 };
 use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
   context::LemmyContext,
   notify::notify_mod_action,
   utils::{generate_featured_url, generate_moderators_url},
@@ -130,6 +131,7 @@ impl Activity for CollectionRemove {
           ModlogInsertForm::mod_add_to_community(actor.id, community.id, remove_mod.id, true);
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         notify_mod_action(action, context);
+        invalidate_community(community.id).await;
       }
       CollectionType::Featured => {
         let post = ObjectId::<ApubPost>::from(self.object)
@@ -140,6 +142,7 @@ impl Activity for CollectionRemove {
           ..Default::default()
         };
         Post::update(&mut context.pool(), post.id, &form).await?;
+        invalidate_posts();
       }
     }
     Ok(())
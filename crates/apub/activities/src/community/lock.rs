@@ -13,7 +13,7 @@ use activitypub_federation::{
   kinds::activity::UndoType,
   traits::Activity,
 };
-use lemmy_api_utils::{context::LemmyContext, notify::notify_mod_action};
+use lemmy_api_utils::{cache::invalidate_posts, context::LemmyContext, notify::notify_mod_action};
 use lemmy_apub_objects::{
   objects::{PostOrComment, community::ApubCommunity},
   utils::{
@@ -66,6 +66,8 @@ impl Activity for LockPageOrNote {
         };
         Post::update(&mut context.pool(), post.id, &form).await?;
 
+        invalidate_posts();
+
         let form = ModlogInsertForm::mod_lock_post(actor.id, &post, true, &reason);
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         notify_mod_action(action, context);
@@ -124,6 +126,8 @@ impl Activity for UndoLockPageOrNote {
 
         Post::update(&mut context.pool(), post.id, &form).await?;
 
+        invalidate_posts();
+
         let form = ModlogInsertForm::mod_lock_post(actor.id, &post, false, &reason);
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         notify_mod_action(action, context);
@@ -21,12 +21,14 @@ use lemmy_db_schema::source::{
   person::{Person, PersonActions},
   site::Site,
 };
+use lemmy_db_schema_file::enums::CommunityVisibility;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::LemmyResult;
 
 pub mod announce;
+pub mod backfill;
 pub mod collection_add;
 pub mod collection_remove;
 pub mod lock;
@@ -39,8 +41,8 @@ pub mod update;
 ///
 /// Activities are sent to the community itself if it lives on another instance. If the community
 /// is local, the activity is directly wrapped into Announce and sent to community followers.
-/// Activities are also sent to those who follow the actor (with exception of moderation
-/// activities).
+/// Activities in public communities are also sent to those who follow the actor (with exception
+/// of moderation activities).
 ///
 /// * `activity` - The activity which is being sent
 /// * `actor` - The user who is sending the activity
@@ -64,8 +66,13 @@ pub(crate) async fn send_activity_in_community(
   // send to any users which are mentioned or affected directly
   let mut inboxes = extra_inboxes;
 
-  // send to user followers
-  if !is_mod_action {
+  // send to user followers, which may be on platforms like Mastodon. Content of private
+  // communities must only go to approved community followers.
+  let is_public = matches!(
+    community.visibility,
+    CommunityVisibility::Public | CommunityVisibility::Unlisted
+  );
+  if !is_mod_action && is_public {
     inboxes.add_inboxes(PersonActions::follower_inboxes(&mut context.pool(), actor.id).await?);
   }
 
@@ -13,7 +13,7 @@ use activitypub_federation::{
   config::Data,
   fetch::object_id::ObjectId,
   kinds::activity::FlagType,
-  traits::{Activity, Object},
+  traits::{Activity, Actor, Object},
 };
 use either::Either;
 use lemmy_api_utils::{
@@ -32,13 +32,18 @@ use lemmy_apub_objects::{
     instance::ApubSite,
     person::ApubPerson,
   },
-  utils::functions::{verify_person_in_community, verify_person_in_site_or_community},
+  utils::{
+    functions::{verify_person_in_community, verify_person_in_site_or_community},
+    quirks::{InstanceQuirks, Software},
+  },
 };
 use lemmy_db_schema::{
   source::{
+    activity::ActivitySendTargets,
     comment_report::{CommentReport, CommentReportForm},
     community::Community,
     community_report::{CommunityReport, CommunityReportForm},
+    person::Person,
     post::Post,
     post_report::{PostReport, PostReportForm},
   },
@@ -77,10 +82,52 @@ impl Report {
     reason: String,
     context: Data<LemmyContext>,
   ) -> LemmyResult<()> {
-    let report = Self::new(&object_id, actor, receiver, Some(reason), &context)?;
-    let inboxes = report_inboxes(object_id, receiver, actor, &context).await?;
+    let reason = if context.settings().federation.federate_report_reasons {
+      reason
+    } else {
+      String::new()
+    };
+    let report = Self::new(&object_id, actor, receiver, Some(reason.clone()), &context)?;
+    let inboxes = report_inboxes(object_id.clone(), receiver, actor, &context).await?;
+    send_lemmy_activity(&context, report, actor, inboxes, false).await?;
+
+    Self::send_to_mastodon(object_id, actor, receiver, reason, &context).await
+  }
 
-    send_lemmy_activity(&context, report, actor, inboxes, false).await
+  /// Mastodon doesn't receive reports through the community, so they are sent directly to the
+  /// instance of the content creator. It only creates reports for accounts which are listed in the
+  /// object, so the creator is included there.
+  async fn send_to_mastodon(
+    object_id: ObjectId<ReportableObjects>,
+    actor: &ApubPerson,
+    receiver: &Either<ApubSite, ApubCommunity>,
+    reason: String,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<()> {
+    let creator_id = match object_id.dereference_local(context).await? {
+      ReportableObjects::Left(PostOrComment::Left(post)) => post.creator_id,
+      ReportableObjects::Left(PostOrComment::Right(comment)) => comment.creator_id,
+      ReportableObjects::Right(_) => return Ok(()),
+    };
+    let creator: ApubPerson = Person::read(&mut context.pool(), creator_id).await?.into();
+    let quirks = InstanceQuirks::read(&mut context.pool(), creator.instance_id).await?;
+    if creator.local || quirks.software != Software::Mastodon {
+      return Ok(());
+    }
+
+    let kind = FlagType::Flag;
+    let report = Report {
+      actor: actor.id().clone().into(),
+      to: [receiver.id().clone().into()],
+      object: ReportObject::Mastodon(vec![creator.id().clone(), object_id.into_inner()]),
+      summary: None,
+      content: Some(reason),
+      id: generate_activity_id(kind.clone(), context)?,
+      kind,
+      audience: None,
+    };
+    let inboxes = ActivitySendTargets::to_inbox(creator.shared_inbox_or_inbox());
+    send_lemmy_activity(context, report, actor, inboxes, false).await
   }
 }
 
@@ -98,7 +145,7 @@ impl Activity for Report {
   }
 
   async fn verify(&self, context: &Data<Self::DataType>) -> LemmyResult<()> {
-    let receiver = self.to[0].dereference(context).await?;
+    let receiver = self.receiver(context).await?;
     verify_person_in_site_or_community(&self.actor, &receiver, context).await?;
     match self.object.dereference(context).await? {
       ReportableObjects::Left(PostOrComment::Left(post)) => {
@@ -127,7 +174,7 @@ impl Activity for Report {
 
   async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
     let actor = self.actor.dereference(context).await?;
-    let reason = self.reason()?;
+    let reason = self.reason();
     match self.object.dereference(context).await? {
       ReportableObjects::Left(PostOrComment::Left(post)) => {
         let report_form = PostReportForm {
@@ -167,7 +214,7 @@ impl Activity for Report {
       }
     };
 
-    let receiver = self.to[0].dereference(context).await?;
+    let receiver = self.receiver(context).await?;
     if let Some(community) = local_community(&receiver) {
       // forward to remote mods
       let object_id = self.object.object_id(context).await?;
@@ -11,7 +11,10 @@ use activitypub_federation::{
   traits::{Activity, Object},
 };
 use either::Either;
-use lemmy_api_utils::context::LemmyContext;
+use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
+  context::LemmyContext,
+};
 use lemmy_apub_objects::{
   objects::{community::ApubCommunity, multi_community::ApubMultiCommunity, person::ApubPerson},
   utils::{
@@ -122,6 +125,8 @@ impl Activity for Update {
           let form = ModlogInsertForm::mod_change_community_visibility(actor.id, old_community.id);
           Modlog::create(&mut context.pool(), &[form]).await?;
         }
+        invalidate_community(community.id).await;
+        invalidate_posts();
       }
       Either::Right(m) => {
         ApubMultiCommunity::from_json(m.clone(), context).await?;
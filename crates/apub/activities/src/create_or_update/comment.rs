@@ -9,11 +9,12 @@ use crate::{
 use activitypub_federation::{
   config::Data,
   protocol::verification::{verify_domains_match, verify_urls_match},
-  traits::{Activity, Object},
+  traits::{Activity, Actor, Object},
 };
 use lemmy_api_utils::{
   context::LemmyContext,
   notify::NotifyData,
+  stream::StreamChannel,
   utils::{check_is_mod_or_admin, check_post_deleted_or_removed},
 };
 use lemmy_apub_objects::{
@@ -69,7 +70,14 @@ impl CreateOrUpdateNote {
       audience: Some(community.ap_id.clone().into()),
     };
 
-    let inboxes = tagged_user_inboxes(&create_or_update.tag, &context).await?;
+    let mut inboxes = tagged_user_inboxes(&create_or_update.tag, &context).await?;
+    // PeerTube only accepts comments on videos which are delivered to the video author.
+    if !post.local && post.embed_video_duration.is_some() {
+      let post_creator: ApubPerson = Person::read(&mut context.pool(), post.creator_id)
+        .await?
+        .into();
+      inboxes.add_inbox(post_creator.shared_inbox_or_inbox());
+    }
 
     // AnnouncableActivities doesnt contain Comment activity but only NoteWrapper,
     // to be able to handle both comment and private message. So to send this out we need
@@ -131,6 +139,9 @@ impl Activity for CreateOrUpdateNote {
 
     // Calculate initial hot_rank
     Comment::update_hot_rank(&mut context.pool(), comment.id).await?;
+    if self.kind == CreateOrUpdateType::Create {
+      StreamChannel::comment_created(&comment, post.community_id);
+    }
 
     let do_send_email =
       self.kind == CreateOrUpdateType::Create && !site_view.local_site.email_notifications_disabled;
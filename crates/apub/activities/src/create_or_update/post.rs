@@ -12,7 +12,12 @@ use activitypub_federation::{
   traits::{Activity, Object},
 };
 use chrono::Utc;
-use lemmy_api_utils::{context::LemmyContext, notify::NotifyData};
+use lemmy_api_utils::{
+  cache::invalidate_posts,
+  context::LemmyContext,
+  notify::NotifyData,
+  stream::StreamChannel,
+};
 use lemmy_apub_objects::{
   objects::{
     community::ApubCommunity,
@@ -121,6 +126,7 @@ impl Activity for CreateOrUpdatePage {
         };
         Post::update(&mut context.pool(), post.id, &form).await?;
         update_apub_post_tags(&self.object, &post, context).await?;
+        invalidate_posts();
         return Ok(());
       } else {
         return Err(LemmyErrorType::NotAModerator.into());
@@ -142,6 +148,11 @@ impl Activity for CreateOrUpdatePage {
 
     // Calculate initial hot_rank for post
     Post::update_ranks(&mut context.pool(), post.id).await?;
+    if self.kind == CreateOrUpdateType::Create {
+      StreamChannel::post_created(&post);
+    } else {
+      invalidate_posts();
+    }
 
     let do_send_email =
       self.kind == CreateOrUpdateType::Create && !site_view.local_site.email_notifications_disabled;
@@ -5,7 +5,12 @@ use crate::{
   protocol::{IdOrNestedObject, deletion::delete::Delete},
 };
 use activitypub_federation::{config::Data, kinds::activity::DeleteType, traits::Activity};
-use lemmy_api_utils::{context::LemmyContext, notify::notify_mod_action};
+use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
+  context::LemmyContext,
+  notify::notify_mod_action,
+  search::{SearchIndexQueue, SearchIndexTask},
+};
 use lemmy_apub_objects::objects::person::ApubPerson;
 use lemmy_db_schema::{
   source::{
@@ -125,7 +130,7 @@ pub(crate) async fn receive_remove_action(
       let action = Modlog::create(&mut context.pool(), &[form]).await?;
       notify_mod_action(action.clone(), context.app_data());
 
-      Community::update(
+      let community = Community::update(
         &mut context.pool(),
         community.id,
         &CommunityUpdateForm {
@@ -134,6 +139,9 @@ pub(crate) async fn receive_remove_action(
         },
       )
       .await?;
+      SearchIndexQueue::submit(SearchIndexTask::community(&community), context)?;
+      invalidate_community(community.id).await;
+      invalidate_posts();
     }
     DeletableObjects::Post(post) => {
       PostReport::resolve_all_for_object(&mut context.pool(), post.id, actor.id).await?;
@@ -149,6 +157,8 @@ pub(crate) async fn receive_remove_action(
         },
       )
       .await?;
+      SearchIndexQueue::submit(SearchIndexTask::post(&post), context)?;
+      invalidate_posts();
 
       let remove_children = with_replies.unwrap_or_default();
       if remove_children {
@@ -223,7 +233,7 @@ pub(crate) async fn receive_remove_action(
         );
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         notify_mod_action(action, context.app_data());
-        Comment::update(
+        let comment = Comment::update(
           &mut context.pool(),
           comment.id,
           &CommentUpdateForm {
@@ -232,6 +242,7 @@ pub(crate) async fn receive_remove_action(
           },
         )
         .await?;
+        SearchIndexQueue::submit(SearchIndexTask::comment(&comment), context)?;
       }
     }
     // TODO these need to be implemented yet, for now, return errors
@@ -13,7 +13,12 @@ use activitypub_federation::{
   protocol::verification::{verify_domains_match, verify_urls_match},
   traits::{Actor, Object},
 };
-use lemmy_api_utils::{context::LemmyContext, utils::purge_user_account};
+use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
+  context::LemmyContext,
+  search::{SearchIndexQueue, SearchIndexTask},
+  utils::purge_user_account,
+};
 use lemmy_apub_objects::{
   objects::{
     comment::ApubComment,
@@ -298,7 +303,7 @@ async fn receive_delete_action(
         send_apub_delete_in_community(mod_, c, object, None, true, None, context).await?;
       }
 
-      Community::update(
+      let community = Community::update(
         &mut context.pool(),
         community.id,
         &CommunityUpdateForm {
@@ -307,6 +312,9 @@ async fn receive_delete_action(
         },
       )
       .await?;
+      SearchIndexQueue::submit(SearchIndexTask::community(&community), context)?;
+      invalidate_community(community.id).await;
+      invalidate_posts();
     }
     DeletableObjects::Person(person) => {
       let site_view = SiteView::read_local(&mut context.pool()).await?;
@@ -317,10 +325,11 @@ async fn receive_delete_action(
       } else {
         Person::delete_account(&mut context.pool(), person.id, local_instance_id).await?;
       }
+      invalidate_posts();
     }
     DeletableObjects::Post(post) => {
       if deleted != post.deleted {
-        Post::update(
+        let post = Post::update(
           &mut context.pool(),
           post.id,
           &PostUpdateForm {
@@ -329,11 +338,13 @@ async fn receive_delete_action(
           },
         )
         .await?;
+        SearchIndexQueue::submit(SearchIndexTask::post(&post), context)?;
+        invalidate_posts();
       }
     }
     DeletableObjects::Comment(comment) => {
       if deleted != comment.deleted {
-        Comment::update(
+        let comment = Comment::update(
           &mut context.pool(),
           comment.id,
           &CommentUpdateForm {
@@ -342,6 +353,7 @@ async fn receive_delete_action(
           },
         )
         .await?;
+        SearchIndexQueue::submit(SearchIndexTask::comment(&comment), context)?;
       }
     }
     DeletableObjects::PrivateMessage(pm) => {
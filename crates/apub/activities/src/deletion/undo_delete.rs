@@ -4,7 +4,12 @@ use crate::{
   protocol::deletion::{delete::Delete, undo_delete::UndoDelete},
 };
 use activitypub_federation::{config::Data, kinds::activity::UndoType, traits::Activity};
-use lemmy_api_utils::{context::LemmyContext, notify::notify_mod_action};
+use lemmy_api_utils::{
+  cache::{invalidate_community, invalidate_posts},
+  context::LemmyContext,
+  notify::notify_mod_action,
+  search::{SearchIndexQueue, SearchIndexTask},
+};
 use lemmy_apub_objects::objects::person::ApubPerson;
 use lemmy_db_schema::source::{
   comment::{Comment, CommentUpdateForm},
@@ -109,7 +114,7 @@ impl UndoDelete {
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         notify_mod_action(action.clone(), context.app_data());
 
-        Community::update(
+        let community = Community::update(
           &mut context.pool(),
           community.id,
           &CommunityUpdateForm {
@@ -118,12 +123,15 @@ impl UndoDelete {
           },
         )
         .await?;
+        SearchIndexQueue::submit(SearchIndexTask::community(&community), context)?;
+        invalidate_community(community.id).await;
+        invalidate_posts();
       }
       DeletableObjects::Post(post) => {
         let form = ModlogInsertForm::mod_remove_post(actor.id, &post, false, &reason, None);
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         notify_mod_action(action, context.app_data());
-        Post::update(
+        let post = Post::update(
           &mut context.pool(),
           post.id,
           &PostUpdateForm {
@@ -132,6 +140,8 @@ impl UndoDelete {
           },
         )
         .await?;
+        SearchIndexQueue::submit(SearchIndexTask::post(&post), context)?;
+        invalidate_posts();
 
         let restore_children = with_replies.unwrap_or_default();
         if restore_children {
@@ -201,7 +211,7 @@ impl UndoDelete {
           );
           let action = Modlog::create(&mut context.pool(), &[form]).await?;
           notify_mod_action(action, context.app_data());
-          Comment::update(
+          let comment = Comment::update(
             &mut context.pool(),
             comment.id,
             &CommentUpdateForm {
@@ -210,6 +220,7 @@ impl UndoDelete {
             },
           )
           .await?;
+          SearchIndexQueue::submit(SearchIndexTask::comment(&comment), context)?;
         }
       }
       // TODO these need to be implemented yet, for now, return errors
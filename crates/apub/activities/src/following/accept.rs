@@ -1,5 +1,6 @@
 use crate::{
   check_community_deleted_or_removed,
+  community::backfill::spawn_backfill_community,
   generate_activity_id,
   protocol::following::{accept::AcceptFollow, follow::Follow},
   send_lemmy_activity,
@@ -12,9 +13,13 @@ use activitypub_federation::{
 };
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_schema::{
-  source::{activity::ActivitySendTargets, community::CommunityActions},
+  source::{
+    activity::ActivitySendTargets,
+    community::{Community, CommunityActions},
+  },
   traits::Followable,
 };
+use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::{LemmyError, LemmyResult, UntranslatedError};
 use url::Url;
 
@@ -67,6 +72,12 @@ impl Activity for AcceptFollow {
     let person_id = person.id;
     CommunityActions::follow_accepted(&mut context.pool(), community_id, person_id).await?;
 
+    // Backfill the community when the first local user subscribes to it
+    let community = Community::read(&mut context.pool(), community_id).await?;
+    if community.subscribers_local <= 1 {
+      spawn_backfill_community(community.into(), context.reset_request_count());
+    }
+
     Ok(())
   }
 }
@@ -12,13 +12,16 @@ use activitypub_federation::{
 };
 use either::Either::*;
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_apub_objects::objects::{CommunityOrMulti, person::ApubPerson};
+use lemmy_apub_objects::{
+  objects::{CommunityOrMulti, person::ApubPerson},
+  utils::quirks::InstanceQuirks,
+};
 use lemmy_db_schema::{
   source::{
     activity::ActivitySendTargets,
     community::{CommunityActions, CommunityFollowerForm},
     community_community_follow::CommunityCommunityFollow,
-    instance::{Instance, InstanceActions},
+    instance::InstanceActions,
     multi_community::{MultiCommunity, MultiCommunityFollowForm},
     person::{PersonActions, PersonFollowerForm},
   },
@@ -114,9 +117,8 @@ impl Activity for Follow {
         check_community_deleted_or_removed(&c)?;
         CommunityPersonBanView::check(&mut context.pool(), person.id, c.id).await?;
         if c.visibility == CommunityVisibility::Private {
-          let instance = Instance::read(&mut context.pool(), person.instance_id).await?;
-          if [Some("kbin"), Some("mbin")].contains(&instance.software.as_deref()) {
-            // TODO: change this to a minimum version check once private communities are supported
+          let quirks = InstanceQuirks::read(&mut context.pool(), person.instance_id).await?;
+          if !quirks.supports_private_communities() {
             return Err(
               UntranslatedError::InvalidFollow("No private community support".to_string()).into(),
             );
@@ -46,7 +46,7 @@ use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::{LemmyError, LemmyResult, UntranslatedError};
 use serde::Serialize;
-use tracing::info;
+use tracing::{Instrument, info};
 use url::{ParseError, Url};
 use uuid::Uuid;
 
@@ -57,6 +57,7 @@ pub mod create_or_update;
 pub mod deletion;
 pub mod following;
 pub mod protocol;
+pub mod site;
 pub mod voting;
 
 const MOD_ACTION_DEFAULT_REASON: &str = "No reason provided";
@@ -143,8 +144,11 @@ where
 }
 
 pub async fn handle_outgoing_activities(context: Data<LemmyContext>) {
-  while let Some(data) = ActivityChannel::retrieve_activity().await {
-    if let Err(e) = match_outgoing_activities(data, &context).await {
+  while let Some((data, span)) = ActivityChannel::retrieve_activity().await {
+    if let Err(e) = match_outgoing_activities(data, &context)
+      .instrument(span)
+      .await
+    {
       tracing::warn!("error while saving outgoing activity to db: {e}");
     }
   }
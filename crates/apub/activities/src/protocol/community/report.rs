@@ -7,9 +7,18 @@ use activitypub_federation::{
 use either::Either;
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_apub_objects::{
-  objects::{ReportableObjects, community::ApubCommunity, instance::ApubSite, person::ApubPerson},
+  objects::{
+    PostOrComment,
+    ReportableObjects,
+    community::ApubCommunity,
+    instance::ApubSite,
+    person::ApubPerson,
+  },
   utils::protocol::InCommunity,
 };
+use lemmy_db_schema::source::{community::Community, post::Post};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -32,12 +41,38 @@ pub struct Report {
 }
 
 impl Report {
-  pub fn reason(&self) -> LemmyResult<String> {
+  /// Empty if the sender doesn't federate report reasons.
+  pub fn reason(&self) -> String {
     self
       .summary
       .clone()
       .or(self.content.clone())
-      .ok_or(LemmyErrorType::NotFound.into())
+      .unwrap_or_default()
+  }
+
+  /// The site or community which handles the report. Mastodon sets `to` to the reported user
+  /// instead, in that case the community of the reported post or comment is used.
+  pub(crate) async fn receiver(
+    &self,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<Either<ApubSite, ApubCommunity>> {
+    if let Ok(receiver) = self.to[0].dereference(context).await {
+      return Ok(receiver);
+    }
+    let community_id = match self.object.dereference(context).await? {
+      ReportableObjects::Left(PostOrComment::Left(post)) => post.community_id,
+      ReportableObjects::Left(PostOrComment::Right(comment)) => {
+        Post::read(&mut context.pool(), comment.post_id)
+          .await?
+          .community_id
+      }
+      ReportableObjects::Right(_) => {
+        let site = SiteView::read_local(&mut context.pool()).await?.site;
+        return Ok(Either::Left(site.into()));
+      }
+    };
+    let community = Community::read(&mut context.pool(), community_id).await?;
+    Ok(Either::Right(community.into()))
   }
 }
 
@@ -97,7 +132,7 @@ impl InCommunity for Report {
     if let Some(audience) = &self.audience {
       return audience.dereference(context).await;
     }
-    match self.to[0].dereference(context).await? {
+    match self.receiver(context).await? {
       Either::Left(_) => Err(LemmyErrorType::NotFound.into()),
       Either::Right(c) => Ok(c),
     }
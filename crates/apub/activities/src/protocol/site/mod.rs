@@ -0,0 +1,14 @@
+pub mod move_site;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::site::move_site::MoveSite;
+  use lemmy_apub_objects::utils::test::test_parse_lemmy_item;
+  use lemmy_utils::error::LemmyResult;
+
+  #[test]
+  fn test_parse_lemmy_move_site() -> LemmyResult<()> {
+    test_parse_lemmy_item::<MoveSite>("../apub/assets/lemmy/activities/site/move_site.json")?;
+    Ok(())
+  }
+}
@@ -0,0 +1,19 @@
+use activitypub_federation::{fetch::object_id::ObjectId, kinds::activity::MoveType};
+use lemmy_apub_objects::objects::instance::ApubSite;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Announces that an instance moved to a new domain. Unlike `Move` of Mastodon accounts, this is
+/// sent by the site actor at the new domain, because the old domain may not be reachable anymore.
+/// Both actors have the same keypair, which proves that they belong to the same instance.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveSite {
+  pub(crate) actor: ObjectId<ApubSite>,
+  /// Id of the site actor at the old domain
+  pub(crate) object: Url,
+  pub(crate) target: ObjectId<ApubSite>,
+  #[serde(rename = "type")]
+  pub(crate) kind: MoveType,
+  pub(crate) id: Url,
+}
@@ -0,0 +1 @@
+pub mod move_site;
@@ -0,0 +1,103 @@
+use crate::{generate_activity_id, protocol::site::move_site::MoveSite, send_lemmy_activity};
+use activitypub_federation::{
+  config::Data,
+  kinds::activity::MoveType,
+  protocol::verification::{verify_domains_match, verify_urls_match},
+  traits::{Activity, Object},
+};
+use chrono::{TimeDelta, Utc};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::objects::instance::ApubSite;
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  instance::Instance,
+  instance_move::{InstanceMove, InstanceMoveInsertForm},
+  site::Site,
+};
+use lemmy_utils::error::{LemmyError, LemmyResult, UntranslatedError};
+use tracing::info;
+use url::Url;
+
+/// An instance can only announce one move in this time, so that it can't keep admins busy or
+/// flood the queue by moving back and forth.
+const MOVE_INTERVAL: TimeDelta = TimeDelta::days(30);
+
+impl MoveSite {
+  /// Tells all known instances that the local site moved away from the given actor id. Needs to
+  /// be called after the local domain was changed.
+  pub async fn send(
+    old_site_id: Url,
+    site: ApubSite,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<()> {
+    let move_site = MoveSite {
+      actor: site.id().clone().into(),
+      object: old_site_id,
+      target: site.id().clone().into(),
+      kind: MoveType::Move,
+      id: generate_activity_id(MoveType::Move, context)?,
+    };
+    let inboxes = ActivitySendTargets::to_all_instances();
+    send_lemmy_activity(context, move_site, &site, inboxes, false).await
+  }
+}
+
+#[async_trait::async_trait]
+impl Activity for MoveSite {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, _context: &Data<LemmyContext>) -> LemmyResult<()> {
+    verify_urls_match(self.actor.inner(), self.target.inner())?;
+    if verify_domains_match(self.actor.inner(), &self.object).is_ok() {
+      return Err(UntranslatedError::InvalidMove.into());
+    }
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let Some(old_site) = Site::read_from_apub_id(&mut context.pool(), &self.object.into()).await?
+    else {
+      // The instance wasn't known before, so there is nothing to move
+      return Ok(());
+    };
+    let new_site = self.actor.dereference(context).await?;
+    if old_site.public_key != new_site.public_key {
+      return Err(UntranslatedError::InvalidMove.into());
+    }
+
+    let since = Utc::now() - MOVE_INTERVAL;
+    if InstanceMove::moved_since(&mut context.pool(), old_site.instance_id, since).await? {
+      return Err(UntranslatedError::InvalidMove.into());
+    }
+
+    let old_domain = Instance::read(&mut context.pool(), old_site.instance_id)
+      .await?
+      .domain;
+    let new_domain = Instance::read(&mut context.pool(), new_site.instance_id)
+      .await?
+      .domain;
+    let old_origin = Url::from(old_site.ap_id).origin().ascii_serialization();
+    let new_origin = new_site.id().origin().ascii_serialization();
+    info!("Instance {old_domain} announced a move to {new_domain}, waiting for admin approval");
+
+    // The urls are only replaced once an admin approved the move
+    let form = InstanceMoveInsertForm::new(
+      old_site.instance_id,
+      old_domain,
+      new_domain,
+      old_origin,
+      new_origin,
+    );
+    InstanceMove::create(&mut context.pool(), &form).await?;
+    Ok(())
+  }
+}
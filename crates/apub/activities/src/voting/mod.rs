@@ -10,6 +10,7 @@ use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::{plugin_hook_after, plugin_hook_before},
+  stream::StreamChannel,
 };
 use lemmy_apub_objects::objects::{
   PostOrComment,
@@ -30,6 +31,7 @@ use lemmy_db_schema::{
 };
 use lemmy_diesel_utils::dburl::DbUrl;
 use lemmy_utils::error::LemmyResult;
+use tracing::warn;
 
 pub mod undo_vote;
 pub mod vote;
@@ -76,6 +78,10 @@ async fn vote_comment(
   like_form = plugin_hook_before("comment_before_vote", like_form).await?;
   let like = CommentActions::like(&mut context.pool(), &like_form).await?;
   plugin_hook_after("comment_after_vote", &like);
+  StreamChannel::comment_vote(comment.id, context)
+    .await
+    .inspect_err(|e| warn!("Failed to stream comment vote: {e}"))
+    .ok();
   Ok(())
 }
 
@@ -90,6 +96,10 @@ async fn vote_post(
   like_form = plugin_hook_before("post_before_vote", like_form).await?;
   let like = PostActions::like(&mut context.pool(), &like_form).await?;
   plugin_hook_after("post_after_vote", &like);
+  StreamChannel::post_vote(post.id, context)
+    .await
+    .inspect_err(|e| warn!("Failed to stream post vote: {e}"))
+    .ok();
   Ok(())
 }
 
@@ -100,6 +110,10 @@ async fn undo_vote_comment(
 ) -> LemmyResult<()> {
   let form = CommentLikeForm::new(comment.id, actor.id, None);
   CommentActions::like(&mut context.pool(), &form).await?;
+  StreamChannel::comment_vote(comment.id, context)
+    .await
+    .inspect_err(|e| warn!("Failed to stream comment vote: {e}"))
+    .ok();
   Ok(())
 }
 
@@ -110,5 +124,9 @@ async fn undo_vote_post(
 ) -> LemmyResult<()> {
   let form = PostLikeForm::new(post.id, actor.id, None);
   PostActions::like(&mut context.pool(), &form).await?;
+  StreamChannel::post_vote(post.id, context)
+    .await
+    .inspect_err(|e| warn!("Failed to stream post vote: {e}"))
+    .ok();
   Ok(())
 }
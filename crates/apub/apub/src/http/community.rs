@@ -6,7 +6,7 @@ use crate::{
     community_moderators::ApubCommunityModerators,
     community_outbox::ApubCommunityOutbox,
   },
-  http::{check_community_fetchable, get_instance_id},
+  http::{check_authorized_fetch, check_community_fetchable, get_instance_id},
 };
 use activitypub_federation::{
   actix_web::{response::create_http_response, signing_actor},
@@ -55,6 +55,7 @@ pub(crate) struct CommunityIsFollowerQuery {
 pub(crate) async fn get_apub_community_http(
   info: Path<CommunityPath>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, None, true)
@@ -62,7 +63,7 @@ pub(crate) async fn get_apub_community_http(
       .ok_or(LemmyErrorType::NotFound)?
       .into();
 
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
 
   community.http_response(&FEDERATION_CONTEXT, &context).await
 }
@@ -80,7 +81,7 @@ pub(crate) async fn get_apub_community_followers(
   if let Some(is_follower) = &query.is_follower {
     return check_is_follower(community, is_follower, context, request).await;
   }
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
   let followers = ApubCommunityFollower::read_local(&community.into(), &context).await?;
   Ok(create_http_response(followers, &FEDERATION_CONTEXT)?)
 }
@@ -139,13 +140,14 @@ pub(crate) async fn get_apub_community_outbox(
 pub(crate) async fn get_apub_community_moderators(
   info: Path<CommunityPath>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, None, false)
       .await?
       .ok_or(LemmyErrorType::NotFound)?
       .into();
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
   let moderators = ApubCommunityModerators::read_local(&community, &context).await?;
   Ok(create_http_response(moderators, &FEDERATION_CONTEXT)?)
 }
@@ -174,7 +176,9 @@ pub(crate) struct MultiCommunityQuery {
 pub(crate) async fn get_apub_person_multi_community(
   query: Path<MultiCommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_authorized_fetch(&request, &context).await?;
   let multi: ApubMultiCommunity =
     MultiCommunity::read_from_name(&mut context.pool(), &query.multi_name, None, false)
       .await?
@@ -187,7 +191,9 @@ pub(crate) async fn get_apub_person_multi_community(
 pub(crate) async fn get_apub_person_multi_community_follows(
   query: Path<MultiCommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_authorized_fetch(&request, &context).await?;
   let multi = MultiCommunity::read_from_name(&mut context.pool(), &query.multi_name, None, false)
     .await?
     .ok_or(LemmyErrorType::NotFound)?
@@ -207,6 +213,7 @@ pub(crate) struct CommunityTagPath {
 pub(crate) async fn get_apub_community_tag_http(
   info: Path<CommunityTagPath>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, None, true)
@@ -214,7 +221,7 @@ pub(crate) async fn get_apub_community_tag_http(
       .ok_or(LemmyErrorType::NotFound)?
       .into();
 
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
 
   let tag = CommunityTag::read_for_community(&mut context.pool(), community.id)
     .await?
@@ -236,6 +243,7 @@ pub(crate) mod tests {
   use lemmy_db_schema::{
     source::{
       community::CommunityInsertForm,
+      local_site::{LocalSite, LocalSiteUpdateForm},
       person::{Person, PersonInsertForm},
       post::{Post, PostInsertForm},
     },
@@ -289,11 +297,12 @@ pub(crate) mod tests {
     let query = CommunityPath {
       community_name: "asd".to_string(),
     };
-    let res = get_apub_community_http(query.into(), context.clone()).await;
+    let res = get_apub_community_http(query.into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
 
     // fetch valid community
-    let res = get_apub_community_http(path.clone().into(), context.clone()).await?;
+    let res =
+      get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await?;
     assert_eq!(200, res.status());
     let res_group: Group = decode_response(res).await?;
     let community: ApubCommunity = community.into();
@@ -308,7 +317,8 @@ pub(crate) mod tests {
       get_apub_community_followers(path.clone().into(), query, context.clone(), request.clone())
         .await?;
     assert_eq!(200, res.status());
-    let res = get_apub_community_moderators(path.clone().into(), context.clone()).await?;
+    let res =
+      get_apub_community_moderators(path.clone().into(), context.clone(), request.clone()).await?;
     assert_eq!(200, res.status());
     let res = get_apub_community_outbox(path, context.clone(), request).await?;
     assert_eq!(200, res.status());
@@ -325,7 +335,8 @@ pub(crate) mod tests {
     let request = TestRequest::default().to_http_request();
 
     // should return tombstone
-    let res = get_apub_community_http(path.clone().into(), context.clone()).await?;
+    let res =
+      get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await?;
     assert_eq!(410, res.status());
     let res_tombstone = decode_response::<Tombstone>(res).await;
     assert!(res_tombstone.is_ok());
@@ -338,7 +349,8 @@ pub(crate) mod tests {
       get_apub_community_followers(path.clone().into(), query, context.clone(), request.clone())
         .await;
     assert!(res.is_err());
-    let res = get_apub_community_moderators(path.clone().into(), context.clone()).await;
+    let res =
+      get_apub_community_moderators(path.clone().into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
     let res = get_apub_community_outbox(path, context.clone(), request).await;
     assert!(res.is_err());
@@ -354,7 +366,7 @@ pub(crate) mod tests {
     let (data, _, path) = init(false, CommunityVisibility::LocalOnlyPrivate, &context).await?;
     let request = TestRequest::default().to_http_request();
 
-    let res = get_apub_community_http(path.clone().into(), context.clone()).await;
+    let res = get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
     let res =
       get_apub_community_featured(path.clone().into(), context.clone(), request.clone()).await;
@@ -364,7 +376,34 @@ pub(crate) mod tests {
       get_apub_community_followers(path.clone().into(), query, context.clone(), request.clone())
         .await;
     assert!(res.is_err());
-    let res = get_apub_community_moderators(path.clone().into(), context.clone()).await;
+    let res =
+      get_apub_community_moderators(path.clone().into(), context.clone(), request.clone()).await;
+    assert!(res.is_err());
+    let res = get_apub_community_outbox(path, context.clone(), request).await;
+    assert!(res.is_err());
+
+    data.delete(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_get_community_authorized_fetch() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let (data, _, path) = init(false, CommunityVisibility::Public, &context).await?;
+    let request = TestRequest::default().to_http_request();
+
+    let form = LocalSiteUpdateForm {
+      authorized_fetch: Some(true),
+      ..Default::default()
+    };
+    LocalSite::update(&mut context.pool(), &form).await?;
+
+    // unsigned requests are rejected
+    let res = get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await;
+    assert!(res.is_err());
+    let res =
+      get_apub_community_featured(path.clone().into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
     let res = get_apub_community_outbox(path, context.clone(), request).await;
     assert!(res.is_err());
@@ -15,19 +15,31 @@ use actix_web::{
 use either::Either;
 use lemmy_api_utils::{context::LemmyContext, plugins::plugin_hook_after};
 use lemmy_apub_activities::activity_lists::SharedInboxActivities;
-use lemmy_apub_objects::objects::{SiteOrMultiOrCommunityOrUser, UserOrCommunity};
+use lemmy_apub_objects::objects::SiteOrMultiOrCommunityOrUser;
 use lemmy_db_schema::source::{
   activity::{ReceivedActivity, SentActivity},
   community::Community,
 };
 use lemmy_db_schema_file::{InstanceId, enums::CommunityVisibility};
 use lemmy_db_views_community_follower_approval::PendingFollowerView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::dburl::DbUrl;
 use lemmy_utils::{
   FEDERATION_CONTEXT,
-  error::{LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
+  error::{
+    LemmyError,
+    LemmyErrorExt,
+    LemmyErrorExt2,
+    LemmyErrorType,
+    LemmyResult,
+    UntranslatedError,
+  },
 };
 use serde::Deserialize;
-use std::time::Duration;
+use std::{
+  sync::{Arc, OnceLock},
+  time::Duration,
+};
 use tokio::time::timeout;
 use tracing::debug;
 use url::Url;
@@ -46,32 +58,106 @@ pub async fn shared_inbox(
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> LemmyResult<HttpResponse> {
-  let receive_fut =
-    receive_activity_with_hook::<SharedInboxActivities, UserOrCommunity, LemmyContext>(
-      request, body, Dummy, &data,
-    );
+  let received_id = Arc::new(OnceLock::new());
+  let hook = StoreReceivedActivity(received_id.clone());
+  let receive_fut = receive_activity_with_hook::<
+    SharedInboxActivities,
+    SiteOrMultiOrCommunityOrUser,
+    LemmyContext,
+  >(request, body, hook, &data);
   // Set a timeout shorter than `REQWEST_TIMEOUT` for processing incoming activities. This is to
   // avoid taking a long time to process an incoming activity when a required data fetch times out.
   // In this case our own instance would timeout and be marked as dead by the sender. Better to
   // consider the activity broken and move on.
-  timeout(INCOMING_ACTIVITY_TIMEOUT, receive_fut)
+  let res = timeout(INCOMING_ACTIVITY_TIMEOUT, receive_fut)
     .await
-    .with_lemmy_type(UntranslatedError::InboxTimeout.into())?
+    .with_lemmy_type(UntranslatedError::InboxTimeout.into())
+    .and_then(|res| res);
+  match res {
+    // Redelivered activities are acknowledged, so that the sender stops retrying them.
+    Err(e) if e.error_type == LemmyErrorType::from(UntranslatedError::DuplicateActivity) => {
+      Ok(HttpResponse::Ok().finish())
+    }
+    // Rate limited activities can be retried by the sender after a delay.
+    Err(e) if e.error_type == LemmyErrorType::TooManyRequests => {
+      Ok(HttpResponse::TooManyRequests().finish())
+    }
+    Err(e) => {
+      // Allow the sender to retry the activity later, but only if processing failed before
+      // anything was written. Otherwise a retry could create duplicate entries.
+      if failed_before_write(&e)
+        && let Some(id) = received_id.get()
+      {
+        ReceivedActivity::delete(&mut data.pool(), id).await?;
+      }
+      Err(e)
+    }
+    Ok(res) => Ok(res),
+  }
 }
 
-struct Dummy;
+/// Returns true for errors of parsing and verification checks, which activity handlers run before
+/// making any changes. Other errors like timeouts may happen after some data was already written.
+fn failed_before_write(e: &LemmyError) -> bool {
+  use UntranslatedError::*;
+  if e.cause.downcast_ref::<serde_json::Error>().is_some() {
+    return true;
+  }
+  match &e.error_type {
+    LemmyErrorType::NotAModerator
+    | LemmyErrorType::NotAModOrAdmin
+    | LemmyErrorType::SiteBan
+    | LemmyErrorType::PersonIsBannedFromCommunity => true,
+    LemmyErrorType::UntranslatedError(Some(error)) => matches!(
+      error,
+      InvalidCommunity
+        | CannotCreatePostOrCommentInDeletedOrRemovedCommunity
+        | PostIsLocked
+        | PersonIsBannedFromSite(_)
+        | InvalidVoteValue
+        | ObjectIsNotPublic
+        | ObjectIsNotPrivate
+        | DomainBlocked(_)
+        | DomainNotInAllowList(_)
+        | FederationDisabled
+        | FederationDisabledByStrictAllowList
+        | CommunityHasNoFollowers(_)
+    ),
+    _ => false,
+  }
+}
 
-impl ReceiveActivityHook<SharedInboxActivities, UserOrCommunity, LemmyContext> for Dummy {
+/// Stores ids of received activities, and rejects those which were already received.
+struct StoreReceivedActivity(Arc<OnceLock<DbUrl>>);
+
+impl ReceiveActivityHook<SharedInboxActivities, SiteOrMultiOrCommunityOrUser, LemmyContext>
+  for StoreReceivedActivity
+{
   async fn hook(
     self,
     activity: &SharedInboxActivities,
-    _actor: &UserOrCommunity,
+    actor: &SiteOrMultiOrCommunityOrUser,
     context: &Data<LemmyContext>,
   ) -> LemmyResult<()> {
+    // Rate limit by the domain of the actor, which is only known after the signature was
+    // verified. Otherwise a sender could get other instances throttled.
+    let domain = actor
+      .id()
+      .domain()
+      .ok_or(UntranslatedError::UrlWithoutDomain)?;
+    let max_requests = context.settings().federation.inbox_rate_limit;
+    if !context.rate_limit_cell().check_inbox(domain, max_requests) {
+      debug!("Rate limited activity {} from {domain}", activity.id());
+      return Err(LemmyErrorType::TooManyRequests.into());
+    }
+
     // Store received activities in the database. This ensures that the same activity doesn't get
-    // received and processed more than once, which would be a waste of resources.
+    // received and processed more than once, which would be a waste of resources and could cause
+    // duplicate votes, comments or modlog entries.
     debug!("Received activity {}", activity.id().to_string());
-    ReceivedActivity::create(&mut context.pool(), &activity.id().clone().into()).await?;
+    let id: DbUrl = activity.id().clone().into();
+    ReceivedActivity::create(&mut context.pool(), &id).await?;
+    self.0.set(id).ok();
 
     // This could also take the actor as param, but lifetimes and serde derives are tricky.
     // It is really a before hook, but doesnt allow modifying the data. It could use a
@@ -94,7 +180,9 @@ struct ActivityQuery {
 async fn get_activity(
   info: web::Path<ActivityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_authorized_fetch(&request, &context).await?;
   let settings = context.settings();
   let activity_id = Url::parse(&format!(
     "{}/activities/{}/{}",
@@ -113,12 +201,31 @@ async fn get_activity(
   }
 }
 
+/// With authorized fetch enabled, objects can only be fetched with requests which are signed by a
+/// remote actor. The site actor is exempt from this, as it is needed to verify the signatures.
+async fn check_authorized_fetch(
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  if local_site.authorized_fetch {
+    signing_actor::<SiteOrMultiOrCommunityOrUser>(request, None, context)
+      .await
+      .with_lemmy_type(UntranslatedError::SignatureRequired.into())?;
+  }
+  Ok(())
+}
+
 /// Ensure that the community is public and not removed/deleted.
-fn check_community_fetchable(community: &Community) -> LemmyResult<()> {
+async fn check_community_fetchable(
+  community: &Community,
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
   if !community.visibility.can_federate() {
     return Err(LemmyErrorType::NotFound.into());
   }
-  Ok(())
+  check_authorized_fetch(request, context).await
 }
 
 /// Check if posts or comments in the community are allowed to be fetched
@@ -129,7 +236,7 @@ async fn check_community_content_fetchable(
 ) -> LemmyResult<()> {
   use CommunityVisibility::*;
   match community.visibility {
-    Public | Unlisted => Ok(()),
+    Public | Unlisted => check_authorized_fetch(request, context).await,
     Private => {
       let signing_actor =
         signing_actor::<SiteOrMultiOrCommunityOrUser>(request, None, context).await?;
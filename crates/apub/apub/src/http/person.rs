@@ -1,9 +1,23 @@
-use crate::protocol::collections::url_collection::UrlCollection;
-use activitypub_federation::{config::Data, traits::Object};
-use actix_web::{HttpResponse, web::Path};
-use lemmy_api_utils::{context::LemmyContext, utils::generate_outbox_url};
+use crate::{
+  http::check_authorized_fetch,
+  protocol::collections::{group_followers::GroupFollowers, url_collection::UrlCollection},
+};
+use activitypub_federation::{
+  actix_web::response::create_http_response,
+  config::Data,
+  kinds::collection::CollectionType,
+  traits::Object,
+};
+use actix_web::{HttpRequest, HttpResponse, web::Path};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{generate_followers_url, generate_outbox_url},
+};
 use lemmy_apub_objects::objects::person::ApubPerson;
-use lemmy_db_schema::{source::person::Person, traits::ApubActor};
+use lemmy_db_schema::{
+  source::person::{Person, PersonActions},
+  traits::ApubActor,
+};
 use lemmy_utils::{
   FEDERATION_CONTEXT,
   error::{LemmyErrorType, LemmyResult},
@@ -19,7 +33,9 @@ pub(crate) struct PersonQuery {
 pub(crate) async fn get_apub_person_http(
   info: Path<PersonQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_authorized_fetch(&request, &context).await?;
   let user_name = info.into_inner().user_name;
   // This needs to be able to read deleted persons, so that it can send tombstones
   let person: ApubPerson = Person::read_from_name(&mut context.pool(), &user_name, None, true)
@@ -33,10 +49,32 @@ pub(crate) async fn get_apub_person_http(
 pub(crate) async fn get_apub_person_outbox(
   info: Path<PersonQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_authorized_fetch(&request, &context).await?;
   let person = Person::read_from_name(&mut context.pool(), &info.user_name, None, false)
     .await?
     .ok_or(LemmyErrorType::NotFound)?;
   let outbox_id = generate_outbox_url(&person.ap_id)?.to_string();
   UrlCollection::new_empty_response(outbox_id)
 }
+
+/// Only returns the number of followers, same as for communities. This is needed so that users
+/// can be followed from platforms like Mastodon.
+pub(crate) async fn get_apub_person_followers(
+  info: Path<PersonQuery>,
+  context: Data<LemmyContext>,
+  request: HttpRequest,
+) -> LemmyResult<HttpResponse> {
+  check_authorized_fetch(&request, &context).await?;
+  let person = Person::read_from_name(&mut context.pool(), &info.user_name, None, false)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+  let followers = GroupFollowers {
+    id: generate_followers_url(&person.ap_id)?.into(),
+    r#type: CollectionType::Collection,
+    total_items: PersonActions::count_followers(&mut context.pool(), person.id).await?,
+    items: vec![],
+  };
+  Ok(create_http_response(followers, &FEDERATION_CONTEXT)?)
+}
@@ -11,7 +11,7 @@ use crate::http::{
     get_apub_person_multi_community_follows,
   },
   get_activity,
-  person::{get_apub_person_http, get_apub_person_outbox},
+  person::{get_apub_person_followers, get_apub_person_http, get_apub_person_outbox},
   post::{get_apub_post, get_apub_post_context},
   shared_inbox,
   site::{get_apub_site_http, get_apub_site_outbox},
@@ -21,6 +21,7 @@ use actix_web::{
   http::{Method, header},
   web,
 };
+use lemmy_utils::settings::SETTINGS;
 
 pub fn config(cfg: &mut web::ServiceConfig) {
   cfg
@@ -55,6 +56,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
       "/u/{user_name}/outbox",
       web::get().to(get_apub_person_outbox),
     )
+    .route(
+      "/u/{user_name}/followers",
+      web::get().to(get_apub_person_followers),
+    )
     .route(
       "/m/{multi_name}",
       web::get().to(get_apub_person_multi_community),
@@ -75,9 +80,13 @@ pub fn config(cfg: &mut web::ServiceConfig) {
     )
     .route("/activities/{type_}/{id}", web::get().to(get_activity));
 
+  let max_payload_size = usize::try_from(SETTINGS.federation.inbox_max_payload_kb)
+    .unwrap_or(usize::MAX)
+    .saturating_mul(1024);
   cfg.service(
     web::scope("")
       .guard(InboxRequestGuard)
+      .app_data(web::PayloadConfig::new(max_payload_size))
       .route("/inbox", web::post().to(shared_inbox)),
   );
 }
@@ -1,6 +1,7 @@
 use crate::{
-  protocol::note::Note,
+  protocol::{note::Note, tags::Hashtag},
   utils::{
+    emojis::{emoji_image_urls, markdown_emojis_to_shortcodes, shortcodes_to_markdown_emojis},
     functions::{
       append_attachments_to_comment,
       check_apub_id_valid_with_strictness,
@@ -28,7 +29,9 @@ use chrono::{DateTime, Utc};
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::{plugin_hook_after, plugin_hook_before},
+  search::{SearchIndexQueue, SearchIndexTask},
   utils::{
+    cache_remote_emojis,
     check_comment_depth,
     check_is_mod_or_admin,
     get_url_blocklist,
@@ -126,21 +129,27 @@ impl Object for ApubComment {
       .ok();
     let maa =
       collect_non_local_mentions(Some(&self.content), parent_creator, &community, context).await?;
+    let (content, emojis) = markdown_emojis_to_shortcodes(&self.content);
+    let mut tag = maa.mentions;
+    tag.extend(emojis);
+    tag.extend(Hashtag::from_text(&self.content, context)?);
 
+    let content = markdown_to_html(&content);
     let note = Note {
       r#type: NoteType::Note,
       id: self.ap_id.clone().into(),
       attributed_to: creator.ap_id.into(),
       to: generate_to(&community)?,
       cc: maa.ccs,
-      content: markdown_to_html(&self.content),
+      content: content.clone(),
       media_type: Some(MediaTypeMarkdownOrHtml::Html),
       source: Some(Source::new(self.content.clone())),
       in_reply_to,
       published: Some(self.published_at),
       updated: self.updated_at,
-      tag: maa.mentions,
+      tag,
       distinguished: Some(self.distinguished),
+      content_map: language.as_ref().and_then(|l| l.content_map(&content)),
       language,
       audience: Some(community.ap_id.into()),
       attachment: vec![],
@@ -214,6 +223,7 @@ impl Object for ApubComment {
     let (post, parent_comment) = note.get_parents(context).await?;
 
     let content = read_from_string_or_source(&note.content, &note.media_type, &note.source);
+    let content = shortcodes_to_markdown_emojis(content, &note.source, &note.tag);
 
     let slur_regex = slur_regex(context).await?;
     let url_blocklist = get_url_blocklist(context).await?;
@@ -223,9 +233,16 @@ impl Object for ApubComment {
     let content =
       process_markdown(&content, &slur_regex, &url_blocklist, &local_site, context).await?;
     let content = markdown_rewrite_remote_links(content, context).await;
+    cache_remote_emojis(emoji_image_urls(&note.tag), &local_site, context);
     let language_id = Some(
-      LanguageTag::to_language_id_single(note.language.unwrap_or_default(), &mut context.pool())
-        .await?,
+      LanguageTag::to_language_id_single(
+        note
+          .language
+          .or_else(|| LanguageTag::from_content_map(&note.content_map))
+          .unwrap_or_default(),
+        &mut context.pool(),
+      )
+      .await?,
     );
 
     let mut form = CommentInsertForm {
@@ -254,6 +271,7 @@ impl Object for ApubComment {
     )
     .await?;
     plugin_hook_after("federated_comment_after_receive", &comment);
+    SearchIndexQueue::submit(SearchIndexTask::comment(&comment), context)?;
     Ok(comment.into())
   }
 }
@@ -21,6 +21,8 @@ use activitypub_federation::{
 use chrono::{DateTime, Utc};
 use lemmy_api_utils::{
   context::LemmyContext,
+  request::uncache_replaced_images,
+  search::{SearchIndexQueue, SearchIndexTask},
   utils::{
     check_nsfw_allowed,
     generate_featured_url,
@@ -238,8 +240,38 @@ impl Object for ApubCommunity {
     let languages =
       LanguageTag::to_language_id_multiple(group.language.clone(), &mut context.pool()).await?;
 
+    let old_community =
+      Community::read_from_apub_id(&mut context.pool(), &group.id.clone().into()).await?;
     let timestamp = group.updated.or(group.published).unwrap_or_else(Utc::now);
-    let community = Community::insert_apub(&mut context.pool(), timestamp, &form).await?;
+    let mut community = Community::insert_apub(&mut context.pool(), timestamp, &form).await?;
+
+    // The upsert skips empty fields, so explicitly clear those which were removed by the remote
+    // mods. Otherwise the local copy keeps showing an outdated sidebar or icon.
+    let cleared = [
+      community.sidebar.is_some() && form.sidebar.is_none(),
+      community.summary.is_some() && form.summary.is_none(),
+      community.icon.is_some() && form.icon.is_none(),
+      community.banner.is_some() && form.banner.is_none(),
+    ];
+    if cleared.contains(&true) {
+      let clear_form = CommunityUpdateForm {
+        sidebar: Some(form.sidebar.clone()),
+        summary: Some(form.summary.clone()),
+        icon: Some(form.icon.clone()),
+        banner: Some(form.banner.clone()),
+        ..Default::default()
+      };
+      community = Community::update(&mut context.pool(), community.id, &clear_form).await?;
+    }
+    if let Some(old) = old_community {
+      uncache_replaced_images(
+        vec![
+          (old.icon, community.icon.clone()),
+          (old.banner, community.banner.clone()),
+        ],
+        context,
+      );
+    }
     CommunityLanguage::update(&mut context.pool(), languages, community.id).await?;
 
     let new_tags = group
@@ -250,6 +282,7 @@ impl Object for ApubCommunity {
     let existing_tags = CommunityTag::read_for_community(&mut context.pool(), community.id).await?;
     CommunityTag::update_many(&mut context.pool(), new_tags, existing_tags).await?;
 
+    SearchIndexQueue::submit(SearchIndexTask::community(&community), context)?;
     let community: ApubCommunity = community.into();
 
     // These collections are not necessary for Lemmy to work, so ignore errors. Reset request count
@@ -1,6 +1,6 @@
 use crate::{
   objects::instance::fetch_instance_actor_for_object,
-  protocol::person::{Person, UserTypes},
+  protocol::person::{EncryptionKey, Person, UserTypes},
   utils::{
     functions::{
       GetActorType,
@@ -19,7 +19,9 @@ use activitypub_federation::{
 use chrono::{DateTime, Utc};
 use lemmy_api_utils::{
   context::LemmyContext,
+  request::uncache_replaced_images,
   utils::{
+    generate_followers_url,
     generate_outbox_url,
     get_url_blocklist,
     process_markdown_opt,
@@ -28,15 +30,23 @@ use lemmy_api_utils::{
   },
 };
 use lemmy_db_schema::{
-  source::person::{Person as DbPerson, PersonInsertForm, PersonUpdateForm},
+  source::{
+    person::{Person as DbPerson, PersonInsertForm, PersonUpdateForm},
+    person_encryption_key::{PersonEncryptionKey, PersonEncryptionKeyInsertForm},
+  },
   traits::ApubActor,
 };
 use lemmy_db_schema_file::enums::ActorType;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{sensitive::SensitiveString, traits::Crud};
 use lemmy_utils::{
+  MAX_ENCRYPTION_KEYS,
   error::{LemmyError, LemmyResult},
-  utils::{markdown::markdown_to_html, slurs::remove_slurs},
+  utils::{
+    markdown::markdown_to_html,
+    slurs::remove_slurs,
+    validation::{is_valid_display_name, is_valid_encryption_key},
+  },
 };
 use std::ops::Deref;
 use url::Url;
@@ -95,7 +105,15 @@ impl Object for ApubPerson {
     self.deleted
   }
 
-  async fn into_json(self, _context: &Data<Self::DataType>) -> LemmyResult<Person> {
+  async fn into_json(self, context: &Data<Self::DataType>) -> LemmyResult<Person> {
+    let encryption_keys = PersonEncryptionKey::list_for_person(&mut context.pool(), self.id)
+      .await?
+      .into_iter()
+      .map(|k| EncryptionKey {
+        device_name: k.device_name,
+        public_key: k.public_key,
+      })
+      .collect();
     let kind = if self.bot_account {
       UserTypes::Service
     } else {
@@ -114,10 +132,12 @@ impl Object for ApubPerson {
       matrix_user_id: self.matrix_user_id.clone(),
       published: Some(self.published_at),
       outbox: generate_outbox_url(&self.ap_id)?.into(),
+      followers: Some(generate_followers_url(&self.ap_id)?.into()),
       endpoints: None,
       public_key: self.public_key(),
       updated: self.updated_at,
       inbox: self.inbox_url.clone().into(),
+      encryption_keys,
     };
     Ok(person)
   }
@@ -149,6 +169,9 @@ impl Object for ApubPerson {
     let banner =
       proxy_image_link_opt_apub(person.image.map(|i| i.url), &local_site, context).await?;
     let display_name = person.name.map(|s| remove_slurs(&s, &slur_regex));
+    let encryption_keys = person.encryption_keys;
+    let old_person =
+      DbPerson::read_from_apub_id(&mut context.pool(), &person.id.clone().into()).await?;
 
     let person_form = PersonInsertForm {
       name: person.preferred_username,
@@ -161,7 +184,10 @@ impl Object for ApubPerson {
       ap_id: Some(person.id.into()),
       bio,
       local: Some(false),
-      bot_account: Some(person.kind == UserTypes::Service),
+      bot_account: Some(matches!(
+        person.kind,
+        UserTypes::Service | UserTypes::Application
+      )),
       private_key: None,
       public_key: person.public_key.public_key_pem,
       last_refreshed_at: Some(Utc::now()),
@@ -176,6 +202,30 @@ impl Object for ApubPerson {
       instance_id,
     };
     let person = DbPerson::upsert(&mut context.pool(), &person_form).await?;
+    if let Some(old) = old_person {
+      uncache_replaced_images(
+        vec![
+          (old.avatar, person.avatar.clone()),
+          (old.banner, person.banner.clone()),
+        ],
+        context,
+      );
+    }
+
+    let encryption_keys = encryption_keys
+      .into_iter()
+      .filter(|k| {
+        is_valid_display_name(&k.device_name).is_ok()
+          && is_valid_encryption_key(&k.public_key).is_ok()
+      })
+      .take(MAX_ENCRYPTION_KEYS)
+      .map(|k| {
+        let device_name = remove_slurs(&k.device_name, &slur_regex);
+        PersonEncryptionKeyInsertForm::new(person.id, device_name, k.public_key)
+      })
+      .collect();
+    PersonEncryptionKey::replace_for_person(&mut context.pool(), person.id, encryption_keys)
+      .await?;
 
     Ok(person.into())
   }
@@ -227,6 +277,9 @@ pub(crate) mod tests {
     assert_eq!(person.display_name, Some("Jean-Luc Picard".to_string()));
     assert!(!person.local);
     assert_eq!(person.bio.as_ref().map(std::string::String::len), Some(39));
+    let encryption_keys =
+      PersonEncryptionKey::list_for_person(&mut context.pool(), person.id).await?;
+    assert_eq!(1, encryption_keys.len());
 
     test_data.delete(&mut context.pool()).await?;
     Instance::delete_all(&mut context.pool()).await?;
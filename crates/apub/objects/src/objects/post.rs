@@ -4,6 +4,7 @@ use crate::{
     tags::{ApubCommunityTag, ApubTag, Hashtag, HashtagType},
   },
   utils::{
+    emojis::{emoji_image_urls, markdown_emojis_to_shortcodes, shortcodes_to_markdown_emojis},
     functions::{
       check_apub_id_valid_with_strictness,
       context_url,
@@ -15,6 +16,7 @@ use crate::{
     markdown_links::{markdown_rewrite_remote_links_opt, to_local_url},
     mentions::collect_non_local_mentions,
     protocol::{AttributedTo, ImageObject, InCommunity, LanguageTag, Source},
+    replies::fetch_post_replies,
   },
 };
 use activitypub_federation::{
@@ -32,11 +34,14 @@ use lemmy_api_utils::{
   context::LemmyContext,
   plugins::{plugin_hook_after, plugin_hook_before},
   request::generate_post_link_metadata,
+  search::{SearchIndexQueue, SearchIndexTask},
   utils::{
+    cache_remote_emojis,
     check_nsfw_allowed,
     get_url_blocklist,
     process_markdown_opt,
     slur_regex,
+    update_post_hashtags,
     update_post_tags,
   },
 };
@@ -154,9 +159,19 @@ impl Object for ApubPost {
     };
     tags.push(ApubTag::Hashtag(hashtag));
 
+    // Add hashtags which are used in title or body
+    let text = format!("{} {}", self.name, self.body.as_deref().unwrap_or_default());
+    tags.extend(Hashtag::from_text(&text, context)?);
+
     let maa = collect_non_local_mentions(self.body.as_deref(), None, &community, context).await?;
     tags.extend(maa.mentions);
 
+    let content = self.body.as_deref().map(|b| {
+      let (body, emojis) = markdown_emojis_to_shortcodes(b);
+      tags.extend(emojis);
+      markdown_to_html(&body)
+    });
+
     let page = Page {
       kind: PageType::Page,
       id: self.ap_id.clone().into(),
@@ -164,12 +179,16 @@ impl Object for ApubPost {
       to: generate_to(&community)?,
       cc: maa.ccs,
       name: Some(self.name.clone()),
-      content: self.body.as_ref().map(|b| markdown_to_html(b)),
       media_type: Some(MediaTypeMarkdownOrHtml::Html),
       source: self.body.clone().map(Source::new),
       attachment,
       image: self.thumbnail_url.clone().map(ImageObject::new),
       sensitive: Some(self.nsfw),
+      content_map: language
+        .as_ref()
+        .zip(content.as_ref())
+        .and_then(|(l, c)| l.content_map(c)),
+      content,
       language,
       published: Some(self.published_at),
       updated: self.updated_at,
@@ -177,6 +196,14 @@ impl Object for ApubPost {
       in_reply_to: None,
       tag: tags,
       context: Some(context_url(&self.ap_id)),
+      replies: None,
+      one_of: vec![],
+      any_of: vec![],
+      end_time: None,
+      voters_count: None,
+      duration: None,
+      uuid: None,
+      icon: vec![],
     };
     Ok(page)
   }
@@ -276,19 +303,43 @@ impl Object for ApubPost {
 
     let alt_text = first_attachment.cloned().and_then(Attachment::alt_text);
 
-    let body = read_from_string_or_source_opt(&page.content, &page.media_type, &page.source);
+    let mut body = read_from_string_or_source_opt(&page.content, &page.media_type, &page.source)
+      .map(|b| shortcodes_to_markdown_emojis(b, &page.source, &page.tag));
+    // Lemmy doesn't support polls, so at least show the options and results in the post body
+    if let Some(summary) = page.question_summary() {
+      body = Some(match body {
+        Some(b) => format!("{b}\n\n{summary}"),
+        None => summary,
+      });
+    }
     let body =
       process_markdown_opt(&body, &slur_regex, &url_blocklist, &local_site, context).await?;
     let body = markdown_rewrite_remote_links_opt(body, context).await;
+    cache_remote_emojis(emoji_image_urls(&page.tag), &local_site, context);
     let language_id = Some(
       LanguageTag::to_language_id_single(
-        page.language.clone().unwrap_or_default(),
+        page
+          .language
+          .clone()
+          .or_else(|| LanguageTag::from_content_map(&page.content_map))
+          .unwrap_or_default(),
         &mut context.pool(),
       )
       .await?,
     );
 
+    let (embed_video_url, embed_video_duration, thumbnail) = if page.kind == PageType::Video {
+      (
+        page.video_embed_url(),
+        page.video_duration(),
+        page.video_thumbnail(),
+      )
+    } else {
+      (None, None, None)
+    };
+
     let orig_post = Post::read_from_apub_id(&mut context.pool(), page.id.clone().into()).await;
+    let is_new_post = orig_post.as_ref().is_ok_and(Option::is_none);
     let mut form = PostInsertForm {
       url: url.map(Into::into),
       body,
@@ -301,6 +352,8 @@ impl Object for ApubPost {
       // May be a local post which is updated by remote mod.
       local: Some(page.id.is_local(context)),
       language_id,
+      embed_video_url: embed_video_url.clone().map(Into::into),
+      embed_video_duration,
       ..PostInsertForm::new(name, creator.id, community.id)
     };
     form = plugin_hook_before("federated_post_before_receive", form).await?;
@@ -310,6 +363,22 @@ impl Object for ApubPost {
     plugin_hook_after("federated_post_after_receive", &post);
 
     update_apub_post_tags(&page, &post, context).await?;
+    let hashtags = page
+      .tag
+      .iter()
+      .filter_map(|t| t.hashtag_name(page.id.inner()))
+      .collect();
+    update_post_hashtags(&post, hashtags, context).await?;
+
+    // Fetch existing replies in background, so that discussions from other platforms are visible
+    if is_new_post
+      && !post.local
+      && context.settings().federation.fetch_replies_per_post > 0
+      && let Some(replies) = page.replies.clone()
+    {
+      let context_ = context.reset_request_count();
+      spawn_try_task(async move { fetch_post_replies(replies, &context_).await });
+    }
 
     let post_ = post.clone();
     let context_ = context.clone();
@@ -319,11 +388,22 @@ impl Object for ApubPost {
     if !no_generate_metadata {
       // Generates a post thumbnail in background task, because some sites can be very slow to
       // respond.
-      spawn_try_task(
-        async move { generate_post_link_metadata(post_, None, |_| None, context_).await },
-      );
+      spawn_try_task(async move {
+        generate_post_link_metadata(post_.clone(), thumbnail, |_| None, context_.clone()).await?;
+        // Metadata generation overwrites the embed url with data from the video page, so make
+        // sure that the PeerTube player is still used.
+        if let Some(embed_video_url) = embed_video_url {
+          let form = PostUpdateForm {
+            embed_video_url: Some(Some(embed_video_url.into())),
+            ..Default::default()
+          };
+          Post::update(&mut context_.pool(), post_.id, &form).await?;
+        }
+        Ok(())
+      });
     }
 
+    SearchIndexQueue::submit(SearchIndexTask::post(&post), context)?;
     Ok(post.into())
   }
 }
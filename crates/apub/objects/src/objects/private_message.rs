@@ -1,9 +1,10 @@
 use crate::{
-  protocol::private_message::{PrivateMessage, PrivateMessageType},
+  protocol::private_message::{EncryptedContent, PrivateMessage, PrivateMessageType},
   utils::{
     functions::{check_apub_id_valid_with_strictness, read_from_string_or_source},
     markdown_links::markdown_rewrite_remote_links,
     protocol::Source,
+    quirks::InstanceQuirks,
   },
 };
 use activitypub_federation::{
@@ -23,7 +24,7 @@ use lemmy_api_utils::{
 };
 use lemmy_db_schema::{
   source::{
-    instance::{Instance, InstanceActions},
+    instance::InstanceActions,
     person::{Person, PersonActions},
     private_message::{PrivateMessage as DbPrivateMessage, PrivateMessageInsertForm},
   },
@@ -35,12 +36,15 @@ use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorType, LemmyResult},
-  utils::markdown::markdown_to_html,
+  utils::{markdown::markdown_to_html, validation::is_valid_encrypted_private_message},
 };
-use semver::{Version, VersionReq};
 use std::ops::Deref;
 use url::Url;
 
+/// Sent as content of encrypted messages, for software which doesn't support them.
+const ENCRYPTED_CONTENT_PLACEHOLDER: &str =
+  "This message is end-to-end encrypted, and can't be displayed here.";
+
 #[derive(Clone, Debug)]
 pub struct ApubPrivateMessage(pub DbPrivateMessage);
 
@@ -94,27 +98,35 @@ impl Object for ApubPrivateMessage {
     let recipient_id = self.recipient_id;
     let recipient = Person::read(&mut context.pool(), recipient_id).await?;
 
-    let instance = Instance::read(&mut context.pool(), recipient.instance_id).await?;
-    let mut kind = PrivateMessageType::Note;
-
+    let quirks = InstanceQuirks::read(&mut context.pool(), recipient.instance_id).await?;
     // Deprecated: For Lemmy versions before 0.20, send private messages with old type
-    if let (Some(software), Some(version)) = (instance.software, &instance.version) {
-      let req = VersionReq::parse("<0.20")?;
-      if software == "lemmy" && req.matches(&Version::parse(version)?) {
-        kind = PrivateMessageType::ChatMessage
-      }
-    }
+    let kind = if quirks.supports_private_message_note() {
+      PrivateMessageType::Note
+    } else {
+      PrivateMessageType::ChatMessage
+    };
+
+    let encrypted_content = self.ciphertext.clone().map(|ciphertext| EncryptedContent {
+      ciphertext,
+      metadata: self.encryption_metadata.clone(),
+    });
+    let content = if encrypted_content.is_some() {
+      ENCRYPTED_CONTENT_PLACEHOLDER.to_string()
+    } else {
+      self.content.clone()
+    };
 
     let note = PrivateMessage {
       kind,
       id: self.ap_id.clone().into(),
       attributed_to: creator.ap_id.into(),
       to: [recipient.ap_id.into()],
-      content: markdown_to_html(&self.content),
+      content: markdown_to_html(&content),
       media_type: Some(MediaTypeHtml::Html),
-      source: Some(Source::new(self.content.clone())),
+      source: Some(Source::new(content)),
       published: Some(self.published_at),
       updated: self.updated_at,
+      encrypted_content,
     };
     Ok(note)
   }
@@ -152,10 +164,20 @@ impl Object for ApubPrivateMessage {
     let url_blocklist = get_url_blocklist(context).await?;
     let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
 
-    let content = read_from_string_or_source(&note.content, &None, &note.source);
-    let content =
-      process_markdown(&content, &slur_regex, &url_blocklist, &local_site, context).await?;
-    let content = markdown_rewrite_remote_links(content, context).await;
+    // The placeholder content of encrypted messages isn't stored
+    let content = if let Some(encrypted) = &note.encrypted_content {
+      is_valid_encrypted_private_message(
+        "",
+        Some(&encrypted.ciphertext),
+        encrypted.metadata.as_deref(),
+      )?;
+      String::new()
+    } else {
+      let content = read_from_string_or_source(&note.content, &None, &note.source);
+      let content =
+        process_markdown(&content, &slur_regex, &url_blocklist, &local_site, context).await?;
+      markdown_rewrite_remote_links(content, context).await
+    };
 
     let mut form = PrivateMessageInsertForm {
       creator_id: creator.id,
@@ -166,6 +188,11 @@ impl Object for ApubPrivateMessage {
       deleted: Some(false),
       ap_id: Some(note.id.into()),
       local: Some(false),
+      ciphertext: note
+        .encrypted_content
+        .as_ref()
+        .map(|e| e.ciphertext.clone()),
+      encryption_metadata: note.encrypted_content.and_then(|e| e.metadata),
     };
     form = plugin_hook_before("federated_private_message_before_receive", form).await?;
     let timestamp = note.updated.or(note.published).unwrap_or_else(Utc::now);
@@ -185,7 +212,7 @@ mod tests {
     utils::test::{file_to_json_object, parse_lemmy_instance},
   };
   use assert_json_diff::assert_json_include;
-  use lemmy_db_schema::test_data::TestData;
+  use lemmy_db_schema::{source::instance::Instance, test_data::TestData};
   use pretty_assertions::assert_eq;
   use serial_test::serial;
 
@@ -229,6 +256,31 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_parse_encrypted_pm() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let test_data = TestData::create(&mut context.pool()).await?;
+    let url = Url::parse("https://enterprise.lemmy.ml/private_message/1622")?;
+    prepare_comment_test(&url, &context).await?;
+    let json: PrivateMessage =
+      file_to_json_object("../apub/assets/lemmy/objects/encrypted_private_message.json")?;
+    ApubPrivateMessage::verify(&json, &url, &context).await?;
+    let pm = ApubPrivateMessage::from_json(json.clone(), &context).await?;
+
+    // The placeholder isn't stored
+    assert!(pm.content.is_empty());
+    assert!(pm.ciphertext.is_some());
+    assert!(pm.encryption_metadata.is_some());
+
+    let to_apub = pm.into_json(&context).await?;
+    assert_json_include!(actual: json, expected: to_apub);
+
+    test_data.delete(&mut context.pool()).await?;
+    Instance::delete_all(&mut context.pool()).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_parse_pleroma_pm() -> LemmyResult<()> {
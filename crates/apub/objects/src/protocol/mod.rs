@@ -29,6 +29,9 @@ mod tests {
     test_parse_lemmy_item::<Page>("../apub/assets/lemmy/objects/page.json")?;
     test_parse_lemmy_item::<Note>("../apub/assets/lemmy/objects/comment.json")?;
     test_parse_lemmy_item::<PrivateMessage>("../apub/assets/lemmy/objects/private_message.json")?;
+    test_parse_lemmy_item::<PrivateMessage>(
+      "../apub/assets/lemmy/objects/encrypted_private_message.json",
+    )?;
     test_parse_lemmy_item::<Tombstone>("../apub/assets/lemmy/objects/tombstone.json")?;
     Ok(())
   }
@@ -50,9 +53,11 @@ mod tests {
   #[test]
   fn test_parse_objects_mastodon() -> LemmyResult<()> {
     test_json::<Person>("../apub/assets/mastodon/objects/person.json")?;
+    test_json::<Person>("../apub/assets/mastodon/objects/instance_actor.json")?;
     test_json::<Note>("../apub/assets/mastodon/objects/note_1.json")?;
     test_json::<Note>("../apub/assets/mastodon/objects/note_2.json")?;
     test_json::<Page>("../apub/assets/mastodon/objects/page.json")?;
+    test_json::<Page>("../apub/assets/mastodon/objects/question.json")?;
     Ok(())
   }
 
@@ -28,6 +28,7 @@ use lemmy_utils::{
 };
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
 use url::Url;
 
 #[skip_serializing_none]
@@ -54,6 +55,8 @@ pub struct Note {
   // lemmy extension
   pub distinguished: Option<bool>,
   pub(crate) language: Option<LanguageTag>,
+  /// Content keyed by language, used by Mastodon to determine the language
+  pub(crate) content_map: Option<BTreeMap<String, String>>,
   pub(crate) audience: Option<ObjectId<ApubCommunity>>,
   #[serde(default)]
   pub(crate) attachment: Vec<Attachment>,
@@ -61,6 +64,11 @@ pub struct Note {
 }
 
 impl Note {
+  /// Id of the post or comment which this is a reply to.
+  pub fn in_reply_to(&self) -> &Url {
+    self.in_reply_to.inner()
+  }
+
   pub async fn get_parents(
     &self,
     context: &Data<LemmyContext>,
@@ -29,7 +29,9 @@ use lemmy_api_utils::{context::LemmyContext, utils::proxy_image_link};
 use lemmy_db_views_site::SiteView;
 use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult, UntranslatedError};
 use serde::{Deserialize, Deserializer, Serialize, de::Error};
+use serde_json::Value;
 use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
 use url::Url;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -39,6 +41,8 @@ pub enum PageType {
   Note,
   Video,
   Event,
+  /// Poll sent by Mastodon or Pleroma
+  Question,
 }
 
 #[skip_serializing_none]
@@ -71,12 +75,60 @@ pub struct Page {
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
   pub(crate) language: Option<LanguageTag>,
+  /// Content keyed by language, used by Mastodon to determine the language
+  pub(crate) content_map: Option<BTreeMap<String, String>>,
   pub(crate) audience: Option<ObjectId<ApubCommunity>>,
   /// Contains hashtags and post tags.
   /// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-tag
   #[serde(deserialize_with = "deserialize_skip_error", default)]
   pub tag: Vec<ApubTag>,
   pub(crate) context: Option<String>,
+  /// Collection of replies, either as url or embedded. Lemmy uses `context` instead.
+  pub(crate) replies: Option<Value>,
+  /// Poll options if this is a single choice `Question`
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) one_of: Vec<QuestionOption>,
+  /// Poll options if this is a multiple choice `Question`
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) any_of: Vec<QuestionOption>,
+  pub(crate) end_time: Option<DateTime<Utc>>,
+  pub(crate) voters_count: Option<i64>,
+  /// Video length in ISO 8601 format, sent by PeerTube
+  pub(crate) duration: Option<String>,
+  /// Used by PeerTube to generate the embed url
+  pub(crate) uuid: Option<String>,
+  /// Video thumbnails in different sizes, sent by PeerTube
+  #[serde(
+    deserialize_with = "deserialize_skip_error",
+    default,
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  pub(crate) icon: Vec<Thumbnail>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Thumbnail {
+  #[serde(rename = "type")]
+  kind: ImageType,
+  url: Url,
+  width: Option<i32>,
+  height: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionOption {
+  pub(crate) name: String,
+  pub(crate) replies: Option<QuestionOptionReplies>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionOptionReplies {
+  pub(crate) total_items: i64,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -170,6 +222,94 @@ impl Page {
         .ok_or_else(|| UntranslatedError::PageDoesNotSpecifyCreator.into()),
     }
   }
+
+  /// Collection with the post and its comments, if the remote software provides it.
+  pub fn context_url(&self) -> Option<Url> {
+    self.context.as_deref().and_then(|c| Url::parse(c).ok())
+  }
+}
+
+impl Page {
+  /// Returns the largest thumbnail of a PeerTube video.
+  pub(crate) fn video_thumbnail(&self) -> Option<Url> {
+    self
+      .icon
+      .iter()
+      .max_by_key(|i| i.width.unwrap_or_default())
+      .map(|i| i.url.clone())
+  }
+
+  /// Returns the url of the PeerTube player, which can be embedded in an iframe.
+  pub(crate) fn video_embed_url(&self) -> Option<Url> {
+    let uuid = self.uuid.as_ref()?;
+    let mut url = self.id.inner().clone();
+    url.set_path(&format!("/videos/embed/{uuid}"));
+    url.set_query(None);
+    Some(url)
+  }
+
+  /// Parses the video duration in seconds. PeerTube only sends durations like `PT1145S`, but
+  /// hours and minutes are also supported.
+  pub(crate) fn video_duration(&self) -> Option<i32> {
+    let mut rest = self.duration.as_deref()?.strip_prefix("PT")?;
+    let mut seconds = 0;
+    while !rest.is_empty() {
+      let unit_pos = rest.find(|c: char| !c.is_ascii_digit())?;
+      let (value, unit) = rest.split_at(unit_pos);
+      let value: i32 = value.parse().ok()?;
+      let mut chars = unit.chars();
+      let factor = match chars.next()? {
+        'H' => 3600,
+        'M' => 60,
+        'S' => 1,
+        _ => return None,
+      };
+      seconds += value.checked_mul(factor)?;
+      rest = chars.as_str();
+    }
+    Some(seconds)
+  }
+
+  /// Renders the options of a `Question` as markdown list with vote counts, so that remote polls
+  /// can be displayed as regular posts.
+  pub(crate) fn question_summary(&self) -> Option<String> {
+    let options = if self.one_of.is_empty() {
+      &self.any_of
+    } else {
+      &self.one_of
+    };
+    if options.is_empty() {
+      return None;
+    }
+    let mut summary = options
+      .iter()
+      .map(|o| {
+        let votes = o
+          .replies
+          .as_ref()
+          .map(|r| r.total_items)
+          .unwrap_or_default();
+        // Strip linebreaks so that each option stays a single list item
+        let name = o.name.split_whitespace().join(" ");
+        format!("- {name} ({votes})")
+      })
+      .join("\n");
+    if let Some(voters_count) = self.voters_count {
+      summary.push_str(&format!("\n\n{voters_count} voters"));
+    }
+    if let Some(end_time) = self.end_time {
+      let verb = if end_time < Utc::now() {
+        "Ended"
+      } else {
+        "Ends"
+      };
+      summary.push_str(&format!(
+        "\n\n{verb} {}",
+        end_time.format("%Y-%m-%d %H:%M UTC")
+      ));
+    }
+    Some(summary)
+  }
 }
 
 impl Attachment {
@@ -266,10 +406,42 @@ where
 
 #[cfg(test)]
 mod tests {
-  use crate::{protocol::page::Page, utils::test::test_parse_lemmy_item};
+  use crate::{
+    protocol::page::{Page, PageType},
+    utils::test::{file_to_json_object, test_parse_lemmy_item},
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
 
   #[test]
   fn test_not_parsing_note_as_page() {
     assert!(test_parse_lemmy_item::<Page>("assets/lemmy/objects/note.json").is_err());
   }
+
+  #[test]
+  fn test_parse_peertube_video() -> LemmyResult<()> {
+    let page: Page = file_to_json_object("../apub/assets/peertube/objects/video.json")?;
+    assert_eq!(page.kind, PageType::Video);
+    assert_eq!(page.video_duration(), Some(1145));
+    assert_eq!(
+      page.video_embed_url().map(String::from).as_deref(),
+      Some("https://tilvids.com/videos/embed/e7946124-7b72-4ad7-9d22-844a84bb2de1")
+    );
+    assert_eq!(
+      page.video_thumbnail().map(String::from).as_deref(),
+      Some("https://tilvids.com/lazy-static/previews/ef6088ee-c83a-4fcf-8be2-58db95ca5135.jpg")
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_mastodon_question() -> LemmyResult<()> {
+    let page: Page = file_to_json_object("../apub/assets/mastodon/objects/question.json")?;
+    assert_eq!(page.kind, PageType::Question);
+    assert_eq!(
+      page.question_summary().as_deref(),
+      Some("- Tabs (12)\n- Spaces (30)\n\n42 voters\n\nEnded 2023-08-05 09:55 UTC")
+    );
+    Ok(())
+  }
 }
@@ -19,6 +19,8 @@ pub enum UserTypes {
   Person,
   Service,
   Organization,
+  /// Used by Mastodon for its instance actor, which sends reports on behalf of its users
+  Application,
 }
 
 #[skip_serializing_none]
@@ -33,6 +35,8 @@ pub struct Person {
   pub(crate) inbox: Url,
   /// mandatory field in activitypub, lemmy currently serves an empty outbox
   pub(crate) outbox: Url,
+  /// only contains the number of followers, so that other platforms can follow users
+  pub(crate) followers: Option<Url>,
   pub(crate) public_key: PublicKey,
   /// displayname
   pub(crate) name: Option<String>,
@@ -50,4 +54,14 @@ pub struct Person {
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
+  /// device keys for end-to-end encrypted private messages
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub(crate) encryption_keys: Vec<EncryptionKey>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EncryptionKey {
+  pub(crate) device_name: String,
+  pub(crate) public_key: String,
 }
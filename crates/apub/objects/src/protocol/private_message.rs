@@ -30,6 +30,19 @@ pub struct PrivateMessage {
   pub(crate) source: Option<Source>,
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
+  /// For end-to-end encrypted messages, the content is only a placeholder for other software
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) encrypted_content: Option<EncryptedContent>,
+}
+
+/// Opaque payload of an end-to-end encrypted private message, which only the clients of sender
+/// and recipient can read
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EncryptedContent {
+  pub(crate) ciphertext: String,
+  pub(crate) metadata: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -1,16 +1,18 @@
-use crate::objects::UserOrCommunity;
+use crate::{objects::UserOrCommunity, utils::protocol::ImageObject};
 use activitypub_federation::{fetch::object_id::ObjectId, kinds::link::MentionType};
+use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_schema::{
   newtypes::CommunityId,
   source::community_tag::{CommunityTag, CommunityTagInsertForm},
 };
 use lemmy_db_schema_file::enums::TagColor;
+use lemmy_utils::{error::LemmyResult, utils::hashtag::scrape_text_for_hashtags};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use url::Url;
 
-/// Possible values in the `tag` field of a federated post or comment. Note that we don't support
-/// hashtags or community tags in comments, but its easier to use the same struct for both
+/// Possible values in the `tag` field of a federated post or comment. Note that we don't store
+/// hashtags or community tags of comments, but its easier to use the same struct for both
 /// (anyway unsupported values are ignored).
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -18,6 +20,7 @@ pub enum ApubTag {
   Hashtag(Hashtag),
   CommunityTag(ApubCommunityTag),
   Mention(Mention),
+  Emoji(Emoji),
   Unknown(Value),
 }
 
@@ -28,6 +31,26 @@ impl ApubTag {
       _ => None,
     }
   }
+  /// Returns the name of a received hashtag, lowercase and without leading `#`. Ignores the
+  /// hashtag which Lemmy automatically adds for the community, as it links to the post itself.
+  pub(crate) fn hashtag_name(&self, object_id: &Url) -> Option<String> {
+    match self {
+      ApubTag::Hashtag(h) if &h.href != object_id => {
+        let name = h.name.trim_start_matches('#').to_lowercase();
+        let valid = !name.is_empty()
+          && name.chars().count() <= 100
+          && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        valid.then_some(name)
+      }
+      _ => None,
+    }
+  }
+  pub(crate) fn emoji(&self) -> Option<&Emoji> {
+    match self {
+      ApubTag::Emoji(e) => Some(e),
+      _ => None,
+    }
+  }
   pub fn mention_id(&self) -> Option<&ObjectId<UserOrCommunity>> {
     match self {
       ApubTag::Mention(m) => Some(&m.href),
@@ -52,11 +75,52 @@ pub struct Hashtag {
   pub(crate) kind: HashtagType,
 }
 
+impl Hashtag {
+  /// Generates hashtags for all `#tags` in the text, linking to the local search page.
+  pub(crate) fn from_text(text: &str, context: &LemmyContext) -> LemmyResult<Vec<ApubTag>> {
+    let search_url = Url::parse(&format!(
+      "{}/search",
+      context.settings().get_protocol_and_hostname()
+    ))?;
+    Ok(
+      scrape_text_for_hashtags(text)
+        .into_iter()
+        .map(|name| {
+          let mut href = search_url.clone();
+          href
+            .query_pairs_mut()
+            .append_pair("q", &format!("#{name}"))
+            .append_pair("type", "Posts");
+          ApubTag::Hashtag(Hashtag {
+            href,
+            name: format!("#{name}"),
+            kind: HashtagType::Hashtag,
+          })
+        })
+        .collect(),
+    )
+  }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum HashtagType {
   Hashtag,
 }
 
+/// Custom emoji, where `name` is the shortcode surrounded by colons.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Emoji {
+  pub(crate) name: String,
+  pub(crate) icon: ImageObject,
+  #[serde(rename = "type")]
+  pub(crate) kind: EmojiType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum EmojiType {
+  Emoji,
+}
+
 /// The [ActivityStreams vocabulary](https://www.w3.org/TR/activitystreams-vocabulary/#dfn-tag)
 /// defines that any object can have a list of tags associated with it.
 /// Tags in AS can be of any type, so we define our own types.
@@ -0,0 +1,157 @@
+use crate::{
+  protocol::tags::{ApubTag, Emoji, EmojiType},
+  utils::protocol::{ImageObject, Source},
+};
+use itertools::Itertools;
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+use url::Url;
+
+/// Custom emojis are stored in markdown as image with a special title, eg
+/// `![party-blob](https://example.com/party-blob.gif "emoji party-blob")`.
+#[expect(clippy::expect_used)]
+static CUSTOM_EMOJI_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(r#"!\[[^\]]*\]\((?P<url>\S+) "emoji (?P<shortcode>[^"\s]+)"\)"#)
+    .expect("compile regex")
+});
+
+/// Replaces custom emojis in markdown with `:shortcode:` and returns the matching `Emoji` tags.
+/// This is how Mastodon and others federate emojis, as they strip images from the content.
+pub(crate) fn markdown_emojis_to_shortcodes(markdown: &str) -> (String, Vec<ApubTag>) {
+  let mut emojis = vec![];
+  let replaced = CUSTOM_EMOJI_REGEX.replace_all(markdown, |caps: &Captures| {
+    let shortcode = caps.name("shortcode").map(|m| m.as_str());
+    let url = caps.name("url").and_then(|m| Url::parse(m.as_str()).ok());
+    match (shortcode, url) {
+      (Some(shortcode), Some(url)) => {
+        let name = format!(":{shortcode}:");
+        emojis.push(Emoji {
+          name: name.clone(),
+          icon: ImageObject::new(url.into()),
+          kind: EmojiType::Emoji,
+        });
+        name
+      }
+      _ => caps
+        .get(0)
+        .map(|m| m.as_str())
+        .unwrap_or_default()
+        .to_string(),
+    }
+  });
+  let tags = emojis
+    .into_iter()
+    .unique_by(|e| e.name.clone())
+    .map(ApubTag::Emoji)
+    .collect();
+  (replaced.to_string(), tags)
+}
+
+/// Converts `:shortcode:` in received content to markdown images based on the `Emoji` tags, so
+/// that they are rendered like local custom emojis. Lemmy sends markdown source which already
+/// contains the images, so nothing is changed in that case.
+pub(crate) fn shortcodes_to_markdown_emojis(
+  mut content: String,
+  source: &Option<Source>,
+  tags: &[ApubTag],
+) -> String {
+  if source.is_some() {
+    return content;
+  }
+  for emoji in tags.iter().filter_map(ApubTag::emoji) {
+    let shortcode = emoji.name.trim_matches(':');
+    if shortcode.is_empty() || shortcode.contains(|c: char| c.is_whitespace() || c == '"') {
+      continue;
+    }
+    let Some(url) = markdown_image_url(&emoji.icon.url) else {
+      continue;
+    };
+    let image = format!("![{shortcode}]({url} \"emoji {shortcode}\")");
+    content = content.replace(&format!(":{shortcode}:"), &image);
+  }
+  content
+}
+
+/// Image urls of the received `Emoji` tags which can be fetched and cached.
+pub(crate) fn emoji_image_urls(tags: &[ApubTag]) -> Vec<Url> {
+  tags
+    .iter()
+    .filter_map(ApubTag::emoji)
+    .map(|e| e.icon.url.clone())
+    .filter(|url| markdown_image_url(url).is_some())
+    .unique()
+    .collect()
+}
+
+/// Formats a remote url for use as markdown image destination. Characters which would end the
+/// destination or title early are percent-encoded, and urls with other schemes than http(s) are
+/// rejected.
+fn markdown_image_url(url: &Url) -> Option<String> {
+  if !matches!(url.scheme(), "http" | "https") {
+    return None;
+  }
+  let mut encoded = String::with_capacity(url.as_str().len());
+  for c in url.as_str().chars() {
+    match c {
+      '(' => encoded.push_str("%28"),
+      ')' => encoded.push_str("%29"),
+      '"' => encoded.push_str("%22"),
+      '<' => encoded.push_str("%3C"),
+      '>' => encoded.push_str("%3E"),
+      '\\' => encoded.push_str("%5C"),
+      c if c.is_whitespace() => return None,
+      c => encoded.push(c),
+    }
+  }
+  Some(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_emojis_roundtrip() {
+    let markdown = r#"Hello ![party](https://example.com/party-blob.gif "emoji party-blob") ![party](https://example.com/party-blob.gif "emoji party-blob")"#;
+    let (replaced, tags) = markdown_emojis_to_shortcodes(markdown);
+    assert_eq!("Hello :party-blob: :party-blob:", replaced);
+    assert_eq!(1, tags.len());
+
+    let content = shortcodes_to_markdown_emojis(replaced, &None, &tags);
+    assert_eq!(
+      r#"Hello ![party-blob](https://example.com/party-blob.gif "emoji party-blob") ![party-blob](https://example.com/party-blob.gif "emoji party-blob")"#,
+      content
+    );
+  }
+
+  #[test]
+  fn test_emoji_url_escaped() -> LemmyResult<()> {
+    let tag = |url: &str| -> LemmyResult<ApubTag> {
+      Ok(ApubTag::Emoji(Emoji {
+        name: ":blob:".to_string(),
+        icon: ImageObject::new(Url::parse(url)?.into()),
+        kind: EmojiType::Emoji,
+      }))
+    };
+
+    // A closing bracket in the url can't be used to inject other markdown
+    let tags = vec![tag(
+      "https://example.com/blob.png?a=)![x](https://evil.com/x.png",
+    )?];
+    let content = shortcodes_to_markdown_emojis(":blob:".to_string(), &None, &tags);
+    assert_eq!(
+      r#"![blob](https://example.com/blob.png?a=%29![x]%28https://evil.com/x.png "emoji blob")"#,
+      content
+    );
+    assert_eq!(1, emoji_image_urls(&tags).len());
+
+    // Other schemes are ignored
+    let tags = vec![tag("javascript:alert(1)")?];
+    let content = shortcodes_to_markdown_emojis(":blob:".to_string(), &None, &tags);
+    assert_eq!(":blob:", content);
+    assert!(emoji_image_urls(&tags).is_empty());
+    Ok(())
+  }
+}
@@ -25,6 +25,7 @@ use lemmy_utils::{
   CACHE_DURATION_FEDERATION,
   CacheLock,
   error::{LemmyError, LemmyResult, UntranslatedError},
+  utils::domain_pattern::{DomainPattern, build_domain_patterns},
 };
 use moka::future::Cache;
 use std::sync::{Arc, LazyLock};
@@ -62,6 +63,18 @@ pub struct LocalSiteData {
   local_site: Option<LocalSite>,
   allowed_instances: Vec<Instance>,
   blocked_instances: Vec<Instance>,
+  allowed_patterns: Vec<DomainPattern>,
+  blocked_patterns: Vec<DomainPattern>,
+}
+
+impl LocalSiteData {
+  fn has_allowlist(&self) -> bool {
+    !self.allowed_instances.is_empty() || !self.allowed_patterns.is_empty()
+  }
+
+  fn is_allowed_by_pattern(&self, domain: &str) -> bool {
+    self.allowed_patterns.iter().any(|p| p.matches(domain))
+  }
 }
 
 pub async fn local_site_data_cached(pool: &mut DbPool<'_>) -> LemmyResult<Arc<LocalSiteData>> {
@@ -88,10 +101,20 @@ pub async fn local_site_data_cached(pool: &mut DbPool<'_>) -> LemmyResult<Arc<Lo
             Instance::blocklist
           ))?;
 
+        // Patterns are validated when saving the site, so this should never fail
+        let patterns =
+          |p: Option<&String>| build_domain_patterns(p.map(String::as_str)).unwrap_or_default();
+        let allowed_patterns =
+          patterns(local_site.as_ref().and_then(|l| l.allowed_instances_patterns.as_ref()));
+        let blocked_patterns =
+          patterns(local_site.as_ref().and_then(|l| l.blocked_instances_patterns.as_ref()));
+
         Ok::<_, LemmyError>(Arc::new(LocalSiteData {
           local_site,
           allowed_instances,
           blocked_instances,
+          allowed_patterns,
+          blocked_patterns,
         }))
       }))
       .await.map_err(|e| anyhow::anyhow!("err getting activity: {e:?}"))?
@@ -116,7 +139,7 @@ pub async fn check_apub_id_valid_with_strictness(
   check_apub_id_valid(apub_id, &local_site_data)?;
 
   // Only check allowlist if this is a community, and there are instances in the allowlist
-  if is_strict && !local_site_data.allowed_instances.is_empty() {
+  if is_strict && local_site_data.has_allowlist() {
     // need to allow this explicitly because apub receive might contain objects from our local
     // instance.
     let mut allowed_and_local = local_site_data
@@ -131,7 +154,7 @@ pub async fn check_apub_id_valid_with_strictness(
       .domain()
       .ok_or(UntranslatedError::UrlWithoutDomain)?
       .to_string();
-    if !allowed_and_local.contains(&domain) {
+    if !allowed_and_local.contains(&domain) && !local_site_data.is_allowed_by_pattern(&domain) {
       return Err(UntranslatedError::FederationDisabledByStrictAllowList.into());
     }
   }
@@ -164,16 +187,21 @@ pub fn check_apub_id_valid(apub_id: &Url, local_site_data: &LocalSiteData) -> Le
     .blocked_instances
     .iter()
     .any(|i| domain.to_lowercase().eq(&i.domain.to_lowercase()))
+    || local_site_data
+      .blocked_patterns
+      .iter()
+      .any(|p| p.matches(&domain))
   {
     return Err(UntranslatedError::DomainBlocked(domain.clone()).into());
   }
 
   // Only check this if there are instances in the allowlist
-  if !local_site_data.allowed_instances.is_empty()
+  if local_site_data.has_allowlist()
     && !local_site_data
       .allowed_instances
       .iter()
       .any(|i| domain.to_lowercase().eq(&i.domain.to_lowercase()))
+    && !local_site_data.is_allowed_by_pattern(&domain)
   {
     return Err(UntranslatedError::DomainNotInAllowList(domain).into());
   }
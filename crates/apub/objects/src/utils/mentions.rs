@@ -1,6 +1,7 @@
 use crate::{
   objects::person::ApubPerson,
   protocol::tags::{ApubTag, Mention},
+  utils::quirks::InstanceQuirks,
 };
 use activitypub_federation::{
   config::Data,
@@ -35,17 +36,20 @@ pub(crate) async fn collect_non_local_mentions(
   let mut mentions = vec![];
 
   if let Some(parent_creator) = parent_creator {
+    let quirks = InstanceQuirks::read(&mut context.pool(), parent_creator.instance_id).await?;
     addressed_ccs.push(parent_creator.id().clone());
     mentions.push(Mention {
       href: parent_creator.id().clone().into(),
-      name: Some(format!(
-        "@{}@{}",
-        &parent_creator.name,
-        &parent_creator
-          .id()
-          .domain()
-          .ok_or(UntranslatedError::UrlWithoutDomain)?
-      )),
+      name: Some(
+        quirks.mention_name(
+          &parent_creator.name,
+          parent_creator
+            .id()
+            .domain()
+            .ok_or(UntranslatedError::UrlWithoutDomain)?,
+          false,
+        ),
+      ),
       kind: MentionType::Mention,
     });
   }
@@ -62,11 +66,12 @@ pub(crate) async fn collect_non_local_mentions(
     let identifier = format!("{}@{}", mention.name, mention.domain);
     let person = webfinger_resolve_actor::<LemmyContext, ApubPerson>(&identifier, context).await;
     if let Ok(person) = person {
+      let quirks = InstanceQuirks::read(&mut context.pool(), person.instance_id).await?;
       addressed_ccs.push(person.ap_id.to_string().parse()?);
 
       let mention_tag = Mention {
         href: person.id().clone().into(),
-        name: Some(mention.full_name()),
+        name: Some(quirks.mention_name(&mention.name, &mention.domain, false)),
         kind: MentionType::Mention,
       };
       mentions.push(mention_tag);
@@ -78,16 +83,19 @@ pub(crate) async fn collect_non_local_mentions(
   //       mention which automatically gets copied into Mastodon replies, and ensures correct
   //       federation.
   //       https://lemmy.ml/post/44552705/24563597
+  let quirks = InstanceQuirks::read(&mut context.pool(), community.instance_id).await?;
   mentions.push(Mention {
     href: community.ap_id.clone().into(),
-    name: Some(format!(
-      "@{}@{}",
-      &community.name,
-      &community
-        .ap_id
-        .domain()
-        .ok_or(UntranslatedError::UrlWithoutDomain)?
-    )),
+    name: Some(
+      quirks.mention_name(
+        &community.name,
+        community
+          .ap_id
+          .domain()
+          .ok_or(UntranslatedError::UrlWithoutDomain)?,
+        true,
+      ),
+    ),
     kind: MentionType::Mention,
   });
 
@@ -1,5 +1,8 @@
+pub mod emojis;
 pub mod functions;
 pub mod markdown_links;
 pub mod mentions;
 pub mod protocol;
+pub mod quirks;
+pub(crate) mod replies;
 pub mod test;
@@ -14,7 +14,7 @@ use lemmy_db_schema::{
 use lemmy_diesel_utils::{connection::DbPool, dburl::DbUrl};
 use lemmy_utils::error::LemmyResult;
 use serde::{Deserialize, Serialize};
-use std::{future::Future, ops::Deref};
+use std::{collections::BTreeMap, future::Future, ops::Deref};
 use url::Url;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -173,7 +173,7 @@ impl LanguageTag {
     lang: Self,
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<LanguageId> {
-    Language::read_id_from_code(pool, &lang.identifier).await
+    Self::code_to_language_id(&lang.identifier, pool).await
   }
 
   pub(crate) async fn to_language_id_multiple(
@@ -183,12 +183,39 @@ impl LanguageTag {
     let mut language_ids = Vec::new();
 
     for l in langs {
-      let id = l.identifier;
-      language_ids.push(Language::read_id_from_code(pool, &id).await?);
+      language_ids.push(Self::code_to_language_id(&l.identifier, pool).await?);
     }
 
     Ok(language_ids.into_iter().collect())
   }
+
+  /// Other platforms often use tags with a region like `en-US` or `pt_BR`, which are mapped to the
+  /// base language if there is no exact match.
+  async fn code_to_language_id(code: &str, pool: &mut DbPool<'_>) -> LemmyResult<LanguageId> {
+    let code = code.to_lowercase();
+    let id = Language::read_id_from_code(pool, &code).await?;
+    match code.split_once(['-', '_']) {
+      Some((base, _)) if id == UNDETERMINED_ID => Language::read_id_from_code(pool, base).await,
+      _ => Ok(id),
+    }
+  }
+
+  /// Reads the language from `contentMap`, which Mastodon and others use instead of a language
+  /// tag.
+  pub(crate) fn from_content_map(content_map: &Option<BTreeMap<String, String>>) -> Option<Self> {
+    let identifier = content_map.as_ref()?.keys().next()?.clone();
+    Some(LanguageTag {
+      identifier,
+      name: String::new(),
+    })
+  }
+
+  /// Content keyed by its language, so that platforms which only read `contentMap` know the
+  /// language too. None if the language is undetermined.
+  pub(crate) fn content_map(&self, content: &str) -> Option<BTreeMap<String, String>> {
+    (*self != LanguageTag::default())
+      .then(|| BTreeMap::from([(self.identifier.clone(), content.to_string())]))
+  }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
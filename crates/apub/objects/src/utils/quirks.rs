@@ -0,0 +1,135 @@
+use lemmy_db_schema::source::instance::Instance;
+use lemmy_db_schema_file::InstanceId;
+use lemmy_diesel_utils::{connection::DbPool, traits::Crud};
+use lemmy_utils::error::LemmyResult;
+use semver::{Version, VersionReq};
+
+/// Fediverse software which needs special handling, detected via nodeinfo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Software {
+  Lemmy,
+  Piefed,
+  /// Also includes the Mbin fork
+  Kbin,
+  Mastodon,
+  Other,
+}
+
+/// Differences in the federation behaviour of other instances, based on their software and
+/// version. If the software is not known yet, the defaults for Lemmy are used.
+#[derive(Clone, Debug)]
+pub struct InstanceQuirks {
+  pub software: Software,
+  version: Option<Version>,
+}
+
+impl InstanceQuirks {
+  pub fn new(instance: &Instance) -> Self {
+    let software = match instance.software.as_deref() {
+      None | Some("lemmy") => Software::Lemmy,
+      Some("piefed") => Software::Piefed,
+      Some("kbin" | "mbin") => Software::Kbin,
+      Some("mastodon" | "hometown" | "glitchsoc") => Software::Mastodon,
+      Some(_) => Software::Other,
+    };
+    let version = instance
+      .version
+      .as_deref()
+      .and_then(|v| Version::parse(v).ok());
+    Self { software, version }
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, instance_id: InstanceId) -> LemmyResult<Self> {
+    let instance = Instance::read(pool, instance_id).await?;
+    Ok(Self::new(&instance))
+  }
+
+  /// Name of a mention tag for the given actor, as the software hosting it formats mentions.
+  /// Piefed uses `!` for communities, while Lemmy, Kbin and Mastodon use `@` for all actors.
+  pub fn mention_name(&self, name: &str, domain: &str, is_community: bool) -> String {
+    let prefix = match self.software {
+      Software::Piefed if is_community => '!',
+      _ => '@',
+    };
+    format!("{prefix}{name}@{domain}")
+  }
+
+  /// Kbin and Mbin cant handle follows which need approval.
+  pub fn supports_private_communities(&self) -> bool {
+    self.software != Software::Kbin
+  }
+
+  /// Lemmy before 0.20 only accepts private messages with the old `ChatMessage` type.
+  pub fn supports_private_message_note(&self) -> bool {
+    !self.is_lemmy_version("<0.20")
+  }
+
+  fn is_lemmy_version(&self, req: &str) -> bool {
+    let req = VersionReq::parse(req).ok();
+    match (self.software, &self.version, req) {
+      (Software::Lemmy, Some(version), Some(req)) => req.matches(version),
+      _ => false,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::Utc;
+  use pretty_assertions::assert_eq;
+
+  fn instance(software: Option<&str>, version: Option<&str>) -> Instance {
+    Instance {
+      id: InstanceId(1),
+      domain: "example.com".to_string(),
+      published_at: Utc::now(),
+      updated_at: None,
+      software: software.map(ToString::to_string),
+      version: version.map(ToString::to_string),
+      dead_since: None,
+      next_probe_at: None,
+    }
+  }
+
+  #[test]
+  fn test_instance_quirks() {
+    let old_lemmy = InstanceQuirks::new(&instance(Some("lemmy"), Some("0.19.3")));
+    assert!(!old_lemmy.supports_private_message_note());
+    assert!(old_lemmy.supports_private_communities());
+    assert_eq!(
+      "@main@example.com",
+      old_lemmy.mention_name("main", "example.com", true)
+    );
+
+    let lemmy = InstanceQuirks::new(&instance(Some("lemmy"), Some("1.0.0")));
+    assert!(lemmy.supports_private_message_note());
+
+    let mbin = InstanceQuirks::new(&instance(Some("mbin"), Some("1.7.0")));
+    assert!(!mbin.supports_private_communities());
+    assert_eq!(
+      "@main@example.com",
+      mbin.mention_name("main", "example.com", true)
+    );
+
+    let piefed = InstanceQuirks::new(&instance(Some("piefed"), Some("1.0.0")));
+    assert_eq!(
+      "!main@example.com",
+      piefed.mention_name("main", "example.com", true)
+    );
+    assert_eq!(
+      "@alice@example.com",
+      piefed.mention_name("alice", "example.com", false)
+    );
+
+    let mastodon = InstanceQuirks::new(&instance(Some("mastodon"), Some("4.3.0")));
+    assert_eq!(
+      "@alice@example.com",
+      mastodon.mention_name("alice", "example.com", false)
+    );
+
+    let unknown = InstanceQuirks::new(&instance(None, None));
+    assert_eq!(Software::Lemmy, unknown.software);
+    assert!(unknown.supports_private_message_note());
+  }
+}
@@ -0,0 +1,129 @@
+use crate::objects::comment::ApubComment;
+use activitypub_federation::{
+  config::Data,
+  fetch::{fetch_object_http, object_id::ObjectId},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_utils::error::LemmyResult;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::debug;
+use url::Url;
+
+/// Maximum number of collection pages which are fetched for a single post.
+const MAX_REPLIES_PAGES: usize = 5;
+
+/// Collection or page of the `replies` collection. Mastodon embeds the first page in the post
+/// and only lists other pages by url, while others put all items directly into the collection.
+/// Items may be urls or embedded objects.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RepliesPage {
+  #[serde(default)]
+  items: Vec<Value>,
+  #[serde(default)]
+  ordered_items: Vec<Value>,
+  first: Option<Value>,
+  next: Option<Value>,
+}
+
+/// Fetches the existing replies of a newly received remote post, so that discussions which
+/// started before the post federated here are visible. Lemmy uses the `context` collection
+/// instead, which is handled by community backfill.
+pub(crate) async fn fetch_post_replies(
+  replies: Value,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let limit: usize = context.settings().federation.fetch_replies_per_post.into();
+  let mut next = Some(replies);
+  let mut fetched = 0;
+
+  for _ in 0..MAX_REPLIES_PAGES {
+    let Some(value) = next.take() else {
+      break;
+    };
+    let page = read_page(value, context).await?;
+    let ids = page
+      .items
+      .iter()
+      .chain(&page.ordered_items)
+      .filter_map(item_id);
+    for id in ids {
+      if fetched >= limit {
+        return Ok(());
+      }
+      // Dereference by id instead of using embedded objects, which might be forged
+      match ObjectId::<ApubComment>::from(id.clone())
+        .dereference(context)
+        .await
+      {
+        Ok(_) => fetched += 1,
+        Err(e) => debug!("Failed to fetch reply {id}: {e}"),
+      }
+    }
+    next = page.first.or(page.next);
+  }
+  Ok(())
+}
+
+async fn read_page(value: Value, context: &Data<LemmyContext>) -> LemmyResult<RepliesPage> {
+  match value {
+    Value::String(url) => Ok(fetch_object_http(&Url::parse(&url)?, context).await?.object),
+    value => Ok(serde_json::from_value(value)?),
+  }
+}
+
+fn item_id(item: &Value) -> Option<Url> {
+  let id = match item {
+    Value::String(id) => id,
+    Value::Object(o) => o.get("id")?.as_str()?,
+    _ => return None,
+  };
+  Url::parse(id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  #[test]
+  fn test_parse_replies_page() -> LemmyResult<()> {
+    // Format used by Mastodon
+    let replies: RepliesPage = serde_json::from_value(json!({
+      "id": "https://mastodon.social/users/alice/statuses/1/replies",
+      "type": "Collection",
+      "first": {
+        "type": "CollectionPage",
+        "next": "https://mastodon.social/users/alice/statuses/1/replies?page=true",
+        "items": []
+      }
+    }))?;
+    let first: RepliesPage = serde_json::from_value(replies.first.unwrap_or_default())?;
+    assert_eq!(
+      Some(json!(
+        "https://mastodon.social/users/alice/statuses/1/replies?page=true"
+      )),
+      first.next
+    );
+
+    let page: RepliesPage = serde_json::from_value(json!({
+      "type": "OrderedCollection",
+      "orderedItems": [
+        "https://example.com/comment/1",
+        { "id": "https://example.com/comment/2", "type": "Note" },
+        5
+      ]
+    }))?;
+    let ids: Vec<_> = page.ordered_items.iter().filter_map(item_id).collect();
+    assert_eq!(
+      vec![
+        Url::parse("https://example.com/comment/1")?,
+        Url::parse("https://example.com/comment/2")?
+      ],
+      ids
+    );
+    Ok(())
+  }
+}
@@ -1,4 +1,4 @@
-use crate::{util::CancellableTask, worker::InstanceWorker};
+use crate::{limiter::SendLimiter, util::CancellableTask, worker::InstanceWorker};
 use activitypub_federation::config::FederationConfig;
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_schema::source::instance::Instance;
@@ -16,6 +16,7 @@ use tracing::info;
 use util::FederationQueueStateWithDomain;
 
 mod inboxes;
+mod limiter;
 mod send;
 mod stats;
 mod util;
@@ -41,7 +42,7 @@ pub struct SendManager {
   context: FederationConfig<LemmyContext>,
   stats_sender: UnboundedSender<FederationQueueStateWithDomain>,
   exit_print: JoinHandle<()>,
-  federation_worker_config: FederationWorkerConfig,
+  limiter: SendLimiter,
 }
 
 impl SendManager {
@@ -64,7 +65,7 @@ impl SendManager {
         stats_receiver,
       )),
       context,
-      federation_worker_config,
+      limiter: SendLimiter::new(federation_worker_config.concurrent_sends_total),
     }
   }
 
@@ -134,7 +135,7 @@ impl SendManager {
           // create new worker
           let context = self.context.clone();
           let stats_sender = self.stats_sender.clone();
-          let federation_worker_config = self.federation_worker_config.clone();
+          let limiter = self.limiter.clone();
 
           self.workers.insert(
             instance.id,
@@ -145,9 +146,9 @@ impl SendManager {
               InstanceWorker::init_and_loop(
                 instance,
                 context.clone(),
-                federation_worker_config.clone(),
                 stop,
                 stats_sender.clone(),
+                limiter.clone(),
               )
             }),
           );
@@ -204,6 +205,7 @@ mod test {
     instance::InstanceForm,
     person::{Person, PersonInsertForm},
   };
+  use lemmy_db_schema_file::enums::FederationBlockSeverity;
   use lemmy_diesel_utils::traits::Crud;
   use lemmy_utils::error::LemmyError;
   use serial_test::serial;
@@ -230,14 +232,6 @@ mod test {
         .app_data(context.app_data().clone())
         .build()
         .await?;
-      let concurrent_sends_per_instance = std::env::var("LEMMY_TEST_FEDERATION_CONCURRENT_SENDS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(1);
-
-      let federation_worker_config = FederationWorkerConfig {
-        concurrent_sends_per_instance,
-      };
       let pool = &mut context.pool();
       let instances = vec![
         Instance::read_or_create(pool, "alpha.com").await?,
@@ -245,7 +239,8 @@ mod test {
         Instance::read_or_create(pool, "gamma.com").await?,
       ];
 
-      let send_manager = SendManager::new(opts, federation_config, federation_worker_config);
+      let send_manager =
+        SendManager::new(opts, federation_config, FederationWorkerConfig::default());
       Ok(Self {
         send_manager,
         context,
@@ -335,6 +330,27 @@ mod test {
     Ok(())
   }
 
+  /// Silenced instances are still federated with
+  #[tokio::test]
+  #[serial]
+  async fn test_send_manager_silenced() -> LemmyResult<()> {
+    let mut data = TestData::init(1, 1).await?;
+
+    let instance_id = data.instances[0].id;
+    let form = FederationBlockListForm {
+      severity: FederationBlockSeverity::Silence,
+      ..FederationBlockListForm::new(instance_id, None)
+    };
+    FederationBlockList::block(&mut data.context.pool(), &form).await?;
+    data.run().await?;
+    let workers = &data.send_manager.workers;
+    assert_eq!(3, workers.len());
+    assert!(workers.contains_key(&instance_id));
+
+    data.cleanup().await?;
+    Ok(())
+  }
+
   /// Use allowlist, should only send to allowed instance
   #[tokio::test]
   #[serial]
@@ -368,6 +384,7 @@ mod test {
       ..InstanceForm::new(instance.domain.clone())
     };
     Instance::update(&mut data.context.pool(), instance.id, form).await?;
+    Instance::mark_dead(&mut data.context.pool(), 3).await?;
 
     data.run().await?;
     let workers = &data.send_manager.workers;
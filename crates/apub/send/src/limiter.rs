@@ -0,0 +1,128 @@
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::{AcquireError, Semaphore, SemaphorePermit};
+
+/// Votes make up most of the outgoing federation traffic, but are less urgent than other
+/// activities like new posts or removals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ActivityPriority {
+  High,
+  Low,
+}
+
+impl ActivityPriority {
+  pub(crate) fn of(activity: &Value) -> Self {
+    let mut activity = activity;
+    // Votes are also sent wrapped in `Announce` or `Undo`
+    loop {
+      match activity.get("type").and_then(Value::as_str) {
+        Some("Like" | "Dislike") => return ActivityPriority::Low,
+        Some("Announce" | "Undo") => match activity.get("object") {
+          Some(object) => activity = object,
+          None => return ActivityPriority::High,
+        },
+        _ => return ActivityPriority::High,
+      }
+    }
+  }
+}
+
+/// Limits the number of concurrent sends across all instance workers. Low priority activities can
+/// only use half of the permits, so that a vote storm doesn't delay the delivery of other
+/// activities.
+#[derive(Clone, Default)]
+pub(crate) struct SendLimiter {
+  limits: Option<Arc<(Semaphore, Semaphore)>>,
+}
+
+pub(crate) struct SendPermit<'a> {
+  _all: SemaphorePermit<'a>,
+  _low_priority: Option<SemaphorePermit<'a>>,
+}
+
+impl SendLimiter {
+  /// No limit if `max_sends` is 0.
+  pub(crate) fn new(max_sends: u16) -> Self {
+    let max_sends = usize::from(max_sends);
+    let limits = (max_sends > 0).then(|| {
+      Arc::new((
+        Semaphore::new(max_sends),
+        Semaphore::new(max_sends.div_ceil(2)),
+      ))
+    });
+    Self { limits }
+  }
+
+  /// Waits until the activity may be sent. The permit must be held until the send is finished.
+  pub(crate) async fn acquire(
+    &self,
+    priority: ActivityPriority,
+  ) -> Result<Option<SendPermit<'_>>, AcquireError> {
+    let Some(limits) = &self.limits else {
+      return Ok(None);
+    };
+    let (all, low_priority) = limits.as_ref();
+    // Acquire the low priority permit first, so that waiting votes don't hold general permits
+    let low_priority = match priority {
+      ActivityPriority::Low => Some(low_priority.acquire().await?),
+      ActivityPriority::High => None,
+    };
+    Ok(Some(SendPermit {
+      _all: all.acquire().await?,
+      _low_priority: low_priority,
+    }))
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn test_activity_priority() {
+    let like = json!({ "type": "Like", "object": "https://example.com/post/1" });
+    assert_eq!(ActivityPriority::Low, ActivityPriority::of(&like));
+    let announce_undo_like = json!({
+      "type": "Announce",
+      "object": { "type": "Undo", "object": like }
+    });
+    assert_eq!(
+      ActivityPriority::Low,
+      ActivityPriority::of(&announce_undo_like)
+    );
+    let announce_remove = json!({
+      "type": "Announce",
+      "object": { "type": "Remove", "object": "https://example.com/post/1" }
+    });
+    assert_eq!(
+      ActivityPriority::High,
+      ActivityPriority::of(&announce_remove)
+    );
+    let announce_url = json!({ "type": "Announce", "object": "https://example.com/activity/1" });
+    assert_eq!(ActivityPriority::High, ActivityPriority::of(&announce_url));
+  }
+
+  #[tokio::test]
+  async fn test_send_limiter() -> Result<(), AcquireError> {
+    let limiter = SendLimiter::new(2);
+    let limits = limiter.limits.as_ref().unwrap();
+    let vote = limiter.acquire(ActivityPriority::Low).await?;
+    // The only low priority permit is taken, but other activities can still be sent
+    assert_eq!(0, limits.1.available_permits());
+    let post = limiter.acquire(ActivityPriority::High).await?;
+    assert_eq!(0, limits.0.available_permits());
+    drop(vote);
+    drop(post);
+    assert_eq!(2, limits.0.available_permits());
+
+    assert!(
+      SendLimiter::new(0)
+        .acquire(ActivityPriority::Low)
+        .await?
+        .is_none()
+    );
+    Ok(())
+  }
+}
@@ -1,4 +1,7 @@
-use crate::util::get_actor_cached;
+use crate::{
+  limiter::{ActivityPriority, SendLimiter},
+  util::get_actor_cached,
+};
 use activitypub_federation::{
   activity_sending::SendActivityTask,
   config::Data,
@@ -8,7 +11,11 @@ use activitypub_federation::{
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_db_schema::{newtypes::ActivityId, source::activity::SentActivity};
+use lemmy_db_schema::{
+  newtypes::ActivityId,
+  source::{activity::SentActivity, sent_activity_delivery::SentActivityDelivery},
+};
+use lemmy_db_schema_file::InstanceId;
 use lemmy_utils::{
   FEDERATION_CONTEXT,
   error::{LemmyError, LemmyResult},
@@ -20,6 +27,7 @@ use serde_json::Value;
 use std::ops::Deref;
 use tokio::{sync::mpsc::UnboundedSender, time::sleep};
 use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, info_span};
 
 #[derive(Debug, Eq)]
 pub(crate) struct SendSuccessInfo {
@@ -78,8 +86,9 @@ pub(crate) struct SendRetryTask<'a> {
   pub object: &'a Value,
   /// Must not be empty at this point
   pub inbox_urls: Vec<Url>,
-  /// Channel to report results back to the main instance worker
-  pub report: &'a mut UnboundedSender<SendActivityResult>,
+  /// Channel to report results back to the main instance worker. None for activities which are
+  /// replayed on request of an admin, as these are sent outside of the regular queue.
+  pub report: Option<&'a mut UnboundedSender<SendActivityResult>>,
   /// The first request will be sent immediately, but subsequent requests will be delayed
   /// according to the number of previous fails + 1
   ///
@@ -89,8 +98,12 @@ pub(crate) struct SendRetryTask<'a> {
   pub initial_fail_count: i32,
   /// For logging purposes
   pub domain: String,
+  /// Failed deliveries are stored for this instance
+  pub instance_id: InstanceId,
   pub context: Data<LemmyContext>,
   pub stop: CancellationToken,
+  /// Shared by all instance workers to limit the total number of concurrent sends
+  pub limiter: SendLimiter,
 }
 
 impl SendRetryTask<'_> {
@@ -101,11 +114,13 @@ impl SendRetryTask<'_> {
       activity,
       object,
       inbox_urls,
-      report,
       initial_fail_count,
       domain,
+      instance_id,
+      mut report,
       context,
       stop,
+      limiter,
     } = self;
     debug_assert!(!inbox_urls.is_empty());
 
@@ -117,19 +132,43 @@ impl SendRetryTask<'_> {
       .await
       .context("failed getting actor instance (was it marked deleted / removed?)")?;
 
+    let priority = ActivityPriority::of(object);
     let object: DummyActivity = serde_json::from_value(object.clone())?;
     let object = WithContext::new(object, FEDERATION_CONTEXT.deref().clone());
     let requests = SendActivityTask::prepare(&object, actor.as_ref(), inbox_urls, &context).await?;
+    // Only deliveries which failed at some point are stored, to keep the number of writes low
+    let mut has_failed = report.is_none() || initial_fail_count > 0;
     for task in requests {
       // usually only one due to shared inbox
       tracing::debug!("sending out {}", task);
       let mut fail_count = initial_fail_count;
-      while let Err(e) = task.sign_and_send(&context).await {
+      loop {
+        let permit = limiter.acquire(priority).await?;
+        // The trace context of this span is added to the request headers
+        let span = info_span!(
+          "send_activity",
+          otel.kind = "client",
+          activity_id = activity.id.0,
+          domain = domain.as_str(),
+        );
+        let res = task.sign_and_send(&context).instrument(span).await;
+        drop(permit);
+        let Err(e) = res else {
+          break;
+        };
         fail_count += 1;
-        report.send(SendActivityResult::Failure {
-          fail_count,
-          // activity_id: activity.id,
-        })?;
+        has_failed = true;
+        if let Err(db_err) =
+          SentActivityDelivery::record_failure(pool, activity.id, instance_id, &e.to_string()).await
+        {
+          tracing::warn!("{domain}: failed to store delivery status: {db_err}");
+        }
+        if let Some(report) = &mut report {
+          report.send(SendActivityResult::Failure {
+            fail_count,
+            // activity_id: activity.id,
+          })?;
+        }
         let retry_delay = federate_retry_sleep_duration(fail_count);
         tracing::info!(
           "{}: retrying {:?} attempt {} with delay {retry_delay:.2?}. ({e})",
@@ -148,11 +187,18 @@ impl SendRetryTask<'_> {
         }
       }
     }
-    report.send(SendActivityResult::Success(SendSuccessInfo {
-      activity_id: activity.id,
-      published_at: Some(activity.published_at),
-      was_skipped: false,
-    }))?;
+    if has_failed
+      && let Err(e) = SentActivityDelivery::mark_delivered(pool, activity.id, instance_id).await
+    {
+      tracing::warn!("{domain}: failed to store delivery status: {e}");
+    }
+    if let Some(report) = report {
+      report.send(SendActivityResult::Success(SendSuccessInfo {
+        activity_id: activity.id,
+        published_at: Some(activity.published_at),
+        was_skipped: false,
+      }))?;
+    }
     Ok(())
   }
 }
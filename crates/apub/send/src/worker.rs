@@ -1,5 +1,6 @@
 use crate::{
   inboxes::RealCommunityInboxCollector,
+  limiter::SendLimiter,
   send::{SendActivityResult, SendRetryTask, SendSuccessInfo},
   util::{
     FederationQueueStateWithDomain,
@@ -17,14 +18,12 @@ use lemmy_db_schema::{
   source::{
     federation_queue_state::FederationQueueState,
     instance::{Instance, InstanceForm},
+    sent_activity_delivery::SentActivityDelivery,
   },
 };
+use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::connection::{ActualDbPool, DbPool};
-use lemmy_utils::{
-  error::LemmyResult,
-  federate_retry_sleep_duration,
-  settings::structs::FederationWorkerConfig,
-};
+use lemmy_utils::{error::LemmyResult, federate_retry_sleep_duration};
 use std::{cmp::max, collections::BinaryHeap, ops::Add, time::Duration};
 use tokio::{
   sync::mpsc::{self, UnboundedSender},
@@ -41,6 +40,8 @@ static SAVE_STATE_EVERY_TIME: Duration = Duration::from_secs(60);
 static SAVE_STATE_EVERY_TIME: Duration = Duration::from_secs(0);
 /// Maximum number of successful sends to allow out of order
 const MAX_SUCCESSFULS: usize = 1000;
+/// Maximum number of replayed activities which are sent at once
+const MAX_REPLAYS: i64 = 100;
 
 /// in prod mode, try to collect multiple send results at the same time to reduce load
 #[cfg(not(test))]
@@ -62,7 +63,6 @@ pub(crate) struct InstanceWorker {
   instance: Instance,
   stop: CancellationToken,
   federation_lib_config: FederationConfig<LemmyContext>,
-  federation_worker_config: FederationWorkerConfig,
   state: FederationQueueState,
   last_state_insert: DateTime<Utc>,
   pool: ActualDbPool,
@@ -78,15 +78,16 @@ pub(crate) struct InstanceWorker {
   successfuls: BinaryHeap<SendSuccessInfo>,
   // number of activities that currently have a task spawned to send it
   in_flight: i8,
+  limiter: SendLimiter,
 }
 
 impl InstanceWorker {
   pub(crate) async fn init_and_loop(
     instance: Instance,
     config: FederationConfig<LemmyContext>,
-    federation_worker_config: FederationWorkerConfig,
     stop: CancellationToken,
     stats_sender: UnboundedSender<FederationQueueStateWithDomain>,
+    limiter: SendLimiter,
   ) -> LemmyResult<()> {
     let pool = config.to_request_data().inner_pool().clone();
     let state = FederationQueueState::load(&mut DbPool::Pool(&pool), instance.id).await?;
@@ -98,7 +99,6 @@ impl InstanceWorker {
         instance.id,
         instance.domain.clone(),
       ),
-      federation_worker_config,
       instance,
       stop,
       federation_lib_config: config,
@@ -110,6 +110,7 @@ impl InstanceWorker {
       report_send_result,
       successfuls: BinaryHeap::<SendSuccessInfo>::new(),
       in_flight: 0,
+      limiter,
     };
 
     worker.loop_until_stopped().await
@@ -122,13 +123,18 @@ impl InstanceWorker {
     let mut last_sent_id = self.get_last_sent_id().await?;
 
     while !self.stop.is_cancelled() {
+      // read on every iteration, so that changes of the site settings apply without a restart
+      let concurrent_sends = SiteView::read_local(&mut self.pool())
+        .await?
+        .local_site
+        .federation_concurrent_sends_per_instance;
       // check if we need to wait for a send to finish before sending the next one
       // we wait if (a) the last request failed, only if a request is already in flight (not at the
       // start of the loop) or (b) if we have too many successfuls in memory or (c) if we have
       // too many in flight
       let need_wait_for_event = (self.in_flight != 0 && self.state.fail_count > 0)
         || self.successfuls.len() >= MAX_SUCCESSFULS
-        || self.in_flight >= self.federation_worker_config.concurrent_sends_per_instance;
+        || i32::from(self.in_flight) >= concurrent_sends;
       if need_wait_for_event || self.receive_send_result.len() > MIN_ACTIVITY_SEND_RESULTS_TO_HANDLE
       {
         // if len() > 0 then this does not block and allows us to write to db more often
@@ -178,7 +184,8 @@ impl InstanceWorker {
             newest_id.0
           );
         }
-        // no more work to be done, wait before rechecking
+        // no more work to be done, use the time to send replays and wait before rechecking
+        self.spawn_replays().await?;
         tokio::select! {
           () = sleep(*WORK_FINISHED_RECHECK_DELAY) => {},
           () = self.stop.cancelled() => {
@@ -392,17 +399,21 @@ impl InstanceWorker {
     let data = self.federation_lib_config.to_request_data();
     let stop = self.stop.clone();
     let domain = self.instance.domain.clone();
+    let instance_id = self.instance.id;
     let mut report = self.report_send_result.clone();
+    let limiter = self.limiter.clone();
     tokio::spawn(async move {
       let res = SendRetryTask {
         activity: &ele,
         object: &ele.data,
         inbox_urls,
-        report: &mut report,
+        report: Some(&mut report),
         initial_fail_count,
         domain,
-        context: data,
+        instance_id,
+        context: data.clone(),
         stop,
+        limiter,
       }
       .send_retry_loop()
       .await;
@@ -412,6 +423,14 @@ impl InstanceWorker {
           ele.ap_id,
           e
         );
+        SentActivityDelivery::record_failure(
+          &mut data.pool(),
+          activity_id,
+          instance_id,
+          &e.to_string(),
+        )
+        .await
+        .ok();
         // An error in this location means there is some deeper internal issue with the activity,
         // for example the actor can't be loaded or similar. These issues are probably not
         // solveable by retrying and would cause the federation for this instance to permanently be
@@ -429,6 +448,51 @@ impl InstanceWorker {
     Ok(())
   }
 
+  /// Sends activities again which an admin marked for replay, after they failed to be delivered.
+  /// These are sent independently of the regular queue and don't affect its state.
+  async fn spawn_replays(&mut self) -> LemmyResult<()> {
+    if self.state.fail_count > 0 {
+      return Ok(());
+    }
+    let activity_ids =
+      SentActivityDelivery::take_replays(&mut self.pool(), self.instance.id, MAX_REPLAYS).await?;
+    for activity_id in activity_ids {
+      let Ok(Some(ele)) = get_activity_cached(&mut self.pool(), activity_id).await else {
+        continue;
+      };
+      let inbox_urls = self.inbox_collector.get_inbox_urls(&ele).await?;
+      if inbox_urls.is_empty() {
+        continue;
+      }
+      tracing::debug!("{}: replaying {:?}", self.instance.domain, activity_id);
+      let context = self.federation_lib_config.to_request_data();
+      let stop = self.stop.clone();
+      let domain = self.instance.domain.clone();
+      let instance_id = self.instance.id;
+      let limiter = self.limiter.clone();
+      tokio::spawn(async move {
+        let res = SendRetryTask {
+          activity: &ele,
+          object: &ele.data,
+          inbox_urls,
+          report: None,
+          initial_fail_count: 0,
+          domain,
+          instance_id,
+          context,
+          stop,
+          limiter,
+        }
+        .send_retry_loop()
+        .await;
+        if let Err(e) = res {
+          tracing::warn!("replaying {} errored internally: {:?}", ele.ap_id, e);
+        }
+      });
+    }
+    Ok(())
+  }
+
   async fn save_and_send_state(&mut self) -> Result<()> {
     tracing::debug!("{}: saving and sending state", self.instance.domain);
     self.last_state_insert = Utc::now();
@@ -460,9 +524,13 @@ mod test {
   use actix_web::{App, HttpResponse, HttpServer, dev::ServerHandle, web};
   use futures::future::try_join_all;
   use lemmy_api_utils::utils::generate_inbox_url;
-  use lemmy_db_schema::source::{
-    activity::{SentActivity, SentActivityForm},
-    person::{Person, PersonInsertForm},
+  use lemmy_db_schema::{
+    source::{
+      activity::{SentActivity, SentActivityForm},
+      local_site::{LocalSite, LocalSiteUpdateForm},
+      person::{Person, PersonInsertForm},
+    },
+    test_data::TestData,
   };
   use lemmy_db_schema_file::enums::ActorType;
   use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
@@ -482,6 +550,7 @@ mod test {
     context: activitypub_federation::config::Data<LemmyContext>,
     instance: Instance,
     person: Person,
+    site_data: Option<TestData>,
     stats_receiver: UnboundedReceiver<FederationQueueStateWithDomain>,
     inbox_receiver: UnboundedReceiver<String>,
     cancel: CancellationToken,
@@ -519,15 +588,18 @@ mod test {
         .and_then(|s| s.parse().ok())
         .unwrap_or(10);
 
-      let fed_config = FederationWorkerConfig {
-        concurrent_sends_per_instance,
+      let site_data = TestData::create(&mut context.pool()).await?;
+      let form = LocalSiteUpdateForm {
+        federation_concurrent_sends_per_instance: Some(concurrent_sends_per_instance),
+        ..Default::default()
       };
+      LocalSite::update(&mut context.pool(), &form).await?;
       spawn(InstanceWorker::init_and_loop(
         instance.clone(),
         context.clone(),
-        fed_config,
         cancel.clone(),
         stats_sender,
+        SendLimiter::default(),
       ));
       // wait for startup
       sleep(*WORK_FINISHED_RECHECK_DELAY).await;
@@ -536,6 +608,7 @@ mod test {
         context: context.to_request_data(),
         instance,
         person,
+        site_data: Some(site_data),
         stats_receiver,
         inbox_receiver,
         cancel,
@@ -553,6 +626,9 @@ mod test {
       self.cleaned_up = true;
       self.cancel.cancel();
       sleep(*WORK_FINISHED_RECHECK_DELAY).await;
+      if let Some(site_data) = self.site_data.take() {
+        site_data.delete(&mut self.context.pool()).await?;
+      }
       Instance::delete_all(&mut self.context.pool()).await?;
       Person::delete(&mut self.context.pool(), self.person.id).await?;
       self.wait_stop_server.stop(true).await;
@@ -3,13 +3,17 @@ use crate::{
   newtypes::ActivityId,
   source::activity::{ReceivedActivity, SentActivity, SentActivityForm},
 };
-use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{insert_into, max},
+};
 use diesel_async::RunQueryDsl;
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
 };
-use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError};
 
 impl SentActivity {
   pub async fn create(pool: &mut DbPool<'_>, form: SentActivityForm) -> LemmyResult<Self> {
@@ -40,6 +44,17 @@ impl SentActivity {
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
+
+  /// The id of the newest sent activity, which the federation workers are catching up to.
+  pub async fn read_latest_id(pool: &mut DbPool<'_>) -> LemmyResult<Option<ActivityId>> {
+    use lemmy_db_schema_file::schema::sent_activity::dsl::{id, sent_activity};
+    let conn = &mut get_conn(pool).await?;
+    sent_activity
+      .select(max(id))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 }
 
 impl ReceivedActivity {
@@ -56,9 +71,19 @@ impl ReceivedActivity {
       // new activity inserted successfully
       Ok(())
     } else {
-      Err(LemmyErrorType::CouldntCreate.into())
+      Err(UntranslatedError::DuplicateActivity.into())
     }
   }
+
+  /// Forget a received activity, so that it can be processed again if the sender retries it.
+  pub async fn delete(pool: &mut DbPool<'_>, ap_id_: &DbUrl) -> LemmyResult<usize> {
+    use lemmy_db_schema_file::schema::received_activity::dsl::{ap_id, received_activity};
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(received_activity.filter(ap_id.eq(ap_id_)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
 }
 
 #[cfg(test)]
@@ -83,7 +108,14 @@ mod tests {
     // inserting activity should only work once
     ReceivedActivity::create(pool, &ap_id).await?;
     let second = ReceivedActivity::create(pool, &ap_id).await;
-    assert!(second.is_err());
+    assert_eq!(
+      Some(UntranslatedError::DuplicateActivity.into()),
+      second.err().map(|e| e.error_type)
+    );
+
+    // after deleting it can be received again
+    assert_eq!(1, ReceivedActivity::delete(pool, &ap_id).await?);
+    ReceivedActivity::create(pool, &ap_id).await?;
 
     Ok(())
   }
@@ -111,12 +143,13 @@ mod tests {
       send_inboxes: vec![],
     };
 
-    SentActivity::create(pool, form).await?;
+    let sent = SentActivity::create(pool, form).await?;
 
     let res = SentActivity::read_from_apub_id(pool, &ap_id).await?;
     assert_eq!(res.ap_id, ap_id);
     assert_eq!(res.data, data);
     assert_eq!(res.sensitive, sensitive);
+    assert_eq!(Some(sent.id), SentActivity::read_latest_id(pool).await?);
 
     Ok(())
   }
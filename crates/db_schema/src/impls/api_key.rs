@@ -0,0 +1,148 @@
+use crate::{
+  newtypes::{ApiKeyId, LocalUserId},
+  source::api_key::{ApiKey, ApiKeyInsertForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::api_key;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl ApiKey {
+  pub async fn create(pool: &mut DbPool<'_>, form: &ApiKeyInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(api_key::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read_from_hash(pool: &mut DbPool<'_>, key_hash: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    api_key::table
+      .filter(api_key::key_hash.eq(key_hash))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotLoggedIn)
+  }
+
+  pub async fn list_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    api_key::table
+      .filter(api_key::local_user_id.eq(local_user_id))
+      .order(api_key::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Revoke a single key of the given user.
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    id: ApiKeyId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      api_key::table
+        .find(id)
+        .filter(api_key::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Revoke all keys of the given user on password reset/change, or account deletion.
+  pub async fn delete_all_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(api_key::table.filter(api_key::local_user_id.eq(local_user_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    api_key::{ApiKey, ApiKeyInsertForm},
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_db_schema_file::enums::TokenScope;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_keys() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "bertha bot");
+    let person = Person::create(pool, &person_form).await?;
+    let user = LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let other_person_form = PersonInsertForm::test_form(inserted_instance.id, "oscar other");
+    let other_person = Person::create(pool, &other_person_form).await?;
+    let other_user = LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(other_person.id),
+      vec![],
+    )
+    .await?;
+
+    let form = ApiKeyInsertForm {
+      rate_limit: Some(10),
+      ..ApiKeyInsertForm::new(
+        user.id,
+        "my bot".to_string(),
+        "hash".to_string(),
+        TokenScope::Read,
+      )
+    };
+    let api_key = ApiKey::create(pool, &form).await?;
+    assert_eq!(TokenScope::Read, api_key.scope);
+    assert_eq!(Some(10), api_key.rate_limit);
+
+    assert_eq!(api_key, ApiKey::read_from_hash(pool, "hash").await?);
+    assert!(ApiKey::read_from_hash(pool, "other").await.is_err());
+    assert_eq!(
+      vec![api_key.clone()],
+      ApiKey::list_for_user(pool, user.id).await?
+    );
+    assert!(ApiKey::list_for_user(pool, other_user.id).await?.is_empty());
+
+    // Keys of other users can't be deleted
+    assert_eq!(0, ApiKey::delete(pool, other_user.id, api_key.id).await?);
+    assert_eq!(1, ApiKey::delete(pool, user.id, api_key.id).await?);
+    assert!(ApiKey::read_from_hash(pool, "hash").await.is_err());
+
+    let second_form = ApiKeyInsertForm::new(
+      user.id,
+      "second bot".to_string(),
+      "second hash".to_string(),
+      TokenScope::Write,
+    );
+    ApiKey::create(pool, &second_form).await?;
+    assert_eq!(1, ApiKey::delete_all_for_user(pool, user.id).await?);
+    assert!(ApiKey::list_for_user(pool, user.id).await?.is_empty());
+
+    Person::delete(pool, person.id).await?;
+    Person::delete(pool, other_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
@@ -16,6 +16,7 @@ use chrono::{DateTime, Utc};
 use diesel::{
   ExpressionMethods,
   JoinOnDsl,
+  NullableExpressionMethods,
   QueryDsl,
   dsl::{insert_into, not},
   expression::SelectableHelper,
@@ -33,7 +34,7 @@ use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
   traits::Crud,
-  utils::functions::{coalesce, hot_rank},
+  utils::functions::{coalesce, hot_rank, search_headline},
 };
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
@@ -42,6 +43,25 @@ use lemmy_utils::{
 use url::Url;
 
 impl Comment {
+  /// Excerpts of the comments with the matches of the search term highlighted.
+  pub async fn read_search_snippets(
+    pool: &mut DbPool<'_>,
+    comment_ids: &[CommentId],
+    search_term: &str,
+  ) -> LemmyResult<Vec<(CommentId, String)>> {
+    let conn = &mut get_conn(pool).await?;
+
+    comment::table
+      .filter(comment::id.eq_any(comment_ids))
+      .select((
+        comment::id,
+        search_headline(comment::content.nullable(), search_term),
+      ))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn permadelete_for_creator(
     pool: &mut DbPool<'_>,
     creator_id: PersonId,
@@ -521,6 +541,7 @@ mod tests {
       unresolved_report_count: 0,
       federation_pending: false,
       locked: false,
+      changed_at: inserted_comment.changed_at,
     };
 
     let child_comment_form = CommentInsertForm::new(
@@ -18,29 +18,34 @@ use crate::{
   traits::{ApubActor, Bannable, Blockable, Followable},
   utils::{format_actor_url, queries::filters::filter_is_subscribed},
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   NullableExpressionMethods,
   QueryDsl,
+  TextExpressionMethods,
+  delete,
   dsl::{exists, insert_into, not},
   expression::SelectableHelper,
   select,
   update,
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   PersonId,
   enums::{CommunityFollowerState, CommunityNotificationsMode, CommunityVisibility, ListingType},
-  schema::{comment, community, community_actions, instance, local_user, post},
+  schema::{comment, community, community_actions, community_alias, instance, local_user, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
   traits::Crud,
-  utils::functions::{coalesce, coalesce_2_nullable, lower, random_smallint},
+  utils::{
+    functions::{coalesce, coalesce_2_nullable, lower, random_smallint},
+    prefix_search,
+  },
 };
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
@@ -119,6 +124,11 @@ pub enum CollectionType {
 }
 
 impl Community {
+  /// Posts which link to the same url and were published after this time count as duplicates.
+  pub fn duplicate_url_since(&self) -> DateTime<Utc> {
+    Utc::now() - TimeDelta::days(self.duplicate_url_days.into())
+  }
+
   pub async fn insert_apub(
     pool: &mut DbPool<'_>,
     timestamp: DateTime<Utc>,
@@ -148,6 +158,83 @@ impl Community {
     Ok(community_)
   }
 
+  /// Changes the name of a local community. The ap_id stays the same, and the previous name is
+  /// kept as alias so that existing links, webfinger handles and follows continue to work. The
+  /// other changes in `form` are written in the same update.
+  pub async fn rename(
+    pool: &mut DbPool<'_>,
+    community: &Community,
+    new_name: &str,
+    form: &CommunityUpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let community_id = community.id;
+    let old_name = community.name.clone();
+    let new_name = new_name.to_string();
+    conn
+      .run_transaction(|conn| {
+        async move {
+          // The community may be renamed back to a previous name
+          delete(community_alias::table)
+            .filter(community_alias::community_id.eq(community_id))
+            .filter(lower(community_alias::name).eq(new_name.to_lowercase()))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          insert_into(community_alias::table)
+            .values((
+              community_alias::name.eq(old_name),
+              community_alias::community_id.eq(community_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          update(community::table.find(community_id))
+            .set((community::name.eq(new_name), form))
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Communities whose name starts with the given prefix, for autocompletion. Unlisted and
+  /// quarantined communities are left out, the biggest communities are listed first.
+  pub async fn list_by_name_prefix(
+    pool: &mut DbPool<'_>,
+    name_prefix: &str,
+    domain_prefix: Option<&str>,
+    logged_in: bool,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = community::table
+      .inner_join(instance::table)
+      .filter(lower(community::name).like(prefix_search(name_prefix)))
+      .filter(community::deleted.eq(false))
+      .filter(community::removed.eq(false))
+      .filter(community::quarantined.eq(false))
+      .filter(community::visibility.ne(CommunityVisibility::Unlisted))
+      .select(community::all_columns)
+      .into_boxed();
+    if !logged_in {
+      query = query.filter(community::visibility.ne(CommunityVisibility::LocalOnlyPrivate));
+    }
+    if let Some(domain_prefix) = domain_prefix {
+      query = query.filter(lower(instance::domain).like(prefix_search(domain_prefix)));
+    }
+    query
+      .order_by(community::subscribers.desc())
+      .then_order_by(community::name)
+      .limit(limit)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   /// Get the community which has a given moderators or featured url, also return the collection
   /// type
   pub async fn get_by_collection_url(
@@ -220,7 +307,8 @@ impl Community {
         .filter(not(
           community::deleted
             .or(community::removed)
-            .or(community::visibility.eq(CommunityVisibility::Private)),
+            .or(community::visibility.eq(CommunityVisibility::Private))
+            .or(community::quarantined),
         ))
         .order(community::random_number.asc())
         .select(community::id)
@@ -266,6 +354,26 @@ impl Community {
       .and(community::deleted.eq(false))
   }
 
+  /// Remote communities with local subscribers which were not refreshed since the given time,
+  /// oldest first.
+  pub async fn list_stale_remote(
+    pool: &mut DbPool<'_>,
+    refreshed_before: DateTime<Utc>,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community::table
+      .filter(community::local.eq(false))
+      .filter(Self::hide_removed_and_deleted())
+      .filter(community::subscribers_local.gt(0))
+      .filter(community::last_refreshed_at.lt(refreshed_before))
+      .order_by(community::last_refreshed_at.asc())
+      .limit(limit)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn update_federated_followers(
     pool: &mut DbPool<'_>,
     for_community_id: CommunityId,
@@ -424,9 +532,12 @@ impl CommunityActions {
     Ok(())
   }
 
+  /// Local users who want to be notified about new posts (or comments if `is_post` is false) in
+  /// the community, up to `limit` of them.
   pub async fn list_subscribers(
     community_id: CommunityId,
     is_post: bool,
+    limit: i64,
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<Vec<PersonId>> {
     let conn = &mut get_conn(pool).await?;
@@ -435,6 +546,8 @@ impl CommunityActions {
       .inner_join(local_user::table.on(community_actions::person_id.eq(local_user::person_id)))
       .filter(community_actions::community_id.eq(community_id))
       .select(local_user::person_id)
+      .order_by(local_user::person_id)
+      .limit(limit)
       .into_boxed();
     if is_post {
       query = query.filter(
@@ -627,10 +740,11 @@ impl ApubActor for Community {
     include_deleted: bool,
   ) -> LemmyResult<Option<Self>> {
     let conn = &mut get_conn(pool).await?;
+    let community_name = community_name.to_lowercase();
     let mut q = community::table
       .inner_join(instance::table)
       .into_boxed()
-      .filter(lower(community::name).eq(community_name.to_lowercase()))
+      .filter(lower(community::name).eq(&community_name))
       .select(community::all_columns);
     if !include_deleted {
       q = q.filter(Self::hide_removed_and_deleted())
@@ -640,6 +754,27 @@ impl ApubActor for Community {
     } else {
       q = q.filter(community::local.eq(true))
     }
+    let community = q
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    if community.is_some() {
+      return Ok(community);
+    }
+
+    // Local communities may have been renamed, in that case use the previous name as alias
+    let mut q = community_alias::table
+      .inner_join(community::table.inner_join(instance::table))
+      .into_boxed()
+      .filter(lower(community_alias::name).eq(&community_name))
+      .select(community::all_columns);
+    if !include_deleted {
+      q = q.filter(Self::hide_removed_and_deleted())
+    }
+    if let Some(domain) = domain {
+      q = q.filter(lower(instance::domain).eq(domain.to_lowercase()))
+    }
     q.first(conn)
       .await
       .optional()
@@ -682,9 +817,10 @@ mod tests {
       person::{Person, PersonInsertForm},
       post::{Post, PostInsertForm},
     },
-    traits::{Bannable, Followable},
+    traits::{ApubActor, Bannable, Followable},
     utils::RANK_DEFAULT,
   };
+  use lemmy_db_schema_file::enums::DuplicateUrlPolicy;
   use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -751,6 +887,12 @@ mod tests {
       unresolved_report_count: 0,
       interactions_month: 0,
       local_removed: false,
+      quarantined: false,
+      duplicate_url_policy: DuplicateUrlPolicy::Allow,
+      duplicate_url_days: 30,
+      pseudonymous_posting: false,
+      default_post_sort_type: None,
+      default_comment_sort_type: None,
     };
 
     let community_follower_form = CommunityFollowerForm::new(
@@ -980,4 +1122,39 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rename() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let form = CommunityInsertForm::new(
+      instance.id,
+      "old_name".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &form).await?;
+
+    let renamed = Community::rename(pool, &community, "new_name", &Default::default()).await?;
+    assert_eq!("new_name", renamed.name);
+    assert_eq!(community.ap_id, renamed.ap_id);
+
+    // Both the new and the previous name can be used
+    let by_new_name = Community::read_from_name(pool, "new_name", None, false).await?;
+    assert_eq!(Some(community.id), by_new_name.map(|c| c.id));
+    let by_alias = Community::read_from_name(pool, "Old_Name", None, false).await?;
+    assert_eq!(Some(community.id), by_alias.map(|c| c.id));
+
+    // Renaming back removes the alias again
+    let renamed = Community::rename(pool, &renamed, "old_name", &Default::default()).await?;
+    assert_eq!("old_name", renamed.name);
+    let by_new_name = Community::read_from_name(pool, "new_name", None, false).await?;
+    assert_eq!(Some(community.id), by_new_name.map(|c| c.id));
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
 }
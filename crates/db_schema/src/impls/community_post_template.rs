@@ -0,0 +1,157 @@
+use crate::{
+  newtypes::{CommunityId, CommunityPostTemplateId},
+  source::community_post_template::{
+    CommunityPostTemplate,
+    CommunityPostTemplateInsertForm,
+    CommunityPostTemplateUpdateForm,
+  },
+};
+use diesel::{ExpressionMethods, QueryDsl, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::community_post_template;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for CommunityPostTemplate {
+  type InsertForm = CommunityPostTemplateInsertForm;
+  type UpdateForm = CommunityPostTemplateUpdateForm;
+  type IdType = CommunityPostTemplateId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_post_template::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    id: CommunityPostTemplateId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_post_template::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl CommunityPostTemplate {
+  pub async fn list_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community_post_template::table
+      .filter(community_post_template::community_id.eq(community_id))
+      .order(community_post_template::name)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Markdown headings of the template body, which are lines starting with `#`.
+  fn headings(&self) -> impl Iterator<Item = &str> {
+    self
+      .body
+      .iter()
+      .flat_map(|b| b.lines())
+      .map(str::trim)
+      .filter(|l| l.starts_with('#'))
+  }
+
+  /// If headings are enforced, checks that the post body contains each of them on its own line.
+  pub fn check_post_body(&self, body: Option<&str>) -> LemmyResult<()> {
+    if !self.enforce_headings {
+      return Ok(());
+    }
+    let lines: Vec<_> = body.iter().flat_map(|b| b.lines()).map(str::trim).collect();
+    if self.headings().all(|h| lines.contains(&h)) {
+      Ok(())
+    } else {
+      Err(LemmyErrorType::MissingPostTemplateHeadings.into())
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    community_post_template::{
+      CommunityPostTemplate,
+      CommunityPostTemplateInsertForm,
+      CommunityPostTemplateUpdateForm,
+    },
+    instance::Instance,
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_templates() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let community_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "post_template_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let form = CommunityPostTemplateInsertForm {
+      title: Some("[Bug] ".to_string()),
+      body: Some("## Steps to reproduce\n\n## Expected behaviour\n".to_string()),
+      enforce_headings: Some(true),
+      ..CommunityPostTemplateInsertForm::new(community.id, "Bug report".to_string())
+    };
+    let bug_report = CommunityPostTemplate::create(pool, &form).await?;
+    // Names are unique per community
+    assert!(CommunityPostTemplate::create(pool, &form).await.is_err());
+    let form = CommunityPostTemplateInsertForm::new(community.id, "Announcement".to_string());
+    let announcement = CommunityPostTemplate::create(pool, &form).await?;
+
+    let templates = CommunityPostTemplate::list_for_community(pool, community.id).await?;
+    assert_eq!(vec![announcement.clone(), bug_report.clone()], templates);
+
+    let body = "## Steps to reproduce\nClick\n  ## Expected behaviour  \nNothing";
+    assert!(bug_report.check_post_body(Some(body)).is_ok());
+    assert!(
+      bug_report
+        .check_post_body(Some("## Steps to reproduce"))
+        .is_err()
+    );
+    assert!(bug_report.check_post_body(None).is_err());
+    assert!(announcement.check_post_body(None).is_ok());
+
+    let form = CommunityPostTemplateUpdateForm {
+      enforce_headings: Some(false),
+      ..Default::default()
+    };
+    let bug_report = CommunityPostTemplate::update(pool, bug_report.id, &form).await?;
+    assert!(bug_report.check_post_body(None).is_ok());
+
+    CommunityPostTemplate::delete(pool, bug_report.id).await?;
+    assert_eq!(
+      vec![announcement],
+      CommunityPostTemplate::list_for_community(pool, community.id).await?
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,155 @@
+use crate::{
+  newtypes::CommunityId,
+  source::{
+    community_pseudonym::{CommunityPseudonym, CommunityPseudonymInsertForm},
+    person::{Person, PersonInsertForm},
+  },
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, insert_into};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::{PersonId, schema::community_pseudonym};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommunityPseudonym {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &CommunityPseudonymInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_pseudonym::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Creates the person for a new pseudonym together with its mapping, so that no unused person
+  /// remains if a concurrent request created the pseudonym first.
+  pub async fn create_with_person(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    person_id: PersonId,
+    pseudonym_form: &PersonInsertForm,
+  ) -> LemmyResult<Person> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let pseudonym = Person::create(&mut conn.into(), pseudonym_form).await?;
+          let form = CommunityPseudonymInsertForm::new(community_id, person_id, pseudonym.id);
+          Self::create(&mut conn.into(), &form).await?;
+          Ok(pseudonym)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// The pseudonym of the person in the community, if they already used it.
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    person_id: PersonId,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community_pseudonym::table
+      .find((community_id, person_id))
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Reveals the real person behind a pseudonym. Returns None for normal persons.
+  pub async fn read_for_pseudonym(
+    pool: &mut DbPool<'_>,
+    pseudonym_id: PersonId,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community_pseudonym::table
+      .filter(community_pseudonym::pseudonym_id.eq(pseudonym_id))
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    community_pseudonym::{CommunityPseudonym, CommunityPseudonymInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pseudonym() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "pseudonym_owner");
+    let person = Person::create(pool, &person_form).await?;
+    let pseudonym_form = PersonInsertForm::test_form(inserted_instance.id, "anon_pseudonym");
+    let pseudonym = Person::create(pool, &pseudonym_form).await?;
+    let community_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "pseudonym_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    assert_eq!(
+      None,
+      CommunityPseudonym::read(pool, community.id, person.id).await?
+    );
+
+    let form = CommunityPseudonymInsertForm::new(community.id, person.id, pseudonym.id);
+    let inserted = CommunityPseudonym::create(pool, &form).await?;
+    // Each person has only one pseudonym per community
+    assert!(CommunityPseudonym::create(pool, &form).await.is_err());
+
+    assert_eq!(
+      Some(&inserted),
+      CommunityPseudonym::read(pool, community.id, person.id)
+        .await?
+        .as_ref()
+    );
+    assert_eq!(
+      Some(person.id),
+      CommunityPseudonym::read_for_pseudonym(pool, pseudonym.id)
+        .await?
+        .map(|p| p.person_id)
+    );
+    assert_eq!(
+      None,
+      CommunityPseudonym::read_for_pseudonym(pool, person.id).await?
+    );
+
+    // If the mapping already exists, the new person is rolled back as well
+    let duplicate_form = PersonInsertForm {
+      local: Some(true),
+      ..PersonInsertForm::test_form(inserted_instance.id, "anon_duplicate")
+    };
+    let duplicate =
+      CommunityPseudonym::create_with_person(pool, community.id, person.id, &duplicate_form).await;
+    assert!(duplicate.is_err());
+    Person::check_username_taken(pool, "anon_duplicate").await?;
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,323 @@
+use crate::{
+  newtypes::{CommunityId, CommunityRecurringPostId, PostId},
+  source::{
+    community_recurring_post::{
+      CommunityRecurringPost,
+      CommunityRecurringPostInsertForm,
+      CommunityRecurringPostUpdateForm,
+    },
+    post::{Post, PostInsertForm},
+  },
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  JoinOnDsl,
+  QueryDsl,
+  SelectableHelper,
+  dsl::{exists, not},
+  insert_into,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::{
+  community,
+  community_actions,
+  community_recurring_post,
+  instance_actions,
+  person,
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for CommunityRecurringPost {
+  type InsertForm = CommunityRecurringPostInsertForm;
+  type UpdateForm = CommunityRecurringPostUpdateForm;
+  type IdType = CommunityRecurringPostId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_recurring_post::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    id: CommunityRecurringPostId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_recurring_post::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl CommunityRecurringPost {
+  pub async fn list_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community_recurring_post::table
+      .filter(community_recurring_post::community_id.eq(community_id))
+      .order(community_recurring_post::next_post_at.asc())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Recurring posts which need to be created now. Skips those whose creator is no longer a
+  /// moderator, or whose community was deleted or removed.
+  pub async fn list_due(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    // Creators are local, so their home instance is the local one
+    let site_ban = instance_actions::table
+      .find((person::id, person::instance_id))
+      .filter(instance_actions::received_ban_at.is_not_null());
+    community_recurring_post::table
+      .inner_join(community::table)
+      .inner_join(
+        community_actions::table.on(
+          community_actions::community_id
+            .eq(community_recurring_post::community_id)
+            .and(community_actions::person_id.eq(community_recurring_post::creator_id)),
+        ),
+      )
+      .inner_join(person::table)
+      .filter(community_recurring_post::next_post_at.le(Utc::now()))
+      .filter(community_actions::became_moderator_at.is_not_null())
+      .filter(community::deleted.eq(false))
+      .filter(community::removed.eq(false))
+      // The creator must not be deleted, or banned from the community or the site
+      .filter(person::deleted.eq(false))
+      .filter(community_actions::received_ban_at.is_null())
+      .filter(not(exists(site_ban)))
+      .select(Self::as_select())
+      .order(community_recurring_post::next_post_at.asc())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Creates the post and schedules the next one in a single transaction, so that the post is
+  /// never created twice.
+  pub async fn create_post(
+    pool: &mut DbPool<'_>,
+    id: CommunityRecurringPostId,
+    form: &PostInsertForm,
+    next_post_at: DateTime<Utc>,
+  ) -> LemmyResult<Post> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let post = Post::create(&mut conn.into(), form).await?;
+          Self::mark_posted(&mut conn.into(), id, next_post_at, post.id).await?;
+          Ok(post)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Stores the newly created post, and schedules the next one.
+  pub async fn mark_posted(
+    pool: &mut DbPool<'_>,
+    id: CommunityRecurringPostId,
+    next_post_at: DateTime<Utc>,
+    post_id: PostId,
+  ) -> LemmyResult<Self> {
+    let form = CommunityRecurringPostUpdateForm {
+      next_post_at: Some(next_post_at),
+      last_post_id: Some(Some(post_id)),
+      ..Default::default()
+    };
+    Self::update(pool, id, &form).await
+  }
+
+  /// Title of the post which is created at `next_post_at`, with placeholders replaced.
+  pub fn post_title(&self) -> String {
+    self
+      .title
+      .replace("{date}", &self.next_post_at.format("%Y-%m-%d").to_string())
+      .replace("{weekday}", &self.next_post_at.format("%A").to_string())
+  }
+
+  /// The first time after `now` which is a whole number of intervals after `next_post_at`. This
+  /// skips any posts which were missed, for example because the server was down.
+  pub fn following_post_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+    let interval_days = i64::from(self.interval_days.max(1));
+    let elapsed_days = (now - self.next_post_at).num_days();
+    let intervals = (elapsed_days / interval_days + 1).max(1);
+    self.next_post_at + TimeDelta::days(intervals * interval_days)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    source::{
+      community::{
+        Community,
+        CommunityActions,
+        CommunityInsertForm,
+        CommunityModeratorForm,
+        CommunityPersonBanForm,
+      },
+      community_recurring_post::{
+        CommunityRecurringPost,
+        CommunityRecurringPostInsertForm,
+        CommunityRecurringPostUpdateForm,
+      },
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::Bannable,
+  };
+  use chrono::{TimeDelta, TimeZone, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_recurring_posts() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "recurring_post");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "recurring_post".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let now = Utc::now();
+    let form = CommunityRecurringPostInsertForm {
+      body: Some("What are you working on?".to_string()),
+      feature: Some(true),
+      ..CommunityRecurringPostInsertForm::new(
+        community.id,
+        person.id,
+        "Weekly thread {date}".to_string(),
+        7,
+        now - TimeDelta::minutes(1),
+      )
+    };
+    let weekly = CommunityRecurringPost::create(pool, &form).await?;
+    let form = CommunityRecurringPostInsertForm::new(
+      community.id,
+      person.id,
+      "{weekday} thread".to_string(),
+      1,
+      now + TimeDelta::hours(1),
+    );
+    let daily = CommunityRecurringPost::create(pool, &form).await?;
+
+    // Only created once the creator is a moderator
+    assert!(CommunityRecurringPost::list_due(pool).await?.is_empty());
+    let moderator_form = CommunityModeratorForm::new(community.id, person.id);
+    CommunityActions::join(pool, &moderator_form).await?;
+
+    assert_eq!(
+      vec![weekly.clone(), daily.clone()],
+      CommunityRecurringPost::list_for_community(pool, community.id).await?
+    );
+    assert_eq!(
+      vec![weekly.id],
+      CommunityRecurringPost::list_due(pool)
+        .await?
+        .iter()
+        .map(|r| r.id)
+        .collect::<Vec<_>>()
+    );
+
+    // Not created while the creator is banned from the community
+    let ban_form = CommunityPersonBanForm::new(community.id, person.id);
+    CommunityActions::ban(pool, &ban_form).await?;
+    assert!(CommunityRecurringPost::list_due(pool).await?.is_empty());
+    CommunityActions::unban(pool, &ban_form).await?;
+    assert_eq!(1, CommunityRecurringPost::list_due(pool).await?.len());
+
+    let post_form = PostInsertForm::new(weekly.post_title(), person.id, community.id);
+    let next_post_at = weekly.following_post_at(now);
+    assert_eq!(weekly.next_post_at + TimeDelta::days(7), next_post_at);
+    let post =
+      CommunityRecurringPost::create_post(pool, weekly.id, &post_form, next_post_at).await?;
+    let weekly = CommunityRecurringPost::read(pool, weekly.id).await?;
+    assert_eq!(Some(post.id), weekly.last_post_id);
+    assert!(CommunityRecurringPost::list_due(pool).await?.is_empty());
+
+    let form = CommunityRecurringPostUpdateForm {
+      interval_days: Some(14),
+      ..Default::default()
+    };
+    let weekly = CommunityRecurringPost::update(pool, weekly.id, &form).await?;
+    assert_eq!(14, weekly.interval_days);
+
+    // Deleting the post keeps the recurring post
+    Post::delete(pool, post.id).await?;
+    let weekly = CommunityRecurringPost::read(pool, weekly.id).await?;
+    assert_eq!(None, weekly.last_post_id);
+
+    CommunityRecurringPost::delete(pool, daily.id).await?;
+    assert_eq!(
+      vec![weekly],
+      CommunityRecurringPost::list_for_community(pool, community.id).await?
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_post_title_and_interval() -> LemmyResult<()> {
+    let next_post_at = Utc
+      .with_ymd_and_hms(2024, 3, 4, 12, 0, 0)
+      .single()
+      .ok_or(LemmyErrorType::InvalidUnixTime)?;
+    let recurring = CommunityRecurringPost {
+      id: Default::default(),
+      community_id: Default::default(),
+      creator_id: Default::default(),
+      title: "{weekday} thread ({date})".to_string(),
+      body: None,
+      interval_days: 3,
+      next_post_at,
+      feature: false,
+      last_post_id: None,
+      published_at: next_post_at,
+      updated_at: None,
+    };
+    assert_eq!("Monday thread (2024-03-04)", recurring.post_title());
+
+    // Missed posts are skipped
+    let now = next_post_at + TimeDelta::days(7);
+    assert_eq!(
+      next_post_at + TimeDelta::days(9),
+      recurring.following_post_at(now)
+    );
+    assert_eq!(
+      next_post_at + TimeDelta::days(3),
+      recurring.following_post_at(next_post_at)
+    );
+
+    Ok(())
+  }
+}
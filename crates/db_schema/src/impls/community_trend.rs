@@ -0,0 +1,155 @@
+use crate::source::community_trend::CommunityTrend;
+use diesel::{delete, sql_query};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::community_trend;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommunityTrend {
+  /// Recalculates the activity of all communities. Returns the number of communities with recent
+  /// activity.
+  pub async fn refresh(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(community_trend::table)
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          // Posts count more than comments and new subscribers more than both. The score compares
+          // the activity of the last day with the daily average of the last week, multiplied with
+          // the activity so that small communities don't trend with a single post.
+          sql_query(
+            r#"WITH posts AS (
+                   SELECT community_id,
+                       count(*) FILTER (WHERE published_at > now() - interval '1 day') AS day,
+                       count(*) AS week
+                   FROM post
+                   WHERE published_at > now() - interval '1 week' AND NOT deleted AND NOT removed
+                   GROUP BY community_id),
+               comments AS (
+                   SELECT post.community_id,
+                       count(*) FILTER (WHERE comment.published_at > now() - interval '1 day') AS day,
+                       count(*) AS week
+                   FROM comment
+                   INNER JOIN post ON post.id = comment.post_id
+                   WHERE comment.published_at > now() - interval '1 week'
+                       AND NOT comment.deleted AND NOT comment.removed
+                   GROUP BY post.community_id),
+               subscribers AS (
+                   SELECT community_id,
+                       count(*) FILTER (WHERE followed_at > now() - interval '1 day') AS day,
+                       count(*) AS week
+                   FROM community_actions
+                   WHERE followed_at > now() - interval '1 week'
+                   GROUP BY community_id),
+               counts AS (
+                   SELECT community.id AS community_id,
+                       coalesce(posts.day, 0) AS posts_day,
+                       coalesce(posts.week, 0) AS posts_week,
+                       coalesce(comments.day, 0) AS comments_day,
+                       coalesce(comments.week, 0) AS comments_week,
+                       coalesce(subscribers.day, 0) AS subscribers_day,
+                       coalesce(subscribers.week, 0) AS subscribers_week
+                   FROM community
+                   LEFT JOIN posts ON posts.community_id = community.id
+                   LEFT JOIN comments ON comments.community_id = community.id
+                   LEFT JOIN subscribers ON subscribers.community_id = community.id
+                   WHERE NOT community.deleted AND NOT community.removed
+                       AND coalesce(posts.week, 0) + coalesce(comments.week, 0)
+                           + coalesce(subscribers.week, 0) > 0),
+               activity AS (
+                   SELECT *,
+                       posts_day * 4 + comments_day + subscribers_day * 8 AS day,
+                       posts_week * 4 + comments_week + subscribers_week * 8 AS week
+                   FROM counts)
+               INSERT INTO community_trend (community_id, posts_day, posts_week, comments_day,
+                   comments_week, subscribers_day, subscribers_week, score)
+               SELECT community_id, posts_day, posts_week, comments_day, comments_week,
+                   subscribers_day, subscribers_week,
+                   (day * day / (week / 7.0 + 1))::real
+               FROM activity"#,
+          )
+          .execute(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    community_trend::CommunityTrend,
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+  };
+  use diesel::QueryDsl;
+  use diesel_async::RunQueryDsl;
+  use lemmy_db_schema_file::schema::community_trend;
+  use lemmy_diesel_utils::{
+    connection::{build_db_pool_for_tests, get_conn},
+    traits::Crud,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_refresh_community_trends() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "tina trender");
+    let inserted_person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "trending_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &community_form).await?;
+    let quiet_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "quiet_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let quiet_community = Community::create(pool, &quiet_form).await?;
+
+    for title in ["first", "second"] {
+      let form = PostInsertForm::new(title.into(), inserted_person.id, inserted_community.id);
+      Post::create(pool, &form).await?;
+    }
+
+    CommunityTrend::refresh(pool).await?;
+    let conn = &mut get_conn(pool).await?;
+    let trend: CommunityTrend = community_trend::table
+      .find(inserted_community.id)
+      .first(conn)
+      .await?;
+    assert_eq!(2, trend.posts_day);
+    assert_eq!(2, trend.posts_week);
+    assert_eq!(0, trend.comments_week);
+    assert!(trend.score > 0.0);
+
+    // Communities without any activity aren't included
+    let quiet_trend = community_trend::table
+      .find(quiet_community.id)
+      .first::<CommunityTrend>(conn)
+      .await;
+    assert!(quiet_trend.is_err());
+
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
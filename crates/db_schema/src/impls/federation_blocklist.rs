@@ -10,6 +10,9 @@ impl FederationBlockList {
     let conn = &mut get_conn(pool).await?;
     insert_into(federation_blocklist::table)
       .values(form)
+      .on_conflict(federation_blocklist::instance_id)
+      .do_update()
+      .set(form)
       .get_result::<Self>(conn)
       .await
       .with_lemmy_type(LemmyErrorType::CouldntCreate)
@@ -1,7 +1,18 @@
-use crate::source::federation_queue_state::FederationQueueState;
-use diesel::{ExpressionMethods, Insertable, OptionalExtension, QueryDsl, SelectableHelper};
+use crate::{newtypes::ActivityId, source::federation_queue_state::FederationQueueState};
+use chrono::{DateTime, Utc};
+use diesel::{
+  ExpressionMethods,
+  Insertable,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  dsl::{max, min},
+};
 use diesel_async::RunQueryDsl;
-use lemmy_db_schema_file::{InstanceId, schema::federation_queue_state};
+use lemmy_db_schema_file::{
+  InstanceId,
+  schema::{federation_queue_state, sent_activity},
+};
 use lemmy_diesel_utils::connection::{DbPool, get_conn};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -25,6 +36,36 @@ impl FederationQueueState {
         }),
     )
   }
+  pub async fn list(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    federation_queue_state::table
+      .select(FederationQueueState::as_select())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Publish time of the oldest activity which wasn't sent to any instance yet. If it is old, the
+  /// federation workers are most likely not running.
+  pub async fn oldest_unsent_activity(pool: &mut DbPool<'_>) -> LemmyResult<Option<DateTime<Utc>>> {
+    let conn = &mut get_conn(pool).await?;
+    let last_successful_id: Option<ActivityId> = federation_queue_state::table
+      .select(max(federation_queue_state::last_successful_id))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    // Without any federated instances, there is nothing to send
+    let Some(last_successful_id) = last_successful_id else {
+      return Ok(None);
+    };
+    sent_activity::table
+      .filter(sent_activity::id.gt(last_successful_id))
+      .select(min(sent_activity::published_at))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn upsert(pool: &mut DbPool<'_>, state: &FederationQueueState) -> LemmyResult<usize> {
     let conn = &mut get_conn(pool).await?;
 
@@ -0,0 +1,165 @@
+use crate::{
+  newtypes::PostId,
+  source::hashtag::{Hashtag, HashtagInsertForm, PostHashtag},
+  utils::limit_fetch,
+};
+use diesel::{
+  ExpressionMethods,
+  PgTextExpressionMethods,
+  QueryDsl,
+  SelectableHelper,
+  delete,
+  dsl::count,
+  insert_into,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::{hashtag, post_hashtag};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::fuzzy_search,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Hashtag {
+  /// Lists hashtags with the most posts first, optionally filtered by a search term.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    search_term: Option<&str>,
+    limit: Option<i64>,
+  ) -> LemmyResult<Vec<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let conn = &mut get_conn(pool).await?;
+    let mut query = hashtag::table
+      .inner_join(post_hashtag::table)
+      .group_by(hashtag::id)
+      .select(Self::as_select())
+      .order_by(count(post_hashtag::post_id).desc())
+      .then_order_by(hashtag::id)
+      .limit(limit)
+      .into_boxed();
+    if let Some(search_term) = search_term {
+      let search_term = search_term.trim_start_matches('#').to_lowercase();
+      query = query.filter(hashtag::name.ilike(fuzzy_search(&search_term)));
+    }
+    query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl PostHashtag {
+  /// Replaces the hashtags of the given post. Hashtags which don't exist yet are created.
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    hashtags: &[String],
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = hashtags
+      .iter()
+      .map(|name| HashtagInsertForm { name: name.clone() })
+      .collect::<Vec<_>>();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(post_hashtag::table.filter(post_hashtag::post_id.eq(post_id)))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::Deleted)?;
+          if forms.is_empty() {
+            return Ok(vec![]);
+          }
+
+          insert_into(hashtag::table)
+            .values(&forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          let hashtag_ids = hashtag::table
+            .filter(hashtag::name.eq_any(hashtags))
+            .select(hashtag::id)
+            .load(conn)
+            .await?;
+
+          let forms = hashtag_ids
+            .into_iter()
+            .map(|hashtag_id| PostHashtag {
+              post_id,
+              hashtag_id,
+            })
+            .collect::<Vec<_>>();
+          insert_into(post_hashtag::table)
+            .values(forms)
+            .returning(Self::as_select())
+            .get_results(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_hashtags() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "hashtag_person");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "hashtag_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("A test post".into(), person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let post_form_2 = PostInsertForm::new("Another test post".into(), person.id, community.id);
+    let post_2 = Post::create(pool, &post_form_2).await?;
+
+    let tags = vec!["lemmy".to_string(), "rust".to_string()];
+    assert_eq!(2, PostHashtag::update(pool, post.id, &tags).await?.len());
+    let tags_2 = vec!["rust".to_string()];
+    assert_eq!(
+      1,
+      PostHashtag::update(pool, post_2.id, &tags_2).await?.len()
+    );
+
+    // Most used hashtag comes first
+    let list = Hashtag::list(pool, None, None).await?;
+    let names = list.iter().map(|h| h.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(vec!["rust", "lemmy"], names);
+
+    let search = Hashtag::list(pool, Some("#Lem"), None).await?;
+    assert_eq!(1, search.len());
+
+    // Removing hashtags from a post
+    assert!(PostHashtag::update(pool, post.id, &[]).await?.is_empty());
+    let list = Hashtag::list(pool, None, None).await?;
+    assert_eq!(1, list.len());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -5,18 +5,22 @@ use crate::source::images::{
   LocalImageForm,
   RemoteImage,
 };
+use chrono::{DateTime, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   QueryDsl,
-  dsl::exists,
+  QueryableByName,
+  dsl::{exists, sql},
   insert_into,
   select,
+  sql_query,
+  sql_types::{BigInt, Text, Timestamptz},
 };
 use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
 use lemmy_db_schema_file::{
   PersonId,
-  schema::{image_details, local_image, remote_image},
+  schema::{community, image_details, local_image, person, remote_image},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -25,6 +29,16 @@ use lemmy_diesel_utils::{
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 use url::Url;
 
+/// Matches links to local images, both the current `/api/v4/image/{alias}` format and the old
+/// `/pictrs/image/{alias}` one. The first group is the pictrs alias.
+const IMAGE_ALIAS_REGEX: &str = r"/(?:pictrs|api/v[0-9]+)/image/([\w.-]+)";
+
+#[derive(QueryableByName)]
+struct OrphanedAlias {
+  #[diesel(sql_type = Text)]
+  pictrs_alias: String,
+}
+
 impl LocalImage {
   pub async fn create(
     pool: &mut DbPool<'_>,
@@ -78,6 +92,83 @@ impl LocalImage {
       .with_lemmy_type(LemmyErrorType::Deleted)
   }
 
+  /// Total size of the uploads by a person. Generated post thumbnails are not counted.
+  pub async fn used_bytes(pool: &mut DbPool<'_>, person_id: PersonId) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    local_image::table
+      .filter(local_image::person_id.eq(person_id))
+      .filter(local_image::thumbnail_for_post_id.is_null())
+      .select(sql::<BigInt>("coalesce(sum(size_bytes), 0)::bigint"))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Uploads which were published before the given time, and which aren't used anywhere anymore.
+  /// This is checked by searching for image links in all posts, comments, private messages, user
+  /// profiles, communities, sites, custom emojis and taglines. Content which was deleted by its
+  /// creator doesn't count as usage.
+  pub async fn list_orphaned(
+    pool: &mut DbPool<'_>,
+    published_before: DateTime<Utc>,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    // Raw `sql_query` is used, because Diesel doesn't support set returning functions like
+    // `regexp_matches`
+    let aliases: Vec<String> = sql_query(
+      r#"WITH content (link) AS (
+             SELECT url FROM post WHERE NOT deleted
+             UNION ALL SELECT thumbnail_url FROM post WHERE NOT deleted
+             UNION ALL SELECT body FROM post WHERE NOT deleted
+             UNION ALL SELECT content FROM comment WHERE NOT deleted
+             UNION ALL SELECT content FROM private_message WHERE NOT deleted
+             UNION ALL SELECT avatar FROM person
+             UNION ALL SELECT banner FROM person
+             UNION ALL SELECT bio FROM person
+             UNION ALL SELECT icon FROM community
+             UNION ALL SELECT banner FROM community
+             UNION ALL SELECT sidebar FROM community
+             UNION ALL SELECT icon FROM site
+             UNION ALL SELECT banner FROM site
+             UNION ALL SELECT sidebar FROM site
+             UNION ALL SELECT image_url FROM custom_emoji
+             UNION ALL SELECT content FROM tagline),
+           used (alias) AS (
+             SELECT (regexp_matches(link, $1, 'g'))[1] FROM content WHERE link IS NOT NULL)
+         SELECT pictrs_alias FROM local_image
+         WHERE published_at < $2 AND pictrs_alias NOT IN (SELECT alias FROM used)
+         ORDER BY published_at
+         LIMIT $3"#,
+    )
+    .bind::<Text, _>(IMAGE_ALIAS_REGEX)
+    .bind::<Timestamptz, _>(published_before)
+    .bind::<BigInt, _>(limit)
+    .get_results::<OrphanedAlias>(conn)
+    .await?
+    .into_iter()
+    .map(|a| a.pictrs_alias)
+    .collect();
+
+    local_image::table
+      .filter(local_image::pictrs_alias.eq_any(aliases))
+      .order_by(local_image::published_at)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Aliases of all uploads, oldest first.
+  pub async fn list_all_aliases(pool: &mut DbPool<'_>) -> LemmyResult<Vec<String>> {
+    let conn = &mut get_conn(pool).await?;
+    local_image::table
+      .select(local_image::pictrs_alias)
+      .order_by(local_image::published_at)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   /// Delete many aliases. Should be used with a pictrs purge.
   pub async fn delete_by_aliases(pool: &mut DbPool<'_>, aliases: &[String]) -> LemmyResult<usize> {
     let conn = &mut get_conn(pool).await?;
@@ -114,6 +205,43 @@ impl RemoteImage {
     .then_some(())
     .ok_or(LemmyErrorType::NotFound.into())
   }
+
+  pub async fn read(pool: &mut DbPool<'_>, link_: &DbUrl) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    remote_image::table
+      .find(link_)
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn set_cached_alias(
+    pool: &mut DbPool<'_>,
+    link_: &DbUrl,
+    alias: Option<&str>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(remote_image::table.find(link_))
+      .set(remote_image::cached_alias.eq(alias))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Checks if any remote person or community still uses the (proxied) image url as avatar, icon
+  /// or banner. The cached copy must not be removed in this case.
+  pub async fn is_used_by_actor(pool: &mut DbPool<'_>, url: &DbUrl) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    select(
+      exists(person::table.filter(person::avatar.eq(url).or(person::banner.eq(url)))).or(exists(
+        community::table.filter(community::icon.eq(url).or(community::banner.eq(url))),
+      )),
+    )
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 }
 
 impl ImageDetails {
@@ -127,4 +255,139 @@ impl ImageDetails {
       .await
       .with_lemmy_type(LemmyErrorType::CouldntCreate)
   }
+
+  pub async fn set_duration(
+    pool: &mut DbPool<'_>,
+    link_: &DbUrl,
+    duration_seconds: Option<i32>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(image_details::table.find(link_))
+      .set(image_details::duration_seconds.eq(duration_seconds))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    source::{
+      images::ImageDetailsInsertForm,
+      person::{Person, PersonUpdateForm},
+    },
+    test_data::TestData,
+  };
+  use chrono::Duration;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_remote_image_cached_alias() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let link = Url::parse("https://example.com/avatar.png")?;
+    RemoteImage::create(pool, vec![link.clone()]).await?;
+    let link: DbUrl = link.into();
+    assert_eq!(None, RemoteImage::read(pool, &link).await?.cached_alias);
+
+    RemoteImage::set_cached_alias(pool, &link, Some("abc.png")).await?;
+    assert_eq!(
+      Some("abc.png".to_string()),
+      RemoteImage::read(pool, &link).await?.cached_alias
+    );
+    assert!(!RemoteImage::is_used_by_actor(pool, &link).await?);
+
+    RemoteImage::set_cached_alias(pool, &link, None).await?;
+    assert_eq!(None, RemoteImage::read(pool, &link).await?.cached_alias);
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_used_bytes() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+    let person_id = data.person.id;
+    assert_eq!(0, LocalImage::used_bytes(pool, person_id).await?);
+
+    for (alias, size) in [("a.png", 1000), ("b.png", 500)] {
+      let form = LocalImageForm {
+        pictrs_alias: alias.to_string(),
+        person_id,
+        thumbnail_for_post_id: None,
+        size_bytes: Some(size),
+      };
+      let details_form = ImageDetailsInsertForm {
+        link: Url::parse(&format!("https://my_domain.tld/api/v4/image/{alias}"))?.into(),
+        width: 10,
+        height: 10,
+        content_type: "image/png".to_string(),
+        blurhash: None,
+        duration_seconds: None,
+      };
+      LocalImage::create(pool, &form, &details_form).await?;
+    }
+    assert_eq!(1500, LocalImage::used_bytes(pool, person_id).await?);
+
+    LocalImage::delete_by_alias(pool, "a.png").await?;
+    assert_eq!(500, LocalImage::used_bytes(pool, person_id).await?);
+
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_orphaned() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+    let person_id = data.person.id;
+
+    let mut links = vec![];
+    for alias in ["avatar.png", "orphan.png"] {
+      let form = LocalImageForm {
+        pictrs_alias: alias.to_string(),
+        person_id,
+        thumbnail_for_post_id: None,
+        size_bytes: None,
+      };
+      let link: DbUrl = Url::parse(&format!("https://my_domain.tld/api/v4/image/{alias}"))?.into();
+      let details_form = ImageDetailsInsertForm {
+        link: link.clone(),
+        width: 10,
+        height: 10,
+        content_type: "image/png".to_string(),
+        blurhash: None,
+        duration_seconds: None,
+      };
+      LocalImage::create(pool, &form, &details_form).await?;
+      links.push(link);
+    }
+    let person_form = PersonUpdateForm {
+      avatar: Some(links.first().cloned()),
+      ..Default::default()
+    };
+    Person::update(pool, person_id, &person_form).await?;
+
+    // Images uploaded after the cutoff are never returned
+    let orphaned = LocalImage::list_orphaned(pool, Utc::now() - Duration::days(1), 10).await?;
+    assert!(orphaned.is_empty());
+
+    let orphaned = LocalImage::list_orphaned(pool, Utc::now(), 10).await?;
+    let aliases: Vec<_> = orphaned.into_iter().map(|i| i.pictrs_alias).collect();
+    assert_eq!(vec!["orphan.png".to_string()], aliases);
+
+    LocalImage::delete_by_aliases(pool, &["avatar.png".to_string(), "orphan.png".to_string()])
+      .await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
 }
@@ -10,26 +10,31 @@ use crate::{
   },
   traits::Bannable,
 };
-use chrono::Utc;
+use chrono::{DateTime, TimeDelta, Utc};
 use diesel::{
+  BoolExpressionMethods,
   ExpressionMethods,
   NullableExpressionMethods,
   OptionalExtension,
   QueryDsl,
   SelectableHelper,
-  dsl::{count_star, exists, insert_into, not, select},
+  dsl::{count_star, exists, insert_into, max, not, select, update},
+  sql_query,
+  sql_types::{Integer, Text},
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
+  enums::FederationBlockSeverity,
   schema::{
     federation_allowlist,
     federation_blocklist,
     federation_queue_state,
     instance,
     instance_actions,
+    sent_activity,
   },
 };
 use lemmy_diesel_utils::{
@@ -107,6 +112,161 @@ impl Instance {
       .with_lemmy_type(LemmyErrorType::Deleted)
   }
 
+  /// Moves an instance to a new domain, for example after restoring a backup on another server or
+  /// when a remote instance announces that it moved. Urls are replaced in all text columns,
+  /// including links in posts and comments. The origins are given as protocol and hostname, like
+  /// `https://lemmy.ml`.
+  ///
+  /// An existing instance with the new domain is deleted first. For remote instances it was only
+  /// created by fetching the moved instance, and its data is replaced by that of the old domain.
+  pub async fn change_domain(
+    pool: &mut DbPool<'_>,
+    old_origin: &str,
+    new_origin: &str,
+    old_domain: &str,
+    new_domain: &str,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          diesel::delete(instance::table.filter(instance::domain.eq(new_domain)))
+            .execute(conn)
+            .await?;
+          update(instance::table.filter(instance::domain.eq(old_domain)))
+            .set(instance::domain.eq(new_domain))
+            .execute(conn)
+            .await?;
+          // The trailing slash prevents matching domains which only start with the old domain. The
+          // origins are passed as settings, as DO blocks can't have bind parameters.
+          sql_query(
+            "SELECT set_config('lemmy.old_origin', $1, true), \
+             set_config('lemmy.new_origin', $2, true)",
+          )
+          .bind::<Text, _>(format!("{old_origin}/"))
+          .bind::<Text, _>(format!("{new_origin}/"))
+          .execute(conn)
+          .await?;
+          sql_query(
+            r#"DO $$
+               DECLARE
+                   col record;
+               BEGIN
+                   FOR col IN
+                       SELECT table_name, column_name
+                       FROM information_schema.columns
+                       WHERE table_schema = 'public'
+                           AND data_type IN ('text', 'character varying')
+                           AND is_generated = 'NEVER'
+                           AND table_name NOT LIKE '\_\_%'
+                           AND table_name IN (
+                               SELECT table_name FROM information_schema.tables
+                               WHERE table_schema = 'public' AND table_type = 'BASE TABLE')
+                   LOOP
+                       EXECUTE format('UPDATE %I SET %I = replace(%I, $1, $2) WHERE %I LIKE $3',
+                           col.table_name, col.column_name, col.column_name, col.column_name)
+                       USING current_setting('lemmy.old_origin'),
+                           current_setting('lemmy.new_origin'),
+                           '%' || current_setting('lemmy.old_origin') || '%';
+                   END LOOP;
+               END $$"#,
+          )
+          .execute(conn)
+          .await?;
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Moves a remote instance to its new domain. Unlike [[Instance::change_domain]], this only
+  /// rewrites the activitypub ids and inboxes of the instance's own actors and their content, so
+  /// that the work is bounded by the size of that instance.
+  pub async fn change_remote_domain(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    old_origin: &str,
+    new_origin: &str,
+    new_domain: &str,
+  ) -> LemmyResult<()> {
+    // Columns with urls of the instance, for tables which reference it directly
+    const ACTOR_COLUMNS: &[(&str, &[&str])] = &[
+      ("site", &["ap_id", "inbox_url"]),
+      ("person", &["ap_id", "inbox_url"]),
+      (
+        "community",
+        &[
+          "ap_id",
+          "inbox_url",
+          "followers_url",
+          "moderators_url",
+          "featured_url",
+        ],
+      ),
+      ("multi_community", &["ap_id", "inbox_url", "following_url"]),
+    ];
+    // Content which is created by persons of the instance
+    const CONTENT_TABLES: &[&str] = &["post", "comment", "private_message"];
+
+    let old_prefix = format!("{old_origin}/");
+    let new_prefix = format!("{new_origin}/");
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          diesel::delete(
+            instance::table
+              .filter(instance::domain.eq(new_domain))
+              .filter(instance::id.ne(instance_id)),
+          )
+          .execute(conn)
+          .await?;
+          update(instance::table.find(instance_id))
+            .set(instance::domain.eq(new_domain))
+            .execute(conn)
+            .await?;
+          for (table, columns) in ACTOR_COLUMNS {
+            let set = columns
+              .iter()
+              .map(|c| format!("{c} = replace({c}, $1, $2)"))
+              .collect::<Vec<_>>()
+              .join(", ");
+            sql_query(format!("UPDATE {table} SET {set} WHERE instance_id = $3"))
+              .bind::<Text, _>(&old_prefix)
+              .bind::<Text, _>(&new_prefix)
+              .bind::<Integer, _>(instance_id)
+              .execute(conn)
+              .await?;
+          }
+          sql_query(
+            "UPDATE community_tag SET ap_id = replace(ap_id, $1, $2) \
+             WHERE community_id IN (SELECT id FROM community WHERE instance_id = $3)",
+          )
+          .bind::<Text, _>(&old_prefix)
+          .bind::<Text, _>(&new_prefix)
+          .bind::<Integer, _>(instance_id)
+          .execute(conn)
+          .await?;
+          for table in CONTENT_TABLES {
+            sql_query(format!(
+              "UPDATE {table} SET ap_id = replace(ap_id, $1, $2) \
+               WHERE creator_id IN (SELECT id FROM person WHERE instance_id = $3) \
+               AND starts_with(ap_id, $1)"
+            ))
+            .bind::<Text, _>(&old_prefix)
+            .bind::<Text, _>(&new_prefix)
+            .bind::<Integer, _>(instance_id)
+            .execute(conn)
+            .await?;
+          }
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
   pub async fn read_all(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Instance>> {
     let conn = &mut get_conn(pool).await?;
     instance::table
@@ -138,23 +298,102 @@ impl Instance {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Instances which are suspended, meaning no federation at all. Silenced instances are not
+  /// included as they are still federated with.
   pub async fn blocklist(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
     let conn = &mut get_conn(pool).await?;
     instance::table
       .inner_join(federation_blocklist::table)
+      .filter(federation_blocklist::severity.eq(FederationBlockSeverity::Suspend))
+      .select(Self::as_select())
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Marks instances as dead which were not reachable for the given number of days. An instance
+  /// counts as reachable when activities are delivered to it, or its nodeinfo can be fetched.
+  pub async fn mark_dead(pool: &mut DbPool<'_>, unreachable_days: i32) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      instance::table
+        .filter(instance::dead_since.is_null())
+        .filter(
+          coalesce(instance::updated_at, instance::published_at)
+            .lt(now() - unreachable_days.days()),
+        ),
+    )
+    .set((
+      instance::dead_since.eq(now().nullable()),
+      instance::next_probe_at.eq((now() + 1.days()).nullable()),
+    ))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Dead instances which are due to be checked if they are back online.
+  pub async fn list_dead_to_probe(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    instance::table
+      .filter(instance::dead_since.is_not_null())
+      .filter(instance::next_probe_at.le(now().nullable()))
       .select(Self::as_select())
       .get_results(conn)
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Schedules the next check of a dead instance after it was still unreachable. The interval
+  /// doubles with every check, from one day up to a month.
+  pub async fn schedule_next_probe(&self, pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let now = Utc::now();
+    let dead_for = now - self.dead_since.unwrap_or(now);
+    let next_probe_at = now + dead_for.clamp(TimeDelta::days(1), TimeDelta::days(30));
+    update(instance::table.find(self.id))
+      .set(instance::next_probe_at.eq(next_probe_at))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Marks a dead instance as alive again. Activities which were created while it was dead are
+  /// skipped, as they are mostly outdated by now.
+  pub async fn revive(pool: &mut DbPool<'_>, instance_id: InstanceId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(instance::table.find(instance_id))
+      .set((
+        instance::dead_since.eq(None::<DateTime<Utc>>),
+        instance::next_probe_at.eq(None::<DateTime<Utc>>),
+        instance::updated_at.eq(now().nullable()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    update(federation_queue_state::table.find(instance_id))
+      .set((
+        federation_queue_state::last_successful_id.eq(
+          sent_activity::table
+            .select(max(sent_activity::id))
+            .single_value(),
+        ),
+        federation_queue_state::fail_count.eq(0),
+        federation_queue_state::last_retry_at.eq(None::<DateTime<Utc>>),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
   /// returns a list of all instances, each with a flag of whether the instance is allowed or not
   /// and dead or not ordered by id
   pub async fn read_federated_with_blocked_and_dead(
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<Vec<(Self, bool, bool)>> {
     let conn = &mut get_conn(pool).await?;
-    let is_dead_expr = coalesce(instance::updated_at, instance::published_at).lt(now() - 3.days());
+    let is_dead_expr = instance::dead_since.is_not_null();
     // this needs to be done in two steps because the meaning of the "blocked" column depends on the
     // existence of any value at all in the allowlist. (so a normal join wouldn't work)
     let use_allowlist = federation_allowlist::table
@@ -178,7 +417,16 @@ impl Instance {
         .left_join(federation_blocklist::table)
         .select((
           Self::as_select(),
-          federation_blocklist::instance_id.nullable().is_null(),
+          // silenced instances are still federated with
+          federation_blocklist::instance_id
+            .nullable()
+            .is_null()
+            .or(
+              federation_blocklist::severity
+                .nullable()
+                .eq(FederationBlockSeverity::Silence),
+            )
+            .assume_not_null(),
           is_dead_expr,
         ))
         .order_by(instance::id)
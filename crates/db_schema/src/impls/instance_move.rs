@@ -0,0 +1,142 @@
+use crate::{
+  newtypes::InstanceMoveId,
+  source::instance_move::{InstanceMove, InstanceMoveInsertForm},
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{exists, insert_into, select},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{InstanceId, PersonId, schema::instance_move};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl InstanceMove {
+  pub async fn create(pool: &mut DbPool<'_>, form: &InstanceMoveInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(instance_move::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, id: InstanceMoveId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    instance_move::table
+      .find(id)
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Moves which still need to be approved or rejected by an admin, oldest first.
+  pub async fn list_pending(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    instance_move::table
+      .filter(instance_move::approved.is_null())
+      .order(instance_move::published_at.asc())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Whether the instance announced any move since the given time, regardless of its outcome.
+  pub async fn moved_since(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    since: DateTime<Utc>,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      instance_move::table
+        .filter(instance_move::instance_id.eq(instance_id))
+        .filter(instance_move::published_at.gt(since)),
+    ))
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Approves or rejects a pending move. Fails if it was already resolved.
+  pub async fn resolve(
+    pool: &mut DbPool<'_>,
+    id: InstanceMoveId,
+    approved: bool,
+    resolver_id: PersonId,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      instance_move::table
+        .find(id)
+        .filter(instance_move::approved.is_null()),
+    )
+    .set((
+      instance_move::approved.eq(approved),
+      instance_move::resolver_id.eq(resolver_id),
+      instance_move::resolved_at.eq(Utc::now()),
+    ))
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    instance::Instance,
+    instance_move::{InstanceMove, InstanceMoveInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{TimeDelta, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_instance_move() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "old-domain.tld").await?;
+    let admin_form = PersonInsertForm::test_form(instance.id, "move_admin");
+    let admin = Person::create(pool, &admin_form).await?;
+
+    let since = Utc::now() - TimeDelta::days(1);
+    assert!(!InstanceMove::moved_since(pool, instance.id, since).await?);
+
+    let form = InstanceMoveInsertForm::new(
+      instance.id,
+      "old-domain.tld".to_string(),
+      "new-domain.tld".to_string(),
+      "https://old-domain.tld".to_string(),
+      "https://new-domain.tld".to_string(),
+    );
+    let instance_move = InstanceMove::create(pool, &form).await?;
+    assert!(InstanceMove::moved_since(pool, instance.id, since).await?);
+    assert_eq!(
+      vec![instance_move.clone()],
+      InstanceMove::list_pending(pool).await?
+    );
+
+    let resolved = InstanceMove::resolve(pool, instance_move.id, false, admin.id).await?;
+    assert_eq!(Some(false), resolved.approved);
+    assert_eq!(Some(admin.id), resolved.resolver_id);
+    assert!(InstanceMove::list_pending(pool).await?.is_empty());
+    // Can only be resolved once
+    assert!(
+      InstanceMove::resolve(pool, instance_move.id, true, admin.id)
+        .await
+        .is_err()
+    );
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,98 @@
+use crate::{
+  newtypes::InstanceReportId,
+  source::instance_report::{InstanceReport, InstanceReportForm},
+  traits::Reportable,
+};
+use chrono::Utc;
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{insert_into, update},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{InstanceId, PersonId, schema::instance_report};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Reportable for InstanceReport {
+  type Form = InstanceReportForm;
+  type IdType = InstanceReportId;
+  type ObjectIdType = InstanceId;
+  /// creates an instance report and returns it
+  ///
+  /// * `conn` - the postgres connection
+  /// * `instance_report_form` - the filled InstanceReportForm to insert
+  async fn report(pool: &mut DbPool<'_>, form: &Self::Form) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(instance_report::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// resolve an instance report
+  ///
+  /// * `conn` - the postgres connection
+  /// * `report_id` - the id of the report to resolve
+  /// * `by_resolver_id` - the id of the user resolving the report
+  async fn update_resolved(
+    pool: &mut DbPool<'_>,
+    report_id_: Self::IdType,
+    by_resolver_id: PersonId,
+    is_resolved: bool,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(instance_report::table.find(report_id_))
+      .set((
+        instance_report::resolved.eq(is_resolved),
+        instance_report::resolver_id.eq(by_resolver_id),
+        instance_report::updated_at.eq(Utc::now()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  async fn resolve_apub(
+    pool: &mut DbPool<'_>,
+    object_id: Self::ObjectIdType,
+    report_creator_id: PersonId,
+    resolver_id: PersonId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      instance_report::table.filter(
+        instance_report::instance_id
+          .eq(object_id)
+          .and(instance_report::creator_id.eq(report_creator_id)),
+      ),
+    )
+    .set((
+      instance_report::resolved.eq(true),
+      instance_report::resolver_id.eq(resolver_id),
+      instance_report::updated_at.eq(Utc::now()),
+    ))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  async fn resolve_all_for_object(
+    pool: &mut DbPool<'_>,
+    instance_id: Self::ObjectIdType,
+    by_resolver_id: PersonId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(instance_report::table.filter(instance_report::instance_id.eq(instance_id)))
+      .set((
+        instance_report::resolved.eq(true),
+        instance_report::resolver_id.eq(by_resolver_id),
+        instance_report::updated_at.eq(Utc::now()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
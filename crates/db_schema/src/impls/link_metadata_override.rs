@@ -0,0 +1,126 @@
+use crate::source::link_metadata_override::{LinkMetadataOverride, LinkMetadataOverrideForm};
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl, delete, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::link_metadata_override;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use url::Url;
+
+impl LinkMetadataOverride {
+  /// Creates the override for the domain, or replaces the existing one.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &LinkMetadataOverrideForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(link_metadata_override::table)
+      .values(form)
+      .on_conflict(link_metadata_override::domain)
+      .do_update()
+      .set((
+        link_metadata_override::oembed_endpoint.eq(&form.oembed_endpoint),
+        link_metadata_override::disable_metadata.eq(form.disable_metadata),
+        link_metadata_override::disable_embed.eq(form.disable_embed),
+        link_metadata_override::updated_at.eq(Utc::now()),
+      ))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  pub async fn delete(pool: &mut DbPool<'_>, domain: &str) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(link_metadata_override::table.find(domain))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    link_metadata_override::table
+      .order_by(link_metadata_override::domain)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// The override which applies to the url. Overrides for subdomains take precedence over those
+  /// for parent domains.
+  pub async fn read_for_url(pool: &mut DbPool<'_>, url: &Url) -> LemmyResult<Option<Self>> {
+    let Some(domain) = url.domain() else {
+      return Ok(None);
+    };
+    let domains = parent_domains(domain);
+    let conn = &mut get_conn(pool).await?;
+    let mut overrides: Vec<Self> = link_metadata_override::table
+      .filter(link_metadata_override::domain.eq_any(&domains))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    overrides.sort_by_key(|o| std::cmp::Reverse(o.domain.len()));
+    Ok(overrides.into_iter().next())
+  }
+}
+
+/// The domain itself and all of its parent domains, eg `www.example.com` and `example.com`.
+fn parent_domains(domain: &str) -> Vec<String> {
+  let domain = domain.to_lowercase();
+  let mut domains = vec![domain.clone()];
+  let mut rest = domain.as_str();
+  while let Some((_, parent)) = rest.split_once('.') {
+    if parent.contains('.') {
+      domains.push(parent.to_string());
+    }
+    rest = parent;
+  }
+  domains
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[test]
+  fn test_parent_domains() {
+    assert_eq!(
+      vec![
+        "m.www.example.com".to_string(),
+        "www.example.com".to_string(),
+        "example.com".to_string()
+      ],
+      parent_domains("m.www.Example.com")
+    );
+    assert_eq!(vec!["localhost".to_string()], parent_domains("localhost"));
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_link_metadata_override() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let mut form = LinkMetadataOverrideForm {
+      domain: "example.com".to_string(),
+      oembed_endpoint: Some(Url::parse("https://example.com/oembed")?.into()),
+      disable_metadata: false,
+      disable_embed: false,
+    };
+    LinkMetadataOverride::upsert(pool, &form).await?;
+    form.oembed_endpoint = None;
+    form.disable_embed = true;
+    let updated = LinkMetadataOverride::upsert(pool, &form).await?;
+    assert_eq!(None, updated.oembed_endpoint);
+    assert!(updated.disable_embed);
+    assert_eq!(1, LinkMetadataOverride::list(pool).await?.len());
+
+    let url = Url::parse("https://video.example.com/watch")?;
+    let read = LinkMetadataOverride::read_for_url(pool, &url).await?;
+    assert_eq!(Some(updated), read);
+    let url = Url::parse("https://example.org/watch")?;
+    assert_eq!(None, LinkMetadataOverride::read_for_url(pool, &url).await?);
+
+    assert_eq!(1, LinkMetadataOverride::delete(pool, "example.com").await?);
+    Ok(())
+  }
+}
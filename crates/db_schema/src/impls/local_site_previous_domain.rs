@@ -0,0 +1,91 @@
+use crate::source::local_site_previous_domain::LocalSitePreviousDomain;
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::local_site_previous_domain;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::{
+  CacheLock,
+  build_cache,
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+};
+use std::sync::LazyLock;
+
+impl LocalSitePreviousDomain {
+  /// Stores the old domain after the instance moved. The new domain is removed, in case the
+  /// instance moves back to a domain which it used before.
+  pub async fn add(pool: &mut DbPool<'_>, old_domain: &str, new_domain: &str) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          diesel::delete(
+            local_site_previous_domain::table
+              .filter(local_site_previous_domain::domain.eq(new_domain)),
+          )
+          .execute(conn)
+          .await?;
+          insert_into(local_site_previous_domain::table)
+            .values(local_site_previous_domain::domain.eq(old_domain))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// All previous domains, which is checked for every request so it is cached.
+  pub async fn list_domains(pool: &mut DbPool<'_>) -> LemmyResult<Vec<String>> {
+    static CACHE: CacheLock<Vec<String>> = LazyLock::new(build_cache);
+    CACHE
+      .try_get_with((), async move {
+        let conn = &mut get_conn(pool).await?;
+        local_site_previous_domain::table
+          .select(local_site_previous_domain::domain)
+          .load(conn)
+          .await
+      })
+      .await
+      .map_err(|_e| LemmyErrorType::NotFound.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::local_site_previous_domain::LocalSitePreviousDomain;
+  use diesel_async::RunQueryDsl;
+  use lemmy_db_schema_file::schema::local_site_previous_domain;
+  use lemmy_diesel_utils::connection::{build_db_pool_for_tests, get_conn};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_previous_domains() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    LocalSitePreviousDomain::add(pool, "old.tld", "new.tld").await?;
+    assert_eq!(
+      vec!["old.tld".to_string()],
+      LocalSitePreviousDomain::list_domains(pool).await?
+    );
+
+    // Moving back removes the domain which is used again
+    LocalSitePreviousDomain::add(pool, "new.tld", "old.tld").await?;
+    assert_eq!(
+      vec!["new.tld".to_string()],
+      LocalSitePreviousDomain::list_domains(pool).await?
+    );
+
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(local_site_previous_domain::table)
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,106 @@
+use crate::source::local_site_rate_limit_override::{
+  LocalSiteRateLimitOverride,
+  LocalSiteRateLimitOverrideForm,
+};
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl, delete, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{
+  enums::{RateLimitAction, RateLimitUserClass},
+  schema::local_site_rate_limit_override,
+};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl LocalSiteRateLimitOverride {
+  /// Creates the override for the user class and action, or replaces the existing one.
+  pub async fn upsert(
+    pool: &mut DbPool<'_>,
+    form: &LocalSiteRateLimitOverrideForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(local_site_rate_limit_override::table)
+      .values(form)
+      .on_conflict((
+        local_site_rate_limit_override::user_class,
+        local_site_rate_limit_override::action,
+      ))
+      .do_update()
+      .set((
+        local_site_rate_limit_override::max_requests.eq(form.max_requests),
+        local_site_rate_limit_override::interval_seconds.eq(form.interval_seconds),
+        local_site_rate_limit_override::updated_at.eq(Utc::now()),
+      ))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    user_class: RateLimitUserClass,
+    action: RateLimitAction,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(local_site_rate_limit_override::table.find((user_class, action)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    local_site_rate_limit_override::table
+      .order_by((
+        local_site_rate_limit_override::user_class,
+        local_site_rate_limit_override::action,
+      ))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::local_site_rate_limit_override::{
+    LocalSiteRateLimitOverride,
+    LocalSiteRateLimitOverrideForm,
+  };
+  use lemmy_db_schema_file::enums::{RateLimitAction, RateLimitUserClass};
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rate_limit_overrides() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let mut form = LocalSiteRateLimitOverrideForm {
+      user_class: RateLimitUserClass::NewAccount,
+      action: RateLimitAction::Post,
+      max_requests: 1,
+      interval_seconds: 600,
+    };
+    LocalSiteRateLimitOverride::upsert(pool, &form).await?;
+    form.max_requests = 2;
+    let updated = LocalSiteRateLimitOverride::upsert(pool, &form).await?;
+    assert_eq!(2, updated.max_requests);
+    assert!(updated.updated_at.is_some());
+    assert_eq!(vec![updated], LocalSiteRateLimitOverride::list(pool).await?);
+
+    let deleted = LocalSiteRateLimitOverride::delete(
+      pool,
+      RateLimitUserClass::NewAccount,
+      RateLimitAction::Post,
+    )
+    .await?;
+    assert_eq!(1, deleted);
+    assert!(LocalSiteRateLimitOverride::list(pool).await?.is_empty());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,100 @@
+use crate::source::local_site_registration_ip_range::{
+  LocalSiteRegistrationIpRange,
+  LocalSiteRegistrationIpRangeForm,
+};
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::local_site_registration_ip_range;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl LocalSiteRegistrationIpRange {
+  /// Replaces either the allowed or the blocked ranges.
+  pub async fn replace(
+    pool: &mut DbPool<'_>,
+    allowed: bool,
+    ip_ranges: Vec<String>,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          diesel::delete(
+            local_site_registration_ip_range::table
+              .filter(local_site_registration_ip_range::allowed.eq(allowed)),
+          )
+          .execute(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::Deleted)?;
+
+          let forms = ip_ranges
+            .into_iter()
+            .map(|ip_range| LocalSiteRegistrationIpRangeForm { ip_range, allowed })
+            .collect::<Vec<_>>();
+
+          insert_into(local_site_registration_ip_range::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  pub async fn get_all(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    local_site_registration_ip_range::table
+      .get_results::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::local_site_registration_ip_range::LocalSiteRegistrationIpRange;
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_replace() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    LocalSiteRegistrationIpRange::replace(pool, false, vec!["10.0.0.0/8".to_string()]).await?;
+    LocalSiteRegistrationIpRange::replace(pool, true, vec!["10.1.0.0/16".to_string()]).await?;
+
+    // Replacing the blocked ranges keeps the allowed ones
+    LocalSiteRegistrationIpRange::replace(pool, false, vec!["192.0.2.0/24".to_string()]).await?;
+    let mut ranges: Vec<_> = LocalSiteRegistrationIpRange::get_all(pool)
+      .await?
+      .into_iter()
+      .map(|r| (r.ip_range, r.allowed))
+      .collect();
+    ranges.sort();
+    assert_eq!(
+      vec![
+        ("10.1.0.0/16".to_string(), true),
+        ("192.0.2.0/24".to_string(), false)
+      ],
+      ranges
+    );
+
+    LocalSiteRegistrationIpRange::replace(pool, false, vec![]).await?;
+    LocalSiteRegistrationIpRange::replace(pool, true, vec![]).await?;
+    assert!(
+      LocalSiteRegistrationIpRange::get_all(pool)
+        .await?
+        .is_empty()
+    );
+
+    Ok(())
+  }
+}
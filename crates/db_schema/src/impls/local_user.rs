@@ -7,10 +7,12 @@ use crate::{
   },
 };
 use bcrypt::{DEFAULT_COST, hash};
+use chrono::{DateTime, Timelike, Utc};
 use diesel::{
   CombineDsl,
   ExpressionMethods,
   JoinOnDsl,
+  NullableExpressionMethods,
   QueryDsl,
   dsl::{IntervalDsl, insert_into, not},
   result::Error,
@@ -144,6 +146,53 @@ impl LocalUser {
       .with_lemmy_type(LemmyErrorType::Deleted)
   }
 
+  /// Users whose account deletion grace period is over.
+  pub async fn list_deletion_due(pool: &mut DbPool<'_>) -> LemmyResult<Vec<LocalUserId>> {
+    let conn = &mut get_conn(pool).await?;
+    local_user::table
+      .inner_join(person::table)
+      .filter(local_user::deletion_scheduled_at.lt(now().nullable()))
+      .filter(not(person::deleted))
+      .select(local_user::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Number of accounts which were registered from the given ip during the last day.
+  pub async fn count_registrations_from_ip(pool: &mut DbPool<'_>, ip: &str) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    local_user::table
+      .inner_join(person::table)
+      .filter(local_user::registration_ip.eq(ip))
+      .filter(person::published_at.gt(now() - 1.days()))
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Removes the registration ip of accounts which are older than the retention period.
+  pub async fn clear_old_registration_ips(
+    pool: &mut DbPool<'_>,
+    retention_days: i32,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let old_persons = person::table
+      .filter(person::local)
+      .filter(person::published_at.lt(now() - retention_days.days()))
+      .select(person::id);
+    diesel::update(
+      local_user::table
+        .filter(local_user::registration_ip.is_not_null())
+        .filter(local_user::person_id.eq_any(old_persons)),
+    )
+    .set(local_user::registration_ip.eq(None::<String>))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
   pub async fn check_is_email_taken(pool: &mut DbPool<'_>, email: &str) -> LemmyResult<()> {
     use diesel::dsl::{exists, select};
     let conn = &mut get_conn(pool).await?;
@@ -309,6 +358,21 @@ impl LocalUser {
       Err(LemmyErrorType::NotHigherMod.into())
     }
   }
+
+  /// Returns true if the user enabled quiet hours, and the given time is within them.
+  pub fn is_quiet_hours(&self, time: DateTime<Utc>) -> bool {
+    if !self.quiet_hours_enabled {
+      return false;
+    }
+    let minute = i32::try_from(time.hour() * 60 + time.minute()).unwrap_or_default();
+    let (start, end) = (self.quiet_hours_start, self.quiet_hours_end);
+    if start <= end {
+      start <= minute && minute < end
+    } else {
+      // The quiet hours last over midnight
+      start <= minute || minute < end
+    }
+  }
 }
 
 /// Adds some helper functions for an optional LocalUser
@@ -402,13 +466,36 @@ pub struct UserBackupLists {
 mod tests {
   use crate::source::{
     instance::Instance,
-    local_user::{LocalUser, LocalUserInsertForm},
+    local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
     person::{Person, PersonInsertForm},
   };
+  use chrono::{DateTime, Days, Utc};
   use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
   use lemmy_utils::error::LemmyResult;
   use serial_test::serial;
 
+  #[test]
+  fn test_quiet_hours() -> LemmyResult<()> {
+    let time = |s: &str| DateTime::parse_from_rfc3339(s).map(|t| t.to_utc());
+    let mut local_user = LocalUser {
+      quiet_hours_start: 22 * 60,
+      quiet_hours_end: 7 * 60,
+      ..Default::default()
+    };
+    assert!(!local_user.is_quiet_hours(time("2026-05-11T23:00:00Z")?));
+
+    local_user.quiet_hours_enabled = true;
+    assert!(local_user.is_quiet_hours(time("2026-05-11T23:00:00Z")?));
+    assert!(local_user.is_quiet_hours(time("2026-05-11T06:59:00Z")?));
+    assert!(!local_user.is_quiet_hours(time("2026-05-11T07:00:00Z")?));
+
+    local_user.quiet_hours_start = 9 * 60;
+    local_user.quiet_hours_end = 17 * 60;
+    assert!(local_user.is_quiet_hours(time("2026-05-11T12:30:00Z")?));
+    assert!(!local_user.is_quiet_hours(time("2026-05-11T23:00:00Z")?));
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_admin_higher_check() -> LemmyResult<()> {
@@ -475,4 +562,78 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_deletion_due() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "deletion_due");
+    let person = Person::create(pool, &person_form).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    assert!(LocalUser::list_deletion_due(pool).await?.is_empty());
+
+    // Still in the grace period
+    let form = LocalUserUpdateForm {
+      deletion_scheduled_at: Some(Utc::now().checked_add_days(Days::new(7))),
+      ..Default::default()
+    };
+    LocalUser::update(pool, local_user.id, &form).await?;
+    assert!(LocalUser::list_deletion_due(pool).await?.is_empty());
+
+    let form = LocalUserUpdateForm {
+      deletion_scheduled_at: Some(Utc::now().checked_sub_days(Days::new(1))),
+      ..Default::default()
+    };
+    LocalUser::update(pool, local_user.id, &form).await?;
+    assert_eq!(
+      vec![local_user.id],
+      LocalUser::list_deletion_due(pool).await?
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_registration_ip() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "registration_ip");
+    let person = Person::create(pool, &person_form).await?;
+    let form = LocalUserInsertForm {
+      registration_ip: Some("192.0.2.1".to_string()),
+      ..LocalUserInsertForm::test_form(person.id)
+    };
+    LocalUser::create(pool, &form, vec![]).await?;
+    assert_eq!(
+      1,
+      LocalUser::count_registrations_from_ip(pool, "192.0.2.1").await?
+    );
+    assert_eq!(
+      0,
+      LocalUser::count_registrations_from_ip(pool, "192.0.2.2").await?
+    );
+
+    // The account is newer than the retention period
+    assert_eq!(0, LocalUser::clear_old_registration_ips(pool, 30).await?);
+    assert_eq!(1, LocalUser::clear_old_registration_ips(pool, 0).await?);
+    assert_eq!(
+      0,
+      LocalUser::count_registrations_from_ip(pool, "192.0.2.1").await?
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
 }
@@ -0,0 +1,191 @@
+use crate::{newtypes::LocalUserId, source::login_failure::LoginFailure};
+use chrono::{DateTime, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  IntoSql,
+  NullableExpressionMethods,
+  QueryDsl,
+  delete,
+  dsl::{IntervalDsl, exists, insert_into, now, select},
+  sql_types::Timestamptz,
+  update,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::login_failure;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl LoginFailure {
+  /// Counts a failed login for the account, and returns the new number of failures.
+  pub async fn record_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(login_failure::table)
+      .values(login_failure::local_user_id.eq(local_user_id))
+      .on_conflict(login_failure::local_user_id)
+      .do_update()
+      .set((
+        login_failure::failure_count.eq(login_failure::failure_count + 1),
+        login_failure::last_failure_at.eq(now),
+      ))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Counts a failed login from the ip, and returns the new number of failures.
+  pub async fn record_for_ip(pool: &mut DbPool<'_>, ip: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(login_failure::table)
+      .values(login_failure::ip.eq(ip))
+      .on_conflict(login_failure::ip)
+      .do_update()
+      .set((
+        login_failure::failure_count.eq(login_failure::failure_count + 1),
+        login_failure::last_failure_at.eq(now),
+      ))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn lock(
+    pool: &mut DbPool<'_>,
+    id: i32,
+    locked_until: DateTime<Utc>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(login_failure::table.find(id))
+      .set(login_failure::locked_until.eq(locked_until))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Whether logins for the account or from the ip are currently locked.
+  pub async fn is_locked(
+    pool: &mut DbPool<'_>,
+    local_user_id: Option<LocalUserId>,
+    ip: Option<&str>,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      login_failure::table
+        .filter(login_failure::locked_until.gt(now.into_sql::<Timestamptz>().nullable()))
+        .filter(
+          login_failure::local_user_id
+            .eq(local_user_id)
+            .or(login_failure::ip.eq(ip)),
+        ),
+    ))
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Resets the failures of the account after a successful login. Failures from the ip are kept,
+  /// so that one valid login doesn't hide guessing against other accounts.
+  pub async fn clear(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    delete(login_failure::table.filter(login_failure::local_user_id.eq(local_user_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)?;
+    Ok(())
+  }
+
+  /// Failures during the last day, with the most failures first.
+  pub async fn list_recent(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    login_failure::table
+      .filter(login_failure::last_failure_at.gt(now.into_sql::<Timestamptz>() - 1.days()))
+      .order_by(login_failure::failure_count.desc())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Forgets failures which are older than a day, and not locked anymore.
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      login_failure::table
+        .filter(login_failure::last_failure_at.lt(now.into_sql::<Timestamptz>() - 1.days()))
+        .filter(
+          login_failure::locked_until
+            .is_null()
+            .or(login_failure::locked_until.lt(now.into_sql::<Timestamptz>().nullable())),
+        ),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    login_failure::LoginFailure,
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{TimeDelta, Utc};
+  use diesel::{QueryDsl, delete};
+  use diesel_async::RunQueryDsl;
+  use lemmy_db_schema_file::schema::login_failure;
+  use lemmy_diesel_utils::{
+    connection::{build_db_pool_for_tests, get_conn},
+    traits::Crud,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_login_failure() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "login_failure");
+    let person = Person::create(pool, &person_form).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let ip = Some("192.0.2.1");
+
+    LoginFailure::record_for_user(pool, local_user.id).await?;
+    let failure = LoginFailure::record_for_user(pool, local_user.id).await?;
+    assert_eq!(2, failure.failure_count);
+    let ip_failure = LoginFailure::record_for_ip(pool, "192.0.2.1").await?;
+    assert_eq!(1, ip_failure.failure_count);
+    assert_eq!(2, LoginFailure::list_recent(pool).await?.len());
+    assert!(!LoginFailure::is_locked(pool, Some(local_user.id), ip).await?);
+
+    LoginFailure::lock(pool, failure.id, Utc::now() + TimeDelta::minutes(1)).await?;
+    assert!(LoginFailure::is_locked(pool, Some(local_user.id), None).await?);
+    // A different ip doesn't unlock the account
+    assert!(LoginFailure::is_locked(pool, Some(local_user.id), Some("192.0.2.2")).await?);
+    assert!(!LoginFailure::is_locked(pool, None, ip).await?);
+
+    LoginFailure::clear(pool, local_user.id).await?;
+    assert!(!LoginFailure::is_locked(pool, Some(local_user.id), ip).await?);
+    // Only the account failures are cleared
+    let remaining = LoginFailure::list_recent(pool).await?;
+    assert_eq!(1, remaining.len());
+    assert_eq!(Some(ip_failure.id), remaining.first().map(|f| f.id));
+
+    delete(login_failure::table.find(ip_failure.id))
+      .execute(&mut get_conn(pool).await?)
+      .await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
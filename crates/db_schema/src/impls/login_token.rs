@@ -1,12 +1,39 @@
 use crate::{
-  diesel::{ExpressionMethods, QueryDsl},
-  newtypes::LocalUserId,
-  source::login_token::{LoginToken, LoginTokenCreateForm},
+  diesel::{ExpressionMethods, NullableExpressionMethods, QueryDsl},
+  newtypes::{LocalUserId, LoginTokenId, OAuthApplicationId},
+  source::{
+    login_token::{LoginToken, LoginTokenCreateForm},
+    oauth_application::OAuthApplication,
+  },
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  IntoSql,
+  delete,
+  dsl::{IntervalDsl, now},
+  insert_into,
+  sql_types::Timestamptz,
+  update,
 };
-use diesel::{delete, dsl::exists, insert_into, select};
 use diesel_async::RunQueryDsl;
-use lemmy_db_schema_file::schema::login_token::{dsl::login_token, user_id};
-use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_db_schema_file::schema::{
+  login_token::{
+    dsl::login_token,
+    id,
+    last_used_at,
+    oauth_application_id,
+    refresh_token_expires_at,
+    refresh_token_hash,
+    token,
+    user_id,
+  },
+  oauth_application,
+  revoked_refresh_token,
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  sensitive::SensitiveString,
+};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 impl LoginToken {
@@ -19,20 +46,35 @@ impl LoginToken {
       .with_lemmy_type(LemmyErrorType::CouldntCreate)
   }
 
-  /// Check if the given token is valid for user.
+  /// Check if the given token is valid for user, and return it.
   pub async fn validate(
     pool: &mut DbPool<'_>,
     user_id_: LocalUserId,
     token_: &str,
-  ) -> LemmyResult<()> {
+  ) -> LemmyResult<Self> {
     let conn = &mut get_conn(pool).await?;
-    select(exists(
-      login_token.find(token_).filter(user_id.eq(user_id_)),
-    ))
-    .get_result::<bool>(conn)
-    .await?
-    .then_some(())
-    .ok_or(LemmyErrorType::NotLoggedIn.into())
+    login_token
+      .find(token_)
+      .filter(user_id.eq(user_id_))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotLoggedIn)
+  }
+
+  /// Remember that the token was used. To avoid writing on every request, this is only done if
+  /// the previous update was more than an hour ago.
+  pub async fn update_last_used(pool: &mut DbPool<'_>, token_: &str) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      login_token
+        .find(token_)
+        .filter(last_used_at.lt(now.into_sql::<Timestamptz>() - 1.hour())),
+    )
+    .set(last_used_at.eq(now))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
   }
 
   pub async fn list(pool: &mut DbPool<'_>, user_id_: LocalUserId) -> LemmyResult<Vec<LoginToken>> {
@@ -54,6 +96,19 @@ impl LoginToken {
       .with_lemmy_type(LemmyErrorType::Deleted)
   }
 
+  /// Revoke a single session of the given user.
+  pub async fn invalidate_by_id(
+    pool: &mut DbPool<'_>,
+    user_id_: LocalUserId,
+    id_: LoginTokenId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(login_token.filter(user_id.eq(user_id_)).filter(id.eq(id_)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
   /// Invalidate all logins of given user on password reset/change, or account deletion.
   pub async fn invalidate_all(pool: &mut DbPool<'_>, user_id_: LocalUserId) -> LemmyResult<usize> {
     let conn = &mut get_conn(pool).await?;
@@ -62,4 +117,264 @@ impl LoginToken {
       .await
       .with_lemmy_type(LemmyErrorType::Deleted)
   }
+
+  /// Read the session of a refresh token, if it isn't expired.
+  pub async fn read_by_refresh_token(
+    pool: &mut DbPool<'_>,
+    refresh_token_hash_: &str,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    login_token
+      .filter(refresh_token_hash.eq(refresh_token_hash_))
+      .filter(refresh_token_expires_at.gt(now.into_sql::<Timestamptz>().nullable()))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotLoggedIn)
+  }
+
+  /// Replaces the access token and refresh token of a session. The old refresh token is
+  /// remembered, to detect if it is used again.
+  pub async fn rotate_refresh_token(
+    pool: &mut DbPool<'_>,
+    old_refresh_token_hash: &str,
+    token_: SensitiveString,
+    refresh_token_hash_: String,
+    refresh_token_expires_at_: DateTime<Utc>,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let updated: Self = update(
+      login_token
+        .filter(refresh_token_hash.eq(old_refresh_token_hash))
+        .filter(refresh_token_expires_at.gt(now.into_sql::<Timestamptz>().nullable())),
+    )
+    .set((
+      token.eq(token_),
+      refresh_token_hash.eq(refresh_token_hash_),
+      refresh_token_expires_at.eq(refresh_token_expires_at_),
+      last_used_at.eq(now),
+    ))
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotLoggedIn)?;
+
+    insert_into(revoked_refresh_token::table)
+      .values((
+        revoked_refresh_token::token_hash.eq(old_refresh_token_hash),
+        revoked_refresh_token::login_token_id.eq(updated.id),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+    Ok(updated)
+  }
+
+  /// Revoke the session if the refresh token was already used before.
+  pub async fn invalidate_by_revoked_refresh_token(
+    pool: &mut DbPool<'_>,
+    refresh_token_hash_: &str,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let login_token_ids = revoked_refresh_token::table
+      .filter(revoked_refresh_token::token_hash.eq(refresh_token_hash_))
+      .select(revoked_refresh_token::login_token_id);
+    delete(login_token.filter(id.eq_any(login_token_ids)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Delete sessions whose refresh token expired, and revoked refresh tokens which are older than
+  /// 30 days, the validity of refresh tokens.
+  pub async fn delete_expired_refresh_tokens(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      revoked_refresh_token::table
+        .filter(revoked_refresh_token::revoked_at.lt(now.into_sql::<Timestamptz>() - 30.days())),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)?;
+    delete(
+      login_token.filter(refresh_token_expires_at.lt(now.into_sql::<Timestamptz>().nullable())),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// List the third-party apps which currently have access to the account of the given user.
+  pub async fn list_applications(
+    pool: &mut DbPool<'_>,
+    user_id_: LocalUserId,
+  ) -> LemmyResult<Vec<OAuthApplication>> {
+    let conn = &mut get_conn(pool).await?;
+    let application_ids = login_token
+      .filter(user_id.eq(user_id_))
+      .select(oauth_application_id);
+    oauth_application::table
+      .filter(oauth_application::id.nullable().eq_any(application_ids))
+      .order(oauth_application::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Revoke all tokens which were issued to a third-party app for the given user.
+  pub async fn invalidate_application(
+    pool: &mut DbPool<'_>,
+    user_id_: LocalUserId,
+    oauth_application_id_: OAuthApplicationId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      login_token
+        .filter(user_id.eq(user_id_))
+        .filter(oauth_application_id.eq(oauth_application_id_)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    login_token::{LoginToken, LoginTokenCreateForm},
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{TimeDelta, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  fn token_form(user: &LocalUser, token: &str) -> LoginTokenCreateForm {
+    LoginTokenCreateForm {
+      token: token.to_string().into(),
+      user_id: user.id,
+      ip: None,
+      user_agent: None,
+      oauth_application_id: None,
+      scope: None,
+      refresh_token_hash: None,
+      refresh_token_expires_at: None,
+    }
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_revoke_logins() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "terry tokens");
+    let person = Person::create(pool, &person_form).await?;
+    let user = LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let other_person_form = PersonInsertForm::test_form(inserted_instance.id, "olga other");
+    let other_person = Person::create(pool, &other_person_form).await?;
+    let other_user = LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(other_person.id),
+      vec![],
+    )
+    .await?;
+
+    let first = LoginToken::create(pool, token_form(&user, "first")).await?;
+    LoginToken::create(pool, token_form(&user, "second")).await?;
+    LoginToken::create(pool, token_form(&user, "third")).await?;
+    LoginToken::create(pool, token_form(&other_user, "other")).await?;
+    assert_eq!(3, LoginToken::list(pool, user.id).await?.len());
+
+    // Logins of other users can't be revoked
+    assert_eq!(
+      0,
+      LoginToken::invalidate_by_id(pool, other_user.id, first.id).await?
+    );
+    assert_eq!(
+      1,
+      LoginToken::invalidate_by_id(pool, user.id, first.id).await?
+    );
+    assert!(LoginToken::validate(pool, user.id, "first").await.is_err());
+    assert!(LoginToken::validate(pool, user.id, "second").await.is_ok());
+
+    // Logging out everywhere keeps the sessions of other users
+    assert_eq!(2, LoginToken::invalidate_all(pool, user.id).await?);
+    assert!(LoginToken::list(pool, user.id).await?.is_empty());
+    assert!(
+      LoginToken::validate(pool, other_user.id, "other")
+        .await
+        .is_ok()
+    );
+
+    Person::delete(pool, person.id).await?;
+    Person::delete(pool, other_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rotate_refresh_token() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "rotating ruth");
+    let person = Person::create(pool, &person_form).await?;
+    let user = LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let expires_at = Utc::now() + TimeDelta::days(30);
+
+    let form = LoginTokenCreateForm {
+      refresh_token_hash: Some("first_refresh".to_string()),
+      refresh_token_expires_at: Some(expires_at),
+      ..token_form(&user, "first_access")
+    };
+    let session = LoginToken::create(pool, form).await?;
+
+    let rotated = LoginToken::rotate_refresh_token(
+      pool,
+      "first_refresh",
+      "second_access".to_string().into(),
+      "second_refresh".to_string(),
+      expires_at,
+    )
+    .await?;
+    assert_eq!(session.id, rotated.id);
+    assert!(
+      LoginToken::validate(pool, user.id, "first_access")
+        .await
+        .is_err()
+    );
+    assert!(
+      LoginToken::validate(pool, user.id, "second_access")
+        .await
+        .is_ok()
+    );
+
+    // The old refresh token can't be used again, and using it revokes the session
+    assert!(
+      LoginToken::rotate_refresh_token(
+        pool,
+        "first_refresh",
+        "third_access".to_string().into(),
+        "third_refresh".to_string(),
+        expires_at,
+      )
+      .await
+      .is_err()
+    );
+    assert_eq!(
+      1,
+      LoginToken::invalidate_by_revoked_refresh_token(pool, "first_refresh").await?
+    );
+    assert!(LoginToken::list(pool, user.id).await?.is_empty());
+
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
 }
@@ -156,6 +156,23 @@ impl<'a> ModlogInsertForm<'a> {
       ..ModlogInsertForm::new(ModlogKind::AdminRemoveCommunity, !removed, mod_person.id)
     }
   }
+  pub fn admin_quarantine_community(
+    mod_person: &Person,
+    community_id: CommunityId,
+    quarantined: bool,
+    reason: &'a str,
+  ) -> Self {
+    Self {
+      reason: Some(reason),
+      target_community_id: Some(community_id),
+      target_instance_id: Some(mod_person.instance_id),
+      ..ModlogInsertForm::new(
+        ModlogKind::AdminQuarantineCommunity,
+        !quarantined,
+        mod_person.id,
+      )
+    }
+  }
 
   pub fn mod_change_community_visibility(
     mod_person_id: PersonId,
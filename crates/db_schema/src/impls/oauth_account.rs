@@ -1,10 +1,13 @@
 use crate::{
-  newtypes::LocalUserId,
+  newtypes::{LocalUserId, OAuthProviderId},
   source::oauth_account::{OAuthAccount, OAuthAccountInsertForm},
 };
 use diesel::{ExpressionMethods, QueryDsl, insert_into};
 use diesel_async::RunQueryDsl;
-use lemmy_db_schema_file::schema::{oauth_account, oauth_account::dsl::local_user_id};
+use lemmy_db_schema_file::schema::{
+  oauth_account,
+  oauth_account::dsl::{local_user_id, oauth_provider_id},
+};
 use lemmy_diesel_utils::connection::{DbPool, get_conn};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -29,4 +32,104 @@ impl OAuthAccount {
       .await
       .with_lemmy_type(LemmyErrorType::Deleted)
   }
+
+  pub async fn list_for_user(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+
+    oauth_account::table
+      .filter(local_user_id.eq(for_local_user_id))
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+    for_oauth_provider_id: OAuthProviderId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+
+    diesel::delete(
+      oauth_account::table
+        .filter(local_user_id.eq(for_local_user_id))
+        .filter(oauth_provider_id.eq(for_oauth_provider_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    oauth_account::{OAuthAccount, OAuthAccountInsertForm},
+    oauth_provider::{AdminOAuthProvider, OAuthProviderInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_and_unlink() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "oliver oauth");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_local_user = LocalUserInsertForm::test_form(inserted_person.id);
+    let local_user = LocalUser::create(pool, &new_local_user, vec![]).await?;
+
+    let url = Url::parse("https://oauth.example.com/endpoint")?;
+    let provider_form = OAuthProviderInsertForm {
+      display_name: "example".to_string(),
+      issuer: Url::parse("https://oauth.example.com")?.into(),
+      authorization_endpoint: url.clone().into(),
+      token_endpoint: url.clone().into(),
+      userinfo_endpoint: url.into(),
+      id_claim: "sub".to_string(),
+      client_id: "client".to_string(),
+      client_secret: "secret".to_string(),
+      scopes: "openid".to_string(),
+      auto_verify_email: None,
+      account_linking_enabled: None,
+      use_pkce: None,
+      enabled: None,
+    };
+    let provider = AdminOAuthProvider::create(pool, &provider_form).await?;
+    let form = OAuthAccountInsertForm::new(local_user.id, provider.id, "oliver".to_string());
+    let oauth_account = OAuthAccount::create(pool, &form).await?;
+
+    assert_eq!(
+      vec![oauth_account],
+      OAuthAccount::list_for_user(pool, local_user.id).await?
+    );
+
+    assert_eq!(
+      1,
+      OAuthAccount::delete(pool, local_user.id, provider.id).await?
+    );
+    assert!(
+      OAuthAccount::list_for_user(pool, local_user.id)
+        .await?
+        .is_empty()
+    );
+
+    AdminOAuthProvider::delete(pool, provider.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
 }
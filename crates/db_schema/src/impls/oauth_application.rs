@@ -0,0 +1,69 @@
+use crate::{
+  newtypes::{LocalUserId, OAuthApplicationId},
+  source::oauth_application::{
+    OAuthApplication,
+    OAuthApplicationInsertForm,
+    OAuthApplicationUpdateForm,
+  },
+};
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::oauth_application;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for OAuthApplication {
+  type InsertForm = OAuthApplicationInsertForm;
+  type UpdateForm = OAuthApplicationUpdateForm;
+  type IdType = OAuthApplicationId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(oauth_application::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    oauth_application_id: OAuthApplicationId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(oauth_application::table.find(oauth_application_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl OAuthApplication {
+  pub async fn read_from_client_id(pool: &mut DbPool<'_>, client_id: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    oauth_application::table
+      .filter(oauth_application::client_id.eq(client_id))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// List the apps which were registered by the given user.
+  pub async fn list_for_creator(
+    pool: &mut DbPool<'_>,
+    creator_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    oauth_application::table
+      .filter(oauth_application::creator_id.eq(creator_id))
+      .order(oauth_application::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
@@ -0,0 +1,155 @@
+use crate::source::oauth_authorization_code::{
+  OAuthAuthorizationCode,
+  OAuthAuthorizationCodeInsertForm,
+};
+use diesel::{
+  ExpressionMethods,
+  IntoSql,
+  QueryDsl,
+  delete,
+  dsl::{IntervalDsl, insert_into, now},
+  sql_types::Timestamptz,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::oauth_authorization_code;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl OAuthAuthorizationCode {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &OAuthAuthorizationCodeInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(oauth_authorization_code::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Codes expire after ten minutes.
+  pub async fn read(pool: &mut DbPool<'_>, code_hash: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    oauth_authorization_code::table
+      .find(code_hash)
+      .filter(
+        oauth_authorization_code::published_at.gt(now.into_sql::<Timestamptz>() - 10.minutes()),
+      )
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::OauthAuthorizationInvalid)
+  }
+
+  /// Codes can only be used once. Returns an error if the code was already used.
+  pub async fn delete_used(pool: &mut DbPool<'_>, code_hash: &str) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let deleted = delete(oauth_authorization_code::table.find(code_hash))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)?;
+    if deleted == 0 {
+      Err(LemmyErrorType::OauthAuthorizationInvalid.into())
+    } else {
+      Ok(())
+    }
+  }
+
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(oauth_authorization_code::table)
+      .filter(
+        oauth_authorization_code::published_at.lt(now.into_sql::<Timestamptz>() - 10.minutes()),
+      )
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    oauth_application::{OAuthApplication, OAuthApplicationInsertForm},
+    oauth_authorization_code::{OAuthAuthorizationCode, OAuthAuthorizationCodeInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{Duration, Utc};
+  use diesel::{ExpressionMethods, QueryDsl, update};
+  use diesel_async::RunQueryDsl;
+  use lemmy_db_schema_file::{enums::TokenScope, schema::oauth_authorization_code};
+  use lemmy_diesel_utils::{
+    connection::{build_db_pool_for_tests, get_conn},
+    traits::Crud,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_use_authorization_code() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "alice authorize");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_local_user = LocalUserInsertForm::test_form(inserted_person.id);
+    let local_user = LocalUser::create(pool, &new_local_user, vec![]).await?;
+
+    let redirect_uri = Url::parse("https://app.example.com/callback")?;
+    let application_form = OAuthApplicationInsertForm {
+      creator_id: local_user.id,
+      name: "example app".to_string(),
+      client_id: "client".to_string(),
+      client_secret: "secret".to_string(),
+      redirect_uri: redirect_uri.clone().into(),
+    };
+    let application = OAuthApplication::create(pool, &application_form).await?;
+
+    let form = OAuthAuthorizationCodeInsertForm {
+      code_hash: "hash".to_string(),
+      oauth_application_id: application.id,
+      local_user_id: local_user.id,
+      scope: TokenScope::Read,
+      redirect_uri: redirect_uri.into(),
+      code_challenge: None,
+    };
+    let code = OAuthAuthorizationCode::create(pool, &form).await?;
+    assert_eq!(code, OAuthAuthorizationCode::read(pool, "hash").await?);
+    assert!(OAuthAuthorizationCode::read(pool, "other").await.is_err());
+
+    // A code can only be used once
+    OAuthAuthorizationCode::delete_used(pool, "hash").await?;
+    assert!(
+      OAuthAuthorizationCode::delete_used(pool, "hash")
+        .await
+        .is_err()
+    );
+    assert!(OAuthAuthorizationCode::read(pool, "hash").await.is_err());
+
+    // Expired codes can't be read, and get cleaned up
+    let expired_form = OAuthAuthorizationCodeInsertForm {
+      code_hash: "expired".to_string(),
+      ..form
+    };
+    OAuthAuthorizationCode::create(pool, &expired_form).await?;
+    let conn = &mut get_conn(pool).await?;
+    update(oauth_authorization_code::table.find("expired"))
+      .set(oauth_authorization_code::published_at.eq(Utc::now() - Duration::minutes(11)))
+      .execute(conn)
+      .await?;
+    assert!(OAuthAuthorizationCode::read(pool, "expired").await.is_err());
+    assert_eq!(1, OAuthAuthorizationCode::delete_expired(pool).await?);
+
+    OAuthApplication::delete(pool, application.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
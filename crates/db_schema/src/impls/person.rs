@@ -7,17 +7,20 @@ use crate::{
     PersonBlockForm,
     PersonFollowerForm,
     PersonInsertForm,
+    PersonMuteForm,
     PersonNoteForm,
     PersonUpdateForm,
   },
   traits::{ApubActor, Blockable, Followable},
   utils::format_actor_url,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use diesel::{
   ExpressionMethods,
   JoinOnDsl,
+  PgExpressionMethods,
   QueryDsl,
+  TextExpressionMethods,
   dsl::{exists, insert_into, not, select},
   expression::SelectableHelper,
 };
@@ -26,13 +29,13 @@ use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  schema::{instance, instance_actions, local_user, person, person_actions},
+  schema::{comment, instance, instance_actions, local_user, person, person_actions, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
   traits::Crud,
-  utils::functions::lower,
+  utils::{functions::lower, prefix_search},
 };
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
@@ -79,6 +82,45 @@ impl Crud for Person {
 }
 
 impl Person {
+  /// Read multiple persons by id, including deleted ones. Fails if any of them are missing.
+  pub async fn read_many(pool: &mut DbPool<'_>, person_ids: &[PersonId]) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let persons: Vec<Self> = person::table
+      .filter(person::id.eq_any(person_ids))
+      .load(conn)
+      .await?;
+    if persons.len() != person_ids.len() {
+      return Err(LemmyErrorType::NotFound.into());
+    }
+    Ok(persons)
+  }
+
+  /// Remote users who posted or commented since the given time, but were not refreshed since
+  /// then, oldest first.
+  pub async fn list_stale_remote(
+    pool: &mut DbPool<'_>,
+    refreshed_before: DateTime<Utc>,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let has_posted = post::table
+      .filter(post::creator_id.eq(person::id))
+      .filter(post::published_at.gt(refreshed_before));
+    let has_commented = comment::table
+      .filter(comment::creator_id.eq(person::id))
+      .filter(comment::published_at.gt(refreshed_before));
+    person::table
+      .filter(person::local.eq(false))
+      .filter(person::deleted.eq(false))
+      .filter(person::last_refreshed_at.lt(refreshed_before))
+      .filter(exists(has_posted).or(exists(has_commented)))
+      .order_by(person::last_refreshed_at.asc())
+      .limit(limit)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   /// Update or insert the person.
   ///
   /// This is necessary for federation, because Activitypub doesn't distinguish between these
@@ -140,6 +182,33 @@ impl Person {
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
 
+  /// Users whose name starts with the given prefix, for autocompletion of mentions. Local users
+  /// are listed first.
+  pub async fn list_by_name_prefix(
+    pool: &mut DbPool<'_>,
+    name_prefix: &str,
+    domain_prefix: Option<&str>,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = person::table
+      .inner_join(instance::table)
+      .filter(lower(person::name).like(prefix_search(name_prefix)))
+      .filter(person::deleted.eq(false))
+      .select(person::all_columns)
+      .into_boxed();
+    if let Some(domain_prefix) = domain_prefix {
+      query = query.filter(lower(instance::domain).like(prefix_search(domain_prefix)));
+    }
+    query
+      .order_by(person::local.desc())
+      .then_order_by(person::name)
+      .limit(limit)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn check_username_taken(pool: &mut DbPool<'_>, username: &str) -> LemmyResult<()> {
     let conn = &mut get_conn(pool).await?;
     select(not(exists(
@@ -337,6 +406,19 @@ impl PersonActions {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  pub async fn count_followers(pool: &mut DbPool<'_>, for_person_id: PersonId) -> LemmyResult<i32> {
+    let conn = &mut get_conn(pool).await?;
+    person_actions::table
+      .filter(person_actions::followed_at.is_not_null())
+      .filter(person_actions::follow_pending.is_distinct_from(true))
+      .filter(person_actions::target_id.eq(for_person_id))
+      .count()
+      .get_result::<i64>(conn)
+      .await
+      .map(i32::try_from)?
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn note(pool: &mut DbPool<'_>, form: &PersonNoteForm) -> LemmyResult<Self> {
     let conn = &mut get_conn(pool).await?;
     insert_into(person_actions::table)
@@ -364,6 +446,50 @@ impl PersonActions {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  pub async fn mute(pool: &mut DbPool<'_>, form: &PersonMuteForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(person_actions::table)
+      .values(form)
+      .on_conflict((person_actions::person_id, person_actions::target_id))
+      .do_update()
+      .set(form)
+      .returning(Self::as_select())
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::AlreadyExists)
+  }
+
+  pub async fn unmute(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    target_id: PersonId,
+  ) -> LemmyResult<UpleteCount> {
+    let conn = &mut get_conn(pool).await?;
+    uplete(person_actions::table.find((person_id, target_id)))
+      .set_null(person_actions::muted_at)
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Returns an error if `person_id` muted notifications from `target_id`.
+  pub async fn read_mute(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    target_id: PersonId,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let find_action = person_actions::table
+      .find((person_id, target_id))
+      .filter(person_actions::muted_at.is_not_null());
+
+    select(not(exists(find_action)))
+      .get_result::<bool>(conn)
+      .await?
+      .then_some(())
+      .ok_or(LemmyErrorType::PersonIsMuted.into())
+  }
+
   pub async fn like(
     pool: &mut DbPool<'_>,
     person_id: PersonId,
@@ -424,6 +550,7 @@ mod tests {
     traits::{Followable, Likeable},
   };
   use diesel_uplete::UpleteCount;
+  use lemmy_db_schema_file::PersonId;
   use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -481,6 +608,32 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn list_by_name_prefix() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+
+    let persons = Person::list_by_name_prefix(pool, "HOL", None, 10).await?;
+    assert_eq!(
+      vec![data.person.id],
+      persons.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+
+    let persons = Person::list_by_name_prefix(pool, "hol", Some("my_dom"), 10).await?;
+    assert_eq!(1, persons.len());
+    let persons = Person::list_by_name_prefix(pool, "hol", Some("other"), 10).await?;
+    assert!(persons.is_empty());
+
+    // Wildcards are matched literally
+    let persons = Person::list_by_name_prefix(pool, "h_lly", None, 10).await?;
+    assert!(persons.is_empty());
+
+    data.delete(pool).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn follow() -> LemmyResult<()> {
@@ -508,6 +661,37 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_read_many() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+
+    let person_form_2 = PersonInsertForm::test_form(data.instance.id, "read_many_2");
+    let person_2 = Person::create(pool, &person_form_2).await?;
+
+    let persons = Person::read_many(pool, &[data.person.id, person_2.id]).await?;
+    assert_eq!(2, persons.len());
+    assert!(persons.iter().any(|p| p.id == person_2.id));
+
+    // Deleted persons are still returned, same as for Person::read
+    Person::delete_account(pool, person_2.id, data.instance.id).await?;
+    let persons = Person::read_many(pool, &[data.person.id, person_2.id]).await?;
+    assert_eq!(2, persons.len());
+
+    // Unknown ids make the whole read fail
+    assert!(
+      Person::read_many(pool, &[data.person.id, PersonId(-1)])
+        .await
+        .is_err()
+    );
+
+    Person::delete(pool, person_2.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_aggregates() -> LemmyResult<()> {
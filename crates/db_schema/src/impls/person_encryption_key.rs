@@ -0,0 +1,141 @@
+use crate::{
+  newtypes::PersonEncryptionKeyId,
+  source::person_encryption_key::{PersonEncryptionKey, PersonEncryptionKeyInsertForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, dsl::insert_into};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::{PersonId, schema::person_encryption_key};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PersonEncryptionKey {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &PersonEncryptionKeyInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(person_encryption_key::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn list_for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    person_encryption_key::table
+      .filter(person_encryption_key::person_id.eq(person_id))
+      .order(person_encryption_key::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    id: PersonEncryptionKeyId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      person_encryption_key::table
+        .find(id)
+        .filter(person_encryption_key::person_id.eq(person_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Replaces all keys of a remote person with the ones from their actor.
+  pub async fn replace_for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    forms: Vec<PersonEncryptionKeyInsertForm>,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(
+            person_encryption_key::table.filter(person_encryption_key::person_id.eq(person_id)),
+          )
+          .execute(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::Deleted)?;
+
+          insert_into(person_encryption_key::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    person_encryption_key::{PersonEncryptionKey, PersonEncryptionKeyInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_encryption_keys() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "encryption_key");
+    let person = Person::create(pool, &person_form).await?;
+
+    let form = PersonEncryptionKeyInsertForm::new(person.id, "phone".into(), "key1".into());
+    let key = PersonEncryptionKey::create(pool, &form).await?;
+    // The same key can't be published twice
+    assert!(PersonEncryptionKey::create(pool, &form).await.is_err());
+
+    let forms = vec![
+      PersonEncryptionKeyInsertForm::new(person.id, "laptop".into(), "key2".into()),
+      PersonEncryptionKeyInsertForm::new(person.id, "tablet".into(), "key3".into()),
+    ];
+    PersonEncryptionKey::replace_for_person(pool, person.id, forms).await?;
+    let keys = PersonEncryptionKey::list_for_person(pool, person.id).await?;
+    let public_keys: Vec<_> = keys.iter().map(|k| k.public_key.as_str()).collect();
+    assert_eq!(vec!["key2", "key3"], public_keys);
+
+    // The replaced key is already gone
+    assert_eq!(
+      0,
+      PersonEncryptionKey::delete(pool, person.id, key.id).await?
+    );
+    let key_id = keys.first().map(|k| k.id).unwrap_or_default();
+    assert_eq!(
+      1,
+      PersonEncryptionKey::delete(pool, person.id, key_id).await?
+    );
+    assert_eq!(
+      1,
+      PersonEncryptionKey::list_for_person(pool, person.id)
+        .await?
+        .len()
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -39,7 +39,7 @@ use lemmy_diesel_utils::{
   dburl::DbUrl,
   traits::Crud,
   utils::{
-    functions::{coalesce, hot_rank, scaled_rank},
+    functions::{coalesce, hot_rank, scaled_rank, search_headline},
     now,
   },
 };
@@ -296,6 +296,64 @@ impl Post {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Count the posts which a user created after the given time. Deleted posts are included, so
+  /// that limits can't be circumvented by deleting posts.
+  pub async fn user_post_count_since(
+    person_id: PersonId,
+    since: DateTime<Utc>,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+
+    post::table
+      .filter(post::creator_id.eq(person_id))
+      .filter(post::published_at.gt(since))
+      .select(count(post::id))
+      .first::<i64>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// The most recent post in the community which links to the same url and was published after
+  /// the given time. Deleted and removed posts are ignored.
+  pub async fn read_duplicate_url(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    url: &DbUrl,
+    since: DateTime<Utc>,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+
+    post::table
+      .filter(post::community_id.eq(community_id))
+      .filter(post::url.eq(url))
+      .filter(post::published_at.gt(since))
+      .filter(not(post::deleted.or(post::removed)))
+      .order(post::published_at.desc())
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Excerpts of the post bodies with the matches of the search term highlighted. Posts without
+  /// body are left out.
+  pub async fn read_search_snippets(
+    pool: &mut DbPool<'_>,
+    post_ids: &[PostId],
+    search_term: &str,
+  ) -> LemmyResult<Vec<(PostId, String)>> {
+    let conn = &mut get_conn(pool).await?;
+
+    post::table
+      .filter(post::id.eq_any(post_ids))
+      .filter(post::body.is_not_null())
+      .select((post::id, search_headline(post::body, search_term)))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn update_ranks(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Self> {
     let conn = &mut get_conn(pool).await?;
 
@@ -547,8 +605,11 @@ impl PostActions {
     Ok(())
   }
 
+  /// Local users who want to be notified about all new comments in the post, up to `limit` of
+  /// them.
   pub async fn list_subscribers(
     post_id: PostId,
+    limit: i64,
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<Vec<PersonId>> {
     let conn = &mut get_conn(pool).await?;
@@ -558,6 +619,8 @@ impl PostActions {
       .filter(post_actions::post_id.eq(post_id))
       .filter(post_actions::notifications.eq(PostNotificationsMode::AllComments))
       .select(local_user::person_id)
+      .order_by(local_user::person_id)
+      .limit(limit)
       .get_results(conn)
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
@@ -577,9 +640,9 @@ mod tests {
     traits::{Likeable, Saveable},
     utils::RANK_DEFAULT,
   };
-  use chrono::DateTime;
+  use chrono::{DateTime, TimeDelta, Utc};
   use diesel_uplete::UpleteCount;
-  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, dburl::DbUrl, traits::Crud};
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
   use serial_test::serial;
@@ -645,6 +708,8 @@ mod tests {
       embed_video_url: None,
       embed_video_width: None,
       embed_video_height: None,
+      embed_video_duration: None,
+      changed_at: inserted_post.changed_at,
       thumbnail_url: None,
       ap_id: Url::parse(&format!("https://lemmy-alpha/post/{}", inserted_post.id))?.into(),
       local: true,
@@ -923,6 +988,110 @@ mod tests {
     Community::delete(pool, inserted_community.id).await?;
     Instance::delete(pool, inserted_instance.id).await?;
 
+    Ok(())
+  }
+  #[tokio::test]
+  #[serial]
+  async fn test_read_duplicate_url() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "duplicate_url_person");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_community = CommunityInsertForm::new(
+      inserted_instance.id,
+      "duplicate_url_community".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &new_community).await?;
+
+    let url: DbUrl = Url::parse("https://example.com/article")?.into();
+    let new_post = PostInsertForm {
+      url: Some(url.clone()),
+      ..PostInsertForm::new(
+        "A link post".into(),
+        inserted_person.id,
+        inserted_community.id,
+      )
+    };
+    let inserted_post = Post::create(pool, &new_post).await?;
+
+    let day_ago = Utc::now() - TimeDelta::days(1);
+    let duplicate = Post::read_duplicate_url(pool, inserted_community.id, &url, day_ago).await?;
+    assert_eq!(Some(inserted_post.id), duplicate.map(|p| p.id));
+
+    // Posts which are older than the given time are not duplicates
+    let duplicate = Post::read_duplicate_url(pool, inserted_community.id, &url, Utc::now()).await?;
+    assert!(duplicate.is_none());
+
+    // Neither are removed posts
+    Post::update(
+      pool,
+      inserted_post.id,
+      &PostUpdateForm {
+        removed: Some(true),
+        ..Default::default()
+      },
+    )
+    .await?;
+    let duplicate = Post::read_duplicate_url(pool, inserted_community.id, &url, day_ago).await?;
+    assert!(duplicate.is_none());
+
+    Post::delete(pool, inserted_post.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_read_search_snippets() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "snippet_person");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_community = CommunityInsertForm::new(
+      inserted_instance.id,
+      "snippet_community".into(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &new_community).await?;
+
+    let new_post = PostInsertForm {
+      body: Some("Async functions in traits are finally stable in rust".into()),
+      ..PostInsertForm::new(
+        "A text post".into(),
+        inserted_person.id,
+        inserted_community.id,
+      )
+    };
+    let inserted_post = Post::create(pool, &new_post).await?;
+    let link_post_form = PostInsertForm::new(
+      "A post without body".into(),
+      inserted_person.id,
+      inserted_community.id,
+    );
+    let link_post = Post::create(pool, &link_post_form).await?;
+
+    let snippets =
+      Post::read_search_snippets(pool, &[inserted_post.id, link_post.id], "stable").await?;
+    assert_eq!(
+      vec![(
+        inserted_post.id,
+        "Async functions in traits are finally **stable** in rust".to_string()
+      )],
+      snippets
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
     Ok(())
   }
 }
@@ -0,0 +1,262 @@
+use crate::{
+  newtypes::{CommunityId, PostId},
+  source::post_feature_schedule::{PostFeatureSchedule, PostFeatureScheduleForm},
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  JoinOnDsl,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  delete,
+  insert_into,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::{community_actions, post, post_feature_schedule};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PostFeatureSchedule {
+  /// Creates the schedule of the post, or replaces the existing one.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &PostFeatureScheduleForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_feature_schedule::table)
+      .values(form)
+      .on_conflict(post_feature_schedule::post_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_feature_schedule::table
+      .find(post_id)
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(post_feature_schedule::table.find(post_id))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  pub async fn list_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_feature_schedule::table
+      .inner_join(post::table)
+      .filter(post::community_id.eq(community_id))
+      .select(Self::as_select())
+      .order((
+        post_feature_schedule::feature_at.asc(),
+        post_feature_schedule::unfeature_at.asc(),
+      ))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Schedules whose post needs to be featured now, in the order in which to feature them. Only
+  /// includes schedules of users who are still moderators of the community.
+  pub async fn list_due_features(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_feature_schedule::table
+      .inner_join(post::table)
+      .inner_join(
+        community_actions::table.on(
+          community_actions::community_id
+            .eq(post::community_id)
+            .and(community_actions::person_id.eq(post_feature_schedule::scheduled_by)),
+        ),
+      )
+      .filter(community_actions::became_moderator_at.is_not_null())
+      .filter(post_feature_schedule::feature_at.le(Utc::now()))
+      .select(Self::as_select())
+      .order((
+        post_feature_schedule::feature_at.asc(),
+        post_feature_schedule::rotation_order.asc(),
+      ))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Schedules of featured posts, which need to be unfeatured now. Only includes schedules of
+  /// users who are still moderators of the community.
+  pub async fn list_due_unfeatures(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_feature_schedule::table
+      .inner_join(post::table)
+      .inner_join(
+        community_actions::table.on(
+          community_actions::community_id
+            .eq(post::community_id)
+            .and(community_actions::person_id.eq(post_feature_schedule::scheduled_by)),
+        ),
+      )
+      .filter(community_actions::became_moderator_at.is_not_null())
+      .filter(post_feature_schedule::feature_at.is_null())
+      .filter(post_feature_schedule::unfeature_at.le(Utc::now()))
+      .select(Self::as_select())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Featured posts of the rotation in the community, other than the given one.
+  pub async fn list_featured_rotation(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    except_post_id: PostId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_feature_schedule::table
+      .inner_join(post::table)
+      .filter(post::community_id.eq(community_id))
+      .filter(post_feature_schedule::post_id.ne(except_post_id))
+      .filter(post_feature_schedule::feature_at.is_null())
+      .filter(post_feature_schedule::rotation_order.is_not_null())
+      .select(Self::as_select())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Marks the post as featured, so that only the unfeature time remains.
+  pub async fn mark_featured(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(post_feature_schedule::table.find(post_id))
+      .set(post_feature_schedule::feature_at.eq(None::<DateTime<Utc>>))
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+    post_feature_schedule::{PostFeatureSchedule, PostFeatureScheduleForm},
+  };
+  use chrono::{TimeDelta, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_feature_schedule() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "feature_schedule");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "feature_schedule".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let moderator_form = CommunityModeratorForm::new(community.id, person.id);
+    CommunityActions::join(pool, &moderator_form).await?;
+    let post_form = PostInsertForm::new("week 1".into(), person.id, community.id);
+    let week_1 = Post::create(pool, &post_form).await?;
+    let post_form = PostInsertForm::new("week 2".into(), person.id, community.id);
+    let week_2 = Post::create(pool, &post_form).await?;
+
+    let now = Utc::now();
+    let form = PostFeatureScheduleForm::new(
+      week_1.id,
+      person.id,
+      Some(now - TimeDelta::minutes(1)),
+      Some(now + TimeDelta::days(7)),
+      Some(1),
+    );
+    PostFeatureSchedule::upsert(pool, &form).await?;
+    let form = PostFeatureScheduleForm::new(
+      week_2.id,
+      person.id,
+      Some(now + TimeDelta::days(7)),
+      None,
+      Some(2),
+    );
+    PostFeatureSchedule::upsert(pool, &form).await?;
+    assert_eq!(
+      2,
+      PostFeatureSchedule::list_for_community(pool, community.id)
+        .await?
+        .len()
+    );
+
+    let due = PostFeatureSchedule::list_due_features(pool).await?;
+    assert_eq!(
+      vec![week_1.id],
+      due.iter().map(|s| s.post_id).collect::<Vec<_>>()
+    );
+    assert!(
+      PostFeatureSchedule::list_due_unfeatures(pool)
+        .await?
+        .is_empty()
+    );
+
+    let featured = PostFeatureSchedule::mark_featured(pool, week_1.id).await?;
+    assert_eq!(None, featured.feature_at);
+    assert!(
+      PostFeatureSchedule::list_due_features(pool)
+        .await?
+        .is_empty()
+    );
+    let rotation =
+      PostFeatureSchedule::list_featured_rotation(pool, community.id, week_2.id).await?;
+    assert_eq!(vec![featured], rotation);
+
+    // Rescheduling replaces the old schedule
+    let form = PostFeatureScheduleForm::new(
+      week_1.id,
+      person.id,
+      None,
+      Some(now - TimeDelta::minutes(1)),
+      None,
+    );
+    PostFeatureSchedule::upsert(pool, &form).await?;
+    let due = PostFeatureSchedule::list_due_unfeatures(pool).await?;
+    assert_eq!(
+      vec![week_1.id],
+      due.iter().map(|s| s.post_id).collect::<Vec<_>>()
+    );
+
+    // Schedules of users who aren't moderators anymore are ignored
+    CommunityActions::leave(pool, &moderator_form).await?;
+    assert!(
+      PostFeatureSchedule::list_due_unfeatures(pool)
+        .await?
+        .is_empty()
+    );
+
+    assert_eq!(1, PostFeatureSchedule::delete(pool, week_1.id).await?);
+    assert_eq!(None, PostFeatureSchedule::read(pool, week_1.id).await?);
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
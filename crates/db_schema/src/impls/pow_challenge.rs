@@ -0,0 +1,93 @@
+use crate::source::pow_challenge::{PowChallenge, PowChallengeInsertForm};
+use diesel::{
+  ExpressionMethods,
+  IntoSql,
+  QueryDsl,
+  delete,
+  dsl::{IntervalDsl, insert_into, now},
+  sql_types::Timestamptz,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::pow_challenge;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PowChallenge {
+  pub async fn create(pool: &mut DbPool<'_>, form: &PowChallengeInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(pow_challenge::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Challenges expire after ten minutes.
+  pub async fn read(pool: &mut DbPool<'_>, challenge: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    pow_challenge::table
+      .find(challenge)
+      .filter(pow_challenge::published_at.gt(now.into_sql::<Timestamptz>() - 10.minutes()))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidPowChallenge)
+  }
+
+  /// Challenges can only be used once for registration.
+  pub async fn read_and_delete(pool: &mut DbPool<'_>, challenge: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    delete(pow_challenge::table)
+      .filter(pow_challenge::challenge.eq(challenge))
+      .filter(pow_challenge::published_at.gt(now.into_sql::<Timestamptz>() - 10.minutes()))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidPowChallenge)
+  }
+
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(pow_challenge::table)
+      .filter(pow_challenge::published_at.lt(now.into_sql::<Timestamptz>() - 10.minutes()))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::pow_challenge::{PowChallenge, PowChallengeInsertForm};
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_read_and_delete() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let form = PowChallengeInsertForm {
+      challenge: "pow_challenge".to_string(),
+      difficulty: 16,
+    };
+    let inserted = PowChallenge::create(pool, &form).await?;
+    assert_eq!(16, inserted.difficulty);
+
+    // Reading doesn't use up the challenge
+    assert_eq!(inserted, PowChallenge::read(pool, "pow_challenge").await?);
+    assert_eq!(
+      inserted,
+      PowChallenge::read_and_delete(pool, "pow_challenge").await?
+    );
+    assert!(PowChallenge::read(pool, "pow_challenge").await.is_err());
+    assert!(
+      PowChallenge::read_and_delete(pool, "pow_challenge")
+        .await
+        .is_err()
+    );
+
+    Ok(())
+  }
+}
@@ -165,6 +165,8 @@ mod tests {
       local: true,
       removed: false,
       deleted_by_recipient: false,
+      ciphertext: None,
+      encryption_metadata: None,
     };
 
     let read_private_message = PrivateMessage::read(pool, inserted_private_message.id).await?;
@@ -0,0 +1,131 @@
+use crate::{
+  newtypes::{LocalUserId, LoginTokenId},
+  source::push_subscription::{PushSubscription, PushSubscriptionForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::push_subscription;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PushSubscription {
+  /// Each session has at most one subscription, so an existing one is replaced.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &PushSubscriptionForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(push_subscription::table)
+      .values(form)
+      .on_conflict(push_subscription::login_token_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn list_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    push_subscription::table
+      .filter(push_subscription::local_user_id.eq(local_user_id))
+      .order(push_subscription::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete_for_login(
+    pool: &mut DbPool<'_>,
+    login_token_id: LoginTokenId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(push_subscription::table.filter(push_subscription::login_token_id.eq(login_token_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Removes a subscription which the push service reported as expired.
+  pub async fn delete(pool: &mut DbPool<'_>, id: i32) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(push_subscription::table.find(id))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    login_token::{LoginToken, LoginTokenCreateForm},
+    person::{Person, PersonInsertForm},
+    push_subscription::{PushSubscription, PushSubscriptionForm},
+  };
+  use lemmy_db_schema_file::enums::PushProvider;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_push_subscriptions() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "paula pusher");
+    let person = Person::create(pool, &person_form).await?;
+    let user = LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let token_form = LoginTokenCreateForm {
+      token: "push_token".to_string().into(),
+      user_id: user.id,
+      ip: None,
+      user_agent: None,
+      oauth_application_id: None,
+      scope: None,
+      refresh_token_hash: None,
+      refresh_token_expires_at: None,
+    };
+    let login = LoginToken::create(pool, token_form).await?;
+
+    let form = PushSubscriptionForm {
+      local_user_id: user.id,
+      login_token_id: login.id,
+      endpoint: "https://push.example.com/first".to_string(),
+      p256dh_key: Some("p256dh".to_string()),
+      auth_key: Some("auth".to_string()),
+      provider: PushProvider::WebPush,
+      device_name: None,
+    };
+    PushSubscription::upsert(pool, &form).await?;
+
+    // Subscribing again from the same session replaces the old subscription
+    let form = PushSubscriptionForm {
+      endpoint: "https://push.example.com/second".to_string(),
+      ..form
+    };
+    let subscription = PushSubscription::upsert(pool, &form).await?;
+    assert_eq!(
+      vec![subscription],
+      PushSubscription::list_for_user(pool, user.id).await?
+    );
+
+    // Logging out removes the subscription
+    LoginToken::invalidate(pool, "push_token").await?;
+    assert!(
+      PushSubscription::list_for_user(pool, user.id)
+        .await?
+        .is_empty()
+    );
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
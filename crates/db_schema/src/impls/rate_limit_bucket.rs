@@ -0,0 +1,127 @@
+use crate::source::rate_limit_bucket::RateLimitBucket;
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::now,
+  sql_query,
+  sql_types::{Integer, Text},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::rate_limit_bucket;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use std::time::Duration;
+
+impl RateLimitBucket {
+  /// Counts a request in the bucket, in a single statement so that concurrent requests from other
+  /// servers are counted correctly. Expired buckets start again with a count of one.
+  pub async fn count_request(
+    pool: &mut DbPool<'_>,
+    key: &str,
+    interval: Duration,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let interval_secs = i32::try_from(interval.as_secs()).unwrap_or(i32::MAX);
+    // Raw `sql_query` is used, because the update depends on whether the existing bucket expired
+    sql_query(
+      r#"INSERT INTO rate_limit_bucket (key, count, expires_at)
+             VALUES ($1, 1, now() + $2 * interval '1 second')
+         ON CONFLICT (key) DO UPDATE SET
+             count = CASE WHEN rate_limit_bucket.expires_at > now()
+                 THEN rate_limit_bucket.count + 1 ELSE 1 END,
+             expires_at = CASE WHEN rate_limit_bucket.expires_at > now()
+                 THEN rate_limit_bucket.expires_at ELSE excluded.expires_at END
+         RETURNING key, count, expires_at"#,
+    )
+    .bind::<Text, _>(key)
+    .bind::<Integer, _>(interval_secs)
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Uncounts a request. Expired buckets are left alone, as the request was counted in a bucket
+  /// which is already gone.
+  pub async fn rollback(pool: &mut DbPool<'_>, key: &str) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      rate_limit_bucket::table
+        .find(key)
+        .filter(rate_limit_bucket::count.gt(0))
+        .filter(rate_limit_bucket::expires_at.gt(now)),
+    )
+    .set(rate_limit_bucket::count.eq(rate_limit_bucket::count - 1))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(rate_limit_bucket::table.filter(rate_limit_bucket::expires_at.lt(now)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::rate_limit_bucket::RateLimitBucket;
+  use chrono::TimeDelta;
+  use diesel::{ExpressionMethods, QueryDsl};
+  use diesel_async::RunQueryDsl;
+  use lemmy_db_schema_file::schema::rate_limit_bucket;
+  use lemmy_diesel_utils::connection::{build_db_pool_for_tests, get_conn};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use std::time::Duration;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rate_limit_bucket() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let interval = Duration::from_secs(60);
+
+    let first = RateLimitBucket::count_request(pool, "post:127.0.0.1", interval).await?;
+    assert_eq!(1, first.count);
+    let second = RateLimitBucket::count_request(pool, "post:127.0.0.1", interval).await?;
+    assert_eq!(2, second.count);
+    assert_eq!(first.expires_at, second.expires_at);
+    // Other keys have their own bucket
+    let other = RateLimitBucket::count_request(pool, "post:127.0.0.2", interval).await?;
+    assert_eq!(1, other.count);
+
+    RateLimitBucket::rollback(pool, "post:127.0.0.1").await?;
+    let third = RateLimitBucket::count_request(pool, "post:127.0.0.1", interval).await?;
+    assert_eq!(2, third.count);
+
+    // Expired buckets start again, or are deleted
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(rate_limit_bucket::table)
+      .set(rate_limit_bucket::expires_at.eq(first.expires_at - TimeDelta::minutes(2)))
+      .execute(conn)
+      .await?;
+    RateLimitBucket::rollback(pool, "post:127.0.0.2").await?;
+    let conn = &mut get_conn(pool).await?;
+    let expired_count: i64 = rate_limit_bucket::table
+      .find("post:127.0.0.2")
+      .select(rate_limit_bucket::count)
+      .first(conn)
+      .await?;
+    assert_eq!(1, expired_count);
+    let fourth = RateLimitBucket::count_request(pool, "post:127.0.0.1", interval).await?;
+    assert_eq!(1, fourth.count);
+    assert!(fourth.expires_at > first.expires_at);
+    assert_eq!(1, RateLimitBucket::delete_expired(pool).await?);
+
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(rate_limit_bucket::table)
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,219 @@
+use crate::{
+  source::remote_community_directory::{
+    RemoteCommunityDirectory,
+    RemoteCommunityDirectoryForm,
+    remote_community_directory_keys as key,
+  },
+  utils::limit_fetch,
+};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  PgTextExpressionMethods,
+  QueryDsl,
+  delete,
+  dsl::{exists, insert_into, not},
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use i_love_jesus::SortDirection;
+use lemmy_db_schema_file::{
+  InstanceId,
+  schema::{community, remote_community_directory},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  utils::fuzzy_search,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use url::Url;
+
+impl PaginationCursorConversion for RemoteCommunityDirectory {
+  type PaginatedType = RemoteCommunityDirectory;
+
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_plain(self.ap_id.to_string())
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    let conn = &mut get_conn(pool).await?;
+    let ap_id: DbUrl = Url::parse(&cursor.plain())?.into();
+    remote_community_directory::table
+      .find(ap_id)
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl RemoteCommunityDirectory {
+  /// Replaces the crawled communities of an instance with the given ones.
+  pub async fn update_for_instance(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    forms: Vec<RemoteCommunityDirectoryForm>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let ap_ids = forms.iter().map(|f| f.ap_id.clone()).collect::<Vec<_>>();
+          delete(
+            remote_community_directory::table
+              .filter(remote_community_directory::instance_id.eq(instance_id))
+              .filter(not(remote_community_directory::ap_id.eq_any(ap_ids))),
+          )
+          .execute(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::Deleted)?;
+          for form in forms {
+            insert_into(remote_community_directory::table)
+              .values(&form)
+              .on_conflict(remote_community_directory::ap_id)
+              .do_update()
+              .set(&form)
+              .execute(conn)
+              .await
+              .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          }
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Removes the communities of all instances which are not crawled anymore, eg because they
+  /// were blocked or went offline.
+  pub async fn delete_except_instances(
+    pool: &mut DbPool<'_>,
+    instance_ids: &[InstanceId],
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(remote_community_directory::table.filter(not(
+      remote_community_directory::instance_id.eq_any(instance_ids),
+    )))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Lists crawled communities which are not known locally yet, with the most subscribers first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    search_term: Option<&str>,
+    show_nsfw: bool,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let mut query = remote_community_directory::table
+      .filter(not(exists(
+        community::table.filter(community::ap_id.eq(remote_community_directory::ap_id)),
+      )))
+      .into_boxed();
+    if let Some(search_term) = search_term {
+      let searcher = fuzzy_search(search_term);
+      query = query.filter(
+        remote_community_directory::name
+          .ilike(searcher.clone())
+          .or(remote_community_directory::title.ilike(searcher)),
+      );
+    }
+    if !show_nsfw {
+      query = query.filter(remote_community_directory::nsfw.eq(false));
+    }
+    let paginated_query =
+      Self::paginate(query.limit(limit), &page_cursor, SortDirection::Desc, pool)
+        .await?
+        .then_order_by(key::subscribers)
+        .then_order_by(key::ap_id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::source::instance::Instance;
+  use chrono::Utc;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  fn form(
+    instance_id: InstanceId,
+    name: &str,
+    subscribers: i32,
+  ) -> LemmyResult<RemoteCommunityDirectoryForm> {
+    Ok(RemoteCommunityDirectoryForm {
+      ap_id: Url::parse(&format!("https://directory.tld/c/{name}"))?.into(),
+      instance_id,
+      name: name.to_string(),
+      title: name.to_uppercase(),
+      description: None,
+      icon: None,
+      nsfw: false,
+      subscribers,
+      posts: 0,
+      updated_at: Utc::now(),
+    })
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_remote_community_directory() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "directory.tld").await?;
+
+    let forms = vec![
+      form(instance.id, "small", 1)?,
+      form(instance.id, "large", 100)?,
+    ];
+    RemoteCommunityDirectory::update_for_instance(pool, instance.id, forms).await?;
+    let list = RemoteCommunityDirectory::list(pool, None, false, None, None).await?;
+    let names = list.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(vec!["large", "small"], names);
+
+    let first_page = RemoteCommunityDirectory::list(pool, None, false, None, Some(1)).await?;
+    assert_eq!(Some("large"), first_page.first().map(|c| c.name.as_str()));
+    let second_page =
+      RemoteCommunityDirectory::list(pool, None, false, first_page.next_page, Some(1)).await?;
+    assert_eq!(Some("small"), second_page.first().map(|c| c.name.as_str()));
+
+    let search = RemoteCommunityDirectory::list(pool, Some("SMA"), false, None, None).await?;
+    assert_eq!(1, search.len());
+
+    // Communities which are missing from the crawl get removed
+    let forms = vec![form(instance.id, "large", 120)?];
+    RemoteCommunityDirectory::update_for_instance(pool, instance.id, forms).await?;
+    let list = RemoteCommunityDirectory::list(pool, None, false, None, None).await?;
+    assert_eq!(1, list.len());
+    assert_eq!(Some(120), list.first().map(|c| c.subscribers));
+
+    RemoteCommunityDirectory::delete_except_instances(pool, &[]).await?;
+    let list = RemoteCommunityDirectory::list(pool, None, false, None, None).await?;
+    assert!(list.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,162 @@
+use crate::{
+  newtypes::{LocalUserId, SavedSearchId},
+  source::saved_search::{SavedSearch, SavedSearchInsertForm},
+};
+use chrono::{DateTime, Utc};
+use diesel::{ExpressionMethods, QueryDsl, delete, insert_into, update};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::saved_search;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl SavedSearch {
+  pub async fn create(pool: &mut DbPool<'_>, form: &SavedSearchInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(saved_search::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn list_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    saved_search::table
+      .filter(saved_search::local_user_id.eq(local_user_id))
+      .order(saved_search::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// All saved searches for which the user wants to be notified about new matches.
+  pub async fn list_with_notifications(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    saved_search::table
+      .filter(saved_search::notify.eq(true))
+      .order(saved_search::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn update_last_checked(
+    pool: &mut DbPool<'_>,
+    id: SavedSearchId,
+    last_checked_at: DateTime<Utc>,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(saved_search::table.find(id))
+      .set(saved_search::last_checked_at.eq(last_checked_at))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Delete a single saved search of the given user.
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    id: SavedSearchId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      saved_search::table
+        .find(id)
+        .filter(saved_search::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+    saved_search::{SavedSearch, SavedSearchInsertForm},
+  };
+  use chrono::Utc;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_saved_searches() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "sam searcher");
+    let person = Person::create(pool, &person_form).await?;
+    let user = LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let other_person_form = PersonInsertForm::test_form(inserted_instance.id, "oscar other");
+    let other_person = Person::create(pool, &other_person_form).await?;
+    let other_user = LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(other_person.id),
+      vec![],
+    )
+    .await?;
+
+    let form = SavedSearchInsertForm {
+      notify: Some(true),
+      ..SavedSearchInsertForm::new(user.id, "rust async".to_string())
+    };
+    let saved_search = SavedSearch::create(pool, &form).await?;
+    let quiet_form = SavedSearchInsertForm::new(user.id, "diesel".to_string());
+    let quiet_search = SavedSearch::create(pool, &quiet_form).await?;
+    assert!(!quiet_search.notify);
+
+    assert_eq!(
+      vec![saved_search.clone(), quiet_search.clone()],
+      SavedSearch::list_for_user(pool, user.id).await?
+    );
+    assert!(
+      SavedSearch::list_for_user(pool, other_user.id)
+        .await?
+        .is_empty()
+    );
+
+    let notify = SavedSearch::list_with_notifications(pool).await?;
+    assert!(notify.contains(&saved_search));
+    assert!(!notify.contains(&quiet_search));
+
+    let checked_at = Utc::now();
+    SavedSearch::update_last_checked(pool, saved_search.id, checked_at).await?;
+    let updated = SavedSearch::list_with_notifications(pool).await?;
+    assert!(
+      updated
+        .iter()
+        .any(|s| s.last_checked_at > saved_search.last_checked_at)
+    );
+
+    // Saved searches of other users can't be deleted
+    assert_eq!(
+      0,
+      SavedSearch::delete(pool, other_user.id, saved_search.id).await?
+    );
+    assert_eq!(
+      1,
+      SavedSearch::delete(pool, user.id, saved_search.id).await?
+    );
+    assert_eq!(
+      vec![quiet_search],
+      SavedSearch::list_for_user(pool, user.id).await?
+    );
+
+    Person::delete(pool, person.id).await?;
+    Person::delete(pool, other_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,210 @@
+use crate::{newtypes::ActivityId, source::sent_activity_delivery::SentActivityDelivery};
+use chrono::{DateTime, Utc};
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, dsl::insert_into, update};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{
+  InstanceId,
+  schema::{sent_activity, sent_activity_delivery},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl SentActivityDelivery {
+  /// Stores a failed attempt to send the activity to the instance.
+  pub async fn record_failure(
+    pool: &mut DbPool<'_>,
+    sent_activity_id: ActivityId,
+    instance_id: InstanceId,
+    error: &str,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(sent_activity_delivery::table)
+      .values((
+        sent_activity_delivery::sent_activity_id.eq(sent_activity_id),
+        sent_activity_delivery::instance_id.eq(instance_id),
+        sent_activity_delivery::fail_count.eq(1),
+        sent_activity_delivery::last_error.eq(error),
+      ))
+      .on_conflict((
+        sent_activity_delivery::sent_activity_id,
+        sent_activity_delivery::instance_id,
+      ))
+      .do_update()
+      .set((
+        sent_activity_delivery::delivered.eq(false),
+        sent_activity_delivery::fail_count.eq(sent_activity_delivery::fail_count + 1),
+        sent_activity_delivery::last_error.eq(error),
+        sent_activity_delivery::updated_at.eq(Utc::now()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+    Ok(())
+  }
+
+  /// Marks a previously failed delivery as successful. Does nothing if sending never failed.
+  pub async fn mark_delivered(
+    pool: &mut DbPool<'_>,
+    sent_activity_id: ActivityId,
+    instance_id: InstanceId,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(sent_activity_delivery::table.find((sent_activity_id, instance_id)))
+      .set((
+        sent_activity_delivery::delivered.eq(true),
+        sent_activity_delivery::replay.eq(false),
+        sent_activity_delivery::updated_at.eq(Utc::now()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Failed deliveries to the instance, newest first. Also returns the id and published time of
+  /// each activity.
+  pub async fn list_failed(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    limit: i64,
+  ) -> LemmyResult<Vec<(Self, DbUrl, DateTime<Utc>)>> {
+    let conn = &mut get_conn(pool).await?;
+    sent_activity_delivery::table
+      .inner_join(sent_activity::table)
+      .filter(sent_activity_delivery::instance_id.eq(instance_id))
+      .filter(sent_activity_delivery::delivered.eq(false))
+      .order_by(sent_activity_delivery::sent_activity_id.desc())
+      .limit(limit)
+      .select((
+        Self::as_select(),
+        sent_activity::ap_id,
+        sent_activity::published_at,
+      ))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Marks all failed deliveries to the instance which were published after `since` for replay.
+  /// They are sent again by the federation worker once the instance is reachable.
+  pub async fn request_replay(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    since: DateTime<Utc>,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let activity_ids = sent_activity::table
+      .filter(sent_activity::published_at.ge(since))
+      .select(sent_activity::id);
+    update(
+      sent_activity_delivery::table
+        .filter(sent_activity_delivery::instance_id.eq(instance_id))
+        .filter(sent_activity_delivery::delivered.eq(false))
+        .filter(sent_activity_delivery::sent_activity_id.eq_any(activity_ids)),
+    )
+    .set(sent_activity_delivery::replay.eq(true))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Returns activities which should be replayed to the instance, oldest first, and resets their
+  /// replay flag.
+  pub async fn take_replays(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    limit: i64,
+  ) -> LemmyResult<Vec<ActivityId>> {
+    let conn = &mut get_conn(pool).await?;
+    let ids: Vec<ActivityId> = sent_activity_delivery::table
+      .filter(sent_activity_delivery::instance_id.eq(instance_id))
+      .filter(sent_activity_delivery::replay.eq(true))
+      .order_by(sent_activity_delivery::sent_activity_id.asc())
+      .limit(limit)
+      .select(sent_activity_delivery::sent_activity_id)
+      .load(conn)
+      .await?;
+    update(
+      sent_activity_delivery::table
+        .filter(sent_activity_delivery::instance_id.eq(instance_id))
+        .filter(sent_activity_delivery::sent_activity_id.eq_any(&ids)),
+    )
+    .set(sent_activity_delivery::replay.eq(false))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(ids)
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+  use super::*;
+  use crate::source::{
+    activity::{SentActivity, SentActivityForm},
+    instance::Instance,
+  };
+  use lemmy_db_schema_file::enums::ActorType;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_failed_delivery_replay() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "delivery.tld").await?;
+    let form = SentActivityForm {
+      ap_id: Url::parse("http://example.com/activity/delivery")?.into(),
+      data: json!({}),
+      sensitive: false,
+      actor_apub_id: Url::parse("http://example.com/u/exampleuser")?.into(),
+      actor_type: ActorType::Person,
+      send_all_instances: true,
+      send_community_followers_of: None,
+      send_inboxes: vec![],
+    };
+    let activity = SentActivity::create(pool, form).await?;
+
+    SentActivityDelivery::record_failure(pool, activity.id, instance.id, "timeout").await?;
+    SentActivityDelivery::record_failure(pool, activity.id, instance.id, "connection refused")
+      .await?;
+    let failed = SentActivityDelivery::list_failed(pool, instance.id, 10).await?;
+    assert_eq!(1, failed.len());
+    let (delivery, ap_id, _) = &failed[0];
+    assert_eq!(2, delivery.fail_count);
+    assert_eq!(Some("connection refused".to_string()), delivery.last_error);
+    assert_eq!(&activity.ap_id, ap_id);
+
+    // Replay all failed deliveries since the activity was published
+    let replayed =
+      SentActivityDelivery::request_replay(pool, instance.id, activity.published_at).await?;
+    assert_eq!(1, replayed);
+    assert_eq!(
+      vec![activity.id],
+      SentActivityDelivery::take_replays(pool, instance.id, 10).await?
+    );
+    assert!(
+      SentActivityDelivery::take_replays(pool, instance.id, 10)
+        .await?
+        .is_empty()
+    );
+
+    SentActivityDelivery::mark_delivered(pool, activity.id, instance.id).await?;
+    assert!(
+      SentActivityDelivery::list_failed(pool, instance.id, 10)
+        .await?
+        .is_empty()
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
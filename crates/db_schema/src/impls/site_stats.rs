@@ -0,0 +1,167 @@
+use crate::source::site_stats::SiteStats;
+use chrono::NaiveDate;
+use diesel::{ExpressionMethods, QueryDsl, sql_query, sql_types::Date};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::site_stats;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl SiteStats {
+  /// Recalculates the statistics of the given day. The storage usage can only be measured for
+  /// the current moment, so it is kept once the day is over.
+  pub async fn refresh(pool: &mut DbPool<'_>, day: NaiveDate) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    // Active users are counted the same way as `r.site_aggregates_activity`
+    sql_query(
+      r#"WITH bounds AS (
+             SELECT $1::timestamp AT TIME ZONE 'UTC' AS start_,
+                 ($1 + 1)::timestamp AT TIME ZONE 'UTC' AS end_),
+         signups AS (
+             SELECT count(*) AS count
+             FROM local_user
+             INNER JOIN person ON person.id = local_user.person_id, bounds
+             WHERE person.published_at >= start_ AND person.published_at < end_),
+         posts AS (
+             SELECT count(*) AS count
+             FROM post, bounds
+             WHERE post.local AND post.published_at >= start_ AND post.published_at < end_),
+         comments AS (
+             SELECT count(*) AS count
+             FROM comment, bounds
+             WHERE comment.local AND comment.published_at >= start_
+                 AND comment.published_at < end_),
+         active_users AS (
+             SELECT count(*) AS count
+             FROM (
+                 SELECT creator_id AS person_id FROM post, bounds
+                 WHERE post.published_at >= start_ AND post.published_at < end_
+                 UNION
+                 SELECT creator_id FROM comment, bounds
+                 WHERE comment.published_at >= start_ AND comment.published_at < end_
+                 UNION
+                 SELECT person_id FROM post_actions, bounds
+                 WHERE voted_at >= start_ AND voted_at < end_
+                 UNION
+                 SELECT person_id FROM comment_actions, bounds
+                 WHERE voted_at >= start_ AND voted_at < end_) a
+             INNER JOIN person ON person.id = a.person_id
+             WHERE person.local AND NOT person.bot_account),
+         received AS (
+             SELECT count(*) AS count
+             FROM received_activity, bounds
+             WHERE published_at >= start_ AND published_at < end_),
+         sent AS (
+             SELECT count(*) AS count
+             FROM sent_activity, bounds
+             WHERE published_at >= start_ AND published_at < end_),
+         reports AS (
+             SELECT count(*) AS count
+             FROM (
+                 SELECT published_at FROM post_report
+                 UNION ALL
+                 SELECT published_at FROM comment_report
+                 UNION ALL
+                 SELECT published_at FROM private_message_report
+                 UNION ALL
+                 SELECT published_at FROM community_report
+                 UNION ALL
+                 SELECT published_at FROM instance_report) r, bounds
+             WHERE published_at >= start_ AND published_at < end_)
+         INSERT INTO site_stats (day, signups, posts, comments, active_users,
+             activities_received, activities_sent, reports, storage_bytes)
+         SELECT $1, signups.count, posts.count, comments.count, active_users.count,
+             received.count, sent.count, reports.count,
+             (SELECT coalesce(sum(size_bytes), 0) FROM local_image)
+         FROM signups, posts, comments, active_users, received, sent, reports
+         ON CONFLICT (day) DO UPDATE SET
+             signups = excluded.signups,
+             posts = excluded.posts,
+             comments = excluded.comments,
+             active_users = excluded.active_users,
+             activities_received = excluded.activities_received,
+             activities_sent = excluded.activities_sent,
+             reports = excluded.reports,
+             storage_bytes = CASE WHEN site_stats.day < (now() AT TIME ZONE 'UTC')::date
+                 THEN site_stats.storage_bytes ELSE excluded.storage_bytes END,
+             updated_at = now()"#,
+    )
+    .bind::<Date, _>(day)
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Statistics of the given number of most recent days, oldest first.
+  pub async fn list(pool: &mut DbPool<'_>, days: i64) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let mut stats: Vec<Self> = site_stats::table
+      .order(site_stats::day.desc())
+      .limit(days)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    stats.reverse();
+    Ok(stats)
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+    site_stats::SiteStats,
+  };
+  use chrono::{Days, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_refresh_site_stats() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let today = Utc::now().date_naive();
+    let yesterday = today - Days::new(1);
+    SiteStats::refresh(pool, yesterday).await?;
+    SiteStats::refresh(pool, today).await?;
+    let before = SiteStats::list(pool, 2).await?;
+    assert_eq!(
+      vec![yesterday, today],
+      before.iter().map(|s| s.day).collect::<Vec<_>>()
+    );
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "stats statler");
+    let inserted_person = Person::create(pool, &person_form).await?;
+    LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(inserted_person.id),
+      vec![],
+    )
+    .await?;
+
+    SiteStats::refresh(pool, today).await?;
+    let after = SiteStats::list(pool, 1).await?;
+    assert_eq!(1, after.len());
+    assert_eq!(before[1].signups + 1, after[0].signups);
+    assert!(after[0].updated_at >= before[1].updated_at);
+
+    // The previous day isn't affected
+    SiteStats::refresh(pool, yesterday).await?;
+    assert_eq!(
+      before[0].signups,
+      SiteStats::list(pool, 2).await?[0].signups
+    );
+
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
+}
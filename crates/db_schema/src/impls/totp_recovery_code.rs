@@ -0,0 +1,136 @@
+use crate::{
+  newtypes::LocalUserId,
+  source::totp_recovery_code::{TotpRecoveryCode, TotpRecoveryCodeInsertForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, dsl::insert_into};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::totp_recovery_code;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl TotpRecoveryCode {
+  /// Replace all existing recovery codes of the user with new ones.
+  pub async fn replace_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    code_hashes: Vec<String>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let forms = code_hashes
+      .into_iter()
+      .map(|code_hash| TotpRecoveryCodeInsertForm {
+        local_user_id,
+        code_hash,
+      })
+      .collect::<Vec<_>>();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(totp_recovery_code::table)
+            .filter(totp_recovery_code::local_user_id.eq(local_user_id))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          insert_into(totp_recovery_code::table)
+            .values(forms)
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Consume a recovery code, so that it can't be used again. Returns an error if the code is
+  /// invalid.
+  pub async fn use_code(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    code_hash: &str,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let deleted = delete(totp_recovery_code::table.find((local_user_id, code_hash)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)?;
+    if deleted == 0 {
+      return Err(LemmyErrorType::IncorrectTotpToken.into());
+    }
+    Ok(())
+  }
+
+  pub async fn delete_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(totp_recovery_code::table.filter(totp_recovery_code::local_user_id.eq(local_user_id)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+    totp_recovery_code::TotpRecoveryCode,
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_totp_recovery_code() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "thommy trc");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_local_user = LocalUserInsertForm::test_form(inserted_person.id);
+    let local_user = LocalUser::create(pool, &new_local_user, vec![]).await?;
+
+    let hashes = vec!["hash1".to_string(), "hash2".to_string()];
+    TotpRecoveryCode::replace_for_user(pool, local_user.id, hashes).await?;
+
+    // Each code can only be used once
+    TotpRecoveryCode::use_code(pool, local_user.id, "hash1").await?;
+    assert!(
+      TotpRecoveryCode::use_code(pool, local_user.id, "hash1")
+        .await
+        .is_err()
+    );
+    assert!(
+      TotpRecoveryCode::use_code(pool, local_user.id, "unknown")
+        .await
+        .is_err()
+    );
+
+    // Regenerating invalidates the old codes
+    TotpRecoveryCode::replace_for_user(pool, local_user.id, vec!["hash3".to_string()]).await?;
+    assert!(
+      TotpRecoveryCode::use_code(pool, local_user.id, "hash2")
+        .await
+        .is_err()
+    );
+    assert_eq!(
+      1,
+      TotpRecoveryCode::delete_for_user(pool, local_user.id).await?
+    );
+
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
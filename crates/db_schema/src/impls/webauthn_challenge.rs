@@ -0,0 +1,46 @@
+use crate::source::webauthn_challenge::{WebauthnChallenge, WebauthnChallengeInsertForm};
+use diesel::{
+  ExpressionMethods,
+  IntoSql,
+  delete,
+  dsl::{IntervalDsl, insert_into, now},
+  sql_types::Timestamptz,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::webauthn_challenge;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl WebauthnChallenge {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &WebauthnChallengeInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webauthn_challenge::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Challenges can only be used once, and expire after five minutes.
+  pub async fn read_and_delete(pool: &mut DbPool<'_>, challenge: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    delete(webauthn_challenge::table)
+      .filter(webauthn_challenge::challenge.eq(challenge))
+      .filter(webauthn_challenge::published_at.gt(now.into_sql::<Timestamptz>() - 5.minutes()))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidWebauthnCredential)
+  }
+
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(webauthn_challenge::table)
+      .filter(webauthn_challenge::published_at.lt(now.into_sql::<Timestamptz>() - 5.minutes()))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
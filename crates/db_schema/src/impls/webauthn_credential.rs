@@ -0,0 +1,92 @@
+use crate::{
+  newtypes::{LocalUserId, WebauthnCredentialId},
+  source::webauthn_credential::{WebauthnCredential, WebauthnCredentialInsertForm},
+};
+use diesel::{
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  delete,
+  dsl::insert_into,
+  update,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::webauthn_credential;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl WebauthnCredential {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &WebauthnCredentialInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webauthn_credential::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read_from_credential_id(
+    pool: &mut DbPool<'_>,
+    credential_id: &str,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    webauthn_credential::table
+      .filter(webauthn_credential::credential_id.eq(credential_id))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidWebauthnCredential)
+  }
+
+  pub async fn list_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    webauthn_credential::table
+      .filter(webauthn_credential::local_user_id.eq(local_user_id))
+      .order(webauthn_credential::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Store the new signature counter after a successful login.
+  pub async fn mark_used(
+    pool: &mut DbPool<'_>,
+    id: WebauthnCredentialId,
+    sign_count: i64,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(webauthn_credential::table.find(id))
+      .set((
+        webauthn_credential::sign_count.eq(sign_count),
+        webauthn_credential::last_used_at.eq(now().nullable()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    id: WebauthnCredentialId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      webauthn_credential::table
+        .find(id)
+        .filter(webauthn_credential::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
@@ -0,0 +1,287 @@
+use crate::{
+  newtypes::WebhookId,
+  source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookInsertForm,
+    WebhookUpdateForm,
+    webhook_delivery_keys as key,
+  },
+  utils::limit_fetch,
+};
+use chrono::{DateTime, Utc};
+use diesel::{ExpressionMethods, QueryDsl, dsl::IntervalDsl, insert_into};
+use diesel_async::RunQueryDsl;
+use i_love_jesus::SortDirection;
+use lemmy_db_schema_file::{
+  enums::WebhookEvent,
+  schema::{webhook, webhook_delivery},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+  utils::now,
+};
+use lemmy_utils::{
+  DB_BATCH_SIZE,
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+};
+
+impl Crud for Webhook {
+  type InsertForm = WebhookInsertForm;
+  type UpdateForm = WebhookUpdateForm;
+  type IdType = WebhookId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook::table.find(webhook_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl Webhook {
+  pub async fn list(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    webhook::table
+      .order(webhook::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Enabled webhooks which want to receive the given event.
+  pub async fn list_for_event(
+    pool: &mut DbPool<'_>,
+    event: WebhookEvent,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let query = webhook::table
+      .filter(webhook::enabled.eq(true))
+      .into_boxed();
+    let query = match event {
+      WebhookEvent::PostCreated => query.filter(webhook::post_created.eq(true)),
+      WebhookEvent::PersonCreated => query.filter(webhook::person_created.eq(true)),
+      WebhookEvent::ReportCreated => query.filter(webhook::report_created.eq(true)),
+      WebhookEvent::CommunityCreated => query.filter(webhook::community_created.eq(true)),
+    };
+    query
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl PaginationCursorConversion for WebhookDelivery {
+  type PaginatedType = WebhookDelivery;
+
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.id)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    let conn = &mut get_conn(pool).await?;
+    webhook_delivery::table
+      .find(cursor.id()?)
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl WebhookDelivery {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    forms: &[WebhookDeliveryInsertForm],
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook_delivery::table)
+      .values(forms)
+      .get_results::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Deliveries which are due for another attempt, together with their webhook. Deliveries to
+  /// disabled webhooks are paused.
+  pub async fn list_pending(pool: &mut DbPool<'_>) -> LemmyResult<Vec<(Self, Webhook)>> {
+    let conn = &mut get_conn(pool).await?;
+    webhook_delivery::table
+      .inner_join(webhook::table)
+      .filter(webhook_delivery::next_attempt_at.le(now()))
+      .filter(webhook::enabled.eq(true))
+      .select((webhook_delivery::all_columns, webhook::all_columns))
+      .order(webhook_delivery::next_attempt_at)
+      .limit(DB_BATCH_SIZE)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Stores the result of a delivery attempt. It is successful if there is no error, otherwise it
+  /// is retried at `next_attempt_at`.
+  pub async fn update_attempt(
+    pool: &mut DbPool<'_>,
+    id: i32,
+    status_code: Option<i32>,
+    error: Option<String>,
+    next_attempt_at: Option<DateTime<Utc>>,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let delivered_at = error.is_none().then(Utc::now);
+    diesel::update(webhook_delivery::table.find(id))
+      .set((
+        webhook_delivery::attempts.eq(webhook_delivery::attempts + 1),
+        webhook_delivery::status_code.eq(status_code),
+        webhook_delivery::error.eq(error),
+        webhook_delivery::delivered_at.eq(delivered_at),
+        webhook_delivery::next_attempt_at.eq(next_attempt_at),
+      ))
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let query = webhook_delivery::table
+      .filter(webhook_delivery::webhook_id.eq(webhook_id))
+      .limit(limit)
+      .into_boxed();
+    let paginated_query = Self::paginate(query, &page_cursor, SortDirection::Desc, pool)
+      .await?
+      .then_order_by(key::published_at)
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+
+  /// Delivery logs are only kept for a limited time.
+  pub async fn delete_old(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      webhook_delivery::table
+        .filter(webhook_delivery::published_at.lt(now() - IntervalDsl::days(30)))
+        .filter(webhook_delivery::next_attempt_at.is_null()),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+  use crate::source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookInsertForm,
+    WebhookUpdateForm,
+  };
+  use chrono::Utc;
+  use lemmy_db_schema_file::enums::WebhookEvent;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_webhooks() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let form = WebhookInsertForm {
+      url: Url::parse("https://example.com/hook")?.into(),
+      secret: "secret".to_string(),
+      post_created: true,
+      person_created: false,
+      report_created: true,
+      community_created: false,
+    };
+    let webhook = Webhook::create(pool, &form).await?;
+
+    let for_post = Webhook::list_for_event(pool, WebhookEvent::PostCreated).await?;
+    assert_eq!(vec![webhook.clone()], for_post);
+    let for_person = Webhook::list_for_event(pool, WebhookEvent::PersonCreated).await?;
+    assert!(for_person.is_empty());
+
+    // Disabled webhooks don't receive any events
+    let form = WebhookUpdateForm {
+      enabled: Some(false),
+      ..Default::default()
+    };
+    Webhook::update(pool, webhook.id, &form).await?;
+    let for_post = Webhook::list_for_event(pool, WebhookEvent::PostCreated).await?;
+    assert!(for_post.is_empty());
+
+    let form = WebhookDeliveryInsertForm {
+      webhook_id: webhook.id,
+      event: WebhookEvent::PostCreated,
+      payload: "{}".to_string(),
+      next_attempt_at: Some(Utc::now()),
+    };
+    let deliveries = WebhookDelivery::create(pool, &[form]).await?;
+    assert!(WebhookDelivery::list_pending(pool).await?.is_empty());
+
+    let form = WebhookUpdateForm {
+      enabled: Some(true),
+      ..Default::default()
+    };
+    let webhook = Webhook::update(pool, webhook.id, &form).await?;
+    let pending = WebhookDelivery::list_pending(pool).await?;
+    assert_eq!(vec![(deliveries[0].clone(), webhook.clone())], pending);
+
+    let delivery =
+      WebhookDelivery::update_attempt(pool, deliveries[0].id, Some(200), None, None).await?;
+    assert_eq!(1, delivery.attempts);
+    assert!(delivery.delivered_at.is_some());
+    assert!(WebhookDelivery::list_pending(pool).await?.is_empty());
+
+    let logs = WebhookDelivery::list(pool, webhook.id, None, None).await?;
+    assert_eq!(vec![delivery], logs.items);
+
+    Webhook::delete(pool, webhook.id).await?;
+
+    Ok(())
+  }
+}
@@ -34,6 +34,7 @@ use {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The search sort types.
 pub enum SearchSortType {
   #[default]
@@ -42,11 +43,27 @@ pub enum SearchSortType {
   Old,
 }
 
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// The types of results which can be returned by search.
+pub enum SearchType {
+  Posts,
+  Comments,
+  Communities,
+  Persons,
+  MultiCommunities,
+}
+
 /// The community sort types. See here for descriptions: https://join-lemmy.org/docs/en/users/03-votes-and-ranking.html
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum CommunitySortType {
   ActiveSixMonths,
   #[default]
@@ -69,6 +86,7 @@ pub enum CommunitySortType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum LocalUserSortType {
   #[default]
   New,
@@ -80,6 +98,7 @@ pub enum LocalUserSortType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum PersonSortType {
   #[default]
   New,
@@ -92,6 +111,7 @@ pub enum PersonSortType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum MultiCommunitySortType {
   New,
   Old,
@@ -107,6 +127,7 @@ pub enum MultiCommunitySortType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A listing type for multi-community fetches.
 pub enum MultiCommunityListingType {
   /// Content from your own site, as well as all connected / federated sites.
@@ -122,6 +143,7 @@ pub enum MultiCommunityListingType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A listing type for person fetches
 pub enum PersonListingType {
   /// persons from your own site, as well as all connected / federated sites.
@@ -137,6 +159,7 @@ pub enum PersonListingType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A list of possible types for the inbox.
 pub enum NotificationTypeFilter {
   #[default]
@@ -151,6 +174,7 @@ pub enum NotificationTypeFilter {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A list of possible types for the various modlog actions.
 pub enum ModlogKindFilter {
   #[default]
@@ -163,6 +187,7 @@ pub enum ModlogKindFilter {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A list of possible types for a person's content.
 pub enum PersonContentType {
   All,
@@ -174,6 +199,7 @@ pub enum PersonContentType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A list of possible types for reports.
 pub enum ReportType {
   All,
@@ -181,6 +207,7 @@ pub enum ReportType {
   Comments,
   PrivateMessages,
   Communities,
+  Instances,
 }
 
 #[derive(
@@ -189,6 +216,7 @@ pub enum ReportType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The feature type for a post.
 pub enum PostFeatureType {
   #[default]
@@ -204,6 +232,7 @@ pub enum PostFeatureType {
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The like_type for a persons liked content.
 pub enum LikeType {
   #[default]
@@ -212,6 +241,19 @@ pub enum LikeType {
   DislikedOnly,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// What a live stream of new content follows.
+pub enum StreamType {
+  /// New posts in a community, and new comments and votes on them.
+  Community,
+  /// New comments and votes in a single post.
+  Post,
+}
+
 /// Wrapper for assert_eq! macro. Checks that vec matches the given length, and prints the
 /// vec on failure.
 #[macro_export]
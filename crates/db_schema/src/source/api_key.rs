@@ -0,0 +1,41 @@
+use crate::newtypes::{ApiKeyId, LocalUserId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::TokenScope;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::api_key;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_key))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A key which bots can use to access the account of a user, without knowing the password.
+pub struct ApiKey {
+  pub id: ApiKeyId,
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  /// The key itself is only shown once on creation, only a hash is stored.
+  #[serde(skip)]
+  pub key_hash: String,
+  pub scope: TokenScope,
+  /// Maximum number of requests per minute which can be made with this key.
+  pub rate_limit: Option<i32>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_key))]
+pub struct ApiKeyInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub key_hash: String,
+  pub scope: TokenScope,
+  #[new(default)]
+  pub rate_limit: Option<i32>,
+}
@@ -1,6 +1,7 @@
 use crate::newtypes::{
   CommentReportId,
   CommunityReportId,
+  InstanceReportId,
   PostReportId,
   PrivateMessageReportId,
   ReportCombinedId,
@@ -31,4 +32,5 @@ pub struct ReportCombined {
   pub private_message_report_id: Option<PrivateMessageReportId>,
   pub community_report_id: Option<CommunityReportId>,
   pub resolved: bool,
+  pub instance_report_id: Option<InstanceReportId>,
 }
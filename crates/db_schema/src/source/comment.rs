@@ -24,6 +24,7 @@ use {
 #[cfg_attr(feature = "full", cursor_keys_module(name = comment_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A comment.
 pub struct Comment {
   pub id: CommentId,
@@ -43,6 +44,7 @@ pub struct Comment {
   #[cfg(feature = "full")]
   #[cfg_attr(feature = "full", serde(with = "LtreeDef"))]
   #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
   /// The path / tree location of a comment, separated by dots, ending with the comment's id. Ex:
   /// 0.24.27
   pub path: Ltree,
@@ -67,6 +69,9 @@ pub struct Comment {
   pub federation_pending: bool,
   /// Whether the comment is locked.
   pub locked: bool,
+  /// Last change of the content, or of the deleted, removed, distinguished or locked state. Votes
+  /// and replies don't change it.
+  pub changed_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, derive_new::new, Serialize, Deserialize)]
@@ -128,6 +133,7 @@ pub struct CommentUpdateForm {
 #[cfg_attr(feature = "full", cursor_keys_module(name = comment_actions_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CommentActions {
   /// When the comment was upvoted or downvoted.
   pub voted_at: Option<DateTime<Utc>>,
@@ -17,6 +17,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A comment report.
 pub struct CommentReport {
   pub id: CommentReportId,
@@ -3,7 +3,14 @@ use chrono::{DateTime, Utc};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  enums::{CommunityFollowerState, CommunityNotificationsMode, CommunityVisibility},
+  enums::{
+    CommentSortType,
+    CommunityFollowerState,
+    CommunityNotificationsMode,
+    CommunityVisibility,
+    DuplicateUrlPolicy,
+    PostSortType,
+  },
 };
 use lemmy_diesel_utils::{dburl::DbUrl, sensitive::SensitiveString};
 use serde::{Deserialize, Serialize};
@@ -25,6 +32,7 @@ use {
 #[cfg_attr(feature = "full", cursor_keys_module(name = community_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A community.
 pub struct Community {
   pub id: CommunityId,
@@ -94,6 +102,19 @@ pub struct Community {
   pub report_count: i16,
   pub unresolved_report_count: i16,
   pub local_removed: bool,
+  /// Whether the community is quarantined by an admin. Quarantined communities are hidden from
+  /// the All and Local feeds, search and suggestions, but remain accessible to subscribers.
+  pub quarantined: bool,
+  pub duplicate_url_policy: DuplicateUrlPolicy,
+  /// Number of days during which a url counts as duplicate after it was posted.
+  pub duplicate_url_days: i32,
+  /// Whether members can post and comment under a pseudonym, which is generated for each of them.
+  pub pseudonymous_posting: bool,
+  /// Post sort used in the community if none is requested. Takes precedence over the default
+  /// sort of the user and site.
+  pub default_post_sort_type: Option<PostSortType>,
+  /// Comment sort used in the community if none is requested.
+  pub default_comment_sort_type: Option<CommentSortType>,
 }
 
 #[derive(Debug, Clone, derive_new::new)]
@@ -144,6 +165,16 @@ pub struct CommunityInsertForm {
   pub summary: Option<String>,
   #[new(default)]
   pub local_removed: Option<bool>,
+  #[new(default)]
+  pub duplicate_url_policy: Option<DuplicateUrlPolicy>,
+  #[new(default)]
+  pub duplicate_url_days: Option<i32>,
+  #[new(default)]
+  pub pseudonymous_posting: Option<bool>,
+  #[new(default)]
+  pub default_post_sort_type: Option<PostSortType>,
+  #[new(default)]
+  pub default_comment_sort_type: Option<CommentSortType>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -172,6 +203,12 @@ pub struct CommunityUpdateForm {
   pub visibility: Option<CommunityVisibility>,
   pub summary: Option<Option<String>>,
   pub local_removed: Option<bool>,
+  pub quarantined: Option<bool>,
+  pub duplicate_url_policy: Option<DuplicateUrlPolicy>,
+  pub duplicate_url_days: Option<i32>,
+  pub pseudonymous_posting: Option<bool>,
+  pub default_post_sort_type: Option<Option<PostSortType>>,
+  pub default_comment_sort_type: Option<Option<CommentSortType>>,
 }
 
 #[skip_serializing_none]
@@ -190,6 +227,7 @@ pub struct CommunityUpdateForm {
 #[cfg_attr(feature = "full", cursor_keys_module(name = community_actions_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CommunityActions {
   /// When the community was followed.
   pub followed_at: Option<DateTime<Utc>>,
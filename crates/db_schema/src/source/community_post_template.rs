@@ -0,0 +1,55 @@
+use crate::newtypes::{CommunityId, CommunityPostTemplateId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_post_template;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_post_template))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A template for new posts, which is created by community moderators and offered by clients in
+/// the post composer.
+pub struct CommunityPostTemplate {
+  pub id: CommunityPostTemplateId,
+  pub community_id: CommunityId,
+  pub name: String,
+  /// Prefilled title, like `[Bug] `.
+  pub title: Option<String>,
+  /// Prefilled body in markdown.
+  pub body: Option<String>,
+  /// Whether posts created with the template must contain all headings of its body.
+  pub enforce_headings: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_post_template))]
+pub struct CommunityPostTemplateInsertForm {
+  pub community_id: CommunityId,
+  pub name: String,
+  #[new(default)]
+  pub title: Option<String>,
+  #[new(default)]
+  pub body: Option<String>,
+  #[new(default)]
+  pub enforce_headings: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_post_template))]
+pub struct CommunityPostTemplateUpdateForm {
+  pub name: Option<String>,
+  pub title: Option<Option<String>>,
+  pub body: Option<Option<String>>,
+  pub enforce_headings: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,33 @@
+use crate::newtypes::CommunityId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_pseudonym;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_pseudonym))]
+#[cfg_attr(feature = "full", diesel(primary_key(community_id, person_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// The pseudonym under which a person posts in a community. Only visible to admins.
+pub struct CommunityPseudonym {
+  pub community_id: CommunityId,
+  /// The real person.
+  pub person_id: PersonId,
+  /// A local person without a local user, which is shown as the creator of posts and comments.
+  pub pseudonym_id: PersonId,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_pseudonym))]
+pub struct CommunityPseudonymInsertForm {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub pseudonym_id: PersonId,
+}
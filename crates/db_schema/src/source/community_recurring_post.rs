@@ -0,0 +1,63 @@
+use crate::newtypes::{CommunityId, CommunityRecurringPostId, PostId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_recurring_post;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_recurring_post))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A post which is created automatically at a regular interval, like a weekly discussion thread.
+pub struct CommunityRecurringPost {
+  pub id: CommunityRecurringPostId,
+  pub community_id: CommunityId,
+  /// The moderator account which is used as creator of the posts.
+  pub creator_id: PersonId,
+  /// Title of the posts. `{date}` is replaced with the date of the post in `YYYY-MM-DD` format,
+  /// and `{weekday}` with the english name of its weekday.
+  pub title: String,
+  pub body: Option<String>,
+  pub interval_days: i32,
+  pub next_post_at: DateTime<Utc>,
+  /// Whether the post is featured in the community, replacing the previous instance.
+  pub feature: bool,
+  pub last_post_id: Option<PostId>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_recurring_post))]
+pub struct CommunityRecurringPostInsertForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub title: String,
+  pub interval_days: i32,
+  pub next_post_at: DateTime<Utc>,
+  #[new(default)]
+  pub body: Option<String>,
+  #[new(default)]
+  pub feature: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_recurring_post))]
+pub struct CommunityRecurringPostUpdateForm {
+  pub creator_id: Option<PersonId>,
+  pub title: Option<String>,
+  pub body: Option<Option<String>>,
+  pub interval_days: Option<i32>,
+  pub next_post_at: Option<DateTime<Utc>>,
+  pub feature: Option<bool>,
+  pub last_post_id: Option<Option<PostId>>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
@@ -18,6 +18,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CommunityTag {
   pub id: CommunityTagId,
   pub ap_id: DbUrl,
@@ -65,6 +66,7 @@ pub struct CommunityTagUpdateForm {
 #[cfg_attr(feature = "full", diesel(sql_type = Nullable<diesel::sql_types::Json>))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CommunityTagsView(pub Vec<CommunityTag>);
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -0,0 +1,29 @@
+use crate::newtypes::CommunityId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_trend;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_trend))]
+#[cfg_attr(feature = "full", diesel(primary_key(community_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Activity of a community over the last day and week, which is periodically recalculated.
+pub struct CommunityTrend {
+  pub community_id: CommunityId,
+  pub posts_day: i32,
+  pub posts_week: i32,
+  pub comments_day: i32,
+  pub comments_week: i32,
+  /// New subscribers over the last day.
+  pub subscribers_day: i32,
+  /// New subscribers over the last week.
+  pub subscribers_week: i32,
+  /// Higher for communities which are more active over the last day than usual.
+  pub score: f32,
+  pub updated_at: DateTime<Utc>,
+}
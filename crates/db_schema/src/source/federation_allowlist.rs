@@ -19,6 +19,7 @@ use std::fmt::Debug;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct FederationAllowList {
   #[serde(skip)]
   pub instance_id: InstanceId,
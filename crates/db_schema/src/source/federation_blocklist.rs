@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
-use lemmy_db_schema_file::InstanceId;
 #[cfg(feature = "full")]
 use lemmy_db_schema_file::schema::federation_blocklist;
+use lemmy_db_schema_file::{InstanceId, enums::FederationBlockSeverity};
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use std::fmt::Debug;
 
+#[skip_serializing_none]
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[cfg_attr(
   feature = "full",
@@ -19,12 +21,16 @@ use std::fmt::Debug;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct FederationBlockList {
   #[serde(skip)]
   pub instance_id: InstanceId,
   pub published_at: DateTime<Utc>,
   pub updated_at: Option<DateTime<Utc>>,
   pub expires_at: Option<DateTime<Utc>>,
+  pub severity: FederationBlockSeverity,
+  /// Public reason for the block, shown on the instances page.
+  pub reason: Option<String>,
 }
 
 #[derive(Clone, Default, derive_new::new)]
@@ -35,4 +41,8 @@ pub struct FederationBlockListForm {
   #[new(default)]
   pub updated_at: Option<DateTime<Utc>>,
   pub expires_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub severity: FederationBlockSeverity,
+  #[new(default)]
+  pub reason: Option<String>,
 }
@@ -0,0 +1,43 @@
+use crate::newtypes::{HashtagId, PostId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{hashtag, post_hashtag};
+use serde::{Deserialize, Serialize};
+
+/// A hashtag which is used in at least one post, eg `#lemmy`. The name is stored lowercase and
+/// without the leading `#`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = hashtag))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Hashtag {
+  pub id: HashtagId,
+  pub name: String,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = hashtag))]
+pub struct HashtagInsertForm {
+  pub name: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Associations, Identifiable, Insertable)
+)]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::post::Post)))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::hashtag::Hashtag)))]
+#[cfg_attr(feature = "full", diesel(table_name = post_hashtag))]
+#[cfg_attr(feature = "full", diesel(primary_key(post_id, hashtag_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// An association between a post and a hashtag.
+pub struct PostHashtag {
+  pub post_id: PostId,
+  pub hashtag_id: HashtagId,
+}
@@ -24,12 +24,15 @@ use {
 #[cfg_attr(feature = "full", cursor_keys_module(name = local_image_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LocalImage {
   pub pictrs_alias: String,
   pub published_at: DateTime<Utc>,
   pub person_id: Option<PersonId>,
   /// This means the image is an auto-generated thumbnail, for a post.
   pub thumbnail_for_post_id: Option<PostId>,
+  /// Zero for images which were uploaded before sizes were recorded.
+  pub size_bytes: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +42,7 @@ pub struct LocalImageForm {
   pub pictrs_alias: String,
   pub person_id: PersonId,
   pub thumbnail_for_post_id: Option<Option<PostId>>,
+  pub size_bytes: Option<i64>,
 }
 
 /// Stores all images which are hosted on remote domains. When attempting to proxy an image, it
@@ -52,6 +56,8 @@ pub struct LocalImageForm {
 pub struct RemoteImage {
   pub link: DbUrl,
   pub published_at: DateTime<Utc>,
+  /// Alias of a permanent copy in pictrs, which is served instead of fetching the remote image.
+  pub cached_alias: Option<String>,
 }
 
 #[skip_serializing_none]
@@ -62,12 +68,15 @@ pub struct RemoteImage {
 #[cfg_attr(feature = "full", diesel(primary_key(link)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ImageDetails {
   pub link: DbUrl,
   pub width: i32,
   pub height: i32,
   pub content_type: String,
   pub blurhash: Option<String>,
+  /// Only set for videos.
+  pub duration_seconds: Option<i32>,
 }
 
 #[derive(Debug, Clone)]
@@ -79,4 +88,6 @@ pub struct ImageDetailsInsertForm {
   pub height: i32,
   pub content_type: String,
   pub blurhash: Option<String>,
+  /// Only set for videos.
+  pub duration_seconds: Option<i32>,
 }
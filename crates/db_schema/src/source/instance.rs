@@ -20,6 +20,7 @@ use {
 #[cfg_attr(feature = "full", cursor_keys_module(name = instance_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Basic data about a Fediverse instance which is available for every known domain. Additional
 /// data may be available in [[Site]].
 pub struct Instance {
@@ -32,6 +33,11 @@ pub struct Instance {
   pub software: Option<String>,
   /// The version of the instance's software.
   pub version: Option<String>,
+  /// Set when the instance was unreachable for a while. No activities are sent to dead instances.
+  pub dead_since: Option<DateTime<Utc>>,
+  /// When a dead instance is checked again to see if it is back online. The interval grows with
+  /// the time it has been dead, up to a month.
+  pub next_probe_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -61,6 +67,7 @@ pub struct InstanceForm {
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct InstanceActions {
   /// When the instance's communities were blocked.
   pub blocked_communities_at: Option<DateTime<Utc>>,
@@ -0,0 +1,42 @@
+use crate::newtypes::InstanceMoveId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::instance_move;
+use lemmy_db_schema_file::{InstanceId, PersonId};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = instance_move))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A remote instance announced that it moved to a new domain. The stored urls of the instance are
+/// only rewritten once an admin approved the move.
+pub struct InstanceMove {
+  pub id: InstanceMoveId,
+  pub instance_id: InstanceId,
+  pub old_domain: String,
+  pub new_domain: String,
+  pub old_origin: String,
+  pub new_origin: String,
+  pub published_at: DateTime<Utc>,
+  /// None while the move is pending.
+  pub approved: Option<bool>,
+  pub resolver_id: Option<PersonId>,
+  pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = instance_move))]
+pub struct InstanceMoveInsertForm {
+  pub instance_id: InstanceId,
+  pub old_domain: String,
+  pub new_domain: String,
+  pub old_origin: String,
+  pub new_origin: String,
+}
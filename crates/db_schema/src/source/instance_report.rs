@@ -0,0 +1,45 @@
+use crate::newtypes::InstanceReportId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::instance_report;
+use lemmy_db_schema_file::{InstanceId, PersonId};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Associations, Identifiable)
+)]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::instance::Instance))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = instance_report))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An instance report. These are only shown to local admins and never federated.
+pub struct InstanceReport {
+  pub id: InstanceReportId,
+  pub creator_id: PersonId,
+  pub instance_id: InstanceId,
+  pub original_instance_domain: String,
+  pub reason: String,
+  pub resolved: bool,
+  pub resolver_id: Option<PersonId>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = instance_report))]
+pub struct InstanceReportForm {
+  pub creator_id: PersonId,
+  pub instance_id: InstanceId,
+  pub original_instance_domain: String,
+  pub reason: String,
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::link_metadata_override;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Changes how link previews are generated for a domain and its subdomains, eg for sites which
+/// block metadata fetching or need a specific oEmbed endpoint.
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = link_metadata_override))]
+#[cfg_attr(feature = "full", diesel(primary_key(domain)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LinkMetadataOverride {
+  pub domain: String,
+  /// Use this oEmbed endpoint instead of the one which is discovered from the page.
+  pub oembed_endpoint: Option<DbUrl>,
+  /// Don't fetch any metadata for links to this domain.
+  pub disable_metadata: bool,
+  /// Don't embed videos or other media from this domain.
+  pub disable_embed: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = link_metadata_override))]
+pub struct LinkMetadataOverrideForm {
+  pub domain: String,
+  pub oembed_endpoint: Option<DbUrl>,
+  pub disable_metadata: bool,
+  pub disable_embed: bool,
+}
@@ -5,6 +5,7 @@ use lemmy_db_schema_file::schema::local_site;
 use lemmy_db_schema_file::{
   PersonId,
   enums::{
+    CaptchaProvider,
     CommentSortType,
     FederationMode,
     ImageMode,
@@ -25,6 +26,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The local site.
 pub struct LocalSite {
   pub id: LocalSiteId,
@@ -113,6 +115,78 @@ pub struct LocalSite {
   /// This affects post and comment images, but not avatars and banners.
   pub image_allow_video_uploads: bool,
   pub image_upload_disabled: bool,
+  /// Accounts younger than this many days are restricted. Zero disables the age check.
+  pub new_account_min_age_days: i32,
+  /// Accounts with less karma (post and comment score) than this are restricted. Zero disables
+  /// the karma check.
+  pub new_account_min_karma: i32,
+  /// The maximum number of posts a restricted account can create per day.
+  pub new_account_max_posts_per_day: Option<i32>,
+  /// Dont allow restricted accounts to create link posts.
+  pub new_account_link_posts_disabled: bool,
+  /// Dont allow restricted accounts to upload images.
+  pub new_account_image_upload_disabled: bool,
+  /// Dont allow restricted accounts to create communities.
+  pub new_account_community_creation_disabled: bool,
+  /// Admins and moderators need to enable two-factor authentication before they can take
+  /// moderation actions.
+  pub moderators_require_totp: bool,
+  /// Days between an account deletion request and the actual erasure. Logging in during this
+  /// time cancels the deletion. If 0, accounts are erased right away.
+  pub account_deletion_grace_period_days: i32,
+  /// The maximum number of registrations from a single ip per day.
+  pub registrations_per_ip_per_day: Option<i32>,
+  /// A DNS blocklist zone, registrations from listed ips are rejected.
+  pub registration_dnsbl: Option<String>,
+  /// Days to keep the registration ip of new users, for admin review. If 0, it isn't stored.
+  pub registration_ip_retention_days: i32,
+  /// If set, registrations require solving a proof of work challenge with this number of
+  /// leading zero bits.
+  pub pow_challenge_difficulty: Option<i32>,
+  /// Only allow fetching activitypub objects with signed requests, and sign all outgoing fetches.
+  /// Needed for Mastodon servers running in secure mode.
+  pub authorized_fetch: bool,
+  /// Comma-delimited wildcard (`*.example.com`) or regex (`/regex/`) patterns for domains which
+  /// are blocked from federation.
+  pub blocked_instances_patterns: Option<String>,
+  /// Patterns for domains which are allowed to federate, in addition to the allowlist.
+  pub allowed_instances_patterns: Option<String>,
+  /// Maximum storage in megabytes which a single user can use for uploads. Admins are exempt.
+  pub image_upload_quota_mb: Option<i32>,
+  /// Re-encode uploaded images, which removes all metadata like EXIF and GPS positions.
+  pub image_strip_metadata: bool,
+  /// Convert uploaded images to webp.
+  pub image_convert_to_webp: bool,
+  /// Allow animated images in posts and comments. Avatars and banners are never animated.
+  pub image_allow_animated: bool,
+  /// Uploads which are wider than this many pixels are rejected.
+  pub image_max_width: Option<i32>,
+  /// Uploads which are higher than this many pixels are rejected.
+  pub image_max_height: Option<i32>,
+  /// Rewrite external images in markdown to go through the image proxy, also if the image mode
+  /// isn't [[ImageMode.ProxyAllImages]].
+  pub image_proxy_markdown: bool,
+  /// If set, only external images in markdown from these domains and their subdomains are
+  /// proxied, others are removed. Use a comma-delimited string.
+  pub image_proxy_safelist: Option<String>,
+  /// External images in markdown which are larger than this many megabytes are removed.
+  pub image_proxy_max_size_mb: Option<i32>,
+  /// Which captcha is shown during registration.
+  pub captcha_provider: CaptchaProvider,
+  /// The public site key of an external captcha provider, which is needed by clients to render
+  /// the captcha.
+  pub captcha_site_key: Option<String>,
+  #[serde(skip)]
+  pub captcha_secret_key: Option<String>,
+  /// Url of the mCaptcha instance.
+  pub captcha_instance_url: Option<String>,
+  /// Limit to the number of concurrent outgoing federation requests per target instance.
+  pub federation_concurrent_sends_per_instance: i32,
+  /// Maximum size of uploaded videos in megabytes. Video uploads also need to be enabled.
+  pub video_max_upload_size_mb: i32,
+  /// Maximum duration of uploaded videos in seconds. This is only checked if the transcoding
+  /// webhook reports the duration.
+  pub video_max_duration_seconds: i32,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -192,6 +266,68 @@ pub struct LocalSiteInsertForm {
   pub image_allow_video_uploads: Option<bool>,
   #[new(default)]
   pub image_upload_disabled: Option<bool>,
+  #[new(default)]
+  pub new_account_min_age_days: Option<i32>,
+  #[new(default)]
+  pub new_account_min_karma: Option<i32>,
+  #[new(default)]
+  pub new_account_max_posts_per_day: Option<i32>,
+  #[new(default)]
+  pub new_account_link_posts_disabled: Option<bool>,
+  #[new(default)]
+  pub new_account_image_upload_disabled: Option<bool>,
+  #[new(default)]
+  pub new_account_community_creation_disabled: Option<bool>,
+  #[new(default)]
+  pub moderators_require_totp: Option<bool>,
+  #[new(default)]
+  pub account_deletion_grace_period_days: Option<i32>,
+  #[new(default)]
+  pub registrations_per_ip_per_day: Option<i32>,
+  #[new(default)]
+  pub registration_dnsbl: Option<String>,
+  #[new(default)]
+  pub registration_ip_retention_days: Option<i32>,
+  #[new(default)]
+  pub pow_challenge_difficulty: Option<i32>,
+  #[new(default)]
+  pub authorized_fetch: Option<bool>,
+  #[new(default)]
+  pub blocked_instances_patterns: Option<String>,
+  #[new(default)]
+  pub allowed_instances_patterns: Option<String>,
+  #[new(default)]
+  pub image_upload_quota_mb: Option<i32>,
+  #[new(default)]
+  pub image_strip_metadata: Option<bool>,
+  #[new(default)]
+  pub image_convert_to_webp: Option<bool>,
+  #[new(default)]
+  pub image_allow_animated: Option<bool>,
+  #[new(default)]
+  pub image_max_width: Option<i32>,
+  #[new(default)]
+  pub image_max_height: Option<i32>,
+  #[new(default)]
+  pub image_proxy_markdown: Option<bool>,
+  #[new(default)]
+  pub image_proxy_safelist: Option<String>,
+  #[new(default)]
+  pub image_proxy_max_size_mb: Option<i32>,
+  #[new(default)]
+  pub captcha_provider: Option<CaptchaProvider>,
+  #[new(default)]
+  pub captcha_site_key: Option<String>,
+  #[new(default)]
+  pub captcha_secret_key: Option<String>,
+  #[new(default)]
+  pub captcha_instance_url: Option<String>,
+  #[new(default)]
+  pub federation_concurrent_sends_per_instance: Option<i32>,
+  #[new(default)]
+  pub video_max_upload_size_mb: Option<i32>,
+  #[new(default)]
+  pub video_max_duration_seconds: Option<i32>,
 }
 
 #[derive(Clone, Default)]
@@ -235,4 +371,35 @@ pub struct LocalSiteUpdateForm {
   pub image_max_upload_size: Option<i32>,
   pub image_allow_video_uploads: Option<bool>,
   pub image_upload_disabled: Option<bool>,
+  pub new_account_min_age_days: Option<i32>,
+  pub new_account_min_karma: Option<i32>,
+  pub new_account_max_posts_per_day: Option<Option<i32>>,
+  pub new_account_link_posts_disabled: Option<bool>,
+  pub new_account_image_upload_disabled: Option<bool>,
+  pub new_account_community_creation_disabled: Option<bool>,
+  pub moderators_require_totp: Option<bool>,
+  pub account_deletion_grace_period_days: Option<i32>,
+  pub registrations_per_ip_per_day: Option<Option<i32>>,
+  pub registration_dnsbl: Option<Option<String>>,
+  pub registration_ip_retention_days: Option<i32>,
+  pub pow_challenge_difficulty: Option<Option<i32>>,
+  pub authorized_fetch: Option<bool>,
+  pub blocked_instances_patterns: Option<Option<String>>,
+  pub allowed_instances_patterns: Option<Option<String>>,
+  pub image_upload_quota_mb: Option<Option<i32>>,
+  pub image_strip_metadata: Option<bool>,
+  pub image_convert_to_webp: Option<bool>,
+  pub image_allow_animated: Option<bool>,
+  pub image_max_width: Option<Option<i32>>,
+  pub image_max_height: Option<Option<i32>>,
+  pub image_proxy_markdown: Option<bool>,
+  pub image_proxy_safelist: Option<Option<String>>,
+  pub image_proxy_max_size_mb: Option<Option<i32>>,
+  pub captcha_provider: Option<CaptchaProvider>,
+  pub captcha_site_key: Option<Option<String>>,
+  pub captcha_secret_key: Option<Option<String>>,
+  pub captcha_instance_url: Option<Option<String>>,
+  pub federation_concurrent_sends_per_instance: Option<i32>,
+  pub video_max_upload_size_mb: Option<i32>,
+  pub video_max_duration_seconds: Option<i32>,
 }
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::local_site_previous_domain;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_site_previous_domain))]
+#[cfg_attr(feature = "full", diesel(primary_key(domain)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// A domain which the local instance used before moving to its current domain.
+pub struct LocalSitePreviousDomain {
+  pub domain: String,
+  pub published_at: DateTime<Utc>,
+}
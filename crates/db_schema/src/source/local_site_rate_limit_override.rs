@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::{RateLimitAction, RateLimitUserClass};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::local_site_rate_limit_override;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_site_rate_limit_override))]
+#[cfg_attr(feature = "full", diesel(primary_key(user_class, action)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Rate limit for a class of users, which replaces the site rate limit of the action.
+pub struct LocalSiteRateLimitOverride {
+  pub user_class: RateLimitUserClass,
+  pub action: RateLimitAction,
+  pub max_requests: i32,
+  pub interval_seconds: i32,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_site_rate_limit_override))]
+pub struct LocalSiteRateLimitOverrideForm {
+  pub user_class: RateLimitUserClass,
+  pub action: RateLimitAction,
+  pub max_requests: i32,
+  pub interval_seconds: i32,
+}
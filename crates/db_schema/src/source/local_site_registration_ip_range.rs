@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::local_site_registration_ip_range;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_site_registration_ip_range))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An ip range for the registration firewall.
+pub struct LocalSiteRegistrationIpRange {
+  pub id: i32,
+  /// In CIDR notation.
+  pub ip_range: String,
+  /// Registrations from allowed ranges skip all other checks, otherwise they are blocked.
+  pub allowed: bool,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = local_site_registration_ip_range))]
+pub struct LocalSiteRegistrationIpRangeForm {
+  pub ip_range: String,
+  pub allowed: bool,
+}
@@ -17,6 +17,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(default)]
 /// A local user.
 pub struct LocalUser {
@@ -78,6 +79,31 @@ pub struct LocalUser {
   pub show_upvote_percentage: bool,
   pub show_person_votes: bool,
   pub default_items_per_page: i32,
+  /// If the user requested to delete their account, when it will be erased.
+  pub deletion_scheduled_at: Option<DateTime<Utc>>,
+  /// Whether the posts and comments are also erased with the account.
+  pub deletion_delete_content: bool,
+  /// The ip which was used for registration, only kept for a limited time.
+  pub registration_ip: Option<String>,
+  /// Send push notifications for replies.
+  pub push_replies: bool,
+  /// Send push notifications for mentions.
+  pub push_mentions: bool,
+  /// Send push notifications for private messages.
+  pub push_private_messages: bool,
+  /// Send push notifications for new reports, for admins and for mods of the community.
+  pub push_reports: bool,
+  /// Send push notifications for new registration applications, only for admins.
+  pub push_registration_applications: bool,
+  /// Don't send push notifications between the start and end of the quiet hours.
+  pub quiet_hours_enabled: bool,
+  /// Start of the quiet hours, in minutes after midnight UTC.
+  pub quiet_hours_start: i32,
+  /// End of the quiet hours, in minutes after midnight UTC.
+  pub quiet_hours_end: i32,
+  /// Also hide the replies to comments of blocked persons, instead of showing them without their
+  /// parent.
+  pub hide_blocked_person_threads: bool,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -150,6 +176,8 @@ pub struct LocalUserInsertForm {
   pub show_upvote_percentage: Option<bool>,
   #[new(default)]
   pub show_person_votes: Option<bool>,
+  #[new(default)]
+  pub registration_ip: Option<String>,
 }
 
 #[derive(Clone, Default)]
@@ -190,4 +218,15 @@ pub struct LocalUserUpdateForm {
   pub show_upvote_percentage: Option<bool>,
   pub show_person_votes: Option<bool>,
   pub default_items_per_page: Option<i32>,
+  pub deletion_scheduled_at: Option<Option<DateTime<Utc>>>,
+  pub deletion_delete_content: Option<bool>,
+  pub push_replies: Option<bool>,
+  pub push_mentions: Option<bool>,
+  pub push_private_messages: Option<bool>,
+  pub push_reports: Option<bool>,
+  pub push_registration_applications: Option<bool>,
+  pub quiet_hours_enabled: Option<bool>,
+  pub quiet_hours_start: Option<i32>,
+  pub quiet_hours_end: Option<i32>,
+  pub hide_blocked_person_threads: Option<bool>,
 }
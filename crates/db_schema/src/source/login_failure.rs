@@ -0,0 +1,26 @@
+use crate::newtypes::LocalUserId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::login_failure;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Failed logins for an account or an ip, which are reset after a successful login.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = login_failure))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LoginFailure {
+  pub id: i32,
+  /// Only one of `local_user_id` and `ip` is set.
+  pub local_user_id: Option<LocalUserId>,
+  pub ip: Option<String>,
+  pub failure_count: i32,
+  /// Logins are rejected until this time.
+  pub locked_until: Option<DateTime<Utc>>,
+  pub last_failure_at: DateTime<Utc>,
+}
@@ -1,5 +1,6 @@
-use crate::newtypes::LocalUserId;
+use crate::newtypes::{LocalUserId, LoginTokenId, OAuthApplicationId};
 use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::TokenScope;
 #[cfg(feature = "full")]
 use lemmy_db_schema_file::schema::login_token;
 use lemmy_diesel_utils::sensitive::SensitiveString;
@@ -15,6 +16,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LoginToken {
   /// Jwt token for this login
   #[serde(skip)]
@@ -26,6 +28,17 @@ pub struct LoginToken {
   /// Could be stored in truncated format, or store derived information for better privacy.
   pub ip: Option<String>,
   pub user_agent: Option<String>,
+  /// The third-party app which this token was issued to.
+  pub oauth_application_id: Option<OAuthApplicationId>,
+  /// Limits what the token can be used for. Empty for regular logins, which have full access.
+  pub scope: Option<TokenScope>,
+  pub id: LoginTokenId,
+  /// Last time that the token was used, updated at most once per hour.
+  pub last_used_at: DateTime<Utc>,
+  #[serde(skip)]
+  pub refresh_token_hash: Option<String>,
+  /// If the session uses a refresh token, the session ends when it expires.
+  pub refresh_token_expires_at: Option<DateTime<Utc>>,
 }
 
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
@@ -35,4 +48,8 @@ pub struct LoginTokenCreateForm {
   pub user_id: LocalUserId,
   pub ip: Option<String>,
   pub user_agent: Option<String>,
+  pub oauth_application_id: Option<OAuthApplicationId>,
+  pub scope: Option<TokenScope>,
+  pub refresh_token_hash: Option<String>,
+  pub refresh_token_expires_at: Option<DateTime<Utc>>,
 }
@@ -4,44 +4,72 @@ use url::Url;
 #[cfg(feature = "full")]
 pub mod activity;
 pub mod actor_language;
+pub mod api_key;
 pub mod combined;
 pub mod comment;
 pub mod comment_report;
 pub mod community;
 #[cfg(feature = "full")]
 pub mod community_community_follow;
+pub mod community_post_template;
+pub mod community_pseudonym;
+pub mod community_recurring_post;
 pub mod community_report;
 pub mod community_tag;
+pub mod community_trend;
 pub mod custom_emoji;
 pub mod custom_emoji_keyword;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
 pub mod federation_queue_state;
+pub mod hashtag;
 pub mod images;
 pub mod instance;
+pub mod instance_move;
+pub mod instance_report;
 pub mod keyword_block;
 pub mod language;
+pub mod link_metadata_override;
 pub mod local_site;
+pub mod local_site_previous_domain;
 pub mod local_site_rate_limit;
+pub mod local_site_rate_limit_override;
+pub mod local_site_registration_ip_range;
 pub mod local_site_url_blocklist;
 pub mod local_user;
+pub mod login_failure;
 pub mod login_token;
 pub mod modlog;
 pub mod multi_community;
 pub mod notification;
 pub mod oauth_account;
+pub mod oauth_application;
+pub mod oauth_authorization_code;
 pub mod oauth_provider;
 pub mod password_reset_request;
 pub mod person;
+pub mod person_encryption_key;
 pub mod post;
+pub mod post_feature_schedule;
 pub mod post_report;
+pub mod pow_challenge;
 pub mod private_message;
 pub mod private_message_report;
+pub mod push_subscription;
+pub mod rate_limit_bucket;
 pub mod registration_application;
+pub mod remote_community_directory;
+pub mod saved_search;
 pub mod secret;
+pub mod sent_activity_delivery;
 pub mod site;
+pub mod site_stats;
 pub mod tagline;
+pub mod totp_recovery_code;
+pub mod webauthn_challenge;
+pub mod webauthn_credential;
+pub mod webhook;
 
 /// Default value for columns like [community::Community.inbox_url] which are marked as serde(skip).
 ///
@@ -27,6 +27,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", cursor_keys_module(name = multi_community_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MultiCommunity {
   pub id: MultiCommunityId,
   pub creator_id: PersonId,
@@ -101,6 +102,7 @@ pub struct MultiCommunityUpdateForm {
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MultiCommunityFollow {
   pub multi_community_id: MultiCommunityId,
   pub person_id: PersonId,
@@ -127,6 +129,7 @@ pub struct MultiCommunityFollowForm {
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MultiCommunityEntry {
   pub multi_community_id: MultiCommunityId,
   pub community_id: CommunityId,
@@ -0,0 +1,56 @@
+use crate::newtypes::{LocalUserId, OAuthApplicationId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::oauth_application;
+use lemmy_diesel_utils::{dburl::DbUrl, sensitive::SensitiveString};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_application))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A third-party app which can request access tokens for users, using Lemmy as OAuth 2.0
+/// provider.
+pub struct OAuthApplication {
+  pub id: OAuthApplicationId,
+  /// The user who registered this app.
+  pub creator_id: LocalUserId,
+  /// The app name which is shown to users when they authorize it.
+  pub name: String,
+  /// Public identifier of the app, which is sent with authorization requests.
+  pub client_id: String,
+  /// Hash of the secret which the app uses to authenticate itself.
+  #[serde(skip)]
+  pub client_secret: SensitiveString,
+  /// Users are only redirected to this URI after authorizing the app.
+  #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
+  pub redirect_uri: DbUrl,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_application))]
+pub struct OAuthApplicationInsertForm {
+  pub creator_id: LocalUserId,
+  pub name: String,
+  pub client_id: String,
+  pub client_secret: String,
+  pub redirect_uri: DbUrl,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_application))]
+pub struct OAuthApplicationUpdateForm {
+  pub name: Option<String>,
+  pub redirect_uri: Option<DbUrl>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
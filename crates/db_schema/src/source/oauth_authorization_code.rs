@@ -0,0 +1,37 @@
+use crate::newtypes::{LocalUserId, OAuthApplicationId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::TokenScope;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::oauth_authorization_code;
+use lemmy_diesel_utils::dburl::DbUrl;
+
+/// A short-lived code which a third-party app can exchange for an access token, after the user
+/// authorized it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_authorization_code))]
+#[cfg_attr(feature = "full", diesel(primary_key(code_hash)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct OAuthAuthorizationCode {
+  /// Only a hash is stored, like for api keys.
+  pub code_hash: String,
+  pub oauth_application_id: OAuthApplicationId,
+  pub local_user_id: LocalUserId,
+  pub scope: TokenScope,
+  pub redirect_uri: DbUrl,
+  /// PKCE code challenge using the S256 method.
+  pub code_challenge: Option<String>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_authorization_code))]
+pub struct OAuthAuthorizationCodeInsertForm {
+  pub code_hash: String,
+  pub oauth_application_id: OAuthApplicationId,
+  pub local_user_id: LocalUserId,
+  pub scope: TokenScope,
+  pub redirect_uri: DbUrl,
+  pub code_challenge: Option<String>,
+}
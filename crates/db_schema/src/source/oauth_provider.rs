@@ -13,6 +13,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// oauth provider with client_secret - should never be sent to the client
 pub struct AdminOAuthProvider {
   pub id: OAuthProviderId,
@@ -20,18 +21,22 @@ pub struct AdminOAuthProvider {
   pub display_name: String,
   /// The issuer url of the OAUTH provider.
   #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
   pub issuer: DbUrl,
   /// The authorization endpoint is used to interact with the resource owner and obtain an
   /// authorization grant. This is usually provided by the OAUTH provider.
   #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
   pub authorization_endpoint: DbUrl,
   /// The token endpoint is used by the client to obtain an access token by presenting its
   /// authorization grant or refresh token. This is usually provided by the OAUTH provider.
   #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
   pub token_endpoint: DbUrl,
   /// The UserInfo Endpoint is an OAuth 2.0 Protected Resource that returns Claims about the
   /// authenticated End-User. This is defined in the OIDC specification.
   #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
   pub userinfo_endpoint: DbUrl,
   /// The OAuth 2.0 claim containing the unique user ID returned by the provider. Usually this
   /// should be set to "sub".
@@ -62,6 +67,7 @@ pub struct AdminOAuthProvider {
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 // A subset of OAuthProvider used for public requests, for example to display the OAUTH buttons on
 // the login page
 pub struct PublicOAuthProvider {
@@ -71,6 +77,7 @@ pub struct PublicOAuthProvider {
   /// The authorization endpoint is used to interact with the resource owner and obtain an
   /// authorization grant. This is usually provided by the OAUTH provider.
   #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  #[cfg_attr(feature = "openapi", schema(value_type = String))]
   pub authorization_endpoint: DbUrl,
   /// The client_id is provided by the OAuth 2.0 provider and is a unique identifier to this
   /// service
@@ -20,6 +20,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", cursor_keys_module(name = person_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A person.
 pub struct Person {
   pub id: PersonId,
@@ -130,6 +131,7 @@ pub struct PersonUpdateForm {
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PersonActions {
   #[serde(skip)]
   pub followed_at: Option<DateTime<Utc>>,
@@ -151,6 +153,8 @@ pub struct PersonActions {
   pub upvotes: Option<i32>,
   /// A total of downvotes given to this person
   pub downvotes: Option<i32>,
+  /// When notifications from the person were muted.
+  pub muted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -174,6 +178,16 @@ pub struct PersonBlockForm {
   pub blocked_at: DateTime<Utc>,
 }
 
+#[derive(derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = person_actions))]
+pub struct PersonMuteForm {
+  pub person_id: PersonId,
+  pub target_id: PersonId,
+  #[new(value = "Utc::now()")]
+  pub muted_at: DateTime<Utc>,
+}
+
 #[derive(derive_new::new)]
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
 #[cfg_attr(feature = "full", diesel(table_name = person_actions))]
@@ -0,0 +1,32 @@
+use crate::newtypes::PersonEncryptionKeyId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::person_encryption_key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_encryption_key))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Public key of a device, which clients use to encrypt private messages to the person.
+pub struct PersonEncryptionKey {
+  pub id: PersonEncryptionKeyId,
+  pub person_id: PersonId,
+  pub device_name: String,
+  /// Opaque for the server, the format is chosen by clients.
+  pub public_key: String,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_encryption_key))]
+pub struct PersonEncryptionKeyInsertForm {
+  pub person_id: PersonId,
+  pub device_name: String,
+  pub public_key: String,
+}
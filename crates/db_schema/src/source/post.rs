@@ -21,6 +21,7 @@ use {
 #[cfg_attr(feature = "full", cursor_keys_module(name = post_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A post.
 pub struct Post {
   pub id: PostId,
@@ -88,6 +89,11 @@ pub struct Post {
   pub federation_pending: bool,
   pub embed_video_width: Option<i32>,
   pub embed_video_height: Option<i32>,
+  /// Duration of the video in seconds.
+  pub embed_video_duration: Option<i32>,
+  /// Last change of the content, or of the deleted, removed, locked or featured state. Votes and
+  /// new comments don't change it.
+  pub changed_at: DateTime<Utc>,
 }
 
 // TODO: FromBytes, ToBytes are only needed to develop wasm plugin, could be behind feature flag
@@ -125,6 +131,8 @@ pub struct PostInsertForm {
   #[new(default)]
   pub embed_video_height: Option<i32>,
   #[new(default)]
+  pub embed_video_duration: Option<i32>,
+  #[new(default)]
   pub thumbnail_url: Option<DbUrl>,
   #[new(default)]
   pub ap_id: Option<DbUrl>,
@@ -164,6 +172,7 @@ pub struct PostUpdateForm {
   pub embed_video_url: Option<Option<DbUrl>>,
   pub embed_video_width: Option<Option<i32>>,
   pub embed_video_height: Option<Option<i32>>,
+  pub embed_video_duration: Option<Option<i32>>,
   pub thumbnail_url: Option<Option<DbUrl>>,
   pub ap_id: Option<DbUrl>,
   pub local: Option<bool>,
@@ -188,6 +197,7 @@ pub struct PostUpdateForm {
 #[cfg_attr(feature = "full", cursor_keys_module(name = post_actions_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PostActions {
   /// When the post was read.
   pub read_at: Option<DateTime<Utc>>,
@@ -0,0 +1,42 @@
+use crate::newtypes::PostId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::post_feature_schedule;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_feature_schedule))]
+#[cfg_attr(feature = "full", diesel(primary_key(post_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Times at which a post is automatically featured and unfeatured in its community.
+pub struct PostFeatureSchedule {
+  pub post_id: PostId,
+  /// The mod who scheduled it, which is the actor of the feature activities.
+  pub scheduled_by: PersonId,
+  /// None once the post was featured.
+  pub feature_at: Option<DateTime<Utc>>,
+  pub unfeature_at: Option<DateTime<Utc>>,
+  /// Posts with a rotation order are featured one at a time, so the next post of the rotation
+  /// unfeatures the previous one. If several are due at once, the highest order stays featured.
+  pub rotation_order: Option<i32>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_feature_schedule))]
+#[cfg_attr(feature = "full", diesel(treat_none_as_null = true))]
+pub struct PostFeatureScheduleForm {
+  pub post_id: PostId,
+  pub scheduled_by: PersonId,
+  pub feature_at: Option<DateTime<Utc>>,
+  pub unfeature_at: Option<DateTime<Utc>>,
+  pub rotation_order: Option<i32>,
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::pow_challenge;
+
+/// A random challenge for registration, which is solved by finding a nonce so that the sha256
+/// hash of challenge and nonce starts with `difficulty` zero bits.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = pow_challenge))]
+#[cfg_attr(feature = "full", diesel(primary_key(challenge)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PowChallenge {
+  pub challenge: String,
+  /// The difficulty at the time the challenge was issued.
+  pub difficulty: i32,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = pow_challenge))]
+pub struct PowChallengeInsertForm {
+  pub challenge: String,
+  pub difficulty: i32,
+}
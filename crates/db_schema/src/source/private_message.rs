@@ -21,6 +21,7 @@ use serde_with::skip_serializing_none;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A private message.
 pub struct PrivateMessage {
   pub id: PrivateMessageId,
@@ -34,6 +35,11 @@ pub struct PrivateMessage {
   pub local: bool,
   pub removed: bool,
   pub deleted_by_recipient: bool,
+  /// Opaque payload of an end-to-end encrypted message, the content is empty in that case.
+  pub ciphertext: Option<String>,
+  /// Client defined data which is needed for decryption, like the encrypted message keys for
+  /// each device.
+  pub encryption_metadata: Option<String>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -56,6 +62,10 @@ pub struct PrivateMessageInsertForm {
   pub ap_id: Option<DbUrl>,
   #[new(default)]
   pub local: Option<bool>,
+  #[new(default)]
+  pub ciphertext: Option<String>,
+  #[new(default)]
+  pub encryption_metadata: Option<String>,
 }
 
 #[derive(Clone, Default)]
@@ -70,4 +80,6 @@ pub struct PrivateMessageUpdateForm {
   pub local: Option<bool>,
   pub removed: Option<bool>,
   pub deleted_by_recipient: Option<bool>,
+  pub ciphertext: Option<Option<String>>,
+  pub encryption_metadata: Option<Option<String>>,
 }
@@ -0,0 +1,47 @@
+use crate::newtypes::{LocalUserId, LoginTokenId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::PushProvider;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::push_subscription;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Push subscription of a browser or app, which belongs to a login session.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PushSubscription {
+  pub id: i32,
+  pub local_user_id: LocalUserId,
+  pub login_token_id: LoginTokenId,
+  /// Url where notifications are sent, or the device token for FCM.
+  pub endpoint: String,
+  /// Public key of the browser for encrypting notifications, only for Web Push.
+  #[serde(skip)]
+  pub p256dh_key: Option<String>,
+  /// Authentication secret of the browser for encrypting notifications, only for Web Push.
+  #[serde(skip)]
+  pub auth_key: Option<String>,
+  pub published_at: DateTime<Utc>,
+  pub provider: PushProvider,
+  /// Name of the device which the user can recognize, like `Firefox on Linux`.
+  pub device_name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+pub struct PushSubscriptionForm {
+  pub local_user_id: LocalUserId,
+  pub login_token_id: LoginTokenId,
+  pub endpoint: String,
+  pub p256dh_key: Option<String>,
+  pub auth_key: Option<String>,
+  pub provider: PushProvider,
+  pub device_name: Option<String>,
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::rate_limit_bucket;
+
+/// Number of requests in a rate limit interval, which is shared by all Lemmy servers if
+/// `rate_limit_backend` is set to `postgres`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, QueryableByName, Selectable, Identifiable)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = rate_limit_bucket))]
+#[cfg_attr(feature = "full", diesel(primary_key(key)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct RateLimitBucket {
+  /// Action type, user class and ip, like `post:anonymous:203.0.113.1`.
+  pub key: String,
+  pub count: i64,
+  pub expires_at: DateTime<Utc>,
+}
@@ -17,6 +17,7 @@ use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::registration_
 #[cfg_attr(feature = "full", cursor_keys_module(name = registration_application_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A registration application.
 pub struct RegistrationApplication {
   pub id: RegistrationApplicationId,
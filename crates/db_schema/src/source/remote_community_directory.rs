@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::InstanceId;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::remote_community_directory};
+
+/// A community of a linked instance, crawled from its community list. These are only used for
+/// discovery, the actual community is fetched over federation once a user opens it.
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = remote_community_directory))]
+#[cfg_attr(feature = "full", diesel(primary_key(ap_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = remote_community_directory_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RemoteCommunityDirectory {
+  pub ap_id: DbUrl,
+  pub instance_id: InstanceId,
+  pub name: String,
+  pub title: String,
+  pub description: Option<String>,
+  pub icon: Option<DbUrl>,
+  pub nsfw: bool,
+  pub subscribers: i32,
+  pub posts: i32,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = remote_community_directory))]
+pub struct RemoteCommunityDirectoryForm {
+  pub ap_id: DbUrl,
+  pub instance_id: InstanceId,
+  pub name: String,
+  pub title: String,
+  pub description: Option<String>,
+  pub icon: Option<DbUrl>,
+  pub nsfw: bool,
+  pub subscribers: i32,
+  pub posts: i32,
+  pub updated_at: DateTime<Utc>,
+}
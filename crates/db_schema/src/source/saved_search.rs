@@ -0,0 +1,40 @@
+use crate::newtypes::{CommunityId, LocalUserId, SavedSearchId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::saved_search;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A search query which a user saved to run it again later.
+pub struct SavedSearch {
+  pub id: SavedSearchId,
+  pub local_user_id: LocalUserId,
+  pub search_term: String,
+  /// Only search in this community.
+  pub community_id: Option<CommunityId>,
+  /// Notify the user in their inbox when new posts match the search.
+  pub notify: bool,
+  /// Posts published after this time weren't checked for notifications yet.
+  pub last_checked_at: DateTime<Utc>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search))]
+pub struct SavedSearchInsertForm {
+  pub local_user_id: LocalUserId,
+  pub search_term: String,
+  #[new(default)]
+  pub community_id: Option<CommunityId>,
+  #[new(default)]
+  pub notify: Option<bool>,
+}
@@ -0,0 +1,29 @@
+use crate::newtypes::ActivityId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::InstanceId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::sent_activity_delivery;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(primary_key(sent_activity_id, instance_id)))]
+#[cfg_attr(feature = "full", diesel(table_name = sent_activity_delivery))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delivery status of an outgoing activity to a remote instance. Only stored once sending has
+/// failed.
+pub struct SentActivityDelivery {
+  pub sent_activity_id: ActivityId,
+  pub instance_id: InstanceId,
+  pub delivered: bool,
+  pub fail_count: i32,
+  pub last_error: Option<String>,
+  /// Set by an admin to send the activity again.
+  pub replay: bool,
+  pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::site_stats;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = site_stats))]
+#[cfg_attr(feature = "full", diesel(primary_key(day)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Activity of the instance during a day (UTC), which is periodically recalculated.
+pub struct SiteStats {
+  pub day: NaiveDate,
+  /// New local users.
+  pub signups: i32,
+  /// Posts by local users.
+  pub posts: i32,
+  /// Comments by local users.
+  pub comments: i32,
+  /// Local users who posted, commented or voted.
+  pub active_users: i32,
+  /// Activities received from other instances.
+  pub activities_received: i32,
+  /// Activities sent to other instances.
+  pub activities_sent: i32,
+  /// Reports of any type, including those from other instances.
+  pub reports: i32,
+  /// Total size of uploaded media at the end of the day.
+  pub storage_bytes: i64,
+  pub updated_at: DateTime<Utc>,
+}
@@ -16,6 +16,7 @@ use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::tagline};
 #[cfg_attr(feature = "full", cursor_keys_module(name = tagline_keys))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A tagline, shown at the top of your site.
 pub struct Tagline {
   pub id: TaglineId,
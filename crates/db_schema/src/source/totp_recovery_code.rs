@@ -0,0 +1,26 @@
+use crate::newtypes::LocalUserId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::totp_recovery_code;
+
+/// A one-time code which can be used instead of a totp token. Only the hash is stored, the code
+/// itself is shown to the user once.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = totp_recovery_code))]
+#[cfg_attr(feature = "full", diesel(primary_key(local_user_id, code_hash)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct TotpRecoveryCode {
+  pub local_user_id: LocalUserId,
+  /// Hex encoded sha256 of the code.
+  pub code_hash: String,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = totp_recovery_code))]
+pub struct TotpRecoveryCodeInsertForm {
+  pub local_user_id: LocalUserId,
+  pub code_hash: String,
+}
@@ -0,0 +1,26 @@
+use crate::newtypes::LocalUserId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::webauthn_challenge;
+
+/// A random challenge which needs to be signed by an authenticator, to register or use a passkey.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = webauthn_challenge))]
+#[cfg_attr(feature = "full", diesel(primary_key(challenge)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct WebauthnChallenge {
+  /// Base64url encoded random bytes.
+  pub challenge: String,
+  /// Only set for registration, as the user is unknown when logging in.
+  pub local_user_id: Option<LocalUserId>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webauthn_challenge))]
+pub struct WebauthnChallengeInsertForm {
+  pub challenge: String,
+  pub local_user_id: Option<LocalUserId>,
+}
@@ -0,0 +1,46 @@
+use crate::newtypes::{LocalUserId, WebauthnCredentialId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::webauthn_credential;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = webauthn_credential))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A passkey which a user registered for their account.
+pub struct WebauthnCredential {
+  pub id: WebauthnCredentialId,
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  /// Base64url encoded id which was chosen by the authenticator.
+  pub credential_id: String,
+  /// DER encoded public key.
+  #[serde(skip)]
+  pub public_key: Vec<u8>,
+  /// COSE algorithm identifier of the public key.
+  #[serde(skip)]
+  pub algorithm: i32,
+  /// Signature counter of the authenticator, used to detect cloned credentials.
+  #[serde(skip)]
+  pub sign_count: i64,
+  pub published_at: DateTime<Utc>,
+  pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webauthn_credential))]
+pub struct WebauthnCredentialInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub credential_id: String,
+  pub public_key: Vec<u8>,
+  pub algorithm: i32,
+  pub sign_count: i64,
+}
@@ -0,0 +1,101 @@
+use crate::newtypes::WebhookId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::WebhookEvent;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {
+  i_love_jesus::CursorKeysModule,
+  lemmy_db_schema_file::schema::{webhook, webhook_delivery},
+};
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An url which is called when one of the selected events happens on the instance.
+pub struct Webhook {
+  pub id: WebhookId,
+  pub url: DbUrl,
+  /// Used to sign the payloads, never returned by the api.
+  #[serde(skip)]
+  pub secret: String,
+  pub post_created: bool,
+  pub person_created: bool,
+  pub report_created: bool,
+  pub community_created: bool,
+  pub enabled: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookInsertForm {
+  pub url: DbUrl,
+  pub secret: String,
+  pub post_created: bool,
+  pub person_created: bool,
+  pub report_created: bool,
+  pub community_created: bool,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookUpdateForm {
+  pub url: Option<DbUrl>,
+  pub secret: Option<String>,
+  pub post_created: Option<bool>,
+  pub person_created: Option<bool>,
+  pub report_created: Option<bool>,
+  pub community_created: Option<bool>,
+  pub enabled: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = webhook_delivery_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An event which is sent to a webhook, with the result of the last delivery attempt.
+pub struct WebhookDelivery {
+  pub id: i32,
+  pub webhook_id: WebhookId,
+  pub event: WebhookEvent,
+  /// The JSON body which is sent to the webhook.
+  pub payload: String,
+  pub attempts: i32,
+  /// Status code of the last response.
+  pub status_code: Option<i32>,
+  /// Why the last attempt failed.
+  pub error: Option<String>,
+  pub published_at: DateTime<Utc>,
+  pub delivered_at: Option<DateTime<Utc>>,
+  /// When the delivery is retried. Empty if it was delivered, or if all attempts failed.
+  pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryInsertForm {
+  pub webhook_id: WebhookId,
+  pub event: WebhookEvent,
+  pub payload: String,
+  pub next_attempt_at: Option<DateTime<Utc>>,
+}
@@ -3,6 +3,7 @@ use diesel::{
   ExpressionMethods,
   NullableExpressionMethods,
   QueryDsl,
+  dsl::{exists, not},
   helper_types::{Eq, NotEq},
 };
 use lemmy_db_schema_file::{
@@ -11,6 +12,7 @@ use lemmy_db_schema_file::{
   schema::{
     community,
     community_actions,
+    federation_blocklist,
     instance_actions,
     local_site,
     multi_community,
@@ -51,6 +53,21 @@ pub fn filter_not_unlisted() -> _ {
   not_unlisted
 }
 
+/// Hide quarantined communities from the general feeds, search and suggestions.
+#[diesel::dsl::auto_type]
+pub fn filter_not_quarantined() -> _ {
+  community::quarantined.eq(false)
+}
+
+/// Hide communities of instances blocked by the admins from the All feed. For silenced instances
+/// this is the only effect, so their content is still visible to subscribers.
+#[diesel::dsl::auto_type]
+pub fn filter_not_silenced() -> _ {
+  not(exists(federation_blocklist::table.filter(
+    federation_blocklist::instance_id.eq(community::instance_id),
+  )))
+}
+
 #[diesel::dsl::auto_type]
 pub fn filter_suggested_communities() -> _ {
   community::id.eq_any(
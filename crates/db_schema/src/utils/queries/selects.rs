@@ -279,6 +279,7 @@ pub fn comment_select_remove_deletes() -> _ {
     comment::unresolved_report_count,
     comment::federation_pending,
     comment::locked,
+    comment::changed_at,
   )
 }
 
@@ -337,6 +338,8 @@ pub fn post_select_remove_deletes() -> _ {
     post::federation_pending,
     post::embed_video_width,
     post::embed_video_height,
+    post::embed_video_duration,
+    post::changed_at,
   )
 }
 
@@ -13,6 +13,7 @@ use strum::Display;
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The post sort types. See here for descriptions: https://join-lemmy.org/docs/en/users/03-votes-and-ranking.html
 pub enum PostSortType {
   #[default]
@@ -37,6 +38,7 @@ pub enum PostSortType {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The comment sort types. See here for descriptions: https://join-lemmy.org/docs/en/users/03-votes-and-ranking.html
 pub enum CommentSortType {
   #[default]
@@ -57,6 +59,7 @@ pub enum CommentSortType {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A listing type for post and comment list fetches.
 pub enum ListingType {
   /// Content from your own site, as well as all connected / federated sites.
@@ -82,6 +85,7 @@ pub enum ListingType {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The registration mode for your site. Determines what happens after a user signs up.
 pub enum RegistrationMode {
   /// Closed to public.
@@ -103,6 +107,7 @@ pub enum RegistrationMode {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A post-view mode that changes how multiple post listings look.
 pub enum PostListingMode {
   /// A compact, list-type view.
@@ -124,6 +129,7 @@ pub enum PostListingMode {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Defines who can browse and interact with content in a community.
 pub enum CommunityVisibility {
   /// Public community, any local or federated user can interact.
@@ -151,6 +157,49 @@ impl CommunityVisibility {
   }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::DuplicateUrlPolicyEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// What happens when a post links to a url which was already posted in the same community
+/// recently.
+pub enum DuplicateUrlPolicy {
+  #[default]
+  Allow,
+  /// Clients should warn the user before submitting, using the `duplicate_url_posts` of the
+  /// similar posts endpoint.
+  Warn,
+  /// The post is rejected.
+  Block,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::FederationBlockSeverityEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// How strongly an instance is blocked by the admins.
+pub enum FederationBlockSeverity {
+  /// Content is hidden from the All feed, but still federated and visible to subscribers.
+  Silence,
+  /// No federation at all with the instance.
+  #[default]
+  Suspend,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "full", derive(DbEnum))]
@@ -161,6 +210,7 @@ impl CommunityVisibility {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The federation mode for an item
 pub enum FederationMode {
   #[default]
@@ -182,6 +232,7 @@ pub enum FederationMode {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A mode for setting how pictrs handles images.
 pub enum ImageMode {
   /// Leave images unchanged, don't generate any local thumbnails for post urls. Instead the
@@ -204,6 +255,54 @@ pub enum ImageMode {
   ProxyAllImages,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::PushProviderEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// The service which delivers push notifications to a device.
+pub enum PushProvider {
+  /// Browser push service, with payloads encrypted for the browser.
+  /// https://developer.mozilla.org/en-US/docs/Web/API/Push_API
+  #[default]
+  WebPush,
+  /// Distributor app on the device, https://unifiedpush.org/
+  UnifiedPush,
+  /// Firebase Cloud Messaging, through the proxy which is configured for the instance.
+  Fcm,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::CaptchaProviderEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Which captcha is shown during registration.
+pub enum CaptchaProvider {
+  /// Image and audio captcha which is generated by the captcha plugin. Disabled if no captcha
+  /// plugin is loaded.
+  #[default]
+  Internal,
+  /// https://www.hcaptcha.com/
+  HCaptcha,
+  /// Self-hosted proof of work captcha, https://mcaptcha.org/
+  MCaptcha,
+  /// https://www.cloudflare.com/products/turnstile/
+  Turnstile,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "full", derive(DbEnum))]
 #[cfg_attr(
@@ -227,6 +326,7 @@ pub enum ActorType {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum CommunityFollowerState {
   Accepted,
   Pending,
@@ -243,6 +343,7 @@ pub enum CommunityFollowerState {
 )]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Color of community tag.
 pub enum TagColor {
   #[default]
@@ -268,6 +369,7 @@ pub enum TagColor {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Lets you show votes for others only, show all votes, or hide all votes.
 pub enum VoteShow {
   #[default]
@@ -286,6 +388,7 @@ pub enum VoteShow {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Available settings for post notifications
 pub enum PostNotificationsMode {
   AllComments,
@@ -304,6 +407,7 @@ pub enum PostNotificationsMode {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Available settings for community notifications
 pub enum CommunityNotificationsMode {
   AllPostsAndComments,
@@ -323,6 +427,7 @@ pub enum CommunityNotificationsMode {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Types of notifications which can be received in inbox
 pub enum NotificationType {
   // Necessary for enumstring
@@ -332,6 +437,8 @@ pub enum NotificationType {
   Subscribed,
   PrivateMessage,
   ModAction,
+  /// A new post which matches a saved search
+  SavedSearch,
 }
 
 #[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -344,6 +451,7 @@ pub enum NotificationType {
 #[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A list of possible types for the various modlog actions.
 pub enum ModlogKind {
   // Necessary for enumstring
@@ -369,4 +477,101 @@ pub enum ModlogKind {
   ModLockComment,
   ModWarnComment,
   ModWarnPost,
+  AdminQuarantineCommunity,
+}
+
+#[derive(
+  Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::TokenScopeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Limits what a token can be used for. Each scope includes the permissions of the previous ones.
+pub enum TokenScope {
+  /// Only read data, no changes are possible.
+  Read,
+  /// Create content, vote, follow communities etc.
+  Write,
+  /// Also take moderator and admin actions.
+  Moderate,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::WebhookEventEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Events on the instance which can be sent to a webhook.
+pub enum WebhookEvent {
+  /// A local user created a post.
+  PostCreated,
+  /// A new local user registered.
+  PersonCreated,
+  /// A local user created a report.
+  ReportCreated,
+  /// A local user created a community.
+  CommunityCreated,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::RateLimitUserClassEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Users which can have different rate limits.
+pub enum RateLimitUserClass {
+  /// Requests without login.
+  Anonymous,
+  /// Accounts which are restricted by the new account settings of the site.
+  NewAccount,
+  /// All other accounts.
+  Established,
+  Bot,
+  Admin,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::RateLimitActionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Groups of endpoints which share a rate limit.
+pub enum RateLimitAction {
+  /// All api requests which don't belong to another group.
+  Message,
+  Register,
+  Post,
+  Image,
+  Comment,
+  Search,
+  ImportUserSettings,
 }
@@ -5,6 +5,10 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "actor_type_enum"))]
   pub struct ActorTypeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "captcha_provider_enum"))]
+  pub struct CaptchaProviderEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "comment_sort_type_enum"))]
   pub struct CommentSortTypeEnum;
@@ -21,6 +25,14 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "community_visibility"))]
   pub struct CommunityVisibility;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "duplicate_url_policy_enum"))]
+  pub struct DuplicateUrlPolicyEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "federation_block_severity_enum"))]
+  pub struct FederationBlockSeverityEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "federation_mode_enum"))]
   pub struct FederationModeEnum;
@@ -57,6 +69,18 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "post_sort_type_enum"))]
   pub struct PostSortTypeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "push_provider_enum"))]
+  pub struct PushProviderEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "rate_limit_action_enum"))]
+  pub struct RateLimitActionEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "rate_limit_user_class_enum"))]
+  pub struct RateLimitUserClassEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "registration_mode_enum"))]
   pub struct RegistrationModeEnum;
@@ -65,9 +89,32 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "tag_color_enum"))]
   pub struct TagColorEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "token_scope_enum"))]
+  pub struct TokenScopeEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "vote_show_enum"))]
   pub struct VoteShowEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "webhook_event_enum"))]
+  pub struct WebhookEventEnum;
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::TokenScopeEnum;
+
+    api_key (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        key_hash -> Text,
+        scope -> TokenScopeEnum,
+        rate_limit -> Nullable<Int4>,
+        published_at -> Timestamptz,
+    }
 }
 
 diesel::table! {
@@ -99,6 +146,7 @@ diesel::table! {
         unresolved_report_count -> Int2,
         federation_pending -> Bool,
         locked -> Bool,
+        changed_at -> Timestamptz,
     }
 }
 
@@ -130,6 +178,9 @@ diesel::table! {
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CommunityVisibility;
+    use super::sql_types::DuplicateUrlPolicyEnum;
+    use super::sql_types::PostSortTypeEnum;
+    use super::sql_types::CommentSortTypeEnum;
 
     community (id) {
         id -> Int4,
@@ -178,6 +229,12 @@ diesel::table! {
         report_count -> Int2,
         unresolved_report_count -> Int2,
         local_removed -> Bool,
+        quarantined -> Bool,
+        duplicate_url_policy -> DuplicateUrlPolicyEnum,
+        duplicate_url_days -> Int4,
+        pseudonymous_posting -> Bool,
+        default_post_sort_type -> Nullable<PostSortTypeEnum>,
+        default_comment_sort_type -> Nullable<CommentSortTypeEnum>,
     }
 }
 
@@ -200,6 +257,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_alias (name) {
+        #[max_length = 255]
+        name -> Varchar,
+        community_id -> Int4,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     community_community_follow (community_id, target_id) {
         target_id -> Int4,
@@ -215,6 +281,44 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_post_template (id) {
+        id -> Int4,
+        community_id -> Int4,
+        name -> Text,
+        title -> Nullable<Text>,
+        body -> Nullable<Text>,
+        enforce_headings -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    community_pseudonym (community_id, person_id) {
+        community_id -> Int4,
+        person_id -> Int4,
+        pseudonym_id -> Int4,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    community_recurring_post (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        title -> Text,
+        body -> Nullable<Text>,
+        interval_days -> Int4,
+        next_post_at -> Timestamptz,
+        feature -> Bool,
+        last_post_id -> Nullable<Int4>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     community_report (id) {
         id -> Int4,
@@ -255,6 +359,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_trend (community_id) {
+        community_id -> Int4,
+        posts_day -> Int4,
+        posts_week -> Int4,
+        comments_day -> Int4,
+        comments_week -> Int4,
+        subscribers_day -> Int4,
+        subscribers_week -> Int4,
+        score -> Float4,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     custom_emoji (id) {
         id -> Int4,
@@ -295,11 +413,16 @@ diesel::table! {
 }
 
 diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::FederationBlockSeverityEnum;
+
     federation_blocklist (instance_id) {
         instance_id -> Int4,
         published_at -> Timestamptz,
         updated_at -> Nullable<Timestamptz>,
         expires_at -> Nullable<Timestamptz>,
+        severity -> FederationBlockSeverityEnum,
+        reason -> Nullable<Text>,
     }
 }
 
@@ -313,6 +436,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    hashtag (id) {
+        id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     image_details (link) {
         link -> Text,
@@ -321,6 +453,7 @@ diesel::table! {
         content_type -> Text,
         #[max_length = 50]
         blurhash -> Nullable<Varchar>,
+        duration_seconds -> Nullable<Int4>,
     }
 }
 
@@ -335,6 +468,8 @@ diesel::table! {
         software -> Nullable<Varchar>,
         #[max_length = 255]
         version -> Nullable<Varchar>,
+        dead_since -> Nullable<Timestamptz>,
+        next_probe_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -349,6 +484,35 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    instance_move (id) {
+        id -> Int4,
+        instance_id -> Int4,
+        old_domain -> Text,
+        new_domain -> Text,
+        old_origin -> Text,
+        new_origin -> Text,
+        published_at -> Timestamptz,
+        approved -> Nullable<Bool>,
+        resolver_id -> Nullable<Int4>,
+        resolved_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    instance_report (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        instance_id -> Int4,
+        original_instance_domain -> Text,
+        reason -> Text,
+        resolved -> Bool,
+        resolver_id -> Nullable<Int4>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     language (id) {
         id -> Int4,
@@ -358,12 +522,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    link_metadata_override (domain) {
+        domain -> Text,
+        oembed_endpoint -> Nullable<Text>,
+        disable_metadata -> Bool,
+        disable_embed -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     local_image (pictrs_alias) {
         pictrs_alias -> Text,
         published_at -> Timestamptz,
         person_id -> Nullable<Int4>,
         thumbnail_for_post_id -> Nullable<Int4>,
+        size_bytes -> Int8,
     }
 }
 
@@ -376,6 +552,7 @@ diesel::table! {
     use super::sql_types::CommentSortTypeEnum;
     use super::sql_types::FederationModeEnum;
     use super::sql_types::ImageModeEnum;
+    use super::sql_types::CaptchaProviderEnum;
 
     local_site (id) {
         id -> Int4,
@@ -427,6 +604,37 @@ diesel::table! {
         image_max_upload_size -> Int4,
         image_allow_video_uploads -> Bool,
         image_upload_disabled -> Bool,
+        new_account_min_age_days -> Int4,
+        new_account_min_karma -> Int4,
+        new_account_max_posts_per_day -> Nullable<Int4>,
+        new_account_link_posts_disabled -> Bool,
+        new_account_image_upload_disabled -> Bool,
+        new_account_community_creation_disabled -> Bool,
+        moderators_require_totp -> Bool,
+        account_deletion_grace_period_days -> Int4,
+        registrations_per_ip_per_day -> Nullable<Int4>,
+        registration_dnsbl -> Nullable<Text>,
+        registration_ip_retention_days -> Int4,
+        pow_challenge_difficulty -> Nullable<Int4>,
+        authorized_fetch -> Bool,
+        blocked_instances_patterns -> Nullable<Text>,
+        allowed_instances_patterns -> Nullable<Text>,
+        image_upload_quota_mb -> Nullable<Int4>,
+        image_strip_metadata -> Bool,
+        image_convert_to_webp -> Bool,
+        image_allow_animated -> Bool,
+        image_max_width -> Nullable<Int4>,
+        image_max_height -> Nullable<Int4>,
+        image_proxy_markdown -> Bool,
+        image_proxy_safelist -> Nullable<Text>,
+        image_proxy_max_size_mb -> Nullable<Int4>,
+        captcha_provider -> CaptchaProviderEnum,
+        captcha_site_key -> Nullable<Text>,
+        captcha_secret_key -> Nullable<Text>,
+        captcha_instance_url -> Nullable<Text>,
+        federation_concurrent_sends_per_instance -> Int4,
+        video_max_upload_size_mb -> Int4,
+        video_max_duration_seconds -> Int4,
     }
 }
 
@@ -452,6 +660,37 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    local_site_previous_domain (domain) {
+        domain -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::RateLimitUserClassEnum;
+    use super::sql_types::RateLimitActionEnum;
+
+    local_site_rate_limit_override (user_class, action) {
+        user_class -> RateLimitUserClassEnum,
+        action -> RateLimitActionEnum,
+        max_requests -> Int4,
+        interval_seconds -> Int4,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    local_site_registration_ip_range (id) {
+        id -> Int4,
+        ip_range -> Text,
+        allowed -> Bool,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     local_site_url_blocklist (id) {
         id -> Int4,
@@ -507,6 +746,18 @@ diesel::table! {
         show_upvote_percentage -> Bool,
         show_person_votes -> Bool,
         default_items_per_page -> Int4,
+        deletion_scheduled_at -> Nullable<Timestamptz>,
+        deletion_delete_content -> Bool,
+        registration_ip -> Nullable<Text>,
+        push_replies -> Bool,
+        push_mentions -> Bool,
+        push_private_messages -> Bool,
+        push_reports -> Bool,
+        push_registration_applications -> Bool,
+        quiet_hours_enabled -> Bool,
+        quiet_hours_start -> Int4,
+        quiet_hours_end -> Int4,
+        hide_blocked_person_threads -> Bool,
     }
 }
 
@@ -526,12 +777,32 @@ diesel::table! {
 }
 
 diesel::table! {
+    login_failure (id) {
+        id -> Int4,
+        local_user_id -> Nullable<Int4>,
+        ip -> Nullable<Text>,
+        failure_count -> Int4,
+        locked_until -> Nullable<Timestamptz>,
+        last_failure_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::TokenScopeEnum;
+
     login_token (token) {
         token -> Text,
         user_id -> Int4,
         published_at -> Timestamptz,
         ip -> Nullable<Text>,
         user_agent -> Nullable<Text>,
+        oauth_application_id -> Nullable<Int4>,
+        scope -> Nullable<TokenScopeEnum>,
+        id -> Int4,
+        last_used_at -> Timestamptz,
+        refresh_token_hash -> Nullable<Text>,
+        refresh_token_expires_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -630,6 +901,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    oauth_application (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        name -> Text,
+        client_id -> Text,
+        client_secret -> Text,
+        redirect_uri -> Text,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::TokenScopeEnum;
+
+    oauth_authorization_code (code_hash) {
+        code_hash -> Text,
+        oauth_application_id -> Int4,
+        local_user_id -> Int4,
+        scope -> TokenScopeEnum,
+        redirect_uri -> Text,
+        code_challenge -> Nullable<Text>,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     oauth_provider (id) {
         id -> Int4,
@@ -703,6 +1002,7 @@ diesel::table! {
         voted_at -> Nullable<Timestamptz>,
         upvotes -> Nullable<Int4>,
         downvotes -> Nullable<Int4>,
+        muted_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -716,6 +1016,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    person_encryption_key (id) {
+        id -> Int4,
+        person_id -> Int4,
+        device_name -> Text,
+        public_key -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     person_liked_combined (id) {
         voted_at -> Timestamptz,
@@ -783,6 +1093,8 @@ diesel::table! {
         federation_pending -> Bool,
         embed_video_width -> Nullable<Int4>,
         embed_video_height -> Nullable<Int4>,
+        embed_video_duration -> Nullable<Int4>,
+        changed_at -> Timestamptz,
     }
 }
 
@@ -812,6 +1124,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_feature_schedule (post_id) {
+        post_id -> Int4,
+        scheduled_by -> Int4,
+        feature_at -> Nullable<Timestamptz>,
+        unfeature_at -> Nullable<Timestamptz>,
+        rotation_order -> Nullable<Int4>,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    post_hashtag (post_id, hashtag_id) {
+        post_id -> Int4,
+        hashtag_id -> Int4,
+    }
+}
+
 diesel::table! {
     post_report (id) {
         id -> Int4,
@@ -830,6 +1160,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    pow_challenge (challenge) {
+        challenge -> Text,
+        difficulty -> Int4,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     private_message (id) {
         id -> Int4,
@@ -844,6 +1182,8 @@ diesel::table! {
         local -> Bool,
         removed -> Bool,
         deleted_by_recipient -> Bool,
+        ciphertext -> Nullable<Text>,
+        encryption_metadata -> Nullable<Text>,
     }
 }
 
@@ -861,6 +1201,31 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PushProviderEnum;
+
+    push_subscription (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        login_token_id -> Int4,
+        endpoint -> Text,
+        p256dh_key -> Nullable<Text>,
+        auth_key -> Nullable<Text>,
+        published_at -> Timestamptz,
+        provider -> PushProviderEnum,
+        device_name -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    rate_limit_bucket (key) {
+        key -> Text,
+        count -> Int8,
+        expires_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     received_activity (ap_id) {
         ap_id -> Text,
@@ -884,6 +1249,25 @@ diesel::table! {
     remote_image (link) {
         link -> Text,
         published_at -> Timestamptz,
+        cached_alias -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    remote_community_directory (ap_id) {
+        #[max_length = 255]
+        ap_id -> Varchar,
+        instance_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        title -> Varchar,
+        description -> Nullable<Text>,
+        icon -> Nullable<Text>,
+        nsfw -> Bool,
+        subscribers -> Int4,
+        posts -> Int4,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -896,6 +1280,27 @@ diesel::table! {
         private_message_report_id -> Nullable<Int4>,
         community_report_id -> Nullable<Int4>,
         resolved -> Bool,
+        instance_report_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    revoked_refresh_token (token_hash) {
+        token_hash -> Text,
+        login_token_id -> Int4,
+        revoked_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    saved_search (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        search_term -> Text,
+        community_id -> Nullable<Int4>,
+        notify -> Bool,
+        last_checked_at -> Timestamptz,
+        published_at -> Timestamptz,
     }
 }
 
@@ -924,6 +1329,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sent_activity_delivery (sent_activity_id, instance_id) {
+        sent_activity_id -> Int8,
+        instance_id -> Int4,
+        delivered -> Bool,
+        fail_count -> Int4,
+        last_error -> Nullable<Text>,
+        replay -> Bool,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     site (id) {
         id -> Int4,
@@ -955,6 +1372,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    site_stats (day) {
+        day -> Date,
+        signups -> Int4,
+        posts -> Int4,
+        comments -> Int4,
+        active_users -> Int4,
+        activities_received -> Int4,
+        activities_sent -> Int4,
+        reports -> Int4,
+        storage_bytes -> Int8,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     tagline (id) {
         id -> Int4,
@@ -964,6 +1396,70 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    totp_recovery_code (local_user_id, code_hash) {
+        local_user_id -> Int4,
+        code_hash -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webauthn_challenge (challenge) {
+        challenge -> Text,
+        local_user_id -> Nullable<Int4>,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webauthn_credential (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        credential_id -> Text,
+        public_key -> Bytea,
+        algorithm -> Int4,
+        sign_count -> Int8,
+        published_at -> Timestamptz,
+        last_used_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    webhook (id) {
+        id -> Int4,
+        url -> Text,
+        secret -> Text,
+        post_created -> Bool,
+        person_created -> Bool,
+        report_created -> Bool,
+        community_created -> Bool,
+        enabled -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventEnum;
+
+    webhook_delivery (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        event -> WebhookEventEnum,
+        payload -> Text,
+        attempts -> Int4,
+        status_code -> Nullable<Int4>,
+        error -> Nullable<Text>,
+        published_at -> Timestamptz,
+        delivered_at -> Nullable<Timestamptz>,
+        next_attempt_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::joinable!(api_key -> local_user (local_user_id));
 diesel::joinable!(comment -> language (language_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
@@ -972,10 +1468,17 @@ diesel::joinable!(comment_actions -> person (person_id));
 diesel::joinable!(comment_report -> comment (comment_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_actions -> community (community_id));
+diesel::joinable!(community_alias -> community (community_id));
 diesel::joinable!(community_language -> community (community_id));
 diesel::joinable!(community_language -> language (language_id));
+diesel::joinable!(community_post_template -> community (community_id));
+diesel::joinable!(community_pseudonym -> community (community_id));
+diesel::joinable!(community_recurring_post -> community (community_id));
+diesel::joinable!(community_recurring_post -> person (creator_id));
+diesel::joinable!(community_recurring_post -> post (last_post_id));
 diesel::joinable!(community_report -> community (community_id));
 diesel::joinable!(community_tag -> community (community_id));
+diesel::joinable!(community_trend -> community (community_id));
 diesel::joinable!(custom_emoji_keyword -> custom_emoji (custom_emoji_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(federation_allowlist -> instance (instance_id));
@@ -983,6 +1486,9 @@ diesel::joinable!(federation_blocklist -> instance (instance_id));
 diesel::joinable!(federation_queue_state -> instance (instance_id));
 diesel::joinable!(instance_actions -> instance (instance_id));
 diesel::joinable!(instance_actions -> person (person_id));
+diesel::joinable!(instance_move -> instance (instance_id));
+diesel::joinable!(instance_move -> person (resolver_id));
+diesel::joinable!(instance_report -> instance (instance_id));
 diesel::joinable!(local_image -> person (person_id));
 diesel::joinable!(local_image -> post (thumbnail_for_post_id));
 diesel::joinable!(local_site -> multi_community (suggested_multi_community_id));
@@ -993,7 +1499,9 @@ diesel::joinable!(local_user -> person (person_id));
 diesel::joinable!(local_user_keyword_block -> local_user (local_user_id));
 diesel::joinable!(local_user_language -> language (language_id));
 diesel::joinable!(local_user_language -> local_user (local_user_id));
+diesel::joinable!(login_failure -> local_user (local_user_id));
 diesel::joinable!(login_token -> local_user (user_id));
+diesel::joinable!(login_token -> oauth_application (oauth_application_id));
 diesel::joinable!(modlog -> comment (target_comment_id));
 diesel::joinable!(modlog -> community (target_community_id));
 diesel::joinable!(modlog -> instance (target_instance_id));
@@ -1010,11 +1518,15 @@ diesel::joinable!(notification -> post (post_id));
 diesel::joinable!(notification -> private_message (private_message_id));
 diesel::joinable!(oauth_account -> local_user (local_user_id));
 diesel::joinable!(oauth_account -> oauth_provider (oauth_provider_id));
+diesel::joinable!(oauth_application -> local_user (creator_id));
+diesel::joinable!(oauth_authorization_code -> local_user (local_user_id));
+diesel::joinable!(oauth_authorization_code -> oauth_application (oauth_application_id));
 diesel::joinable!(password_reset_request -> local_user (local_user_id));
 diesel::joinable!(person -> instance (instance_id));
 diesel::joinable!(person_content_combined -> comment (comment_id));
 diesel::joinable!(person_content_combined -> person (creator_id));
 diesel::joinable!(person_content_combined -> post (post_id));
+diesel::joinable!(person_encryption_key -> person (person_id));
 diesel::joinable!(person_liked_combined -> comment (comment_id));
 diesel::joinable!(person_liked_combined -> post (post_id));
 diesel::joinable!(person_saved_combined -> comment (comment_id));
@@ -1026,33 +1538,57 @@ diesel::joinable!(post_actions -> person (person_id));
 diesel::joinable!(post_actions -> post (post_id));
 diesel::joinable!(post_community_tag -> community_tag (community_tag_id));
 diesel::joinable!(post_community_tag -> post (post_id));
+diesel::joinable!(post_feature_schedule -> person (scheduled_by));
+diesel::joinable!(post_feature_schedule -> post (post_id));
+diesel::joinable!(post_hashtag -> hashtag (hashtag_id));
+diesel::joinable!(post_hashtag -> post (post_id));
 diesel::joinable!(post_report -> post (post_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
+diesel::joinable!(push_subscription -> local_user (local_user_id));
 diesel::joinable!(registration_application -> local_user (local_user_id));
 diesel::joinable!(registration_application -> person (admin_id));
+diesel::joinable!(remote_community_directory -> instance (instance_id));
 diesel::joinable!(report_combined -> comment_report (comment_report_id));
 diesel::joinable!(report_combined -> community_report (community_report_id));
+diesel::joinable!(report_combined -> instance_report (instance_report_id));
 diesel::joinable!(report_combined -> post_report (post_report_id));
 diesel::joinable!(report_combined -> private_message_report (private_message_report_id));
+diesel::joinable!(saved_search -> community (community_id));
+diesel::joinable!(saved_search -> local_user (local_user_id));
+diesel::joinable!(sent_activity_delivery -> instance (instance_id));
+diesel::joinable!(sent_activity_delivery -> sent_activity (sent_activity_id));
 diesel::joinable!(site -> instance (instance_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
+diesel::joinable!(totp_recovery_code -> local_user (local_user_id));
+diesel::joinable!(webauthn_challenge -> local_user (local_user_id));
+diesel::joinable!(webauthn_credential -> local_user (local_user_id));
+diesel::joinable!(webhook_delivery -> webhook (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+  api_key,
   comment,
   comment_actions,
   comment_report,
   community,
   community_actions,
+  community_alias,
   community_language,
+  community_post_template,
+  community_pseudonym,
+  community_recurring_post,
   community_report,
   community_tag,
+  community_trend,
   email_verification,
   federation_allowlist,
   federation_blocklist,
   federation_queue_state,
+  hashtag,
   instance,
   instance_actions,
+  instance_move,
+  instance_report,
   language,
   local_image,
   local_site,
@@ -1060,6 +1596,7 @@ diesel::allow_tables_to_appear_in_same_query!(
   local_user,
   local_user_keyword_block,
   local_user_language,
+  login_failure,
   login_token,
   modlog,
   multi_community,
@@ -1067,22 +1604,38 @@ diesel::allow_tables_to_appear_in_same_query!(
   multi_community_follow,
   notification,
   oauth_account,
+  oauth_application,
+  oauth_authorization_code,
   oauth_provider,
   password_reset_request,
   person,
   person_content_combined,
+  person_encryption_key,
   person_liked_combined,
   person_saved_combined,
   post,
   post_actions,
   post_community_tag,
+  post_feature_schedule,
+  post_hashtag,
   post_report,
+  pow_challenge,
   private_message,
   private_message_report,
+  push_subscription,
   registration_application,
+  remote_community_directory,
   report_combined,
+  revoked_refresh_token,
+  saved_search,
   site,
   site_language,
+  site_stats,
+  totp_recovery_code,
+  webauthn_challenge,
+  webauthn_credential,
+  webhook,
+  webhook_delivery,
   person_actions,
   image_details,
 );
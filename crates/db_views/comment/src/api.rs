@@ -1,4 +1,5 @@
 use crate::CommentView;
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::newtypes::{CommentId, CommunityId, LanguageId, PostId};
 use lemmy_db_schema_file::enums::{CommentSortType, ListingType};
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -9,6 +10,7 @@ use serde_with::skip_serializing_none;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A comment response.
 pub struct CommentResponse {
   pub comment_view: CommentView,
@@ -18,17 +20,21 @@ pub struct CommentResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a comment.
 pub struct CreateComment {
   pub content: String,
   pub post_id: PostId,
   pub parent_id: Option<CommentId>,
   pub language_id: Option<LanguageId>,
+  /// Publish under your pseudonym in the community, if the community allows it.
+  pub pseudonymous: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Like a comment.
 pub struct CreateCommentLike {
   pub comment_id: CommentId,
@@ -40,6 +46,7 @@ pub struct CreateCommentLike {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete your own comment.
 pub struct DeleteComment {
   pub comment_id: CommentId,
@@ -50,6 +57,7 @@ pub struct DeleteComment {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Distinguish a comment (IE speak as moderator).
 pub struct DistinguishComment {
   pub comment_id: CommentId,
@@ -60,6 +68,7 @@ pub struct DistinguishComment {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Fetch an individual comment.
 pub struct GetComment {
   pub id: CommentId,
@@ -69,6 +78,7 @@ pub struct GetComment {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Get a list of comments.
 pub struct GetComments {
   pub type_: Option<ListingType>,
@@ -84,12 +94,36 @@ pub struct GetComments {
   pub post_id: Option<PostId>,
   pub parent_id: Option<CommentId>,
   pub search_term: Option<String>,
+  /// Removed comments are shown in place and marked as removed, so that clients can collapse
+  /// them. Their content is only visible to mods, admins and the creator. If false, then leave
+  /// them out instead.
+  pub show_removed: Option<bool>,
+  /// If true, then leave out the creator, post, community, tags and counts of each comment, for
+  /// clients which only need ids and content. These fields are then missing from the items, even
+  /// if they are required in `CommentView`.
+  pub minimal: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Get the comments of a post which are new, edited, deleted or removed since the last refresh.
+/// To refresh again later, pass the `changed_at` of the last comment as `since`.
+pub struct GetCommentUpdates {
+  pub post_id: PostId,
+  /// Only comments which changed at or after this time. Without it, all comments are returned.
+  pub since: Option<DateTime<Utc>>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// List comment likes. Admins-only.
 pub struct ListCommentLikes {
   pub comment_id: CommentId,
@@ -101,6 +135,7 @@ pub struct ListCommentLikes {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Locks a comment and its children, IE prevents new replies.
 pub struct LockComment {
   pub comment_id: CommentId,
@@ -112,6 +147,7 @@ pub struct LockComment {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Purges a comment from the database. This will delete all content attached to that comment.
 pub struct PurgeComment {
   pub comment_id: CommentId,
@@ -122,6 +158,7 @@ pub struct PurgeComment {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Remove a comment (only doable by mods).
 pub struct RemoveComment {
   pub comment_id: CommentId,
@@ -132,9 +169,21 @@ pub struct RemoveComment {
   pub remove_children: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Remove multiple comments at once (only doable by mods). Useful for cleaning up spam waves.
+pub struct RemoveManyComments {
+  pub comment_ids: Vec<CommentId>,
+  pub removed: bool,
+  pub reason: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Save / bookmark a comment.
 pub struct SaveComment {
   pub comment_id: CommentId,
@@ -145,6 +194,7 @@ pub struct SaveComment {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Edit a comment.
 pub struct EditComment {
   pub comment_id: CommentId,
@@ -156,6 +206,7 @@ pub struct EditComment {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Creates a warning against a comment and notifies the user.
 pub struct CreateCommentWarning {
   pub comment_id: CommentId,
@@ -1,16 +1,17 @@
 use crate::{CommentSlimView, CommentView};
+use chrono::{DateTime, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
   NullableExpressionMethods,
-  PgTextExpressionMethods,
   QueryDsl,
   SelectableHelper,
+  dsl::{exists, not},
 };
 use diesel_async::RunQueryDsl;
 use diesel_ltree::{Ltree, LtreeExtensions, nlevel};
-use i_love_jesus::asc_if;
+use i_love_jesus::{SortDirection, asc_if};
 use lemmy_db_schema::{
   impls::local_user::LocalUserOptionHelper,
   newtypes::{CommentId, CommunityId, LanguageId, PostId},
@@ -22,7 +23,13 @@ use lemmy_db_schema::{
   },
   utils::{
     limit_fetch,
-    queries::filters::{filter_blocked, filter_is_subscribed, filter_suggested_communities},
+    queries::filters::{
+      filter_blocked,
+      filter_is_subscribed,
+      filter_not_quarantined,
+      filter_not_silenced,
+      filter_suggested_communities,
+    },
   },
 };
 use lemmy_db_schema_file::{
@@ -46,7 +53,7 @@ use lemmy_db_schema_file::{
     my_local_user_admin_join,
     my_person_actions_join,
   },
-  schema::{comment, community, community_actions, person, post},
+  schema::{comment, community, community_actions, person, person_actions, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -58,7 +65,12 @@ use lemmy_diesel_utils::{
     paginate_response,
   },
   traits::Crud,
-  utils::{Subpath, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    Subpath,
+    functions::{TsMatch, search_query, search_vector, ts_rank_cd},
+    now,
+    seconds_to_pg_interval,
+  },
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -155,6 +167,57 @@ impl CommentView {
       creator_is_moderator: self.creator_is_moderator,
     }
   }
+
+  /// List the comments of a post which were created, edited, deleted or removed since the given
+  /// time, ordered by the time of the change, so that clients can refresh a thread without
+  /// loading the whole tree again.
+  pub async fn list_updates(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    since: Option<DateTime<Utc>>,
+    my_local_user: Option<&'_ LocalUser>,
+    local_instance_id: InstanceId,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<CommentView>> {
+    let limit = limit_fetch(limit, None)?;
+    let my_person_id = my_local_user.person_id();
+    let mut query = Self::joins(my_person_id, local_instance_id)
+      .filter(comment::post_id.eq(post_id))
+      .filter(
+        comment::federation_pending
+          .eq(false)
+          .or(comment::creator_id.nullable().eq(my_person_id)),
+      )
+      .limit(limit)
+      .select(Self::as_select())
+      .into_boxed();
+
+    if let Some(since) = since {
+      query = query.filter(comment::changed_at.ge(since));
+    }
+    query = my_local_user.visible_communities_only(query);
+    if !my_local_user.is_admin() {
+      query = query.filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
+      );
+    }
+
+    let paginated_query = CommentView::paginate(query, &page_cursor, SortDirection::Asc, pool)
+      .await?
+      .then_order_by(key::changed_at)
+      // Tie breaker
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
 }
 
 #[derive(Clone, Default)]
@@ -167,7 +230,18 @@ pub struct CommentQuery<'a> {
   pub parent_path: Option<Ltree>,
   pub local_user: Option<&'a LocalUser>,
   pub max_depth: Option<i32>,
+  pub creator_id: Option<PersonId>,
+  pub show_nsfw: Option<bool>,
+  pub published_after: Option<DateTime<Utc>>,
+  pub published_before: Option<DateTime<Utc>>,
+  pub min_score: Option<i32>,
   pub search_term: Option<String>,
+  /// Sort search results by relevance instead of `sort`. Only the first page can be fetched this
+  /// way, as relevance can't be used for pagination cursors.
+  pub sort_by_search_rank: Option<bool>,
+  /// Removed comments are kept in place by default, so that threads stay intact. Their content
+  /// is only visible to mods, admins and the creator. Set to false to leave them out.
+  pub show_removed: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
@@ -210,25 +284,69 @@ impl CommentQuery<'_> {
       ListingType::Suggested => query.filter(filter_suggested_communities()),
     };
 
+    // Hide quarantined communities from the general types, unless viewing a single post or
+    // community
+    if self.post_id.is_none()
+      && self.community_id.is_none()
+      && [ListingType::Local, ListingType::All, ListingType::Suggested]
+        .contains(&self.listing_type.unwrap_or_default())
+    {
+      query = query.filter(filter_not_quarantined());
+    }
+
+    // Hide silenced instances from All, subscribers still see their content
+    if self.post_id.is_none()
+      && self.community_id.is_none()
+      && self.listing_type.unwrap_or_default() == ListingType::All
+    {
+      query = query.filter(filter_not_silenced());
+    }
+
     if !self.local_user.show_bot_accounts() {
       query = query.filter(person::bot_account.eq(false));
     };
 
+    if !self.show_removed.unwrap_or(true) {
+      query = query.filter(comment::removed.eq(false));
+    }
+
     if self.listing_type.unwrap_or_default() != ListingType::ModeratorView {
       if let Some(language_ids) = language_ids {
         query = query.filter(comment::language_id.eq_any(language_ids));
       }
 
       query = query.filter(filter_blocked());
+
+      // Also hide the replies below comments of blocked persons
+      if let Some(local_user) = self.local_user
+        && local_user.hide_blocked_person_threads
+      {
+        let ancestor = diesel::alias!(comment as ancestor);
+        query = query.filter(not(exists(
+          ancestor
+            .inner_join(
+              person_actions::table
+                .on(person_actions::target_id.eq(ancestor.field(comment::creator_id))),
+            )
+            .filter(person_actions::person_id.eq(local_user.person_id))
+            .filter(person_actions::blocked_at.is_not_null())
+            .filter(ancestor.field(comment::post_id).eq(comment::post_id))
+            .filter(ancestor.field(comment::path).contains(comment::path)),
+        )));
+      }
     };
 
     // The search term
     if let Some(search_term) = self.search_term {
-      let searcher = fuzzy_search(&search_term);
-      query = query.filter(comment::content.ilike(searcher));
+      let search = || search_query(search_term.clone());
+      let vector = || search_vector(comment::content.nullable());
+      query = query.filter(TsMatch::new(vector(), search()));
+      if self.sort_by_search_rank.unwrap_or_default() && self.page_cursor.is_none() {
+        query = query.order_by(ts_rank_cd(vector(), search()).desc());
+      }
     }
 
-    if !self.local_user.show_nsfw(site) {
+    if !self.show_nsfw.unwrap_or(self.local_user.show_nsfw(site)) {
       query = query
         .filter(post::nsfw.eq(false))
         .filter(community::nsfw.eq(false));
@@ -254,6 +372,20 @@ impl CommentQuery<'_> {
       query =
         query.filter(comment::published_at.gt(now() - seconds_to_pg_interval(time_range_seconds)));
     }
+    if let Some(published_after) = self.published_after {
+      query = query.filter(comment::published_at.ge(published_after));
+    }
+    if let Some(published_before) = self.published_before {
+      query = query.filter(comment::published_at.lt(published_before));
+    }
+
+    if let Some(creator_id) = self.creator_id {
+      query = query.filter(comment::creator_id.eq(creator_id));
+    }
+
+    if let Some(min_score) = self.min_score {
+      query = query.filter(comment::score.ge(min_score));
+    }
 
     // A Max depth given means its a tree fetch
     let limit = if let Some(max_depth) = self.max_depth {
@@ -534,6 +666,25 @@ mod tests {
     // Make sure its 1, not showing the blocked comment
     assert_length!(5, read_comment_views_with_person);
 
+    // Replies to the blocked comment can be hidden as well
+    let hide_threads_user = LocalUser {
+      hide_blocked_person_threads: true,
+      ..data.timmy_local_user_view.local_user.clone()
+    };
+    let read_comment_views_hide_threads = CommentQuery {
+      post_id: (Some(data.post.id)),
+      local_user: (Some(&hide_threads_user)),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let ids: Vec<_> = read_comment_views_hide_threads
+      .iter()
+      .map(|c| c.comment.id)
+      .collect();
+    assert_length!(2, ids);
+    assert!(ids.contains(&data.comment_0.id) && ids.contains(&data.comment_2.id));
+
     let read_comment_from_blocked_person = CommentView::read(
       pool,
       data.comment_1.id,
@@ -715,6 +866,58 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_list_updates() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let since = Utc::now();
+
+    let updates = CommentView::list_updates(
+      pool,
+      data.post.id,
+      Some(since),
+      None,
+      data.instance.id,
+      None,
+      None,
+    )
+    .await?;
+    assert_length!(0, updates);
+
+    let form = CommentUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_2.id, &form).await?;
+    let form = CommentUpdateForm {
+      content: Some("Edited comment".into()),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_1.id, &form).await?;
+
+    // Ordered by the time of the change, deleted comments are included without content
+    let updates = CommentView::list_updates(
+      pool,
+      data.post.id,
+      Some(since),
+      None,
+      data.instance.id,
+      None,
+      None,
+    )
+    .await?;
+    assert_length!(2, updates);
+    assert_eq!(data.comment_2.id, updates[0].comment.id);
+    assert!(updates[0].comment.deleted);
+    assert_eq!("", updates[0].comment.content);
+    assert_eq!(data.comment_1.id, updates[1].comment.id);
+    assert_eq!("Edited comment", updates[1].comment.content);
+
+    cleanup(data, pool).await
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_creator_is_moderator() -> LemmyResult<()> {
@@ -920,6 +1123,51 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn comment_listings_quarantined_community() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let form = CommunityUpdateForm {
+      quarantined: Some(true),
+      ..Default::default()
+    };
+    Community::update(pool, data.community.id, &form).await?;
+
+    // Hidden from the general listing and search
+    let comments = CommentQuery::default().list(&data.site, pool).await?;
+    assert_eq!(0, comments.len());
+
+    let comments = CommentQuery {
+      search_term: Some("comment 2".into()),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(0, comments.len());
+
+    // Still visible in the community and post
+    let comments = CommentQuery {
+      community_id: Some(data.community.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(6, comments.len());
+
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(6, comments.len());
+
+    cleanup(data, pool).await
+  }
+
   #[tokio::test]
   #[serial]
   async fn comment_listing_private_community() -> LemmyResult<()> {
@@ -1089,6 +1337,63 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn comment_listing_show_removed() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let viewer_form = PersonInsertForm::test_form(data.instance.id, "removed_viewer");
+    let viewer = Person::create(pool, &viewer_form).await?;
+    let viewer_local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(viewer.id), vec![]).await?;
+
+    let form = CommentUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_2.id, &form).await?;
+
+    let query = CommentQuery {
+      post_id: Some(data.post.id),
+      local_user: Some(&viewer_local_user),
+      ..Default::default()
+    };
+    let find_removed = |comments: &[CommentView]| {
+      comments
+        .iter()
+        .find(|c| c.comment.id == data.comment_2.id)
+        .cloned()
+    };
+
+    // Shown in place and marked as removed, but content is hidden from regular users
+    let comments = query.clone().list(&data.site, pool).await?;
+    let removed = find_removed(&comments).ok_or(LemmyErrorType::NotFound)?;
+    assert!(removed.comment.removed);
+    assert_eq!("", removed.comment.content);
+
+    // Mods can read the content
+    let form = CommunityModeratorForm::new(data.community.id, viewer.id);
+    CommunityActions::join(pool, &form).await?;
+    let comments = query.clone().list(&data.site, pool).await?;
+    let removed = find_removed(&comments).ok_or(LemmyErrorType::NotFound)?;
+    assert!(removed.comment.removed);
+    assert_eq!(data.comment_2.content, removed.comment.content);
+
+    // Left out entirely when not requested
+    let comments = CommentQuery {
+      show_removed: Some(false),
+      ..query
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(find_removed(&comments).is_none());
+
+    Person::delete(pool, viewer.id).await?;
+    cleanup(data, pool).await
+  }
+
   #[tokio::test]
   #[serial]
   async fn search() -> LemmyResult<()> {
@@ -1107,6 +1412,32 @@ mod tests {
     assert_length!(1, comment_search_by_name);
     assert_eq!(data.comment_2.id, comment_search_by_name[0].comment.id);
 
+    // Within a single post, results include the path to locate them in the thread
+    let comment_search_in_post = CommentQuery {
+      search_term: Some("comment 2".into()),
+      post_id: Some(data.post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(1, comment_search_in_post);
+    assert_eq!(data.comment_2.path, comment_search_in_post[0].comment.path);
+
+    // Negated terms are excluded
+    let comment_search_negated = CommentQuery {
+      search_term: Some("comment -2".into()),
+      sort_by_search_rank: Some(true),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(!comment_search_negated.is_empty());
+    assert!(
+      comment_search_negated
+        .iter()
+        .all(|c| c.comment.id != data.comment_2.id)
+    );
+
     cleanup(data, pool).await?;
 
     Ok(())
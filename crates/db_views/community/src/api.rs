@@ -1,14 +1,33 @@
-use crate::{CommunityView, MultiCommunityView};
+use crate::{CommunityView, MultiCommunityView, TrendingCommunityView};
 use lemmy_db_schema::{
   CommunitySortType,
   MultiCommunityListingType,
   MultiCommunitySortType,
-  newtypes::{CommunityId, CommunityTagId, LanguageId, MultiCommunityId},
-  source::site::Site,
+  newtypes::{
+    CommunityId,
+    CommunityPostTemplateId,
+    CommunityRecurringPostId,
+    CommunityTagId,
+    LanguageId,
+    MultiCommunityId,
+  },
+  source::{
+    community_post_template::CommunityPostTemplate,
+    community_recurring_post::CommunityRecurringPost,
+    site::Site,
+  },
 };
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommunityNotificationsMode, CommunityVisibility, ListingType, TagColor},
+  enums::{
+    CommentSortType,
+    CommunityNotificationsMode,
+    CommunityVisibility,
+    DuplicateUrlPolicy,
+    ListingType,
+    PostSortType,
+    TagColor,
+  },
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -18,6 +37,7 @@ use serde_with::skip_serializing_none;
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Add a moderator to a community.
 pub struct AddModToCommunity {
   pub community_id: CommunityId,
@@ -28,6 +48,7 @@ pub struct AddModToCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The response of adding a moderator to a community.
 pub struct AddModToCommunityResponse {
   pub moderators: Vec<CommunityModeratorView>,
@@ -36,6 +57,7 @@ pub struct AddModToCommunityResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ApproveCommunityPendingFollower {
   pub community_id: CommunityId,
   pub follower_id: PersonId,
@@ -46,6 +68,7 @@ pub struct ApproveCommunityPendingFollower {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Ban a user from a community.
 pub struct BanFromCommunity {
   pub community_id: CommunityId,
@@ -61,9 +84,28 @@ pub struct BanFromCommunity {
   pub expires_at: Option<i64>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Ban multiple users from a community at once.
+pub struct BanManyFromCommunity {
+  pub community_id: CommunityId,
+  pub person_ids: Vec<PersonId>,
+  pub ban: bool,
+  /// Optionally remove or restore all their data.
+  /// If ban is true, then this means remove. If ban is false, it means restore.
+  pub remove_or_restore_data: Option<bool>,
+  pub reason: String,
+  /// A time that the bans will expire, in unix epoch seconds.
+  pub expires_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Block a community.
 pub struct BlockCommunity {
   pub community_id: CommunityId,
@@ -75,6 +117,7 @@ pub struct BlockCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CommunityIdQuery {
   pub id: CommunityId,
 }
@@ -82,6 +125,7 @@ pub struct CommunityIdQuery {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A simple community response.
 pub struct CommunityResponse {
   pub community_view: CommunityView,
@@ -91,6 +135,7 @@ pub struct CommunityResponse {
 #[skip_serializing_none]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 /// Create a community.
 pub struct CreateCommunity {
@@ -112,12 +157,23 @@ pub struct CreateCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Whether to warn about or block posts with a url which was recently posted in the community.
+  pub duplicate_url_policy: Option<DuplicateUrlPolicy>,
+  /// Number of days during which a url counts as duplicate.
+  pub duplicate_url_days: Option<i32>,
+  /// Whether members can post and comment under a pseudonym. Only for local communities.
+  pub pseudonymous_posting: Option<bool>,
+  /// Post sort used in the community if none is requested.
+  pub default_post_sort_type: Option<PostSortType>,
+  /// Comment sort used in the community if none is requested.
+  pub default_comment_sort_type: Option<CommentSortType>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete your own community.
 pub struct DeleteCommunity {
   pub community_id: CommunityId,
@@ -128,9 +184,13 @@ pub struct DeleteCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Edit a community.
 pub struct EditCommunity {
   pub community_id: CommunityId,
+  /// Change the name of a local community, only allowed for admins. The previous name continues
+  /// to work as alias.
+  pub name: Option<String>,
   /// A longer title.
   pub title: Option<String>,
   /// A sidebar for the community in markdown.
@@ -143,11 +203,24 @@ pub struct EditCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Whether to warn about or block posts with a url which was recently posted in the community.
+  pub duplicate_url_policy: Option<DuplicateUrlPolicy>,
+  /// Number of days during which a url counts as duplicate.
+  pub duplicate_url_days: Option<i32>,
+  /// Whether members can post and comment under a pseudonym. Only for local communities.
+  pub pseudonymous_posting: Option<bool>,
+  /// Post sort used in the community if none is requested.
+  pub default_post_sort_type: Option<PostSortType>,
+  /// Comment sort used in the community if none is requested.
+  pub default_comment_sort_type: Option<CommentSortType>,
+  /// Remove the default sorts of the community, so that those of the user or site are used.
+  pub clear_default_sort_types: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Follow / subscribe to a community.
 pub struct FollowCommunity {
   pub community_id: CommunityId,
@@ -158,6 +231,7 @@ pub struct FollowCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 // TODO make this into a tagged enum
 /// Get a community. Must provide either an id, or a name.
 pub struct GetCommunity {
@@ -170,6 +244,7 @@ pub struct GetCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The community response.
 pub struct GetCommunityResponse {
   pub community_view: CommunityView,
@@ -182,6 +257,26 @@ pub struct GetCommunityResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Lists the communities which are most active over the last day compared to their usual
+/// activity. Recalculated every hour.
+pub struct ListTrendingCommunities {
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListTrendingCommunitiesResponse {
+  pub communities: Vec<TrendingCommunityView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Fetches a random community
 pub struct GetRandomCommunity {
   pub type_: Option<ListingType>,
@@ -192,6 +287,7 @@ pub struct GetRandomCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Hide a community from the main view.
 pub struct HideCommunity {
   pub community_id: CommunityId,
@@ -203,6 +299,7 @@ pub struct HideCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Fetches a list of communities.
 pub struct ListCommunities {
   pub type_: Option<ListingType>,
@@ -218,20 +315,48 @@ pub struct ListCommunities {
   pub limit: Option<i64>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Browse communities of linked instances which are not known locally yet. Use
+/// `ResolveObject` with the `ap_id` to fetch one of them.
+pub struct ListCommunityDirectory {
+  pub search_term: Option<String>,
+  pub show_nsfw: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Purges a community from the database. This will delete all content attached to that community.
 pub struct PurgeCommunity {
   pub community_id: CommunityId,
   pub reason: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Quarantine a community (only doable by admins). It will be hidden from the All and Local feeds,
+/// search and suggestions, but stays accessible for subscribers.
+pub struct QuarantineCommunity {
+  pub community_id: CommunityId,
+  pub quarantined: bool,
+  pub reason: String,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Remove a community (only doable by moderators).
 pub struct RemoveCommunity {
   pub community_id: CommunityId,
@@ -242,6 +367,7 @@ pub struct RemoveCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Transfer a community to a new owner.
 pub struct TransferCommunity {
   pub community_id: CommunityId,
@@ -251,6 +377,7 @@ pub struct TransferCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateMultiCommunity {
   pub name: String,
   pub title: Option<String>,
@@ -261,6 +388,7 @@ pub struct CreateMultiCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EditMultiCommunity {
   pub id: MultiCommunityId,
   pub title: Option<String>,
@@ -272,6 +400,7 @@ pub struct EditMultiCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateOrDeleteMultiCommunityEntry {
   pub id: MultiCommunityId,
   pub community_id: CommunityId,
@@ -280,6 +409,7 @@ pub struct CreateOrDeleteMultiCommunityEntry {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ListMultiCommunities {
   pub type_: Option<MultiCommunityListingType>,
   pub sort: Option<MultiCommunitySortType>,
@@ -296,6 +426,7 @@ pub struct ListMultiCommunities {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GetMultiCommunity {
   pub id: Option<MultiCommunityId>,
   pub name: Option<String>,
@@ -304,6 +435,7 @@ pub struct GetMultiCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GetMultiCommunityResponse {
   pub multi_community_view: MultiCommunityView,
   pub communities: Vec<CommunityView>,
@@ -312,6 +444,7 @@ pub struct GetMultiCommunityResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MultiCommunityResponse {
   pub multi_community_view: MultiCommunityView,
 }
@@ -319,6 +452,7 @@ pub struct MultiCommunityResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct FollowMultiCommunity {
   pub multi_community_id: MultiCommunityId,
   pub follow: bool,
@@ -327,6 +461,7 @@ pub struct FollowMultiCommunity {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Change notification settings for a community
 pub struct EditCommunityNotifications {
   pub community_id: CommunityId,
@@ -337,6 +472,7 @@ pub struct EditCommunityNotifications {
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a tag for a community.
 pub struct CreateCommunityTag {
   pub community_id: CommunityId,
@@ -350,6 +486,7 @@ pub struct CreateCommunityTag {
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Make changes to a community tag
 pub struct EditCommunityTag {
   pub tag_id: CommunityTagId,
@@ -362,8 +499,127 @@ pub struct EditCommunityTag {
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete a community tag.
 pub struct DeleteCommunityTag {
   pub tag_id: CommunityTagId,
   pub delete: bool,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Create a post template for a community.
+pub struct CreateCommunityPostTemplate {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub title: Option<String>,
+  pub body: Option<String>,
+  /// Whether posts created with the template must contain all headings of its body.
+  pub enforce_headings: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Make changes to a community post template.
+pub struct EditCommunityPostTemplate {
+  pub template_id: CommunityPostTemplateId,
+  pub name: Option<String>,
+  pub title: Option<String>,
+  pub body: Option<String>,
+  pub enforce_headings: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delete a community post template.
+pub struct DeleteCommunityPostTemplate {
+  pub template_id: CommunityPostTemplateId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// List the post templates of a community.
+pub struct ListCommunityPostTemplates {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListCommunityPostTemplatesResponse {
+  pub templates: Vec<CommunityPostTemplate>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Create a post which is published automatically at a regular interval.
+pub struct CreateCommunityRecurringPost {
+  pub community_id: CommunityId,
+  /// A moderator of the community who is used as creator of the posts. Defaults to yourself.
+  pub creator_id: Option<PersonId>,
+  /// `{date}` is replaced with the date of the post, and `{weekday}` with its weekday.
+  pub title: String,
+  pub body: Option<String>,
+  pub interval_days: i32,
+  /// Time of the first post, as unix timestamp.
+  pub first_post_at: i64,
+  /// Feature the post in the community, and unfeature the previous instance.
+  pub feature: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Make changes to a recurring post.
+pub struct EditCommunityRecurringPost {
+  pub recurring_post_id: CommunityRecurringPostId,
+  pub creator_id: Option<PersonId>,
+  pub title: Option<String>,
+  pub body: Option<String>,
+  pub interval_days: Option<i32>,
+  /// Time of the next post, as unix timestamp.
+  pub next_post_at: Option<i64>,
+  pub feature: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delete a recurring post. Posts which were already created are kept.
+pub struct DeleteCommunityRecurringPost {
+  pub recurring_post_id: CommunityRecurringPostId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// List the recurring posts of a community. Only available to moderators.
+pub struct ListCommunityRecurringPosts {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListCommunityRecurringPostsResponse {
+  pub recurring_posts: Vec<CommunityRecurringPost>,
+}
@@ -1,4 +1,4 @@
-use crate::{CommunityView, MultiCommunityView};
+use crate::{CommunityView, MultiCommunityView, TrendingCommunityView};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
@@ -22,7 +22,12 @@ use lemmy_db_schema::{
   },
   utils::{
     limit_fetch,
-    queries::filters::{filter_is_subscribed, filter_not_unlisted, filter_suggested_communities},
+    queries::filters::{
+      filter_is_subscribed,
+      filter_not_quarantined,
+      filter_not_unlisted,
+      filter_suggested_communities,
+    },
   },
 };
 use lemmy_db_schema_file::{
@@ -37,6 +42,7 @@ use lemmy_db_schema_file::{
   schema::{
     community,
     community_actions,
+    community_trend,
     instance_actions,
     multi_community,
     multi_community_entry,
@@ -54,7 +60,13 @@ use lemmy_diesel_utils::{
     paginate_response,
   },
   traits::Crud,
-  utils::{LowerKey, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    LowerKey,
+    functions::{TsMatch, search_query, weighted_search_vector},
+    fuzzy_search,
+    now,
+    seconds_to_pg_interval,
+  },
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -150,16 +162,24 @@ impl CommunityQuery<'_> {
 
     if let Some(listing_type) = self.listing_type {
       query = match listing_type {
-        ListingType::All => query.filter(filter_not_unlisted()),
+        ListingType::All => query
+          .filter(filter_not_unlisted())
+          .filter(filter_not_quarantined()),
         ListingType::Subscribed => query.filter(filter_is_subscribed()),
         ListingType::Local => query
           .filter(community::local.eq(true))
-          .filter(filter_not_unlisted()),
+          .filter(filter_not_unlisted())
+          .filter(filter_not_quarantined()),
         ListingType::ModeratorView => {
           query.filter(community_actions::became_moderator_at.is_not_null())
         }
-        ListingType::Suggested => query.filter(filter_suggested_communities()),
+        ListingType::Suggested => query
+          .filter(filter_suggested_communities())
+          .filter(filter_not_quarantined()),
       };
+    } else {
+      // Without a listing type (eg for search), quarantined communities are also hidden
+      query = query.filter(filter_not_quarantined());
     }
 
     // Don't show blocked communities and communities on blocked instances. nsfw communities are
@@ -190,9 +210,10 @@ impl CommunityQuery<'_> {
       query = if self.search_title_only.unwrap_or_default() {
         query.filter(name_or_title_filter)
       } else {
-        let body_or_description_filter = community::summary
-          .ilike(searcher.clone())
-          .or(community::sidebar.ilike(searcher.clone()));
+        let body_or_description_filter = TsMatch::new(
+          weighted_search_vector(community::summary, community::sidebar),
+          search_query(search_term),
+        );
         query.filter(name_or_title_filter.or(body_or_description_filter))
       }
     }
@@ -237,6 +258,41 @@ impl CommunityQuery<'_> {
   }
 }
 
+impl TrendingCommunityView {
+  /// Communities with the highest trend score first. Only communities which are also visible in
+  /// the All feed are included.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    local_user: Option<&LocalUser>,
+    site: &Site,
+    limit: Option<i64>,
+  ) -> LemmyResult<Vec<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let mut query = CommunityView::joins(local_user.person_id())
+      .inner_join(community_trend::table)
+      .filter(Community::hide_removed_and_deleted())
+      .filter(filter_not_unlisted())
+      .filter(filter_not_quarantined())
+      .filter(instance_actions::blocked_communities_at.is_null())
+      .filter(community_actions::blocked_at.is_null())
+      .select(Self::as_select())
+      .order_by(community_trend::score.desc())
+      .then_order_by(community::id)
+      .limit(limit)
+      .into_boxed();
+    if !local_user.show_nsfw(site) {
+      query = query.filter(community::nsfw.eq(false));
+    }
+    query = local_user.visible_communities_only(query);
+
+    let conn = &mut get_conn(pool).await?;
+    query
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
 // TODO this should be its own view crate
 impl MultiCommunityView {
   #[diesel::dsl::auto_type(no_type_alias)]
@@ -339,9 +395,10 @@ impl MultiCommunityQuery<'_> {
       query = if self.search_title_only.unwrap_or_default() {
         query.filter(name_or_title_filter)
       } else {
-        let body_or_description_filter = multi_community::summary
-          .ilike(searcher.clone())
-          .or(multi_community::sidebar.ilike(searcher.clone()));
+        let body_or_description_filter = TsMatch::new(
+          weighted_search_vector(multi_community::summary, multi_community::sidebar),
+          search_query(search_term),
+        );
         query.filter(name_or_title_filter.or(body_or_description_filter))
       }
     }
@@ -404,7 +461,7 @@ mod tests {
     },
     traits::Followable,
   };
-  use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
+  use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility, ListingType};
   use lemmy_diesel_utils::{
     connection::{DbPool, build_db_pool_for_tests},
     traits::Crud,
@@ -842,6 +899,65 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn quarantined_community() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let quarantined_id = data.communities[1].id;
+
+    let form = CommunityUpdateForm {
+      quarantined: Some(true),
+      ..Default::default()
+    };
+    Community::update(pool, quarantined_id, &form).await?;
+
+    // Hidden from the general listings and search
+    for listing_type in [None, Some(ListingType::All), Some(ListingType::Local)] {
+      let communities = CommunityQuery {
+        listing_type,
+        local_user: Some(&data.local_user),
+        ..Default::default()
+      }
+      .list(&data.site, pool)
+      .await?;
+      assert!(!communities.iter().any(|c| c.community.id == quarantined_id));
+    }
+
+    let community_search_by_name = CommunityQuery {
+      search_term: Some("test_community_2".into()),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(community_search_by_name.is_empty());
+
+    // Still readable directly, and listed for subscribers
+    let community_view =
+      CommunityView::read(pool, quarantined_id, Some(&data.local_user), false).await?;
+    assert!(community_view.community.quarantined);
+
+    let follow_form = CommunityFollowerForm::new(
+      quarantined_id,
+      data.local_user.person_id,
+      CommunityFollowerState::Accepted,
+    );
+    CommunityActions::follow(pool, &follow_form).await?;
+
+    let subscribed = CommunityQuery {
+      listing_type: Some(ListingType::Subscribed),
+      local_user: Some(&data.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(1, subscribed);
+    assert_eq!(quarantined_id, subscribed[0].community.id);
+
+    cleanup(data, pool).await
+  }
+
   #[tokio::test]
   #[serial]
   async fn multi_community_search() -> LemmyResult<()> {
@@ -1,6 +1,7 @@
 use lemmy_db_schema::source::{
   community::{Community, CommunityActions},
   community_tag::CommunityTagsView,
+  community_trend::CommunityTrend,
   multi_community::MultiCommunity,
   person::Person,
 };
@@ -27,6 +28,7 @@ pub mod impls;
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A community view.
 pub struct CommunityView {
   #[cfg_attr(feature = "full", diesel(embed))]
@@ -47,12 +49,27 @@ pub struct CommunityView {
   pub tags: CommunityTagsView,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A community together with its recent activity.
+pub struct TrendingCommunityView {
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub community_view: CommunityView,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub trend: CommunityTrend,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MultiCommunityView {
   #[cfg_attr(feature = "full", diesel(embed))]
   pub multi: MultiCommunity,
@@ -3,6 +3,7 @@ use actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
+  JoinOnDsl,
   NullableExpressionMethods,
   QueryDsl,
   SelectableHelper,
@@ -11,7 +12,7 @@ use diesel_async::RunQueryDsl;
 use i_love_jesus::asc_if;
 use lemmy_db_schema::{
   LocalUserSortType,
-  newtypes::{LocalUserId, OAuthProviderId},
+  newtypes::{CommunityId, LocalUserId, OAuthProviderId},
   source::{
     instance::Instance,
     local_user::{LocalUser, LocalUserInsertForm},
@@ -22,7 +23,7 @@ use lemmy_db_schema_file::{
   PersonId,
   aliases::creator_home_instance_actions,
   joins::creator_home_instance_actions_join,
-  schema::{instance_actions, local_user, oauth_account, person},
+  schema::{community_actions, instance_actions, local_user, oauth_account, person},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -123,6 +124,32 @@ impl LocalUserView {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  pub async fn list_admins(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(local_user::admin.eq(true))
+      .select(Self::as_select())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Local users who moderate the given community.
+  pub async fn list_community_moderators(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .inner_join(community_actions::table.on(community_actions::person_id.eq(person::id)))
+      .filter(community_actions::community_id.eq(community_id))
+      .filter(community_actions::became_moderator_at.is_not_null())
+      .select(Self::as_select())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn list_admins_with_emails(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
     let conn = &mut get_conn(pool).await?;
     Self::joins()
@@ -1,9 +1,13 @@
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
   ModlogKindFilter,
   newtypes::{CommentId, CommunityId, ModlogId, PostId},
 };
-use lemmy_db_schema_file::{PersonId, enums::ListingType};
-use lemmy_diesel_utils::pagination::PaginationCursor;
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::{ListingType, ModlogKind},
+};
+use lemmy_diesel_utils::{dburl::DbUrl, pagination::PaginationCursor};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -11,6 +15,7 @@ use serde_with::skip_serializing_none;
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Fetches the modlog.
 pub struct GetModlog {
   /// Filter by the moderator.
@@ -35,3 +40,44 @@ pub struct GetModlog {
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Fetches the modlog of a remote community directly from its instance. This includes actions
+/// which were never federated to the local instance, so users can see why content disappeared.
+pub struct GetRemoteModlog {
+  pub community_id: CommunityId,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetRemoteModlogResponse {
+  /// Newest entries first.
+  pub entries: Vec<RemoteModlogEntry>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A modlog entry as reported by the remote instance. Objects are referenced by their ap_id, as
+/// they may not be known locally.
+pub struct RemoteModlogEntry {
+  pub kind: ModlogKind,
+  /// For example a restored post, or an unbanned user.
+  pub is_revert: bool,
+  pub published_at: DateTime<Utc>,
+  pub reason: Option<String>,
+  /// Not set if the remote instance hides moderator names.
+  pub moderator: Option<DbUrl>,
+  pub target: DbUrl,
+  /// Post title, comment content or user name of the target.
+  pub target_name: String,
+}
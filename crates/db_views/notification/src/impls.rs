@@ -19,6 +19,7 @@ use lemmy_db_schema::{
 };
 use lemmy_db_schema_file::{
   PersonId,
+  enums::NotificationType,
   schema::{notification, person},
 };
 use lemmy_db_views_modlog::ModlogView;
@@ -38,11 +39,36 @@ use lemmy_diesel_utils::{
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 impl NotificationView {
-  /// Gets the number of unread mentions
+  /// Gets the number of unread notifications
   pub async fn get_unread_count(
     pool: &mut DbPool<'_>,
     my_person: &Person,
     show_bot_accounts: bool,
+  ) -> LemmyResult<i64> {
+    Self::unread_count(pool, my_person, show_bot_accounts, None).await
+  }
+
+  /// Gets the number of unread notifications about new posts in followed communities and new
+  /// comments in followed posts. These are also included in [`Self::get_unread_count`].
+  pub async fn get_unread_subscribed_count(
+    pool: &mut DbPool<'_>,
+    my_person: &Person,
+    show_bot_accounts: bool,
+  ) -> LemmyResult<i64> {
+    Self::unread_count(
+      pool,
+      my_person,
+      show_bot_accounts,
+      Some(NotificationType::Subscribed),
+    )
+    .await
+  }
+
+  async fn unread_count(
+    pool: &mut DbPool<'_>,
+    my_person: &Person,
+    show_bot_accounts: bool,
+    kind: Option<NotificationType>,
   ) -> LemmyResult<i64> {
     use diesel::dsl::count;
     let conn = &mut get_conn(pool).await?;
@@ -63,6 +89,9 @@ impl NotificationView {
     if !show_bot_accounts {
       query = query.filter(person::bot_account.is_distinct_from(true));
     }
+    if let Some(kind) = kind {
+      query = query.filter(notification::kind.eq(kind));
+    }
 
     query
       .first::<i64>(conn)
@@ -108,6 +108,7 @@ struct NotificationViewInternal {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct NotificationView {
   pub notification: Notification,
   pub data: NotificationData,
@@ -116,6 +117,7 @@ pub struct NotificationView {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type_", rename_all = "snake_case")]
 pub enum NotificationData {
   Comment(CommentView),
@@ -128,7 +130,10 @@ pub enum NotificationData {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
-/// Get your inbox (replies, comment mentions, post mentions, and messages)
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Get your inbox, with all types of notifications (replies, mentions, private messages,
+/// subscriptions, mod actions and saved searches) merged into a single paginated list. Use
+/// `type_` to only list one of them.
 pub struct ListNotifications {
   pub type_: Option<NotificationTypeFilter>,
   pub unread_only: Option<bool>,
@@ -39,7 +39,10 @@ use lemmy_diesel_utils::{
     paginate_response,
   },
   traits::Crud,
-  utils::fuzzy_search,
+  utils::{
+    functions::{TsMatch, search_query, search_vector},
+    fuzzy_search,
+  },
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -160,7 +163,8 @@ impl PersonQuery<'_> {
       query = if self.search_title_only.unwrap_or_default() {
         query.filter(name_or_title_filter)
       } else {
-        let body_or_description_filter = person::bio.ilike(searcher.clone());
+        let body_or_description_filter =
+          TsMatch::new(search_vector(person::bio), search_query(search_term));
         query.filter(name_or_title_filter.or(body_or_description_filter))
       }
     }
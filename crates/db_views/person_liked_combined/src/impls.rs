@@ -64,6 +64,7 @@ pub struct PersonLikedCombinedQuery {
 
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 struct PostCommentCombinedViewWrapper(PostCommentCombinedView);
 
 impl PaginationCursorConversion for PostCommentCombinedViewWrapper {
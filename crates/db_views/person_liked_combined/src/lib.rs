@@ -12,6 +12,7 @@ pub mod impls;
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Gets your liked / disliked posts
 pub struct ListPersonLiked {
   pub type_: Option<PersonContentType>,
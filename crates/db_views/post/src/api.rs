@@ -1,7 +1,21 @@
 use crate::PostView;
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
   PostFeatureType,
-  newtypes::{CommentId, CommunityId, CommunityTagId, LanguageId, MultiCommunityId, PostId},
+  newtypes::{
+    CommentId,
+    CommunityId,
+    CommunityPostTemplateId,
+    CommunityTagId,
+    LanguageId,
+    MultiCommunityId,
+    PostId,
+  },
+  source::{
+    hashtag::Hashtag,
+    link_metadata_override::LinkMetadataOverride,
+    post_feature_schedule::PostFeatureSchedule,
+  },
 };
 use lemmy_db_schema_file::enums::{ListingType, PostNotificationsMode, PostSortType};
 use lemmy_db_views_community::CommunityView;
@@ -13,6 +27,7 @@ use serde_with::skip_serializing_none;
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a post.
 pub struct CreatePost {
   pub name: String,
@@ -31,11 +46,16 @@ pub struct CreatePost {
   pub tags: Option<Vec<CommunityTagId>>,
   /// Time when this post should be scheduled. Null means publish immediately.
   pub scheduled_publish_time_at: Option<i64>,
+  /// Publish under your pseudonym in the community, if the community allows it.
+  pub pseudonymous: Option<bool>,
+  /// The post template which was used in the composer. Its headings may be enforced.
+  pub template_id: Option<CommunityPostTemplateId>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Like a post.
 pub struct CreatePostLike {
   pub post_id: PostId,
@@ -46,6 +66,7 @@ pub struct CreatePostLike {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete a post.
 pub struct DeletePost {
   pub post_id: PostId,
@@ -56,6 +77,7 @@ pub struct DeletePost {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Edit a post.
 pub struct EditPost {
   pub post_id: PostId,
@@ -78,6 +100,7 @@ pub struct EditPost {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Mods can change some metadata for posts
 pub struct ModEditPost {
   pub post_id: PostId,
@@ -88,6 +111,7 @@ pub struct ModEditPost {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Feature a post (stickies / pins to the top).
 pub struct FeaturePost {
   pub post_id: PostId,
@@ -95,9 +119,55 @@ pub struct FeaturePost {
   pub feature_type: PostFeatureType,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Schedule when a post is featured and unfeatured in its community, for mods. Replaces the
+/// existing schedule of the post, and removes it if both times are missing.
+pub struct SchedulePostFeature {
+  pub post_id: PostId,
+  /// Unix time at which the post is featured. If missing, the post is expected to be featured
+  /// already.
+  pub feature_at: Option<i64>,
+  /// Unix time at which the post is unfeatured. If missing, it stays featured.
+  pub unfeature_at: Option<i64>,
+  /// Posts with a rotation order are featured one at a time, so that a weekly thread replaces
+  /// the one from the previous week.
+  pub rotation_order: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PostFeatureScheduleResponse {
+  pub schedule: Option<PostFeatureSchedule>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// List the scheduled features in a community, for mods.
+pub struct ListPostFeatureSchedules {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListPostFeatureSchedulesResponse {
+  pub schedules: Vec<PostFeatureSchedule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Change notification settings for a post
 pub struct EditPostNotifications {
   pub post_id: PostId,
@@ -108,6 +178,7 @@ pub struct EditPostNotifications {
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Get a list of posts.
 pub struct GetPosts {
   pub type_: Option<ListingType>,
@@ -125,6 +196,10 @@ pub struct GetPosts {
   pub show_read: Option<bool>,
   /// If true, then show the nsfw posts (even if your user setting is to hide them)
   pub show_nsfw: Option<bool>,
+  /// If true, then show removed posts inline, marked as removed so that clients can collapse
+  /// them. Only works for admins, and for mods in the communities they moderate. Defaults to true
+  /// for admins.
+  pub show_removed: Option<bool>,
   /// If false, then show posts with media attached (even if your user setting is to hide them)
   pub hide_media: Option<bool>,
   /// Whether to automatically mark fetched posts as read.
@@ -134,6 +209,12 @@ pub struct GetPosts {
   pub search_term: Option<String>,
   pub search_title_only: Option<bool>,
   pub search_url_only: Option<bool>,
+  /// Only show posts which use the given hashtag, eg `lemmy` or `#lemmy`.
+  pub hashtag: Option<String>,
+  /// If true, then leave out the creator, community, image details, tags and counts of each
+  /// post, for clients which only need ids and titles. These fields are then missing from the
+  /// items, even if they are required in `PostView`.
+  pub minimal: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   /// For backwards compat with API v3 (not available on API v4)
   #[serde(skip)]
@@ -141,9 +222,25 @@ pub struct GetPosts {
   pub limit: Option<i64>,
 }
 
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Get the posts which changed since the last sync, including tombstones of deleted and removed
+/// posts. To sync again later, pass the `changed_at` of the last post as `since`.
+pub struct GetPostUpdates {
+  /// Only posts which changed at or after this time. Without it, all posts are returned.
+  pub since: Option<DateTime<Utc>>,
+  pub community_id: Option<CommunityId>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Get metadata for a given site.
 pub struct GetSiteMetadata {
   pub url: String,
@@ -152,15 +249,59 @@ pub struct GetSiteMetadata {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The site metadata response.
 pub struct GetSiteMetadataResponse {
   pub metadata: LinkMetadata,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Change how link previews are generated for a domain and its subdomains. Replaces the existing
+/// override for the domain. Only for admins.
+pub struct SetLinkMetadataOverride {
+  pub domain: String,
+  /// Use this oEmbed endpoint instead of the one which is discovered from the page.
+  pub oembed_endpoint: Option<String>,
+  /// Don't fetch any metadata for links to this domain.
+  pub disable_metadata: Option<bool>,
+  /// Don't embed videos or other media from this domain.
+  pub disable_embed: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Remove the link preview override for a domain. Only for admins.
+pub struct DeleteLinkMetadataOverride {
+  pub domain: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LinkMetadataOverrideResponse {
+  pub link_metadata_override: LinkMetadataOverride,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListLinkMetadataOverridesResponse {
+  pub link_metadata_overrides: Vec<LinkMetadataOverride>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Default, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Site metadata, from its opengraph tags.
 pub struct LinkMetadata {
   #[serde(flatten)]
@@ -172,6 +313,7 @@ pub struct LinkMetadata {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Hide a post from list views
 pub struct HidePost {
   pub post_id: PostId,
@@ -182,6 +324,26 @@ pub struct HidePost {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// List hashtags, with the most used ones first.
+pub struct ListHashtags {
+  pub search_term: Option<String>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListHashtagsResponse {
+  pub hashtags: Vec<Hashtag>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// List post likes. Admins-only.
 pub struct ListPostLikes {
   pub post_id: PostId,
@@ -192,6 +354,7 @@ pub struct ListPostLikes {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Lock a post (prevent new comments).
 pub struct LockPost {
   pub post_id: PostId,
@@ -203,6 +366,7 @@ pub struct LockPost {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Mark a post as read.
 pub struct MarkPostAsRead {
   pub post_id: PostId,
@@ -213,6 +377,7 @@ pub struct MarkPostAsRead {
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Default, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Site metadata, from its opengraph tags.
 pub struct OpenGraphData {
   pub title: Option<String>,
@@ -228,6 +393,7 @@ pub struct OpenGraphData {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PostResponse {
   pub post_view: PostView,
 }
@@ -236,6 +402,7 @@ pub struct PostResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Purges a post from the database. This will delete all content attached to that post.
 pub struct PurgePost {
   pub post_id: PostId,
@@ -246,6 +413,7 @@ pub struct PurgePost {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Remove a post (only doable by mods).
 pub struct RemovePost {
   pub post_id: PostId,
@@ -256,9 +424,21 @@ pub struct RemovePost {
   pub remove_children: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Remove multiple posts at once (only doable by mods). Useful for cleaning up spam waves.
+pub struct RemoveManyPosts {
+  pub post_ids: Vec<PostId>,
+  pub removed: bool,
+  pub reason: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Save / bookmark a post.
 pub struct SavePost {
   pub post_id: PostId,
@@ -269,25 +449,68 @@ pub struct SavePost {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Mark several posts as read.
 pub struct MarkManyPostsAsRead {
   pub post_ids: Vec<PostId>,
   pub read: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Gets the posts which you marked as read since the last sync, oldest first. Posts which were
+/// marked as unread again are not included, so clients should check the read state of the posts
+/// which they show.
+pub struct SyncReadPosts {
+  /// Only posts which were marked as read at or after this time.
+  pub since: Option<DateTime<Utc>>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Creates a warning against a post and notifies the user.
 pub struct CreatePostWarning {
   pub post_id: PostId,
   pub reason: String,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Find possible duplicates of a new post, while the user is typing its title.
+pub struct GetSimilarPosts {
+  /// The title of the new post.
+  pub q: String,
+  pub community_id: Option<CommunityId>,
+  /// The url of the new post. Only checked if the community warns about or blocks duplicate urls.
+  pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetSimilarPostsResponse {
+  /// Posts with a similar title, the most similar ones first.
+  pub posts: Vec<PostView>,
+  /// Recent posts in the community which link to the same url.
+  pub duplicate_url_posts: Vec<PostView>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Get a post. Needs either the post id, or comment_id.
 pub struct GetPost {
   pub id: Option<PostId>,
@@ -298,6 +521,7 @@ pub struct GetPost {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The post response.
 pub struct GetPostResponse {
   pub post_view: PostView,
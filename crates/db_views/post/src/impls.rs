@@ -1,4 +1,5 @@
-use crate::PostView;
+use crate::{PostReadState, PostTombstone, PostUpdateView, PostView};
+use chrono::{DateTime, Utc};
 use diesel::{
   self,
   BoolExpressionMethods,
@@ -10,7 +11,7 @@ use diesel::{
   SelectableHelper,
   TextExpressionMethods,
   debug_query,
-  dsl::not,
+  dsl::{exists, not},
   pg::Pg,
   query_builder::AsQuery,
 };
@@ -31,7 +32,12 @@ use lemmy_db_schema::{
   },
   utils::{
     limit_fetch,
-    queries::filters::{filter_blocked, filter_not_unlisted},
+    queries::filters::{
+      filter_blocked,
+      filter_not_quarantined,
+      filter_not_silenced,
+      filter_not_unlisted,
+    },
   },
 };
 use lemmy_db_schema_file::{
@@ -51,7 +57,7 @@ use lemmy_db_schema_file::{
     my_person_actions_join,
     my_post_actions_join,
   },
-  schema::{community, community_actions, person, post, post_actions},
+  schema::{community, community_actions, hashtag, person, post, post_actions, post_hashtag},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -63,7 +69,22 @@ use lemmy_diesel_utils::{
     paginate_response,
   },
   traits::Crud,
-  utils::{CoalesceKey, Commented, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    CoalesceKey,
+    Commented,
+    functions::{
+      TrigramSimilar,
+      TsMatch,
+      regex_substring,
+      search_query,
+      search_vector,
+      similarity,
+      ts_rank_cd,
+      weighted_search_vector,
+    },
+    now,
+    seconds_to_pg_interval,
+  },
 };
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
@@ -72,6 +93,9 @@ use lemmy_utils::{
 use tracing::debug;
 use url::Url;
 
+/// Extracts the hostname from a post url, for filtering by domain.
+const URL_HOST_PATTERN: &str = "^[a-zA-Z]+://([^/:?#]+)";
+
 impl PaginationCursorConversion for PostView {
   type PaginatedType = Post;
   fn to_cursor(&self) -> CursorData {
@@ -86,6 +110,101 @@ impl PaginationCursorConversion for PostView {
   }
 }
 
+impl PaginationCursorConversion for PostUpdateView {
+  type PaginatedType = Post;
+  fn to_cursor(&self) -> CursorData {
+    let post_id = match self {
+      PostUpdateView::Post(v) => v.post.id,
+      PostUpdateView::Tombstone(t) => t.post_id,
+    };
+    CursorData::new_id(post_id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    Post::read(pool, PostId(cursor.id()?)).await
+  }
+}
+
+impl PaginationCursorConversion for PostReadState {
+  type PaginatedType = PostActions;
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_multi([self.post_id.0, self.person_id.0])
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    let [post_id, person_id] = cursor.multi()?;
+    PostActions::read(pool, PostId(post_id), PersonId(person_id)).await
+  }
+}
+
+impl PostReadState {
+  /// List the posts which you marked as read since the given time, oldest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    my_person_id: PersonId,
+    since: Option<DateTime<Utc>>,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<PostReadState>> {
+    let limit = limit_fetch(limit, None)?;
+    let mut query = post_actions::table
+      .filter(post_actions::person_id.eq(my_person_id))
+      .filter(post_actions::read_at.is_not_null())
+      .limit(limit)
+      .select(PostActions::as_select())
+      .into_boxed();
+    if let Some(since) = since {
+      query = query.filter(post_actions::read_at.ge(since));
+    }
+
+    let paginated_query = PostReadState::paginate(query, &page_cursor, SortDirection::Asc, pool)
+      .await?
+      .then_order_by(pa_key::read_at)
+      // Tie breaker
+      .then_order_by(pa_key::post_id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<PostActions>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?
+      .into_iter()
+      .filter_map(|a| {
+        a.read_at.map(|read_at| PostReadState {
+          post_id: a.post_id,
+          person_id: a.person_id,
+          read_at,
+        })
+      })
+      .collect();
+    paginate_response(res, limit, page_cursor)
+  }
+}
+
+impl From<PostView> for PostUpdateView {
+  fn from(view: PostView) -> Self {
+    let post = view.post;
+    if post.deleted || post.removed {
+      PostUpdateView::Tombstone(PostTombstone {
+        post_id: post.id,
+        ap_id: post.ap_id,
+        community_id: post.community_id,
+        deleted: post.deleted,
+        removed: post.removed,
+        changed_at: post.changed_at,
+      })
+    } else {
+      PostUpdateView::Post(PostView { post, ..view })
+    }
+  }
+}
+
 /// This dummy struct is necessary to allow pagination using PostAction keys
 struct PostViewDummy(PostActions);
 impl PaginationCursorConversion for PostViewDummy {
@@ -290,6 +409,60 @@ impl PostView {
       .with_lemmy_type(LemmyErrorType::NotFound)?;
     paginate_response(res, limit, page_cursor)
   }
+
+  /// List the posts which changed since the given time, ordered by the time of the change, so that
+  /// clients can sync incrementally instead of fetching all pages again.
+  pub async fn list_updates(
+    pool: &mut DbPool<'_>,
+    since: Option<DateTime<Utc>>,
+    community_id: Option<CommunityId>,
+    my_local_user: Option<&'_ LocalUser>,
+    local_instance_id: InstanceId,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<PostUpdateView>> {
+    let limit = limit_fetch(limit, None)?;
+    let my_person_id = my_local_user.person_id();
+    let mut query = PostView::joins(my_person_id, local_instance_id)
+      // Deleted and removed posts are included, and returned as tombstones
+      .filter(community::deleted.eq(false))
+      .filter(community::removed.eq(false))
+      .filter(community::local_removed.eq(false))
+      .filter(post::scheduled_publish_time_at.is_null())
+      .filter(post::federation_pending.eq(false))
+      .filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
+      )
+      .limit(limit)
+      .select(PostView::as_select())
+      .into_boxed();
+
+    if let Some(since) = since {
+      query = query.filter(post::changed_at.ge(since));
+    }
+    if let Some(community_id) = community_id {
+      query = query.filter(post::community_id.eq(community_id));
+    }
+    query = my_local_user.visible_communities_only(query);
+
+    let paginated_query = PostUpdateView::paginate(query, &page_cursor, SortDirection::Asc, pool)
+      .await?
+      .then_order_by(key::changed_at)
+      // Tie breaker
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<PostView>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?
+      .into_iter()
+      .map(PostUpdateView::from)
+      .collect();
+    paginate_response(res, limit, page_cursor)
+  }
 }
 
 #[derive(Clone, Default)]
@@ -303,12 +476,28 @@ pub struct PostQuery<'a> {
   pub show_hidden: Option<bool>,
   pub show_read: Option<bool>,
   pub show_nsfw: Option<bool>,
+  /// Show removed posts inline. Only has an effect for admins, and for mods in the communities
+  /// they moderate. Defaults to true for admins.
+  pub show_removed: Option<bool>,
   pub hide_media: Option<bool>,
   pub no_comments_only: Option<bool>,
   pub keyword_blocks: Option<Vec<String>>,
   pub search_term: Option<String>,
   pub search_title_only: Option<bool>,
   pub search_url_only: Option<bool>,
+  /// Sort search results by relevance instead of `sort`. Only the first page can be fetched this
+  /// way, as relevance can't be used for pagination cursors.
+  pub sort_by_search_rank: Option<bool>,
+  pub hashtag: Option<String>,
+  pub creator_id: Option<PersonId>,
+  /// Only posts which link to this domain or one of its subdomains
+  pub domain: Option<String>,
+  pub published_after: Option<DateTime<Utc>>,
+  pub published_before: Option<DateTime<Utc>>,
+  pub min_score: Option<i32>,
+  /// Only posts with a title which is similar to this one, for example to find duplicates. The
+  /// most similar posts are returned first.
+  pub similar_title: Option<String>,
   pub page_cursor: Option<PaginationCursor>,
   /// For backwards compat with API v3 (not available on API v4).
   pub page: Option<i64>,
@@ -426,30 +615,70 @@ impl PostQuery<'_> {
 
     // The search term
     if let Some(search_term) = self.search_term {
-      let searcher = fuzzy_search(&search_term);
-
-      let name_or_title_filter = post::name.ilike(searcher.clone());
+      let search = || search_query(search_term.clone());
+      let sort_by_rank = self.sort_by_search_rank.unwrap_or_default() && self.page_cursor.is_none();
 
       // A url / cross-post search
-      query = if self.search_url_only.unwrap_or_default() {
+      if self.search_url_only.unwrap_or_default() {
         // Parse and normalize the url, removing tracking parameters (same logic which is used
         // when creating a new post).
         let normalized_url = Url::parse(&search_term).map(|u| clean_url(&u).to_string())?;
 
-        query.filter(post::url.eq(normalized_url))
+        query = query.filter(post::url.eq(normalized_url));
       } else if self.search_title_only.unwrap_or_default() {
-        query.filter(name_or_title_filter)
+        let vector = || search_vector(post::name.nullable());
+        query = query.filter(TsMatch::new(vector(), search()));
+        if sort_by_rank {
+          query = query.order_by(ts_rank_cd(vector(), search()).desc());
+        }
       } else {
-        let body_or_description_filter = post::body.ilike(searcher.clone());
-        query.filter(name_or_title_filter.or(body_or_description_filter))
+        let vector = || weighted_search_vector(post::name.nullable(), post::body);
+        query = query.filter(TsMatch::new(vector(), search()));
+        if sort_by_rank {
+          query = query.order_by(ts_rank_cd(vector(), search()).desc());
+        }
       }
     }
 
+    if let Some(similar_title) = self.similar_title {
+      query = query.filter(TrigramSimilar::new(post::name, similar_title.clone()));
+      if self.page_cursor.is_none() {
+        query = query.order_by(similarity(post::name, similar_title).desc());
+      }
+    }
+
+    if let Some(name) = self.hashtag {
+      let name = name.trim_start_matches('#').to_lowercase();
+      query = query.filter(exists(
+        post_hashtag::table
+          .inner_join(hashtag::table)
+          .filter(post_hashtag::post_id.eq(post::id))
+          .filter(hashtag::name.eq(name)),
+      ));
+    }
+
     // Hide the unlisted communities for the general types. Subscribed will still show them
     if [ListingType::Local, ListingType::All].contains(&self.listing_type.unwrap_or_default()) {
       query = query.filter(filter_not_unlisted());
     }
 
+    // Hide quarantined communities from the general types, unless the community is viewed
+    // directly. Subscribed will still show them
+    if self.community_id.is_none()
+      && [ListingType::Local, ListingType::All, ListingType::Suggested]
+        .contains(&self.listing_type.unwrap_or_default())
+    {
+      query = query.filter(filter_not_quarantined());
+    }
+
+    // Hide silenced instances from All, subscribers still see their content
+    if self.community_id.is_none()
+      && self.multi_community_id.is_none()
+      && self.listing_type.unwrap_or_default() == ListingType::All
+    {
+      query = query.filter(filter_not_silenced());
+    }
+
     if !self.show_nsfw.unwrap_or(self.local_user.show_nsfw(site)) {
       query = query
         .filter(post::nsfw.eq(false))
@@ -491,17 +720,28 @@ impl PostQuery<'_> {
         .or(post::creator_id.nullable().eq(my_person_id)),
     );
 
-    if !self.local_user.is_admin() {
+    let is_admin = self.local_user.is_admin();
+    if !is_admin {
       query = query
         .filter(
           community::visibility
             .ne(CommunityVisibility::Private)
             .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
         )
-        // only show removed posts to admin
+        // only show removed communities to admin
         .filter(community::removed.eq(false))
-        .filter(community::local_removed.eq(false))
-        .filter(post::removed.eq(false));
+        .filter(community::local_removed.eq(false));
+    }
+
+    // Removed posts are only shown to admins, and to mods if they explicitly ask for them
+    if !self.show_removed.unwrap_or(is_admin) {
+      query = query.filter(post::removed.eq(false));
+    } else if !is_admin {
+      query = query.filter(
+        post::removed
+          .eq(false)
+          .or(community_actions::became_moderator_at.is_not_null()),
+      );
     }
 
     // Dont filter blocks or missing languages for moderator view type
@@ -532,6 +772,30 @@ impl PostQuery<'_> {
       query =
         query.filter(post::published_at.gt(now() - seconds_to_pg_interval(time_range_seconds)));
     }
+    if let Some(published_after) = self.published_after {
+      query = query.filter(post::published_at.ge(published_after));
+    }
+    if let Some(published_before) = self.published_before {
+      query = query.filter(post::published_at.lt(published_before));
+    }
+
+    if let Some(creator_id) = self.creator_id {
+      query = query.filter(post::creator_id.eq(creator_id));
+    }
+
+    if let Some(min_score) = self.min_score {
+      query = query.filter(post::score.ge(min_score));
+    }
+
+    if let Some(domain) = self.domain {
+      let domain = domain.trim().trim_start_matches('.').to_lowercase();
+      let host = || regex_substring(post::url, URL_HOST_PATTERN);
+      query = query.filter(
+        host()
+          .ilike(domain.clone())
+          .or(host().ilike(format!("%.{domain}"))),
+      );
+    }
 
     // Only sort by ascending for Old
     let sort = self.sort.unwrap_or(PostSortType::Hot);
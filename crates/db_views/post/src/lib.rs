@@ -1,11 +1,15 @@
 use chrono::{DateTime, Utc};
-use lemmy_db_schema::source::{
-  community::{Community, CommunityActions},
-  community_tag::CommunityTagsView,
-  images::ImageDetails,
-  person::{Person, PersonActions},
-  post::{Post, PostActions},
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId, PostId},
+  source::{
+    community::{Community, CommunityActions},
+    community_tag::CommunityTagsView,
+    images::ImageDetails,
+    person::{Person, PersonActions},
+    post::{Post, PostActions},
+  },
 };
+use lemmy_diesel_utils::dburl::DbUrl;
 use serde::{Deserialize, Serialize};
 #[cfg(test)]
 mod db_perf;
@@ -38,6 +42,7 @@ pub mod impls;
 #[cfg_attr(feature = "full", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A post view.
 pub struct PostView {
   #[cfg_attr(feature = "full",
@@ -108,3 +113,40 @@ pub struct PostView {
   )]
   pub creator_community_ban_expires_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type_", rename_all = "snake_case")]
+/// A post which changed since the last sync. Deleted and removed posts are returned as tombstone,
+/// so that clients can delete their copy.
+pub enum PostUpdateView {
+  Post(PostView),
+  Tombstone(PostTombstone),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A deleted or removed post, without its content.
+pub struct PostTombstone {
+  pub post_id: PostId,
+  pub ap_id: DbUrl,
+  pub community_id: CommunityId,
+  pub deleted: bool,
+  pub removed: bool,
+  pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A read marker of your account, for syncing read posts between devices.
+pub struct PostReadState {
+  pub post_id: PostId,
+  pub person_id: PersonId,
+  pub read_at: DateTime<Utc>,
+}
@@ -1,6 +1,6 @@
 #![expect(clippy::indexing_slicing, clippy::expect_used, clippy::unreachable)]
 
-use crate::{PostView, impls::PostQuery};
+use crate::{PostReadState, PostUpdateView, PostView, impls::PostQuery};
 use chrono::{DateTime, Days, Utc};
 use diesel_async::SimpleAsyncConnection;
 use diesel_uplete::UpleteCount;
@@ -703,6 +703,7 @@ async fn post_listing_read_only(data: &mut Data) -> LemmyResult<()> {
 
   // Mark the bot post, then the tags post as read
   PostActions::mark_as_read(pool, data.tegan.person.id, &[data.bot_post.id]).await?;
+  let since = Utc::now();
 
   PostActions::mark_as_read(pool, data.tegan.person.id, &[data.post_with_tags.id]).await?;
 
@@ -715,6 +716,15 @@ async fn post_listing_read_only(data: &mut Data) -> LemmyResult<()> {
     names(&read_read_post_listing)
   );
 
+  // Read markers for syncing are ordered from oldest
+  let read_state = PostReadState::list(pool, data.tegan.person.id, None, None, None).await?;
+  let post_ids: Vec<_> = read_state.iter().map(|r| r.post_id).collect();
+  assert_eq!(vec![data.bot_post.id, data.post_with_tags.id], post_ids);
+
+  let read_state = PostReadState::list(pool, data.tegan.person.id, Some(since), None, None).await?;
+  let post_ids: Vec<_> = read_state.iter().map(|r| r.post_id).collect();
+  assert_eq!(vec![data.post_with_tags.id], post_ids);
+
   Ok(())
 }
 
@@ -951,6 +961,44 @@ async fn post_listings_removed(data: &mut Data) -> LemmyResult<()> {
     names(&post_listings_is_admin)
   );
 
+  // Admins can hide removed posts
+  let post_listings_hide_removed = PostQuery {
+    show_removed: Some(false),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(
+    vec![POST_WITH_TAGS, POST],
+    names(&post_listings_hide_removed)
+  );
+
+  // Mods only see removed posts when they ask for them
+  let show_removed_query = PostQuery {
+    local_user: Some(&data.john.local_user),
+    show_removed: Some(true),
+    ..data.default_post_query()
+  };
+  let post_listings_not_mod = show_removed_query
+    .clone()
+    .list(pool, &data.site, &data.local_site)
+    .await?;
+  assert_eq!(
+    vec![POST_WITH_TAGS, POST, POST_BY_BLOCKED_PERSON],
+    names(&post_listings_not_mod)
+  );
+
+  let john_mod_form = CommunityModeratorForm::new(data.community.id, data.john.person.id);
+  CommunityActions::join(pool, &john_mod_form).await?;
+  let post_listings_mod = show_removed_query
+    .list(pool, &data.site, &data.local_site)
+    .await?;
+  assert_eq!(
+    vec![POST_WITH_TAGS, POST_BY_BOT, POST, POST_BY_BLOCKED_PERSON],
+    names(&post_listings_mod)
+  );
+  CommunityActions::leave(pool, &john_mod_form).await?;
+
   Ok(())
 }
 
@@ -993,6 +1041,58 @@ async fn post_listings_deleted(data: &mut Data) -> LemmyResult<()> {
   Ok(())
 }
 
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn post_listing_updates(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+  let since = Utc::now();
+
+  let list_updates = async |pool: &mut DbPool<'_>| {
+    PostView::list_updates(
+      pool,
+      Some(since),
+      Some(data.community.id),
+      Some(&data.john.local_user),
+      data.instance.id,
+      None,
+      None,
+    )
+    .await
+  };
+  assert_length!(0, list_updates(pool).await?);
+
+  Post::update(
+    pool,
+    data.post.id,
+    &PostUpdateForm {
+      name: Some("edited post".to_string()),
+      ..Default::default()
+    },
+  )
+  .await?;
+  Post::update(
+    pool,
+    data.bot_post.id,
+    &PostUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    },
+  )
+  .await?;
+
+  // Edited post is returned in full, deleted post only as tombstone, in the order of changes
+  let updates = list_updates(pool).await?;
+  assert_length!(2, updates);
+  assert!(matches!(&updates[0], PostUpdateView::Post(v) if v.post.id == data.post.id));
+  assert!(
+    matches!(&updates[1], PostUpdateView::Tombstone(t) if t.post_id == data.bot_post.id && t.deleted)
+  );
+
+  Ok(())
+}
+
 #[test_context(Data)]
 #[tokio::test]
 #[serial]
@@ -1040,6 +1140,74 @@ async fn post_listings_hidden_community(data: &mut Data) -> LemmyResult<()> {
   Ok(())
 }
 
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn post_listings_quarantined_community(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  Community::update(
+    pool,
+    data.community.id,
+    &CommunityUpdateForm {
+      quarantined: Some(true),
+      ..Default::default()
+    },
+  )
+  .await?;
+
+  // Hidden from the general listings and search
+  let posts = data
+    .default_post_query()
+    .list(pool, &data.site, &data.local_site)
+    .await?;
+  assert!(posts.is_empty());
+
+  let posts = PostQuery {
+    listing_type: Some(ListingType::Local),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(posts.is_empty());
+
+  let posts = PostQuery {
+    search_term: Some(POST.into()),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(posts.is_empty());
+
+  // Still visible in the community itself
+  let posts = PostQuery {
+    community_id: Some(data.community.id),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(!posts.is_empty());
+
+  // And for subscribers
+  let form = CommunityFollowerForm::new(
+    data.community.id,
+    data.tegan.person.id,
+    CommunityFollowerState::Accepted,
+  );
+  CommunityActions::follow(pool, &form).await?;
+
+  let posts = PostQuery {
+    listing_type: Some(ListingType::Subscribed),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(!posts.is_empty());
+
+  Ok(())
+}
+
 #[test_context(Data)]
 #[tokio::test]
 #[serial]
@@ -2427,5 +2595,68 @@ async fn search(data: &mut Data) -> LemmyResult<()> {
   assert_length!(1, search_url_only);
   assert_eq!(POST_WITH_TAGS, search_url_only[0].post.name);
 
+  // Filter by the domain of the post url
+  let search_domain = PostQuery {
+    domain: Some("Google.com".into()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_length!(1, search_domain);
+  assert_eq!(POST_WITH_TAGS, search_domain[0].post.name);
+
+  let search_other_domain = PostQuery {
+    domain: Some("oogle.com".into()),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(search_other_domain.is_empty());
+
+  let search_creator = PostQuery {
+    creator_id: Some(data.bot.person.id),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_BY_BOT], names(&search_creator));
+
+  let search_min_score = PostQuery {
+    min_score: Some(1),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(search_min_score.is_empty());
+
+  let search_published = PostQuery {
+    published_after: Some(data.post.published_at),
+    published_before: Some(data.post_with_tags.published_at),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(vec![POST_BY_BOT, POST], names(&search_published));
+
+  // Similar titles, with the most similar one first
+  let search_similar = PostQuery {
+    similar_title: Some("posts with tag".into()),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert_eq!(
+    Some(POST_WITH_TAGS),
+    names(&search_similar).first().copied()
+  );
+
+  let search_not_similar = PostQuery {
+    similar_title: Some("unrelated headline".into()),
+    ..data.default_post_query()
+  }
+  .list(pool, &data.site, &data.local_site)
+  .await?;
+  assert!(search_not_similar.is_empty());
+
   Ok(())
 }
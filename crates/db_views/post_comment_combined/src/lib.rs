@@ -74,6 +74,7 @@ pub struct PostCommentCombinedViewInternal {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type_", rename_all = "snake_case")]
 pub enum PostCommentCombinedView {
   Post(PostView),
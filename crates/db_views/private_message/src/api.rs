@@ -1,20 +1,30 @@
 use crate::PrivateMessageView;
-use lemmy_db_schema::newtypes::PrivateMessageId;
+use lemmy_db_schema::{
+  newtypes::{PersonEncryptionKeyId, PrivateMessageId},
+  source::person_encryption_key::PersonEncryptionKey,
+};
 use lemmy_db_schema_file::PersonId;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a private message.
 pub struct CreatePrivateMessage {
+  /// Must be empty for encrypted messages.
   pub content: String,
   pub recipient_id: PersonId,
+  /// Opaque payload of an end-to-end encrypted message.
+  pub ciphertext: Option<String>,
+  /// Client defined data which is needed for decryption.
+  pub encryption_metadata: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete a private message.
 pub struct DeletePrivateMessage {
   pub private_message_id: PrivateMessageId,
@@ -24,16 +34,67 @@ pub struct DeletePrivateMessage {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Edit a private message.
 pub struct EditPrivateMessage {
   pub private_message_id: PrivateMessageId,
+  /// Must be empty for encrypted messages.
   pub content: String,
+  /// Opaque payload of an end-to-end encrypted message.
+  pub ciphertext: Option<String>,
+  /// Client defined data which is needed for decryption.
+  pub encryption_metadata: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A single private message response.
 pub struct PrivateMessageResponse {
   pub private_message_view: PrivateMessageView,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Publish the public key of a device, so that others can send encrypted private messages to it.
+pub struct CreateEncryptionKey {
+  pub device_name: String,
+  pub public_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EncryptionKeyResponse {
+  pub encryption_key: PersonEncryptionKey,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delete a published encryption key of your account.
+pub struct DeleteEncryptionKey {
+  pub id: PersonEncryptionKeyId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// List the encryption keys of a person, to encrypt private messages for all their devices.
+pub struct ListEncryptionKeys {
+  pub person_id: PersonId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListEncryptionKeysResponse {
+  pub encryption_keys: Vec<PersonEncryptionKey>,
+}
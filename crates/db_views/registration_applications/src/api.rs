@@ -11,6 +11,7 @@ use {extism::ToBytes, extism_convert::Json};
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Approves a registration application.
 pub struct ApproveRegistrationApplication {
   pub id: RegistrationApplicationId,
@@ -22,6 +23,7 @@ pub struct ApproveRegistrationApplication {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Gets a registration application for a person
 pub struct GetRegistrationApplication {
   pub person_id: PersonId,
@@ -31,6 +33,7 @@ pub struct GetRegistrationApplication {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Fetches a list of registration applications.
 pub struct ListRegistrationApplications {
   /// Only shows the unread applications (IE those without an admin actor)
@@ -43,6 +46,7 @@ pub struct ListRegistrationApplications {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Register / Sign up to lemmy.
 pub struct Register {
   pub username: String,
@@ -53,8 +57,13 @@ pub struct Register {
   pub email: Option<SensitiveString>,
   /// The UUID of the captcha item.
   pub captcha_uuid: Option<String>,
-  /// Your captcha answer.
+  /// Your captcha answer. For external captcha providers this is the token which is generated by
+  /// their widget.
   pub captcha_answer: Option<String>,
+  /// The proof of work challenge, if it is enabled on the server.
+  pub pow_challenge: Option<String>,
+  /// The nonce which solves the proof of work challenge.
+  pub pow_nonce: Option<String>,
   /// A form field to trick signup bots. Should be None.
   pub honeypot: Option<String>,
   /// An answer is mandatory if require application is enabled on the server
@@ -74,6 +83,7 @@ pub struct CaptchaAnswer {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The response of an action done to a registration application.
 pub struct RegistrationApplicationResponse {
   pub registration_application: RegistrationApplicationView,
@@ -266,6 +266,18 @@ mod tests {
         show_score: sara_local_user.show_score,
         show_upvote_percentage: sara_local_user.show_upvote_percentage,
         show_person_votes: sara_local_user.show_person_votes,
+        deletion_scheduled_at: sara_local_user.deletion_scheduled_at,
+        deletion_delete_content: sara_local_user.deletion_delete_content,
+        registration_ip: None,
+        push_replies: sara_local_user.push_replies,
+        push_mentions: sara_local_user.push_mentions,
+        push_private_messages: sara_local_user.push_private_messages,
+        push_reports: sara_local_user.push_reports,
+        push_registration_applications: sara_local_user.push_registration_applications,
+        quiet_hours_enabled: sara_local_user.quiet_hours_enabled,
+        quiet_hours_start: sara_local_user.quiet_hours_start,
+        quiet_hours_end: sara_local_user.quiet_hours_end,
+        hide_blocked_person_threads: sara_local_user.hide_blocked_person_threads,
       },
       creator: Person {
         id: sara_person.id,
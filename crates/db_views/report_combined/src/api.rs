@@ -1,4 +1,10 @@
-use crate::{CommentReportView, CommunityReportView, PostReportView, PrivateMessageReportView};
+use crate::{
+  CommentReportView,
+  CommunityReportView,
+  InstanceReportView,
+  PostReportView,
+  PrivateMessageReportView,
+};
 use lemmy_db_schema::{
   ReportType,
   newtypes::{
@@ -6,12 +12,14 @@ use lemmy_db_schema::{
     CommentReportId,
     CommunityId,
     CommunityReportId,
+    InstanceReportId,
     PostId,
     PostReportId,
     PrivateMessageId,
     PrivateMessageReportId,
   },
 };
+use lemmy_db_schema_file::InstanceId;
 use lemmy_diesel_utils::pagination::PaginationCursor;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -20,6 +28,7 @@ use serde_with::skip_serializing_none;
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// List reports.
 pub struct ListReports {
   /// Only shows the unresolved reports
@@ -41,6 +50,7 @@ pub struct ListReports {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The comment report response.
 pub struct CommentReportResponse {
   pub comment_report_view: CommentReportView,
@@ -49,6 +59,7 @@ pub struct CommentReportResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A community report response.
 pub struct CommunityReportResponse {
   pub community_report_view: CommunityReportView,
@@ -57,6 +68,7 @@ pub struct CommunityReportResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Report a comment.
 pub struct CreateCommentReport {
   pub comment_id: CommentId,
@@ -69,15 +81,36 @@ pub struct CreateCommentReport {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a report for a community.
 pub struct CreateCommunityReport {
   pub community_id: CommunityId,
   pub reason: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Report an instance to the local admins.
+pub struct CreateInstanceReport {
+  pub instance_id: InstanceId,
+  pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An instance report response.
+pub struct InstanceReportResponse {
+  pub instance_report_view: InstanceReportView,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a post report.
 pub struct CreatePostReport {
   pub post_id: PostId,
@@ -90,6 +123,7 @@ pub struct CreatePostReport {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Resolve a comment report (only doable by mods).
 pub struct ResolveCommentReport {
   pub report_id: CommentReportId,
@@ -99,6 +133,7 @@ pub struct ResolveCommentReport {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Resolve a community report.
 pub struct ResolveCommunityReport {
   pub report_id: CommunityReportId,
@@ -108,6 +143,17 @@ pub struct ResolveCommunityReport {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Resolve an instance report (admins only).
+pub struct ResolveInstanceReport {
+  pub report_id: InstanceReportId,
+  pub resolved: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Resolve a post report (mods only).
 pub struct ResolvePostReport {
   pub report_id: PostReportId,
@@ -117,6 +163,7 @@ pub struct ResolvePostReport {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Resolve a private message report.
 pub struct ResolvePrivateMessageReport {
   pub report_id: PrivateMessageReportId,
@@ -126,6 +173,7 @@ pub struct ResolvePrivateMessageReport {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a report for a private message.
 pub struct CreatePrivateMessageReport {
   pub private_message_id: PrivateMessageId,
@@ -135,6 +183,7 @@ pub struct CreatePrivateMessageReport {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A private message report response.
 pub struct PrivateMessageReportResponse {
   pub private_message_report_view: PrivateMessageReportView,
@@ -143,6 +192,7 @@ pub struct PrivateMessageReportResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The post report response.
 pub struct PostReportResponse {
   pub post_report_view: PostReportView,
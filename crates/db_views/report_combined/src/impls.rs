@@ -1,6 +1,7 @@
 use crate::{
   CommentReportView,
   CommunityReportView,
+  InstanceReportView,
   LocalUserView,
   PostReportView,
   PrivateMessageReportView,
@@ -24,6 +25,7 @@ use lemmy_db_schema::{
     CommentReportId,
     CommunityId,
     CommunityReportId,
+    InstanceReportId,
     PostId,
     PostReportId,
     PrivateMessageReportId,
@@ -118,6 +120,25 @@ impl ReportCombinedViewInternal {
     Ok(c)
   }
 
+  pub async fn read_instance_report(
+    pool: &mut DbPool<'_>,
+    report_id: InstanceReportId,
+    my_person: &Person,
+  ) -> LemmyResult<InstanceReportView> {
+    let conn = &mut get_conn(pool).await?;
+    let res = report_combined_joins(my_person.id, my_person.instance_id)
+      .filter(report_combined::instance_report_id.eq(report_id))
+      .select(ReportCombinedViewInternal::as_select())
+      .first(conn)
+      .await?;
+
+    let res = InternalToCombinedView::map_to_enum(res);
+    let Some(ReportCombinedView::Instance(i)) = res else {
+      return Err(LemmyErrorType::NotFound.into());
+    };
+    Ok(i)
+  }
+
   pub async fn read_private_message_report(
     pool: &mut DbPool<'_>,
     report_id: PrivateMessageReportId,
@@ -170,6 +191,7 @@ impl PaginationCursorConversion for ReportCombinedView {
       ReportCombinedView::Post(v) => ('P', v.post_report.id.0),
       ReportCombinedView::PrivateMessage(v) => ('M', v.private_message_report.id.0),
       ReportCombinedView::Community(v) => ('Y', v.community_report.id.0),
+      ReportCombinedView::Instance(v) => ('I', v.instance_report.id.0),
     };
     CursorData::new_with_prefix(prefix, id)
   }
@@ -190,6 +212,7 @@ impl PaginationCursorConversion for ReportCombinedView {
       'P' => query.filter(report_combined::post_report_id.eq(id)),
       'M' => query.filter(report_combined::private_message_report_id.eq(id)),
       'Y' => query.filter(report_combined::community_report_id.eq(id)),
+      'I' => query.filter(report_combined::instance_report_id.eq(id)),
       _ => return Err(LemmyErrorType::CouldntParsePaginationToken.into()),
     };
     let token = query.first(conn).await?;
@@ -260,6 +283,7 @@ impl ReportCombinedQuery {
           query.filter(report_combined::private_message_report_id.is_not_null())
         }
         ReportType::Communities => query.filter(report_combined::community_report_id.is_not_null()),
+        ReportType::Instances => query.filter(report_combined::instance_report_id.is_not_null()),
       }
     }
 
@@ -301,8 +325,9 @@ impl ReportCombinedQuery {
 fn filter_mod_reports() -> _ {
   community_actions::became_moderator_at
     .is_not_null()
-    // Reporting a community or private message must go to admins
+    // Reporting a community, instance or private message must go to admins
     .and(report_combined::community_report_id.is_null())
+    .and(report_combined::instance_report_id.is_null())
     .and(report_combined::private_message_report_id.is_null())
     .and(filter_violates_instance_rules().is_distinct_from(true))
 }
@@ -318,12 +343,13 @@ fn filter_admin_reports(interval: DateTime<Utc>) -> _ {
 }
 
 /// Filter reports which are only for admins (either post/comment report with
-/// `violates_instance_rules=true`, or report on a community/person/private message.
+/// `violates_instance_rules=true`, or report on a community/instance/person/private message.
 #[diesel::dsl::auto_type]
 fn filter_violates_instance_rules() -> _ {
   post_report::violates_instance_rules
     .or(comment_report::violates_instance_rules)
     .or(report_combined::community_report_id.is_not_null())
+    .or(report_combined::instance_report_id.is_not_null())
     .or(report_combined::private_message_report_id.is_not_null())
 }
 
@@ -419,6 +445,16 @@ impl InternalToCombinedView for ReportCombinedViewInternal {
         creator_banned_from_community: v.creator_banned_from_community,
         creator_community_ban_expires_at: v.creator_community_ban_expires_at,
       }))
+    } else if let (Some(instance), Some(instance_report)) = (v.instance, v.instance_report) {
+      Some(ReportCombinedView::Instance(InstanceReportView {
+        instance_report,
+        instance,
+        creator: v.report_creator,
+        resolver: v.resolver,
+        creator_is_admin: v.creator_is_admin,
+        creator_banned: v.creator_banned,
+        creator_ban_expires_at: v.creator_ban_expires_at,
+      }))
     } else {
       None
     }
@@ -447,6 +483,7 @@ mod tests {
       community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
       community_report::{CommunityReport, CommunityReportForm},
       instance::{Instance, InstanceActions, InstanceBanForm},
+      instance_report::{InstanceReport, InstanceReportForm},
       local_user::{LocalUser, LocalUserInsertForm},
       person::{Person, PersonInsertForm},
       post::{Post, PostInsertForm},
@@ -1129,6 +1166,80 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn instance_reports() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let remote_instance = Instance::read_or_create(pool, "remote_irv.tld").await?;
+
+    // jessica reports the remote instance
+    let instance_report_form = InstanceReportForm {
+      creator_id: data.jessica.id,
+      instance_id: remote_instance.id,
+      original_instance_domain: remote_instance.domain.clone(),
+      reason: "full of spam".into(),
+    };
+    let instance_report = InstanceReport::report(pool, &instance_report_form).await?;
+
+    let reports = ReportCombinedQuery {
+      type_: Some(ReportType::Instances),
+      ..Default::default()
+    }
+    .list(pool, &data.admin_view)
+    .await?;
+    assert_length!(1, reports);
+    if let ReportCombinedView::Instance(v) = &reports[0] {
+      assert!(!v.instance_report.resolved);
+      assert_eq!(data.jessica.name, v.creator.name);
+      assert_eq!(instance_report.reason, v.instance_report.reason);
+      assert_eq!(remote_instance.domain, v.instance.domain);
+      let read_report = ReportCombinedViewInternal::read_instance_report(
+        pool,
+        instance_report.id,
+        &data.admin_view.person,
+      )
+      .await?;
+      assert_eq!(&read_report, v);
+    } else {
+      panic!("wrong type");
+    }
+
+    // Instance reports are only for admins, so the mod doesn't see it, and it isn't counted
+    let reports = ReportCombinedQuery::default()
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_length!(0, reports);
+    let timmy_count = ReportCombinedViewInternal::get_report_count(pool, &data.timmy_view).await?;
+    assert_eq!(0, timmy_count);
+    let admin_count = ReportCombinedViewInternal::get_report_count(pool, &data.admin_view).await?;
+    assert_eq!(1, admin_count);
+
+    // admin resolves the report
+    InstanceReport::update_resolved(pool, instance_report.id, data.admin_view.person.id, true)
+      .await?;
+
+    let read_report = ReportCombinedViewInternal::read_instance_report(
+      pool,
+      instance_report.id,
+      &data.admin_view.person,
+    )
+    .await?;
+    assert!(read_report.instance_report.resolved);
+    assert_eq!(
+      Some(&data.admin_view.person.name),
+      read_report.resolver.as_ref().map(|r| &r.name)
+    );
+    let admin_count = ReportCombinedViewInternal::get_report_count(pool, &data.admin_view).await?;
+    assert_eq!(0, admin_count);
+
+    Instance::delete(pool, remote_instance.id).await?;
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn violates_instance_rules() -> LemmyResult<()> {
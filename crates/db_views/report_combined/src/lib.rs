@@ -5,6 +5,8 @@ use lemmy_db_schema::source::{
   comment_report::CommentReport,
   community::{Community, CommunityActions},
   community_report::CommunityReport,
+  instance::Instance,
+  instance_report::InstanceReport,
   person::{Person, PersonActions},
   post::{Post, PostActions},
   post_report::PostReport,
@@ -50,6 +52,8 @@ pub struct ReportCombinedViewInternal {
   pub private_message_report: Option<PrivateMessageReport>,
   #[diesel(embed)]
   pub community_report: Option<CommunityReport>,
+  #[diesel(embed)]
+  pub instance_report: Option<InstanceReport>,
   #[diesel(
     select_expression_type = Person1AliasAllColumnsTuple,
     select_expression = person1_select()
@@ -86,6 +90,8 @@ pub struct ReportCombinedViewInternal {
   #[diesel(embed)]
   pub community: Option<Community>,
   #[diesel(embed)]
+  pub instance: Option<Instance>,
+  #[diesel(embed)]
   pub community_actions: Option<CommunityActions>,
   #[diesel(embed)]
   pub post_actions: Option<PostActions>,
@@ -98,18 +104,21 @@ pub struct ReportCombinedViewInternal {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type_", rename_all = "snake_case")]
 pub enum ReportCombinedView {
   Post(PostReportView),
   Comment(CommentReportView),
   PrivateMessage(PrivateMessageReportView),
   Community(CommunityReportView),
+  Instance(InstanceReportView),
 }
 
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A private message report view.
 pub struct PrivateMessageReportView {
   pub private_message_report: PrivateMessageReport,
@@ -126,6 +135,7 @@ pub struct PrivateMessageReportView {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A comment report view.
 pub struct CommentReportView {
   pub comment_report: CommentReport,
@@ -150,6 +160,7 @@ pub struct CommentReportView {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A community report view.
 pub struct CommunityReportView {
   pub community_report: CommunityReport,
@@ -168,6 +179,23 @@ pub struct CommunityReportView {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An instance report view.
+pub struct InstanceReportView {
+  pub instance_report: InstanceReport,
+  pub instance: Instance,
+  pub creator: Person,
+  pub resolver: Option<Person>,
+  pub creator_is_admin: bool,
+  pub creator_banned: bool,
+  pub creator_ban_expires_at: Option<DateTime<Utc>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A post report view.
 pub struct PostReportView {
   pub post_report: PostReport,
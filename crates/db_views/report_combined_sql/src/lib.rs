@@ -22,6 +22,8 @@ use lemmy_db_schema_file::{
     community,
     community_actions,
     community_report,
+    instance,
+    instance_report,
     local_user,
     person,
     person_actions,
@@ -64,7 +66,8 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
       .eq(report_creator)
       .or(comment_report::creator_id.eq(report_creator))
       .or(private_message_report::creator_id.eq(report_creator))
-      .or(community_report::creator_id.eq(report_creator)),
+      .or(community_report::creator_id.eq(report_creator))
+      .or(instance_report::creator_id.eq(report_creator)),
   );
 
   let item_creator_join = person::table.on(
@@ -79,7 +82,8 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
       .eq(resolver)
       .or(post_report::resolver_id.eq(resolver))
       .or(comment_report::resolver_id.eq(resolver))
-      .or(community_report::resolver_id.eq(resolver)),
+      .or(community_report::resolver_id.eq(resolver))
+      .or(instance_report::resolver_id.eq(resolver)),
   );
 
   let community_join = community::table.on(
@@ -88,6 +92,8 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
       .or(post::community_id.eq(community::id)),
   );
 
+  let instance_join = instance::table.on(instance_report::instance_id.eq(instance::id));
+
   let local_user_join = local_user::table.on(
     item_creator
       .eq(local_user::person_id)
@@ -130,6 +136,7 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
     .left_join(comment_report::table)
     .left_join(private_message_report::table)
     .left_join(community_report::table)
+    .left_join(instance_report::table)
     .inner_join(report_creator_join)
     .left_join(comment_join)
     .left_join(private_message_join)
@@ -137,6 +144,7 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
     .left_join(item_creator_join)
     .left_join(resolver_join)
     .left_join(community_join)
+    .left_join(instance_join)
     .left_join(creator_community_actions_join)
     .left_join(creator_home_instance_actions_join())
     .left_join(creator_local_instance_actions_join)
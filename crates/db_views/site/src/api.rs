@@ -1,34 +1,74 @@
-use crate::SiteView;
+use crate::{ResolveObjectView, SiteView};
+use chrono::{DateTime, Utc};
 #[cfg(feature = "full")]
 use extism::FromBytes;
 use extism_convert::Json;
 use lemmy_db_schema::{
-  newtypes::{LanguageId, MultiCommunityId, OAuthProviderId, TaglineId},
+  SearchType,
+  StreamType,
+  newtypes::{
+    ApiKeyId,
+    CommentId,
+    CommunityId,
+    InstanceMoveId,
+    LanguageId,
+    LoginTokenId,
+    MultiCommunityId,
+    OAuthApplicationId,
+    OAuthProviderId,
+    PostId,
+    SavedSearchId,
+    TaglineId,
+    WebauthnCredentialId,
+    WebhookId,
+  },
   source::{
+    api_key::ApiKey,
     comment::Comment,
     community::Community,
+    community_pseudonym::CommunityPseudonym,
+    federation_queue_state::FederationQueueState,
     instance::Instance,
+    instance_move::InstanceMove,
     language::Language,
+    local_site_rate_limit_override::LocalSiteRateLimitOverride,
+    local_site_registration_ip_range::LocalSiteRegistrationIpRange,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
     local_user::LocalUser,
+    login_failure::LoginFailure,
     login_token::LoginToken,
+    oauth_account::OAuthAccount,
+    oauth_application::OAuthApplication,
     oauth_provider::{AdminOAuthProvider, PublicOAuthProvider},
     person::Person,
     post::Post,
     private_message::PrivateMessage,
+    push_subscription::PushSubscription,
+    saved_search::SavedSearch,
+    sent_activity_delivery::SentActivityDelivery,
+    site_stats::SiteStats,
     tagline::Tagline,
+    webauthn_credential::WebauthnCredential,
+    webhook::Webhook,
   },
 };
 use lemmy_db_schema_file::{
   InstanceId,
+  PersonId,
   enums::{
+    CaptchaProvider,
     CommentSortType,
+    FederationBlockSeverity,
     FederationMode,
     ImageMode,
     ListingType,
     PostListingMode,
     PostSortType,
+    PushProvider,
+    RateLimitAction,
+    RateLimitUserClass,
     RegistrationMode,
+    TokenScope,
     VoteShow,
   },
 };
@@ -39,37 +79,186 @@ use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_person::PersonView;
 use lemmy_db_views_post::PostView;
-use lemmy_diesel_utils::{pagination::PaginationCursor, sensitive::SensitiveString};
-use serde::{Deserialize, Serialize};
+use lemmy_diesel_utils::{dburl::DbUrl, pagination::PaginationCursor, sensitive::SensitiveString};
+use serde::{Deserialize, Deserializer, Serialize, de};
 use serde_with::skip_serializing_none;
+use std::{fmt::Display, str::FromStr};
 use url::Url;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AdminAllowInstanceParams {
   pub instance: String,
   pub allow: bool,
   pub reason: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Moves of remote instances to a new domain, which wait for admin approval.
+pub struct ListInstanceMovesResponse {
+  pub instance_moves: Vec<InstanceMove>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Approve or reject the move of a remote instance. Approving replaces the old domain in the
+/// stored urls of the instance.
+pub struct ResolveInstanceMove {
+  pub instance_move_id: InstanceMoveId,
+  pub approve: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AdminBlockInstanceParams {
   pub instance: String,
   pub block: bool,
+  /// Shown publicly on the instances page.
   pub reason: String,
   /// A time that the block will expire, in unix epoch seconds.
   ///
   /// An i64 unix timestamp is used for a simpler API client implementation.
   pub expires_at: Option<i64>,
+  /// Defaults to suspend.
+  pub severity: Option<FederationBlockSeverity>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// The external auth accounts which are linked to your account.
+pub struct ListOAuthAccountsResponse {
+  pub oauth_accounts: Vec<OAuthAccount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Remove the link between your account and an external auth method.
+pub struct UnlinkOAuthAccount {
+  pub oauth_provider_id: OAuthProviderId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Register a third-party app, which can then request access to user accounts.
+pub struct CreateOAuthApplication {
+  pub name: String,
+  pub redirect_uri: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateOAuthApplicationResponse {
+  pub oauth_application: OAuthApplication,
+  /// Only returned once, store it in a safe place.
+  pub client_secret: SensitiveString,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Third-party apps, either the ones you registered, or the ones you authorized.
+pub struct ListOAuthApplicationsResponse {
+  pub oauth_applications: Vec<OAuthApplication>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delete a third-party app which you registered. This also revokes all of its tokens.
+pub struct DeleteOAuthApplication {
+  pub id: OAuthApplicationId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Grant a third-party app access to your account. The returned code needs to be passed to the
+/// redirect uri.
+pub struct AuthorizeOAuthApplication {
+  pub client_id: String,
+  pub redirect_uri: String,
+  pub scope: TokenScope,
+  /// PKCE code challenge, required for apps which can't keep their client secret private.
+  pub code_challenge: Option<String>,
+  /// Only `S256` is supported.
+  pub code_challenge_method: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuthorizeOAuthApplicationResponse {
+  pub code: SensitiveString,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Exchange an authorization code for an access token. This is sent by the third-party app as
+/// form data, as defined in RFC 6749.
+pub struct OAuthTokenRequest {
+  /// Must be `authorization_code`.
+  pub grant_type: String,
+  pub code: SensitiveString,
+  pub redirect_uri: String,
+  pub client_id: String,
+  pub client_secret: Option<SensitiveString>,
+  /// PKCE code verifier, if a code challenge was sent with the authorization.
+  pub code_verifier: Option<SensitiveString>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct OAuthTokenResponse {
+  /// Can be used like a regular login token, but is limited to the given scope.
+  pub access_token: SensitiveString,
+  /// Always `bearer`.
+  pub token_type: String,
+  /// Seconds until the token expires. The user needs to authorize the app again after that.
+  pub expires_in: i64,
+  pub scope: TokenScope,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Revoke the access of a third-party app to your account.
+pub struct RevokeOAuthApplication {
+  pub oauth_application_id: OAuthApplicationId,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Logging in with an OAuth 2.0 authorization
 pub struct AuthenticateWithOauth {
   pub code: String,
@@ -89,6 +278,7 @@ pub struct AuthenticateWithOauth {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create an external auth method.
 pub struct CreateOAuthProvider {
   pub display_name: String,
@@ -110,6 +300,7 @@ pub struct CreateOAuthProvider {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Creates a site. Should be done after first running lemmy.
 pub struct CreateSite {
   pub name: String,
@@ -166,11 +357,89 @@ pub struct CreateSite {
   pub image_max_upload_size: Option<i32>,
   pub image_allow_video_uploads: Option<bool>,
   pub image_upload_disabled: Option<bool>,
+  /// Accounts younger than this many days are restricted. Zero disables the age check.
+  pub new_account_min_age_days: Option<i32>,
+  /// Accounts with less combined post and comment score than this are restricted. Zero disables
+  /// the karma check.
+  pub new_account_min_karma: Option<i32>,
+  /// The maximum number of posts a restricted account can create per day.
+  pub new_account_max_posts_per_day: Option<i32>,
+  /// Dont allow restricted accounts to create link posts.
+  pub new_account_link_posts_disabled: Option<bool>,
+  /// Dont allow restricted accounts to upload images.
+  pub new_account_image_upload_disabled: Option<bool>,
+  /// Dont allow restricted accounts to create communities.
+  pub new_account_community_creation_disabled: Option<bool>,
+  /// Admins and moderators need to enable two-factor authentication before they can take
+  /// moderation actions.
+  pub moderators_require_totp: Option<bool>,
+  /// Days between an account deletion request and the actual erasure. Logging in during this
+  /// time cancels the deletion. If 0, accounts are erased right away.
+  pub account_deletion_grace_period_days: Option<i32>,
+  /// The maximum number of registrations from a single ip per day. Requires the registration ip
+  /// to be stored.
+  pub registrations_per_ip_per_day: Option<i32>,
+  /// A DNS blocklist zone like `zen.spamhaus.org`, registrations from listed ips are rejected.
+  pub registration_dnsbl: Option<String>,
+  /// Days to keep the registration ip of new users, for admin review. If 0, it isn't stored.
+  pub registration_ip_retention_days: Option<i32>,
+  /// Require a proof of work challenge with this number of leading zero bits for registration,
+  /// as an alternative to the captcha. If 0, it is disabled.
+  pub pow_challenge_difficulty: Option<i32>,
+  /// Reject unsigned fetches of activitypub objects, and sign all outgoing fetches. Needed for
+  /// Mastodon servers running in secure mode.
+  pub authorized_fetch: Option<bool>,
+  /// Block federation with all domains matching these patterns. Use a comma-delimited string of
+  /// wildcards or regexes.
+  ///
+  /// Example: *.example.com,/^spam\d+\.net$/
+  pub blocked_instances_patterns: Option<String>,
+  /// Allow federation with all domains matching these patterns, same format as
+  /// `blocked_instances_patterns`.
+  pub allowed_instances_patterns: Option<String>,
+  /// Maximum storage in megabytes which a single user can use for uploads. Admins are exempt.
+  /// Zero removes the limit.
+  pub image_upload_quota_mb: Option<i32>,
+  /// Re-encode uploaded images, which removes all metadata like EXIF and GPS positions.
+  pub image_strip_metadata: Option<bool>,
+  /// Convert uploaded images to webp.
+  pub image_convert_to_webp: Option<bool>,
+  /// Allow animated images in posts and comments.
+  pub image_allow_animated: Option<bool>,
+  /// Reject uploads which are wider than this many pixels. Zero removes the limit.
+  pub image_max_width: Option<i32>,
+  /// Reject uploads which are higher than this many pixels. Zero removes the limit.
+  pub image_max_height: Option<i32>,
+  /// Rewrite external images in markdown to go through the image proxy, also if the image mode
+  /// isn't [[ImageMode.ProxyAllImages]].
+  pub image_proxy_markdown: Option<bool>,
+  /// Only proxy external images in markdown from these domains and their subdomains, others are
+  /// removed. Use a comma-delimited string.
+  ///
+  /// Example: i.imgur.com,wikimedia.org
+  pub image_proxy_safelist: Option<String>,
+  /// Remove external images in markdown which are larger than this many megabytes. Zero removes
+  /// the limit.
+  pub image_proxy_max_size_mb: Option<i32>,
+  /// Which captcha is shown during registration. External providers require the site key and
+  /// secret key, and mCaptcha also the instance url.
+  pub captcha_provider: Option<CaptchaProvider>,
+  pub captcha_site_key: Option<String>,
+  pub captcha_secret_key: Option<SensitiveString>,
+  pub captcha_instance_url: Option<String>,
+  /// Limit to the number of concurrent outgoing federation requests per target instance, between
+  /// 1 and 16. Only use a higher value than 1 if a receiving instance doesn't keep up.
+  pub federation_concurrent_sends_per_instance: Option<i32>,
+  /// Maximum size of uploaded videos in megabytes.
+  pub video_max_upload_size_mb: Option<i32>,
+  /// Maximum duration of uploaded videos in seconds.
+  pub video_max_duration_seconds: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete an external auth method.
 pub struct DeleteOAuthProvider {
   pub id: OAuthProviderId,
@@ -180,6 +449,7 @@ pub struct DeleteOAuthProvider {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Edit an external auth method.
 pub struct EditOAuthProvider {
   pub id: OAuthProviderId,
@@ -200,6 +470,7 @@ pub struct EditOAuthProvider {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Edits a site.
 pub struct EditSite {
   pub name: Option<String>,
@@ -265,6 +536,10 @@ pub struct EditSite {
   pub federation_enabled: Option<bool>,
   /// A list of blocked URLs
   pub blocked_urls: Option<Vec<String>>,
+  /// Ip ranges in CIDR notation from which registrations are blocked
+  pub registration_blocked_ip_ranges: Option<Vec<String>>,
+  /// Ip ranges in CIDR notation from which registrations skip the other ip checks
+  pub registration_allowed_ip_ranges: Option<Vec<String>>,
   pub registration_mode: Option<RegistrationMode>,
   /// Whether to email admins for new reports.
   pub reports_email_admins: Option<bool>,
@@ -302,24 +577,106 @@ pub struct EditSite {
   pub image_max_upload_size: Option<i32>,
   pub image_allow_video_uploads: Option<bool>,
   pub image_upload_disabled: Option<bool>,
+  /// Accounts younger than this many days are restricted. Zero disables the age check.
+  pub new_account_min_age_days: Option<i32>,
+  /// Accounts with less combined post and comment score than this are restricted. Zero disables
+  /// the karma check.
+  pub new_account_min_karma: Option<i32>,
+  /// The maximum number of posts a restricted account can create per day. Sending a zero erases
+  /// this field.
+  pub new_account_max_posts_per_day: Option<i32>,
+  /// Dont allow restricted accounts to create link posts.
+  pub new_account_link_posts_disabled: Option<bool>,
+  /// Dont allow restricted accounts to upload images.
+  pub new_account_image_upload_disabled: Option<bool>,
+  /// Dont allow restricted accounts to create communities.
+  pub new_account_community_creation_disabled: Option<bool>,
+  /// Admins and moderators need to enable two-factor authentication before they can take
+  /// moderation actions.
+  pub moderators_require_totp: Option<bool>,
+  /// Days between an account deletion request and the actual erasure. Logging in during this
+  /// time cancels the deletion. If 0, accounts are erased right away.
+  pub account_deletion_grace_period_days: Option<i32>,
+  /// The maximum number of registrations from a single ip per day. Requires the registration ip
+  /// to be stored.
+  pub registrations_per_ip_per_day: Option<i32>,
+  /// A DNS blocklist zone like `zen.spamhaus.org`, registrations from listed ips are rejected.
+  pub registration_dnsbl: Option<String>,
+  /// Days to keep the registration ip of new users, for admin review. If 0, it isn't stored.
+  pub registration_ip_retention_days: Option<i32>,
+  /// Require a proof of work challenge with this number of leading zero bits for registration,
+  /// as an alternative to the captcha. If 0, it is disabled.
+  pub pow_challenge_difficulty: Option<i32>,
+  /// Reject unsigned fetches of activitypub objects, and sign all outgoing fetches. Needed for
+  /// Mastodon servers running in secure mode.
+  pub authorized_fetch: Option<bool>,
+  /// Block federation with all domains matching these patterns. Use a comma-delimited string of
+  /// wildcards or regexes.
+  ///
+  /// Example: *.example.com,/^spam\d+\.net$/
+  pub blocked_instances_patterns: Option<String>,
+  /// Allow federation with all domains matching these patterns, same format as
+  /// `blocked_instances_patterns`.
+  pub allowed_instances_patterns: Option<String>,
+  /// Maximum storage in megabytes which a single user can use for uploads. Admins are exempt.
+  /// Zero removes the limit.
+  pub image_upload_quota_mb: Option<i32>,
+  /// Re-encode uploaded images, which removes all metadata like EXIF and GPS positions.
+  pub image_strip_metadata: Option<bool>,
+  /// Convert uploaded images to webp.
+  pub image_convert_to_webp: Option<bool>,
+  /// Allow animated images in posts and comments.
+  pub image_allow_animated: Option<bool>,
+  /// Reject uploads which are wider than this many pixels. Zero removes the limit.
+  pub image_max_width: Option<i32>,
+  /// Reject uploads which are higher than this many pixels. Zero removes the limit.
+  pub image_max_height: Option<i32>,
+  /// Rewrite external images in markdown to go through the image proxy, also if the image mode
+  /// isn't [[ImageMode.ProxyAllImages]].
+  pub image_proxy_markdown: Option<bool>,
+  /// Only proxy external images in markdown from these domains and their subdomains, others are
+  /// removed. Use a comma-delimited string.
+  ///
+  /// Example: i.imgur.com,wikimedia.org
+  pub image_proxy_safelist: Option<String>,
+  /// Remove external images in markdown which are larger than this many megabytes. Zero removes
+  /// the limit.
+  pub image_proxy_max_size_mb: Option<i32>,
+  /// Which captcha is shown during registration. External providers require the site key and
+  /// secret key, and mCaptcha also the instance url.
+  pub captcha_provider: Option<CaptchaProvider>,
+  pub captcha_site_key: Option<String>,
+  pub captcha_secret_key: Option<SensitiveString>,
+  pub captcha_instance_url: Option<String>,
+  /// Limit to the number of concurrent outgoing federation requests per target instance, between
+  /// 1 and 16. Only use a higher value than 1 if a receiving instance doesn't keep up.
+  pub federation_concurrent_sends_per_instance: Option<i32>,
+  /// Maximum size of uploaded videos in megabytes.
+  pub video_max_upload_size_mb: Option<i32>,
+  /// Maximum duration of uploaded videos in seconds.
+  pub video_max_duration_seconds: Option<i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum GetFederatedInstancesKind {
   #[default]
   All,
   Linked,
   Allowed,
   Blocked,
+  /// Instances which were unreachable for a while, and don't receive activities.
+  Dead,
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GetFederatedInstances {
   pub domain_filter: Option<String>,
   pub kind: GetFederatedInstancesKind,
@@ -331,6 +688,7 @@ pub struct GetFederatedInstances {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// An expanded response for a site.
 pub struct GetSiteResponse {
   pub site_view: SiteView,
@@ -344,17 +702,24 @@ pub struct GetSiteResponse {
   pub oauth_providers: Vec<PublicOAuthProvider>,
   pub admin_oauth_providers: Vec<AdminOAuthProvider>,
   pub blocked_urls: Vec<LocalSiteUrlBlocklist>,
+  /// The ip ranges of the registration firewall, only visible to admins.
+  pub admin_registration_ip_ranges: Vec<LocalSiteRegistrationIpRange>,
   pub active_plugins: Vec<PluginMetadata>,
   /// The number of seconds between the last application published, and approved / denied time.
   ///
   /// Useful for estimating when your application will be approved.
   pub last_application_duration_seconds: Option<i64>,
   pub captcha_enabled: bool,
+  /// Key for subscribing to Web Push notifications, if they are enabled.
+  pub vapid_public_key: Option<String>,
+  /// Providers which devices can use to subscribe to push notifications.
+  pub push_providers: Vec<PushProvider>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The response for a site.
 pub struct SiteResponse {
   pub site_view: SiteView,
@@ -365,6 +730,7 @@ pub struct SiteResponse {
 #[cfg_attr(feature = "full", encoding(Json))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A captcha response.
 pub struct CaptchaResponse {
   /// A Base64 encoded png
@@ -378,6 +744,7 @@ pub struct CaptchaResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Changes your account password.
 pub struct ChangePassword {
   pub new_password: SensitiveString,
@@ -390,6 +757,7 @@ pub struct ChangePassword {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete your account.
 pub struct DeleteAccount {
   pub password: SensitiveString,
@@ -400,15 +768,288 @@ pub struct DeleteAccount {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A wrapper for the captcha response.
 pub struct GetCaptchaResponse {
   /// Will be None if captchas are disabled.
   pub ok: Option<CaptchaResponse>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A proof of work challenge. It is solved by finding a nonce, so that the sha256 hash of the
+/// challenge followed by the nonce starts with `difficulty` zero bits.
+pub struct PowChallengeResponse {
+  pub challenge: String,
+  pub difficulty: i32,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A wrapper for the proof of work challenge.
+pub struct GetPowChallengeResponse {
+  /// Will be None if proof of work challenges are disabled.
+  pub ok: Option<PowChallengeResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Checks the solution of a proof of work challenge, without using it up.
+pub struct VerifyPowChallenge {
+  pub challenge: String,
+  pub nonce: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Failed logins during the last day, for admins.
+pub struct ListLoginFailuresResponse {
+  pub locked_accounts: i64,
+  pub locked_ips: i64,
+  pub failures_last_day: i64,
+  /// The accounts and ips with the most failures.
+  pub login_failures: Vec<LoginFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Reveals the person behind a pseudonym, for admins.
+pub struct GetPseudonym {
+  pub person_id: PersonId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GetPseudonymResponse {
+  pub pseudonym: CommunityPseudonym,
+  pub person: Person,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Fetches the daily statistics of the instance, for admins.
+pub struct GetAdminStats {
+  /// Number of days, defaults to 30 and at most 365.
+  pub days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Daily statistics, oldest first. The current day is updated every hour.
+pub struct GetAdminStatsResponse {
+  pub stats: Vec<SiteStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// State of the outgoing federation queue, for admins.
+pub struct ListFederationQueueResponse {
+  /// Instances which receive all activities without delay.
+  pub up_to_date: i64,
+  /// Instances which are behind, but don't have failures.
+  pub behind: i64,
+  /// Instances where sending currently fails, and which are retried with increasing delays.
+  pub failing: i64,
+  /// Instances which are dead or blocked, and don't receive any activities.
+  pub paused: i64,
+  /// The instances which are the furthest behind.
+  pub instances: Vec<FederationQueueInstance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FederationQueueInstance {
+  pub instance: Instance,
+  pub queue_state: FederationQueueState,
+  /// Number of activities which still need to be sent to the instance.
+  pub activities_behind: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// List outgoing activities which couldn't be delivered to an instance, for admins.
+pub struct ListFailedDeliveries {
+  pub instance_id: InstanceId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListFailedDeliveriesResponse {
+  /// Newest first.
+  pub deliveries: Vec<FailedDelivery>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FailedDelivery {
+  pub delivery: SentActivityDelivery,
+  pub activity_ap_id: DbUrl,
+  pub activity_published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Send all activities again which couldn't be delivered to the instance since the given time.
+/// Useful after an instance recovers from extended downtime.
+pub struct ReplayFailedDeliveries {
+  pub instance_id: InstanceId,
+  pub since: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReplayFailedDeliveriesResponse {
+  /// Number of activities which are going to be sent again.
+  pub replayed: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Activities received from remote instances since the server was started, for admins.
+pub struct ListFederationInboxResponse {
+  /// Sorted by the number of rejected activities.
+  pub instances: Vec<FederationInboxInstance>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FederationInboxInstance {
+  pub domain: String,
+  pub received: i64,
+  /// Activities which were rejected because of the inbox rate limit.
+  pub rejected: i64,
+  /// Set while all activities from the instance are rejected, because it exceeded the rate
+  /// limit repeatedly.
+  pub throttled_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Set a different rate limit for a class of users, which replaces the site rate limit of the
+/// action. Only for admins.
+pub struct SetRateLimitOverride {
+  pub user_class: RateLimitUserClass,
+  pub action: RateLimitAction,
+  pub max_requests: i32,
+  pub interval_seconds: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Remove a rate limit override, so that the site rate limit applies again. Only for admins.
+pub struct DeleteRateLimitOverride {
+  pub user_class: RateLimitUserClass,
+  pub action: RateLimitAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RateLimitOverrideResponse {
+  pub rate_limit_override: LocalSiteRateLimitOverride,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListRateLimitOverridesResponse {
+  pub rate_limit_overrides: Vec<LocalSiteRateLimitOverride>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Clients which are slowed down or blocked because they repeatedly hit rate limits or failed to
+/// log in, for admins.
+pub struct ListClientPenaltiesResponse {
+  /// Sorted by the number of strikes.
+  pub clients: Vec<ClientPenalty>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClientPenalty {
+  /// IPv6 addresses are grouped by /64 subnet.
+  pub ip: String,
+  /// Violations in the last minutes. Each one above a small allowance delays further requests.
+  pub strikes: i64,
+  /// Set while all api requests from the client are rejected.
+  pub blocked_until: Option<DateTime<Utc>>,
+  pub rate_limited: i64,
+  pub auth_failures: i64,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Lifts the delays and blocks of a client.
+pub struct ClearClientPenalties {
+  /// Clears the penalties of all clients if empty.
+  pub ip: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ClearClientPenaltiesResponse {
+  /// Number of clients whose penalties were removed.
+  pub cleared: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct GenerateTotpSecretResponse {
   pub totp_secret_url: SensitiveString,
 }
@@ -416,14 +1057,237 @@ pub struct GenerateTotpSecretResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ListLoginsResponse {
   pub logins: Vec<LoginToken>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Log out a single session, for example on a lost device.
+pub struct RevokeLogin {
+  pub id: LoginTokenId,
+}
+
 #[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Create a key for bots, which can be passed in the `X-Api-Key` header instead of logging in.
+pub struct CreateApiKey {
+  pub name: String,
+  pub scope: TokenScope,
+  /// Maximum number of requests per minute.
+  pub rate_limit: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateApiKeyResponse {
+  pub api_key: ApiKey,
+  /// Only returned once, store it in a safe place.
+  pub key: SensitiveString,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListApiKeysResponse {
+  pub api_keys: Vec<ApiKey>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Revoke an api key.
+pub struct DeleteApiKey {
+  pub id: ApiKeyId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Save a search query to run it again later.
+pub struct CreateSavedSearch {
+  pub search_term: String,
+  /// Only search in this community.
+  pub community_id: Option<CommunityId>,
+  /// Get an inbox notification when new posts match the search.
+  pub notify: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SavedSearchResponse {
+  pub saved_search: SavedSearch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListSavedSearchesResponse {
+  pub saved_searches: Vec<SavedSearch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeleteSavedSearch {
+  pub id: SavedSearchId,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Receive push notifications on this device. Only one subscription is kept for each login
+/// session.
+///
+/// For Web Push the values come from the `PushSubscription` of the browser, which needs
+/// `vapid_public_key` from the site response.
+pub struct RegisterPushSubscription {
+  /// Url of the push service or UnifiedPush distributor, or the device token for FCM.
+  pub endpoint: String,
+  /// Public key of the browser, base64url encoded. Required for Web Push.
+  pub p256dh: Option<String>,
+  /// Authentication secret of the browser, base64url encoded. Required for Web Push.
+  pub auth: Option<String>,
+  /// Web Push by default. The available providers are listed in the site response.
+  pub provider: Option<PushProvider>,
+  /// Name of the device, to recognize it in the list of subscriptions.
+  pub device_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListPushSubscriptionsResponse {
+  pub push_subscriptions: Vec<PushSubscription>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Options which need to be passed to `navigator.credentials.create()` to register a passkey.
+pub struct WebauthnRegistrationOptions {
+  /// Base64url encoded.
+  pub challenge: String,
+  pub rp_id: String,
+  pub rp_name: String,
+  /// Base64url encoded user handle.
+  pub user_id: String,
+  pub user_name: String,
+  /// Passkeys which are already registered for the account.
+  pub exclude_credentials: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Register a passkey. Values are taken from the credential which is returned by
+/// `navigator.credentials.create()`, and base64url encoded. Only ES256 keys are supported, and
+/// the attestation statement is not verified.
+pub struct RegisterWebauthnCredential {
+  pub name: String,
+  pub credential_id: String,
+  pub client_data_json: String,
+  /// The authenticator data and public key are read from the attestation object.
+  pub attestation_object: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebauthnCredentialResponse {
+  pub webauthn_credential: WebauthnCredential,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListWebauthnCredentialsResponse {
+  pub webauthn_credentials: Vec<WebauthnCredential>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delete a passkey.
+pub struct DeleteWebauthnCredential {
+  pub id: WebauthnCredentialId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Start logging in with a passkey. If a user is given, only their passkeys are allowed, which
+/// is necessary when using it as second factor.
+pub struct StartWebauthnLogin {
+  pub username_or_email: Option<SensitiveString>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Options which need to be passed to `navigator.credentials.get()`.
+pub struct WebauthnLoginOptions {
+  /// Base64url encoded.
+  pub challenge: String,
+  pub rp_id: String,
+  /// Empty for passwordless login, so that the authenticator can offer any passkey.
+  pub allow_credentials: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A signed challenge from `navigator.credentials.get()`, with base64url encoded values.
+pub struct WebauthnAssertion {
+  pub credential_id: String,
+  pub client_data_json: String,
+  pub authenticator_data: String,
+  pub signature: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Log in with a passkey instead of a password.
+pub struct WebauthnLogin {
+  pub assertion: WebauthnAssertion,
+  /// If this is true the login is valid forever, otherwise it expires after one week.
+  pub stay_logged_in: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Logging into lemmy.
 ///
 /// Note: Banned users can still log in, to be able to do certain things like delete
@@ -433,14 +1297,21 @@ pub struct Login {
   pub password: SensitiveString,
   /// May be required, if totp is enabled for their account.
   pub totp_2fa_token: Option<String>,
+  /// Can be used instead of the totp token as second factor. Required if the user registered a
+  /// passkey and totp isn't enabled. The login needs to be started with their username.
+  pub webauthn_assertion: Option<WebauthnAssertion>,
   /// If this is true the login is valid forever, otherwise it expires after one week.
   pub stay_logged_in: Option<bool>,
+  /// Get a jwt which expires after 15 minutes, and a refresh token to get new ones. The session
+  /// ends if the refresh token isn't used for 30 days. Ignores `stay_logged_in`.
+  pub use_refresh_token: Option<bool>,
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A response for your login.
 pub struct LoginResponse {
   /// This is None in response to `Register` if email verification is enabled, or the server
@@ -450,11 +1321,24 @@ pub struct LoginResponse {
   pub registration_created: bool,
   /// If email verifications are required, this will return true for a signup response.
   pub verify_email_sent: bool,
+  /// Only set if requested with `use_refresh_token`. Can be used once with `RefreshToken` to get a
+  /// new jwt, and a new refresh token.
+  pub refresh_token: Option<SensitiveString>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Get a new jwt for a session which uses a refresh token.
+pub struct RefreshToken {
+  pub refresh_token: SensitiveString,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Your user info.
 pub struct MyUserInfo {
   pub local_user_view: LocalUserView,
@@ -472,6 +1356,7 @@ pub struct MyUserInfo {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Change your password after receiving a reset request.
 pub struct PasswordChangeAfterReset {
   pub token: SensitiveString,
@@ -482,6 +1367,7 @@ pub struct PasswordChangeAfterReset {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Reset your password via email.
 pub struct PasswordReset {
   pub email: SensitiveString,
@@ -490,6 +1376,7 @@ pub struct PasswordReset {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Make a request to resend your verification email.
 pub struct ResendVerificationEmail {
   pub email: SensitiveString,
@@ -499,6 +1386,7 @@ pub struct ResendVerificationEmail {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Saves settings for your user.
 pub struct SaveUserSettings {
   /// Show nsfw posts.
@@ -533,6 +1421,23 @@ pub struct SaveUserSettings {
   pub show_avatars: Option<bool>,
   /// Sends notifications to your email.
   pub send_notifications_to_email: Option<bool>,
+  /// Send push notifications for replies.
+  pub push_replies: Option<bool>,
+  /// Send push notifications for mentions.
+  pub push_mentions: Option<bool>,
+  /// Send push notifications for private messages.
+  pub push_private_messages: Option<bool>,
+  /// Send push notifications for new reports, for admins and for mods of the community.
+  pub push_reports: Option<bool>,
+  /// Send push notifications for new registration applications, only for admins.
+  pub push_registration_applications: Option<bool>,
+  /// Don't send push notifications between the start and end of the quiet hours.
+  pub quiet_hours_enabled: Option<bool>,
+  /// Start of the quiet hours, in minutes after midnight UTC.
+  pub quiet_hours_start: Option<i32>,
+  /// End of the quiet hours, in minutes after midnight UTC. May be before the start, if the
+  /// quiet hours last over midnight.
+  pub quiet_hours_end: Option<i32>,
   /// Whether this account is a bot account. Users can hide these accounts easily if they wish.
   pub bot_account: Option<bool>,
   /// Whether to show bot accounts.
@@ -565,26 +1470,53 @@ pub struct SaveUserSettings {
   pub hide_media: Option<bool>,
   /// Whether to show vote totals given to others.
   pub show_person_votes: Option<bool>,
+  /// Also hide the replies to comments of blocked persons, instead of showing them without their
+  /// parent.
+  pub hide_blocked_person_threads: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EditTotp {
   pub totp_token: String,
   pub enabled: bool,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EditTotpResponse {
   pub enabled: bool,
+  /// One-time codes which can be used instead of a totp token. Only returned when enabling 2FA,
+  /// and need to be stored by the user.
+  pub recovery_codes: Option<Vec<SensitiveString>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Replace all totp recovery codes with new ones.
+pub struct RegenerateTotpRecoveryCodes {
+  pub totp_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TotpRecoveryCodesResponse {
+  pub recovery_codes: Vec<SensitiveString>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Block an instance's persons.
 pub struct UserBlockInstancePersonsParams {
   pub instance_id: InstanceId,
@@ -594,6 +1526,7 @@ pub struct UserBlockInstancePersonsParams {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Block an instance's communities.
 pub struct UserBlockInstanceCommunitiesParams {
   pub instance_id: InstanceId,
@@ -603,6 +1536,7 @@ pub struct UserBlockInstanceCommunitiesParams {
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Verify your email.
 pub struct VerifyEmail {
   pub token: String,
@@ -611,6 +1545,7 @@ pub struct VerifyEmail {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Create a tagline
 pub struct CreateTagline {
   pub content: String,
@@ -619,6 +1554,7 @@ pub struct CreateTagline {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Delete a tagline
 pub struct DeleteTagline {
   pub id: TaglineId,
@@ -628,6 +1564,7 @@ pub struct DeleteTagline {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Fetches a list of taglines.
 pub struct ListTaglines {
   pub page_cursor: Option<PaginationCursor>,
@@ -637,6 +1574,7 @@ pub struct ListTaglines {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TaglineResponse {
   pub tagline: Tagline,
 }
@@ -644,17 +1582,90 @@ pub struct TaglineResponse {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Update a tagline
 pub struct EditTagline {
   pub id: TaglineId,
   pub content: String,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Create a webhook, which is called for the selected events.
+pub struct CreateWebhook {
+  pub url: Url,
+  /// Used to sign the payloads with HMAC-SHA256, in the `X-Lemmy-Signature` header. The signed
+  /// value is the `X-Lemmy-Timestamp` header and the body, separated by a dot.
+  pub secret: String,
+  pub post_created: Option<bool>,
+  pub person_created: Option<bool>,
+  pub report_created: Option<bool>,
+  pub community_created: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Update a webhook
+pub struct EditWebhook {
+  pub id: WebhookId,
+  pub url: Option<Url>,
+  pub secret: Option<String>,
+  pub post_created: Option<bool>,
+  pub person_created: Option<bool>,
+  pub report_created: Option<bool>,
+  pub community_created: Option<bool>,
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Delete a webhook, together with its delivery logs.
+pub struct DeleteWebhook {
+  pub id: WebhookId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebhookResponse {
+  pub webhook: Webhook,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ListWebhooksResponse {
+  pub webhooks: Vec<Webhook>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Fetches the delivery logs of a webhook, newest first.
+pub struct ListWebhookDeliveries {
+  pub webhook_id: WebhookId,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "full", derive(FromBytes))]
 #[cfg_attr(feature = "full", encoding(Json))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PluginMetadata {
   pub name: String,
   pub url: Option<Url>,
@@ -674,39 +1685,261 @@ impl PluginMetadata {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Does an apub fetch for an object.
 pub struct ResolveObject {
   /// Can be the full url, or a shortened version like: !fediverse@lemmy.ml
   pub q: String,
+  /// Fetch the object again from its instance, even if it is known already. Useful if a remote
+  /// object is outdated, for example because an edit didn't federate. Requires login.
+  pub refresh: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
-/// An *all* type search that returns many objects sorted by new.
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Finds the local copy of a post, comment or user, given any link to it. Unlike `ResolveObject`,
+/// this also understands links from the web interface of Mastodon, Kbin, Piefed and Lemmy, which
+/// differ from the ActivityPub id.
+pub struct ResolvePermalink {
+  pub url: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Reads many posts, comments, communities and users at once, for example to render a list of
+/// saved items. At most 50 objects can be requested.
+pub struct ResolveMany {
+  pub post_ids: Option<Vec<PostId>>,
+  pub comment_ids: Option<Vec<CommentId>>,
+  pub community_ids: Option<Vec<CommunityId>>,
+  pub person_ids: Option<Vec<PersonId>>,
+  /// Urls or shortened names, like `q` in `ResolveObject`.
+  pub urls: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResolveManyResponse {
+  /// The objects in the order of the request, starting with posts. Objects which don't exist or
+  /// which the user can't see are left out.
+  pub objects: Vec<ResolveObjectView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// An *all* type search that returns many objects. Posts and comments are sorted by relevance,
+/// everything else by new.
+///
+/// Post titles and bodies, comments and descriptions are matched with full-text search, which
+/// supports quoted phrases, `or` and negation with `-`.
+///
+/// The search term can contain the operators `author:name`, `community:name` and
+/// `site:example.com`, which work like the corresponding filters. Remote users and communities
+/// are written as `name@example.com`.
 ///
 /// This will likely be deprecated, and you should use the list endpoints with `search_term`
 /// instead.
 pub struct Search {
   pub search_term: String,
   pub search_title_only: Option<bool>,
+  /// Only return these types of results, as comma separated list like `posts,comments`. Returns
+  /// all types by default.
+  #[serde(default, deserialize_with = "deserialize_comma_separated")]
+  pub types: Option<Vec<SearchType>>,
+  /// Only posts and comments by this user
+  pub creator_id: Option<PersonId>,
+  pub creator_name: Option<String>,
+  /// Only posts and comments in this community
+  pub community_id: Option<CommunityId>,
+  pub community_name: Option<String>,
+  /// Only posts which link to this domain or one of its subdomains
+  pub domain: Option<String>,
+  pub show_nsfw: Option<bool>,
+  /// Only posts and comments published at or after this time
+  pub published_after: Option<DateTime<Utc>>,
+  /// Only posts and comments published before this time
+  pub published_before: Option<DateTime<Utc>>,
+  /// Only posts and comments with at least this score
+  pub min_score: Option<i32>,
+  /// Only search the comments of this post. The comment paths in the results can be used to
+  /// jump to them in the thread.
+  pub post_id: Option<PostId>,
+  /// Return the full post bodies and comments, instead of replacing them with snippets.
+  pub full_content: Option<bool>,
+}
+
+/// Query parameters can't contain arrays, so lists are passed as comma separated strings.
+fn deserialize_comma_separated<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: FromStr,
+  T::Err: Display,
+{
+  let Some(list) = Option::<String>::deserialize(deserializer)? else {
+    return Ok(None);
+  };
+  list
+    .split(',')
+    .map(str::trim)
+    .filter(|item| !item.is_empty())
+    .map(|item| item.parse().map_err(de::Error::custom))
+    .collect::<Result<_, _>>()
+    .map(Some)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// The search response, containing lists of the return type possibilities
 pub struct SearchResponse {
-  pub comments: Vec<CommentView>,
-  pub posts: Vec<PostView>,
+  pub comments: Vec<SearchCommentView>,
+  pub posts: Vec<SearchPostView>,
   pub communities: Vec<CommunityView>,
   pub persons: Vec<PersonView>,
   pub multi_communities: Vec<MultiCommunityView>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A post in the search results.
+pub struct SearchPostView {
+  pub post_view: PostView,
+  /// Excerpt of the body with the matches marked as bold in markdown. The body itself is left
+  /// out when this is set.
+  pub snippet: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// A comment in the search results.
+pub struct SearchCommentView {
+  pub comment_view: CommentView,
+  /// Excerpt of the comment with the matches marked as bold in markdown. The content itself is
+  /// left empty when this is set.
+  pub snippet: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Follow new content in a community or post in real time, as server-sent events. The `id` is
+/// the id of the community or post.
+pub struct GetStream {
+  pub type_: StreamType,
+  pub id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type_", rename_all = "snake_case")]
+/// An event in a live stream. It only contains ids, the content itself is fetched with the
+/// normal api so that the usual permission checks and user settings apply.
+pub enum StreamEvent {
+  PostCreated {
+    post_id: PostId,
+    community_id: CommunityId,
+  },
+  CommentCreated {
+    comment_id: CommentId,
+    post_id: PostId,
+    community_id: CommunityId,
+  },
+  PostVote {
+    post_id: PostId,
+    community_id: CommunityId,
+    score: i32,
+    upvotes: i32,
+    downvotes: i32,
+  },
+  CommentVote {
+    comment_id: CommentId,
+    post_id: PostId,
+    community_id: CommunityId,
+    score: i32,
+    upvotes: i32,
+    downvotes: i32,
+  },
+  /// Some events were left out because there were too many, or the connection was too slow. The
+  /// content should be reloaded.
+  Lagged,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Find users and communities whose name starts with the given text, for mentions and community
+/// pickers. Remote ones can be narrowed down by domain as `name@domain`.
+pub struct Autocomplete {
+  pub q: String,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AutocompleteResponse {
+  pub communities: Vec<AutocompleteCommunity>,
+  pub persons: Vec<AutocompletePerson>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Only the community fields which are needed to show and insert a suggestion.
+pub struct AutocompleteCommunity {
+  pub id: CommunityId,
+  pub name: String,
+  pub title: String,
+  pub ap_id: DbUrl,
+  pub icon: Option<DbUrl>,
+  pub local: bool,
+  pub nsfw: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+/// Only the user fields which are needed to show and insert a suggestion.
+pub struct AutocompletePerson {
+  pub id: PersonId,
+  pub name: String,
+  pub display_name: Option<String>,
+  pub ap_id: DbUrl,
+  pub avatar: Option<DbUrl>,
+  pub local: bool,
+  pub bot_account: bool,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(tag = "type_", rename_all = "snake_case")]
 pub enum PostOrCommentOrPrivateMessage {
   Post(Post),
@@ -726,6 +1959,7 @@ pub enum PostOrCommentOrPrivateMessage {
 #[serde(deny_unknown_fields)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UserSettingsBackup {
   pub display_name: Option<String>,
   pub bio: Option<String>,
@@ -761,6 +1995,7 @@ pub struct UserSettingsBackup {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// Your exported data.
 pub struct ExportDataResponse {
   pub notifications: Vec<PostOrCommentOrPrivateMessage>,
@@ -774,6 +2009,7 @@ pub struct ExportDataResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A response that completes successfully.
 pub struct SuccessResponse {
   pub success: bool,
@@ -792,8 +2028,12 @@ impl Default for SuccessResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UnreadCountsResponse {
   pub notification_count: i64,
+  /// Unread notifications about new posts in followed communities and new comments in followed
+  /// posts. These are also included in `notification_count`.
+  pub subscribed_count: i64,
   pub report_count: Option<i64>,
   pub pending_follow_count: Option<i64>,
   pub registration_application_count: Option<i64>,
@@ -160,6 +160,7 @@ impl FederatedInstanceView {
       GetFederatedInstancesKind::Blocked => {
         query.filter(federation_blocklist::instance_id.is_not_null())
       }
+      GetFederatedInstancesKind::Dead => query.filter(instance::dead_since.is_not_null()),
     };
 
     let mut pq = Self::paginate(query, &data.page_cursor, SortDirection::Desc, pool).await?;
@@ -22,6 +22,7 @@ pub mod impls;
 #[cfg_attr(feature = "full", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 struct VoteViewPost {
   #[cfg_attr(feature = "full", diesel(embed))]
   pub creator: Person,
@@ -43,6 +44,7 @@ struct VoteViewPost {
 #[cfg_attr(feature = "full", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 struct VoteViewComment {
   #[cfg_attr(feature = "full", diesel(embed))]
   pub creator: Person,
@@ -63,6 +65,7 @@ struct VoteViewComment {
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 /// A vote view for checking a post or comments votes.
 pub struct VoteView {
   pub creator: Person,
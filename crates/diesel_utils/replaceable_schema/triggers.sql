@@ -362,6 +362,25 @@ CREATE TRIGGER change_values
     BEFORE INSERT OR UPDATE ON comment
     FOR EACH ROW
     EXECUTE FUNCTION r.comment_change_values ();
+CREATE FUNCTION r.comment_set_changed_at ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    NEW.changed_at = now();
+    RETURN NEW;
+END
+$$;
+CREATE TRIGGER set_changed_at
+    BEFORE UPDATE ON comment
+    FOR EACH ROW
+    WHEN (
+        (OLD.content, OLD.removed, OLD.deleted, OLD.distinguished, OLD.language_id, OLD.locked,
+        OLD.federation_pending)
+        IS DISTINCT FROM
+        (NEW.content, NEW.removed, NEW.deleted, NEW.distinguished, NEW.language_id, NEW.locked,
+        NEW.federation_pending))
+    EXECUTE FUNCTION r.comment_set_changed_at ();
 CREATE FUNCTION r.post_change_values ()
     RETURNS TRIGGER
     LANGUAGE plpgsql
@@ -378,6 +397,28 @@ CREATE TRIGGER change_values
     BEFORE INSERT ON post
     FOR EACH ROW
     EXECUTE FUNCTION r.post_change_values ();
+-- Counts and ranks change too often, so only changes of the content and state are tracked
+CREATE FUNCTION r.post_set_changed_at ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    NEW.changed_at = now();
+    RETURN NEW;
+END
+$$;
+CREATE TRIGGER set_changed_at
+    BEFORE UPDATE ON post
+    FOR EACH ROW
+    WHEN (
+        (OLD.name, OLD.url, OLD.body, OLD.removed, OLD.locked, OLD.deleted, OLD.nsfw,
+        OLD.language_id, OLD.featured_community, OLD.featured_local, OLD.alt_text,
+        OLD.scheduled_publish_time_at, OLD.federation_pending)
+        IS DISTINCT FROM
+        (NEW.name, NEW.url, NEW.body, NEW.removed, NEW.locked, NEW.deleted, NEW.nsfw,
+        NEW.language_id, NEW.featured_community, NEW.featured_local, NEW.alt_text,
+        NEW.scheduled_publish_time_at, NEW.federation_pending))
+    EXECUTE FUNCTION r.post_set_changed_at ();
 CREATE FUNCTION r.private_message_change_values ()
     RETURNS TRIGGER
     LANGUAGE plpgsql
@@ -396,7 +437,7 @@ CREATE TRIGGER change_values
     EXECUTE FUNCTION r.private_message_change_values ();
 -- Combined tables triggers
 -- These insert (published_at, item_id) into X_combined tables
--- Reports (comment_report, post_report, private_message_report)
+-- Reports (comment_report, post_report, private_message_report, community_report, instance_report)
 CREATE PROCEDURE r.create_report_combined_trigger (table_name text)
 LANGUAGE plpgsql
 AS $a$
@@ -440,6 +481,7 @@ CALL r.create_report_combined_trigger ('post_report');
 CALL r.create_report_combined_trigger ('comment_report');
 CALL r.create_report_combined_trigger ('private_message_report');
 CALL r.create_report_combined_trigger ('community_report');
+CALL r.create_report_combined_trigger ('instance_report');
 -- person_content (comment, post)
 CREATE PROCEDURE r.create_person_content_combined_trigger (table_name text)
 LANGUAGE plpgsql
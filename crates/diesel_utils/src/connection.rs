@@ -1,8 +1,11 @@
 use deadpool::Runtime;
-use diesel::result::{
-  ConnectionError,
-  ConnectionResult,
-  Error::{self as DieselError, QueryBuilderError},
+use diesel::{
+  connection::{Instrumentation, InstrumentationEvent},
+  result::{
+    ConnectionError,
+    ConnectionResult,
+    Error::{self as DieselError, QueryBuilderError},
+  },
 };
 use diesel_async::{
   AsyncConnection,
@@ -37,7 +40,7 @@ use std::{
   sync::Arc,
   time::Duration,
 };
-use tracing::error;
+use tracing::{Span, error, info_span};
 
 pub type ActualDbPool = Pool<AsyncPgConnection>;
 
@@ -201,7 +204,7 @@ pub fn build_db_pool_for_tests() -> ActualDbPool {
 fn establish_connection(config: &str) -> BoxFuture<'_, ConnectionResult<AsyncPgConnection>> {
   let fut = async {
     // We only support TLS with sslmode=require currently
-    let conn = if config.contains("sslmode=require") {
+    let mut conn = if config.contains("sslmode=require") {
       let rustls_config = DangerousClientConfigBuilder {
         cfg: ClientConfig::builder(),
       }
@@ -221,12 +224,48 @@ fn establish_connection(config: &str) -> BoxFuture<'_, ConnectionResult<AsyncPgC
     } else {
       AsyncPgConnection::establish(config).await?
     };
+    if SETTINGS.opentelemetry_url.is_some() {
+      conn.set_instrumentation(QuerySpans::default());
+    }
 
     Ok(conn)
   };
   fut.boxed()
 }
 
+/// Creates a span for each database query, so that they show up in traces below the api request
+/// or task which made them.
+#[derive(Default)]
+struct QuerySpans {
+  current: Option<Span>,
+}
+
+impl Instrumentation for QuerySpans {
+  fn on_connection_event(&mut self, event: InstrumentationEvent<'_>) {
+    match event {
+      InstrumentationEvent::StartQuery { query, .. } => {
+        // Leave out the bind parameters, as they can contain private data
+        let query = query.to_string();
+        let statement = query.split(" -- binds:").next().unwrap_or_default();
+        self.current = Some(info_span!(
+          "db_query",
+          otel.kind = "client",
+          db.system = "postgresql",
+          db.statement = statement,
+          otel.status_code = tracing::field::Empty,
+        ));
+      }
+      InstrumentationEvent::FinishQuery { error, .. } => {
+        // The span ends when it is dropped
+        if let (Some(span), Some(_)) = (self.current.take(), error) {
+          span.record("otel.status_code", "ERROR");
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
 #[derive(Debug)]
 struct NoCertVerifier {}
 
@@ -25,6 +25,7 @@ use url::Url;
 #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Text))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DbUrl(pub Box<Url>);
 
 impl DbUrl {
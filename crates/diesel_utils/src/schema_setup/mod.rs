@@ -10,7 +10,7 @@ use diesel::{
   RunQueryDsl,
   connection::SimpleConnection,
   dsl::exists,
-  migration::{Migration, MigrationVersion},
+  migration::{Migration, MigrationSource, MigrationVersion},
   pg::Pg,
   select,
   update,
@@ -316,6 +316,17 @@ fn run_selected_migrations(
   Ok(())
 }
 
+/// Version of the newest migration which is included in this binary, in the same format as the
+/// `version` column of `__diesel_schema_migrations`.
+pub fn latest_migration_version() -> anyhow::Result<String> {
+  MigrationSource::<Pg>::migrations(&migrations())
+    .map_err(convert_err)?
+    .iter()
+    .map(|m| m.name().version().to_string())
+    .max()
+    .context("No migrations found")
+}
+
 /// Makes `diesel::migration::Result` work with `anyhow` and `LemmyError`
 fn convert_err(e: Box<dyn std::error::Error + Send + Sync>) -> anyhow::Error {
   anyhow!(e)
@@ -8,6 +8,7 @@ use std::{fmt::Debug, ops::Deref};
 #[serde(transparent)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SensitiveString(String);
 
 impl SensitiveString {
@@ -150,6 +150,17 @@ pub fn fuzzy_search(q: &str) -> String {
   format!("%{replaced}%")
 }
 
+/// Pattern for a case insensitive prefix match with `like`, which needs to be compared with a
+/// lowercase column.
+pub fn prefix_search(q: &str) -> String {
+  let replaced = q
+    .to_lowercase()
+    .replace('\\', "\\\\")
+    .replace('%', "\\%")
+    .replace('_', "\\_");
+  format!("{replaced}%")
+}
+
 /// Takes an API optional text input, and converts it to an optional diesel DB update.
 pub fn diesel_string_update(opt: Option<&str>) -> Option<Option<String>> {
   match opt {
@@ -251,6 +262,50 @@ pub mod functions {
   define_sql_function!(#[sql_name = "coalesce"] fn coalesce_2_nullable<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: diesel::sql_types::Nullable<T>) -> diesel::sql_types::Nullable<T>);
 
   define_sql_function!(#[sql_name = "coalesce"] fn coalesce_3_nullable<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: diesel::sql_types::Nullable<T>, z: diesel::sql_types::Nullable<T>) -> diesel::sql_types::Nullable<T>);
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(oid = 3614, array_oid = 3643))]
+  pub struct TsVector;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(oid = 3615, array_oid = 3645))]
+  pub struct TsQuery;
+
+  // The search functions are defined in a migration, and also used by the full-text search
+  // indexes.
+  define_sql_function!(fn search_vector(text: diesel::sql_types::Nullable<Text>) -> TsVector);
+
+  define_sql_function! {
+    #[sql_name = "search_vector"]
+    fn weighted_search_vector(
+      title: diesel::sql_types::Nullable<Text>,
+      body: diesel::sql_types::Nullable<Text>
+    ) -> TsVector;
+  }
+
+  define_sql_function!(fn search_query(query: Text) -> TsQuery);
+
+  define_sql_function!(fn ts_rank_cd(vector: TsVector, query: TsQuery) -> Float);
+
+  define_sql_function! {
+    fn search_headline(text: diesel::sql_types::Nullable<Text>, query: Text) -> Text;
+  }
+
+  diesel::infix_operator!(TsMatch, " @@ ", backend: diesel::pg::Pg);
+
+  // Trigram similarity from the pg_trgm extension, which is also used by the trigram indexes
+  define_sql_function!(fn similarity(a: Text, b: Text) -> Float);
+
+  diesel::infix_operator!(TrigramSimilar, " % ", backend: diesel::pg::Pg);
+
+  // The regular expression variant of substring, which returns the first capture group
+  define_sql_function! {
+    #[sql_name = "substring"]
+    fn regex_substring(
+      text: diesel::sql_types::Nullable<Text>,
+      pattern: Text
+    ) -> diesel::sql_types::Nullable<Text>;
+  }
 }
 
 pub fn now() -> AsExprOf<diesel::dsl::now, diesel::sql_types::Timestamptz> {
@@ -281,6 +336,11 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_prefix_search() {
+    assert_eq!(prefix_search("Lemmy_Dev%"), "lemmy\\_dev\\%%".to_string());
+  }
+
   #[test]
   fn test_diesel_option_overwrite() {
     assert_eq!(diesel_string_update(None), None);
@@ -137,3 +137,103 @@ pub fn send_email_verified_email(
   );
   Ok(())
 }
+
+/// Ask for confirmation of a new email address, and notify the previous address about the change.
+/// The email of the user only changes after the token is confirmed with `verify_email`.
+pub async fn send_email_change_emails(
+  user: &LocalUserView,
+  new_email: SensitiveString,
+  pool: &mut DbPool<'_>,
+  settings: &'static Settings,
+) -> LemmyResult<()> {
+  let form = EmailVerificationForm {
+    local_user_id: user.local_user.id,
+    email: new_email.to_string(),
+    verification_token: uuid::Uuid::new_v4().to_string(),
+  };
+  let verify_link = format!(
+    "{}/verify_email/{}",
+    settings.get_protocol_and_hostname(),
+    &form.verification_token
+  );
+  EmailVerification::create(pool, &form).await?;
+
+  let lang = user_language(&user.local_user);
+  let subject = lang.email_change_confirm_subject(&settings.hostname);
+  let body = lang.email_change_confirm_body(&settings.hostname, &user.person.name, verify_link);
+  send_email(subject, new_email, user.person.name.clone(), body, settings);
+
+  if let Ok(previous_email) = user_email(user) {
+    let subject = lang.email_change_notice_subject(&settings.hostname);
+    let body = lang.email_change_notice_body(&settings.hostname, &user.person.name);
+    send_email(
+      subject,
+      previous_email,
+      user.person.name.clone(),
+      body,
+      settings,
+    );
+  }
+  Ok(())
+}
+
+/// Notify the user that their password was changed, either directly or with a password reset.
+pub fn send_password_changed_email(
+  user: &LocalUserView,
+  settings: &'static Settings,
+) -> LemmyResult<()> {
+  let lang = user_language(&user.local_user);
+  let subject = lang.password_changed_subject(&settings.hostname);
+  let email = user_email(user)?;
+  let body = lang.password_changed_body(&settings.hostname, &user.person.name);
+  send_email(subject, email, user.person.name.clone(), body, settings);
+  Ok(())
+}
+
+/// Notify the user about a login from a new device.
+pub fn send_new_login_email(
+  user: &LocalUserView,
+  ip: Option<&str>,
+  user_agent: Option<&str>,
+  settings: &'static Settings,
+) -> LemmyResult<()> {
+  let lang = user_language(&user.local_user);
+  let subject = lang.new_login_subject(&settings.hostname);
+  let email = user_email(user)?;
+  // Both values are sent by the client, so they must not be interpreted as html
+  let ip = escape_html(ip.unwrap_or("unknown"));
+  let user_agent = escape_html(user_agent.unwrap_or("unknown"));
+  let body = lang.new_login_body(&settings.hostname, ip, user_agent, &user.person.name);
+  send_email(subject, email, user.person.name.clone(), body, settings);
+  Ok(())
+}
+
+pub fn send_login_lockout_email(
+  user: &LocalUserView,
+  ip: Option<&str>,
+  failure_count: i32,
+  lockout_minutes: i64,
+  settings: &'static Settings,
+) -> LemmyResult<()> {
+  let lang = user_language(&user.local_user);
+  let subject = lang.login_lockout_subject(&settings.hostname);
+  let email = user_email(user)?;
+  let ip = escape_html(ip.unwrap_or("unknown"));
+  let body = lang.login_lockout_body(
+    failure_count.to_string(),
+    &settings.hostname,
+    ip,
+    lockout_minutes.to_string(),
+    &user.person.name,
+  );
+  send_email(subject, email, user.person.name.clone(), body, settings);
+  Ok(())
+}
+
+fn escape_html(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
@@ -611,6 +611,16 @@ fn create_modlog_items(
           },
           settings,
         ),
+        ModlogKind::AdminQuarantineCommunity => build_modlog_item(
+          r,
+          &modlog_url,
+          if r.modlog.is_revert {
+            format!("Unquarantined community {}", &target_community_name)
+          } else {
+            format!("Quarantined community {}", &target_community_name)
+          },
+          settings,
+        ),
         ModlogKind::ModRemovePost => build_modlog_item(
           r,
           &modlog_url,
@@ -711,7 +721,7 @@ fn build_item(
   let title = match notification.kind {
     NotificationType::Mention => lang.mention_from_x(creator.name.clone()),
     NotificationType::Reply => lang.reply_from_x(creator.name.clone()),
-    NotificationType::Subscribed => lang.subscribed().to_string(),
+    NotificationType::Subscribed | NotificationType::SavedSearch => lang.subscribed().to_string(),
     NotificationType::PrivateMessage => lang.private_message_from_x(creator.name.clone()),
     NotificationType::ModAction => lang.mod_action().to_string(),
   };
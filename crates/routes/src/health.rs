@@ -0,0 +1,167 @@
+//! Endpoints for orchestrators and load balancers. `/healthz` only checks that the server is
+//! running, while `/readyz` also checks the services which are needed to handle requests.
+
+use actix_web::{
+  HttpResponse,
+  http::StatusCode,
+  web::{self, Data},
+};
+use chrono::{TimeDelta, Utc};
+use diesel::{QueryableByName, sql_query, sql_types::Text};
+use diesel_async::RunQueryDsl;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::federation_queue_state::FederationQueueState;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::{connection::get_conn, schema_setup::latest_migration_version};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::warn;
+
+/// Readiness checks should fail quickly, instead of piling up while a service hangs.
+const PICTRS_HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// If no instance received any activity for this long, the federation queue is stuck.
+const FEDERATION_STUCK_AFTER: TimeDelta = TimeDelta::hours(1);
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+  cfg
+    .route("/healthz", web::get().to(healthz))
+    .route("/readyz", web::get().to(readyz));
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum CheckStatus {
+  Ok,
+  Failed,
+  /// The service isn't configured, or can't be checked because a previous check failed.
+  Skipped,
+}
+
+impl<T> From<LemmyResult<T>> for CheckStatus {
+  fn from(res: LemmyResult<T>) -> Self {
+    match res {
+      Ok(_) => CheckStatus::Ok,
+      Err(e) => {
+        warn!("Readiness check failed: {e}");
+        CheckStatus::Failed
+      }
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct ReadinessResponse {
+  database: CheckStatus,
+  migrations: CheckStatus,
+  pictrs: CheckStatus,
+  federation_queue: CheckStatus,
+}
+
+impl ReadinessResponse {
+  fn is_ready(&self) -> bool {
+    [
+      self.database,
+      self.migrations,
+      self.pictrs,
+      self.federation_queue,
+    ]
+    .iter()
+    .all(|s| *s != CheckStatus::Failed)
+  }
+}
+
+/// Liveness: the server process is running and handles requests.
+async fn healthz() -> HttpResponse {
+  HttpResponse::Ok().body("OK")
+}
+
+/// Readiness: returns 503 if a check fails, so that no requests are routed to this server.
+async fn readyz(context: Data<LemmyContext>) -> HttpResponse {
+  let database = CheckStatus::from(check_database(&context).await);
+  let migrations = if database == CheckStatus::Ok {
+    check_migrations(&context).await.into()
+  } else {
+    CheckStatus::Skipped
+  };
+  let federation_enabled = SiteView::read_local(&mut context.pool())
+    .await
+    .is_ok_and(|s| s.local_site.federation_enabled);
+  let federation_queue = if database == CheckStatus::Ok && federation_enabled {
+    check_federation_queue(&context).await.into()
+  } else {
+    CheckStatus::Skipped
+  };
+  let pictrs = if context.settings().pictrs().is_ok() {
+    check_pictrs(&context).await.into()
+  } else {
+    CheckStatus::Skipped
+  };
+
+  let res = ReadinessResponse {
+    database,
+    migrations,
+    pictrs,
+    federation_queue,
+  };
+  let status = if res.is_ready() {
+    StatusCode::OK
+  } else {
+    StatusCode::SERVICE_UNAVAILABLE
+  };
+  HttpResponse::build(status).json(res)
+}
+
+async fn check_database(context: &LemmyContext) -> LemmyResult<()> {
+  let conn = &mut get_conn(&mut context.pool()).await?;
+  sql_query("SELECT 1").execute(conn).await?;
+  Ok(())
+}
+
+#[derive(QueryableByName)]
+struct MigrationVersion {
+  #[diesel(sql_type = Text)]
+  version: String,
+}
+
+/// The newest migration of this binary was run. During a deploy, servers with the new version
+/// only become ready after the migrations are finished.
+async fn check_migrations(context: &LemmyContext) -> LemmyResult<()> {
+  let latest = latest_migration_version()?;
+  let conn = &mut get_conn(&mut context.pool()).await?;
+  let found = sql_query("SELECT version FROM __diesel_schema_migrations WHERE version = $1")
+    .bind::<Text, _>(&latest)
+    .get_results::<MigrationVersion>(conn)
+    .await?;
+  if found.iter().any(|m| m.version == latest) {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::Unknown(format!("Migration {latest} wasn't run yet")).into())
+  }
+}
+
+async fn check_pictrs(context: &LemmyContext) -> LemmyResult<()> {
+  let pictrs_config = context.settings().pictrs()?;
+  context
+    .pictrs_client()
+    .get(format!("{}healthz", pictrs_config.url))
+    .timeout(PICTRS_HEALTH_TIMEOUT)
+    .send()
+    .await?
+    .error_for_status()?;
+  Ok(())
+}
+
+async fn check_federation_queue(context: &LemmyContext) -> LemmyResult<()> {
+  let oldest_unsent = FederationQueueState::oldest_unsent_activity(&mut context.pool()).await?;
+  match oldest_unsent {
+    Some(published_at) if Utc::now() - published_at > FEDERATION_STUCK_AFTER => Err(
+      LemmyErrorType::Unknown(format!(
+        "Federation queue is stuck, oldest unsent activity from {published_at}"
+      ))
+      .into(),
+    ),
+    _ => Ok(()),
+  }
+}
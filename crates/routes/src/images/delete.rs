@@ -1,8 +1,10 @@
 use super::utils::delete_old_image;
 use actix_web::web::*;
+use chrono::Utc;
 use lemmy_api_utils::{
   context::LemmyContext,
   request::{delete_image_alias, purge_image_from_pictrs},
+  send_activity::{ActivityChannel, SendActivityData},
   utils::{is_admin, is_mod_or_admin},
 };
 use lemmy_db_schema::source::{
@@ -65,9 +67,14 @@ pub async fn delete_community_icon(
 
   let form = CommunityUpdateForm {
     icon: Some(None),
+    updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
-  Community::update(&mut context.pool(), community.id, &form).await?;
+  let community = Community::update(&mut context.pool(), community.id, &form).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
 
   Ok(Json(SuccessResponse::default()))
 }
@@ -80,13 +87,18 @@ pub async fn delete_community_banner(
   let community = Community::read(&mut context.pool(), data.id).await?;
   is_mod_or_admin(&mut context.pool(), &local_user_view, community.id).await?;
 
-  delete_old_image(&community.icon, &context).await?;
+  delete_old_image(&community.banner, &context).await?;
 
   let form = CommunityUpdateForm {
-    icon: Some(None),
+    banner: Some(None),
+    updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
-  Community::update(&mut context.pool(), community.id, &form).await?;
+  let community = Community::update(&mut context.pool(), community.id, &form).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
 
   Ok(Json(SuccessResponse::default()))
 }
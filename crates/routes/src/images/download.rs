@@ -53,17 +53,25 @@ pub async fn image_proxy(
 
   // Check that url corresponds to a federated image so that this can't be abused as a proxy
   // for arbitrary purposes.
-  RemoteImage::validate(&mut context.pool(), url.clone().into()).await?;
+  let remote_image = RemoteImage::read(&mut context.pool(), &url.clone().into()).await?;
 
+  // Serve the permanent copy if there is one, so that the remote instance isn't contacted.
+  let source = match &remote_image.cached_alias {
+    Some(alias) => format!("src={alias}"),
+    None => format!("proxy={encoded_url}"),
+  };
   let pictrs_config = context.settings().pictrs()?;
   let processed_url = if params.file_type.is_none() && params.max_size.is_none() {
-    format!("{}image/original?proxy={}", pictrs_config.url, encoded_url)
+    match &remote_image.cached_alias {
+      Some(alias) => format!("{}image/original/{}", pictrs_config.url, alias),
+      None => format!("{}image/original?proxy={}", pictrs_config.url, encoded_url),
+    }
   } else {
     let file_type = file_type(params.file_type, url.path()).unwrap_or_default();
 
     let mut url = format!(
-      "{}image/process.{}?proxy={}",
-      pictrs_config.url, file_type, encoded_url
+      "{}image/process.{}?{}",
+      pictrs_config.url, file_type, source
     );
 
     if let Some(size) = params.max_size {
@@ -5,6 +5,7 @@ use lemmy_utils::error::LemmyResult;
 
 pub mod delete;
 pub mod download;
+mod policy;
 pub mod upload;
 mod utils;
 
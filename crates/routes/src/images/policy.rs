@@ -0,0 +1,175 @@
+use super::upload::UploadType::{self, *};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  request::{PictrsFile, PictrsResponse, delete_image_alias, fetch_pictrs_file_size},
+};
+use lemmy_db_schema::source::{
+  images::{LocalImage, LocalImageForm},
+  local_site::LocalSite,
+};
+use lemmy_db_schema_file::PersonId;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use std::time::Duration;
+use tracing::warn;
+
+/// Pictrs parameters to downscale images and enforce the upload policy of the site.
+/// https://git.asonix.dog/asonix/pict-rs/#api
+pub(super) fn upload_query(
+  upload_type: &UploadType,
+  local_site: &LocalSite,
+) -> Vec<(&'static str, String)> {
+  let (max_size, allow_animation, allow_video) = match upload_type {
+    Avatar => (local_site.image_max_avatar_size, false, false),
+    Banner => (local_site.image_max_banner_size, false, false),
+    Other => (
+      local_site.image_max_upload_size,
+      local_site.image_allow_animated,
+      local_site.image_allow_video_uploads,
+    ),
+  };
+  let mut query = vec![
+    ("resize", max_size.to_string()),
+    ("allow_animation", allow_animation.to_string()),
+    ("allow_video", allow_video.to_string()),
+  ];
+  if let Some(max_width) = local_site.image_max_width {
+    query.push(("max_width", max_width.to_string()));
+  }
+  if let Some(max_height) = local_site.image_max_height {
+    query.push(("max_height", max_height.to_string()));
+  }
+  query
+}
+
+/// The pictrs output format for re-encoding an upload, or `None` if it should be kept as is.
+/// Animations and videos are never re-encoded.
+fn reencode_format(content_type: &str, local_site: &LocalSite) -> Option<&'static str> {
+  let format = match content_type {
+    "image/jpeg" => "jpg",
+    "image/png" => "png",
+    "image/webp" => "webp",
+    "image/avif" => "avif",
+    "image/jxl" => "jxl",
+    _ => return None,
+  };
+  if local_site.image_convert_to_webp {
+    Some("webp")
+  } else if local_site.image_strip_metadata {
+    Some(format)
+  } else {
+    None
+  }
+}
+
+/// Re-encodes an uploaded image with pictrs if required by the site settings, which removes all
+/// metadata and optionally converts it to webp. Returns the file which should be used, the
+/// original upload is deleted if it was replaced.
+///
+/// The caller is responsible for deleting the upload if this fails.
+pub(super) async fn apply_upload_policy(
+  file: PictrsFile,
+  person_id: PersonId,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<PictrsFile> {
+  let Some(format) = reencode_format(&file.details.content_type, local_site) else {
+    return Ok(file);
+  };
+
+  // Store the processed image as a new upload, so that it replaces the original everywhere
+  let pictrs_url = context.settings().pictrs()?.url;
+  let process_url = format!(
+    "{pictrs_url}image/process.{format}?src={}&identity=true",
+    file.file
+  );
+  let download_url = format!(
+    "{pictrs_url}image/download?url={}",
+    utf8_percent_encode(&process_url, NON_ALPHANUMERIC)
+  );
+  let mut res = context
+    .pictrs_client()
+    .get(&download_url)
+    .timeout(Duration::from_secs(
+      local_site.image_upload_timeout_seconds.try_into()?,
+    ))
+    .send()
+    .await?
+    .error_for_status()?
+    .json::<PictrsResponse>()
+    .await?;
+  let reencoded = res
+    .files
+    .pop()
+    .ok_or(LemmyErrorType::PictrsResponseError(res.msg))?;
+
+  let form = LocalImageForm {
+    pictrs_alias: reencoded.file.clone(),
+    person_id,
+    thumbnail_for_post_id: None,
+    size_bytes: fetch_pictrs_file_size(&reencoded.file, context).await.ok(),
+  };
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  let details_form = reencoded
+    .details
+    .build_image_details_form(&reencoded.image_url(&protocol_and_hostname)?);
+  LocalImage::create(&mut context.pool(), &form, &details_form).await?;
+
+  if let Err(e) = delete_image_alias(&file.file, context).await {
+    warn!(
+      "Failed to delete original of re-encoded image {}: {e}",
+      file.file
+    );
+  }
+  Ok(reencoded)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_upload_query() {
+    let local_site = LocalSite {
+      image_max_avatar_size: 512,
+      image_max_upload_size: 1024,
+      image_allow_animated: false,
+      image_allow_video_uploads: true,
+      image_max_width: Some(4000),
+      ..Default::default()
+    };
+    assert_eq!(
+      vec![
+        ("resize", "512".to_string()),
+        ("allow_animation", "false".to_string()),
+        ("allow_video", "false".to_string()),
+        ("max_width", "4000".to_string()),
+      ],
+      upload_query(&Avatar, &local_site)
+    );
+    assert_eq!(
+      vec![
+        ("resize", "1024".to_string()),
+        ("allow_animation", "false".to_string()),
+        ("allow_video", "true".to_string()),
+        ("max_width", "4000".to_string()),
+      ],
+      upload_query(&Other, &local_site)
+    );
+  }
+
+  #[test]
+  fn test_reencode_format() {
+    let mut local_site = LocalSite::default();
+    assert_eq!(None, reencode_format("image/jpeg", &local_site));
+
+    local_site.image_strip_metadata = true;
+    assert_eq!(Some("jpg"), reencode_format("image/jpeg", &local_site));
+    assert_eq!(None, reencode_format("image/gif", &local_site));
+    assert_eq!(None, reencode_format("video/mp4", &local_site));
+
+    local_site.image_convert_to_webp = true;
+    assert_eq!(Some("webp"), reencode_format("image/png", &local_site));
+  }
+}
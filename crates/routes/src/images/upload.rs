@@ -1,10 +1,17 @@
-use super::utils::{adapt_request, delete_old_image, make_send};
+use super::{
+  policy::{apply_upload_policy, upload_query},
+  utils::{adapt_request, delete_old_image, make_send},
+};
 use UploadType::*;
-use actix_web::{self, HttpRequest, web::*};
+use actix_web::{self, HttpRequest, http::header::CONTENT_LENGTH, web::*};
+use chrono::Utc;
 use lemmy_api_utils::{
+  classifier::classify_upload,
   context::LemmyContext,
-  request::PictrsResponse,
-  utils::{is_admin, is_mod_or_admin},
+  request::{PictrsResponse, delete_image_alias, fetch_pictrs_file_size},
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_new_account_image_upload, image_upload_quota_bytes, is_admin, is_mod_or_admin},
+  video::process_video_upload,
 };
 use lemmy_db_schema::source::{
   community::{Community, CommunityUpdateForm},
@@ -21,6 +28,7 @@ use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 use reqwest::Body;
 use std::time::Duration;
+use tracing::warn;
 
 pub enum UploadType {
   Avatar,
@@ -39,6 +47,7 @@ pub async fn upload_image(
   if local_site.image_upload_disabled {
     return Err(LemmyErrorType::ImageUploadDisabled.into());
   }
+  check_new_account_image_upload(&local_user_view, &local_site)?;
 
   Ok(Json(
     do_upload_image(req, body, Other, &local_user_view, &local_site, &context).await?,
@@ -52,6 +61,7 @@ pub async fn upload_user_avatar(
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<UploadImageResponse>> {
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_new_account_image_upload(&local_user_view, &local_site)?;
 
   let image = do_upload_image(req, body, Avatar, &local_user_view, &local_site, &context).await?;
   delete_old_image(&local_user_view.person.avatar, &context).await?;
@@ -72,6 +82,7 @@ pub async fn upload_user_banner(
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<UploadImageResponse>> {
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_new_account_image_upload(&local_user_view, &local_site)?;
 
   let image = do_upload_image(req, body, Banner, &local_user_view, &local_site, &context).await?;
   delete_old_image(&local_user_view.person.banner, &context).await?;
@@ -93,6 +104,7 @@ pub async fn upload_community_icon(
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<UploadImageResponse>> {
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_new_account_image_upload(&local_user_view, &local_site)?;
 
   let community: Community = Community::read(&mut context.pool(), query.id).await?;
   is_mod_or_admin(&mut context.pool(), &local_user_view, community.id).await?;
@@ -102,9 +114,14 @@ pub async fn upload_community_icon(
 
   let form = CommunityUpdateForm {
     icon: Some(Some(image.image_url.clone().into())),
+    updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
-  Community::update(&mut context.pool(), community.id, &form).await?;
+  let community = Community::update(&mut context.pool(), community.id, &form).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
 
   Ok(Json(image))
 }
@@ -117,6 +134,7 @@ pub async fn upload_community_banner(
   context: Data<LemmyContext>,
 ) -> LemmyResult<Json<UploadImageResponse>> {
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  check_new_account_image_upload(&local_user_view, &local_site)?;
 
   let community: Community = Community::read(&mut context.pool(), query.id).await?;
   is_mod_or_admin(&mut context.pool(), &local_user_view, community.id).await?;
@@ -126,9 +144,14 @@ pub async fn upload_community_banner(
 
   let form = CommunityUpdateForm {
     banner: Some(Some(image.image_url.clone().into())),
+    updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
-  Community::update(&mut context.pool(), community.id, &form).await?;
+  let community = Community::update(&mut context.pool(), community.id, &form).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
 
   Ok(Json(image))
 }
@@ -189,40 +212,17 @@ async fn do_upload_image(
   local_site: &LocalSite,
   context: &Data<LemmyContext>,
 ) -> LemmyResult<UploadImageResponse> {
-  let pictrs_url = context.settings().pictrs()?.url;
-  let max_upload_size = local_site.image_max_upload_size.to_string();
-  let image_url = format!("{}image", pictrs_url);
-
-  let mut client_req = adapt_request(&req, image_url, context);
-
-  // Set pictrs parameters to downscale images and restrict file types.
-  // https://git.asonix.dog/asonix/pict-rs/#api
-  client_req = match upload_type {
-    Avatar => {
-      let max_size = local_site.image_max_avatar_size.to_string();
-      client_req.query(&[
-        ("resize", max_size.as_ref()),
-        ("allow_animation", "false"),
-        ("allow_video", "false"),
-      ])
-    }
-    Banner => {
-      let max_size = local_site.image_max_banner_size.to_string();
-      client_req.query(&[
-        ("resize", max_size.as_ref()),
-        ("allow_animation", "false"),
-        ("allow_video", "false"),
-      ])
-    }
-    Other => {
-      let mut query = vec![(
-        "allow_video",
-        local_site.image_allow_video_uploads.to_string(),
-      )];
-      query.push(("resize", max_upload_size));
-      client_req.query(&query)
-    }
-  };
+  let person_id = local_user_view.person.id;
+  let quota = image_upload_quota_bytes(local_user_view, local_site);
+  if let Some(quota) = quota
+    && LocalImage::used_bytes(&mut context.pool(), person_id).await? >= quota
+  {
+    return Err(LemmyErrorType::UploadQuotaExceeded.into());
+  }
+
+  let image_url = format!("{}image", context.settings().pictrs()?.url);
+  let client_req = adapt_request(&req, image_url, context);
+  let mut client_req = client_req.query(&upload_query(&upload_type, local_site));
   if let Some(addr) = req.head().peer_addr {
     client_req = client_req.header("X-Forwarded-For", addr.to_string())
   };
@@ -247,8 +247,9 @@ async fn do_upload_image(
     // to allow deletion via web ui.
     let form = LocalImageForm {
       pictrs_alias: image.file.clone(),
-      person_id: local_user_view.person.id,
+      person_id,
       thumbnail_for_post_id: None,
+      size_bytes: fetch_pictrs_file_size(&image.file, context).await.ok(),
     };
 
     let protocol_and_hostname = context.settings().get_protocol_and_hostname();
@@ -258,12 +259,60 @@ async fn do_upload_image(
     let details_form = image.details.build_image_details_form(&thumbnail_url);
     LocalImage::create(&mut context.pool(), &form, &details_form).await?;
   }
+
+  // The size is only known after uploading, so remove the files again if they exceed the quota
+  if let Some(quota) = quota
+    && LocalImage::used_bytes(&mut context.pool(), person_id).await? > quota
+  {
+    for image in &images.files {
+      delete_image_alias(&image.file, context).await?;
+    }
+    return Err(LemmyErrorType::UploadQuotaExceeded.into());
+  }
+
   let image = images
     .files
     .pop()
     .ok_or(LemmyErrorType::PictrsInvalidImageUpload(images.msg))?;
 
+  // Strip metadata and convert the image if configured
+  let alias = image.file.clone();
+  let image = match apply_upload_policy(image, person_id, local_site, context).await {
+    Ok(image) => image,
+    Err(e) => {
+      if let Err(delete_err) = delete_image_alias(&alias, context).await {
+        warn!("Failed to delete rejected upload {alias}: {delete_err}");
+      }
+      return Err(e);
+    }
+  };
+
+  // Check limits for videos, and transcode them if configured
+  let alias = image.file.clone();
+  let upload_size = req
+    .headers()
+    .get(CONTENT_LENGTH)
+    .and_then(|v| v.to_str().ok())
+    .and_then(|v| v.parse().ok());
+  let image = match process_video_upload(image, upload_size, person_id, local_site, context).await {
+    Ok(image) => image,
+    Err(e) => {
+      if let Err(delete_err) = delete_image_alias(&alias, context).await {
+        warn!("Failed to delete rejected video {alias}: {delete_err}");
+      }
+      return Err(e);
+    }
+  };
+
   let url = image.image_url(&context.settings().get_protocol_and_hostname())?;
+
+  if let Err(e) = classify_upload(&url, local_site, context).await {
+    if let Err(delete_err) = delete_image_alias(&image.file, context).await {
+      warn!("Failed to delete rejected upload {url}: {delete_err}");
+    }
+    return Err(e);
+  }
+
   Ok(UploadImageResponse {
     image_url: url,
     filename: image.file,
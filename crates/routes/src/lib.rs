@@ -1,4 +1,5 @@
 pub mod feeds;
+pub mod health;
 pub mod images;
 pub mod middleware;
 pub mod nodeinfo;
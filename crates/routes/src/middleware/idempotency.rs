@@ -2,17 +2,21 @@ use actix_web::{
   Error,
   HttpMessage,
   HttpResponse,
-  body::EitherBody,
-  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
-  http::Method,
+  body::{BoxBody, MessageBody, to_bytes},
+  dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+  error::ErrorInternalServerError,
+  http::{Method, StatusCode, header::HeaderMap},
+  web::Bytes,
 };
 use futures_util::future::LocalBoxFuture;
 use lemmy_db_schema::newtypes::LocalUserId;
 use lemmy_db_views_local_user::LocalUserView;
 use std::{
-  collections::HashSet,
+  collections::HashMap,
+  error::Error as StdError,
   future::{Ready, ready},
-  hash::{Hash, Hasher},
+  hash::{DefaultHasher, Hash, Hasher},
+  rc::Rc,
   sync::{Arc, LazyLock, RwLock},
   time::{Duration, Instant},
 };
@@ -20,8 +24,9 @@ use std::{
 /// https://www.ietf.org/archive/id/draft-ietf-httpapi-idempotency-key-header-01.html
 const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
 
-/// Delete idempotency keys older than this
-const CLEANUP_INTERVAL_SECS: u32 = 120;
+/// Delete idempotency keys older than this. Long enough for mobile clients to retry after
+/// the connection comes back.
+const CLEANUP_INTERVAL_SECS: u32 = 600;
 
 /// Smaller than `std::time::Instant` because it uses a smaller integer for seconds and doesn't
 /// store nanoseconds
@@ -42,36 +47,42 @@ impl InstantSecs {
   }
 }
 
+type EntryKey = (LocalUserId, String);
+
 #[derive(Debug)]
 struct Entry {
-  user_id: LocalUserId,
-  key: String,
-  // Creation time is ignored for Eq, Hash and only used to cleanup old entries
+  /// Hash of method, path and body, to detect reuse of a key for a different request
+  request_hash: u64,
+  /// None while the first request is still being handled
+  response: Option<StoredResponse>,
+  // Only used to cleanup old entries
   created: InstantSecs,
 }
 
-impl PartialEq for Entry {
-  fn eq(&self, other: &Self) -> bool {
-    self.user_id == other.user_id && self.key == other.key
-  }
+/// Successful response of the first request, which is returned again for retries.
+#[derive(Debug, Clone)]
+struct StoredResponse {
+  status: StatusCode,
+  headers: HeaderMap,
+  body: Bytes,
 }
-impl Eq for Entry {}
 
-impl Hash for Entry {
-  fn hash<H: Hasher>(&self, state: &mut H) {
-    self.user_id.hash(state);
-    self.key.hash(state);
+impl StoredResponse {
+  fn to_response(&self) -> HttpResponse {
+    let mut res = HttpResponse::with_body(self.status, self.body.clone());
+    *res.headers_mut() = self.headers.clone();
+    res.map_into_boxed_body()
   }
 }
 
 #[derive(Clone)]
 pub struct IdempotencySet {
-  set: Arc<RwLock<HashSet<Entry>>>,
+  set: Arc<RwLock<HashMap<EntryKey, Entry>>>,
 }
 
 impl Default for IdempotencySet {
   fn default() -> Self {
-    let set: Arc<RwLock<HashSet<Entry>>> = Default::default();
+    let set: Arc<RwLock<HashMap<EntryKey, Entry>>> = Default::default();
 
     let set_ = set.clone();
     tokio::spawn(async move {
@@ -85,7 +96,7 @@ impl Default for IdempotencySet {
         let now = InstantSecs::now();
         #[expect(clippy::expect_used)]
         let mut lock = state.write().expect("lock failed");
-        lock.retain(|e| e.created.secs > now.secs.saturating_sub(CLEANUP_INTERVAL_SECS));
+        lock.retain(|_, e| e.created.secs > now.secs.saturating_sub(CLEANUP_INTERVAL_SECS));
         lock.shrink_to_fit();
       }
     });
@@ -93,6 +104,88 @@ impl Default for IdempotencySet {
   }
 }
 
+enum Lookup {
+  New,
+  Replay(StoredResponse),
+  InProgress,
+  Mismatch,
+}
+
+#[expect(clippy::expect_used)]
+impl IdempotencySet {
+  /// Checks for an earlier request with the same key, and otherwise marks the key as in progress.
+  fn lookup_or_insert(&self, key: &EntryKey, request_hash: u64) -> Lookup {
+    let mut lock = self.set.write().expect("lock failed");
+    match lock.get(key) {
+      Some(e) if e.request_hash != request_hash => Lookup::Mismatch,
+      Some(Entry {
+        response: Some(response),
+        ..
+      }) => Lookup::Replay(response.clone()),
+      Some(_) => Lookup::InProgress,
+      None => {
+        lock.insert(
+          key.clone(),
+          Entry {
+            request_hash,
+            response: None,
+            created: InstantSecs::now(),
+          },
+        );
+        Lookup::New
+      }
+    }
+  }
+
+  /// Stores the response of a successful request. Failed requests are removed, so that they can
+  /// be retried with the same key.
+  fn finish(&self, key: &EntryKey, response: Option<StoredResponse>) {
+    let mut lock = self.set.write().expect("lock failed");
+    match response {
+      Some(response) => {
+        if let Some(e) = lock.get_mut(key) {
+          e.response = Some(response);
+        }
+      }
+      None => {
+        lock.remove(key);
+      }
+    }
+  }
+}
+
+/// Marks a key as in progress while the request is handled. If the request fails, or the future
+/// is dropped because the client disconnected, the key is removed so that it can be retried.
+struct InProgressGuard {
+  idempotency_set: IdempotencySet,
+  key: EntryKey,
+  finished: bool,
+}
+
+impl InProgressGuard {
+  fn new(idempotency_set: IdempotencySet, key: EntryKey) -> Self {
+    Self {
+      idempotency_set,
+      key,
+      finished: false,
+    }
+  }
+
+  /// Stores the response of a successful request for retries.
+  fn finish(mut self, response: StoredResponse) {
+    self.idempotency_set.finish(&self.key, Some(response));
+    self.finished = true;
+  }
+}
+
+impl Drop for InProgressGuard {
+  fn drop(&mut self) {
+    if !self.finished {
+      self.idempotency_set.finish(&self.key, None);
+    }
+  }
+}
+
 pub struct IdempotencyMiddleware {
   idempotency_set: IdempotencySet,
 }
@@ -105,11 +198,11 @@ impl IdempotencyMiddleware {
 
 impl<S, B> Transform<S, ServiceRequest> for IdempotencyMiddleware
 where
-  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
   S::Future: 'static,
-  B: 'static,
+  B: MessageBody + 'static,
 {
-  type Response = ServiceResponse<EitherBody<B>>;
+  type Response = ServiceResponse<BoxBody>;
   type Error = Error;
   type InitError = ();
   type Transform = IdempotencyService<S>;
@@ -117,31 +210,30 @@ where
 
   fn new_transform(&self, service: S) -> Self::Future {
     ready(Ok(IdempotencyService {
-      service,
+      service: Rc::new(service),
       idempotency_set: self.idempotency_set.clone(),
     }))
   }
 }
 
 pub struct IdempotencyService<S> {
-  service: S,
+  service: Rc<S>,
   idempotency_set: IdempotencySet,
 }
 
 impl<S, B> Service<ServiceRequest> for IdempotencyService<S>
 where
-  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
   S::Future: 'static,
-  B: 'static,
+  B: MessageBody + 'static,
 {
-  type Response = ServiceResponse<EitherBody<B>>;
+  type Response = ServiceResponse<BoxBody>;
   type Error = Error;
   type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
 
   forward_ready!(service);
 
-  #[expect(clippy::expect_used)]
-  fn call(&self, req: ServiceRequest) -> Self::Future {
+  fn call(&self, mut req: ServiceRequest) -> Self::Future {
     let is_post_or_put = req.method() == Method::POST || req.method() == Method::PUT;
     let idempotency = req
       .headers()
@@ -149,46 +241,121 @@ where
       .map(|i| i.to_str().unwrap_or_default().to_string())
       // Ignore values longer than 32 chars
       .and_then(|i| (i.len() <= 32).then_some(i))
-      // Only use idempotency for POST and PUT requests
-      .and_then(|i| is_post_or_put.then_some(i));
+      // Only use idempotency for POST and PUT requests with json body. Uploads are too large
+      // to buffer the body.
+      .and_then(|i| (is_post_or_put && req.content_type() == "application/json").then_some(i));
 
     let user_id = {
       let ext = req.extensions();
       ext.get().map(|u: &LocalUserView| u.local_user.id)
     };
 
-    if let (Some(key), Some(user_id)) = (idempotency, user_id) {
-      let value = Entry {
-        user_id,
-        key,
-        created: InstantSecs::now(),
+    let svc = self.service.clone();
+    let (Some(key), Some(user_id)) = (idempotency, user_id) else {
+      return Box::pin(async move {
+        svc
+          .call(req)
+          .await
+          .map(ServiceResponse::map_into_boxed_body)
+      });
+    };
+    let idempotency_set = self.idempotency_set.clone();
+
+    Box::pin(async move {
+      // Read the body for the hash, and put it back for the handler
+      let body = req.extract::<Bytes>().await?;
+      let mut hasher = DefaultHasher::new();
+      req.method().hash(&mut hasher);
+      req.path().hash(&mut hasher);
+      body.hash(&mut hasher);
+      req.set_payload(Payload::from(body));
+
+      let key = (user_id, key);
+      let response = match idempotency_set.lookup_or_insert(&key, hasher.finish()) {
+        Lookup::New => None,
+        // Retry of a request which already succeeded, return the same result
+        Lookup::Replay(stored) => Some(stored.to_response()),
+        // Retry while the first request is still running
+        Lookup::InProgress => Some(HttpResponse::Conflict().finish()),
+        // Key was already used for a different request
+        Lookup::Mismatch => Some(HttpResponse::UnprocessableEntity().finish()),
       };
-      if self
-        .idempotency_set
-        .set
-        .read()
-        .expect("lock failed")
-        .contains(&value)
-      {
-        // Duplicate request, return error
+      if let Some(response) = response {
         let (req, _pl) = req.into_parts();
-        let response = HttpResponse::UnprocessableEntity()
-          .finish()
-          .map_into_right_body();
-        return Box::pin(async { Ok(ServiceResponse::new(req, response)) });
-      } else {
-        // New request, store key and continue
-        self
-          .idempotency_set
-          .set
-          .write()
-          .expect("lock failed")
-          .insert(value);
+        return Ok(ServiceResponse::new(req, response));
       }
-    }
 
-    let fut = self.service.call(req);
+      let guard = InProgressGuard::new(idempotency_set, key);
+      let res = svc.call(req).await?;
+      if !res.status().is_success() {
+        return Ok(res.map_into_boxed_body());
+      }
 
-    Box::pin(async move { fut.await.map(ServiceResponse::map_into_left_body) })
+      // Buffer the response so that it can be stored
+      let (req, res) = res.into_parts();
+      let (res, body) = res.into_parts();
+      let body = to_bytes(body)
+        .await
+        .map_err(|e| ErrorInternalServerError(Into::<Box<dyn StdError>>::into(e)))?;
+      let stored = StoredResponse {
+        status: res.status(),
+        headers: res.headers().clone(),
+        body: body.clone(),
+      };
+      guard.finish(stored);
+      let res = res.set_body(body).map_into_boxed_body();
+      Ok(ServiceResponse::new(req, res))
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[tokio::test]
+  async fn test_idempotency_set() {
+    let set = IdempotencySet::default();
+    let key = (LocalUserId(1), "key".to_string());
+    assert!(matches!(set.lookup_or_insert(&key, 1), Lookup::New));
+    assert!(matches!(set.lookup_or_insert(&key, 1), Lookup::InProgress));
+    assert!(matches!(set.lookup_or_insert(&key, 2), Lookup::Mismatch));
+
+    let stored = StoredResponse {
+      status: StatusCode::OK,
+      headers: HeaderMap::new(),
+      body: Bytes::from_static(b"{}"),
+    };
+    set.finish(&key, Some(stored));
+    let Lookup::Replay(replay) = set.lookup_or_insert(&key, 1) else {
+      unreachable!()
+    };
+    assert_eq!(Bytes::from_static(b"{}"), replay.body);
+
+    // Failed requests can be retried with the same key
+    let other_key = (LocalUserId(1), "other".to_string());
+    assert!(matches!(set.lookup_or_insert(&other_key, 1), Lookup::New));
+    set.finish(&other_key, None);
+    assert!(matches!(set.lookup_or_insert(&other_key, 1), Lookup::New));
+  }
+
+  #[tokio::test]
+  async fn test_in_progress_guard() {
+    let set = IdempotencySet::default();
+    let key = (LocalUserId(1), "key".to_string());
+
+    // Dropping the guard without finishing, eg when the client disconnects, removes the key
+    assert!(matches!(set.lookup_or_insert(&key, 1), Lookup::New));
+    drop(InProgressGuard::new(set.clone(), key.clone()));
+    assert!(matches!(set.lookup_or_insert(&key, 1), Lookup::New));
+
+    let stored = StoredResponse {
+      status: StatusCode::OK,
+      headers: HeaderMap::new(),
+      body: Bytes::from_static(b"{}"),
+    };
+    InProgressGuard::new(set.clone(), key.clone()).finish(stored);
+    assert!(matches!(set.lookup_or_insert(&key, 1), Lookup::Replay(_)));
   }
 }
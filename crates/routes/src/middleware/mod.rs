@@ -1,2 +1,7 @@
 pub mod idempotency;
+pub mod previous_domain;
+pub mod request_span;
+pub mod response_size;
 pub mod session;
+pub mod tarpit;
+pub mod token_scope;
@@ -0,0 +1,96 @@
+//! After the instance moved to a new domain, requests to the old domain are redirected to the
+//! same path on the new domain. This keeps links and federated urls working, as long as the old
+//! domain points to this server. The domains only change with the `change-domain` command, which
+//! runs while Lemmy is stopped, so they are loaded once on startup.
+
+use actix_web::{
+  Error,
+  HttpResponse,
+  body::EitherBody,
+  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+  http::header::LOCATION,
+};
+use core::future::Ready;
+use futures_util::future::LocalBoxFuture;
+use lemmy_utils::settings::SETTINGS;
+use std::{future::ready, rc::Rc, sync::Arc};
+
+#[derive(Clone)]
+pub struct PreviousDomainMiddleware {
+  previous_domains: Arc<Vec<String>>,
+}
+
+impl PreviousDomainMiddleware {
+  pub fn new(previous_domains: Arc<Vec<String>>) -> Self {
+    PreviousDomainMiddleware { previous_domains }
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for PreviousDomainMiddleware
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Transform = PreviousDomainService<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(PreviousDomainService {
+      service: Rc::new(service),
+      previous_domains: self.previous_domains.clone(),
+    }))
+  }
+}
+
+pub struct PreviousDomainService<S> {
+  service: Rc<S>,
+  previous_domains: Arc<Vec<String>>,
+}
+
+impl<S, B> Service<ServiceRequest> for PreviousDomainService<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let svc = self.service.clone();
+    let previous_domains = self.previous_domains.clone();
+
+    Box::pin(async move {
+      let host = req.connection_info().host().to_string();
+      let host = host.split(':').next().unwrap_or_default();
+      if previous_domains.iter().any(|d| d == host) {
+        // Permanent redirect which keeps the method and body, so that activities which are
+        // sent to old inbox urls still arrive
+        let location = format!(
+          "{}{}",
+          SETTINGS.get_protocol_and_hostname(),
+          req
+            .uri()
+            .path_and_query()
+            .map(|p| p.as_str())
+            .unwrap_or("/")
+        );
+        let (req, _pl) = req.into_parts();
+        let response = HttpResponse::PermanentRedirect()
+          .insert_header((LOCATION, location))
+          .finish()
+          .map_into_right_body();
+        return Ok(ServiceResponse::new(req, response));
+      }
+
+      svc.call(req).await.map(ServiceResponse::map_into_left_body)
+    })
+  }
+}
@@ -0,0 +1,110 @@
+//! Root span of each request, which is the parent of all logs written while handling it. With
+//! `json_logging` its fields are included in every log line, and a summary is logged when the
+//! request is finished.
+
+use super::token_scope::ScopedLogin;
+use actix_web::{
+  HttpMessage,
+  body::MessageBody,
+  dev::{ServiceRequest, ServiceResponse},
+  web::Data,
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::newtypes::LocalUserId;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::{error::LemmyError, settings::SETTINGS};
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+use tracing::{
+  Span,
+  field::{Empty, display},
+  info,
+};
+use tracing_actix_web::{DefaultRootSpanBuilder, RootSpanBuilder, root_span};
+
+/// Number of hex characters of the user id hash which are logged.
+const USER_ID_HASH_LENGTH: usize = 16;
+
+/// When the request started, to log its latency.
+#[derive(Clone, Copy)]
+struct RequestStart(Instant);
+
+/// Adds the route, a hash of the user id, the error type and the latency to the span of
+/// [DefaultRootSpanBuilder].
+pub struct LemmyRootSpanBuilder;
+
+impl RootSpanBuilder for LemmyRootSpanBuilder {
+  fn on_request_start(request: &ServiceRequest) -> Span {
+    request
+      .extensions_mut()
+      .insert(RequestStart(Instant::now()));
+    // The session middleware runs first, so the user is already known
+    let user_id_hash = {
+      let extensions = request.extensions();
+      let local_user_id = extensions
+        .get::<LocalUserView>()
+        .or(extensions.get::<ScopedLogin>().map(|s| &s.local_user_view))
+        .map(|u| u.local_user.id);
+      local_user_id
+        .zip(request.app_data::<Data<LemmyContext>>())
+        .map(|(id, context)| user_id_hash(id, context.secret().jwt_secret.as_ref()))
+    };
+    root_span!(
+      request,
+      user_id_hash = user_id_hash.as_deref(),
+      error_type = Empty,
+      latency_ms = Empty
+    )
+  }
+
+  fn on_request_end<B: MessageBody>(
+    span: Span,
+    outcome: &Result<ServiceResponse<B>, actix_web::Error>,
+  ) {
+    DefaultRootSpanBuilder::on_request_end(span.clone(), outcome);
+    let Ok(response) = outcome else {
+      return;
+    };
+    if let Some(RequestStart(start)) = response.request().extensions().get::<RequestStart>() {
+      span.record("latency_ms", start.elapsed().as_millis());
+    }
+    let lemmy_error = response
+      .response()
+      .error()
+      .and_then(|e| e.as_error::<LemmyError>());
+    if let Some(e) = lemmy_error {
+      span.record("error_type", display(&e.error_type));
+    }
+    // Replaces the access log of actix, which is only written as plain text
+    if SETTINGS.json_logging {
+      info!(parent: &span, status = response.status().as_u16(), "Request finished");
+    }
+  }
+}
+
+/// Logs of the same user can be correlated, without revealing who the user is. The hash is salted
+/// with the jwt secret, as the ids are easy to guess.
+fn user_id_hash(local_user_id: LocalUserId, secret: &[u8]) -> String {
+  let hash = Sha256::new()
+    .chain_update(secret)
+    .chain_update(local_user_id.0.to_string())
+    .finalize();
+  let mut hash = format!("{hash:x}");
+  hash.truncate(USER_ID_HASH_LENGTH);
+  hash
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::{assert_eq, assert_ne};
+
+  #[test]
+  fn test_user_id_hash() {
+    let hash = user_id_hash(LocalUserId(1), b"secret");
+    assert_eq!(USER_ID_HASH_LENGTH, hash.len());
+    assert_eq!(hash, user_id_hash(LocalUserId(1), b"secret"));
+    assert_ne!(hash, user_id_hash(LocalUserId(2), b"secret"));
+    assert_ne!(hash, user_id_hash(LocalUserId(1), b"other secret"));
+  }
+}
@@ -0,0 +1,75 @@
+//! Logs a warning for responses which are larger than the budget, so that endpoints which
+//! suddenly return much more data are noticed. The size is measured before compression.
+
+use actix_web::{
+  Error,
+  body::{BodySize, MessageBody},
+  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+};
+use core::future::Ready;
+use futures_util::future::LocalBoxFuture;
+use std::{future::ready, rc::Rc};
+use tracing::warn;
+
+/// Api responses are usually much smaller, even a full page of posts is a few hundred KiB.
+const RESPONSE_SIZE_BUDGET: u64 = 1024 * 1024;
+
+#[derive(Clone, Default)]
+pub struct ResponseSizeMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for ResponseSizeMiddleware
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: MessageBody + 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Transform = ResponseSizeService<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(ResponseSizeService {
+      service: Rc::new(service),
+    }))
+  }
+}
+
+pub struct ResponseSizeService<S> {
+  service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ResponseSizeService<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: MessageBody + 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let svc = self.service.clone();
+
+    Box::pin(async move {
+      let res = svc.call(req).await?;
+      // Streamed responses like media and event streams have no known size
+      if let BodySize::Sized(size) = res.response().body().size()
+        && size > RESPONSE_SIZE_BUDGET
+      {
+        let request = res.request();
+        warn!(
+          route = request.match_pattern().as_deref().unwrap_or(request.path()),
+          size,
+          budget = RESPONSE_SIZE_BUDGET,
+          "Response is larger than the budget"
+        );
+      }
+      Ok(res)
+    })
+  }
+}
@@ -1,3 +1,4 @@
+use super::token_scope::ScopedLogin;
 use actix_web::{
   Error,
   HttpMessage,
@@ -9,8 +10,17 @@ use core::future::Ready;
 use futures_util::future::LocalBoxFuture;
 use lemmy_api_utils::{
   context::LemmyContext,
-  utils::{local_user_view_from_jwt, read_auth_token},
+  utils::{
+    local_user_view_and_scope_from_jwt,
+    local_user_view_from_api_key,
+    rate_limit_user_class,
+    read_api_key,
+    read_auth_token,
+  },
 };
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::rate_limit::UserClass;
 use std::{future::ready, rc::Rc};
 
 #[derive(Clone)]
@@ -66,14 +76,41 @@ where
 
     Box::pin(async move {
       let jwt = read_auth_token(req.request())?;
-
-      if let Some(jwt) = &jwt {
+      let api_key = read_api_key(req.request());
+
+      if let Some(api_key) = &api_key {
+        // Unlike an invalid jwt, an invalid api key is rejected. Otherwise bots would silently
+        // act without auth.
+        let (local_user_view, api_key) = local_user_view_from_api_key(api_key, &context).await?;
+        req
+          .extensions_mut()
+          .insert(user_class(&local_user_view, &context).await);
+        req.extensions_mut().insert(ScopedLogin {
+          local_user_view,
+          scope: api_key.scope,
+        });
+      } else if let Some(jwt) = &jwt {
         // Ignore any invalid auth so the site can still be used
         // This means it is be impossible to get any error message for invalid jwt. Need
         // to use `/api/v4/account/validate_auth` for that.
-        let local_user_view = local_user_view_from_jwt(jwt, &context).await.ok();
-        if let Some(local_user_view) = local_user_view {
-          req.extensions_mut().insert(local_user_view);
+        let local_user_view = local_user_view_and_scope_from_jwt(jwt, &context).await.ok();
+        if let Some((local_user_view, _)) = &local_user_view {
+          req
+            .extensions_mut()
+            .insert(user_class(local_user_view, &context).await);
+        }
+        match local_user_view {
+          // Tokens of third-party apps are only valid for endpoints which declare their scope
+          Some((local_user_view, Some(scope))) => {
+            req.extensions_mut().insert(ScopedLogin {
+              local_user_view,
+              scope,
+            });
+          }
+          Some((local_user_view, None)) => {
+            req.extensions_mut().insert(local_user_view);
+          }
+          None => {}
         }
       }
 
@@ -83,7 +120,7 @@ where
       if !res.headers().contains_key(CACHE_CONTROL) {
         // If user is authenticated, mark as private. Otherwise cache
         // up to one minute.
-        let cache_value = if jwt.is_some() {
+        let cache_value = if jwt.is_some() || api_key.is_some() {
           "private"
         } else {
           "public, max-age=60"
@@ -97,6 +134,15 @@ where
   }
 }
 
+/// Stored in the request extensions, so that the rate limits of the user class apply.
+async fn user_class(local_user_view: &LocalUserView, context: &LemmyContext) -> UserClass {
+  let local_site = SiteView::read_local(&mut context.pool())
+    .await
+    .ok()
+    .map(|s| s.local_site);
+  rate_limit_user_class(local_user_view, local_site.as_ref())
+}
+
 #[cfg(test)]
 mod tests {
 
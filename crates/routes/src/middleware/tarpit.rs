@@ -0,0 +1,124 @@
+//! Slows down and eventually blocks clients which keep hitting rate limits or failing to log in.
+//! The penalties themselves are tracked by [RateLimit].
+
+use actix_web::{
+  Error,
+  ResponseError,
+  body::EitherBody,
+  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+  http::{
+    StatusCode,
+    header::{HeaderValue, RETRY_AFTER},
+  },
+  rt::time::sleep,
+};
+use chrono::Utc;
+use core::future::Ready;
+use futures_util::future::LocalBoxFuture;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType},
+  rate_limit::{Penalty, RateLimit, Violation},
+};
+use std::{future::ready, rc::Rc};
+
+#[derive(Clone)]
+pub struct TarpitMiddleware {
+  rate_limit: RateLimit,
+}
+
+impl TarpitMiddleware {
+  pub fn new(rate_limit: RateLimit) -> Self {
+    TarpitMiddleware { rate_limit }
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for TarpitMiddleware
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Transform = TarpitService<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(TarpitService {
+      service: Rc::new(service),
+      rate_limit: self.rate_limit.clone(),
+    }))
+  }
+}
+
+pub struct TarpitService<S> {
+  service: Rc<S>,
+  rate_limit: RateLimit,
+}
+
+impl<S, B> Service<ServiceRequest> for TarpitService<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let svc = self.service.clone();
+    let rate_limit = self.rate_limit.clone();
+
+    Box::pin(async move {
+      let ip = req
+        .connection_info()
+        .realip_remote_addr()
+        .map(ToString::to_string);
+      match rate_limit.check_penalty(ip.as_deref()) {
+        Penalty::Blocked(blocked_until) => {
+          let (req, _pl) = req.into_parts();
+          let mut response = LemmyError::from(LemmyErrorType::TooManyRequests).error_response();
+          let retry_after = (blocked_until - Utc::now()).num_seconds().max(1);
+          response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+          return Ok(ServiceResponse::new(req, response.map_into_right_body()));
+        }
+        Penalty::Delay(delay) => sleep(delay).await,
+        Penalty::None => {}
+      }
+
+      let res = svc.call(req).await;
+      let violation = match &res {
+        Ok(res) => violation(
+          res.status(),
+          res
+            .response()
+            .error()
+            .and_then(|e| e.as_error::<LemmyError>()),
+        ),
+        Err(e) => violation(e.as_response_error().status_code(), e.as_error()),
+      };
+      if let Some(violation) = violation {
+        rate_limit.record_violation(ip.as_deref(), violation);
+      }
+      res.map(ServiceResponse::map_into_left_body)
+    })
+  }
+}
+
+fn violation(status: StatusCode, error: Option<&LemmyError>) -> Option<Violation> {
+  if status == StatusCode::TOO_MANY_REQUESTS {
+    return Some(Violation::RateLimited);
+  }
+  match error.map(|e| &e.error_type) {
+    Some(LemmyErrorType::IncorrectLogin | LemmyErrorType::IncorrectTotpToken) => {
+      Some(Violation::AuthFailure)
+    }
+    _ => None,
+  }
+}
@@ -0,0 +1,172 @@
+//! Tokens of third-party apps and api keys are limited to a scope. Endpoints declare the scope
+//! which they require by wrapping them in [TokenScopeMiddleware], and scoped tokens are ignored
+//! by endpoints which don't.
+
+use actix_web::{
+  Error,
+  HttpMessage,
+  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+  http::Method,
+  web::Data,
+};
+use core::future::Ready;
+use futures_util::future::LocalBoxFuture;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_moderator_totp_enabled, check_token_scope},
+};
+use lemmy_db_schema_file::enums::TokenScope;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
+use std::{future::ready, rc::Rc};
+
+/// Login with a scoped token, which is stored by the session middleware. It only becomes a
+/// [LocalUserView] for endpoints which allow the scope.
+#[derive(Clone)]
+pub(super) struct ScopedLogin {
+  pub(super) local_user_view: LocalUserView,
+  pub(super) scope: TokenScope,
+}
+
+/// Declares the scope which is required to use the wrapped endpoints. When nested, the scopes
+/// of all wrappers are checked. Regular logins can use all endpoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenScopeMiddleware {
+  /// Required scope for GET requests, `None` if only a regular login can be used
+  read: Option<TokenScope>,
+  /// Required scope for all other requests
+  write: Option<TokenScope>,
+}
+
+impl TokenScopeMiddleware {
+  /// Read scope for GET requests, and write scope for everything else.
+  pub fn by_method() -> Self {
+    TokenScopeMiddleware {
+      read: Some(TokenScope::Read),
+      write: Some(TokenScope::Write),
+    }
+  }
+
+  /// Read scope for GET requests, and moderate scope for everything else.
+  pub fn moderate_changes() -> Self {
+    TokenScopeMiddleware {
+      read: Some(TokenScope::Read),
+      write: Some(TokenScope::Moderate),
+    }
+  }
+
+  pub fn moderate() -> Self {
+    TokenScopeMiddleware {
+      read: Some(TokenScope::Moderate),
+      write: Some(TokenScope::Moderate),
+    }
+  }
+
+  /// For authentication and account security, which third-party apps can't access at all.
+  pub fn login_only() -> Self {
+    TokenScopeMiddleware {
+      read: None,
+      write: None,
+    }
+  }
+
+  fn required(&self, method: &Method) -> Option<TokenScope> {
+    if method == Method::GET {
+      self.read
+    } else {
+      self.write
+    }
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for TokenScopeMiddleware
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Transform = TokenScopeService<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(TokenScopeService {
+      service: Rc::new(service),
+      config: *self,
+    }))
+  }
+}
+
+pub struct TokenScopeService<S> {
+  service: Rc<S>,
+  config: TokenScopeMiddleware,
+}
+
+impl<S, B> Service<ServiceRequest> for TokenScopeService<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  S::Future: 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let svc = self.service.clone();
+    let required = self.config.required(req.method());
+
+    Box::pin(async move {
+      let scoped_login = req.extensions().get::<ScopedLogin>().cloned();
+      if let Some(scoped_login) = scoped_login {
+        check_token_scope(Some(scoped_login.scope), required)?;
+        req.extensions_mut().insert(scoped_login.local_user_view);
+      }
+
+      if required == Some(TokenScope::Moderate) {
+        let local_user_view = req.extensions().get::<LocalUserView>().cloned();
+        if let Some(local_user_view) = local_user_view {
+          let context = req
+            .app_data::<Data<LemmyContext>>()
+            .ok_or(LemmyError::from(LemmyErrorType::Unknown(
+              "missing context".into(),
+            )))?;
+          check_moderator_totp_enabled(&local_user_view, context).await?;
+        }
+      }
+
+      svc.call(req).await
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::TokenScopeMiddleware;
+  use actix_web::http::Method;
+  use lemmy_db_schema_file::enums::TokenScope::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_required_scope() {
+    let by_method = TokenScopeMiddleware::by_method();
+    assert_eq!(Some(Read), by_method.required(&Method::GET));
+    assert_eq!(Some(Write), by_method.required(&Method::POST));
+    assert_eq!(Some(Write), by_method.required(&Method::DELETE));
+
+    let moderate_changes = TokenScopeMiddleware::moderate_changes();
+    assert_eq!(Some(Read), moderate_changes.required(&Method::GET));
+    assert_eq!(Some(Moderate), moderate_changes.required(&Method::PUT));
+
+    let moderate = TokenScopeMiddleware::moderate();
+    assert_eq!(Some(Moderate), moderate.required(&Method::GET));
+
+    let login_only = TokenScopeMiddleware::login_only();
+    assert_eq!(None, login_only.required(&Method::GET));
+    assert_eq!(None, login_only.required(&Method::POST));
+  }
+}
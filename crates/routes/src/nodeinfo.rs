@@ -1,14 +1,15 @@
 use actix_web::{Error, HttpResponse, Result, web};
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_db_schema_file::enums::RegistrationMode;
+use lemmy_db_schema::{source::local_site_rate_limit::LocalSiteRateLimit, utils::FETCH_LIMIT_MAX};
+use lemmy_db_schema_file::enums::{FederationMode, RegistrationMode};
 use lemmy_db_views_site::SiteView;
 use lemmy_utils::{
   VERSION,
   cache_header::{cache_1hour, cache_3days},
   error::LemmyResult,
+  utils::validation::{BODY_MAX_LENGTH, POST_BODY_MAX_LENGTH, POST_TITLE_MAX_LENGTH},
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use url::Url;
 
 /// A description of the nodeinfo endpoint is here:
@@ -26,6 +27,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
     .route(
       "/.well-known/nodeinfo",
       web::get().to(node_info_well_known).wrap(cache_3days()),
+    )
+    .route(
+      "/.well-known/lemmy",
+      web::get().to(lemmy_well_known).wrap(cache_1hour()),
     );
 }
 
@@ -44,10 +49,21 @@ async fn node_info_well_known(context: web::Data<LemmyContext>) -> LemmyResult<H
 
 async fn node_info(context: web::Data<LemmyContext>) -> Result<HttpResponse, Error> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_site = site_view.local_site;
 
   // Since there are 3 registration options,
   // we need to set open_registrations as true if RegistrationMode is not Closed.
-  let open_registrations = Some(site_view.local_site.registration_mode != RegistrationMode::Closed);
+  let open_registrations = Some(local_site.registration_mode != RegistrationMode::Closed);
+  // Private instances don't reveal how active they are
+  let (active_halfyear, active_month) = if local_site.private_instance {
+    (None, None)
+  } else {
+    (
+      Some(local_site.users_active_half_year),
+      Some(local_site.users_active_month),
+    )
+  };
+  let federates = |mode| local_site.federation_enabled && mode == FederationMode::All;
   let json = NodeInfo {
     version: Some("2.1".to_string()),
     software: Some(NodeInfoSoftware {
@@ -59,24 +75,60 @@ async fn node_info(context: web::Data<LemmyContext>) -> Result<HttpResponse, Err
     protocols: Some(vec!["activitypub".to_string()]),
     usage: Some(NodeInfoUsage {
       users: Some(NodeInfoUsers {
-        total: Some(site_view.local_site.users),
-        active_halfyear: Some(site_view.local_site.users_active_half_year),
-        active_month: Some(site_view.local_site.users_active_month),
+        total: Some(local_site.users),
+        active_halfyear,
+        active_month,
       }),
-      local_posts: Some(site_view.local_site.posts),
-      local_comments: Some(site_view.local_site.comments),
+      local_posts: Some(local_site.posts),
+      local_comments: Some(local_site.comments),
     }),
     open_registrations,
     services: Some(NodeInfoServices {
       inbound: Some(vec![]),
       outbound: Some(vec![]),
     }),
-    metadata: Some(HashMap::new()),
+    metadata: Some(NodeInfoMetadata {
+      node_name: Some(site_view.site.name),
+      node_description: site_view.site.summary,
+      federation: Some(NodeInfoFederation {
+        enabled: local_site.federation_enabled,
+        post_upvotes: federates(local_site.post_upvotes),
+        post_downvotes: federates(local_site.post_downvotes),
+        comment_upvotes: federates(local_site.comment_upvotes),
+        comment_downvotes: federates(local_site.comment_downvotes),
+      }),
+    }),
   };
 
   Ok(HttpResponse::Ok().json(json))
 }
 
+/// Describes the API of this instance, so that clients can configure themselves without
+/// hardcoding limits for each Lemmy version.
+async fn lemmy_well_known(context: web::Data<LemmyContext>) -> LemmyResult<HttpResponse> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let rate_limit = &site_view.local_site_rate_limit;
+  let json = LemmyWellKnown {
+    version: VERSION.to_string(),
+    api: vec![LemmyWellKnownApi {
+      version: "v4".to_string(),
+      url: Url::parse(&format!(
+        "{}/api/v4",
+        context.settings().get_protocol_and_hostname()
+      ))?,
+    }],
+    limits: LemmyWellKnownLimits {
+      post_title_max_length: POST_TITLE_MAX_LENGTH,
+      post_body_max_length: POST_BODY_MAX_LENGTH,
+      comment_max_length: BODY_MAX_LENGTH,
+      page_size_default: site_view.local_site.default_items_per_page,
+      page_size_max: FETCH_LIMIT_MAX,
+      rate_limits: LemmyWellKnownRateLimits::new(rate_limit),
+    },
+  };
+  Ok(HttpResponse::Ok().json(json))
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct NodeInfoWellKnown {
   pub links: Vec<NodeInfoWellKnownLinks>,
@@ -101,7 +153,7 @@ pub(crate) struct NodeInfo {
   pub open_registrations: Option<bool>,
   /// These fields are required by the spec for no reason
   pub services: Option<NodeInfoServices>,
-  pub metadata: Option<HashMap<String, String>>,
+  pub metadata: Option<NodeInfoMetadata>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -143,3 +195,93 @@ pub(crate) struct NodeInfoServices {
   pub inbound: Option<Vec<String>>,
   pub outbound: Option<Vec<String>>,
 }
+
+/// Free-form in the spec, these fields are also used by Mastodon and others.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub(crate) struct NodeInfoMetadata {
+  pub node_name: Option<String>,
+  pub node_description: Option<String>,
+  pub federation: Option<NodeInfoFederation>,
+}
+
+/// Which activities this instance sends to other instances.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub(crate) struct NodeInfoFederation {
+  pub enabled: bool,
+  pub post_upvotes: bool,
+  pub post_downvotes: bool,
+  pub comment_upvotes: bool,
+  pub comment_downvotes: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub(crate) struct LemmyWellKnown {
+  pub version: String,
+  pub api: Vec<LemmyWellKnownApi>,
+  pub limits: LemmyWellKnownLimits,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub(crate) struct LemmyWellKnownApi {
+  pub version: String,
+  #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  pub url: Url,
+}
+
+/// Maximum lengths are in characters.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub(crate) struct LemmyWellKnownLimits {
+  pub post_title_max_length: usize,
+  pub post_body_max_length: usize,
+  pub comment_max_length: usize,
+  pub page_size_default: i32,
+  pub page_size_max: usize,
+  pub rate_limits: LemmyWellKnownRateLimits,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub(crate) struct LemmyWellKnownRateLimits {
+  pub post: LemmyWellKnownRateLimit,
+  pub comment: LemmyWellKnownRateLimit,
+  pub message: LemmyWellKnownRateLimit,
+  pub image: LemmyWellKnownRateLimit,
+  pub search: LemmyWellKnownRateLimit,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub(crate) struct LemmyWellKnownRateLimit {
+  pub max_requests: i32,
+  pub interval_seconds: i32,
+}
+
+impl LemmyWellKnownRateLimits {
+  fn new(r: &LocalSiteRateLimit) -> Self {
+    let limit = |max_requests, interval_seconds| LemmyWellKnownRateLimit {
+      max_requests,
+      interval_seconds,
+    };
+    Self {
+      post: limit(r.post_max_requests, r.post_interval_seconds),
+      comment: limit(r.comment_max_requests, r.comment_interval_seconds),
+      message: limit(r.message_max_requests, r.message_interval_seconds),
+      image: limit(r.image_max_requests, r.image_interval_seconds),
+      search: limit(r.search_max_requests, r.search_interval_seconds),
+    }
+  }
+}
@@ -1,6 +1,6 @@
 use crate::nodeinfo::{NodeInfo, NodeInfoWellKnown};
-use activitypub_federation::config::Data;
-use chrono::{DateTime, TimeZone, Utc};
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use chrono::{DateTime, Days, TimeDelta, TimeZone, Utc};
 use clokwerk::{AsyncScheduler, TimeUnits as CTimeUnits};
 use diesel::{
   BoolExpressionMethods,
@@ -16,35 +16,62 @@ use diesel::{
 };
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use diesel_uplete::uplete;
+use futures::StreamExt;
 use lemmy_api_utils::{
   context::LemmyContext,
+  notify::notify_saved_searches,
+  request::delete_image_alias,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::send_webmention,
+  stream::StreamChannel,
+  utils::{delete_user_account, send_webmention},
+  webhook::{deliver_pending_webhooks, send_webhook_event},
 };
+use lemmy_apub_objects::objects::{community::ApubCommunity, person::ApubPerson};
 use lemmy_db_schema::{
+  newtypes::PostId,
   source::{
-    community::Community,
+    community::{Community, CommunityUpdateForm},
+    community_recurring_post::CommunityRecurringPost,
+    community_trend::CommunityTrend,
+    images::LocalImage,
     instance::{Instance, InstanceForm},
     local_user::LocalUser,
-    post::{Post, PostUpdateForm},
+    login_failure::LoginFailure,
+    login_token::LoginToken,
+    modlog::{Modlog, ModlogInsertForm},
+    oauth_authorization_code::OAuthAuthorizationCode,
+    person::{Person, PersonUpdateForm},
+    post::{Post, PostInsertForm, PostUpdateForm},
+    post_feature_schedule::PostFeatureSchedule,
+    pow_challenge::PowChallenge,
+    rate_limit_bucket::RateLimitBucket,
+    remote_community_directory::{RemoteCommunityDirectory, RemoteCommunityDirectoryForm},
+    site_stats::SiteStats,
+    webauthn_challenge::WebauthnChallenge,
+    webhook::WebhookDelivery,
   },
   utils::DELETED_REPLACEMENT_TEXT,
 };
-use lemmy_db_schema_file::schema::{
-  comment,
-  community,
-  community_actions,
-  federation_blocklist,
-  instance,
-  instance_actions,
-  local_site,
-  local_user,
-  person,
-  post,
-  received_activity,
-  sent_activity,
-  site,
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::WebhookEvent,
+  schema::{
+    comment,
+    community,
+    community_actions,
+    federation_blocklist,
+    instance,
+    instance_actions,
+    local_site,
+    local_user,
+    person,
+    post,
+    received_activity,
+    sent_activity,
+    site,
+  },
 };
+use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -56,8 +83,9 @@ use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
 };
 use reqwest_middleware::ClientWithMiddleware;
+use serde::Deserialize;
 use std::time::Duration;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 /// Schedules various cleanup tasks for lemmy in a background thread
 pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
@@ -65,7 +93,8 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   let mut scheduler = AsyncScheduler::with_tz(Utc);
 
   let context_1 = context.clone();
-  // Every 10 minutes update hot ranks, delete expired captchas and publish scheduled posts
+  // Every 10 minutes update hot ranks, publish scheduled posts, feature and unfeature posts as
+  // scheduled, notify about new matches for saved searches and retry failed webhook deliveries
   scheduler.every(CTimeUnits::minutes(10)).run(move || {
     let context = context_1.clone();
 
@@ -78,14 +107,39 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to publish scheduled posts: {e}"))
         .ok();
+      update_scheduled_features(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to update scheduled features: {e}"))
+        .ok();
+      create_recurring_posts(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to create recurring posts: {e}"))
+        .ok();
+      notify_saved_searches(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to notify saved searches: {e}"))
+        .ok();
+      deliver_pending_webhooks(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to deliver webhooks: {e}"))
+        .ok();
     }
   });
 
   let context_1 = context.clone();
   // Hourly tasks:
   // - Update active daily counts
+  // - Trending communities
+  // - Daily statistics for the admin dashboard
   // - Expired bans
   // - Expired instance blocks
+  // - Expired OAuth authorization codes
+  // - Expired passkey challenges
+  // - Expired rate limit buckets
+  // - Accounts after their deletion grace period
+  // - Software of newly discovered instances
+  // - Refresh stale remote actors
+  // - Mark unreachable instances as dead, and check if dead ones are back online
   scheduler.every(CTimeUnits::hour(1)).run(move || {
     let context = context_1.clone();
 
@@ -94,6 +148,14 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to update active counts: {e}"))
         .ok();
+      CommunityTrend::refresh(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to update trending communities: {e}"))
+        .ok();
+      update_site_stats(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to update site stats: {e}"))
+        .ok();
       update_banned_when_expired(&mut context.pool())
         .await
         .inspect_err(|e| warn!("Failed to update expired bans: {e}"))
@@ -102,6 +164,46 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to delete expired instance bans: {e}"))
         .ok();
+      OAuthAuthorizationCode::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired authorization codes: {e}"))
+        .ok();
+      WebauthnChallenge::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired passkey challenges: {e}"))
+        .ok();
+      PowChallenge::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired proof of work challenges: {e}"))
+        .ok();
+      RateLimitBucket::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired rate limit buckets: {e}"))
+        .ok();
+      LoginFailure::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired login failures: {e}"))
+        .ok();
+      LoginToken::delete_expired_refresh_tokens(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired refresh tokens: {e}"))
+        .ok();
+      delete_accounts_after_grace_period(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to delete accounts after grace period: {e}"))
+        .ok();
+      update_new_instance_software(&mut context.pool(), context.client())
+        .await
+        .inspect_err(|e| warn!("Failed to update software of new instances: {e}"))
+        .ok();
+      refresh_stale_remote_actors(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to refresh stale remote actors: {e}"))
+        .ok();
+      update_dead_instances(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to update dead instances: {e}"))
+        .ok();
     }
   });
 
@@ -111,8 +213,12 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // - Update local user count
   // - Overwrite deleted & removed posts and comments every day
   // - Delete old denied users
+  // - Clear old registration ips
   // - Update instance software
+  // - Crawl communities of linked instances for the directory
   // - Delete old outgoing activities
+  // - Delete orphaned uploads
+  // - Delete old webhook deliveries
   scheduler.every(CTimeUnits::days(1)).run(move || {
     let context = context_1.reset_request_count();
 
@@ -133,14 +239,30 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to delete old denied users: {e}"))
         .ok();
+      clear_old_registration_ips(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to clear old registration ips: {e}"))
+        .ok();
       update_instance_software(&mut context.pool(), context.client())
         .await
         .inspect_err(|e| warn!("Failed to update instance software: {e}"))
         .ok();
+      crawl_remote_community_directory(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to crawl remote community directory: {e}"))
+        .ok();
       clear_old_activities(&mut context.pool())
         .await
         .inspect_err(|e| warn!("Failed to clear old activities: {e}"))
         .ok();
+      delete_orphaned_images(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to delete orphaned images: {e}"))
+        .ok();
+      WebhookDelivery::delete_old(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete old webhook deliveries: {e}"))
+        .ok();
     }
   });
 
@@ -151,6 +273,15 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   }
 }
 
+/// Recalculates the statistics of the current day, and of the previous day so that it is complete
+/// after midnight.
+async fn update_site_stats(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  let today = Utc::now().date_naive();
+  SiteStats::refresh(pool, today - Days::new(1)).await?;
+  SiteStats::refresh(pool, today).await?;
+  Ok(())
+}
+
 /// Update the hot_rank columns for the aggregates tables
 /// Runs in batches until all necessary rows are updated once
 async fn update_hot_ranks(pool: &mut DbPool<'_>) -> LemmyResult<()> {
@@ -592,20 +723,195 @@ async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()
     };
     Post::update(&mut context.pool(), post.id, &form).await?;
 
-    // send out post via federation and webmention
+    // send out post via federation, webhooks and webmention
     let send_activity = SendActivityData::CreatePost(post.clone());
     ActivityChannel::submit_activity(send_activity, context)?;
+    send_webhook_event(WebhookEvent::PostCreated, &post, context);
+    StreamChannel::post_created(&post);
     send_webmention(post, &community);
   }
   Ok(())
 }
 
+/// Features and unfeatures posts in their community as scheduled by mods.
+async fn update_scheduled_features(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  for schedule in PostFeatureSchedule::list_due_unfeatures(pool).await? {
+    unfeature_scheduled_post(&schedule, context)
+      .await
+      .inspect_err(|e| warn!("Failed to unfeature post {}: {e}", schedule.post_id))
+      .ok();
+  }
+
+  for schedule in PostFeatureSchedule::list_due_features(pool).await? {
+    feature_scheduled_post(&schedule, context)
+      .await
+      .inspect_err(|e| warn!("Failed to feature post {}: {e}", schedule.post_id))
+      .ok();
+  }
+  Ok(())
+}
+
+async fn unfeature_scheduled_post(
+  schedule: &PostFeatureSchedule,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  set_featured_community(schedule.post_id, schedule.scheduled_by, false, context).await?;
+  PostFeatureSchedule::delete(&mut context.pool(), schedule.post_id).await?;
+  Ok(())
+}
+
+async fn feature_scheduled_post(
+  schedule: &PostFeatureSchedule,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let post = set_featured_community(schedule.post_id, schedule.scheduled_by, true, context).await?;
+  // Only one post of the rotation stays featured
+  if schedule.rotation_order.is_some() {
+    let previous =
+      PostFeatureSchedule::list_featured_rotation(pool, post.community_id, post.id).await?;
+    for p in previous {
+      set_featured_community(p.post_id, schedule.scheduled_by, false, context).await?;
+      PostFeatureSchedule::delete(pool, p.post_id).await?;
+    }
+  }
+  if schedule.unfeature_at.is_none() && schedule.rotation_order.is_none() {
+    PostFeatureSchedule::delete(pool, post.id).await?;
+  } else {
+    PostFeatureSchedule::mark_featured(pool, post.id).await?;
+  }
+  Ok(())
+}
+
+/// Creates the recurring posts of communities which are due, and schedules the next ones.
+async fn create_recurring_posts(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  for recurring in CommunityRecurringPost::list_due(&mut context.pool()).await? {
+    create_recurring_post(&recurring, context)
+      .await
+      .inspect_err(|e| warn!("Failed to create recurring post {}: {e}", recurring.id.0))
+      .ok();
+  }
+  Ok(())
+}
+
+async fn create_recurring_post(
+  recurring: &CommunityRecurringPost,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let form = PostInsertForm {
+    body: recurring.body.clone(),
+    ..PostInsertForm::new(
+      recurring.post_title(),
+      recurring.creator_id,
+      recurring.community_id,
+    )
+  };
+  let next_post_at = recurring.following_post_at(Utc::now());
+  let post = CommunityRecurringPost::create_post(pool, recurring.id, &form, next_post_at).await?;
+
+  let community = Community::read(pool, post.community_id).await?;
+  ActivityChannel::submit_activity(SendActivityData::CreatePost(post.clone()), context)?;
+  send_webhook_event(WebhookEvent::PostCreated, &post, context);
+  StreamChannel::post_created(&post);
+  send_webmention(post.clone(), &community);
+
+  if recurring.feature {
+    if let Some(last_post_id) = recurring.last_post_id
+      && Post::read(pool, last_post_id).await?.featured_community
+    {
+      set_featured_community(last_post_id, recurring.creator_id, false, context).await?;
+    }
+    set_featured_community(post.id, recurring.creator_id, true, context).await?;
+  }
+  Ok(())
+}
+
+/// Same as a mod featuring the post in the community, with the mod who scheduled it as actor.
+async fn set_featured_community(
+  post_id: PostId,
+  mod_id: PersonId,
+  featured: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Post> {
+  let pool = &mut context.pool();
+  let form = PostUpdateForm {
+    featured_community: Some(featured),
+    ..Default::default()
+  };
+  let post = Post::update(pool, post_id, &form).await?;
+  let modlog_form = ModlogInsertForm::mod_feature_post_community(mod_id, &post, featured);
+  Modlog::create(pool, &[modlog_form]).await?;
+
+  let actor = Person::read(pool, mod_id).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::FeaturePost(post.clone(), actor, featured),
+    context,
+  )?;
+  Ok(post)
+}
+
+/// Remove the registration ips which are older than the retention period of the site.
+async fn clear_old_registration_ips(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  let retention_days = SiteView::read_local(pool)
+    .await?
+    .local_site
+    .registration_ip_retention_days;
+  let cleared = LocalUser::clear_old_registration_ips(pool, retention_days).await?;
+  info!("Cleared {cleared} old registration ips.");
+  Ok(())
+}
+
+/// Delete uploads which aren't used anywhere anymore, if enabled in the config. Only handles one
+/// batch per run, so that a large backlog doesn't keep pictrs busy for too long.
+async fn delete_orphaned_images(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pictrs_config = context.settings().pictrs()?;
+  if !pictrs_config.delete_orphaned_images {
+    return Ok(());
+  }
+  let published_before =
+    Utc::now() - TimeDelta::days(pictrs_config.orphaned_image_grace_days.into());
+  let images =
+    LocalImage::list_orphaned(&mut context.pool(), published_before, DB_BATCH_SIZE).await?;
+
+  let mut deleted = 0;
+  for image in images {
+    match delete_image_alias(&image.pictrs_alias, context).await {
+      Ok(()) => deleted += 1,
+      Err(e) => warn!(
+        "Failed to delete orphaned image {}: {e}",
+        image.pictrs_alias
+      ),
+    }
+  }
+  info!("Deleted {deleted} orphaned images.");
+  Ok(())
+}
+
+/// Erase the accounts whose deletion grace period is over, and federate the deletion.
+async fn delete_accounts_after_grace_period(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let local_user_ids = LocalUser::list_deletion_due(&mut context.pool()).await?;
+
+  for local_user_id in local_user_ids {
+    let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
+    let delete_content = local_user_view.local_user.deletion_delete_content;
+    let name = local_user_view.person.name.clone();
+    info!("Deleting account {name} after grace period");
+    delete_user_account(local_user_view, delete_content, context)
+      .await
+      .inspect_err(|e| warn!("Failed to delete account {name}: {e}"))
+      .ok();
+  }
+  Ok(())
+}
+
 /// Updates the instance software and version.
 ///
 /// Does so using the /.well-known/nodeinfo protocol described here:
 /// https://github.com/jhass/nodeinfo/blob/main/PROTOCOL.md
 ///
-/// TODO: if instance has been dead for a long time, it should be checked less frequently
+/// Dead instances are skipped, they are checked less frequently by [update_dead_instances].
 async fn update_instance_software(
   pool: &mut DbPool<'_>,
   client: &ClientWithMiddleware,
@@ -613,7 +919,10 @@ async fn update_instance_software(
   info!("Updating instances software and versions...");
   let conn = &mut get_conn(pool).await?;
 
-  let instances = instance::table.get_results::<Instance>(conn).await?;
+  let instances = instance::table
+    .filter(instance::dead_since.is_null())
+    .get_results::<Instance>(conn)
+    .await?;
 
   for instance in instances {
     if let Some(form) = build_update_instance_form(&instance.domain, client).await {
@@ -624,6 +933,103 @@ async fn update_instance_software(
   Ok(())
 }
 
+/// Detects the software of instances which were discovered recently, so that federation quirks
+/// for them apply without waiting for the daily update.
+async fn update_new_instance_software(
+  pool: &mut DbPool<'_>,
+  client: &ClientWithMiddleware,
+) -> LemmyResult<()> {
+  let conn = &mut get_conn(pool).await?;
+
+  let instances = instance::table
+    .filter(instance::software.is_null())
+    .filter(instance::published_at.gt(now() - 1.days()))
+    .get_results::<Instance>(conn)
+    .await?;
+
+  for instance in instances {
+    if let Some(form) = build_update_instance_form(&instance.domain, client).await {
+      Instance::update(pool, instance.id, form).await?;
+    }
+  }
+  Ok(())
+}
+
+/// Marks instances which were unreachable for too long as dead, so that no more activities are
+/// sent to them. Dead instances are checked with growing intervals, and revived once they respond.
+async fn update_dead_instances(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let days = context.settings().federation.dead_instance_after_days;
+  let marked = Instance::mark_dead(&mut context.pool(), days.into()).await?;
+  if marked > 0 {
+    info!("Marked {marked} unreachable instances as dead");
+  }
+
+  for instance in Instance::list_dead_to_probe(&mut context.pool()).await? {
+    if let Some(form) = build_update_instance_form(&instance.domain, context.client()).await {
+      info!("Instance {} is back online", instance.domain);
+      Instance::update(&mut context.pool(), instance.id, form).await?;
+      Instance::revive(&mut context.pool(), instance.id).await?;
+    } else {
+      instance.schedule_next_probe(&mut context.pool()).await?;
+    }
+  }
+  Ok(())
+}
+
+/// Maximum number of communities and users each which are refreshed per run.
+const REFRESH_ACTORS_LIMIT: i64 = 100;
+
+/// Fetches remote actors again which were not updated for a while, so that changes which didn't
+/// federate (eg a missed `Update` activity) eventually show up.
+///
+/// Failed fetches still mark the actor as refreshed, so that unreachable actors don't block others.
+async fn refresh_stale_remote_actors(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let days = context
+    .settings()
+    .federation
+    .refresh_remote_actors_after_days;
+  if days == 0 {
+    return Ok(());
+  }
+  let refreshed_before = Utc::now() - TimeDelta::days(days.into());
+
+  let communities =
+    Community::list_stale_remote(&mut context.pool(), refreshed_before, REFRESH_ACTORS_LIMIT)
+      .await?;
+  for community in communities {
+    let ap_id: ObjectId<ApubCommunity> = community.ap_id.into();
+    if let Err(e) = ap_id
+      .dereference_forced(&context.reset_request_count())
+      .await
+    {
+      debug!("Failed to refresh community {ap_id}: {e}");
+      let form = CommunityUpdateForm {
+        last_refreshed_at: Some(Utc::now()),
+        ..Default::default()
+      };
+      Community::update(&mut context.pool(), community.id, &form).await?;
+    }
+  }
+
+  let persons =
+    Person::list_stale_remote(&mut context.pool(), refreshed_before, REFRESH_ACTORS_LIMIT).await?;
+  for person in persons {
+    let ap_id: ObjectId<ApubPerson> = person.ap_id.into();
+    if let Err(e) = ap_id
+      .dereference_forced(&context.reset_request_count())
+      .await
+    {
+      debug!("Failed to refresh user {ap_id}: {e}");
+      let form = PersonUpdateForm {
+        last_refreshed_at: Some(Utc::now()),
+        ..Default::default()
+      };
+      Person::update(&mut context.pool(), person.id, &form).await?;
+    }
+  }
+  Ok(())
+}
+
 /// This builds an instance update form, for a given domain.
 /// If the instance sends a response, but doesn't have a well-known or nodeinfo,
 /// Then return a default form with only the updated field.
@@ -689,6 +1095,150 @@ async fn build_update_instance_form(
   Some(instance_form)
 }
 
+/// Number of pages which are fetched from the community list of each instance.
+const DIRECTORY_CRAWL_PAGES: i64 = 10;
+const DIRECTORY_CRAWL_PAGE_SIZE: i64 = 50;
+/// Number of instances which are crawled at the same time.
+const DIRECTORY_CRAWL_PARALLELISM: usize = 10;
+/// Maximum time for fetching all pages of a single instance.
+const DIRECTORY_CRAWL_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct DirectoryListCommunities {
+  communities: Vec<DirectoryCommunityView>,
+}
+
+#[derive(Deserialize)]
+struct DirectoryCommunityView {
+  community: DirectoryCommunity,
+  counts: DirectoryCommunityCounts,
+}
+
+#[derive(Deserialize)]
+struct DirectoryCommunity {
+  actor_id: url::Url,
+  name: String,
+  title: String,
+  description: Option<String>,
+  icon: Option<url::Url>,
+  #[serde(default)]
+  nsfw: bool,
+  #[serde(default)]
+  deleted: bool,
+  #[serde(default)]
+  removed: bool,
+}
+
+#[derive(Deserialize)]
+struct DirectoryCommunityCounts {
+  subscribers: i32,
+  posts: i32,
+}
+
+/// Fills the community directory with the local communities of linked Lemmy instances, so that
+/// users can discover remote communities without knowing their exact address.
+///
+/// Uses the v3 community list which is supported by all Lemmy versions in use.
+async fn crawl_remote_community_directory(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  info!("Crawling remote community directory...");
+  let local_domain = context.settings().get_hostname_without_port()?;
+
+  let instances: Vec<_> = Instance::read_federated_with_blocked_and_dead(&mut context.pool())
+    .await?
+    .into_iter()
+    .filter(|(instance, allowed, is_dead)| {
+      *allowed
+        && !is_dead
+        && instance.domain != local_domain
+        && instance.software.as_deref() == Some("lemmy")
+    })
+    .map(|(instance, ..)| instance)
+    .collect();
+  // Entries of instances which fail to respond are kept until the next crawl
+  let eligible: Vec<_> = instances.iter().map(|i| i.id).collect();
+
+  let results = futures::stream::iter(instances)
+    .map(|instance| async move {
+      let forms = match tokio::time::timeout(
+        DIRECTORY_CRAWL_TIMEOUT,
+        fetch_instance_communities(&instance, context.client()),
+      )
+      .await
+      {
+        Ok(forms) => forms,
+        Err(e) => Err(e.into()),
+      };
+      (instance, forms)
+    })
+    .buffer_unordered(DIRECTORY_CRAWL_PARALLELISM)
+    .collect::<Vec<_>>()
+    .await;
+
+  let mut crawled = 0;
+  for (instance, forms) in results {
+    let res = match forms {
+      Ok(forms) => {
+        RemoteCommunityDirectory::update_for_instance(&mut context.pool(), instance.id, forms).await
+      }
+      Err(e) => Err(e),
+    };
+    match res {
+      Ok(()) => crawled += 1,
+      Err(e) => warn!("Failed to crawl communities of {}: {e}", instance.domain),
+    }
+  }
+
+  // Remove communities of instances which are not linked anymore, or which are dead
+  RemoteCommunityDirectory::delete_except_instances(&mut context.pool(), &eligible).await?;
+  info!("Finished crawling remote community directory of {crawled} instances");
+  Ok(())
+}
+
+async fn fetch_instance_communities(
+  instance: &Instance,
+  client: &ClientWithMiddleware,
+) -> LemmyResult<Vec<RemoteCommunityDirectoryForm>> {
+  let mut forms = vec![];
+  for page in 1..=DIRECTORY_CRAWL_PAGES {
+    let url = format!(
+      "https://{}/api/v3/community/list?type_=Local&sort=TopMonth&limit={DIRECTORY_CRAWL_PAGE_SIZE}&page={page}",
+      instance.domain
+    );
+    let res = client
+      .get(&url)
+      .send()
+      .await?
+      .error_for_status()?
+      .json::<DirectoryListCommunities>()
+      .await?;
+    let count = res.communities.len();
+
+    forms.extend(
+      res
+        .communities
+        .into_iter()
+        .filter(|c| !c.community.deleted && !c.community.removed)
+        .map(|c| RemoteCommunityDirectoryForm {
+          ap_id: c.community.actor_id.into(),
+          instance_id: instance.id,
+          name: c.community.name,
+          title: c.community.title,
+          description: c.community.description,
+          icon: c.community.icon.map(Into::into),
+          nsfw: c.community.nsfw,
+          subscribers: c.counts.subscribers,
+          posts: c.counts.posts,
+          updated_at: Utc::now(),
+        }),
+    );
+
+    if i64::try_from(count).unwrap_or(i64::MAX) < DIRECTORY_CRAWL_PAGE_SIZE {
+      break;
+    }
+  }
+  Ok(forms)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -9,7 +9,7 @@ use lemmy_api_utils::utils::generate_inbox_url;
 use lemmy_db_schema::{
   source::{
     instance::Instance,
-    local_site::{LocalSite, LocalSiteInsertForm},
+    local_site::{LocalSite, LocalSiteInsertForm, LocalSiteUpdateForm},
     local_site_rate_limit::{LocalSiteRateLimit, LocalSiteRateLimitInsertForm},
     local_user::{LocalUser, LocalUserInsertForm},
     person::{Person, PersonInsertForm},
@@ -29,7 +29,7 @@ use lemmy_utils::{
   settings::structs::Settings,
 };
 use rand::{RngExt, distr::Alphanumeric};
-use tracing::info;
+use tracing::{info, warn};
 use url::Url;
 
 pub async fn setup_local_site(pool: &mut DbPool<'_>, settings: &Settings) -> LemmyResult<SiteView> {
@@ -127,5 +127,45 @@ pub async fn setup_local_site(pool: &mut DbPool<'_>, settings: &Settings) -> Lem
       .await?;
   }
 
+  migrate_deprecated_settings(pool, settings).await?;
+
   SiteView::read_local(pool).await
 }
+
+/// Copies limits which moved from the config file to the site settings, so that upgraded
+/// instances keep their configured values.
+async fn migrate_deprecated_settings(
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let pictrs = settings.pictrs().ok();
+  let form = LocalSiteUpdateForm {
+    federation_concurrent_sends_per_instance: settings
+      .federation
+      .concurrent_sends_per_instance
+      .map(i32::from),
+    video_max_upload_size_mb: pictrs
+      .as_ref()
+      .and_then(|p| p.video_max_upload_size_mb)
+      .map(i32::try_from)
+      .transpose()?,
+    video_max_duration_seconds: pictrs
+      .as_ref()
+      .and_then(|p| p.video_max_duration_seconds)
+      .map(i32::try_from)
+      .transpose()?,
+    ..Default::default()
+  };
+  if form.federation_concurrent_sends_per_instance.is_some()
+    || form.video_max_upload_size_mb.is_some()
+    || form.video_max_duration_seconds.is_some()
+  {
+    warn!(
+      "The config options federation.concurrent_sends_per_instance, \
+       pictrs.video_max_upload_size_mb and pictrs.video_max_duration_seconds are deprecated. \
+       Their values were copied into the site settings, please remove them from the config file."
+    );
+    LocalSite::update(pool, &form).await?;
+  }
+  Ok(())
+}
@@ -0,0 +1,149 @@
+//! Backups of an instance, which consist of a directory with a database dump in the custom format
+//! of pg_dump, and a manifest with the site settings, custom emojis and the aliases of uploaded
+//! media. The media itself is stored by pict-rs and needs to be backed up separately.
+
+use chrono::{DateTime, Utc};
+use lemmy_db_schema::source::{images::LocalImage, instance::Instance};
+use lemmy_db_views_custom_emoji::CustomEmojiView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::{connection::build_db_pool, schema_setup::latest_migration_version};
+use lemmy_utils::{
+  VERSION,
+  error::{LemmyErrorType, LemmyResult},
+  settings::SETTINGS,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+  fs::{self, File},
+  path::Path,
+  process::Command,
+};
+
+const DATABASE_FILE: &str = "database.dump";
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize)]
+struct BackupManifest {
+  lemmy_version: String,
+  /// Newest migration of the Lemmy version which created the backup.
+  migration_version: String,
+  created_at: DateTime<Utc>,
+  /// Protocol and hostname of the instance, used to fix up urls when restoring on another domain.
+  origin: String,
+  domain: String,
+  site: SiteView,
+  custom_emojis: Vec<CustomEmojiView>,
+  /// Aliases of all uploads, which need to be present in pict-rs after restoring.
+  image_aliases: Vec<String>,
+}
+
+/// Writes a backup to the given directory, which must not exist yet.
+pub async fn backup(dir: &Path) -> LemmyResult<()> {
+  fs::create_dir(dir)?;
+
+  // pg_dump reads everything in a single transaction, so the dump is consistent even while
+  // Lemmy is running
+  println!("Dumping database...");
+  run(
+    database_command("pg_dump")?
+      .args(["--format=custom", "--no-owner", "--no-privileges", "--file"])
+      .arg(dir.join(DATABASE_FILE)),
+  )?;
+
+  println!("Writing manifest...");
+  let pool = build_db_pool()?;
+  let pool = &mut (&pool).into();
+  let manifest = BackupManifest {
+    lemmy_version: VERSION.to_string(),
+    migration_version: latest_migration_version()?,
+    created_at: Utc::now(),
+    origin: SETTINGS.get_protocol_and_hostname(),
+    domain: SETTINGS.hostname.clone(),
+    site: SiteView::read_local(pool).await?,
+    custom_emojis: CustomEmojiView::list(pool, &None).await?,
+    image_aliases: LocalImage::list_all_aliases(pool).await?,
+  };
+  serde_json::to_writer_pretty(File::create(dir.join(MANIFEST_FILE))?, &manifest)?;
+
+  println!(
+    "Backup written to {}. Uploaded media is stored by pict-rs and needs to be backed up \
+     separately, it has {} images.",
+    dir.display(),
+    manifest.image_aliases.len()
+  );
+  Ok(())
+}
+
+/// Replaces all data in the database with the backup in the given directory, and runs pending
+/// migrations. If the backup comes from another domain, local urls are changed to the hostname
+/// in the config. Lemmy must not be running while restoring.
+pub async fn restore(dir: &Path) -> LemmyResult<()> {
+  let manifest: BackupManifest = serde_json::from_reader(File::open(dir.join(MANIFEST_FILE))?)?;
+  if manifest.migration_version > latest_migration_version()? {
+    return Err(
+      LemmyErrorType::Unknown(format!(
+        "Backup was created by Lemmy v{}, which is newer than this version",
+        manifest.lemmy_version
+      ))
+      .into(),
+    );
+  }
+
+  println!("Restoring database from {}...", manifest.created_at);
+  run(
+    database_command("pg_restore")?
+      .args([
+        "--clean",
+        "--if-exists",
+        "--no-owner",
+        "--no-privileges",
+        "--single-transaction",
+      ])
+      .arg(dir.join(DATABASE_FILE)),
+  )?;
+
+  // Creating the pool runs pending migrations, so that backups of older versions are upgraded
+  let pool = build_db_pool()?;
+
+  if manifest.domain != SETTINGS.hostname {
+    println!(
+      "Changing domain from {} to {}...",
+      manifest.domain, SETTINGS.hostname
+    );
+    Instance::change_domain(
+      &mut (&pool).into(),
+      &manifest.origin,
+      &SETTINGS.get_protocol_and_hostname(),
+      &manifest.domain,
+      &SETTINGS.hostname,
+    )
+    .await?;
+  }
+
+  println!(
+    "Backup restored. Make sure that pict-rs contains the {} uploaded images.",
+    manifest.image_aliases.len()
+  );
+  Ok(())
+}
+
+/// Command for a postgres tool which connects to the configured database. The password is passed
+/// in an environment variable, as arguments are visible to other users in the process list.
+fn database_command(program: &str) -> LemmyResult<Command> {
+  let (url, password) = SETTINGS.get_database_url_and_password()?;
+  let mut command = Command::new(program);
+  command.arg("--dbname").arg(url);
+  if let Some(password) = password {
+    command.env("PGPASSWORD", password);
+  }
+  Ok(command)
+}
+
+fn run(command: &mut Command) -> LemmyResult<()> {
+  let status = command.status()?;
+  if !status.success() {
+    let program = command.get_program().to_string_lossy();
+    return Err(LemmyErrorType::Unknown(format!("{program} failed with {status}")).into());
+  }
+  Ok(())
+}
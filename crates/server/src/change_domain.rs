@@ -0,0 +1,53 @@
+//! Moves the local instance to the hostname in the config. Other Lemmy instances are notified
+//! with a `Move` activity and replace the old urls in their database, and requests to the old
+//! domain are redirected as long as it points to this server.
+
+use activitypub_federation::config::Data;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_activities::protocol::site::move_site::MoveSite;
+use lemmy_db_schema::source::{
+  instance::Instance,
+  local_site_previous_domain::LocalSitePreviousDomain,
+  site::Site,
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  settings::SETTINGS,
+};
+use url::Url;
+
+/// Replaces the old domain in all local urls, then queues the activity which tells other
+/// instances about the move. It is sent once Lemmy is started again. Lemmy must not be running
+/// while the domain is changed.
+pub async fn change_domain(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let old_domain = site_view.instance.domain;
+  let new_domain = SETTINGS.get_hostname_without_port()?;
+  if old_domain == new_domain {
+    return Err(LemmyErrorType::Unknown(format!("Domain is already {new_domain}")).into());
+  }
+  let old_site_id: Url = site_view.site.ap_id.into();
+  let old_origin = old_site_id.origin().ascii_serialization();
+
+  println!("Changing domain from {old_domain} to {new_domain}...");
+  Instance::change_domain(
+    &mut context.pool(),
+    &old_origin,
+    &SETTINGS.get_protocol_and_hostname(),
+    &old_domain,
+    &new_domain,
+  )
+  .await?;
+  LocalSitePreviousDomain::add(&mut context.pool(), &old_domain, &new_domain).await?;
+
+  // Read the site again, the cached site view still has the old urls
+  let site = Site::read_from_instance_id(&mut context.pool(), site_view.instance.id).await?;
+  MoveSite::send(old_site_id, site.into(), context).await?;
+
+  println!(
+    "Domain changed. Other instances are notified once Lemmy is started. Keep {old_domain} \
+     pointing to this server, so that requests to it are redirected."
+  );
+  Ok(())
+}
@@ -7,13 +7,19 @@ use actix_web::{
   middleware::{self, Condition, ErrorHandlerResponse, ErrorHandlers},
   web::{Data, get, scope},
 };
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use lemmy_api::sitemap::get_sitemap;
 use lemmy_api_utils::{
   context::LemmyContext,
   request::client_builder,
+  search::{SearchIndexQueue, handle_search_index_queue},
   send_activity::ActivityChannel,
-  utils::local_site_rate_limit_to_rate_limit_config,
+  utils::{
+    PostgresRateLimitStore,
+    local_site_rate_limit_to_rate_limit_config,
+    reload_rate_limit_overrides,
+    reload_site_settings,
+  },
 };
 use lemmy_apub::{
   FEDERATION_HTTP_FETCH_LIMIT,
@@ -23,13 +29,20 @@ use lemmy_apub::{
 use lemmy_apub_activities::handle_outgoing_activities;
 use lemmy_apub_objects::objects::{community::FETCH_COMMUNITY_COLLECTIONS, instance::ApubSite};
 use lemmy_apub_send::{Opts, SendManager};
-use lemmy_db_schema::source::secret::Secret;
+use lemmy_db_schema::source::{
+  local_site_previous_domain::LocalSitePreviousDomain,
+  secret::Secret,
+};
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::connection::build_db_pool;
 use lemmy_routes::{
   feeds,
+  health,
   middleware::{
     idempotency::{IdempotencyMiddleware, IdempotencySet},
+    previous_domain::PreviousDomainMiddleware,
+    request_span::LemmyRootSpanBuilder,
+    response_size::ResponseSizeMiddleware,
     session::SessionMiddleware,
   },
   nodeinfo,
@@ -46,14 +59,21 @@ use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   rate_limit::RateLimit,
   response::jsonify_plain_text_errors,
-  settings::{SETTINGS, structs::Settings},
+  settings::{
+    SETTINGS,
+    structs::{RateLimitBackendType, Settings},
+  },
 };
 use reqwest_middleware::ClientBuilder;
 use reqwest_tracing::TracingMiddleware;
 use serde_json::json;
-use std::{ops::Deref, time::Duration};
+use std::{ops::Deref, path::PathBuf, sync::Arc, time::Duration};
 use tokio::signal::unix::SignalKind;
-use tracing_actix_web::{DefaultRootSpanBuilder, TracingLogger};
+use tracing_actix_web::TracingLogger;
+
+pub mod backup;
+pub mod change_domain;
+pub mod telemetry;
 
 #[cfg_attr(target_arch = "x86_64", global_allocator)]
 #[cfg(target_arch = "x86_64")]
@@ -75,6 +95,13 @@ const ACTIVITY_SENDING_TIMEOUT: Duration = Duration::from_secs(125);
 // TODO: Instead of defining individual env vars, only specify prefix once supported by clap.
 //       https://github.com/clap-rs/clap/issues/3221
 pub struct CmdArgs {
+  /// Which parts of Lemmy this process runs.
+  ///
+  /// Large instances can run multiple `api` processes behind a load balancer, and a separately
+  /// scaled set of `federation` processes which share the same database. The federation processes
+  /// split the remote instances between them, see --federate-process-index.
+  #[arg(long, value_enum, default_value_t = ServerMode::All, env = "LEMMY_MODE")]
+  mode: ServerMode,
   /// Don't run scheduled tasks.
   ///
   /// If you are running multiple Lemmy server processes, you probably want to disable scheduled
@@ -113,6 +140,26 @@ pub struct CmdArgs {
   subcommand: Option<CmdSubcommand>,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ServerMode {
+  /// Serve the HTTP API and send outgoing activities.
+  All,
+  /// Only serve the HTTP API, outgoing activities are sent by separate federation processes.
+  Api,
+  /// Only send outgoing activities, without HTTP server.
+  Federation,
+}
+
+impl CmdArgs {
+  fn http_server_enabled(&self) -> bool {
+    !self.disable_http_server && self.mode != ServerMode::Federation
+  }
+
+  fn activity_sending_enabled(&self) -> bool {
+    !self.disable_activity_sending && self.mode != ServerMode::Api
+  }
+}
+
 #[derive(Subcommand, Debug)]
 enum CmdSubcommand {
   /// Do something with migrations, then exit.
@@ -126,6 +173,26 @@ enum CmdSubcommand {
     #[arg(long, default_value_t = 1)]
     number: u64,
   },
+  /// Write a backup of the database, site settings, custom emojis and the list of uploaded media
+  /// to a new directory, then exit. Requires `pg_dump`.
+  Backup {
+    /// Directory for the backup, which must not exist yet.
+    path: PathBuf,
+  },
+  /// Replace all data in the database with a backup, then exit. Requires `pg_restore`.
+  ///
+  /// If the backup comes from another domain, local urls are changed to the hostname in the
+  /// config. Lemmy must not be running while restoring.
+  Restore {
+    /// Directory which was written by the backup command.
+    path: PathBuf,
+  },
+  /// Move the instance to the hostname in the config, then exit.
+  ///
+  /// Local urls in the database are changed, and other instances are notified about the move
+  /// when Lemmy is started again. Requests to the old domain are redirected. Lemmy must not be
+  /// running while changing the domain.
+  ChangeDomain,
 }
 
 #[derive(Subcommand, Debug, PartialEq, Eq)]
@@ -138,6 +205,12 @@ enum MigrationSubcommand {
 
 /// Placing the main function in lib.rs allows other crates to import it and embed Lemmy
 pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
+  match &args.subcommand {
+    Some(CmdSubcommand::Backup { path }) => return backup::backup(path).await,
+    Some(CmdSubcommand::Restore { path }) => return backup::restore(path).await,
+    _ => {}
+  }
+
   if let Some(CmdSubcommand::Migration {
     subcommand,
     all,
@@ -171,7 +244,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
 
   // return error 503 while running db migrations and startup tasks
   let mut startup_server_handle = None;
-  if !args.disable_http_server {
+  if args.http_server_enabled() && args.subcommand.is_none() {
     startup_server_handle = Some(create_startup_server()?);
   }
 
@@ -192,7 +265,10 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   // Set up the rate limiter
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
-  let rate_limit_cell = RateLimit::new(rate_limit_config);
+  let mut rate_limit_cell = RateLimit::new(rate_limit_config);
+  if SETTINGS.rate_limit_backend == RateLimitBackendType::Postgres {
+    rate_limit_cell = rate_limit_cell.with_store(Arc::new(PostgresRateLimitStore(pool.clone())));
+  }
 
   println!(
     "Starting HTTP server at {}:{}",
@@ -212,6 +288,16 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
     secret.clone(),
     rate_limit_cell,
   );
+  reload_rate_limit_overrides(&context).await?;
+
+  if let Some(CmdSubcommand::ChangeDomain) = args.subcommand {
+    let federation_config = FederationConfig::builder()
+      .domain(SETTINGS.hostname.clone())
+      .app_data(context)
+      .build()
+      .await?;
+    return change_domain::change_domain(&federation_config.to_request_data()).await;
+  }
 
   if let Some(prometheus) = SETTINGS.prometheus.clone() {
     serve_prometheus(prometheus, context.clone())?;
@@ -226,7 +312,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
     .debug(cfg!(debug_assertions))
     .http_signature_compat(true)
     .url_verifier(Box::new(VerifyUrlData(context.inner_pool().clone())));
-  if site_view.local_site.federation_signed_fetch {
+  if site_view.local_site.federation_signed_fetch || site_view.local_site.authorized_fetch {
     let site: ApubSite = site_view.site.clone().into();
     federation_config_builder.signed_fetch_actor(&site);
   }
@@ -239,21 +325,25 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   let request_data = federation_config.to_request_data();
   let outgoing_activities_task =
     tokio::task::spawn(handle_outgoing_activities(request_data.clone()));
+  let search_index_task = tokio::task::spawn(handle_search_index_queue(request_data.clone()));
+  let _reload_site_settings_task = tokio::task::spawn(reload_site_settings(request_data.clone()));
 
   if !args.disable_scheduled_tasks {
     // Schedules various cleanup tasks for the DB
     let _scheduled_tasks = tokio::task::spawn(scheduled_tasks::setup(request_data.clone()));
   }
 
-  let server = if !args.disable_http_server {
+  let server = if args.http_server_enabled() {
     if let Some(startup_server_handle) = startup_server_handle {
       startup_server_handle.stop(true).await;
     }
 
+    let previous_domains = LocalSitePreviousDomain::list_domains(&mut context.pool()).await?;
     Some(create_http_server(
       federation_config.clone(),
       SETTINGS.clone(),
       site_view,
+      previous_domains,
     )?)
   } else {
     None
@@ -261,7 +351,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
 
   // This FederationConfig instance is exclusively used to send activities, so we can safely
   // increase the timeout without affecting timeouts for resolving objects anywhere.
-  let federation_sender_config = if !args.disable_activity_sending {
+  let federation_sender_config = if args.activity_sending_enabled() {
     let mut federation_sender_config = federation_config_builder.clone();
     federation_sender_config.request_timeout(ACTIVITY_SENDING_TIMEOUT);
     Some(federation_sender_config.build().await?)
@@ -301,6 +391,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
 
   // Wait for outgoing apub sends to complete
   ActivityChannel::close(outgoing_activities_task).await?;
+  SearchIndexQueue::close(search_index_task).await?;
 
   Ok(())
 }
@@ -329,10 +420,12 @@ fn create_http_server(
   federation_config: FederationConfig<LemmyContext>,
   settings: Settings,
   site_view: SiteView,
+  previous_domains: Vec<String>,
 ) -> LemmyResult<ServerHandle> {
   // These must come before HttpServer creation so they can collect data across threads.
   let prom_api_metrics = new_prometheus_metrics()?;
   let idempotency_set = IdempotencySet::default();
+  let previous_domains = Arc::new(previous_domains);
 
   // Create Http server
   let bind = (settings.bind, settings.port);
@@ -343,15 +436,23 @@ fn create_http_server(
     let cors_config = cors_config(&settings);
     let app = App::new()
       .wrap(ErrorHandlers::new().default_handler(jsonify_plain_text_errors))
-      .wrap(middleware::Logger::new(
-        // This is the default log format save for the usage of %{r}a over %a to guarantee to
-        // record the client's (forwarded) IP and not the last peer address, since the latter is
-        // frequently just a reverse proxy
-        "%{r}a '%r' %s %b '%{Referer}i' '%{User-Agent}i' %T",
+      .wrap(Condition::new(
+        // With json logging, requests are logged by the root span builder instead
+        !settings.json_logging,
+        middleware::Logger::new(
+          // This is the default log format save for the usage of %{r}a over %a to guarantee to
+          // record the client's (forwarded) IP and not the last peer address, since the latter
+          // is frequently just a reverse proxy
+          "%{r}a '%r' %s %b '%{Referer}i' '%{User-Agent}i' %T",
+        ),
       ))
+      // Inside of compression, so that the uncompressed size is checked
+      .wrap(ResponseSizeMiddleware)
+      // Picks brotli, zstd or gzip based on Accept-Encoding. All of them use fast compression
+      // levels, which shrink json responses well without slowing down requests.
       .wrap(middleware::Compress::default())
       .wrap(cors_config)
-      .wrap(TracingLogger::<DefaultRootSpanBuilder>::new())
+      .wrap(TracingLogger::<LemmyRootSpanBuilder>::new())
       .app_data(Data::new(context.clone()))
       .wrap(FederationMiddleware::new(federation_config.clone()))
       .wrap(IdempotencyMiddleware::new(idempotency_set.clone()))
@@ -359,7 +460,8 @@ fn create_http_server(
       .wrap(Condition::new(
         SETTINGS.prometheus.is_some(),
         prom_api_metrics.clone(),
-      ));
+      ))
+      .wrap(PreviousDomainMiddleware::new(previous_domains.clone()));
 
     // The routes
     app
@@ -373,6 +475,7 @@ fn create_http_server(
       })
       .configure(feeds::config)
       .configure(nodeinfo::config)
+      .configure(health::config)
       .service(
         scope("/sitemap.xml")
           .wrap(rate_limit.message())
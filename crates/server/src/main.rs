@@ -1,25 +1,17 @@
 use clap::Parser;
-use lemmy_server::{CmdArgs, start_lemmy_server};
+use lemmy_server::{CmdArgs, start_lemmy_server, telemetry::init_tracing};
 use lemmy_utils::{error::LemmyResult, settings::SETTINGS};
-use tracing::level_filters::LevelFilter;
-use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
 pub async fn main() -> LemmyResult<()> {
-  let filter = EnvFilter::builder()
-    .with_default_directive(LevelFilter::INFO.into())
-    .from_env_lossy();
-  if SETTINGS.json_logging {
-    tracing_subscriber::fmt()
-      .with_env_filter(filter)
-      .json()
-      .init();
-  } else {
-    tracing_subscriber::fmt().with_env_filter(filter).init();
-  }
+  let tracer_provider = init_tracing(&SETTINGS)?;
 
   let args = CmdArgs::parse();
 
-  start_lemmy_server(args).await?;
-  Ok(())
+  let res = start_lemmy_server(args).await;
+  if let Some(tracer_provider) = tracer_provider {
+    // Export the remaining spans
+    tracer_provider.shutdown()?;
+  }
+  res
 }
@@ -0,0 +1,71 @@
+use lemmy_utils::{error::LemmyResult, settings::structs::Settings};
+use opentelemetry::{global, trace::TracerProvider as _};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+  Resource,
+  propagation::TraceContextPropagator,
+  trace::{Sampler, SdkTracerProvider},
+};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{EnvFilter, Layer, layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Name of the service in exported traces.
+const SERVICE_NAME: &str = "lemmy";
+
+/// Sets up logging, and exports traces to the OpenTelemetry collector if one is configured.
+///
+/// The returned provider needs to be shut down before exiting, so that the remaining spans are
+/// exported.
+pub fn init_tracing(settings: &Settings) -> LemmyResult<Option<SdkTracerProvider>> {
+  let filter = EnvFilter::builder()
+    .with_default_directive(LevelFilter::INFO.into())
+    .from_env_lossy();
+  let fmt_layer = if settings.json_logging {
+    // Write the event fields and the fields of the request span at the top level, so they can be
+    // queried directly
+    tracing_subscriber::fmt::layer()
+      .json()
+      .flatten_event(true)
+      .with_current_span(true)
+      .with_span_list(false)
+      .boxed()
+  } else {
+    tracing_subscriber::fmt::layer().boxed()
+  };
+
+  let provider = settings
+    .opentelemetry_url
+    .as_ref()
+    .map(|url| tracer_provider(url.as_str(), settings.opentelemetry_sample_ratio))
+    .transpose()?;
+  let otel_layer = provider
+    .as_ref()
+    .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME)));
+
+  tracing_subscriber::registry()
+    .with(filter)
+    .with(fmt_layer)
+    .with(otel_layer)
+    .init();
+  Ok(provider)
+}
+
+fn tracer_provider(url: &str, sample_ratio: f64) -> LemmyResult<SdkTracerProvider> {
+  let exporter = SpanExporter::builder()
+    .with_tonic()
+    .with_endpoint(url)
+    .build()?;
+  // Follow the sampling decision of the caller, so that traces which come from other services or
+  // instances are either complete or left out entirely
+  let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(sample_ratio)));
+  let provider = SdkTracerProvider::builder()
+    .with_batch_exporter(exporter)
+    .with_sampler(sampler)
+    .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+    .build();
+
+  // Read the trace context from incoming requests, and add it to outgoing federation requests
+  global::set_text_map_propagator(TraceContextPropagator::new());
+  global::set_tracer_provider(provider.clone());
+  Ok(provider)
+}
@@ -20,6 +20,7 @@ pub enum LemmyErrorType {
   NotAnAdmin,
   CantBlockYourself,
   CantNoteYourself,
+  CantMuteYourself,
   CantBlockAdmin,
   PasswordsDoNotMatch,
   EmailNotVerified,
@@ -33,6 +34,10 @@ pub enum LemmyErrorType {
   NoContentTypeHeader,
   NotAnImageType,
   ImageUploadDisabled,
+  UnsupportedVideoFormat,
+  VideoTooLarge,
+  VideoTooLong,
+  UploadQuotaExceeded,
   NotAModOrAdmin,
   NotTopMod,
   NotLoggedIn,
@@ -41,6 +46,7 @@ pub enum LemmyErrorType {
   SiteBan,
   Deleted,
   PersonIsBlocked,
+  PersonIsMuted,
   CommunityIsBlocked,
   InstanceIsBlocked,
   InstanceIsPrivate,
@@ -77,6 +83,7 @@ pub enum LemmyErrorType {
   InvalidMatrixId,
   InvalidPostTitle,
   InvalidBodyField,
+  InvalidQuietHours,
   BioLengthOverflow,
   AltTextLengthOverflow,
   CouldntParseTotpSecret,
@@ -85,8 +92,21 @@ pub enum LemmyErrorType {
   MissingTotpSecret,
   IncorrectTotpToken,
   TotpAlreadyEnabled,
+  TotpNotEnabled,
+  /// Thrown when the site requires admins and mods to enable 2FA before taking moderation actions
+  TotpRequiredForModeration,
   BlockedUrl,
   InvalidUrl,
+  /// The community doesn't allow posting a url which was posted there recently.
+  DuplicatePostUrl,
+  /// The community doesn't allow posting under a pseudonym.
+  PseudonymousPostingDisabled,
+  /// The post body lacks headings of the post template which it was created with.
+  MissingPostTemplateHeadings,
+  /// The interval of a recurring post must be between one day and a year.
+  InvalidRecurringPostInterval,
+  /// Recurring posts can only be created by a local account.
+  InvalidRecurringPostCreator,
   EmailSendFailed,
   Slurs,
   RegistrationDenied(String),
@@ -94,6 +114,7 @@ pub enum LemmyErrorType {
   SiteNameLengthOverflow,
   PermissiveRegex,
   InvalidRegex,
+  InvalidDomainPattern,
   InvalidUrlScheme,
   ContradictingFilters,
   /// Thrown when an API call is submitted with more than 1000 array elements, see
@@ -109,6 +130,14 @@ pub enum LemmyErrorType {
   OauthAuthorizationInvalid,
   OauthLoginFailed,
   OauthRegistrationClosed,
+  /// Thrown when unlinking the only way to log into an account
+  CantUnlinkLastLoginMethod,
+  /// Thrown when a token which was issued to a third-party app lacks permission for an endpoint
+  InsufficientTokenScope,
+  /// Thrown when a passkey registration or login can't be verified
+  InvalidWebauthnCredential,
+  /// Thrown when logging in with password to an account which has a passkey as second factor
+  MissingWebauthnAssertion,
   NotFound,
   PostScheduleTimeMustBeInFuture,
   TooManyScheduledPosts,
@@ -117,11 +146,39 @@ pub enum LemmyErrorType {
   PluginError(String),
   InvalidFetchLimit,
   EmailNotificationsDisabled,
+  PushNotificationsDisabled,
   MultiCommunityUpdateWrongUser,
   CannotCombineCommunityIdAndMultiCommunityId,
   MultiCommunityEntryLimitReached,
   TooManyRequests,
   ResolveObjectFailed(String),
+  NewAccountPostLimitReached,
+  NewAccountLinkPostsNotAllowed,
+  NewAccountImageUploadNotAllowed,
+  NewAccountCommunityCreationNotAllowed,
+  InvalidNewAccountRestriction,
+  InvalidAccountDeletionGracePeriod,
+  InvalidIpRange,
+  InvalidRegistrationIpSetting,
+  RegistrationIpBlocked,
+  TooManyRegistrationsFromIp,
+  InvalidPowChallenge,
+  InvalidPowChallengeDifficulty,
+  InvalidImageUploadQuota,
+  InvalidImageMaxDimensions,
+  InvalidImageProxyMaxSize,
+  InvalidFederationConcurrentSends,
+  InvalidVideoLimits,
+  InvalidRateLimit,
+  CaptchaNotConfigured,
+  CaptchaIncorrect,
+  TooManyLoginAttempts,
+  /// Thrown when an encrypted private message also has plaintext content, or lacks ciphertext
+  InvalidEncryptedPrivateMessage,
+  InvalidEncryptionKey,
+  /// Thrown when fetching the remote modlog of a local community, or of a community on software
+  /// other than Lemmy
+  RemoteModlogUnavailable,
   #[serde(untagged)]
   #[cfg_attr(feature = "ts-rs", ts(skip))]
   UntranslatedError(Option<UntranslatedError>),
@@ -137,6 +194,7 @@ pub enum UntranslatedError {
   CannotReceivePage,
   OnlyLocalAdminCanRemoveCommunity,
   OnlyLocalAdminCanRestoreCommunity,
+  OnlyLocalAdminCanRenameCommunity,
   PostIsLocked,
   PersonIsBannedFromSite(String),
   InvalidVoteValue,
@@ -158,6 +216,13 @@ pub enum UntranslatedError {
   /// A remote community sent an activity to us, but actually no local user follows the community
   /// so the activity was rejected.
   CommunityHasNoFollowers(String),
+  /// Authorized fetch is enabled, and the request for an activitypub object wasn't signed.
+  SignatureRequired,
+  /// The activity was already received before, and isn't processed again.
+  DuplicateActivity,
+  /// An instance announced a move to a new domain, but the site actors at the old and new domain
+  /// don't have the same keypair, or it already announced a move recently.
+  InvalidMove,
 }
 
 cfg_if! {
@@ -226,6 +291,9 @@ cfg_if! {
         match self.error_type {
           LemmyErrorType::IncorrectLogin => actix_web::http::StatusCode::UNAUTHORIZED,
           LemmyErrorType::NotFound => actix_web::http::StatusCode::NOT_FOUND,
+          LemmyErrorType::UntranslatedError(Some(UntranslatedError::SignatureRequired)) => {
+            actix_web::http::StatusCode::UNAUTHORIZED
+          }
           _ => actix_web::http::StatusCode::BAD_REQUEST,
         }
       }
@@ -37,6 +37,12 @@ pub const CACHE_DURATION_API: Duration = Duration::from_secs(1);
 
 pub const MAX_COMMENT_DEPTH_LIMIT: usize = 50;
 
+/// Number of devices for which a person can publish encryption keys.
+pub const MAX_ENCRYPTION_KEYS: usize = 20;
+
+/// Number of searches which a user can save.
+pub const MAX_SAVED_SEARCHES: usize = 50;
+
 /// Doing DB transactions of bigger batches than this tend to cause seq scans.
 pub const DB_BATCH_SIZE: i64 = 1000;
 
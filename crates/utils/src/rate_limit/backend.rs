@@ -1,7 +1,13 @@
 //! The content in this file is mostly copy-pasted from library code:
 //! https://github.com/jacob-pro/actix-extensible-rate-limit/blob/master/src/backend/memory.rs
 
-use crate::rate_limit::{ActionType, BucketConfig, input::LemmyInput};
+use crate::rate_limit::{
+  ActionType,
+  BucketConfig,
+  RateLimitOverrides,
+  input::LemmyInput,
+  store::RateLimitStore,
+};
 use actix_extensible_rate_limit::backend::{
   Backend,
   Decision,
@@ -9,21 +15,30 @@ use actix_extensible_rate_limit::backend::{
   memory::DEFAULT_GC_INTERVAL_SECONDS,
 };
 use actix_web::rt::{task::JoinHandle, time::Instant};
+use chrono::Utc;
 use dashmap::DashMap;
 use enum_map::EnumMap;
 use std::{
   convert::Infallible,
+  hash::Hash,
   sync::{Arc, RwLock},
   time::Duration,
 };
+use tracing::warn;
 
 /// A Fixed Window rate limiter [Backend] that uses [Dashmap](dashmap::DashMap) to store keys
 /// in memory.
 #[derive(Clone)]
 pub struct LemmyBackend {
   map: Arc<DashMap<LemmyInput, Value>>,
+  /// Buckets for the rate limits of individual api keys, by api key id.
+  api_key_map: Arc<DashMap<i32, Value>>,
   gc_handle: Option<Arc<JoinHandle<()>>>,
   pub(super) configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
+  pub(super) overrides: Arc<RwLock<RateLimitOverrides>>,
+  /// Shared buckets for the regular rate limits, if configured. Api key limits are always kept in
+  /// memory.
+  pub(super) store: Option<Arc<dyn RateLimitStore>>,
 }
 
 struct Value {
@@ -34,20 +49,29 @@ struct Value {
 impl LemmyBackend {
   pub(crate) fn new(configs: EnumMap<ActionType, BucketConfig>, enable_gc: bool) -> Self {
     let map = Arc::new(DashMap::<LemmyInput, Value>::new());
+    let api_key_map = Arc::new(DashMap::<i32, Value>::new());
     let gc_handle = enable_gc.then(|| {
       Arc::new(LemmyBackend::garbage_collector(
         map.clone(),
+        api_key_map.clone(),
         Duration::from_secs(DEFAULT_GC_INTERVAL_SECONDS),
       ))
     });
     LemmyBackend {
       map,
+      api_key_map,
       gc_handle,
       configs: Arc::new(RwLock::new(configs)),
+      overrides: Default::default(),
+      store: None,
     }
   }
 
-  fn garbage_collector(map: Arc<DashMap<LemmyInput, Value>>, interval: Duration) -> JoinHandle<()> {
+  fn garbage_collector(
+    map: Arc<DashMap<LemmyInput, Value>>,
+    api_key_map: Arc<DashMap<i32, Value>>,
+    interval: Duration,
+  ) -> JoinHandle<()> {
     assert!(
       interval.as_secs_f64() > 0f64,
       "GC interval must be non-zero"
@@ -56,70 +80,133 @@ impl LemmyBackend {
       loop {
         let now = Instant::now();
         map.retain(|_k, v| v.ttl > now);
+        api_key_map.retain(|_k, v| v.ttl > now);
         tokio::time::sleep_until(now + interval).await;
       }
     })
   }
+
+  /// Check the rate limit of an api key, which allows `max_requests` per minute.
+  pub(super) fn check_api_key(&self, api_key_id: i32, max_requests: u32) -> bool {
+    let (count, _) = count_request(&self.api_key_map, api_key_id, Duration::from_secs(60));
+    count <= max_requests.into()
+  }
+}
+
+/// Count a request in the bucket of the given key, and return the number of requests in the
+/// bucket together with its expiry.
+#[expect(clippy::expect_used)]
+fn count_request<K: Eq + Hash>(
+  map: &DashMap<K, Value>,
+  key: K,
+  interval: Duration,
+) -> (u64, Instant) {
+  let now = Instant::now();
+  let mut count = 1;
+  let mut expiry = now
+    .checked_add(interval)
+    .expect("Interval unexpectedly large");
+  map
+    .entry(key)
+    .and_modify(|v| {
+      // If this bucket hasn't yet expired, increment and extract the count/expiry
+      if v.ttl > now {
+        v.count += 1;
+        count = v.count;
+        expiry = v.ttl;
+      } else {
+        // If this bucket has expired we will reset the count to 1 and set a new TTL.
+        v.ttl = expiry;
+        v.count = count;
+      }
+    })
+    .or_insert_with(|| Value {
+      // If the bucket doesn't exist, create it with a count of 1, and set the TTL.
+      ttl: expiry,
+      count,
+    });
+  (count, expiry)
+}
+
+/// Identifies the bucket in which a request was counted, so that a rollback undoes it in the same
+/// place.
+pub struct RollbackToken {
+  input: LemmyInput,
+  /// Whether the request was counted in the shared store, or in the local map instead.
+  in_store: bool,
 }
 
 impl Backend<LemmyInput> for LemmyBackend {
   type Output = SimpleOutput;
-  type RollbackToken = LemmyInput;
+  type RollbackToken = RollbackToken;
   type Error = Infallible;
 
-  #[expect(clippy::expect_used)]
   async fn request(
     &self,
     input: LemmyInput,
   ) -> Result<(Decision, Self::Output, Self::RollbackToken), Self::Error> {
     #[expect(clippy::expect_used)]
-    let config = self.configs.read().expect("read rwlock")[input.1];
+    let config = self.overrides.read().expect("read rwlock")[input.2][input.1]
+      .unwrap_or(self.configs.read().expect("read rwlock")[input.1]);
 
     let max_requests: u64 = config.max_requests.into();
     let interval = Duration::from_secs(config.interval.into());
 
-    let now = Instant::now();
-    let mut count = 1;
-    let mut expiry = now
-      .checked_add(interval)
-      .expect("Interval unexpectedly large");
-    self
-      .map
-      .entry(input)
-      .and_modify(|v| {
-        // If this bucket hasn't yet expired, increment and extract the count/expiry
-        if v.ttl > now {
-          v.count += 1;
-          count = v.count;
-          expiry = v.ttl;
-        } else {
-          // If this bucket has expired we will reset the count to 1 and set a new TTL.
-          v.ttl = expiry;
-          v.count = count;
+    let (count, expiry, in_store) = match &self.store {
+      Some(store) => match store.count_request(store_key(input), interval).await {
+        Ok((count, expiry)) => {
+          let remaining = (expiry - Utc::now()).to_std().unwrap_or_default();
+          (count, Instant::now() + remaining, true)
         }
-      })
-      .or_insert_with(|| Value {
-        // If the bucket doesn't exist, create it with a count of 1, and set the TTL.
-        ttl: expiry,
-        count,
-      });
+        // Falls back to the local bucket, so that an outage of the store doesn't block all
+        // requests
+        Err(e) => {
+          warn!("Failed to count request in rate limit store: {e}");
+          let (count, expiry) = count_request(&self.map, input, interval);
+          (count, expiry, false)
+        }
+      },
+      None => {
+        let (count, expiry) = count_request(&self.map, input, interval);
+        (count, expiry, false)
+      }
+    };
     let allow = count <= max_requests;
     let output = SimpleOutput {
       limit: max_requests,
       remaining: max_requests.saturating_sub(count),
       reset: expiry,
     };
-    Ok((Decision::from_allowed(allow), output, input))
+    let token = RollbackToken { input, in_store };
+    Ok((Decision::from_allowed(allow), output, token))
   }
 
   async fn rollback(&self, token: Self::RollbackToken) -> Result<(), Self::Error> {
-    self.map.entry(token).and_modify(|v| {
+    if let Some(store) = self.store.as_ref().filter(|_| token.in_store) {
+      store
+        .rollback(store_key(token.input))
+        .await
+        .inspect_err(|e| warn!("Failed to roll back request in rate limit store: {e}"))
+        .ok();
+      return Ok(());
+    }
+    self.map.entry(token.input).and_modify(|v| {
       v.count = v.count.saturating_sub(1);
     });
     Ok(())
   }
 }
 
+/// Key of the bucket in the shared store, like `post:anonymous:203.0.113.1`.
+fn store_key(input: LemmyInput) -> String {
+  let LemmyInput(ip, action_type, user_class) = input;
+  format!(
+    "{}:{}:{ip}",
+    action_type.as_ref().to_lowercase(),
+    user_class.as_ref().to_lowercase()
+  )
+}
+
 impl Drop for LemmyBackend {
   fn drop(&mut self) {
     if let Some(handle) = &self.gc_handle {
@@ -132,10 +219,12 @@ impl Drop for LemmyBackend {
 mod tests {
   use super::*;
   use crate::{
-    error::LemmyResult,
-    rate_limit::{ActionType, input::raw_ip_key},
+    error::{LemmyErrorType, LemmyResult},
+    rate_limit::{ActionType, RateLimit, UserClass, input::raw_ip_key},
   };
+  use chrono::DateTime;
   use enum_map::enum_map;
+  use futures::future::BoxFuture;
 
   const MINUTE_SECS: u32 = 60;
   const MINUTE: Duration = Duration::from_secs(60);
@@ -178,7 +267,7 @@ mod tests {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), true);
     let key = raw_ip_key(Some("127.0.0.2"));
-    let input = LemmyInput(key, ActionType::Message);
+    let input = LemmyInput(key, ActionType::Message, UserClass::Anonymous);
     for _ in 0..5 {
       // First 5 should be allowed
       let (allow, _, _) = backend.request(input).await?;
@@ -194,7 +283,11 @@ mod tests {
   async fn test_reset() -> LemmyResult<()> {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), false);
-    let input = LemmyInput(raw_ip_key(Some("127.0.0.3")), ActionType::Message);
+    let input = LemmyInput(
+      raw_ip_key(Some("127.0.0.3")),
+      ActionType::Message,
+      UserClass::Anonymous,
+    );
     // Make first request, should be allowed
     let (decision, _, _) = backend.request(input).await?;
     assert!(decision.is_allowed());
@@ -214,8 +307,16 @@ mod tests {
   async fn test_garbage_collection() -> LemmyResult<()> {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), true);
-    let key1 = LemmyInput(raw_ip_key(Some("127.0.0.4")), ActionType::Message);
-    let key2 = LemmyInput(raw_ip_key(Some("127.0.0.5")), ActionType::Post);
+    let key1 = LemmyInput(
+      raw_ip_key(Some("127.0.0.4")),
+      ActionType::Message,
+      UserClass::Anonymous,
+    );
+    let key2 = LemmyInput(
+      raw_ip_key(Some("127.0.0.5")),
+      ActionType::Post,
+      UserClass::Anonymous,
+    );
     backend.request(key1).await?;
     backend.request(key2).await?;
     assert!(backend.map.contains_key(&key1));
@@ -233,7 +334,7 @@ mod tests {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 2), true);
     let key = raw_ip_key(Some("127.0.0.6"));
-    let input = LemmyInput(key, ActionType::Message);
+    let input = LemmyInput(key, ActionType::Message, UserClass::Anonymous);
     // First of 2 should be allowed.
     let (decision, output, _) = backend.request(input).await?;
     assert!(decision.is_allowed());
@@ -260,7 +361,7 @@ mod tests {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), true);
     let key = raw_ip_key(Some("127.0.0.7"));
-    let input = LemmyInput(key, ActionType::Message);
+    let input = LemmyInput(key, ActionType::Message, UserClass::Anonymous);
     let (_, output, rollback) = backend.request(input).await?;
     assert_eq!(output.remaining, 4);
     backend.rollback(rollback).await?;
@@ -269,4 +370,125 @@ mod tests {
     assert_eq!(output.remaining, 4);
     Ok(())
   }
+
+  #[actix_web::test]
+  async fn test_overrides() -> LemmyResult<()> {
+    tokio::time::pause();
+    let rate_limit = RateLimit::new(test_config(MINUTE_SECS, 2));
+    let mut overrides = RateLimitOverrides::default();
+    overrides[UserClass::NewAccount][ActionType::Message] = Some(BucketConfig {
+      max_requests: 1,
+      interval: MINUTE_SECS,
+    });
+    rate_limit.set_overrides(overrides);
+    let backend = &rate_limit.backend;
+    let key = raw_ip_key(Some("127.0.0.9"));
+    let new_account = LemmyInput(key, ActionType::Message, UserClass::NewAccount);
+    assert!(backend.request(new_account).await?.0.is_allowed());
+    assert!(backend.request(new_account).await?.0.is_denied());
+    // Other classes and actions keep the regular limit
+    let established = LemmyInput(key, ActionType::Message, UserClass::Established);
+    assert!(backend.request(established).await?.0.is_allowed());
+    assert!(backend.request(established).await?.0.is_allowed());
+    assert!(backend.request(established).await?.0.is_denied());
+    Ok(())
+  }
+
+  /// Buckets without expiry, shared by multiple backends like a database.
+  #[derive(Default)]
+  struct TestStore(DashMap<String, u64>);
+
+  impl RateLimitStore for TestStore {
+    fn count_request(
+      &self,
+      key: String,
+      _interval: Duration,
+    ) -> BoxFuture<'_, LemmyResult<(u64, DateTime<Utc>)>> {
+      let mut count = self.0.entry(key).or_default();
+      *count += 1;
+      let count = *count;
+      Box::pin(async move { Ok((count, Utc::now())) })
+    }
+
+    fn rollback(&self, key: String) -> BoxFuture<'_, LemmyResult<()>> {
+      self.0.entry(key).and_modify(|c| *c = c.saturating_sub(1));
+      Box::pin(async { Ok(()) })
+    }
+  }
+
+  #[actix_web::test]
+  async fn test_store() -> LemmyResult<()> {
+    let store: Arc<dyn RateLimitStore> = Arc::new(TestStore::default());
+    let mut backend1 = LemmyBackend::new(test_config(MINUTE_SECS, 2), false);
+    backend1.store = Some(store.clone());
+    let mut backend2 = LemmyBackend::new(test_config(MINUTE_SECS, 2), false);
+    backend2.store = Some(store);
+    let input = LemmyInput(
+      raw_ip_key(Some("127.0.0.8")),
+      ActionType::Message,
+      UserClass::Anonymous,
+    );
+
+    // Both backends count requests in the same bucket
+    let (decision, _, rollback) = backend1.request(input).await?;
+    assert!(decision.is_allowed());
+    backend1.rollback(rollback).await?;
+    assert!(backend1.request(input).await?.0.is_allowed());
+    assert!(backend2.request(input).await?.0.is_allowed());
+    assert!(backend1.request(input).await?.0.is_denied());
+    assert!(backend1.map.is_empty());
+    Ok(())
+  }
+
+  /// Store which is unavailable, so that requests are counted in the local map instead.
+  struct FailingStore;
+
+  impl RateLimitStore for FailingStore {
+    fn count_request(
+      &self,
+      _key: String,
+      _interval: Duration,
+    ) -> BoxFuture<'_, LemmyResult<(u64, DateTime<Utc>)>> {
+      Box::pin(async { Err(LemmyErrorType::NotFound.into()) })
+    }
+
+    fn rollback(&self, _key: String) -> BoxFuture<'_, LemmyResult<()>> {
+      Box::pin(async { Err(LemmyErrorType::NotFound.into()) })
+    }
+  }
+
+  #[actix_web::test]
+  async fn test_store_fallback_rollback() -> LemmyResult<()> {
+    tokio::time::pause();
+    let mut backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), false);
+    backend.store = Some(Arc::new(FailingStore));
+    let input = LemmyInput(
+      raw_ip_key(Some("127.0.0.10")),
+      ActionType::Message,
+      UserClass::Anonymous,
+    );
+
+    // The rollback applies to the local map where the request was counted
+    let (decision, _, rollback) = backend.request(input).await?;
+    assert!(decision.is_allowed());
+    backend.rollback(rollback).await?;
+    assert!(backend.request(input).await?.0.is_allowed());
+    assert!(backend.request(input).await?.0.is_denied());
+    Ok(())
+  }
+
+  #[actix_web::test]
+  async fn test_api_key() -> LemmyResult<()> {
+    tokio::time::pause();
+    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), false);
+    assert!(backend.check_api_key(1, 2));
+    assert!(backend.check_api_key(1, 2));
+    assert!(!backend.check_api_key(1, 2));
+    // Other keys have their own limit
+    assert!(backend.check_api_key(2, 2));
+    // The limit resets after a minute
+    tokio::time::advance(MINUTE).await;
+    assert!(backend.check_api_key(1, 2));
+    Ok(())
+  }
 }
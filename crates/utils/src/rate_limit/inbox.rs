@@ -0,0 +1,152 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use std::sync::Arc;
+
+/// Length of the window in which activities are counted.
+const WINDOW: TimeDelta = TimeDelta::minutes(1);
+/// Instances which exceed the limit in this many consecutive windows get throttled.
+const THROTTLE_AFTER_WINDOWS: u32 = 3;
+/// While throttled, all activities from the instance are rejected.
+const THROTTLE_DURATION: TimeDelta = TimeDelta::minutes(15);
+
+/// Limits the number of activities which each remote instance can send to the inbox. Keyed by
+/// domain instead of IP, as large instances often send from multiple servers.
+#[derive(Clone, Default)]
+pub(super) struct InboxRateLimit {
+  instances: Arc<DashMap<String, InboxState>>,
+}
+
+struct InboxState {
+  window_start: DateTime<Utc>,
+  window_count: u32,
+  /// Number of consecutive windows in which the limit was exceeded.
+  exceeded_windows: u32,
+  throttled_until: Option<DateTime<Utc>>,
+  received: u64,
+  rejected: u64,
+}
+
+/// Inbox counters of a single remote instance, since the server was started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboxStats {
+  pub domain: String,
+  pub received: u64,
+  pub rejected: u64,
+  pub throttled_until: Option<DateTime<Utc>>,
+}
+
+impl InboxRateLimit {
+  /// Count an activity from the given domain, and return false if it should be rejected.
+  pub(super) fn check(&self, domain: &str, max_per_minute: u32) -> bool {
+    self.check_at(domain, max_per_minute, Utc::now())
+  }
+
+  fn check_at(&self, domain: &str, max_per_minute: u32, now: DateTime<Utc>) -> bool {
+    let mut state = self
+      .instances
+      .entry(domain.to_string())
+      .or_insert_with(|| InboxState {
+        window_start: now,
+        window_count: 0,
+        exceeded_windows: 0,
+        throttled_until: None,
+        received: 0,
+        rejected: 0,
+      });
+    state.received += 1;
+
+    if state.throttled_until.is_some_and(|t| t > now) {
+      state.rejected += 1;
+      return false;
+    }
+    state.throttled_until = None;
+    if max_per_minute == 0 {
+      return true;
+    }
+
+    if now >= state.window_start + WINDOW {
+      // Only directly following windows count towards throttling
+      if state.window_count <= max_per_minute || now >= state.window_start + WINDOW * 2 {
+        state.exceeded_windows = 0;
+      }
+      state.window_start = now;
+      state.window_count = 0;
+    }
+    state.window_count += 1;
+    if state.window_count <= max_per_minute {
+      return true;
+    }
+
+    state.rejected += 1;
+    // Only count the first rejection in each window
+    if state.window_count == max_per_minute + 1 {
+      state.exceeded_windows += 1;
+      if state.exceeded_windows >= THROTTLE_AFTER_WINDOWS {
+        state.throttled_until = Some(now + THROTTLE_DURATION);
+        state.exceeded_windows = 0;
+      }
+    }
+    false
+  }
+
+  pub(super) fn stats(&self) -> Vec<InboxStats> {
+    self
+      .instances
+      .iter()
+      .map(|s| InboxStats {
+        domain: s.key().clone(),
+        received: s.received,
+        rejected: s.rejected,
+        throttled_until: s.throttled_until,
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_inbox_rate_limit() {
+    let limit = InboxRateLimit::default();
+    let start = Utc::now();
+    let mut now = start;
+
+    // Exceed the limit in three consecutive windows
+    for _ in 0..THROTTLE_AFTER_WINDOWS {
+      assert!(limit.check_at("example.com", 2, now));
+      assert!(limit.check_at("example.com", 2, now));
+      assert!(!limit.check_at("example.com", 2, now));
+      // Other instances are not affected
+      assert!(limit.check_at("lemmy.ml", 2, now));
+      now += WINDOW;
+    }
+
+    // Now the instance is throttled, even though a new window started
+    assert!(!limit.check_at("example.com", 2, now));
+    now = start + THROTTLE_DURATION * 2;
+    assert!(limit.check_at("example.com", 2, now));
+
+    let mut stats = limit.stats();
+    stats.sort_by(|a, b| a.domain.cmp(&b.domain));
+    assert_eq!(
+      vec![
+        InboxStats {
+          domain: "example.com".to_string(),
+          received: 11,
+          rejected: 4,
+          throttled_until: None,
+        },
+        InboxStats {
+          domain: "lemmy.ml".to_string(),
+          received: 3,
+          rejected: 0,
+          throttled_until: None,
+        }
+      ],
+      stats
+    );
+  }
+}
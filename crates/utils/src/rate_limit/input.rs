@@ -1,12 +1,17 @@
-use crate::rate_limit::ActionType;
+use crate::rate_limit::{ActionType, UserClass};
 use std::{
+  fmt,
   future::Ready,
   net::{IpAddr, Ipv4Addr, SocketAddr},
   str::FromStr,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct LemmyInput(pub(crate) RateLimitIpAddr, pub(crate) ActionType);
+pub struct LemmyInput(
+  pub(crate) RateLimitIpAddr,
+  pub(crate) ActionType,
+  pub(crate) UserClass,
+);
 
 pub(crate) type LemmyInputFuture = Ready<Result<LemmyInput, actix_web::Error>>;
 
@@ -30,6 +35,15 @@ impl From<IpAddr> for RateLimitIpAddr {
   }
 }
 
+impl fmt::Display for RateLimitIpAddr {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RateLimitIpAddr::V4(addr) => write!(f, "{addr}"),
+      RateLimitIpAddr::V6([a, b, c, d]) => write!(f, "{a:x}:{b:x}:{c:x}:{d:x}::/64"),
+    }
+  }
+}
+
 /// Generate a raw byte key for backend which uses less memory.
 pub(crate) fn raw_ip_key(ip_str: Option<&str>) -> RateLimitIpAddr {
   parse_ip(ip_str).into()
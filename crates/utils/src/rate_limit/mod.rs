@@ -1,15 +1,24 @@
 use crate::rate_limit::{
   backend::LemmyBackend,
+  inbox::InboxRateLimit,
   input::{LemmyInput, LemmyInputFuture, raw_ip_key},
+  penalty::PenaltyBox,
 };
 use actix_extensible_rate_limit::{RateLimiter, backend::SimpleOutput};
-use actix_web::dev::ServiceRequest;
+use actix_web::{HttpMessage, dev::ServiceRequest};
 use enum_map::{EnumMap, enum_map};
-use std::future::ready;
+use std::{future::ready, sync::Arc};
 use strum::{AsRefStr, Display};
 
 mod backend;
+mod inbox;
 mod input;
+mod penalty;
+mod store;
+
+pub use inbox::InboxStats;
+pub use penalty::{Penalty, PenaltyStats, Violation};
+pub use store::RateLimitStore;
 
 #[derive(Debug, enum_map::Enum, Copy, Clone, Display, AsRefStr, Eq, PartialEq, Hash)]
 pub enum ActionType {
@@ -22,6 +31,21 @@ pub enum ActionType {
   ImportUserSettings,
 }
 
+/// Users which can have different rate limits. The session middleware stores it in the request
+/// extensions, requests without it are anonymous.
+#[derive(Debug, enum_map::Enum, Copy, Clone, Display, AsRefStr, Eq, PartialEq, Hash, Default)]
+pub enum UserClass {
+  #[default]
+  Anonymous,
+  NewAccount,
+  Established,
+  Bot,
+  Admin,
+}
+
+/// Limits of a user class which replace the regular limits, for the actions where it is set.
+pub type RateLimitOverrides = EnumMap<UserClass, EnumMap<ActionType, Option<BucketConfig>>>;
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct BucketConfig {
   pub max_requests: u32,
@@ -31,15 +55,31 @@ pub struct BucketConfig {
 #[derive(Clone)]
 pub struct RateLimit {
   backend: LemmyBackend,
+  inbox: InboxRateLimit,
+  penalties: PenaltyBox,
 }
 
 impl RateLimit {
   pub fn new(configs: EnumMap<ActionType, BucketConfig>) -> Self {
     Self {
       backend: LemmyBackend::new(configs, true),
+      inbox: InboxRateLimit::default(),
+      penalties: PenaltyBox::default(),
     }
   }
 
+  #[expect(clippy::expect_used)]
+  pub fn set_overrides(&self, overrides: RateLimitOverrides) {
+    *self.backend.overrides.write().expect("write rwlock") = overrides;
+  }
+
+  /// Stores the buckets in the given store instead of memory, so that the limits are shared with
+  /// other Lemmy servers.
+  pub fn with_store(mut self, store: Arc<dyn RateLimitStore>) -> Self {
+    self.backend.store = Some(store);
+    self
+  }
+
   pub fn with_debug_config() -> Self {
     Self::new(enum_map! {
       ActionType::Message => BucketConfig {
@@ -78,6 +118,45 @@ impl RateLimit {
     *self.backend.configs.write().expect("write rwlock") = configs;
   }
 
+  /// Apply the per-minute rate limit of an api key, in addition to the regular rate limits.
+  /// Returns false if the limit is exceeded.
+  pub fn check_api_key(&self, api_key_id: i32, max_requests: u32) -> bool {
+    self.backend.check_api_key(api_key_id, max_requests)
+  }
+
+  /// Apply the per-minute rate limit for activities which a remote instance sends to the inbox.
+  /// Instances which exceed it repeatedly are throttled for a while. Returns false if the activity
+  /// should be rejected.
+  pub fn check_inbox(&self, domain: &str, max_requests: u32) -> bool {
+    self.inbox.check(domain, max_requests)
+  }
+
+  /// Inbox counters of all instances which sent activities since the server was started.
+  pub fn inbox_stats(&self) -> Vec<InboxStats> {
+    self.inbox.stats()
+  }
+
+  /// Penalty for the next request from the given IP, because of previous violations.
+  pub fn check_penalty(&self, ip: Option<&str>) -> Penalty {
+    self.penalties.check(raw_ip_key(ip))
+  }
+
+  /// Adds a strike for the given IP. Repeated violations delay its requests, and eventually block
+  /// the IP for a while.
+  pub fn record_violation(&self, ip: Option<&str>, violation: Violation) {
+    self.penalties.record(raw_ip_key(ip), violation)
+  }
+
+  /// All clients which currently have strikes or are blocked.
+  pub fn penalty_stats(&self) -> Vec<PenaltyStats> {
+    self.penalties.stats()
+  }
+
+  /// Removes the penalties of the given IP, or of all clients. Returns the number of clients.
+  pub fn clear_penalties(&self, ip: Option<&str>) -> usize {
+    self.penalties.clear(ip.map(|ip| raw_ip_key(Some(ip))))
+  }
+
   fn build_rate_limiter(
     &self,
     action_type: ActionType,
@@ -142,8 +221,13 @@ fn new_input(action_type: ActionType) -> impl Fn(&ServiceRequest) -> LemmyInputF
     ready({
       let info = req.connection_info();
       let key = raw_ip_key(info.realip_remote_addr());
+      let user_class = req
+        .extensions()
+        .get::<UserClass>()
+        .copied()
+        .unwrap_or_default();
 
-      Ok(LemmyInput(key, action_type))
+      Ok(LemmyInput(key, action_type, user_class))
     })
   }
 }
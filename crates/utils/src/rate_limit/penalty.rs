@@ -0,0 +1,208 @@
+use crate::rate_limit::input::RateLimitIpAddr;
+use chrono::{DateTime, TimeDelta, Utc};
+use dashmap::DashMap;
+use std::{sync::Arc, time::Duration};
+
+/// Strikes are forgotten if the client had no violation for this long.
+const STRIKE_EXPIRY: TimeDelta = TimeDelta::minutes(10);
+/// Number of strikes which are tolerated before requests get delayed.
+const FREE_STRIKES: u32 = 3;
+/// Delay for the first strike above the free ones, which doubles with each further strike.
+const BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longer delays would make reverse proxies time out, so the client can retry right away.
+const MAX_DELAY: Duration = Duration::from_secs(10);
+/// Clients with this many strikes get all requests rejected for a while.
+const BLOCK_AFTER_STRIKES: u32 = 20;
+const BLOCK_DURATION: TimeDelta = TimeDelta::hours(1);
+
+/// Something a client did which adds a strike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+  RateLimited,
+  AuthFailure,
+}
+
+/// What happens to the next request of a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Penalty {
+  None,
+  /// Wait before handling the request, which slows down scrapers without rejecting anything.
+  Delay(Duration),
+  /// Reject the request.
+  Blocked(DateTime<Utc>),
+}
+
+/// Escalating penalties for clients which repeatedly exceed rate limits or fail to log in. The
+/// rate limits alone only reject requests, which doesn't keep scrapers from retrying right away.
+/// Keyed by IP like the rate limits.
+#[derive(Clone, Default)]
+pub(super) struct PenaltyBox {
+  clients: Arc<DashMap<RateLimitIpAddr, PenaltyState>>,
+}
+
+struct PenaltyState {
+  strikes: u32,
+  last_strike: DateTime<Utc>,
+  blocked_until: Option<DateTime<Utc>>,
+  rate_limited: u64,
+  auth_failures: u64,
+}
+
+impl PenaltyState {
+  fn is_expired(&self, now: DateTime<Utc>) -> bool {
+    now >= self.last_strike + STRIKE_EXPIRY && self.blocked_until.is_none_or(|b| b <= now)
+  }
+}
+
+/// Penalty counters of a single client, since its strikes were last cleared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PenaltyStats {
+  /// IPv6 addresses are grouped by /64 subnet.
+  pub ip: String,
+  pub strikes: u32,
+  pub blocked_until: Option<DateTime<Utc>>,
+  pub rate_limited: u64,
+  pub auth_failures: u64,
+}
+
+impl PenaltyBox {
+  pub(super) fn check(&self, ip: RateLimitIpAddr) -> Penalty {
+    self.check_at(ip, Utc::now())
+  }
+
+  fn check_at(&self, ip: RateLimitIpAddr, now: DateTime<Utc>) -> Penalty {
+    if self
+      .clients
+      .remove_if(&ip, |_, s| s.is_expired(now))
+      .is_some()
+    {
+      return Penalty::None;
+    }
+    let Some(state) = self.clients.get(&ip) else {
+      return Penalty::None;
+    };
+    if let Some(blocked_until) = state.blocked_until.filter(|b| *b > now) {
+      return Penalty::Blocked(blocked_until);
+    }
+    match state.strikes.saturating_sub(FREE_STRIKES) {
+      0 => Penalty::None,
+      n => Penalty::Delay(
+        BASE_DELAY
+          .saturating_mul(1 << (n - 1).min(16))
+          .min(MAX_DELAY),
+      ),
+    }
+  }
+
+  pub(super) fn record(&self, ip: RateLimitIpAddr, violation: Violation) {
+    self.record_at(ip, violation, Utc::now())
+  }
+
+  fn record_at(&self, ip: RateLimitIpAddr, violation: Violation, now: DateTime<Utc>) {
+    let mut state = self.clients.entry(ip).or_insert_with(|| PenaltyState {
+      strikes: 0,
+      last_strike: now,
+      blocked_until: None,
+      rate_limited: 0,
+      auth_failures: 0,
+    });
+    if now >= state.last_strike + STRIKE_EXPIRY {
+      state.strikes = 0;
+    }
+    state.strikes += 1;
+    state.last_strike = now;
+    match violation {
+      Violation::RateLimited => state.rate_limited += 1,
+      Violation::AuthFailure => state.auth_failures += 1,
+    }
+    if state.strikes >= BLOCK_AFTER_STRIKES {
+      state.blocked_until = Some(now + BLOCK_DURATION);
+      state.strikes = 0;
+    }
+  }
+
+  pub(super) fn stats(&self) -> Vec<PenaltyStats> {
+    let now = Utc::now();
+    self.clients.retain(|_, s| !s.is_expired(now));
+    self
+      .clients
+      .iter()
+      .map(|s| PenaltyStats {
+        ip: s.key().to_string(),
+        strikes: s.strikes,
+        blocked_until: s.blocked_until,
+        rate_limited: s.rate_limited,
+        auth_failures: s.auth_failures,
+      })
+      .collect()
+  }
+
+  /// Removes the penalties of a single client, or of all clients. Returns the number of clients.
+  pub(super) fn clear(&self, ip: Option<RateLimitIpAddr>) -> usize {
+    match ip {
+      Some(ip) => usize::from(self.clients.remove(&ip).is_some()),
+      None => {
+        let count = self.clients.len();
+        self.clients.clear();
+        count
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use std::net::Ipv4Addr;
+
+  #[test]
+  fn test_penalty_box() {
+    let penalties = PenaltyBox::default();
+    let ip = RateLimitIpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+    let other_ip = RateLimitIpAddr::V4(Ipv4Addr::new(5, 6, 7, 8));
+    let start = Utc::now();
+
+    for _ in 0..FREE_STRIKES {
+      penalties.record_at(ip, Violation::RateLimited, start);
+    }
+    assert_eq!(Penalty::None, penalties.check_at(ip, start));
+
+    // Delays double with each further strike
+    penalties.record_at(ip, Violation::AuthFailure, start);
+    assert_eq!(Penalty::Delay(BASE_DELAY), penalties.check_at(ip, start));
+    penalties.record_at(ip, Violation::AuthFailure, start);
+    assert_eq!(
+      Penalty::Delay(BASE_DELAY * 2),
+      penalties.check_at(ip, start)
+    );
+    assert_eq!(Penalty::None, penalties.check_at(other_ip, start));
+
+    // Strikes expire after a while without violations
+    let later = start + STRIKE_EXPIRY;
+    assert_eq!(Penalty::None, penalties.check_at(ip, later));
+    assert!(penalties.stats().is_empty());
+
+    // Too many strikes block the client
+    for _ in 0..BLOCK_AFTER_STRIKES {
+      penalties.record_at(ip, Violation::RateLimited, start);
+    }
+    assert_eq!(
+      Penalty::Blocked(start + BLOCK_DURATION),
+      penalties.check_at(ip, later)
+    );
+    assert_eq!(
+      vec![PenaltyStats {
+        ip: "1.2.3.4".to_string(),
+        strikes: 0,
+        blocked_until: Some(start + BLOCK_DURATION),
+        rate_limited: BLOCK_AFTER_STRIKES.into(),
+        auth_failures: 0,
+      }],
+      penalties.stats()
+    );
+
+    assert_eq!(1, penalties.clear(Some(ip)));
+    assert_eq!(Penalty::None, penalties.check_at(ip, later));
+  }
+}
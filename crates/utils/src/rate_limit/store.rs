@@ -0,0 +1,19 @@
+use crate::error::LemmyResult;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use std::time::Duration;
+
+/// Storage for rate limit buckets which is shared between all Lemmy servers, so that running
+/// multiple replicas doesn't multiply the limits. Without it, buckets are kept in memory.
+pub trait RateLimitStore: Send + Sync {
+  /// Counts a request in the bucket with the given key, and returns the number of requests in the
+  /// bucket together with its expiry. Expired buckets start again with a new interval.
+  fn count_request(
+    &self,
+    key: String,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, DateTime<Utc>)>>;
+
+  /// Removes a counted request from the bucket again.
+  fn rollback(&self, key: String) -> BoxFuture<'_, LemmyResult<()>>;
+}
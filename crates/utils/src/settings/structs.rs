@@ -1,4 +1,9 @@
-use super::pictrs_placeholder_url;
+use super::{
+  classifier_placeholder_url,
+  fcm_proxy_placeholder_url,
+  pictrs_placeholder_url,
+  search_placeholder_url,
+};
 use doku::Document;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
@@ -41,6 +46,11 @@ pub struct Settings {
   /// set this option
   #[doku(skip)]
   pub opentelemetry_url: Option<Url>,
+  /// Share of traces which are exported to opentelemetry, between 0 and 1. Traces which started
+  /// on another service keep the sampling decision of that service.
+  #[default(1.0)]
+  #[doku(skip)]
+  pub opentelemetry_sample_ratio: f64,
   pub federation: FederationWorkerConfig,
   // Prometheus configuration.
   #[doku(example = "Some(Default::default())")]
@@ -50,10 +60,36 @@ pub struct Settings {
   /// https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Access-Control-Allow-Origin
   #[doku(example = "lemmy.tld")]
   cors_origin: Vec<String>,
-  /// Print logs in JSON format. You can also disable ANSI colors in logs with env var `NO_COLOR`.
+  /// Print logs in JSON format, for ingestion by Loki or Elasticsearch. Each line includes the
+  /// route, a hash of the user id, the error type and the latency of the request. You can also
+  /// disable ANSI colors in logs with env var `NO_COLOR`.
   pub json_logging: bool,
+  /// Where the rate limit counters are kept. When running multiple Lemmy servers behind a load
+  /// balancer, use `postgres` so that they share the limits instead of each allowing the full
+  /// amount of requests.
+  pub rate_limit_backend: RateLimitBackendType,
   /// Data for loading Lemmy plugins
   pub plugins: Vec<PluginSettings>,
+  /// Webhook for automatic classification of new posts, comments and uploads, for example to
+  /// detect nsfw or spam content with your own models.
+  #[doku(example = "Some(Default::default())")]
+  pub classifier: Option<ClassifierConfig>,
+  /// External search engine for posts, comments and communities. If not set, the full-text
+  /// search of Postgres is used.
+  #[doku(example = "Some(Default::default())")]
+  pub search: Option<SearchConfig>,
+  /// Keys for sending Web Push notifications to browsers. If not set, push notifications are
+  /// disabled.
+  #[doku(example = "Some(Default::default())")]
+  pub web_push: Option<WebPushConfig>,
+  /// Proxy which forwards push notifications to native apps through Firebase Cloud Messaging. If
+  /// not set, apps can only use Web Push or UnifiedPush.
+  #[doku(example = "Some(Default::default())")]
+  pub fcm_proxy: Option<FcmProxyConfig>,
+  /// Allow apps to receive push notifications through UnifiedPush. Lemmy then sends requests to
+  /// servers chosen by the user, so this is disabled by default.
+  #[default(false)]
+  pub unified_push: bool,
 }
 
 impl Settings {
@@ -75,6 +111,39 @@ pub struct PictrsConfig {
 
   /// Set a custom pictrs API key. ( Required for deleting images )
   pub api_key: Option<String>,
+
+  /// Store a permanent copy of proxied avatars, banners and post thumbnails in pictrs, so that
+  /// they are still available when the remote instance is down. Only used with image mode
+  /// `ProxyAllImages`.
+  #[default(true)]
+  pub cache_remote_images: bool,
+
+  /// Deprecated, this is now a site setting. If set, the value is copied into the site settings
+  /// on startup, overwriting changes made through the API. Remove it after upgrading.
+  #[doku(skip)]
+  pub video_max_upload_size_mb: Option<u32>,
+
+  /// Deprecated, this is now a site setting. If set, the value is copied into the site settings
+  /// on startup, overwriting changes made through the API. Remove it after upgrading.
+  #[doku(skip)]
+  pub video_max_duration_seconds: Option<u32>,
+
+  /// Uploaded videos are sent to this webhook as JSON in a POST request, eg to normalize their
+  /// format. It may respond with `{"file": "<alias>", "duration_seconds": 30}`, where `file` is a
+  /// transcoded copy which the webhook uploaded to pictrs, and which replaces the original.
+  #[doku(example = "http://localhost:8000/transcode")]
+  pub video_transcode_webhook: Option<Url>,
+
+  /// Daily delete uploads which aren't used in any post, comment, private message, profile,
+  /// community or site anymore. Admins can list affected uploads with `GET /image/orphaned`
+  /// before enabling this.
+  #[default(false)]
+  pub delete_orphaned_images: bool,
+
+  /// Uploads are only considered orphaned once they are older than this many days, so that users
+  /// have time to finish writing the post or comment which uses them.
+  #[default(7)]
+  pub orphaned_image_grace_days: u32,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
@@ -151,11 +220,130 @@ pub struct PrometheusConfig {
 #[serde(default, deny_unknown_fields)]
 // named federation"worker"config to disambiguate from the activitypub library configuration
 pub struct FederationWorkerConfig {
-  /// Limit to the number of concurrent outgoing federation requests per target instance.
-  /// Set this to a higher value than 1 (e.g. 6) only if you have a huge instance (>10 activities
-  /// per second) and if a receiving instance is not keeping up.
-  #[default(1)]
-  pub concurrent_sends_per_instance: i8,
+  /// Deprecated, this is now a site setting. If set, the value is copied into the site settings
+  /// on startup, overwriting changes made through the API. Remove it after upgrading.
+  #[doku(skip)]
+  pub concurrent_sends_per_instance: Option<i8>,
+  /// Limit to the number of concurrent outgoing federation requests across all instances. Votes
+  /// can only use half of these, so that a large number of votes doesn't delay other activities
+  /// like new posts or removals. Set to 0 for no limit.
+  #[default(100)]
+  pub concurrent_sends_total: u16,
+  /// Number of outbox pages which are fetched when the first local user subscribes to a remote
+  /// community, so that it doesn't look empty. Set to 0 to disable backfilling.
+  #[default(3)]
+  pub backfill_pages: u8,
+  /// Maximum number of top-level comments which are fetched for each backfilled post.
+  #[default(20)]
+  pub backfill_comments_per_post: u16,
+  /// Maximum number of replies which are fetched from the `replies` collection of a newly
+  /// received remote post, so that existing discussions from other platforms are visible. Set to
+  /// 0 to disable.
+  #[default(50)]
+  pub fetch_replies_per_post: u16,
+  /// Remote communities with local subscribers and recently active remote users are fetched
+  /// again if they weren't updated for this many days, to pick up changes like a new avatar
+  /// which weren't federated. Set to 0 to disable.
+  #[default(7)]
+  pub refresh_remote_actors_after_days: u16,
+  /// Maximum number of activities per minute which each remote instance can send to the inbox.
+  /// Instances which exceed it repeatedly are throttled for 15 minutes. Set to 0 to disable.
+  #[default(1200)]
+  pub inbox_rate_limit: u32,
+  /// Instances which are unreachable for this many days are marked as dead, and don't receive
+  /// activities anymore. They are checked regularly, and revived once they are back online.
+  #[default(3)]
+  pub dead_instance_after_days: u16,
+  /// Maximum size of incoming activities in kilobytes.
+  #[default(256)]
+  pub inbox_max_payload_kb: u32,
+  /// Include the reason when sending reports to other instances. If disabled, remote moderators
+  /// and admins only see that the content was reported.
+  #[default(true)]
+  pub federate_report_reasons: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClassifierConfig {
+  /// Address of the classification webhook. Lemmy sends the content as JSON in a POST request,
+  /// and expects labels like `{"nsfw": true, "spam_score": 0.3}` in the response.
+  #[default(classifier_placeholder_url())]
+  #[doku(example = "http://localhost:8000/classify")]
+  pub url: Url,
+  /// Sent as bearer token in the `Authorization` header, if set
+  pub api_key: Option<String>,
+  /// Content with a spam score at or above this value is automatically reported to the mods
+  #[default(0.8)]
+  #[doku(example = "0.8")]
+  pub spam_report_threshold: f32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct SearchConfig {
+  /// Which search engine is running at the url
+  pub backend: SearchBackendType,
+  /// Address of the search engine
+  #[default(search_placeholder_url())]
+  #[doku(example = "http://localhost:7700")]
+  pub url: Url,
+  /// Meilisearch master key or Elasticsearch api key, if required
+  pub api_key: Option<String>,
+  /// Prefix for the names of the search indexes, which allows multiple instances to share one
+  /// search engine
+  #[default("lemmy")]
+  #[doku(example = "lemmy")]
+  pub index_prefix: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebPushConfig {
+  /// VAPID private key, as base64url encoded P-256 key. Can be generated with
+  /// `openssl ecparam -genkey -name prime256v1 -noout | openssl ec -outform DER | tail -c +8 |
+  /// head -c 32 | base64 | tr '/+' '_-' | tr -d '='`.
+  #[doku(example = "string")]
+  pub vapid_private_key: String,
+  /// VAPID public key which belongs to the private key, as base64url encoded uncompressed point.
+  /// Clients need it to subscribe to push notifications.
+  #[doku(example = "string")]
+  pub vapid_public_key: String,
+  /// Contact for the push services in case of problems, as `mailto:` or `https:` url. Defaults
+  /// to the url of the instance.
+  #[doku(example = "mailto:admin@example.com")]
+  pub subject: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct FcmProxyConfig {
+  /// Address of the proxy. Lemmy sends a POST request with the device token and the
+  /// notification as JSON, like `{"token": "...", "title": "...", "body": "...", "link": "..."}`.
+  #[default(fcm_proxy_placeholder_url())]
+  #[doku(example = "http://localhost:8090/push")]
+  pub url: Url,
+  /// Sent as bearer token in the `Authorization` header, if set
+  pub api_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Document)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchBackendType {
+  #[default]
+  Meilisearch,
+  /// Also works with OpenSearch
+  Elasticsearch,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Document)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitBackendType {
+  /// Counters of each server process
+  #[default]
+  Memory,
+  /// Shared counters in the database
+  Postgres,
 }
 
 /// See the extism docs for more details: https://extism.org/docs/concepts/manifest
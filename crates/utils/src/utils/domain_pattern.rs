@@ -0,0 +1,75 @@
+use crate::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::build_and_check_regex,
+};
+use regex::Regex;
+
+/// A pattern for the federation allow- and blocklist, which can match multiple domains.
+#[derive(Clone, Debug)]
+pub enum DomainPattern {
+  /// Written as `*.example.com`, matches `example.com` and all of its subdomains.
+  Wildcard(String),
+  /// Written as `/regex/`, matches domains which the regex matches.
+  Regex(Regex),
+}
+
+impl DomainPattern {
+  pub fn matches(&self, domain: &str) -> bool {
+    let domain = domain.to_lowercase();
+    match self {
+      DomainPattern::Wildcard(base) => {
+        domain == *base
+          || domain
+            .strip_suffix(base.as_str())
+            .is_some_and(|sub| sub.ends_with('.'))
+      }
+      DomainPattern::Regex(regex) => regex.is_match(&domain),
+    }
+  }
+}
+
+/// Parses a comma-delimited list of domain patterns, eg `*.example.com,/^spam\d+\.net$/`.
+pub fn build_domain_patterns(patterns: Option<&str>) -> LemmyResult<Vec<DomainPattern>> {
+  patterns
+    .unwrap_or_default()
+    .split(',')
+    .map(str::trim)
+    .filter(|p| !p.is_empty())
+    .map(|p| {
+      if let Some(regex) = p.strip_prefix('/').and_then(|p| p.strip_suffix('/')) {
+        Ok(DomainPattern::Regex(build_and_check_regex(Some(regex))?))
+      } else if let Some(base) = p.strip_prefix("*.")
+        && !base.is_empty()
+        && !base.contains(['*', '/'])
+      {
+        Ok(DomainPattern::Wildcard(base.to_lowercase()))
+      } else {
+        Err(LemmyErrorType::InvalidDomainPattern.into())
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_domain_patterns() -> LemmyResult<()> {
+    let patterns = build_domain_patterns(Some(r"*.example.com, /^spam\d+\.net$/"))?;
+    let matches = |domain| patterns.iter().any(|p| p.matches(domain));
+    assert!(matches("example.com"));
+    assert!(matches("lemmy.example.com"));
+    assert!(matches("A.B.Example.com"));
+    assert!(!matches("badexample.com"));
+    assert!(matches("spam123.net"));
+    assert!(!matches("spam.net"));
+    assert!(!matches("lemmy.ml"));
+
+    assert!(build_domain_patterns(None)?.is_empty());
+    assert!(build_domain_patterns(Some("example.com")).is_err());
+    assert!(build_domain_patterns(Some("*.")).is_err());
+    assert!(build_domain_patterns(Some("/(/")).is_err());
+    Ok(())
+  }
+}
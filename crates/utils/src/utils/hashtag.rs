@@ -0,0 +1,36 @@
+use itertools::Itertools;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Hashtags must not directly follow a word character, `/` or `&`, to avoid matching url
+/// fragments and html entities.
+#[expect(clippy::expect_used)]
+static HASHTAG_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"(?:^|[^\w/&#])#(?P<name>\w{1,100})").expect("compile regex"));
+
+/// Returns all hashtags in the text, lowercase and without the leading `#`. Hashtags consisting
+/// only of digits are ignored, as they are usually references like `#1`.
+pub fn scrape_text_for_hashtags(text: &str) -> Vec<String> {
+  HASHTAG_REGEX
+    .captures_iter(text)
+    .filter_map(|caps| caps.name("name"))
+    .map(|name| name.as_str().to_lowercase())
+    .filter(|name| !name.chars().all(|c| c.is_ascii_digit()))
+    .unique()
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use crate::utils::hashtag::scrape_text_for_hashtags;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_hashtags_regex() {
+    let text = "#Lemmy is great. #rust #rust, see https://example.com/#anchor, issue #123 and &#39; or \n# Heading\n#Fediverse";
+    assert_eq!(
+      vec!["lemmy", "rust", "fediverse"],
+      scrape_text_for_hashtags(text)
+    );
+  }
+}
@@ -14,7 +14,12 @@ use url::Url;
 use urlencoding::encode;
 
 /// Rewrites all links to remote domains in markdown, so they go through `/api/v4/image_proxy`.
-pub fn markdown_rewrite_image_links(mut src: String) -> (String, Vec<Url>) {
+/// Remote images for which `allow_image` returns false are removed, so that they aren't loaded
+/// from the remote host either.
+pub fn markdown_rewrite_image_links(
+  mut src: String,
+  allow_image: impl Fn(&Url) -> bool,
+) -> (String, Vec<Url>) {
   let links_offsets = find_urls::<Image>(&src);
 
   let mut links = vec![];
@@ -22,10 +27,13 @@ pub fn markdown_rewrite_image_links(mut src: String) -> (String, Vec<Url>) {
   for (start, end) in links_offsets.into_iter().rev() {
     let (url, extra) = markdown_handle_title(&src, start, end);
     match Url::parse(url) {
+      Ok(parsed) if is_remote(&parsed) && !allow_image(&parsed) => {
+        src.replace_range(start..end, "");
+      }
       Ok(parsed) => {
         links.push(parsed.clone());
         // If link points to remote domain, replace with proxied link
-        if parsed.domain() != Some(&SETTINGS.hostname) {
+        if is_remote(&parsed) {
           let mut proxied = format!(
             "{}/api/v4/image/proxy?url={}",
             SETTINGS.get_protocol_and_hostname(),
@@ -49,6 +57,19 @@ pub fn markdown_rewrite_image_links(mut src: String) -> (String, Vec<Url>) {
   (src, links)
 }
 
+/// All images in markdown which point to remote domains.
+pub fn markdown_remote_image_links(src: &str) -> Vec<Url> {
+  find_urls::<Image>(src)
+    .into_iter()
+    .filter_map(|(start, end)| Url::parse(markdown_handle_title(src, start, end).0).ok())
+    .filter(is_remote)
+    .collect()
+}
+
+fn is_remote(url: &Url) -> bool {
+  url.domain() != Some(&SETTINGS.hostname)
+}
+
 pub fn markdown_handle_title(src: &str, start: usize, end: usize) -> (&str, Option<&str>) {
   let content = src.get(start..end).unwrap_or_default();
   // necessary for custom emojis which look like `![name](url "title")`
@@ -201,7 +222,7 @@ mod tests {
     ];
 
     tests.iter().for_each(|&(msg, input, expected)| {
-      let result = markdown_rewrite_image_links(input.to_string());
+      let result = markdown_rewrite_image_links(input.to_string(), |_| true);
 
       assert_eq!(
         result.0, expected,
@@ -210,4 +231,32 @@ mod tests {
       );
     });
   }
+
+  #[test]
+  fn test_markdown_remove_disallowed_images() -> Result<(), url::ParseError> {
+    let input =
+      "![a](http://example.com/a.jpg) ![b](http://other.com/b.jpg) ![c](http://lemmy-alpha/c.jpg)";
+    assert_eq!(
+      vec![
+        Url::parse("http://example.com/a.jpg")?,
+        Url::parse("http://other.com/b.jpg")?
+      ],
+      markdown_remote_image_links(input)
+    );
+
+    let (result, links) =
+      markdown_rewrite_image_links(input.to_string(), |url| url.domain() == Some("example.com"));
+    assert_eq!(
+      "![a](https://lemmy-alpha/api/v4/image/proxy?url=http%3A%2F%2Fexample.com%2Fa.jpg) ![b]() ![c](http://lemmy-alpha/c.jpg)",
+      result
+    );
+    assert_eq!(
+      vec![
+        Url::parse("http://lemmy-alpha/c.jpg")?,
+        Url::parse("http://example.com/a.jpg")?
+      ],
+      links
+    );
+    Ok(())
+  }
 }
@@ -1,3 +1,5 @@
+pub mod domain_pattern;
+pub mod hashtag;
 pub mod markdown;
 pub mod mention;
 pub mod slurs;
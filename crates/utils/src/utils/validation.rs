@@ -19,9 +19,13 @@ static URL_CLEANER: LazyLock<UrlCleaner> =
   LazyLock::new(|| UrlCleaner::from_embedded_rules().expect("compile clearurls"));
 const ALLOWED_POST_URL_SCHEMES: [&str; 3] = ["http", "https", "magnet"];
 
-const BODY_MAX_LENGTH: usize = 10000;
-const POST_BODY_MAX_LENGTH: usize = 50000;
+pub const BODY_MAX_LENGTH: usize = 10000;
+pub const POST_BODY_MAX_LENGTH: usize = 50000;
+pub const POST_TITLE_MAX_LENGTH: usize = 200;
 const BIO_MAX_LENGTH: usize = 1000;
+const CIPHERTEXT_MAX_LENGTH: usize = 50000;
+const ENCRYPTION_METADATA_MAX_LENGTH: usize = 10000;
+const ENCRYPTION_KEY_MAX_LENGTH: usize = 10000;
 const URL_MAX_LENGTH: usize = 2000;
 const ALT_TEXT_MAX_LENGTH: usize = 1500;
 const SITE_NAME_MAX_LENGTH: usize = 20;
@@ -95,8 +99,9 @@ pub fn is_valid_matrix_id(matrix_id: &str) -> LemmyResult<()> {
 
 pub fn is_valid_post_title(title: &str) -> LemmyResult<()> {
   let length = title.trim().chars().count();
-  let check =
-    (3..=200).contains(&length) && !has_newline(title) && has_3_permitted_display_chars(title);
+  let check = (3..=POST_TITLE_MAX_LENGTH).contains(&length)
+    && !has_newline(title)
+    && has_3_permitted_display_chars(title);
   if !check {
     Err(LemmyErrorType::InvalidPostTitle.into())
   } else {
@@ -114,6 +119,40 @@ pub fn is_valid_body_field(body: &str, post: bool) -> LemmyResult<()> {
   Ok(())
 }
 
+/// Encrypted private messages can't have plaintext content, and the metadata is only valid
+/// together with ciphertext.
+pub fn is_valid_encrypted_private_message(
+  content: &str,
+  ciphertext: Option<&str>,
+  encryption_metadata: Option<&str>,
+) -> LemmyResult<()> {
+  let err = LemmyErrorType::InvalidEncryptedPrivateMessage;
+  match ciphertext {
+    Some(ciphertext) => {
+      if !content.is_empty() || ciphertext.is_empty() {
+        return Err(err.into());
+      }
+      max_length_check(ciphertext, CIPHERTEXT_MAX_LENGTH, err.clone())?;
+      max_length_check(
+        encryption_metadata.unwrap_or_default(),
+        ENCRYPTION_METADATA_MAX_LENGTH,
+        err,
+      )
+    }
+    None if encryption_metadata.is_some() => Err(err.into()),
+    None => Ok(()),
+  }
+}
+
+pub fn is_valid_encryption_key(public_key: &str) -> LemmyResult<()> {
+  min_length_check(public_key, 1, LemmyErrorType::InvalidEncryptionKey)?;
+  max_length_check(
+    public_key,
+    ENCRYPTION_KEY_MAX_LENGTH,
+    LemmyErrorType::InvalidEncryptionKey,
+  )
+}
+
 pub fn is_valid_bio_field(bio: &str) -> LemmyResult<()> {
   max_length_check(bio, BIO_MAX_LENGTH, LemmyErrorType::BioLengthOverflow)
 }
@@ -361,6 +400,7 @@ mod tests {
     error::{LemmyErrorType, LemmyResult},
     utils::validation::{
       BIO_MAX_LENGTH,
+      CIPHERTEXT_MAX_LENGTH,
       SITE_NAME_MAX_LENGTH,
       SITE_SUMMARY_MAX_LENGTH,
       URL_MAX_LENGTH,
@@ -372,6 +412,8 @@ mod tests {
       is_valid_actor_name,
       is_valid_bio_field,
       is_valid_display_name,
+      is_valid_encrypted_private_message,
+      is_valid_encryption_key,
       is_valid_matrix_id,
       is_valid_post_title,
       is_valid_url,
@@ -548,6 +590,21 @@ Line3",
     );
   }
 
+  #[test]
+  fn test_valid_encrypted_private_message() {
+    assert!(is_valid_encrypted_private_message("hello", None, None).is_ok());
+    assert!(is_valid_encrypted_private_message("", Some("abc"), Some("keys")).is_ok());
+    assert!(is_valid_encrypted_private_message("hello", Some("abc"), None).is_err());
+    assert!(is_valid_encrypted_private_message("", Some(""), None).is_err());
+    assert!(is_valid_encrypted_private_message("hello", None, Some("keys")).is_err());
+
+    let long = (0..=CIPHERTEXT_MAX_LENGTH).map(|_| 'A').collect::<String>();
+    assert!(is_valid_encrypted_private_message("", Some(&long), None).is_err());
+
+    assert!(is_valid_encryption_key("key").is_ok());
+    assert!(is_valid_encryption_key("").is_err());
+  }
+
   #[test]
   fn test_valid_site_description() {
     assert!(
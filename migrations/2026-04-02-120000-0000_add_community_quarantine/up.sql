@@ -0,0 +1,6 @@
+ALTER TABLE community
+    ADD COLUMN quarantined boolean NOT NULL DEFAULT FALSE;
+
+ALTER TYPE modlog_kind
+    ADD VALUE 'AdminQuarantineCommunity';
+
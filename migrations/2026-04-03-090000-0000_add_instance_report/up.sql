@@ -0,0 +1,25 @@
+-- Allow users to report a whole (usually remote) instance to the local admins.
+CREATE TABLE instance_report (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    instance_id int REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    original_instance_domain text NOT NULL,
+    reason text NOT NULL,
+    resolved bool NOT NULL DEFAULT FALSE,
+    resolver_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz,
+    UNIQUE (instance_id, creator_id)
+);
+
+CREATE INDEX idx_instance_report_published ON instance_report (published_at DESC);
+
+CREATE INDEX idx_instance_report_creator ON instance_report (creator_id);
+
+CREATE INDEX idx_instance_report_resolver ON instance_report (resolver_id);
+
+ALTER TABLE report_combined
+    ADD COLUMN instance_report_id int UNIQUE REFERENCES instance_report ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT report_combined_check,
+    ADD CHECK (num_nonnulls (post_report_id, comment_report_id, private_message_report_id, community_report_id, instance_report_id) = 1);
+
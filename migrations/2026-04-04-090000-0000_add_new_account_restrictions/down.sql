@@ -0,0 +1,8 @@
+ALTER TABLE local_site
+    DROP COLUMN new_account_min_age_days,
+    DROP COLUMN new_account_min_karma,
+    DROP COLUMN new_account_max_posts_per_day,
+    DROP COLUMN new_account_link_posts_disabled,
+    DROP COLUMN new_account_image_upload_disabled,
+    DROP COLUMN new_account_community_creation_disabled;
+
@@ -0,0 +1,9 @@
+-- Restrictions for new accounts, to make life harder for spammers
+ALTER TABLE local_site
+    ADD COLUMN new_account_min_age_days int NOT NULL DEFAULT 0,
+    ADD COLUMN new_account_min_karma int NOT NULL DEFAULT 0,
+    ADD COLUMN new_account_max_posts_per_day int,
+    ADD COLUMN new_account_link_posts_disabled boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN new_account_image_upload_disabled boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN new_account_community_creation_disabled boolean NOT NULL DEFAULT FALSE;
+
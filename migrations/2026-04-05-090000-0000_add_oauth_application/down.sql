@@ -0,0 +1,10 @@
+ALTER TABLE login_token
+    DROP COLUMN oauth_application_id,
+    DROP COLUMN scope;
+
+DROP TABLE oauth_authorization_code;
+
+DROP TABLE oauth_application;
+
+DROP TYPE token_scope_enum;
+
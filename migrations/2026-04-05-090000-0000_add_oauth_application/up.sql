@@ -0,0 +1,42 @@
+-- Allows third-party apps to request access tokens with limited permissions, via the OAuth 2.0
+-- authorization code flow.
+CREATE TYPE token_scope_enum AS enum (
+    'Read',
+    'Write',
+    'Moderate'
+);
+
+CREATE TABLE oauth_application (
+    id serial PRIMARY KEY,
+    creator_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    client_id text NOT NULL UNIQUE,
+    -- bcrypt hash of the client secret
+    client_secret text NOT NULL,
+    redirect_uri text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_oauth_application_creator ON oauth_application (creator_id);
+
+CREATE TABLE oauth_authorization_code (
+    -- sha256 hash of the code
+    code_hash text PRIMARY KEY,
+    oauth_application_id int NOT NULL REFERENCES oauth_application ON UPDATE CASCADE ON DELETE CASCADE,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    scope token_scope_enum NOT NULL,
+    redirect_uri text NOT NULL,
+    code_challenge text,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- Tokens without scope are regular logins with full access
+ALTER TABLE login_token
+    ADD COLUMN oauth_application_id int REFERENCES oauth_application ON UPDATE CASCADE ON DELETE CASCADE,
+    ADD COLUMN scope token_scope_enum;
+
+CREATE INDEX idx_login_token_oauth_application ON login_token (oauth_application_id)
+WHERE
+    oauth_application_id IS NOT NULL;
+
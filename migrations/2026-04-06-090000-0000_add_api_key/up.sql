@@ -0,0 +1,15 @@
+-- Named keys with limited permissions, which bots can use instead of logging in with a password
+CREATE TABLE api_key (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    -- sha256 hash of the key
+    key_hash text NOT NULL UNIQUE,
+    scope token_scope_enum NOT NULL,
+    -- Maximum number of requests per minute
+    rate_limit int,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_api_key_local_user ON api_key (local_user_id);
+
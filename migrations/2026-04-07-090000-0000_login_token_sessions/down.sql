@@ -0,0 +1,4 @@
+ALTER TABLE login_token
+    DROP COLUMN id,
+    DROP COLUMN last_used_at;
+
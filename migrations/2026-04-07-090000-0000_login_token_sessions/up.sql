@@ -0,0 +1,5 @@
+-- Allows identifying individual sessions, so that users can revoke them
+ALTER TABLE login_token
+    ADD COLUMN id serial UNIQUE NOT NULL,
+    ADD COLUMN last_used_at timestamptz NOT NULL DEFAULT now();
+
@@ -0,0 +1,2 @@
+DROP TABLE webauthn_credential, webauthn_challenge;
+
@@ -0,0 +1,25 @@
+-- Passkeys which can be used for passwordless login, or as second factor
+CREATE TABLE webauthn_credential (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    -- base64url encoded id which is chosen by the authenticator
+    credential_id text NOT NULL UNIQUE,
+    -- SEC1 encoded public key, uncompressed
+    public_key bytea NOT NULL,
+    -- COSE algorithm identifier
+    algorithm int NOT NULL,
+    sign_count bigint NOT NULL DEFAULT 0,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    last_used_at timestamptz
+);
+
+CREATE INDEX idx_webauthn_credential_local_user ON webauthn_credential (local_user_id);
+
+CREATE TABLE webauthn_challenge (
+    challenge text PRIMARY KEY,
+    -- Not set for passwordless login, as the user isn't known yet
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
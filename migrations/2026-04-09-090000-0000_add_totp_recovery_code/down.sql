@@ -0,0 +1,5 @@
+DROP TABLE totp_recovery_code;
+
+ALTER TABLE local_site
+    DROP COLUMN moderators_require_totp;
+
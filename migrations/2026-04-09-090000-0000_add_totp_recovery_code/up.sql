@@ -0,0 +1,12 @@
+-- One-time codes which can be used instead of a totp token, in case the device is lost
+CREATE TABLE totp_recovery_code (
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    -- sha256 of the code
+    code_hash text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (local_user_id, code_hash)
+);
+
+ALTER TABLE local_site
+    ADD COLUMN moderators_require_totp boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,7 @@
+ALTER TABLE local_site
+    DROP COLUMN account_deletion_grace_period_days;
+
+ALTER TABLE local_user
+    DROP COLUMN deletion_scheduled_at,
+    DROP COLUMN deletion_delete_content;
+
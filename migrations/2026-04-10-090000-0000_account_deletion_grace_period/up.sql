@@ -0,0 +1,12 @@
+-- Account deletion only takes effect after a grace period, during which logging in cancels it
+ALTER TABLE local_site
+    ADD COLUMN account_deletion_grace_period_days int NOT NULL DEFAULT 7;
+
+ALTER TABLE local_user
+    ADD COLUMN deletion_scheduled_at timestamptz,
+    ADD COLUMN deletion_delete_content boolean NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_local_user_deletion_scheduled_at ON local_user (deletion_scheduled_at)
+WHERE
+    deletion_scheduled_at IS NOT NULL;
+
@@ -0,0 +1,10 @@
+DROP TABLE local_site_registration_ip_range;
+
+ALTER TABLE local_site
+    DROP COLUMN registrations_per_ip_per_day,
+    DROP COLUMN registration_dnsbl,
+    DROP COLUMN registration_ip_retention_days;
+
+ALTER TABLE local_user
+    DROP COLUMN registration_ip;
+
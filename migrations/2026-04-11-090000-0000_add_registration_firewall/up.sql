@@ -0,0 +1,24 @@
+-- Registration firewall, to make it harder to create spam accounts in bulk
+CREATE TABLE local_site_registration_ip_range (
+    id serial PRIMARY KEY,
+    -- In CIDR notation
+    ip_range text NOT NULL,
+    -- Allowed ranges skip all the other checks, otherwise registrations are blocked
+    allowed boolean NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (ip_range, allowed)
+);
+
+ALTER TABLE local_site
+    ADD COLUMN registrations_per_ip_per_day int,
+    ADD COLUMN registration_dnsbl text,
+    ADD COLUMN registration_ip_retention_days int NOT NULL DEFAULT 30;
+
+-- Kept for admin review of spam waves, and cleared after the retention period
+ALTER TABLE local_user
+    ADD COLUMN registration_ip text;
+
+CREATE INDEX idx_local_user_registration_ip ON local_user (registration_ip)
+WHERE
+    registration_ip IS NOT NULL;
+
@@ -0,0 +1,5 @@
+DROP TABLE pow_challenge;
+
+ALTER TABLE local_site
+    DROP COLUMN pow_challenge_difficulty;
+
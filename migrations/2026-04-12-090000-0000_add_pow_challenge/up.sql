@@ -0,0 +1,11 @@
+-- Proof of work challenges for registration, as an alternative to the captcha plugin. The
+-- difficulty is the number of leading zero bits in the sha256 hash of challenge and nonce.
+ALTER TABLE local_site
+    ADD COLUMN pow_challenge_difficulty int;
+
+CREATE TABLE pow_challenge (
+    challenge text PRIMARY KEY,
+    difficulty int NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,13 @@
+-- Failed logins per account and per ip, for brute force protection. Repeated failures lock
+-- logins for an exponentially growing time.
+CREATE TABLE login_failure (
+    id serial PRIMARY KEY,
+    local_user_id int UNIQUE REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    ip text UNIQUE,
+    failure_count int NOT NULL DEFAULT 1,
+    locked_until timestamptz,
+    last_failure_at timestamptz NOT NULL DEFAULT now(),
+    -- Either the account or the ip
+    CHECK (num_nonnulls (local_user_id, ip) = 1)
+);
+
@@ -0,0 +1,6 @@
+DROP TABLE revoked_refresh_token;
+
+ALTER TABLE login_token
+    DROP COLUMN refresh_token_hash,
+    DROP COLUMN refresh_token_expires_at;
+
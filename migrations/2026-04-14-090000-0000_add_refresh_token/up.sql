@@ -0,0 +1,16 @@
+-- Sessions can use short-lived access tokens, and a refresh token to get new ones. Only a hash
+-- of the refresh token is stored.
+ALTER TABLE login_token
+    ADD COLUMN refresh_token_hash text UNIQUE,
+    ADD COLUMN refresh_token_expires_at timestamptz;
+
+-- Refresh tokens which were already used. If one of them is used again it was probably leaked,
+-- so the session is revoked.
+CREATE TABLE revoked_refresh_token (
+    token_hash text PRIMARY KEY,
+    login_token_id int NOT NULL REFERENCES login_token (id) ON UPDATE CASCADE ON DELETE CASCADE,
+    revoked_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_revoked_refresh_token_login_token ON revoked_refresh_token (login_token_id);
+
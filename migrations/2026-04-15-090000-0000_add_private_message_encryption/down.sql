@@ -0,0 +1,6 @@
+ALTER TABLE private_message
+    DROP COLUMN ciphertext,
+    DROP COLUMN encryption_metadata;
+
+DROP TABLE person_encryption_key;
+
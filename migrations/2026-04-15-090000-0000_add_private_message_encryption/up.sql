@@ -0,0 +1,17 @@
+-- Public keys of user devices, which are used by clients to encrypt private messages. The key
+-- format is chosen by clients, the server only stores and federates them.
+CREATE TABLE person_encryption_key (
+    id serial PRIMARY KEY,
+    person_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    device_name text NOT NULL,
+    public_key text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (person_id, public_key)
+);
+
+-- End-to-end encrypted private messages have empty content, and opaque payloads which can only be
+-- read by the clients of sender and recipient.
+ALTER TABLE private_message
+    ADD COLUMN ciphertext text,
+    ADD COLUMN encryption_metadata text;
+
@@ -0,0 +1,4 @@
+-- Reject unsigned fetches of activitypub objects, like Mastodon's secure mode.
+ALTER TABLE local_site
+    ADD COLUMN authorized_fetch boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,4 @@
+-- Duration in seconds of videos federated from PeerTube.
+ALTER TABLE post
+    ADD COLUMN embed_video_duration int;
+
@@ -0,0 +1,4 @@
+DROP TABLE post_hashtag;
+
+DROP TABLE hashtag;
+
@@ -0,0 +1,15 @@
+-- Hashtags which are used in post titles or bodies, both local and federated.
+CREATE TABLE hashtag (
+    id serial PRIMARY KEY,
+    name varchar(100) NOT NULL UNIQUE,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE post_hashtag (
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    hashtag_id int REFERENCES hashtag ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    PRIMARY KEY (post_id, hashtag_id)
+);
+
+CREATE INDEX idx_post_hashtag_hashtag ON post_hashtag (hashtag_id);
+
@@ -0,0 +1,19 @@
+-- Communities of linked instances which are periodically crawled, so that users can discover
+-- communities which were not fetched yet.
+CREATE TABLE remote_community_directory (
+    ap_id varchar(255) PRIMARY KEY,
+    instance_id int REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name varchar(255) NOT NULL,
+    title varchar(255) NOT NULL,
+    description text,
+    icon text,
+    nsfw boolean NOT NULL DEFAULT FALSE,
+    subscribers int NOT NULL DEFAULT 0,
+    posts int NOT NULL DEFAULT 0,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_remote_community_directory_instance ON remote_community_directory (instance_id);
+
+CREATE INDEX idx_remote_community_directory_subscribers ON remote_community_directory (subscribers DESC);
+
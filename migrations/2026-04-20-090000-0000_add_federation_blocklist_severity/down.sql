@@ -0,0 +1,6 @@
+ALTER TABLE federation_blocklist
+    DROP COLUMN severity,
+    DROP COLUMN reason;
+
+DROP TYPE federation_block_severity_enum;
+
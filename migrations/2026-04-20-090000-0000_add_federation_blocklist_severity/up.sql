@@ -0,0 +1,9 @@
+CREATE TYPE federation_block_severity_enum AS ENUM (
+    'Silence',
+    'Suspend'
+);
+
+ALTER TABLE federation_blocklist
+    ADD COLUMN severity federation_block_severity_enum NOT NULL DEFAULT 'Suspend',
+    ADD COLUMN reason text;
+
@@ -0,0 +1,4 @@
+ALTER TABLE local_site
+    DROP COLUMN blocked_instances_patterns,
+    DROP COLUMN allowed_instances_patterns;
+
@@ -0,0 +1,4 @@
+ALTER TABLE local_site
+    ADD COLUMN blocked_instances_patterns text,
+    ADD COLUMN allowed_instances_patterns text;
+
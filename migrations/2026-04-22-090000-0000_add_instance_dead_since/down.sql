@@ -0,0 +1,4 @@
+ALTER TABLE instance
+    DROP COLUMN dead_since,
+    DROP COLUMN next_probe_at;
+
@@ -0,0 +1,13 @@
+ALTER TABLE instance
+    ADD COLUMN dead_since timestamptz,
+    ADD COLUMN next_probe_at timestamptz;
+
+-- Same condition which was previously checked on every read
+UPDATE
+    instance
+SET
+    dead_since = now(),
+    next_probe_at = now() + interval '1 day'
+WHERE
+    coalesce(updated_at, published_at) < now() - interval '3 days';
+
@@ -0,0 +1,12 @@
+-- Previous names of renamed local communities, so that old links, webfinger handles and remote
+-- follows keep working.
+CREATE TABLE community_alias (
+    name varchar(255) PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX idx_community_alias_name_lower ON community_alias (lower(name));
+
+CREATE INDEX idx_community_alias_community ON community_alias (community_id);
+
@@ -0,0 +1,15 @@
+-- Delivery status of outgoing activities per remote instance. Rows are only written once sending
+-- fails, so that admins can inspect and replay failed deliveries.
+CREATE TABLE sent_activity_delivery (
+    sent_activity_id bigint NOT NULL REFERENCES sent_activity (id) ON UPDATE CASCADE ON DELETE CASCADE,
+    instance_id int NOT NULL REFERENCES instance (id) ON UPDATE CASCADE ON DELETE CASCADE,
+    delivered boolean NOT NULL DEFAULT FALSE,
+    fail_count int NOT NULL DEFAULT 0,
+    last_error text,
+    replay boolean NOT NULL DEFAULT FALSE,
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (sent_activity_id, instance_id)
+);
+
+CREATE INDEX idx_sent_activity_delivery_instance ON sent_activity_delivery (instance_id, delivered);
+
@@ -0,0 +1,3 @@
+ALTER TABLE remote_image
+    DROP COLUMN cached_alias;
+
@@ -0,0 +1,5 @@
+-- Alias of a permanent copy of the remote image in pictrs, so that avatars and thumbnails can
+-- still be served when the remote instance is down.
+ALTER TABLE remote_image
+    ADD COLUMN cached_alias text;
+
@@ -0,0 +1,4 @@
+-- Duration of uploaded videos, reported by the transcoding webhook
+ALTER TABLE image_details
+    ADD COLUMN duration_seconds int;
+
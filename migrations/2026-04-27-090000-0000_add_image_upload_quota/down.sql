@@ -0,0 +1,6 @@
+ALTER TABLE local_image
+    DROP COLUMN size_bytes;
+
+ALTER TABLE local_site
+    DROP COLUMN image_upload_quota_mb;
+
@@ -0,0 +1,8 @@
+-- Size of uploads in bytes, so that storage usage can be shown per user and limited by a quota.
+-- Existing uploads are counted as zero.
+ALTER TABLE local_image
+    ADD COLUMN size_bytes bigint NOT NULL DEFAULT 0;
+
+ALTER TABLE local_site
+    ADD COLUMN image_upload_quota_mb int;
+
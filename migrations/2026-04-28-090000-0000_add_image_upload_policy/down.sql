@@ -0,0 +1,7 @@
+ALTER TABLE local_site
+    DROP COLUMN image_strip_metadata,
+    DROP COLUMN image_convert_to_webp,
+    DROP COLUMN image_allow_animated,
+    DROP COLUMN image_max_width,
+    DROP COLUMN image_max_height;
+
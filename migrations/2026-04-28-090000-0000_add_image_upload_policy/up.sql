@@ -0,0 +1,8 @@
+-- Policies which are applied to uploaded images by the image routes.
+ALTER TABLE local_site
+    ADD COLUMN image_strip_metadata boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN image_convert_to_webp boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN image_allow_animated boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN image_max_width int,
+    ADD COLUMN image_max_height int;
+
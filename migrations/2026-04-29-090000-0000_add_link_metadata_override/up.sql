@@ -0,0 +1,10 @@
+-- Lets admins change how link previews are generated for specific domains and their subdomains.
+CREATE TABLE link_metadata_override (
+    domain text PRIMARY KEY,
+    oembed_endpoint text,
+    disable_metadata boolean NOT NULL DEFAULT FALSE,
+    disable_embed boolean NOT NULL DEFAULT FALSE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
@@ -0,0 +1,5 @@
+ALTER TABLE local_site
+    DROP COLUMN image_proxy_markdown,
+    DROP COLUMN image_proxy_safelist,
+    DROP COLUMN image_proxy_max_size_mb;
+
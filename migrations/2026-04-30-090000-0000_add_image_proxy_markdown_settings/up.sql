@@ -0,0 +1,7 @@
+-- Settings for proxying external images in markdown, so that their hosts can't see the ips of
+-- users.
+ALTER TABLE local_site
+    ADD COLUMN image_proxy_markdown boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN image_proxy_safelist text,
+    ADD COLUMN image_proxy_max_size_mb int;
+
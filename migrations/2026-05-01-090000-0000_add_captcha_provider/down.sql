@@ -0,0 +1,8 @@
+ALTER TABLE local_site
+    DROP COLUMN captcha_provider,
+    DROP COLUMN captcha_site_key,
+    DROP COLUMN captcha_secret_key,
+    DROP COLUMN captcha_instance_url;
+
+DROP TYPE captcha_provider_enum;
+
@@ -0,0 +1,14 @@
+CREATE TYPE captcha_provider_enum AS ENUM (
+    'Internal',
+    'HCaptcha',
+    'MCaptcha',
+    'Turnstile'
+);
+
+ALTER TABLE local_site
+    ADD COLUMN captcha_provider captcha_provider_enum NOT NULL DEFAULT 'Internal',
+    ADD COLUMN captcha_site_key text,
+    ADD COLUMN captcha_secret_key text,
+    -- Only for mCaptcha, which is self-hosted
+    ADD COLUMN captcha_instance_url text;
+
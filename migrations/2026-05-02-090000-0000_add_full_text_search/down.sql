@@ -0,0 +1,4 @@
+DROP INDEX idx_post_search, idx_post_search_title, idx_comment_search;
+
+DROP FUNCTION search_vector (text), search_vector (text, text), search_query (text);
+
@@ -0,0 +1,27 @@
+-- Full-text search uses the language independent `simple` configuration, because content can be
+-- in any language. These functions are used both by the indexes and by queries, so that the
+-- expressions always match.
+CREATE FUNCTION search_vector (text text)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN to_tsvector('simple', coalesce(text, ''));
+
+-- Matches in the title are ranked higher than matches in the body.
+CREATE FUNCTION search_vector (title text, body text)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN setweight(to_tsvector('simple', coalesce(title, '')), 'A') || setweight(to_tsvector('simple', coalesce(body, '')), 'B');
+
+-- Supports quoted phrases, `or` and negation with `-`, and never fails on invalid input.
+CREATE FUNCTION search_query (query text)
+    RETURNS tsquery
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN websearch_to_tsquery('simple', query);
+
+-- Only posts and comments need indexes, the other searched tables are small.
+CREATE INDEX idx_post_search ON post USING gin (search_vector (name, body));
+
+CREATE INDEX idx_post_search_title ON post USING gin (search_vector (name));
+
+CREATE INDEX idx_comment_search ON comment USING gin (search_vector (content));
+
@@ -0,0 +1,24 @@
+DROP TABLE saved_search;
+
+-- reverting an enum value addition is not supported by postgres:
+-- https://www.postgresql.org/docs/current/datatype-enum.html#DATATYPE-ENUM-IMPLEMENTATION-DETAILS
+-- so this workaround is necessary
+DELETE FROM notification
+WHERE kind = 'SavedSearch';
+
+ALTER TYPE notification_type_enum RENAME TO notification_type_enum__;
+
+CREATE TYPE notification_type_enum AS ENUM (
+    'Mention',
+    'Reply',
+    'Subscribed',
+    'PrivateMessage',
+    'ModAction'
+);
+
+ALTER TABLE notification
+    ALTER COLUMN kind TYPE notification_type_enum
+    USING kind::text::notification_type_enum;
+
+DROP TYPE notification_type_enum__;
+
@@ -0,0 +1,22 @@
+-- Search queries which users saved, optionally with a notification for new matching posts
+CREATE TABLE saved_search (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    search_term text NOT NULL,
+    -- Only search in this community
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    notify boolean NOT NULL DEFAULT FALSE,
+    -- Posts published after this time haven't been checked for notifications yet
+    last_checked_at timestamptz NOT NULL DEFAULT now(),
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_saved_search_local_user ON saved_search (local_user_id);
+
+CREATE INDEX idx_saved_search_notify ON saved_search (notify)
+WHERE
+    notify;
+
+ALTER TYPE notification_type_enum
+    ADD VALUE 'SavedSearch';
+
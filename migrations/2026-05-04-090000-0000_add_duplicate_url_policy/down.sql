@@ -0,0 +1,6 @@
+ALTER TABLE community
+    DROP COLUMN duplicate_url_policy,
+    DROP COLUMN duplicate_url_days;
+
+DROP TYPE duplicate_url_policy_enum;
+
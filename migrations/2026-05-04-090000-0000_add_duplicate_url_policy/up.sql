@@ -0,0 +1,10 @@
+CREATE TYPE duplicate_url_policy_enum AS ENUM (
+    'Allow',
+    'Warn',
+    'Block'
+);
+
+ALTER TABLE community
+    ADD COLUMN duplicate_url_policy duplicate_url_policy_enum NOT NULL DEFAULT 'Allow',
+    ADD COLUMN duplicate_url_days int NOT NULL DEFAULT 30;
+
@@ -0,0 +1,4 @@
+DROP INDEX idx_person_lower_name_prefix;
+
+DROP INDEX idx_community_lower_name_prefix;
+
@@ -0,0 +1,6 @@
+-- Prefix matches with `like` can only use an index with text_pattern_ops, unless the database
+-- uses the C collation.
+CREATE INDEX idx_person_lower_name_prefix ON person (lower(name) text_pattern_ops);
+
+CREATE INDEX idx_community_lower_name_prefix ON community (lower(name) text_pattern_ops);
+
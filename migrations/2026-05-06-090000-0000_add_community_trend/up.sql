@@ -0,0 +1,16 @@
+-- Activity of communities over the last day and week, which is periodically recalculated. Only
+-- communities with recent activity have a row.
+CREATE TABLE community_trend (
+    community_id int PRIMARY KEY REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    posts_day int NOT NULL DEFAULT 0,
+    posts_week int NOT NULL DEFAULT 0,
+    comments_day int NOT NULL DEFAULT 0,
+    comments_week int NOT NULL DEFAULT 0,
+    subscribers_day int NOT NULL DEFAULT 0,
+    subscribers_week int NOT NULL DEFAULT 0,
+    score real NOT NULL DEFAULT 0,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_community_trend_score ON community_trend (score DESC);
+
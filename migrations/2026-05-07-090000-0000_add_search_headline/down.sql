@@ -0,0 +1,2 @@
+DROP FUNCTION search_headline (text, text);
+
@@ -0,0 +1,7 @@
+-- Highlights the matches of a search query in the text, for snippets in search results. The
+-- matches are marked as bold in markdown.
+CREATE FUNCTION search_headline (text text, query text)
+    RETURNS text
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN ts_headline('simple', coalesce(text, ''), search_query (query), 'StartSel=**, StopSel=**, MinWords=15, MaxWords=35, MaxFragments=2, FragmentDelimiter=" … "');
+
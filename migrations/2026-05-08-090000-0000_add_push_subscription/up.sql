@@ -0,0 +1,16 @@
+-- Web Push subscriptions of browsers, at most one for each login session. They are removed
+-- together with the session.
+CREATE TABLE push_subscription (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    login_token_id int NOT NULL UNIQUE REFERENCES login_token (id) ON UPDATE CASCADE ON DELETE CASCADE,
+    -- Url of the push service where notifications are sent
+    endpoint text NOT NULL,
+    -- Keys of the browser for encrypting the notifications
+    p256dh_key text NOT NULL,
+    auth_key text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_push_subscription_local_user ON push_subscription (local_user_id);
+
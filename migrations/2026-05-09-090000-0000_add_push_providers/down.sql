@@ -0,0 +1,17 @@
+ALTER TABLE local_user
+    DROP COLUMN push_replies,
+    DROP COLUMN push_mentions,
+    DROP COLUMN push_private_messages,
+    DROP COLUMN push_reports;
+
+DELETE FROM push_subscription
+WHERE provider != 'WebPush';
+
+ALTER TABLE push_subscription
+    DROP COLUMN provider,
+    DROP COLUMN device_name,
+    ALTER COLUMN p256dh_key SET NOT NULL,
+    ALTER COLUMN auth_key SET NOT NULL;
+
+DROP TYPE push_provider_enum;
+
@@ -0,0 +1,20 @@
+CREATE TYPE push_provider_enum AS ENUM (
+    'WebPush',
+    'UnifiedPush',
+    'Fcm'
+);
+
+-- Native apps receive notifications through UnifiedPush or an FCM proxy, which don't use the
+-- browser keys.
+ALTER TABLE push_subscription
+    ADD COLUMN provider push_provider_enum NOT NULL DEFAULT 'WebPush',
+    ADD COLUMN device_name text,
+    ALTER COLUMN p256dh_key DROP NOT NULL,
+    ALTER COLUMN auth_key DROP NOT NULL;
+
+ALTER TABLE local_user
+    ADD COLUMN push_replies boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN push_mentions boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN push_private_messages boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN push_reports boolean NOT NULL DEFAULT TRUE;
+
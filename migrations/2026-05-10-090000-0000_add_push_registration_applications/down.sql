@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    DROP COLUMN push_registration_applications;
+
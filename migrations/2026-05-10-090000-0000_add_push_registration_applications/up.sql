@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    ADD COLUMN push_registration_applications boolean NOT NULL DEFAULT TRUE;
+
@@ -0,0 +1,8 @@
+ALTER TABLE person_actions
+    DROP COLUMN muted_at;
+
+ALTER TABLE local_user
+    DROP COLUMN quiet_hours_enabled,
+    DROP COLUMN quiet_hours_start,
+    DROP COLUMN quiet_hours_end;
+
@@ -0,0 +1,10 @@
+-- Muted persons can still be seen, but don't cause any notifications
+ALTER TABLE person_actions
+    ADD COLUMN muted_at timestamptz;
+
+-- Quiet hours are stored as minutes after midnight UTC, and may wrap around midnight
+ALTER TABLE local_user
+    ADD COLUMN quiet_hours_enabled boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN quiet_hours_start integer NOT NULL DEFAULT 1320 CHECK (quiet_hours_start BETWEEN 0 AND 1439),
+    ADD COLUMN quiet_hours_end integer NOT NULL DEFAULT 420 CHECK (quiet_hours_end BETWEEN 0 AND 1439);
+
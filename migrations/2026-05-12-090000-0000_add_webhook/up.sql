@@ -0,0 +1,45 @@
+CREATE TYPE webhook_event_enum AS ENUM (
+    'PostCreated',
+    'PersonCreated',
+    'ReportCreated',
+    'CommunityCreated'
+);
+
+-- Urls which are called by the instance when one of the selected events happens
+CREATE TABLE webhook (
+    id serial PRIMARY KEY,
+    url text NOT NULL,
+    -- Used to sign the payloads, so that the receiver can verify them
+    secret text NOT NULL,
+    post_created boolean NOT NULL DEFAULT FALSE,
+    person_created boolean NOT NULL DEFAULT FALSE,
+    report_created boolean NOT NULL DEFAULT FALSE,
+    community_created boolean NOT NULL DEFAULT FALSE,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+-- Each event which is sent to a webhook. Failed deliveries are retried until next_attempt_at is
+-- cleared.
+CREATE TABLE webhook_delivery (
+    id serial PRIMARY KEY,
+    webhook_id int NOT NULL REFERENCES webhook ON UPDATE CASCADE ON DELETE CASCADE,
+    event webhook_event_enum NOT NULL,
+    -- The signed request body, which stays the same for retries
+    payload text NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    -- Response of the last attempt
+    status_code int,
+    error text,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    delivered_at timestamptz,
+    next_attempt_at timestamptz DEFAULT now()
+);
+
+CREATE INDEX idx_webhook_delivery_webhook ON webhook_delivery (webhook_id);
+
+CREATE INDEX idx_webhook_delivery_next_attempt ON webhook_delivery (next_attempt_at)
+WHERE
+    next_attempt_at IS NOT NULL;
+
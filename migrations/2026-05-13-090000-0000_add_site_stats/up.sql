@@ -0,0 +1,15 @@
+-- Daily statistics of the instance for the admin dashboard, which are periodically recalculated
+-- for the current and the previous day.
+CREATE TABLE site_stats (
+    day date PRIMARY KEY,
+    signups int NOT NULL DEFAULT 0,
+    posts int NOT NULL DEFAULT 0,
+    comments int NOT NULL DEFAULT 0,
+    active_users int NOT NULL DEFAULT 0,
+    activities_received int NOT NULL DEFAULT 0,
+    activities_sent int NOT NULL DEFAULT 0,
+    reports int NOT NULL DEFAULT 0,
+    storage_bytes bigint NOT NULL DEFAULT 0,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,5 @@
+ALTER TABLE local_site
+    DROP COLUMN federation_concurrent_sends_per_instance,
+    DROP COLUMN video_max_upload_size_mb,
+    DROP COLUMN video_max_duration_seconds;
+
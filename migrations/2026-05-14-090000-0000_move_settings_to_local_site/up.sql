@@ -0,0 +1,7 @@
+-- Settings which were previously only in the config file, so that admins can change them through
+-- the api without restarting Lemmy.
+ALTER TABLE local_site
+    ADD COLUMN federation_concurrent_sends_per_instance int NOT NULL DEFAULT 1,
+    ADD COLUMN video_max_upload_size_mb int NOT NULL DEFAULT 50,
+    ADD COLUMN video_max_duration_seconds int NOT NULL DEFAULT 300;
+
@@ -0,0 +1,2 @@
+DROP TABLE local_site_previous_domain;
+
@@ -0,0 +1,7 @@
+-- Domains which the local instance used before moving to its current domain. Requests to them are
+-- redirected.
+CREATE TABLE local_site_previous_domain (
+    domain text PRIMARY KEY,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,10 @@
+-- Rate limit counters which are shared by all Lemmy servers using this database. The table is
+-- unlogged, as losing it in a crash only resets the limits.
+CREATE UNLOGGED TABLE rate_limit_bucket (
+    key text PRIMARY KEY,
+    count bigint NOT NULL,
+    expires_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_rate_limit_bucket_expires_at ON rate_limit_bucket (expires_at);
+
@@ -0,0 +1,6 @@
+DROP TABLE local_site_rate_limit_override;
+
+DROP TYPE rate_limit_user_class_enum;
+
+DROP TYPE rate_limit_action_enum;
+
@@ -0,0 +1,30 @@
+CREATE TYPE rate_limit_user_class_enum AS ENUM (
+    'Anonymous',
+    'NewAccount',
+    'Established',
+    'Bot',
+    'Admin'
+);
+
+CREATE TYPE rate_limit_action_enum AS ENUM (
+    'Message',
+    'Register',
+    'Post',
+    'Image',
+    'Comment',
+    'Search',
+    'ImportUserSettings'
+);
+
+-- Rate limits for a class of users, which replace the limit of local_site_rate_limit for the
+-- action. Without overrides, all users have the same limits.
+CREATE TABLE local_site_rate_limit_override (
+    user_class rate_limit_user_class_enum NOT NULL,
+    action rate_limit_action_enum NOT NULL,
+    max_requests int NOT NULL,
+    interval_seconds int NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz,
+    PRIMARY KEY (user_class, action)
+);
+
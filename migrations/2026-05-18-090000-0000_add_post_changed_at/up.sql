@@ -0,0 +1,13 @@
+-- Last change of the content or state of a post, for clients which sync posts incrementally.
+-- Unlike updated_at, this also changes when a post is deleted, removed, locked or featured. It is
+-- set by a trigger.
+ALTER TABLE post
+    ADD COLUMN changed_at timestamptz NOT NULL DEFAULT now();
+
+UPDATE
+    post
+SET
+    changed_at = coalesce(updated_at, published_at);
+
+CREATE INDEX idx_post_changed_at ON post (changed_at, id);
+
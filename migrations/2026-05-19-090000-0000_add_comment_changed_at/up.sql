@@ -0,0 +1,12 @@
+-- Last change of the content or state of a comment, for clients which refresh threads
+-- incrementally. Like post.changed_at, it is set by a trigger and ignores votes.
+ALTER TABLE comment
+    ADD COLUMN changed_at timestamptz NOT NULL DEFAULT now();
+
+UPDATE
+    comment
+SET
+    changed_at = coalesce(updated_at, published_at);
+
+CREATE INDEX idx_comment_post_changed_at ON comment (post_id, changed_at, id);
+
@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    ADD COLUMN hide_blocked_person_threads boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,5 @@
+DROP TABLE community_pseudonym;
+
+ALTER TABLE community
+    DROP COLUMN pseudonymous_posting;
+
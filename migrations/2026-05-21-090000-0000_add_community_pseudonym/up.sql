@@ -0,0 +1,13 @@
+ALTER TABLE community
+    ADD COLUMN pseudonymous_posting boolean NOT NULL DEFAULT FALSE;
+
+-- Pseudonyms are local persons without a local user, under which a user can post in a single
+-- community. The mapping to the real person is only visible to admins.
+CREATE TABLE community_pseudonym (
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    person_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    pseudonym_id int NOT NULL UNIQUE REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (community_id, person_id)
+);
+
@@ -0,0 +1,14 @@
+-- Templates which mods offer for new posts in their community, like a bug report format. They
+-- are only used by clients of this instance, so they aren't federated.
+CREATE TABLE community_post_template (
+    id serial PRIMARY KEY,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    title text,
+    body text,
+    enforce_headings boolean NOT NULL DEFAULT FALSE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz,
+    UNIQUE (community_id, name)
+);
+
@@ -0,0 +1,13 @@
+-- Times at which a post is featured and unfeatured in its community by the scheduled tasks. A
+-- null feature_at means that the post was already featured.
+CREATE TABLE post_feature_schedule (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    scheduled_by int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    feature_at timestamptz,
+    unfeature_at timestamptz,
+    rotation_order int,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_post_feature_schedule_feature_at ON post_feature_schedule (feature_at);
+
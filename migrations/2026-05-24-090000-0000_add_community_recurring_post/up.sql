@@ -0,0 +1,18 @@
+-- Posts which are created regularly in a community by the scheduled tasks, like a daily
+-- discussion thread.
+CREATE TABLE community_recurring_post (
+    id serial PRIMARY KEY,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    title text NOT NULL,
+    body text,
+    interval_days int NOT NULL,
+    next_post_at timestamptz NOT NULL,
+    feature boolean NOT NULL DEFAULT FALSE,
+    last_post_id int REFERENCES post ON UPDATE CASCADE ON DELETE SET NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_community_recurring_post_next_post_at ON community_recurring_post (next_post_at);
+
@@ -0,0 +1,4 @@
+ALTER TABLE community
+    DROP COLUMN default_post_sort_type,
+    DROP COLUMN default_comment_sort_type;
+
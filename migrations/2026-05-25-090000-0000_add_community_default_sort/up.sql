@@ -0,0 +1,4 @@
+ALTER TABLE community
+    ADD COLUMN default_post_sort_type post_sort_type_enum,
+    ADD COLUMN default_comment_sort_type comment_sort_type_enum;
+
@@ -0,0 +1,20 @@
+-- Moves of remote instances to a new domain, which are only applied after an admin approved them.
+CREATE TABLE instance_move (
+    id serial PRIMARY KEY,
+    instance_id int NOT NULL REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE,
+    old_domain text NOT NULL,
+    new_domain text NOT NULL,
+    old_origin text NOT NULL,
+    new_origin text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    approved boolean,
+    resolver_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    resolved_at timestamptz
+);
+
+CREATE INDEX idx_instance_move_instance_published ON instance_move (instance_id, published_at);
+
+CREATE INDEX idx_instance_move_pending ON instance_move (published_at)
+WHERE
+    approved IS NULL;
+